@@ -137,6 +137,20 @@ fn preprocess_assets() {
         include_str!("src/assets/player.js"),
         "FAIRCAMP_PLAYER_JS"
     );
+
+    preprocess_js(
+        "player_legacy.js",
+        None,
+        include_str!("src/assets/player_legacy.js"),
+        "FAIRCAMP_PLAYER_LEGACY_JS"
+    );
+
+    preprocess_js(
+        "search.js",
+        None,
+        include_str!("src/assets/search.js"),
+        "FAIRCAMP_SEARCH_JS"
+    );
 }
 
 fn preprocess_css(
@@ -0,0 +1,161 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Through the catalog's `ai_scraping` option, the site signals whether its
+//! pages, audio and images may be collected for training AI models. This is
+//! communicated in all ways that are currently in use: noai/noimageai meta
+//! tags on all pages, an ai.txt file, and sections for known AI crawlers in
+//! robots.txt. None of these are enforceable, they only express a
+//! preference that well-behaved crawlers honor.
+
+use std::fs;
+
+use crate::{Build, WarningCategory};
+
+const AI_TXT_FILENAME: &str = "ai.txt";
+const ROBOTS_TXT_FILENAME: &str = "robots.txt";
+
+/// User agents of crawlers that (exclusively) gather data for AI models,
+/// sorted alphabetically. General purpose crawlers (e.g. for search
+/// engines) are deliberately not included.
+const AI_CRAWLERS: &[&str] = &[
+    "anthropic-ai",
+    "Applebot-Extended",
+    "Bytespider",
+    "CCBot",
+    "ChatGPT-User",
+    "Claude-Web",
+    "ClaudeBot",
+    "cohere-ai",
+    "Diffbot",
+    "FacebookBot",
+    "Google-Extended",
+    "GPTBot",
+    "ImagesiftBot",
+    "meta-externalagent",
+    "Omgilibot",
+    "PerplexityBot",
+    "Timpibot",
+    "YouBot"
+];
+
+/// Audio files and the download archives (which contain audio and images)
+const AUDIO_EXTENSIONS: &[&str] = &["aac", "aif", "aifc", "aiff", "flac", "m4a", "mp3", "ogg", "opus", "wav", "zip"];
+const IMAGE_EXTENSIONS: &[&str] = &["avif", "gif", "jpeg", "jpg", "png", "svg", "webp"];
+const PAGE_EXTENSIONS: &[&str] = &["gmi", "html", "txt"];
+
+/// Whether each kind of content may be scraped (true) or not (false)
+#[derive(Clone, Copy, Debug)]
+pub struct AiScraping {
+    pub audio: bool,
+    pub images: bool,
+    pub pages: bool
+}
+
+impl AiScraping {
+    pub const ALLOWED: AiScraping = AiScraping { audio: true, images: true, pages: true };
+    pub const DISALLOWED: AiScraping = AiScraping { audio: false, images: false, pages: false };
+
+    /// See https://site.spawning.ai/spawning-ai-txt - file types that may
+    /// not be used are disallowed by extension. The site_path is the path
+    /// under which the site lives on its domain (e.g. "/" or "/music/").
+    fn ai_txt(&self, site_path: &str) -> String {
+        let mut ai_txt = String::from("# Signals which content of this site may not be used for training AI models\n\nUser-Agent: *\n");
+
+        if self.is_all_disallowed() {
+            ai_txt.push_str(&format!("Disallow: {site_path}\n"));
+        } else {
+            for extension in self.disallowed_extensions() {
+                ai_txt.push_str(&format!("Disallow: {site_path}*.{extension}\n"));
+            }
+            ai_txt.push_str(&format!("Allow: {site_path}\n"));
+        }
+
+        ai_txt
+    }
+
+    fn disallowed_extensions(&self) -> Vec<&'static str> {
+        let mut extensions = Vec::new();
+
+        if !self.audio { extensions.extend(AUDIO_EXTENSIONS); }
+        if !self.images { extensions.extend(IMAGE_EXTENSIONS); }
+        if !self.pages { extensions.extend(PAGE_EXTENSIONS); }
+
+        extensions
+    }
+
+    fn is_all_allowed(&self) -> bool {
+        self.audio && self.images && self.pages
+    }
+
+    fn is_all_disallowed(&self) -> bool {
+        !self.audio && !self.images && !self.pages
+    }
+
+    /// Returns the noai/noimageai robots meta tag for the head of all pages,
+    /// if it applies.
+    pub fn meta_tag(&self) -> Option<String> {
+        let mut directives = Vec::new();
+
+        if !self.pages { directives.push("noai"); }
+        if !self.images { directives.push("noimageai"); }
+
+        if directives.is_empty() {
+            None
+        } else {
+            Some(format!(r#"<meta name="robots" content="{}">"#, directives.join(", ")))
+        }
+    }
+
+    /// A single group that applies to all known AI crawlers. As only these
+    /// are addressed, all other crawlers remain unrestricted. Paths in
+    /// robots.txt are relative to the root of the domain, so they are all
+    /// prefixed with the site_path (e.g. "/" or "/music/").
+    fn robots_txt(&self, site_path: &str) -> String {
+        let mut robots_txt = String::from("# Known crawlers that gather training data for AI models\n");
+
+        for user_agent in AI_CRAWLERS {
+            robots_txt.push_str(&format!("User-agent: {user_agent}\n"));
+        }
+
+        if self.is_all_disallowed() {
+            robots_txt.push_str(&format!("Disallow: {site_path}\n"));
+        } else {
+            for extension in self.disallowed_extensions() {
+                robots_txt.push_str(&format!("Disallow: {site_path}*.{extension}$\n"));
+            }
+
+            // Clean urls end in a slash rather than .html
+            if !self.pages {
+                robots_txt.push_str(&format!("Disallow: {site_path}*/$\nDisallow: {site_path}$\n"));
+            }
+        }
+
+        robots_txt
+    }
+
+    /// Writes ai.txt and robots.txt to the root of the build directory,
+    /// unless everything is allowed (in which case there is nothing to
+    /// signal).
+    pub fn write(&self, build: &mut Build) {
+        if self.is_all_allowed() {
+            return;
+        }
+
+        let site_path = match &build.base_url {
+            Some(base_url) => base_url.path(),
+            None => String::from("/")
+        };
+
+        if site_path != "/" {
+            let warning = format!("Crawlers only look for {ROBOTS_TXT_FILENAME} and {AI_TXT_FILENAME} at the root of a domain, but the base_url of the site points to the subdirectory {site_path} - for the ai_scraping option to take effect, the generated files (or their content) need to be placed at the root of the domain.");
+            build.warning(WarningCategory::NotAtDomainRoot, &warning);
+        }
+
+        fs::write(build.build_dir.join(AI_TXT_FILENAME), self.ai_txt(&site_path)).unwrap();
+        build.reserve_filename(AI_TXT_FILENAME);
+
+        fs::write(build.build_dir.join(ROBOTS_TXT_FILENAME), self.robots_txt(&site_path)).unwrap();
+        build.reserve_filename(ROBOTS_TXT_FILENAME);
+    }
+}
@@ -0,0 +1,32 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::util::parse_timecode;
+
+/// A short text that is attached to a point in time of a track (e.g.
+/// "Guitar solo" or "Sample credit: ..."), configured through the track
+/// manifest's `annotations` option and shown as a marker on the waveform.
+#[derive(Clone, Debug)]
+pub struct Annotation {
+    pub seconds: f32,
+    pub text: String
+}
+
+impl Annotation {
+    /// Parses an annotation given as a timecode followed by the text,
+    /// e.g. "1:30 Guitar solo" (see [parse_timecode] for the timecode).
+    pub fn parse(value: &str) -> Option<Annotation> {
+        let (timecode, text) = value.trim().split_once(char::is_whitespace)?;
+        let seconds = parse_timecode(timecode)?;
+        let text = text.trim();
+
+        if text.is_empty() {
+            return None;
+        }
+
+        Some(Annotation {
+            seconds,
+            text: text.to_string()
+        })
+    }
+}
@@ -2,11 +2,13 @@
 // SPDX-FileCopyrightText: 2025 Sandro Santilli
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::net::IpAddr;
 use std::path::PathBuf;
 
-#[derive(Debug, Parser)]
+use crate::TranscodeProcesses;
+
+#[derive(Clone, Debug, Parser)]
 #[clap(version = concat!(env!("FAIRCAMP_VERSION_DETAILED"), " (", env!("FAIRCAMP_REVISION"), ") (", env!("FAIRCAMP_FEATURES"), ")"))]
 pub struct Args {
     /// Reports cached assets that currently appear obsolete and their consumed disk space (no build is performed)
@@ -17,10 +19,26 @@ pub struct Args {
     #[clap(long = "build-dir")]
     pub build_dir: Option<PathBuf>,
     
+    /// Writes a summary of the build (warnings, releases that are new or changed since the last build, sizes of the written assets, assets reused from the cache, build time) to .faircamp_build_report.html in the catalog directory (not deployed)
+    #[clap(long = "build-report")]
+    pub build_report: bool,
+
     /// Override cache directory (default is .faircamp_cache/ inside the catalog directory)
     #[clap(long = "cache-dir")]
     pub cache_dir: Option<PathBuf>,
     
+    /// Checks all external links found in the manifests (links, payment info, external download access) and reports those that appear to be dead as warnings (requires curl). Links found alive are not checked again for a week
+    #[clap(long = "check-links")]
+    pub check_links: bool,
+
+    /// Checks the synopsis and more texts in all manifests for common issues (text apparently not in the catalog language, double spaces, unbalanced markdown emphasis, overly long synopsis lines) and reports them as warnings
+    #[clap(long = "check-texts")]
+    pub check_texts: bool,
+
+    /// Developer tools and the control server, these are performed instead of a build
+    #[clap(subcommand)]
+    pub command: Option<ArgsCommand>,
+
     /// Override catalog directory (default is the current working directory)
     #[clap(long = "catalog-dir")]
     pub catalog_dir: Option<PathBuf>,
@@ -33,20 +51,38 @@ pub struct Args {
     #[clap(long = "debug-translations")]
     pub debug_translations: bool,
 
-    /// Deploys to the configured server via rsync after the build is finished. Specify the destination with --deploy-destination. (The full command is: `rsync -avz --delete [your_build_dir/] [deploy_destination]`)
+    /// Deploys to the configured server via rsync (or sftp, for sftp:// destinations) after the build is finished. Specify the destination with --deploy-destination or the catalog's deploy_destination option. (The full rsync command is: `rsync -avz --delete [your_build_dir/] [deploy_destination]`)
     #[clap(long = "deploy", short = 'd')]
     pub deploy: bool,
     
-    /// Configures the deploy destination (passed to rsync as [DEST] argument), e.g. "user@example.com:/var/www/example.com/music/"
+    /// Configures the deploy destination (passed to rsync as [DEST] argument), e.g. "user@example.com:/var/www/example.com/music/", or an sftp url for hosts that only offer sftp, e.g. "sftp://user@example.com/var/www/example.com/music" (only changed files are then uploaded). Takes precedence over the catalog's deploy_destination option
     #[clap(long = "deploy-destination")]
     pub deploy_destination: Option<String>,
 
+    /// Builds only the embeddable players (and the audio and theme assets they need) for all releases and tracks with embedding enabled, plus an index page listing their embed codes - no regular site is generated. Meant for artists who already have a website and only want self-hosted players (requires base_url to be set). Embeds are placed at the same urls as in a regular build
+    #[clap(long = "embeds-only")]
+    pub embeds_only: bool,
+
     /// Excludes all file paths that contain the specified pattern from being
     /// processed. Multiple can be supplied. Matching is done by simple
     /// case-sensitive string comparison - no glob/regex.
     #[clap(long = "exclude")]
     pub exclude_patterns: Vec<String>,
 
+    /// Export the entire catalog (original files, metadata and checksums)
+    /// as a BagIt bag for long-term archival into the given directory (which
+    /// must be empty or not exist yet). No regular build is performed.
+    #[clap(long = "export-bag")]
+    pub export_bag: Option<PathBuf>,
+
+    /// Writes a fulfillment sheet (CSV and Markdown) for each release whose downloads are protected by download codes or a paycurtain to .faircamp_fulfillment/ in the catalog directory (not deployed), listing all download code, unlock and download page urls along with a suggested email text, e.g. for handing out downloads after verifying payments made off-site
+    #[clap(long = "fulfillment-sheets")]
+    pub fulfillment_sheets: bool,
+
+    /// For catalogs kept in a git repository: Only the directories that changed since the last build (according to git) are read from disk again, all others are listed from a record kept in the cache directory, which speeds up scanning huge catalogs. Without a git repository the catalog is read entirely, as usual
+    #[clap(long = "git-scan")]
+    pub git_scan: bool,
+
     /// By default, a build is interrupted when there are errors (e.g. invalid
     /// manifest options). With this option the build continues anyway when
     /// there are errors. Note that some critical errors can not be ignored
@@ -54,12 +90,20 @@ pub struct Args {
     #[clap(long = "ignore-errors")]
     pub ignore_errors: bool,
 
+    /// Also builds releases that are marked as drafts (through 'status: draft' in their manifest), e.g. for sharing them through the preview or a staging deploy
+    #[clap(long = "include-drafts")]
+    pub include_drafts: bool,
+
     /// Pass this so only file paths that contain the specified pattern will
     /// get processed. Multiple can be supplied. Matching is done by simple
     /// case-sensitive string comparison - no glob/regex.
     #[clap(long = "include")]
     pub include_patterns: Vec<String>,
 
+    /// Measures integrated loudness (LUFS) and true peak of all tracks and writes a report to .faircamp_loudness_report.html in the catalog directory (not deployed), highlighting tracks that are much louder or quieter than the rest of their release
+    #[clap(long = "loudness-report")]
+    pub loudness_report: bool,
+
     /// Opens the faircamp manual in your browser, does not do anything else.
     #[clap(long = "manual")]
     pub manual: bool,
@@ -84,14 +128,34 @@ pub struct Args {
     #[clap(long = "preview-port")]
     pub preview_port: Option<u16>,
 
+    /// Makes the build fail (with a nonzero exit code, and before deploying/previewing) if any warnings are encountered, which are then summarized by category. Warning categories silenced through the catalog's warnings option are not considered
+    #[clap(long = "strict")]
+    pub strict: bool,
+
     /// Injects a small widget into the page which allows you to interactively explore different theme color configurations
     #[clap(long = "theming-widget")]
     pub theming_widget: bool,
 
+    /// Records the time spent in each phase of the build (scanning, decoding, transcoding per format, image processing, zipping, rendering, deploying) and per release, prints it at the end of the build and writes it to .faircamp_timings.json in the catalog directory
+    #[clap(long = "timings")]
+    pub timings: bool,
+
+    /// Runs ffmpeg (transcoding audio, encoding visual loops) with the given niceness from 0 to 19 (higher means lower priority), so that builds on a shared server don't starve other services. On Linux this lowers the I/O priority of ffmpeg too. Not supported on Windows
+    #[clap(long = "transcode-nice", value_parser = clap::value_parser!(u8).range(0..=19))]
+    pub transcode_nice: Option<u8>,
+
+    /// How many ffmpeg processes transcode audio at the same time, either for all formats (e.g. "4") or for a single format (e.g. "flac=8", formats are named aac, aiff, alac, flac, mp3-v0, mp3-v5, mp3-v7, ogg, opus-48, opus-96, opus-128 and wav). Can be passed multiple times, the last setting that applies to a format wins. Default is the number of CPUs for all formats
+    #[clap(long = "transcode-processes")]
+    pub transcode_processes: Vec<TranscodeProcesses>,
+
     /// Show more messages during build
     #[clap(long = "verbose", short = 'v')]
     pub verbose: bool,
 
+    /// Keeps running after the build, watching the catalog directory for changes and rebuilding the site whenever something changed (only what changed is processed anew, everything else is reused from the cache). Combine with --preview to keep the preview server running in the meantime, then simply reload the page in your browser after a rebuild
+    #[clap(long = "watch", short = 'w')]
+    pub watch: bool,
+
     /// Wipes the build and cache directory and exits (no build is performed)
     #[clap(long = "wipe-all")]
     pub wipe_all: bool,
@@ -104,3 +168,47 @@ pub struct Args {
     #[clap(long = "wipe-cache")]
     pub wipe_cache: bool
 }
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum ArgsCommand {
+    /// Synthesizes a catalog with sine-wave audio files, covers and manifests
+    /// (including edge cases like unicode names, missing tags and a huge
+    /// file) into the given (empty or not yet existing) directory, for use in
+    /// integration tests and build benchmarks. Requires ffmpeg.
+    GenerateTestCatalog {
+        /// The directory in which the test catalog is created
+        dir: PathBuf,
+
+        /// Number of releases to generate
+        #[clap(default_value_t = 50, long = "releases")]
+        releases: usize,
+
+        /// Maximum number of tracks per release (the number varies between releases)
+        #[clap(default_value_t = 12, long = "tracks")]
+        tracks: usize
+    },
+    /// Serves a local http interface (JSON over REST) through which a GUI
+    /// or web admin can list the catalog, trigger builds, follow their
+    /// progress and query their warnings. Only binds to 127.0.0.1.
+    ServeControl {
+        /// The port to serve on (default is 7070)
+        #[clap(long = "port")]
+        port: Option<u16>
+    },
+    /// Builds the fixture catalog in the given directory and compares all
+    /// generated html pages against the snapshots recorded in the snapshot
+    /// directory, reporting every difference (with the screenshots build
+    /// feature, screenshots taken with a headless chromium are compared
+    /// too). Used to validate refactorings of the render modules.
+    SnapshotTest {
+        /// The directory containing the fixture catalog
+        fixture_dir: PathBuf,
+
+        /// The directory containing the recorded snapshots
+        snapshot_dir: PathBuf,
+
+        /// Record new snapshots (replacing all existing ones) instead of comparing
+        #[clap(long = "update")]
+        update: bool
+    }
+}
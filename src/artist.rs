@@ -9,8 +9,13 @@ use crate::{
     DescribedImage,
     HtmlAndStripped,
     Link,
+    Localizations,
     Permalink,
     ReleaseRc,
+    ShareButton,
+    SiteAsset,
+    SiteMetadata,
+    SiteUrl,
     Theme
 };
 
@@ -29,6 +34,8 @@ pub struct Artist {
     pub featured: bool,
     pub image: Option<DescribedImage>,
     pub links: Vec<Link>,
+    /// Translated more/synopsis texts (from artist.[language].eno overlays)
+    pub localizations: Localizations,
     /// Whether an m3u playlist should be generated and provided for this artist
     pub m3u: bool,
     pub more: Option<HtmlAndStripped>,
@@ -36,8 +43,26 @@ pub struct Artist {
     /// artist page and points to additional long-form content for the artist.
     pub more_label: Option<String>,
     pub name: String,
+    /// Whether search engines are asked not to index the artist page
+    /// (through the artist manifest's search_engines option)
+    pub noindex: bool,
+    /// Files that are written next to the artist page, to be referenced
+    /// from page_metadata
+    pub page_assets: Vec<SiteAsset>,
+    /// Markup that is injected into the <head> of the artist page only
+    pub page_metadata: Option<SiteMetadata>,
     pub permalink: Permalink,
+    /// Whether a timeline of the artist's releases by year is shown on
+    /// the artist page (set through the artist manifest's release_timeline
+    /// option)
+    pub release_timeline: bool,
     pub releases: Vec<ReleaseRc>,
+    /// Services for which share buttons are displayed on the artist page
+    pub share_buttons: Vec<ShareButton>,
+    /// Set through the artist manifest's standalone_site option - in label
+    /// mode, an additional site that presents only this artist is then
+    /// built (with this url as its base_url)
+    pub standalone_site: Option<SiteUrl>,
     pub synopsis: Option<String>,
     pub theme: Theme,
     pub unlisted: bool
@@ -63,12 +88,19 @@ impl Artist {
             featured: false,
             image: None,
             links: Vec::new(),
+            localizations: Localizations::new(),
             m3u: false,
             more: None,
             more_label: None,
             name: name.to_string(),
+            noindex: false,
+            page_assets: Vec::new(),
+            page_metadata: None,
             permalink,
+            release_timeline: false,
             releases: Vec::new(),
+            share_buttons: catalog.share_buttons.clone(),
+            standalone_site: None,
             synopsis: None,
             theme: catalog.theme.clone(),
             unlisted: false
@@ -83,11 +115,18 @@ impl Artist {
         external_page: Option<String>,
         image: Option<DescribedImage>,
         links: Vec<Link>,
+        localizations: Localizations,
         m3u: bool,
         more: Option<HtmlAndStripped>,
         more_label: Option<String>,
         name: &str,
+        noindex: bool,
+        page_assets: Vec<SiteAsset>,
+        page_metadata: Option<SiteMetadata>,
         permalink: Option<Permalink>,
+        release_timeline: bool,
+        share_buttons: Vec<ShareButton>,
+        standalone_site: Option<SiteUrl>,
         synopsis: Option<String>,
         theme: Theme
     ) -> Artist {
@@ -100,12 +139,19 @@ impl Artist {
             featured: false,
             image,
             links,
+            localizations,
             m3u,
             more,
             more_label,
             name: name.to_string(),
+            noindex,
+            page_assets,
+            page_metadata,
             permalink,
+            release_timeline,
             releases: Vec::new(),
+            share_buttons,
+            standalone_site,
             synopsis,
             theme,
             unlisted: false
@@ -137,12 +183,19 @@ impl Artist {
             featured: false,
             image: None,
             links: Vec::new(),
+            localizations: Localizations::new(),
             m3u: false,
             more: None,
             more_label: None,
             name: name.to_string(),
+            noindex: false,
+            page_assets: Vec::new(),
+            page_metadata: None,
             permalink,
+            release_timeline: false,
             releases: Vec::new(),
+            share_buttons: Vec::new(),
+            standalone_site: None,
             synopsis: None,
             theme: catalog.theme.clone(),
             unlisted: false
@@ -25,7 +25,9 @@ pub enum AssetIntent {
 impl Asset {
     pub fn new(build: &Build, filename: String, intent: AssetIntent) -> Asset {
         let metadata = fs::metadata(build.cache_dir.join(&filename)).unwrap();
-        
+
+        build.stats.add_generated(metadata.len());
+
         Asset {
             filename,
             filesize_bytes: metadata.len(),
@@ -0,0 +1,227 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! A listing of all files in the build directory with their size and sha256
+//! checksum (assets-manifest.json), written as the very last step of the
+//! build when the catalog's asset_manifest option is enabled. Mirrors and
+//! IPFS pinners can use it to verify that a copy of the site is complete
+//! and that no file was corrupted along the way. Files that are not meant
+//! to be discoverable (see private_dirs) are left out.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::{Build, Catalog, DownloadAccess, GENERATOR_INFO, TRACK_NUMBERS};
+use crate::bagit::sha256_file;
+use crate::util::{self, json_escape};
+
+pub const ASSET_MANIFEST_FILENAME: &str = "assets-manifest.json";
+
+const ASSET_MANIFEST_SCHEMA: &str = "faircamp-asset-manifest/1";
+
+/// Paths in the manifest are always separated by forward slashes (as
+/// in urls), independent of the platform faircamp runs on.
+fn manifest_path(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+/// Directories (relative to the build directory) whose files are not listed,
+/// as listing them would publish urls that are otherwise not discoverable:
+/// - Unlisted releases (this includes releases published through a secret
+///   link), and unlisted artists
+/// - For releases and tracks whose downloads are protected by download codes
+///   or a paycurtain: The download, unlock and purchase pages (their urls
+///   contain the codes and hashes that grant access), as well as the
+///   downloadable files (archives, extras and track files in a format that
+///   is not also used for streaming)
+/// - The subdirectories of the language sites, which are listed in their
+///   own manifest
+fn private_dirs(build: &Build, catalog: &Catalog) -> Vec<PathBuf> {
+    let mut private_dirs: Vec<PathBuf> = catalog.languages
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+
+    let protected_dirnames = [
+        build.locale.downloads_permalink(),
+        build.locale.purchase_permalink(),
+        build.locale.unlock_permalink(),
+        "extras"
+    ];
+
+    for artist in &catalog.featured_artists {
+        let artist_ref = artist.borrow();
+        if artist_ref.unlisted {
+            private_dirs.push(PathBuf::from(&artist_ref.permalink.slug));
+        }
+    }
+
+    for release in &catalog.releases {
+        let release_ref = release.borrow();
+        let release_dir = PathBuf::from(&release_ref.permalink.slug);
+
+        if release_ref.unlisted {
+            private_dirs.push(release_dir);
+            continue;
+        }
+
+        if is_protected(&release_ref.download_access) {
+            for dirname in protected_dirnames {
+                private_dirs.push(release_dir.join(dirname));
+            }
+
+            for download_format in &release_ref.download_formats {
+                private_dirs.push(release_dir.join(download_format.as_audio_format().asset_dirname()));
+            }
+        }
+
+        for (track, track_number) in release_ref.tracks.iter().zip(TRACK_NUMBERS) {
+            if !is_protected(&track.download_access) {
+                continue;
+            }
+
+            let track_dir = release_dir.join(track_number.to_string());
+
+            for dirname in protected_dirnames {
+                private_dirs.push(track_dir.join(dirname));
+            }
+
+            // Download files in a streaming format end up at the very same
+            // path as the streaming files, which are public anyway.
+            let streaming_formats = track.streaming_quality.formats();
+
+            for download_format in &track.download_formats {
+                let dirname = download_format.as_audio_format().asset_dirname();
+                if !streaming_formats.iter().any(|streaming_format| streaming_format.asset_dirname() == dirname) {
+                    private_dirs.push(track_dir.join(dirname));
+                }
+            }
+        }
+    }
+
+    private_dirs
+}
+
+fn is_protected(download_access: &DownloadAccess) -> bool {
+    matches!(download_access, DownloadAccess::Code { .. } | DownloadAccess::Paycurtain { .. })
+}
+
+/// Must be called after everything else was written to the build directory
+/// (including the user's site assets), as only the files present at that
+/// point are listed.
+pub fn write(build: &mut Build, catalog: &Catalog) {
+    let mut paths = Vec::new();
+    util::collect_files(&build.build_dir, &build.build_dir, &mut paths);
+    paths.sort();
+
+    let private_dirs = private_dirs(build, catalog);
+
+    let mut files = Vec::new();
+    let mut total_size = 0;
+
+    for path in paths {
+        let path_string = manifest_path(&path);
+
+        if path_string == ASSET_MANIFEST_FILENAME ||
+            private_dirs.iter().any(|private_dir| path.starts_with(private_dir)) {
+            continue;
+        }
+
+        match sha256_file(&build.build_dir.join(&path)) {
+            Ok((sha256, size)) => {
+                files.push(format!(
+                    "    {{ \"path\": \"{}\", \"sha256\": \"{sha256}\", \"size\": {size} }}",
+                    json_escape(&path_string)
+                ));
+                total_size += size;
+            }
+            Err(err) => {
+                let error = format!("The file '{path_string}' could not be read for the asset manifest ({err})");
+                build.error(&error);
+            }
+        }
+    }
+
+    let r_files = files.join(",\n");
+
+    let json = format!(
+        "{{\n  \"files\": [\n{r_files}\n  ],\n  \"generator\": \"{}\",\n  \"schema\": \"{ASSET_MANIFEST_SCHEMA}\",\n  \"total_size\": {total_size}\n}}\n",
+        json_escape(GENERATOR_INFO)
+    );
+
+    fs::write(build.build_dir.join(ASSET_MANIFEST_FILENAME), json).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+    use std::process;
+
+    use clap::Parser;
+
+    use crate::{Args, Build, Catalog, DownloadAccess, DownloadFormat, ReleaseRc};
+    use crate::release::tests::test_release;
+    use crate::util;
+
+    use super::{ASSET_MANIFEST_FILENAME, write};
+
+    fn write_file(path: &Path) {
+        util::ensure_dir_all(path.parent().unwrap());
+        fs::write(path, "content").unwrap();
+    }
+
+    #[test]
+    fn protected_and_unlisted_paths_are_left_out() {
+        let mut build = Build::new(&Args::parse_from(["faircamp"]));
+        build.build_dir = env::temp_dir().join(format!("faircamp-asset-manifest-{}", process::id()));
+        util::ensure_empty_dir(&build.build_dir);
+
+        let mut catalog = Catalog::new();
+
+        let mut protected_release = test_release();
+        protected_release.download_access = DownloadAccess::Code {
+            download_codes: vec![String::from("secret-code")],
+            unlock_info: None
+        };
+        protected_release.download_formats = vec![DownloadFormat::Flac];
+        protected_release.permalink.slug = String::from("protected");
+        catalog.releases.push(ReleaseRc::new(protected_release));
+
+        let mut unlisted_release = test_release();
+        unlisted_release.permalink.slug = String::from("unlisted");
+        unlisted_release.unlisted = true;
+        catalog.releases.push(ReleaseRc::new(unlisted_release));
+
+        let protected_dir = build.build_dir.join("protected");
+
+        write_file(&build.build_dir.join("index.html"));
+        write_file(&protected_dir.join("index.html"));
+        write_file(&protected_dir.join(build.locale.downloads_permalink()).join("secret-code").join("index.html"));
+        write_file(&protected_dir.join(build.locale.unlock_permalink()).join("unlock-hash").join("index.html"));
+        write_file(&protected_dir.join("extras").join("extras-hash").join("liner_notes.pdf"));
+        write_file(&protected_dir.join("flac").join("archive-hash").join("Artist - Release.zip"));
+        write_file(&build.build_dir.join("unlisted").join("index.html"));
+
+        write(&mut build, &catalog);
+
+        let manifest = fs::read_to_string(build.build_dir.join(ASSET_MANIFEST_FILENAME)).unwrap();
+
+        let _ = fs::remove_dir_all(&build.build_dir);
+
+        assert!(manifest.contains("\"path\": \"index.html\""));
+        assert!(manifest.contains("\"path\": \"protected/index.html\""));
+        assert!(!manifest.contains("archive-hash"));
+        assert!(!manifest.contains("extras-hash"));
+        assert!(!manifest.contains("secret-code"));
+        assert!(!manifest.contains("unlisted/"));
+        assert!(!manifest.contains("unlock-hash"));
+    }
+}
@@ -8,6 +8,7 @@ const searchField = browser.querySelector('input');
 const statusField = browser.querySelector('[role="status"]');
 
 const indexSuffix = window.location.pathname.endsWith('index.html') ? 'index.html' : '';
+const rootAssetPrefix = browser.dataset.rootAssetPrefix;
 const rootPrefix = browser.dataset.rootPrefix;
 
 function truncateArtistList(artists, othersLink)  {
@@ -69,11 +70,11 @@ for (const release of RELEASES) {
     let imgRelease;
     if (release.cover) {
         imgRelease = document.createElement('img');
-        imgRelease.src = rootPrefix + release.url + release.cover;
+        imgRelease.src = rootAssetPrefix + release.url + release.cover;
     } else {
         imgRelease = document.createElement('img');
         imgRelease.classList.add('procedural');
-        imgRelease.src = rootPrefix + release.url + release.coverProcedural;
+        imgRelease.src = rootAssetPrefix + release.url + release.coverProcedural;
     }
 
     const aText = document.createElement('a');
@@ -105,7 +106,7 @@ for (const release of RELEASES) {
         let imgTrack;
         if (track.cover) {
             imgTrack = document.createElement('img');
-            imgTrack.src = rootPrefix + track.url + track.cover;
+            imgTrack.src = rootAssetPrefix + track.url + track.cover;
         } else {
             imgTrack = imgRelease.cloneNode(true);
         }
@@ -156,7 +157,7 @@ for (const artist of ARTISTS) {
     if (artist.image) {
         imageArtist = document.createElement('img');
         imageArtist.classList.add('crop');
-        imageArtist.src = rootPrefix + artist.url + artist.image;
+        imageArtist.src = rootAssetPrefix + artist.url + artist.image;
     } else {
         imageArtist = document.createElement('span');
         imageArtist.classList.add('placeholder');
@@ -262,3 +263,40 @@ searchField.addEventListener('input', () => {
         statusField.textContent = '';
     }
 });
+
+// Only present if the theme offers the dyslexia-friendly font, the choice is
+// remembered across pages (and restored by an inline script in the body).
+const dyslexiaFontToggle = document.querySelector('footer button.dyslexia_font_toggle');
+if (dyslexiaFontToggle) {
+    const enabled = document.body.classList.contains('dyslexia_font');
+    dyslexiaFontToggle.setAttribute('aria-pressed', enabled ? 'true' : 'false');
+
+    dyslexiaFontToggle.addEventListener('click', () => {
+        const enabled = document.body.classList.toggle('dyslexia_font');
+        dyslexiaFontToggle.setAttribute('aria-pressed', enabled ? 'true' : 'false');
+
+        if (enabled) {
+            localStorage.setItem('faircampDyslexiaFont', 'true');
+        } else {
+            localStorage.removeItem('faircampDyslexiaFont');
+        }
+    });
+}
+
+// Clicks on download links are announced through a faircamp:download-click
+// event on the document, complementing the events dispatched by the player
+// (see player.js) for site operators measuring engagement themselves.
+document.addEventListener('click', event => {
+    const link = event.target.closest('.download_entry a[download]');
+
+    if (link) {
+        const downloadClickEvent = new CustomEvent('faircamp:download-click', {
+            detail: {
+                label: link.textContent.trim(),
+                url: link.href
+            }
+        });
+
+        document.dispatchEvent(downloadClickEvent);
+    }
+});
@@ -118,6 +118,40 @@ function formatTimeWrittenOut(seconds) {
     }
 }
 
+// Each track provides multiple streaming formats (opus as primary, mp3 as
+// fallback) through <source> elements, in order of preference. Browsers pick
+// the first source whose type they think they might be able to play, which
+// is not always the best choice (e.g. a source reported as only "maybe"
+// playable can be chosen over a later one that is "probably" playable, and
+// then fail). We therefore rank the sources by reported capability, keeping
+// our order of preference among equally capable ones, and drop those that
+// can't be played at all (as long as there is at least one playable source).
+function negotiateSources(audio) {
+    const sources = [...audio.querySelectorAll('source')];
+    const rank = source => ({ probably: 2, maybe: 1 })[audio.canPlayType(source.type)] ?? 0;
+    const ranked = sources
+        .map((source, index) => ({ index, rank: rank(source), source }))
+        .sort((a, b) => (b.rank - a.rank) || (a.index - b.index));
+
+    if (ranked[0].rank === 0) return;
+
+    const unchanged = ranked.every(({ index, rank }, position) => index === position && rank > 0);
+    if (unchanged) return;
+
+    for (const { rank, source } of ranked) {
+        if (rank === 0) {
+            source.remove();
+        } else {
+            audio.appendChild(source);
+        }
+    }
+
+    // The browser already selected a source when the <audio> element was
+    // parsed, reordering the <source> elements only has an effect once the
+    // source selection is run again.
+    audio.load();
+}
+
 // Open the docked player and update its various subelements to display the
 // given track. If track.seekTo is set a seek is indicated by advancing both
 // the track's own progress indicator and the docked player progress bar to
@@ -152,22 +186,19 @@ function open(track) {
     track.open = true;
 }
 
-// Parses (and validates) track/time parameters from the current url
-// (e.g. https://example.com/#track=3&time=4m12s) and returns them as an
-// object (e.g. { time: 252, track: [reference to track] }). Track can be
-// specified as n=1 or track=1, time can be specified as t=60 or time=60, but
-// also supports complex specifiers like t=1h, t=1m t=1s, t=1h1m, t=1h1m1s,
-// etc. In case of no known params being present or errors being encountered
-// (wrong syntax for params, out-of-bound track numbers or timecodes, etc.)
-// null is returned. Note that in case of a non-null return value, there is
-// always a reference to a track returned (!), i.e. if the hash specified
-// only #t=60, this is interpreted as "seek to 60 seconds on the first
-// track".
-function parseHashParams() {
-    if (location.hash.length === 0) return null;
-
-    const params = new URLSearchParams(location.hash.substring(1));
-
+// Parses (and validates) track/time parameters from the given
+// URLSearchParams (e.g. obtained from https://example.com/#track=3&time=4m12s
+// or https://example.com/?track=3&t=252) and returns them as an object
+// (e.g. { time: 252, track: [reference to track] }). Track can be specified
+// as n=1 or track=1, time can be specified as t=60 or time=60, but also
+// supports complex specifiers like t=1h, t=1m t=1s, t=1h1m, t=1h1m1s, etc.,
+// as well as clock-style specifiers like t=4:12 or t=1:04:12. In case of no
+// known params being present or errors being encountered (wrong syntax for
+// params, out-of-bound track numbers or timecodes, etc.) null is returned.
+// Note that in case of a non-null return value, there is always a reference
+// to a track returned (!), i.e. if the params specified only t=60, this is
+// interpreted as "seek to 60 seconds on the first track".
+function parseParams(params) {
     const timeParam = params.get('t') ?? params.get('time');
     const trackParam = params.get('n') ?? params.get('track');
 
@@ -190,11 +221,13 @@ function parseHashParams() {
     if (timeParam !== null) {
         // Match all of "1", "1s", "1m" "1h" "1m1s", "1h1m1s", "1h1s", "1h1m", etc.
         const match = timeParam.match(/^(?:([0-9]+)h)?(?:([0-9]+)m)?(?:([0-9]+)s?)?$/);
+        // Match all of "1:01", "01:01", "1:01:01", etc.
+        const clockMatch = timeParam.match(/^(?:([0-9]+):)?([0-9]+):([0-9]{2})$/);
 
-        if (match) {
+        if (match || clockMatch) {
             result.time = 0;
 
-            const [_, h, m, s] = match;
+            const [_, h, m, s] = match ?? clockMatch;
 
             if (h) { result.time += parseInt(h) * 3600; }
             if (m) { result.time += parseInt(m) * 60; }
@@ -211,6 +244,22 @@ function parseHashParams() {
     return result;
 }
 
+// Parses track/time parameters from the hash of the current url (e.g.
+// https://example.com/#track=3&time=4m12s), see parseParams for details.
+function parseHashParams() {
+    if (location.hash.length === 0) return null;
+
+    return parseParams(new URLSearchParams(location.hash.substring(1)));
+}
+
+// Parses track/time parameters from the query string of the current url
+// (e.g. https://example.com/?track=3&t=252), see parseParams for details.
+function parseQueryParams() {
+    if (location.search.length === 0) return null;
+
+    return parseParams(new URLSearchParams(location.search));
+}
+
 function play(track) {
     if (!track.open) {
         open(track);
@@ -823,6 +872,8 @@ for (const container of document.querySelectorAll('.track')) {
         title
     };
 
+    negotiateSources(audio);
+
     // We only unmute tracks right before they play, muting them again at any
     // pause event. We do this because a bug in browsers on apple systems can
     // trigger sporadic, unsolicited playback of tracks in certain conditions
@@ -930,7 +981,7 @@ window.addEventListener('keydown', event => {
 });
 
 // Set active track (and optionally set seekTo)
-const params = parseHashParams();
+const params = parseHashParams() ?? parseQueryParams();
 if (params) {
     setActive(params.track);
 
@@ -6,6 +6,15 @@ const WAVEFORM_HEIGHT = TRACK_HEIGHT_EM - WAVEFORM_PADDING_EM * 2.0;
 const WAVEFORM_WIDTH_PADDING_REM = 5;
 const WAVEFORM_WIDTH_TOLERANCE_REM = 2.5;
 
+// A track that fails to load is retried with increasing delays (1s, 2s, 4s),
+// after which we give up and let the visitor know.
+const PLAYBACK_RETRY_DELAY_MS = 1000;
+const PLAYBACK_RETRY_LIMIT = 3;
+
+// On releases that play continuously (see continuousPlayback below), the
+// next track starts loading this many seconds before the current one ends.
+const PRELOAD_AHEAD_SECONDS = 20;
+
 const loadingIcon = document.querySelector('#loading_icon').content;
 const pauseIcon = document.querySelector('#pause_icon').content;
 const playIcon = document.querySelector('#play_icon').content;
@@ -27,16 +36,36 @@ const listenButtonLabel = document.querySelector('button.listen .label');
 // on it, see the respective parts in the code.
 let activeTrack;
 
+// Shared by volume normalization and crossfading, only created when first
+// needed (an audio context can only be started following a user
+// interaction).
+let audioContext = null;
+
 const tracks = [];
 
+// The order in which tracks are played back one after another. Initially
+// this is the order of the tracks on the page, but on pages with a queue
+// panel visitors can reorder tracks and remove them from the queue. The
+// queue is remembered per page (see persistQueue/restoreQueue).
+let queue = [];
+const QUEUE_STORAGE_KEY = `faircampQueue:${location.pathname}`;
+
 const dockedPlayerContainer = document.querySelector('.docked_player');
 const dockedPlayer = {
     container: dockedPlayerContainer,
+    copyTimecodeLinkButton: dockedPlayerContainer.querySelector('button.copy_timecode_link'),
     currentTime: dockedPlayerContainer.querySelector('.time .current'),
+    error: dockedPlayerContainer.querySelector('.error'),
     nextTrackButton: dockedPlayerContainer.querySelector('button.next_track'),
     number: dockedPlayerContainer.querySelector('.number'),
     playbackButton: dockedPlayerContainer.querySelector('button.playback'),
     progress: dockedPlayerContainer.querySelector('.progress'),
+    queue: {
+        container: dockedPlayerContainer.querySelector('.queue'),
+        list: dockedPlayerContainer.querySelector('.queue ol'),
+        resetButton: dockedPlayerContainer.querySelector('button.reset_queue'),
+        toggleButton: dockedPlayerContainer.querySelector('button.queue_toggle')
+    },
     speedButton: dockedPlayerContainer.querySelector('button.speed'),
     speedMultiplier: dockedPlayerContainer.querySelector('button.speed .multiplier'),
     status: document.querySelector('.docked_player_status'),
@@ -51,11 +80,24 @@ const dockedPlayer = {
         slider: dockedPlayerContainer.querySelector('.volume .slider'),
         sliderInput: dockedPlayerContainer.querySelector('.volume .slider input'),
         sliderSvg: dockedPlayerContainer.querySelector('.volume .slider svg')
-    }
+    },
+    volumeNormalizationButton: dockedPlayerContainer.querySelector('button.volume_normalization')
+};
+
+// Only set on release pages when the crossfade or gapless option is
+// enabled. With a crossfade the end of each track and the beginning of the
+// next one overlap, with gapless playback the next track is merely loaded
+// ahead of time so that it starts right when the previous one ends.
+const continuousPlayback = {
+    crossfade: parseFloat(document.querySelector('.tracks[data-crossfade]')?.dataset.crossfade ?? '0'),
+    enabled: document.querySelector('.tracks[data-crossfade], .tracks[data-gapless]') !== null
 };
 
 let globalUpdatePlayHeadInterval;
 
+// Only present on track pages, for tracks with synced (LRC) lyrics
+const lyricsLines = document.querySelectorAll('.lyrics.synced p[data-time]');
+
 // We internally manage speed as (integer) percent values to avoid having to
 // deal with float rounding issues.
 let speed = 100;
@@ -95,6 +137,19 @@ if (persistedVolume !== null) {
 }
 updateVolume(false);
 
+// Volume normalization applies the gain that was pre-computed for each track
+// at build time through a Web Audio gain node. Audio elements are only routed
+// through the audio context once they are played with normalization enabled
+// (an audio context can only be started following a user interaction), after
+// that the gain is merely reset to 1 when the visitor disables normalization.
+const volumeNormalization = {
+    enabled: localStorage.getItem('faircampVolumeNormalization') !== 'disabled'
+};
+
+if (dockedPlayer.volumeNormalizationButton) {
+    updateVolumeNormalization(false);
+}
+
 // While the underlying data model of the playhead (technically the invisible
 // range input and visible svg representation) change granularly, we only
 // trigger screenreader announcements when it makes sense - e.g. when
@@ -109,6 +164,41 @@ function announcePlayhead(track) {
     }
 }
 
+// Starts playing the next track while the given one fades out over its
+// remaining time (and the next one fades in). The fading out track keeps
+// playing until it ends, but as far as the interface is concerned, the next
+// track is the active one right away.
+function crossfade(track, nextTrack) {
+    const fadeSeconds = Math.max(0, track.duration - track.audio.currentTime) / track.audio.playbackRate;
+
+    routeThroughAudioContext(track);
+    routeThroughAudioContext(nextTrack);
+
+    if (audioContext.state === 'suspended') {
+        audioContext.resume();
+    }
+
+    const now = audioContext.currentTime;
+    track.fadeNode.gain.setValueAtTime(1, now);
+    track.fadeNode.gain.linearRampToValueAtTime(0, now + fadeSeconds);
+    nextTrack.fadeNode.gain.setValueAtTime(0, now);
+    nextTrack.fadeNode.gain.linearRampToValueAtTime(1, now + fadeSeconds);
+
+    // Without solicitedPlayback, the pause event at the end of the fading
+    // out track is ignored (see comments on solicitedPlayback).
+    delete track.solicitedPlayback;
+    track.fadingOut = true;
+    track.open = false;
+
+    clearInterval(globalUpdatePlayHeadInterval);
+    track.container.classList.remove('active', 'playing');
+    track.playbackButtonIcon.replaceChildren(playIcon.cloneNode(true));
+
+    setActive(nextTrack);
+    emitPlayerEvent('trackchange', nextTrack);
+    play(nextTrack);
+}
+
 // Decodes a sequence of peaks that is encoded using a custom base64 alphabet
 // (A-Za-z0-9+/) into a sequence of numbers (0-63)
 function decode(string) {
@@ -132,6 +222,42 @@ function decode(string) {
     return peaks;
 }
 
+// Dispatches one of the documented player events (faircamp:play,
+// faircamp:pause, faircamp:trackchange, faircamp:finish) on the document.
+// Faircamp itself never listens to these, they are there for site operators
+// who want to measure engagement through their own scripts (e.g. injected
+// through site_metadata).
+function emitPlayerEvent(type, track) {
+    const event = new CustomEvent(`faircamp:${type}`, {
+        detail: {
+            currentTime: track.audio.currentTime,
+            duration: track.duration,
+            number: track.number.textContent,
+            src: track.audio.currentSrc,
+            title: track.title.textContent
+        }
+    });
+
+    document.dispatchEvent(event);
+}
+
+// Returns a track that was crossfaded into the next one to its original
+// state, once it has ended (or when its fade out is interrupted).
+function endFadeOut(track) {
+    delete track.fadingOut;
+
+    track.audio.currentTime = 0;
+    track.audio.muted = true;
+    track.fadeNode.gain.cancelScheduledValues(0);
+    track.fadeNode.gain.value = 1;
+
+    if (track.waveform) {
+        track.waveform.svg.querySelector('linearGradient.playback stop:nth-child(1)').setAttribute('offset', 0);
+        track.waveform.svg.querySelector('linearGradient.playback stop:nth-child(2)').setAttribute('offset', 0.0001);
+        track.waveform.input.value = 0;
+    }
+}
+
 function formatTime(seconds) {
     if (seconds < 60) {
         return `0:${Math.floor(seconds).toString().padStart(2, '0')}`;
@@ -158,6 +284,16 @@ function formatTimeWrittenOut(seconds) {
     }
 }
 
+// Formats seconds as a timecode parameter for use in links, e.g. 252 as
+// "4m12s" (see parseParams for the counterpart of this).
+function formatTimecodeParam(seconds) {
+    const h = Math.floor(seconds / 3600);
+    const m = Math.floor((seconds % 3600) / 60);
+    const s = Math.floor(seconds % 60);
+
+    return `${h > 0 ? h + 'h' : ''}${h > 0 || m > 0 ? m + 'm' : ''}${s}s`;
+}
+
 // In most cases, hover capability remains constant during runtime, however
 // during testing in desktop browsers with device simulation and possibly in
 // some edge cases in real usage, hover capability might change at runtime,
@@ -166,6 +302,65 @@ function hoverAvailable() {
     return window.matchMedia('(hover: hover)').matches;
 }
 
+// Moves the track at fromIndex in the queue to toIndex, shifting the tracks
+// in between accordingly.
+function moveInQueue(fromIndex, toIndex) {
+    const [track] = queue.splice(fromIndex, 1);
+    queue.splice(toIndex, 0, track);
+
+    persistQueue();
+    renderQueue();
+}
+
+// Each track provides multiple streaming formats (opus as primary, mp3 as
+// fallback) through <source> elements, in order of preference. Browsers pick
+// the first source whose type they think they might be able to play, which
+// is not always the best choice (e.g. a source reported as only "maybe"
+// playable can be chosen over a later one that is "probably" playable, and
+// then fail). We therefore rank the sources by reported capability, keeping
+// our order of preference among equally capable ones, and drop those that
+// can't be played at all (as long as there is at least one playable source).
+function negotiateSources(audio) {
+    const sources = [...audio.querySelectorAll('source')];
+    const rank = source => ({ probably: 2, maybe: 1 })[audio.canPlayType(source.type)] ?? 0;
+    const ranked = sources
+        .map((source, index) => ({ index, rank: rank(source), source }))
+        .sort((a, b) => (b.rank - a.rank) || (a.index - b.index));
+
+    if (ranked[0].rank === 0) return;
+
+    const unchanged = ranked.every(({ index, rank }, position) => index === position && rank > 0);
+    if (unchanged) return;
+
+    for (const { rank, source } of ranked) {
+        if (rank === 0) {
+            source.remove();
+        } else {
+            audio.appendChild(source);
+        }
+    }
+
+    // The browser already selected a source when the <audio> element was
+    // parsed, reordering the <source> elements only has an effect once the
+    // source selection is run again.
+    audio.load();
+}
+
+// Returns the track to be played back after the given one, or null if
+// there is none. A track that is not (or no longer) in the queue continues
+// with the next queued track that follows it on the page.
+function nextInQueue(track) {
+    const index = queue.indexOf(track);
+
+    if (index === -1) {
+        return tracks
+            .slice(tracks.indexOf(track) + 1)
+            .find(followingTrack => queue.includes(followingTrack)) ?? null;
+    }
+
+    return queue[index + 1] ?? null;
+}
+
 // Open the docked player and update its various subelements to display the
 // given track. If track.seekTo is set a seek is indicated by advancing both
 // the track's own progress indicator and the docked player progress bar to
@@ -187,7 +382,7 @@ function open(track) {
 
     // Not available on a track player
     if (dockedPlayer.number) {
-        dockedPlayer.nextTrackButton.toggleAttribute('disabled', !track.nextTrack);
+        dockedPlayer.nextTrackButton.toggleAttribute('disabled', !nextInQueue(track));
         dockedPlayer.number.textContent = track.number.textContent;
     }
 
@@ -208,22 +403,19 @@ function open(track) {
     track.open = true;
 }
 
-// Parses (and validates) track/time parameters from the current url
-// (e.g. https://example.com/#track=3&time=4m12s) and returns them as an
-// object (e.g. { time: 252, track: [reference to track] }). Track can be
-// specified as n=1 or track=1, time can be specified as t=60 or time=60, but
-// also supports complex specifiers like t=1h, t=1m t=1s, t=1h1m, t=1h1m1s,
-// etc. In case of no known params being present or errors being encountered
-// (wrong syntax for params, out-of-bound track numbers or timecodes, etc.)
-// null is returned. Note that in case of a non-null return value, there is
-// always a reference to a track returned (!), i.e. if the hash specified
-// only #t=60, this is interpreted as "seek to 60 seconds on the first
-// track".
-function parseHashParams() {
-    if (location.hash.length === 0) return null;
-
-    const params = new URLSearchParams(location.hash.substring(1));
-
+// Parses (and validates) track/time parameters from the given
+// URLSearchParams (e.g. obtained from https://example.com/#track=3&time=4m12s
+// or https://example.com/?track=3&t=252) and returns them as an object
+// (e.g. { time: 252, track: [reference to track] }). Track can be specified
+// as n=1 or track=1, time can be specified as t=60 or time=60, but also
+// supports complex specifiers like t=1h, t=1m t=1s, t=1h1m, t=1h1m1s, etc.,
+// as well as clock-style specifiers like t=4:12 or t=1:04:12. In case of no
+// known params being present or errors being encountered (wrong syntax for
+// params, out-of-bound track numbers or timecodes, etc.) null is returned.
+// Note that in case of a non-null return value, there is always a reference
+// to a track returned (!), i.e. if the params specified only t=60, this is
+// interpreted as "seek to 60 seconds on the first track".
+function parseParams(params) {
     const timeParam = params.get('t') ?? params.get('time');
     const trackParam = params.get('n') ?? params.get('track');
 
@@ -246,11 +438,13 @@ function parseHashParams() {
     if (timeParam !== null) {
         // Match all of "1", "1s", "1m" "1h" "1m1s", "1h1m1s", "1h1s", "1h1m", etc.
         const match = timeParam.match(/^(?:([0-9]+)h)?(?:([0-9]+)m)?(?:([0-9]+)s?)?$/);
+        // Match all of "1:01", "01:01", "1:01:01", etc.
+        const clockMatch = timeParam.match(/^(?:([0-9]+):)?([0-9]+):([0-9]{2})$/);
 
-        if (match) {
+        if (match || clockMatch) {
             result.time = 0;
 
-            const [_, h, m, s] = match;
+            const [_, h, m, s] = match ?? clockMatch;
 
             if (h) { result.time += parseInt(h) * 3600; }
             if (m) { result.time += parseInt(m) * 60; }
@@ -267,7 +461,35 @@ function parseHashParams() {
     return result;
 }
 
+// Parses track/time parameters from the hash of the current url (e.g.
+// https://example.com/#track=3&time=4m12s), see parseParams for details.
+function parseHashParams() {
+    if (location.hash.length === 0) return null;
+
+    return parseParams(new URLSearchParams(location.hash.substring(1)));
+}
+
+// Parses track/time parameters from the query string of the current url
+// (e.g. https://example.com/?track=3&t=252), see parseParams for details.
+function parseQueryParams() {
+    if (location.search.length === 0) return null;
+
+    return parseParams(new URLSearchParams(location.search));
+}
+
+// The queue is stored as the (1-based) numbers of the tracks on the page
+function persistQueue() {
+    const numbers = queue.map(track => tracks.indexOf(track) + 1);
+    localStorage.setItem(QUEUE_STORAGE_KEY, JSON.stringify(numbers));
+}
+
 function play(track) {
+    setPlayerError('');
+
+    if (track.fadingOut) {
+        endFadeOut(track);
+    }
+
     if (!track.open) {
         open(track);
 
@@ -307,6 +529,7 @@ function play(track) {
         track.solicitedPlayback = true;
         setSpeed(track);
         setVolume(track);
+        setVolumeNormalization(track);
         track.audio.play();
     }
 
@@ -317,6 +540,127 @@ function play(track) {
     }
 }
 
+// Called when the track's audio fails, either because none of its sources
+// could be loaded (failedSource is null), or because the source in use broke
+// off during playback (e.g. a stale CDN returning 404 for parts of a file
+// replaced by a newer deployment). We first fall back to the alternative
+// streaming format (if there is one that has not failed yet), then retry all
+// formats with increasing delays, and eventually give up.
+function recover(track, failedSource) {
+    if (track !== activeTrack || !(track.solicitedPlayback || track.seeking)) return;
+
+    if (failedSource !== null && track.audio.querySelectorAll('source').length > 1) {
+        failedSource.remove();
+        reload(track);
+        return;
+    }
+
+    track.retries = (track.retries ?? 0) + 1;
+
+    clearInterval(globalUpdatePlayHeadInterval);
+    delete track.solicitedPlayback;
+    if (track.seeking) {
+        track.seeking.cancel();
+    }
+
+    if (track.retries > PLAYBACK_RETRY_LIMIT) {
+        delete track.retries;
+
+        // All formats get a fresh chance when the visitor tries again
+        track.audio.replaceChildren(...track.sources);
+        track.audio.preload = 'none';
+
+        dockedPlayer.playbackButton.replaceChildren(playIcon.cloneNode(true));
+        listenButtonIcon.replaceChildren(playIcon.cloneNode(true));
+        listenButtonLabel.textContent = PLAYER_JS_T.listen;
+        track.playbackButtonIcon.replaceChildren(playIcon.cloneNode(true));
+
+        setPlayerError(PLAYER_JS_T.playerErrorUnavailable);
+        return;
+    }
+
+    setPlayerError(PLAYER_JS_T.playerErrorRetrying);
+
+    const delay = PLAYBACK_RETRY_DELAY_MS * 2 ** (track.retries - 1);
+    track.retryTimeout = setTimeout(() => {
+        delete track.retryTimeout;
+        if (track !== activeTrack) return;
+        track.audio.replaceChildren(...track.sources);
+        reload(track);
+    }, delay);
+}
+
+// Reloads the track's audio (after its sources were changed) and resumes
+// playback where it left off.
+function reload(track) {
+    const position = track.seekTo ?? track.audio.currentTime;
+
+    clearInterval(globalUpdatePlayHeadInterval);
+    clearTimeout(track.retryTimeout);
+    delete track.retryTimeout;
+
+    if (track.seeking) {
+        track.seeking.cancel();
+    }
+
+    // Prevents the pause event that loading triggers from resetting the
+    // interface (see the pause event handler)
+    delete track.solicitedPlayback;
+
+    track.audio.load();
+
+    if (position > 0) {
+        track.seekTo = position;
+    }
+
+    play(track);
+}
+
+// Renders the queue into the queue panel (only present on pages with
+// more than one track) and updates the next track button, as the queue
+// determines which track comes next.
+function renderQueue() {
+    if (dockedPlayer.nextTrackButton) {
+        dockedPlayer.nextTrackButton.toggleAttribute('disabled', !nextInQueue(activeTrack));
+    }
+
+    if (!dockedPlayer.queue.container) return;
+
+    const queueButton = (className, label, text, disabled = false) => {
+        const button = document.createElement('button');
+        button.classList.add(className);
+        button.disabled = disabled;
+        button.setAttribute('aria-label', label);
+        button.textContent = text;
+        button.title = label;
+        return button;
+    };
+
+    const items = queue.map((track, index) => {
+        const item = document.createElement('li');
+        item.draggable = true;
+
+        if (track === activeTrack) {
+            item.setAttribute('aria-current', 'true');
+        }
+
+        const playButton = document.createElement('button');
+        playButton.classList.add('play');
+        playButton.textContent = `${track.number.textContent} ${track.title.textContent}`;
+
+        item.append(
+            playButton,
+            queueButton('move_up', PLAYER_JS_T.moveUp, '↑', index === 0),
+            queueButton('move_down', PLAYER_JS_T.moveDown, '↓', index === queue.length - 1),
+            queueButton('remove', PLAYER_JS_T.removeFromQueue, '×')
+        );
+
+        return item;
+    });
+
+    dockedPlayer.queue.list.replaceChildren(...items);
+}
+
 // One of the following:
 // - Request to play the active track
 // - Request to cancel seeking/loading the active track
@@ -334,6 +678,7 @@ function requestPlaybackChange(track) {
     } else {
         const playNext = () => {
             setActive(track);
+            emitPlayerEvent('trackchange', track);
             play(track);
         };
 
@@ -341,6 +686,24 @@ function requestPlaybackChange(track) {
     }
 }
 
+// Returns the queue persisted for this page, or null if there is none or if
+// it does not match the tracks on the page (anymore).
+function restoreQueue() {
+    try {
+        const numbers = JSON.parse(localStorage.getItem(QUEUE_STORAGE_KEY));
+
+        if (Array.isArray(numbers) &&
+            numbers.every(number => Number.isInteger(number) && number >= 1 && number <= tracks.length) &&
+            new Set(numbers).size === numbers.length) {
+            return numbers.map(number => tracks[number - 1]);
+        }
+    } catch (_) {
+        // Ignore corrupted data, the default queue is used instead
+    }
+
+    return null;
+}
+
 // One of the following:
 // - Request to make the active and playing track jump to another point
 // - Request to play the active but paused track from a specific point
@@ -361,6 +724,7 @@ function requestSeek(track, seekTo) {
     } else {
         const playNext = () => {
             setActive(track);
+            emitPlayerEvent('trackchange', track);
             track.seekTo = seekTo;
             play(track);
         };
@@ -408,6 +772,25 @@ function reset(track, onComplete = null) {
     }
 }
 
+// Routes the track's audio through the audio context, via a gain node for
+// crossfading and one for volume normalization. This can only be done once
+// for each audio element, from then on it always plays through the context.
+function routeThroughAudioContext(track) {
+    if (track.gainNode) return;
+
+    if (!audioContext) {
+        audioContext = new AudioContext();
+    }
+
+    track.fadeNode = audioContext.createGain();
+    track.gainNode = audioContext.createGain();
+    audioContext
+        .createMediaElementSource(track.audio)
+        .connect(track.fadeNode)
+        .connect(track.gainNode)
+        .connect(audioContext.destination);
+}
+
 function seek(track, onComplete = null) {
     const seeking = { onComplete };
 
@@ -474,6 +857,16 @@ function setActive(track) {
 
     // Emphasize active track in the track list.
     track.container.classList.add('active');
+
+    renderQueue();
+}
+
+// Shows the message in the docked player (and announces it to screenreaders),
+// an empty message hides it again.
+function setPlayerError(message) {
+    if (dockedPlayer.error) {
+        dockedPlayer.error.textContent = message;
+    }
 }
 
 function setSpeed(track) {
@@ -491,6 +884,33 @@ function setVolume(track) {
     }
 }
 
+function setVolumeNormalization(track) {
+    if (!dockedPlayer.volumeNormalizationButton || track.gain === undefined || !window.AudioContext) return;
+
+    if (!track.gainNode) {
+        if (!volumeNormalization.enabled) return;
+
+        routeThroughAudioContext(track);
+    }
+
+    if (audioContext.state === 'suspended') {
+        audioContext.resume();
+    }
+
+    track.gainNode.gain.value = volumeNormalization.enabled ? track.gain : 1;
+}
+
+// Silences tracks that are still fading out after a crossfade, e.g. when
+// the visitor pauses or skips to another track in the meantime.
+function stopFadeOuts() {
+    for (const track of tracks) {
+        if (track.fadingOut) {
+            track.audio.pause();
+            endFadeOut(track);
+        }
+    }
+}
+
 function toggleMute() {
     if (volume.level > 0) {
         volume.restoreLevel = volume.level;
@@ -504,6 +924,20 @@ function toggleMute() {
     updateVolume();
 }
 
+// Highlights the last lyrics line whose time has been reached
+function updateLyrics(currentTime) {
+    let currentLine = null;
+
+    for (const line of lyricsLines) {
+        if (parseFloat(line.dataset.time) > currentTime) break;
+        currentLine = line;
+    }
+
+    for (const line of lyricsLines) {
+        line.classList.toggle('current', line === currentLine);
+    }
+}
+
 function updatePlayhead(track, reset = false) {
     const { audio } = track;
     const factor = reset ? 0 : audio.currentTime / track.duration;
@@ -517,6 +951,10 @@ function updatePlayhead(track, reset = false) {
         track.waveform.svg.querySelector('linearGradient.playback stop:nth-child(2)').setAttribute('offset', factor + 0.0001);
         track.waveform.input.value = audio.currentTime;
     }
+
+    if (lyricsLines.length > 0) {
+        updateLyrics(reset ? -1 : audio.currentTime);
+    }
 }
 
 function updateSpeed() {
@@ -610,6 +1048,18 @@ function updateVolume(persist = true) {
     }
 }
 
+function updateVolumeNormalization(persist = true) {
+    dockedPlayer.volumeNormalizationButton.setAttribute('aria-pressed', volumeNormalization.enabled.toString());
+
+    if (activeTrack) {
+        setVolumeNormalization(activeTrack);
+    }
+
+    if (persist) {
+        localStorage.setItem('faircampVolumeNormalization', volumeNormalization.enabled ? 'enabled' : 'disabled');
+    }
+}
+
 dockedPlayer.container.addEventListener('keydown', event => {
     if (event.target === dockedPlayer.volume.sliderInput) return;
 
@@ -631,12 +1081,126 @@ dockedPlayer.playbackButton.addEventListener('click', () => {
 // Not available on a track player
 if (dockedPlayer.nextTrackButton) {
     dockedPlayer.nextTrackButton.addEventListener('click', () => {
-        if (activeTrack.nextTrack) {
-            requestPlaybackChange(activeTrack.nextTrack);
+        const nextTrack = nextInQueue(activeTrack);
+
+        if (nextTrack) {
+            requestPlaybackChange(nextTrack);
         }
     });
 }
 
+// Only available on pages with more than one track
+if (dockedPlayer.queue.container) {
+    const { container, list, resetButton, toggleButton } = dockedPlayer.queue;
+
+    toggleButton.addEventListener('click', () => {
+        const expand = container.hidden;
+        container.hidden = !expand;
+        toggleButton.setAttribute('aria-expanded', expand ? 'true' : 'false');
+    });
+
+    resetButton.addEventListener('click', () => {
+        queue = [...tracks];
+        localStorage.removeItem(QUEUE_STORAGE_KEY);
+        renderQueue();
+    });
+
+    list.addEventListener('click', event => {
+        const button = event.target.closest('button');
+        if (!button) return;
+
+        const index = [...list.children].indexOf(button.closest('li'));
+
+        // As the list is rendered anew after each change, we restore
+        // keyboard focus to the equivalent button after the change.
+        const refocus = (itemIndex, className) => {
+            const item = list.children[itemIndex];
+            if (item) {
+                const target = item.querySelector(`button.${className}`);
+                (target.disabled ? item.querySelector('button.play') : target).focus();
+            } else {
+                resetButton.focus();
+            }
+        };
+
+        if (button.classList.contains('move_down')) {
+            moveInQueue(index, index + 1);
+            refocus(index + 1, 'move_down');
+        } else if (button.classList.contains('move_up')) {
+            moveInQueue(index, index - 1);
+            refocus(index - 1, 'move_up');
+        } else if (button.classList.contains('remove')) {
+            queue.splice(index, 1);
+            persistQueue();
+            renderQueue();
+            refocus(Math.min(index, queue.length - 1), 'remove');
+        } else {
+            requestPlaybackChange(queue[index]);
+        }
+    });
+
+    // Drag and drop reordering (the buttons above are the equivalent for
+    // keyboard and assistive technology users)
+    let dragIndex = null;
+
+    list.addEventListener('dragstart', event => {
+        const item = event.target.closest('li');
+        dragIndex = [...list.children].indexOf(item);
+        event.dataTransfer.effectAllowed = 'move';
+        event.dataTransfer.setData('text/plain', queue[dragIndex].title.textContent);
+        item.classList.add('dragging');
+    });
+
+    list.addEventListener('dragover', event => {
+        if (dragIndex !== null && event.target.closest('li')) {
+            event.preventDefault();
+            event.dataTransfer.dropEffect = 'move';
+        }
+    });
+
+    list.addEventListener('drop', event => {
+        const item = event.target.closest('li');
+
+        if (dragIndex !== null && item) {
+            event.preventDefault();
+            moveInQueue(dragIndex, [...list.children].indexOf(item));
+        }
+
+        dragIndex = null;
+    });
+
+    list.addEventListener('dragend', () => {
+        dragIndex = null;
+        renderQueue();
+    });
+}
+
+// Only available when copy_link is enabled. Copies a link that points to the
+// current playback position (track and timecode), relying on the feedback
+// routines from clipboard.js (which is always present alongside the button).
+if (dockedPlayer.copyTimecodeLinkButton) {
+    if (navigator.clipboard) {
+        dockedPlayer.copyTimecodeLinkButton.addEventListener('click', () => {
+            const button = dockedPlayer.copyTimecodeLinkButton;
+            const iconContainer = button.querySelector('.icon');
+            const pageUrl = button.dataset.content || location.href.split(/[?#]/)[0];
+            const time = formatTimecodeParam(activeTrack.seekTo ?? activeTrack.audio.currentTime);
+
+            // On a track player there is no track number (there is only one track)
+            const url = dockedPlayer.number
+                ? `${pageUrl}#track=${tracks.indexOf(activeTrack) + 1}&time=${time}`
+                : `${pageUrl}#time=${time}`;
+
+            navigator.clipboard
+                .writeText(url)
+                .then(() => copyFeedback(url, successIcon, iconContainer))
+                .catch(_err => copyFeedback(url, failedIcon, iconContainer));
+        });
+    } else {
+        dockedPlayer.copyTimecodeLinkButton.remove();
+    }
+}
+
 // Only available when enabled
 if (dockedPlayer.speedButton) {
     dockedPlayer.speedButton.addEventListener('auxclick', event => {
@@ -897,6 +1461,13 @@ dockedPlayer.volume.sliderInput.addEventListener('keydown', event => {
 // hence we disable the default behavior and let the event bubble up to our own handler
 dockedPlayer.volume.sliderInput.addEventListener('wheel', event => event.preventDefault());
 
+if (dockedPlayer.volumeNormalizationButton) {
+    dockedPlayer.volumeNormalizationButton.addEventListener('click', () => {
+        volumeNormalization.enabled = !volumeNormalization.enabled;
+        updateVolumeNormalization();
+    });
+}
+
 listenButton.addEventListener('click', () => {
     requestPlaybackChange(activeTrack);
 });
@@ -920,7 +1491,6 @@ const resizeObserver = new ResizeObserver(entries => {
     waveforms(minWidth);
 });
 
-let previousTrack = null;
 let trackIndex = 0;
 for (const container of document.querySelectorAll('.track')) {
     const artists = container.querySelector('.artists');
@@ -932,17 +1502,45 @@ for (const container of document.querySelectorAll('.track')) {
 
     const duration = parseFloat(container.dataset.duration);
 
+    // Only present if volume normalization is enabled for the track
+    const gain = container.dataset.gain === undefined ? undefined : parseFloat(container.dataset.gain);
+
     const track = {
         artists,
         audio,
         container,
         duration,
+        gain,
         number,
         playbackButton,
         playbackButtonIcon,
         title
     };
 
+    negotiateSources(audio);
+
+    // The negotiated sources, restored when retrying after an error
+    track.sources = [...audio.querySelectorAll('source')];
+
+    // When a source fails to load, the browser moves on to the next one by
+    // itself, only when the last one fails too we need to step in.
+    for (const source of track.sources) {
+        source.addEventListener('error', () => {
+            if (source === audio.querySelector('source:last-of-type')) {
+                recover(track, null);
+            }
+        });
+    }
+
+    // Errors that occur after a source was picked (e.g. when the rest of
+    // the file can not be fetched while playing back)
+    audio.addEventListener('error', () => {
+        if (audio.error && audio.error.code === MediaError.MEDIA_ERR_ABORTED) return;
+
+        const failedSource = track.sources.find(source => source.src === audio.currentSrc) ?? null;
+        recover(track, failedSource);
+    });
+
     // We only unmute tracks right before they play, muting them again at any
     // pause event. We do this because a bug in browsers on apple systems can
     // trigger sporadic, unsolicited playback of tracks in certain conditions
@@ -957,15 +1555,19 @@ for (const container of document.querySelectorAll('.track')) {
     // reachable by keyboard.
     playbackButton.tabIndex = 0;
 
-    if (previousTrack !== null) {
-        previousTrack.nextTrack = track;
-    }
+    audio.addEventListener('ended', event => {
+        emitPlayerEvent('finish', track);
 
-    previousTrack = track;
+        // The next track already plays since the crossfade began
+        if (track.fadingOut) {
+            endFadeOut(track);
+            return;
+        }
 
-    audio.addEventListener('ended', event => {
-        if (track.nextTrack) {
-            requestPlaybackChange(track.nextTrack);
+        const nextTrack = nextInQueue(track);
+
+        if (nextTrack) {
+            requestPlaybackChange(nextTrack);
         } else {
             reset(track);
             // Hide docked player
@@ -981,6 +1583,10 @@ for (const container of document.querySelectorAll('.track')) {
         delete track.solicitedPlayback;
         track.audio.muted = true;
 
+        stopFadeOuts();
+
+        emitPlayerEvent('pause', track);
+
         clearInterval(globalUpdatePlayHeadInterval);
 
         container.classList.remove('playing');
@@ -1007,6 +1613,8 @@ for (const container of document.querySelectorAll('.track')) {
             return;
         }
 
+        emitPlayerEvent('play', track);
+
         container.classList.add('playing');
         dockedPlayer.playbackButton.replaceChildren(pauseIcon.cloneNode(true));
         listenButtonIcon.replaceChildren(pauseIcon.cloneNode(true));
@@ -1021,12 +1629,37 @@ for (const container of document.querySelectorAll('.track')) {
     audio.addEventListener('playing', event => {
         if (!track.solicitedPlayback) { return; }
 
+        delete track.retries;
+        setPlayerError('');
+
         dockedPlayer.playbackButton.replaceChildren(pauseIcon.cloneNode(true));
         listenButtonIcon.replaceChildren(pauseIcon.cloneNode(true));
         listenButtonLabel.textContent = PLAYER_JS_T.pause;
         track.playbackButtonIcon.replaceChildren(pauseIcon.cloneNode(true));
     });
 
+    // Loads the next track ahead of time on releases that play
+    // continuously, and starts crossfading into it when the end of this
+    // track is near (if a crossfade is configured).
+    audio.addEventListener('timeupdate', event => {
+        if (!continuousPlayback.enabled || track !== activeTrack || !track.solicitedPlayback) return;
+
+        const nextTrack = nextInQueue(track);
+
+        if (!nextTrack) return;
+
+        const remainingSeconds = (track.duration - audio.currentTime) / audio.playbackRate;
+
+        if (remainingSeconds < PRELOAD_AHEAD_SECONDS && nextTrack.audio.preload !== 'auto') {
+            nextTrack.audio.preload = 'auto';
+            nextTrack.audio.load();
+        }
+
+        if (continuousPlayback.crossfade > 0 && window.AudioContext && remainingSeconds <= continuousPlayback.crossfade) {
+            crossfade(track, nextTrack);
+        }
+    });
+
     audio.addEventListener('waiting', event => {
         if (!track.solicitedPlayback) { return; }
 
@@ -1154,6 +1787,8 @@ for (const container of document.querySelectorAll('.track')) {
     tracks.push(track);
 }
 
+queue = restoreQueue() ?? [...tracks];
+
 window.addEventListener('hashchange', event => {
     const params = parseHashParams();
 
@@ -1179,8 +1814,52 @@ window.addEventListener('keydown', event => {
     }
 });
 
+// A visual loop is rendered as a still poster image, which we only replace
+// with the looping video if the visitor does not prefer reduced motion and
+// has not requested reduced data usage. If the preference for reduced motion
+// is turned on later, the loop is paused (and resumed when it's turned off).
+const visualLoop = document.querySelector('.visual_loop[data-src]');
+if (visualLoop) {
+    const reducedMotion = window.matchMedia('(prefers-reduced-motion: reduce)');
+    const saveData = navigator.connection && navigator.connection.saveData;
+
+    if (!reducedMotion.matches && !saveData) {
+        const poster = visualLoop.querySelector('img');
+        const video = document.createElement('video');
+
+        video.autoplay = true;
+        video.disablePictureInPicture = true;
+        video.loop = true;
+        video.muted = true;
+        video.playsInline = true;
+        video.poster = poster.src;
+        video.src = visualLoop.dataset.src;
+
+        if (poster.alt) {
+            video.setAttribute('aria-label', poster.alt);
+        } else {
+            video.setAttribute('aria-hidden', 'true');
+        }
+
+        // Only swap out the poster once the video can actually play, so
+        // that a failing video never leaves an empty spot behind.
+        video.addEventListener('canplay', () => {
+            poster.replaceWith(video);
+            video.play();
+        }, { once: true });
+
+        reducedMotion.addEventListener('change', () => {
+            if (reducedMotion.matches) {
+                video.pause();
+            } else {
+                video.play();
+            }
+        });
+    }
+}
+
 // Set active track (and optionally set seekTo and/or open player)
-const params = parseHashParams();
+const params = parseHashParams() ?? parseQueryParams();
 if (params) {
     setActive(params.track);
 
@@ -0,0 +1,107 @@
+// A reduced player for older browsers that can not run player.js, which is
+// only loaded through <script nomodule> when the catalog's legacy_support
+// option is enabled (browsers that ignore nomodule can't parse player.js,
+// so each browser only runs one of the two). It plays and pauses tracks through their
+// playback buttons and the listen button, and continues with the next track
+// when one ends - waveforms, the docked player, the queue, volume
+// normalization and crossfading are not available. This file therefore must
+// only use syntax that older browsers understand (var and plain functions -
+// no arrow functions, template literals, optional chaining, etc.), and it
+// does not depend on the Web Audio API.
+
+(function () {
+    var listenButton = document.querySelector('button.listen');
+    var listenButtonIcon = document.querySelector('button.listen .icon');
+    var listenButtonLabel = document.querySelector('button.listen .label');
+    var pauseIcon = document.querySelector('#pause_icon');
+    var playIcon = document.querySelector('#play_icon');
+
+    var activeTrack = null;
+    var tracks = [];
+
+    // Template content is not available in all older browsers, the markup
+    // inside the template element can however always be copied.
+    function setIcon(element, template) {
+        if (element && template) {
+            element.innerHTML = template.innerHTML;
+        }
+    }
+
+    function setPlaying(track, playing) {
+        track.container.className = track.container.className.replace(/ ?\b(active|playing)\b/g, '');
+
+        if (playing) {
+            track.container.className += ' active playing';
+        }
+
+        setIcon(track.playbackButtonIcon, playing ? pauseIcon : playIcon);
+
+        if (listenButton) {
+            setIcon(listenButtonIcon, playing ? pauseIcon : playIcon);
+            listenButtonLabel.textContent = playing ? PLAYER_LEGACY_JS_T.pause : PLAYER_LEGACY_JS_T.listen;
+        }
+    }
+
+    function togglePlayback(track) {
+        if (activeTrack !== null && activeTrack !== track) {
+            activeTrack.audio.pause();
+            activeTrack.audio.currentTime = 0;
+        }
+
+        activeTrack = track;
+
+        if (track.audio.paused) {
+            track.audio.play();
+        } else {
+            track.audio.pause();
+        }
+    }
+
+    var containers = document.querySelectorAll('.track');
+
+    for (var index = 0; index < containers.length; index++) {
+        (function (container) {
+            var track = {
+                audio: container.querySelector('audio'),
+                container: container,
+                playbackButton: container.querySelector('.track_playback'),
+                playbackButtonIcon: container.querySelector('.track_playback .icon')
+            };
+
+            if (!track.audio || !track.playbackButton) return;
+
+            tracks.push(track);
+
+            // See the corresponding comment in player.js
+            track.playbackButton.tabIndex = 0;
+
+            track.playbackButton.addEventListener('click', function () {
+                togglePlayback(track);
+            });
+
+            track.audio.addEventListener('pause', function () {
+                setPlaying(track, false);
+            });
+
+            track.audio.addEventListener('play', function () {
+                setPlaying(track, true);
+            });
+
+            track.audio.addEventListener('ended', function () {
+                var nextTrack = tracks[tracks.indexOf(track) + 1];
+
+                track.audio.currentTime = 0;
+
+                if (nextTrack) {
+                    togglePlayback(nextTrack);
+                }
+            });
+        })(containers[index]);
+    }
+
+    if (listenButton && tracks.length > 0) {
+        listenButton.addEventListener('click', function () {
+            togglePlayback(activeTrack || tracks[0]);
+        });
+    }
+})();
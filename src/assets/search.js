@@ -0,0 +1,151 @@
+const searchForm = document.querySelector('#search');
+const searchInput = searchForm.querySelector('input');
+const searchResults = document.querySelector('#search_results');
+const searchStatus = document.querySelector('#search_status');
+
+const indexSuffix = window.location.pathname.endsWith('index.html') ? 'index.html' : '';
+const rootPrefix = searchForm.dataset.rootPrefix;
+
+const MAX_RESULTS = 50;
+
+// The fields of the index entries (see scripts/search.rs) with their weight,
+// a match in the title counts the most, a match in the synopsis the least.
+const FIELDS = [
+    ['t', 3], // title
+    ['a', 2], // artists
+    ['k', 1.5], // keywords (imprint, region, categories)
+    ['r', 1.5], // release title (for tracks)
+    ['s', 1] // synopsis
+];
+
+// Case and diacritics are ignored when matching
+function normalize(text) {
+    return text.normalize('NFD').replace(/[\u0300-\u036f]/g, '').toLowerCase();
+}
+
+const normalizedIndex = SEARCH_INDEX.map(entry => {
+    const normalized = {};
+    for (const [field] of FIELDS) {
+        if (entry[field]) {
+            normalized[field] = normalize(entry[field]);
+        }
+    }
+    return normalized;
+});
+
+// Scores how well a single query term matches a text: 1 for a match at the
+// beginning of a word, 0.8 for a match anywhere else, and for a fuzzy match
+// (all characters of the term in order, with other characters in between)
+// less the more spread out the characters are. 0 means no match.
+function scoreTerm(term, text) {
+    const index = text.indexOf(term);
+
+    if (index !== -1) {
+        return index === 0 || /[\s\p{P}]/u.test(text[index - 1]) ? 1 : 0.8;
+    }
+
+    // Short terms match almost anything fuzzily
+    if (term.length < 3) return 0;
+
+    let start = -1;
+    let position = 0;
+
+    for (const char of term) {
+        position = text.indexOf(char, position);
+        if (position === -1) return 0;
+        if (start === -1) { start = position; }
+        position += 1;
+    }
+
+    const span = position - start;
+
+    if (span > term.length * 3) return 0;
+
+    return 0.5 * term.length / span;
+}
+
+// Every term of the query needs to match at least one field of an entry,
+// entries are ranked by the sum of their best (weighted) match per term.
+function search(query) {
+    const terms = normalize(query).split(/\s+/).filter(term => term.length > 0);
+    const results = [];
+
+    normalizedIndex.forEach((normalized, entryIndex) => {
+        let score = 0;
+
+        for (const term of terms) {
+            let termScore = 0;
+
+            for (const [field, weight] of FIELDS) {
+                if (normalized[field]) {
+                    termScore = Math.max(termScore, scoreTerm(term, normalized[field]) * weight);
+                }
+            }
+
+            if (termScore === 0) return;
+
+            score += termScore;
+        }
+
+        results.push({ entry: SEARCH_INDEX[entryIndex], score });
+    });
+
+    return results
+        .sort((a, b) => b.score - a.score)
+        .slice(0, MAX_RESULTS);
+}
+
+function showResults(query) {
+    searchResults.replaceChildren();
+
+    if (query.length === 0) {
+        searchStatus.textContent = '';
+        return;
+    }
+
+    const results = search(query);
+
+    for (const { entry } of results) {
+        const link = document.createElement('a');
+        link.href = rootPrefix + entry.u + indexSuffix;
+        link.textContent = entry.t;
+
+        const item = document.createElement('li');
+        item.appendChild(link);
+
+        const details = [entry.a, entry.r].filter(detail => detail).join(' – ');
+
+        if (details.length > 0) {
+            const span = document.createElement('span');
+            span.textContent = details;
+            item.appendChild(span);
+        }
+
+        searchResults.appendChild(item);
+    }
+
+    if (results.length === 0) {
+        searchStatus.textContent = SEARCH_JS_T.nothingFoundForXxx(query);
+    } else {
+        searchStatus.textContent = SEARCH_JS_T.showingXxxResultsForXxx(results.length, query);
+    }
+}
+
+searchForm.addEventListener('submit', event => event.preventDefault());
+
+// The query is kept in the url, so that searches can be linked to and
+// survive navigating back from a result.
+searchInput.addEventListener('input', () => {
+    const query = searchInput.value.trim();
+    const url = query.length > 0 ? `?q=${encodeURIComponent(query)}` : window.location.pathname;
+
+    history.replaceState(null, '', url);
+    showResults(query);
+});
+
+const initialQuery = new URLSearchParams(window.location.search).get('q');
+
+if (initialQuery) {
+    searchInput.value = initialQuery;
+    showResults(initialQuery.trim());
+}
@@ -25,6 +25,10 @@ pub enum AudioFormat {
     Opus48Kbps,
     Opus96Kbps,
     Opus128Kbps,
+    /// The source file itself, copied verbatim without transcoding. Only
+    /// available as a download format - its extension and family depend on
+    /// the source file (see Track::download_extension).
+    Original,
     Wav
 }
 
@@ -57,6 +61,7 @@ impl AudioFormat {
             AudioFormat::Opus48Kbps => "opus-48",
             AudioFormat::Opus96Kbps => "opus-96",
             AudioFormat::Opus128Kbps => "opus-128",
+            AudioFormat::Original => "original",
             AudioFormat::Wav => "wav"
         }
     }
@@ -74,6 +79,7 @@ impl AudioFormat {
             AudioFormat::Opus48Kbps |
             AudioFormat::Opus96Kbps |
             AudioFormat::Opus128Kbps => ".opus",
+            AudioFormat::Original => unimplemented!(),
             AudioFormat::Wav => ".wav"
         }
     }
@@ -91,6 +97,7 @@ impl AudioFormat {
             AudioFormat::Opus48Kbps |
             AudioFormat::Opus96Kbps |
             AudioFormat::Opus128Kbps => AudioFormatFamily::Opus,
+            AudioFormat::Original => unimplemented!(),
             AudioFormat::Wav => AudioFormatFamily::Wav
         }
     }
@@ -117,6 +124,7 @@ impl AudioFormat {
             AudioFormat::Opus48Kbps |
             AudioFormat::Opus96Kbps |
             AudioFormat::Opus128Kbps => "audio/ogg; codecs=opus",
+            AudioFormat::Original => unimplemented!(),
             AudioFormat::Wav => unimplemented!()
         }
     }
@@ -136,6 +144,7 @@ impl Display for AudioFormat {
             AudioFormat::Opus48Kbps => "Opus 48",
             AudioFormat::Opus96Kbps => "Opus 96",
             AudioFormat::Opus128Kbps => "Opus 128",
+            AudioFormat::Original => "Original",
             AudioFormat::Wav => "WAV"
         };
         
@@ -13,7 +13,7 @@ use std::path::Path;
 
 use serde_derive::{Serialize, Deserialize};
 
-use crate::{AudioFormatFamily, Build};
+use crate::{AudioFormatFamily, Build, Loudness};
 use crate::decode::DecodeResult;
 
 mod aiff;
@@ -37,9 +37,12 @@ pub struct AudioMeta {
     /// The track artists as provided by tags
     /// (Vec because some tag standards support multiple artists)
     pub artists: Vec<String>,
+    /// The disc number as provided by tags (for releases spanning multiple discs)
+    pub disc_number: Option<u32>,
     pub duration_seconds: f32,
     pub format_family: AudioFormatFamily,
     pub lossless: bool,
+    pub loudness: Loudness,
     /// A simplified, compressed sequence of peaks in the audio,
     /// which are used to later compute the waveform visualization
     pub peaks: Vec<f32>,
@@ -68,6 +71,7 @@ impl AudioMeta {
                 //     album_artists: Vec::new(),
                 //     artists: Vec::new(),
                 //     comment: None,
+                //     disc_number: None,
                 //     duration_seconds: 0.0,
                 //     format_family: AudioFormatFamily::Aac,
                 //     license: None,
@@ -154,7 +158,8 @@ fn compute_peaks(decode_result: DecodeResult, points: u32) -> Vec<f32> {
 /// We don't ever need the total track count so this is a parsing routine
 /// that extracts only the track number. This function practically also
 /// accepts nonsense like "01/boom", happily returning 1, as there's
-/// not really any harm coming from that.
+/// not really any harm coming from that. Disc numbers follow the same
+/// convention ("1" or "1/2"), so we use this for parsing them as well.
 pub fn parse_track_number_ignoring_total_tracks(string: &str) -> Option<u32> {
     let mut split_by_slash = string.trim().split('/');
 
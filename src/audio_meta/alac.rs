@@ -3,7 +3,7 @@
 
 use std::path::Path;
 
-use crate::AudioFormatFamily;
+use crate::{AudioFormatFamily, Loudness};
 use crate::decode::alac;
 
 use mp4parse::TryString;
@@ -15,9 +15,10 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
     let format_family = AudioFormatFamily::Alac;
     let lossless = true;
 
-    let (duration_seconds, peaks) = match alac::decode(path) {
+    let (duration_seconds, loudness, peaks) = match alac::decode(path) {
         Ok(decode_result) => (
             decode_result.duration,
+            Loudness::measure(&decode_result),
             compute_peaks(decode_result, 320)
         ),
         Err(err) => return Err(err)
@@ -29,15 +30,18 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
         let artists = extract_multiple(meta.artist); // '©art' or '©ART'
         let title = extract_single(meta.title); // '©nam'
 
+        let disc_number = meta.disc_number.map(|number| number as u32);
         let track_number = meta.track_number.map(|number| number as u32);
 
         AudioMeta {
             album,
             album_artists,
             artists,
+            disc_number,
             duration_seconds,
             format_family,
             lossless,
+            loudness,
             peaks,
             title,
             track_number
@@ -47,9 +51,11 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
             album: None,
             album_artists: Vec::new(),
             artists: Vec::new(),
+            disc_number: None,
             duration_seconds,
             format_family,
             lossless,
+            loudness,
             peaks,
             title: None,
             track_number: None
@@ -5,7 +5,7 @@ use std::path::Path;
 
 use metaflac::Tag;
 
-use crate::AudioFormatFamily;
+use crate::{AudioFormatFamily, Loudness};
 use crate::decode::flac;
 
 use super::{
@@ -19,9 +19,10 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
     let format_family = AudioFormatFamily::Flac;
     let lossless = true;
 
-    let (duration_seconds, peaks) = match flac::decode(path) {
+    let (duration_seconds, loudness, peaks) = match flac::decode(path) {
         Ok(decode_result) => (
             decode_result.duration,
+            Loudness::measure(&decode_result),
             compute_peaks(decode_result, 320)
         ),
         Err(err) => return Err(err)
@@ -47,13 +48,23 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
             None => None
         };
 
+        let disc_number = match tag.get_vorbis("discnumber") {
+            Some(fields) => fields.fold(None, |result, field| {
+                parse_track_number_ignoring_total_tracks(field)
+                    .or(result)
+            }),
+            None => None
+        };
+
         AudioMeta {
             album,
             album_artists,
             artists,
+            disc_number,
             duration_seconds,
             format_family,
             lossless,
+            loudness,
             peaks,
             title,
             track_number
@@ -63,9 +74,11 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
             album: None,
             album_artists: Vec::new(),
             artists: Vec::new(),
+            disc_number: None,
             duration_seconds,
             format_family,
             lossless,
+            loudness,
             peaks,
             title: None,
             track_number: None
@@ -3,7 +3,7 @@
 
 use std::path::Path;
 
-use crate::AudioFormatFamily;
+use crate::{AudioFormatFamily, Loudness};
 use crate::decode::ogg_vorbis;
 
 use super::{
@@ -17,9 +17,10 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
     let format_family = AudioFormatFamily::OggVorbis;
     let lossless = false;
 
-    let (duration_seconds, peaks, comment_header) = match ogg_vorbis::decode(path) {
+    let (duration_seconds, loudness, peaks, comment_header) = match ogg_vorbis::decode(path) {
         Ok((decode_result, comment_header)) => (
             decode_result.duration,
+            Loudness::measure(&decode_result),
             compute_peaks(decode_result, 320),
             Some(comment_header)
         ),
@@ -29,6 +30,7 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
     let mut album = None;
     let mut album_artists = Vec::new();
     let mut artists = Vec::new();
+    let mut disc_number = None;
     let mut title = None;
     let mut track_number = None;
 
@@ -45,6 +47,9 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
                 "artist" => if let Some(trimmed) = trim_and_reject_empty(&value) {
                     artists.push(trimmed);
                 }
+                "discnumber" => if let Some(number) = parse_track_number_ignoring_total_tracks(&value) {
+                    disc_number = Some(number);
+                }
                 "title" => if let Some(trimmed) = trim_and_reject_empty(&value) {
                     title = Some(trimmed);
                 }
@@ -59,9 +64,11 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
             album,
             album_artists,
             artists,
+            disc_number,
             duration_seconds,
             format_family,
             lossless,
+            loudness,
             peaks,
             title,
             track_number
@@ -71,9 +78,11 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
             album,
             album_artists,
             artists,
+            disc_number,
             duration_seconds,
             format_family,
             lossless,
+            loudness,
             peaks,
             title,
             track_number
@@ -6,7 +6,7 @@ use std::path::Path;
 
 use opus_headers::parse_from_path;
 
-use crate::AudioFormatFamily;
+use crate::{AudioFormatFamily, Loudness};
 use crate::decode::opus;
 
 use super::{
@@ -20,9 +20,10 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
     let format_family = AudioFormatFamily::Opus;
     let lossless = false;
 
-    let (duration_seconds, peaks) = match opus::decode(path) {
+    let (duration_seconds, loudness, peaks) = match opus::decode(path) {
         Ok(decode_result) => (
             decode_result.duration,
+            Loudness::measure(&decode_result),
             compute_peaks(decode_result, 320)
         ),
         Err(err) => return Err(err)
@@ -41,13 +42,20 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
             None => None
         };
 
+        let disc_number = match user_comments.get("discnumber") {
+            Some(disc_number) => parse_track_number_ignoring_total_tracks(disc_number),
+            None => None
+        };
+
         AudioMeta {
             album,
             album_artists,
             artists,
+            disc_number,
             duration_seconds,
             format_family,
             lossless,
+            loudness,
             peaks,
             title,
             track_number
@@ -57,9 +65,11 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
             album: None,
             album_artists: Vec::new(),
             artists: Vec::new(),
+            disc_number: None,
             duration_seconds,
             format_family,
             lossless,
+            loudness,
             peaks,
             title: None,
             track_number: None
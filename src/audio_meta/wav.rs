@@ -5,7 +5,7 @@ use std::path::Path;
 
 use id3::{Tag, TagLike};
 
-use crate::AudioFormatFamily;
+use crate::{AudioFormatFamily, Loudness};
 use crate::decode::wav;
 
 use super::{AudioMeta, compute_peaks, Id3Util};
@@ -14,9 +14,10 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
     let format_family = AudioFormatFamily::Wav;
     let lossless = true;
 
-    let (duration_seconds, peaks) = match wav::decode(path) {
+    let (duration_seconds, loudness, peaks) = match wav::decode(path) {
         Ok(decode_result) => (
             decode_result.duration,
+            Loudness::measure(&decode_result),
             compute_peaks(decode_result, 320)
         ),
         Err(err) => return Err(err)
@@ -34,9 +35,11 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
             album,
             album_artists,
             artists,
+            disc_number: tag.disc(),
             duration_seconds,
             format_family,
             lossless,
+            loudness,
             peaks,
             title,
             track_number: tag.track()
@@ -46,9 +49,11 @@ pub fn extract(path: &Path) -> Result<AudioMeta, String> {
             album: None,
             album_artists: Vec::new(),
             artists: Vec::new(),
+            disc_number: None,
             duration_seconds,
             format_family,
             lossless,
+            loudness,
             peaks,
             title: None,
             track_number: None
@@ -0,0 +1,288 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Export of the entire catalog as a BagIt bag (RFC 8493), for long-term
+//! archival deposit (e.g. with archive.org or a library). The bag contains
+//! the original source files (audio, images, extras), a json file that
+//! describes the catalog as faircamp understands it, and sha256 checksums
+//! for everything. This is entirely separate from the web build.
+
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::process::ExitCode;
+
+use sha2::{Digest, Sha256};
+
+use crate::{ArtistRc, Build, Catalog, GENERATOR_INFO};
+use crate::util::{format_bytes, json_escape};
+
+/// The directory inside the payload (data/) into which the original source
+/// files are copied, preserving their paths relative to the catalog.
+const CATALOG_PAYLOAD_DIR: &str = "catalog";
+const METADATA_FILENAME: &str = "metadata.json";
+
+/// Packages the catalog as a BagIt bag into the given directory (which must
+/// be empty or not exist yet).
+pub fn export(build: &Build, catalog: &Catalog, dir: &Path) -> ExitCode {
+    if dir.exists() && fs::read_dir(dir).map_or(true, |mut entries| entries.next().is_some()) {
+        error!("The directory {} for the bag export must be empty or not exist yet - aborting export", dir.display());
+        return ExitCode::FAILURE;
+    }
+
+    info!("Exporting the catalog as a BagIt bag to {}", dir.display());
+
+    let data_dir = dir.join("data");
+
+    // Both the (sorted) manifest lines and the payload oxum (total size and
+    // number of files) for bag-info.txt are accumulated while copying.
+    let mut manifest_lines = Vec::new();
+    let mut payload_bytes = 0;
+    let mut payload_files = 0;
+
+    for source_path in source_paths(catalog) {
+        let target_path = data_dir.join(CATALOG_PAYLOAD_DIR).join(&source_path);
+
+        fs::create_dir_all(target_path.parent().unwrap()).unwrap();
+
+        if let Err(err) = fs::copy(build.catalog_dir.join(&source_path), &target_path) {
+            error!("Could not copy {} into the bag ({}) - aborting export", source_path.display(), err);
+            return ExitCode::FAILURE;
+        }
+
+        let (checksum, size) = match sha256_file(&target_path) {
+            Ok(result) => result,
+            Err(err) => {
+                error!("Could not compute the checksum of {} ({}) - aborting export", target_path.display(), err);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        manifest_lines.push(format!("{checksum}  data/{}\n", manifest_path(&source_path)));
+        payload_bytes += size;
+        payload_files += 1;
+    }
+
+    let metadata = metadata_json(catalog);
+    let mut hasher = Sha256::new();
+    hasher.update(metadata.as_bytes());
+    manifest_lines.push(format!("{}  data/{METADATA_FILENAME}\n", hasher.finalize()));
+    payload_bytes += metadata.len() as u64;
+    payload_files += 1;
+
+    fs::write(data_dir.join(METADATA_FILENAME), &metadata).unwrap();
+
+    manifest_lines.sort();
+
+    let bagit_txt = "BagIt-Version: 1.0\nTag-File-Character-Encoding: UTF-8\n".to_string();
+
+    let bagging_date = build.build_begin.format("%Y-%m-%d");
+    let external_description = catalog.title().replace(['\n', '\r'], " ");
+    let bag_info_txt = format!("Bag-Software-Agent: {GENERATOR_INFO}\nBagging-Date: {bagging_date}\nExternal-Description: {external_description}\nPayload-Oxum: {payload_bytes}.{payload_files}\n");
+
+    let manifest_txt = manifest_lines.concat();
+
+    let mut tag_manifest_lines = Vec::new();
+
+    for (filename, content) in [
+        ("bag-info.txt", &bag_info_txt),
+        ("bagit.txt", &bagit_txt),
+        ("manifest-sha256.txt", &manifest_txt)
+    ] {
+        fs::write(dir.join(filename), content).unwrap();
+
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        tag_manifest_lines.push(format!("{}  {filename}\n", hasher.finalize()));
+    }
+
+    fs::write(dir.join("tagmanifest-sha256.txt"), tag_manifest_lines.concat()).unwrap();
+
+    info!("The bag was exported ({} files, {})", payload_files, format_bytes(payload_bytes));
+
+    ExitCode::SUCCESS
+}
+
+/// Paths inside BagIt manifests always use forward slashes, and line breaks
+/// and percent signs need to be percent-encoded.
+fn manifest_path(source_path: &Path) -> String {
+    payload_reference(source_path)
+        .replace('%', "%25")
+        .replace('\n', "%0A")
+        .replace('\r', "%0D")
+}
+
+fn metadata_json(catalog: &Catalog) -> String {
+    let json_artist_names = |artists: &[ArtistRc]| {
+        artists
+            .iter()
+            .map(|artist| format!(r#""{}""#, json_escape(&artist.borrow().name)))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+
+    let json_optional_file = |path: Option<&Path>| match path {
+        Some(path) => format!(r#""{}""#, json_escape(&payload_reference(path))),
+        None => String::from("null")
+    };
+
+    let artists = catalog.artists
+        .iter()
+        .map(|artist| {
+            let artist_ref = artist.borrow();
+            let aliases = artist_ref.aliases
+                .iter()
+                .map(|alias| format!(r#""{}""#, json_escape(alias)))
+                .collect::<Vec<String>>()
+                .join(", ");
+            let image = json_optional_file(artist_ref.image.as_ref().map(|image| image.file_meta.path.as_path()));
+
+            format!(
+                "    {{\n      \"aliases\": [{aliases}],\n      \"image\": {image},\n      \"name\": \"{}\",\n      \"permalink\": \"{}\"\n    }}",
+                json_escape(&artist_ref.name),
+                json_escape(&artist_ref.permalink.slug)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",\n");
+
+    let releases = catalog.releases
+        .iter()
+        .map(|release| {
+            let release_ref = release.borrow();
+
+            let cover = json_optional_file(release_ref.cover.as_ref().map(|cover| cover.file_meta.path.as_path()));
+            let date = match &release_ref.date {
+                Some(date) => format!(r#""{date}""#),
+                None => String::from("null")
+            };
+            let extras = release_ref.extras
+                .iter()
+                .map(|extra| format!(r#""{}""#, json_escape(&payload_reference(&extra.file_meta.path))))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            let tracks = release_ref.tracks
+                .iter()
+                .enumerate()
+                .map(|(index, track)| {
+                    let duration_seconds = track.transcodes.borrow().source_meta.duration_seconds;
+
+                    format!(
+                        "        {{\n          \"artists\": [{}],\n          \"duration_seconds\": {duration_seconds:.3},\n          \"file\": \"{}\",\n          \"number\": {},\n          \"title\": \"{}\"\n        }}",
+                        json_artist_names(&track.artists),
+                        json_escape(&payload_reference(&track.transcodes.file_meta.path)),
+                        index + 1,
+                        json_escape(&track.title())
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(",\n");
+
+            format!(
+                "    {{\n      \"artists\": [{}],\n      \"cover\": {cover},\n      \"date\": {date},\n      \"extras\": [{extras}],\n      \"permalink\": \"{}\",\n      \"support_artists\": [{}],\n      \"title\": \"{}\",\n      \"tracks\": [\n{tracks}\n      ],\n      \"unlisted\": {}\n    }}",
+                json_artist_names(&release_ref.main_artists),
+                json_escape(&release_ref.permalink.slug),
+                json_artist_names(&release_ref.support_artists),
+                json_escape(&release_ref.title),
+                release_ref.unlisted
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",\n");
+
+    let home_image = json_optional_file(catalog.home_image.as_ref().map(|image| image.file_meta.path.as_path()));
+
+    format!(
+        "{{\n  \"artists\": [\n{artists}\n  ],\n  \"generator\": \"{}\",\n  \"home_image\": {home_image},\n  \"releases\": [\n{releases}\n  ],\n  \"title\": \"{}\"\n}}\n",
+        json_escape(GENERATOR_INFO),
+        json_escape(&catalog.title())
+    )
+}
+
+/// How a source file (given relative to the catalog directory) is referenced
+/// from within metadata.json, i.e. relative to the payload directory.
+fn payload_reference(source_path: &Path) -> String {
+    let path = Path::new(CATALOG_PAYLOAD_DIR).join(source_path);
+
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+pub fn sha256_file(path: &Path) -> Result<(String, u64), std::io::Error> {
+    let mut file = File::open(path)?;
+    let mut buffer = vec![0; 1024 * 1024];
+    let mut hasher = Sha256::new();
+    let mut size = 0;
+
+    loop {
+        let read = file.read(&mut buffer)?;
+
+        if read == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..read]);
+        size += read as u64;
+    }
+
+    let checksum = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    Ok((checksum, size))
+}
+
+/// All original source files that the catalog model references (relative to
+/// the catalog directory), deduplicated and sorted.
+fn source_paths(catalog: &Catalog) -> BTreeSet<PathBuf> {
+    let mut paths = BTreeSet::new();
+
+    if let Some(home_image) = &catalog.home_image {
+        paths.insert(home_image.file_meta.path.clone());
+    }
+
+    if let Some(hero_image) = catalog.hero.as_ref().and_then(|hero| hero.custom_image()) {
+        paths.insert(hero_image.file_meta.path.clone());
+    }
+
+    for artist in &catalog.artists {
+        if let Some(image) = &artist.borrow().image {
+            paths.insert(image.file_meta.path.clone());
+        }
+    }
+
+    for release in &catalog.releases {
+        let release_ref = release.borrow();
+
+        if let Some(cover) = &release_ref.cover {
+            paths.insert(cover.file_meta.path.clone());
+        }
+
+        for extra in &release_ref.extras {
+            paths.insert(extra.file_meta.path.clone());
+        }
+
+        for track in &release_ref.tracks {
+            paths.insert(track.transcodes.file_meta.path.clone());
+
+            if let Some(cover) = &track.cover {
+                paths.insert(cover.file_meta.path.clone());
+            }
+
+            for extra in &track.extras {
+                paths.insert(extra.file_meta.path.clone());
+            }
+        }
+    }
+
+    paths
+}
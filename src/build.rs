@@ -2,22 +2,31 @@
 // SPDX-FileCopyrightText: 2025 Sandro Santilli
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use std::cell::Cell;
 use std::collections::{HashMap, HashSet};
 use std::collections::hash_map::DefaultHasher;
 use std::env;
 use std::hash::{Hash, Hasher};
+use std::mem;
 use std::net::IpAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use base64::Engine;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
 use crate::{
     Args,
+    EXTRA_SIZE_WARNING_DEFAULT,
+    GitScan,
     ImageProcessor,
     Locale,
-    SiteUrl
+    ManifestText,
+    NotifyWebhook,
+    PageType,
+    SiteUrl,
+    Timings,
+    TranscodeProcesses
 };
 use crate::util::format_bytes;
 
@@ -28,11 +37,14 @@ pub const GENERATOR_INFO: &str = concat!("Faircamp ", env!("FAIRCAMP_VERSION_DET
 /// the file content of the asset. We do this in order to prompt browsers to
 /// fetch new, uncached assets when their content has changed. This struct
 /// groups together those hashes for all assets we use.
+#[derive(Clone)]
 pub struct AssetHashes {
     pub browser_js: Option<String>,
     pub embeds_js: Option<String>,
     pub favicon_custom: Option<String>,
     pub player_js: Option<String>,
+    pub player_legacy_js: Option<String>,
+    pub search_js: Option<String>,
     pub site_css: Option<String>,
     pub theme_css: HashMap<String, String>
 }
@@ -47,8 +59,18 @@ pub struct Build {
     pub build_dir: PathBuf,
     pub cache_dir: PathBuf,
     pub catalog_dir: PathBuf,
+    /// Set through --check-texts, collects synopsis and more texts from the
+    /// manifests (see manifest_texts) to check them for common issues
+    pub check_texts: bool,
     pub clean_urls: bool,
+    /// Critical css (the rules needed to render what is visible before
+    /// scrolling) extracted from site.css for each page type. Only populated
+    /// when the catalog's critical_css option is enabled.
+    pub critical_css: HashMap<PageType, String>,
     pub deploy_destination: Option<String>,
+    /// Whether only the embeddable players (and an index page listing their
+    /// embed codes) are built, instead of a full site (--embeds-only)
+    pub embeds_only: bool,
     /// Whether at least one embed was requested to be generated somewhere.
     /// This lets us know to generate some css/js used in embeds only, and/or
     /// to print a warning in case the base_url is missing and we hence
@@ -57,19 +79,62 @@ pub struct Build {
     /// Counts errors during build
     pub errors: usize,
     pub exclude_patterns: Vec<String>,
+    /// Set through the catalog's `extra_ignore` option - filename patterns
+    /// (with `*` and `?` wildcards) for files that are left out of extras.
+    pub extra_ignore_patterns: Vec<String>,
+    /// Set through the catalog's `extra_size_warning` option - extra files
+    /// above this size (in megabytes) are reported.
+    pub extra_size_warning: Option<u64>,
+    /// Set through --git-scan (and only if the catalog is inside a git
+    /// repository), lists unchanged directories from a record of the last build
+    pub git_scan: Option<GitScan>,
     pub image_processor: ImageProcessor,
     /// Forces continuation of build even when there are errors in the
     /// manifests or during building in general.
     pub ignore_errors: bool,
+    /// Set through --include-drafts, builds releases marked as drafts too
+    pub include_drafts: bool,
     pub include_patterns: Vec<String>,
     pub locale: Locale,
+    /// Only populated with --check-texts, the texts are checked (and the
+    /// list emptied again) once the entire catalog is read.
+    pub manifest_texts: Vec<ManifestText>,
+    /// A hash over the paths and contents of all manifests read in the
+    /// build, used as part of the signature of cached rendered pages.
+    pub manifest_signature: u64,
+    /// Set through the catalog's `minify` option - when enabled, generated
+    /// html/css/js is stripped of formatting whitespace and comments.
+    pub minify: bool,
     /// If we encounter missing image descriptions during the build we set this flag.
     /// This lets us know to inject optional css used for indicating these images.
     pub missing_image_descriptions: bool,
+    /// Set through the catalog's `notify_webhook` option (see the webhook module)
+    pub notify_webhook: Option<NotifyWebhook>,
+    /// Titles and publish dates of releases whose publish date is still in
+    /// the future, summarized at the end of the build
+    pub pending_releases: Vec<(String, NaiveDate)>,
     pub post_build_action: PostBuildAction,
+    /// Set through the catalog's `image_metadata` option - when enabled, the
+    /// artist and copyright fields of source images are preserved in the
+    /// image assets, otherwise all image metadata is stripped.
+    pub preserve_image_copyright: bool,
     reserved_filenames: HashSet<String>,
+    /// Only set while the site for one of the additional languages of the
+    /// catalog is written (see the `languages` option), then holds the url
+    /// of the main site. The assets (images, audio, downloads and styles)
+    /// are only written once, for the main site, and the pages of the
+    /// language sites reference them there.
+    pub shared_assets_url: Option<SiteUrl>,
     pub stats: Stats,
+    /// Set through --strict, makes the build fail if there are any warnings
+    pub strict: bool,
     pub theming_widget: bool,
+    /// Only records anything when --timings is passed
+    pub timings: Timings,
+    /// Set through --transcode-nice, the niceness with which ffmpeg is run
+    pub transcode_nice: Option<u8>,
+    /// Set through --transcode-processes, see TranscodeProcesses::for_format
+    pub transcode_processes: Vec<TranscodeProcesses>,
     /// Most asset urls contain a deterministically random (=hashed) path
     /// segment. Out of the box, a static default string is used as a salt
     /// for hashing, which means that initially all urls remain stable
@@ -80,7 +145,17 @@ pub struct Build {
     /// deployment.
     pub url_salt: String,
     pub verbose: bool,
-    pub warnings: usize
+    /// Set through the catalog's `warnings` option - categories that are
+    /// not contained here are reported as regular warnings.
+    pub warning_levels: HashMap<WarningCategory, WarningLevel>,
+    /// All warnings that were counted, in the order they were reported, so
+    /// they can be made available to programmatic builds (see BuildReport)
+    pub warning_messages: Vec<(WarningCategory, String)>,
+    /// Counts warnings during build (silenced warnings are not counted,
+    /// warnings escalated to errors are counted as errors)
+    pub warnings: usize,
+    /// Counts warnings per category, for the summary printed with --strict
+    pub warnings_by_category: HashMap<WarningCategory, usize>
 }
 
 #[derive(Debug, PartialEq)]
@@ -94,16 +169,79 @@ pub enum PostBuildAction {
 }
 
 pub struct Stats {
+    /// Assets that were newly created (transcoded, resized, zipped) in the
+    /// cache during this build, as opposed to reused from an earlier build.
+    /// This is recorded deep inside the build, where we usually only have
+    /// shared access to Build, hence the interior mutability.
+    bytes_generated: Cell<u64>,
     bytes_used_archives: u64,
     bytes_used_extras: u64,
     bytes_used_images: u64,
     bytes_used_tracks: u64,
     num_archives: u32,
     num_extras: u32,
+    num_generated: Cell<u32>,
     num_images: u32,
     num_tracks: u32
 }
 
+/// All warnings faircamp emits belong to one of these categories. Through
+/// the catalog's `warnings` option the categories can individually be
+/// silenced or escalated to errors, for which they are identified by their
+/// key - these keys are part of the manifest format and need to stay stable.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WarningCategory {
+    /// An external url appears to be dead (only checked with --check-links)
+    DeadLink,
+    /// A manifest option is used that has been superseded by another one
+    DeprecatedOption,
+    /// A section is listed more than once in a release's layout option
+    DuplicateLayoutSection,
+    /// A release subdirectory that is neither a track directory nor
+    /// otherwise used
+    IgnoredDirectory,
+    /// A symlink or otherwise unsupported file in the catalog
+    IgnoredFile,
+    /// A track from a lossy source is offered in a lossless download format
+    LossyToLossless,
+    /// Embeds, feeds and the like can not be generated without base_url
+    MissingBaseUrl,
+    MissingImageDescription,
+    /// Purchase/download options without payment info are not displayed
+    MissingPaymentInfo,
+    /// Artists/releases have no user-assigned permalink
+    MissingPermalink,
+    /// A file that only takes effect at the root of a domain (such as
+    /// robots.txt) is generated for a site that lives in a subdirectory
+    NotAtDomainRoot,
+    /// An extra file is larger than the catalog's `extra_size_warning`
+    OversizedExtra,
+    /// An original download (see `downloads: original`) is very large
+    OversizedOriginal,
+    /// A conflicting permalink was automatically suffixed (only happens
+    /// with `permalink_conflicts: auto`)
+    PermalinkConflict,
+    /// A track exceeds the duration or size configured through the catalog's
+    /// `streaming_limits` option
+    StreamingLimit,
+    /// A synopsis or more text has a possible issue (only checked with
+    /// --check-texts)
+    TextIssue,
+    /// A cover image is too small to be displayed sharply at larger sizes
+    UndersizedCover
+}
+
+/// How warnings of a certain category are handled
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WarningLevel {
+    /// The warning is reported and counted as an error
+    Error,
+    /// The warning is neither reported nor counted
+    Silence,
+    /// The warning is reported and counted as a warning (the default)
+    Warning
+}
+
 impl AssetHashes {
     pub const CLIPBOARD_JS: &str = env!("FAIRCAMP_CLIPBOARD_JS_HASH");
     pub const EMBEDS_CSS: &str = env!("FAIRCAMP_EMBEDS_CSS_HASH");
@@ -117,6 +255,8 @@ impl AssetHashes {
             embeds_js: None,
             favicon_custom: None,
             player_js: None,
+            player_legacy_js: None,
+            search_js: None,
             site_css: None,
             theme_css: HashMap::default()
         }
@@ -124,6 +264,29 @@ impl AssetHashes {
 }
 
 impl Build {
+    /// The prefix through which a page references the assets located in
+    /// dir (given relative to the root of the site, e.g. "some-release" or
+    /// "some-release/3", "" for the root itself). On the main site this is
+    /// just local_prefix (the page's own prefix to that directory), on a
+    /// language site the assets of the main site are referenced, by going
+    /// up from root_prefix.
+    pub fn asset_prefix(&self, local_prefix: &str, root_prefix: &str, dir: &str) -> String {
+        match (&self.shared_assets_url, dir) {
+            (None, _) => local_prefix.to_string(),
+            (Some(_), "") => format!("{root_prefix}../"),
+            (Some(_), dir) => format!("{root_prefix}../{dir}/")
+        }
+    }
+
+    /// The url of the site on which the assets are found, for absolute
+    /// links to them (in feeds, the json api, etc.). On a language site
+    /// this is the url of the main site (see [Build::shared_assets_url]).
+    pub fn asset_url(&self) -> &SiteUrl {
+        self.shared_assets_url
+            .as_ref()
+            .unwrap_or_else(|| self.base_url_unchecked())
+    }
+
     /// By using this we signal that in the respective location in the code we
     /// expect base_url to be present (because we made sure of it). Panics if
     /// base_url is not available.
@@ -180,6 +343,8 @@ impl Build {
             .map(|path| path.to_path_buf())
             .unwrap_or_else(|| catalog_dir.join(".faircamp_cache"));
 
+        let git_scan = if args.git_scan { GitScan::new(&cache_dir, &catalog_dir) } else { None };
+
         let post_build_action = PostBuildAction::new(args);
 
         let locale = if args.debug_translations { Locale::keys() } else { Locale::default() };
@@ -191,25 +356,46 @@ impl Build {
             build_dir,
             cache_dir,
             catalog_dir,
+            check_texts: args.check_texts,
             clean_urls: !args.no_clean_urls,
+            critical_css: HashMap::new(),
             deploy_destination: args.deploy_destination.clone(),
+            embeds_only: args.embeds_only,
             embeds_requested: false,
             errors: 0,
             exclude_patterns: args.exclude_patterns.clone(),
+            extra_ignore_patterns: Vec::new(),
+            extra_size_warning: Some(EXTRA_SIZE_WARNING_DEFAULT),
+            git_scan,
+            include_drafts: args.include_drafts,
             include_patterns: args.include_patterns.clone(),
             image_processor: ImageProcessor::new(),
             ignore_errors: args.ignore_errors,
             locale,
+            manifest_texts: Vec::new(),
+            manifest_signature: 0,
+            minify: false,
             missing_image_descriptions: false,
+            notify_webhook: None,
+            pending_releases: Vec::new(),
             post_build_action,
+            preserve_image_copyright: false,
             reserved_filenames: HashSet::new(),
+            shared_assets_url: None,
             stats: Stats::new(),
+            strict: args.strict,
             theming_widget: args.theming_widget,
+            timings: Timings::new(args.timings),
+            transcode_nice: args.transcode_nice,
+            transcode_processes: args.transcode_processes.clone(),
             // Changing this can invalidate urls of already deployed faircamp
             // sites, handle with care.
             url_salt: String::from(""),
             verbose: args.verbose,
-            warnings: 0
+            warning_levels: HashMap::new(),
+            warning_messages: Vec::new(),
+            warnings: 0,
+            warnings_by_category: HashMap::new()
         }
     }
 
@@ -228,6 +414,42 @@ impl Build {
         info_stats!("Build finished in {}", elapsed_time_string);
     }
 
+    /// Lists the releases that are not (or only unlisted) published yet,
+    /// ordered by their publish date.
+    pub fn print_pending_releases(&self) {
+        if self.pending_releases.is_empty() { return; }
+
+        let mut pending_releases = self.pending_releases.clone();
+        pending_releases.sort_by_key(|(_title, date)| *date);
+
+        info!("{} release(s) pending publication:", pending_releases.len());
+
+        for (title, date) in &pending_releases {
+            info!("{date} – {title}");
+        }
+    }
+
+    /// Prints how many warnings were encountered per category (in the order
+    /// of the categories), used when the build fails due to --strict.
+    pub fn print_warnings_summary(&self) {
+        for category in WarningCategory::ALL_CATEGORIES {
+            if let Some(count) = self.warnings_by_category.get(&category) {
+                info!("{} × {}", count, category.key());
+            }
+        }
+    }
+
+    /// Every manifest read during the build is registered here, so that
+    /// cached rendered pages are invalidated whenever any manifest changes.
+    /// The hashes are combined independently of the order in which the
+    /// manifests are read.
+    pub fn register_manifest(&mut self, manifest_path: &Path, content: &str) {
+        let mut hasher = DefaultHasher::new();
+        manifest_path.hash(&mut hasher);
+        content.hash(&mut hasher);
+        self.manifest_signature = self.manifest_signature.wrapping_add(hasher.finish());
+    }
+
     /// We use this to track directory and file names we write to the build.
     /// Currently this is used solely to track files at the root directory of
     /// the build (catalog-level). Principal use for this is to detect and
@@ -238,9 +460,38 @@ impl Build {
         self.reserved_filenames.insert(filename.into())
     }
 
-    pub fn warning(&mut self, warning: &str) {
-        warn!("{}", warning);
-        self.warnings += 1;
+    /// Replaces the set of reserved filenames, returning the previous one.
+    /// Used when additional sites (standalone artist sites) are written to
+    /// their own build directories during the same build.
+    pub fn replace_reserved_filenames(&mut self, filenames: HashSet<String>) -> HashSet<String> {
+        mem::replace(&mut self.reserved_filenames, filenames)
+    }
+
+    /// Reports the warning according to the level that is configured for
+    /// its category. The category key is always printed along, so that it
+    /// can be looked up for the catalog's `warnings` option.
+    pub fn warning(&mut self, category: WarningCategory, warning: &str) {
+        let level = self.warning_levels
+            .get(&category)
+            .copied()
+            .unwrap_or(WarningLevel::Warning);
+
+        match level {
+            WarningLevel::Error => {
+                self.error(&format!("[{}] {}", category.key(), warning));
+            }
+            WarningLevel::Silence => (),
+            WarningLevel::Warning => {
+                if category.is_discouraged() {
+                    warn_discouraged!("[{}] {}", category.key(), warning);
+                } else {
+                    warn!("[{}] {}", category.key(), warning);
+                }
+                self.warning_messages.push((category, warning.to_string()));
+                self.warnings += 1;
+                *self.warnings_by_category.entry(category).or_insert(0) += 1;
+            }
+        }
     }
 }
 
@@ -274,6 +525,11 @@ impl Stats {
         self.num_extras += 1;
     }
 
+    pub fn add_generated(&self, filesize_bytes: u64) {
+        self.bytes_generated.set(self.bytes_generated.get() + filesize_bytes);
+        self.num_generated.set(self.num_generated.get() + 1);
+    }
+
     pub fn add_image(&mut self, filesize_bytes: u64) {
         self.bytes_used_images += filesize_bytes;
         self.num_images += 1;
@@ -284,14 +540,39 @@ impl Stats {
         self.num_tracks += 1;
     }
 
+    /// Label, number and total size of the written assets, per class
+    pub fn asset_classes(&self) -> [(&'static str, u32, u64); 4] {
+        [
+            ("Archives", self.num_archives, self.bytes_used_archives),
+            ("Extras", self.num_extras, self.bytes_used_extras),
+            ("Images", self.num_images, self.bytes_used_images),
+            ("Tracks", self.num_tracks, self.bytes_used_tracks)
+        ]
+    }
+
+    /// Total size of all assets written to the build directory
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_used_archives +
+        self.bytes_used_extras +
+        self.bytes_used_images +
+        self.bytes_used_tracks
+    }
+
+    /// Number and total size of the assets newly created in this build
+    pub fn generated(&self) -> (u32, u64) {
+        (self.num_generated.get(), self.bytes_generated.get())
+    }
+
     pub fn new() -> Stats {
         Stats {
+            bytes_generated: Cell::new(0),
             bytes_used_archives: 0,
             bytes_used_extras: 0,
             bytes_used_images: 0,
             bytes_used_tracks: 0,
             num_archives: 0,
             num_extras: 0,
+            num_generated: Cell::new(0),
             num_images: 0,
             num_tracks: 0
         }
@@ -311,3 +592,88 @@ impl Stats {
         )
     }
 }
+
+impl WarningCategory {
+    pub const ALL_CATEGORIES: [WarningCategory; 17] = [
+        WarningCategory::DeadLink,
+        WarningCategory::DeprecatedOption,
+        WarningCategory::DuplicateLayoutSection,
+        WarningCategory::IgnoredDirectory,
+        WarningCategory::IgnoredFile,
+        WarningCategory::LossyToLossless,
+        WarningCategory::MissingBaseUrl,
+        WarningCategory::MissingImageDescription,
+        WarningCategory::MissingPaymentInfo,
+        WarningCategory::MissingPermalink,
+        WarningCategory::NotAtDomainRoot,
+        WarningCategory::OversizedExtra,
+        WarningCategory::OversizedOriginal,
+        WarningCategory::PermalinkConflict,
+        WarningCategory::StreamingLimit,
+        WarningCategory::TextIssue,
+        WarningCategory::UndersizedCover
+    ];
+
+    pub fn from_manifest_key(key: &str) -> Option<WarningCategory> {
+        WarningCategory::ALL_CATEGORIES
+            .into_iter()
+            .find(|category| category.key() == key)
+    }
+
+    /// Discouraged practices are not problems per se, they are therefore
+    /// reported with a [DISCOURAGED] instead of a [WARNING] label.
+    pub fn is_discouraged(&self) -> bool {
+        match self {
+            WarningCategory::LossyToLossless |
+            WarningCategory::MissingImageDescription |
+            WarningCategory::UndersizedCover => true,
+            WarningCategory::DeadLink |
+            WarningCategory::DeprecatedOption |
+            WarningCategory::DuplicateLayoutSection |
+            WarningCategory::IgnoredDirectory |
+            WarningCategory::IgnoredFile |
+            WarningCategory::MissingBaseUrl |
+            WarningCategory::MissingPaymentInfo |
+            WarningCategory::MissingPermalink |
+            WarningCategory::NotAtDomainRoot |
+            WarningCategory::OversizedExtra |
+            WarningCategory::OversizedOriginal |
+            WarningCategory::PermalinkConflict |
+            WarningCategory::StreamingLimit |
+            WarningCategory::TextIssue => false
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            WarningCategory::DeadLink => "dead_link",
+            WarningCategory::DeprecatedOption => "deprecated_option",
+            WarningCategory::DuplicateLayoutSection => "duplicate_layout_section",
+            WarningCategory::IgnoredDirectory => "ignored_directory",
+            WarningCategory::IgnoredFile => "ignored_file",
+            WarningCategory::LossyToLossless => "lossy_to_lossless",
+            WarningCategory::MissingBaseUrl => "missing_base_url",
+            WarningCategory::MissingImageDescription => "missing_image_description",
+            WarningCategory::MissingPaymentInfo => "missing_payment_info",
+            WarningCategory::MissingPermalink => "missing_permalink",
+            WarningCategory::NotAtDomainRoot => "not_at_domain_root",
+            WarningCategory::OversizedExtra => "oversized_extra",
+            WarningCategory::OversizedOriginal => "oversized_original",
+            WarningCategory::PermalinkConflict => "permalink_conflict",
+            WarningCategory::StreamingLimit => "streaming_limit",
+            WarningCategory::TextIssue => "text_issue",
+            WarningCategory::UndersizedCover => "undersized_cover"
+        }
+    }
+}
+
+impl WarningLevel {
+    pub fn from_manifest_key(key: &str) -> Option<WarningLevel> {
+        match key {
+            "error" => Some(WarningLevel::Error),
+            "silence" => Some(WarningLevel::Silence),
+            "warning" => Some(WarningLevel::Warning),
+            _ => None
+        }
+    }
+}
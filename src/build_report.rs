@@ -0,0 +1,244 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Through --build-report, a summary of the build is written to an internal
+//! (never deployed) html file in the catalog directory after each build:
+//! Warnings, releases that are new or changed since the last build, the
+//! sizes of the written assets, how much was reused from the cache and how
+//! long the build took. This is easier to skim than the terminal output,
+//! and can be kept as an artifact of CI builds.
+//!
+//! To tell which releases changed, a fingerprint of each release (its title,
+//! artists, cover and tracks, including the size and modification time of
+//! their source files) is recorded in the cache directory for comparison
+//! with the next build.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+
+use chrono::Utc;
+use indoc::formatdoc;
+
+use crate::{Build, Catalog, GENERATOR_INFO, Release};
+use crate::util::{format_bytes, html_escape_outside_attribute};
+
+/// Stored in the cache directory, but not subject to cache maintenance.
+pub const BUILD_REPORT_CACHE_FILENAME: &str = "build_report.cache";
+
+pub const BUILD_REPORT_FILENAME: &str = ".faircamp_build_report.html";
+
+/// Computes a hash over everything that visibly makes up a release on the
+/// site. This does not cover every last manifest option, but catches the
+/// changes one usually wants to double-check before deploying.
+fn fingerprint(release: &Release) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    release.title.hash(&mut hasher);
+
+    for artist in &release.main_artists {
+        artist.borrow().name.hash(&mut hasher);
+    }
+
+    if let Some(described_image) = &release.cover {
+        described_image.file_meta.hash(&mut hasher);
+    }
+
+    for track in &release.tracks {
+        track.title().hash(&mut hasher);
+        track.transcodes.file_meta.hash(&mut hasher);
+    }
+
+    hasher.finish()
+}
+
+pub fn write(build: &Build, catalog: &Catalog) {
+    let cache_path = build.cache_dir.join(BUILD_REPORT_CACHE_FILENAME);
+
+    let previous_fingerprints: Option<HashMap<String, u64>> = fs::read(&cache_path)
+        .ok()
+        .and_then(|bytes| bincode::deserialize(&bytes).ok());
+
+    let mut fingerprints = HashMap::new();
+    let mut r_changed_releases = Vec::new();
+
+    for release in &catalog.releases {
+        let release_ref = release.borrow();
+        let slug = release_ref.permalink.slug.clone();
+        let fingerprint = fingerprint(&release_ref);
+
+        if let Some(previous_fingerprints) = &previous_fingerprints {
+            let status = match previous_fingerprints.get(&slug) {
+                Some(previous_fingerprint) if *previous_fingerprint == fingerprint => None,
+                Some(_) => Some("changed"),
+                None => Some("new")
+            };
+
+            if let Some(status) = status {
+                r_changed_releases.push(format!(
+                    "<li>{title} <span class=\"{status}\">({status})</span></li>",
+                    title = html_escape_outside_attribute(&release_ref.title)
+                ));
+            }
+        }
+
+        fingerprints.insert(slug, fingerprint);
+    }
+
+    if let Some(previous_fingerprints) = &previous_fingerprints {
+        let mut removed_slugs = previous_fingerprints
+            .keys()
+            .filter(|slug| !fingerprints.contains_key(*slug))
+            .collect::<Vec<&String>>();
+
+        removed_slugs.sort();
+
+        for slug in removed_slugs {
+            r_changed_releases.push(format!(
+                "<li>{slug} <span class=\"removed\">(removed)</span></li>",
+                slug = html_escape_outside_attribute(slug)
+            ));
+        }
+    }
+
+    let r_changes = match previous_fingerprints {
+        Some(_) if r_changed_releases.is_empty() => String::from("<p>No releases were added, changed or removed since the last build.</p>"),
+        Some(_) => format!("<ul>{}</ul>", r_changed_releases.join("")),
+        None => String::from("<p>There is no record of a previous build to compare against yet.</p>")
+    };
+
+    let r_warnings = if build.warning_messages.is_empty() {
+        String::from("<p>No warnings were encountered.</p>")
+    } else {
+        let r_items = build.warning_messages
+            .iter()
+            .map(|(category, message)| {
+                format!(
+                    "<li><code>{key}</code> {message}</li>",
+                    key = category.key(),
+                    message = html_escape_outside_attribute(message)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("");
+
+        format!("<ul>{r_items}</ul>")
+    };
+
+    let r_assets = build.stats.asset_classes()
+        .into_iter()
+        .map(|(label, count, bytes)| {
+            formatdoc!(r#"
+                <tr>
+                    <td>{label}</td>
+                    <td>{count}</td>
+                    <td>{size}</td>
+                </tr>
+            "#,
+                size = format_bytes(bytes)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("");
+
+    let (num_generated, bytes_generated) = build.stats.generated();
+    let bytes_written = build.stats.bytes_written();
+    let r_cache = formatdoc!(r#"
+        <p>
+            {num_generated} assets ({generated}) were newly transcoded, resized
+            or zipped in this build, everything else that was written to the
+            build directory ({reused} of {written}) was reused from the cache.
+        </p>
+    "#,
+        generated = format_bytes(bytes_generated),
+        reused = format_bytes(bytes_written.saturating_sub(bytes_generated)),
+        written = format_bytes(bytes_written)
+    );
+
+    let elapsed_seconds = Utc::now()
+        .signed_duration_since(build.build_begin)
+        .num_milliseconds() as f64 / 1000.0;
+
+    let r_phases = build.timings.phases()
+        .iter()
+        .map(|(phase, duration)| {
+            format!(
+                "<tr><td>{phase}</td><td>{seconds:.3}s</td></tr>",
+                phase = html_escape_outside_attribute(phase),
+                seconds = duration.as_secs_f64()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("");
+
+    let r_timing = if r_phases.is_empty() {
+        format!("<p>The build took {elapsed_seconds:.3}s (run with --timings for a breakdown by phase).</p>")
+    } else {
+        formatdoc!(r#"
+            <p>The build took {elapsed_seconds:.3}s (phases can overlap).</p>
+            <table>
+                {r_phases}
+            </table>
+        "#)
+    };
+
+    let html = formatdoc!(r#"
+        <!DOCTYPE html>
+        <html>
+            <head>
+                <meta charset="utf-8">
+                <title>Build report</title>
+                <style>
+                    body {{ font-family: sans-serif; margin: 2rem; }}
+                    table {{ border-collapse: collapse; }}
+                    td, th {{ border: 1px solid #ccc; padding: .25rem .5rem; text-align: left; }}
+                    .changed {{ color: #a60; }}
+                    .new {{ color: #070; }}
+                    .removed {{ color: #a00; }}
+                </style>
+            </head>
+            <body>
+                <h1>Build report</h1>
+                <p>
+                    Built on {date} by {generator}, with {errors} errors and {warnings} warnings.
+                </p>
+                <h2>Warnings</h2>
+                {r_warnings}
+                <h2>New and changed releases</h2>
+                {r_changes}
+                <h2>Assets</h2>
+                <table>
+                    <tr>
+                        <th>Class</th>
+                        <th>Count</th>
+                        <th>Size</th>
+                    </tr>
+                    {r_assets}
+                </table>
+                <h2>Cache</h2>
+                {r_cache}
+                <h2>Timing</h2>
+                {r_timing}
+            </body>
+        </html>
+    "#,
+        date = build.build_begin.format("%Y-%m-%d %H:%M:%S UTC"),
+        errors = build.errors,
+        generator = GENERATOR_INFO,
+        warnings = build.warnings
+    );
+
+    let path = build.catalog_dir.join(BUILD_REPORT_FILENAME);
+
+    match fs::write(&path, html) {
+        Ok(()) => info_stats!("Build report was written to {}", path.display()),
+        Err(err) => error!("Build report could not be written to {} ({})", path.display(), err)
+    }
+
+    let serialized = bincode::serialize(&fingerprints).unwrap();
+
+    if fs::create_dir_all(&build.cache_dir).is_ok() {
+        let _ = fs::write(&cache_path, serialized);
+    }
+}
@@ -0,0 +1,1030 @@
+// SPDX-FileCopyrightText: 2021-2025 Simon Repp
+// SPDX-FileCopyrightText: 2025 Sandro Santilli
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! The catalog-scan → build pipeline, exposed through the [Builder] so that
+//! it can be driven both by the faircamp command-line interface and
+//! programmatically by other tools (e.g. graphical frontends).
+
+use std::collections::HashSet;
+use std::fs;
+use std::mem;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use clap::Parser;
+use indoc::formatdoc;
+use serde_derive::Serialize;
+
+use crate::{
+    Args,
+    ArtistRc,
+    AssetHashes,
+    Build,
+    Cache,
+    CacheOptimization,
+    Catalog,
+    Feeds,
+    JSON_API_ARTIST_FILENAME,
+    JSON_API_CATALOG_FILENAME,
+    LanguageMirror,
+    Locale,
+    M3U_PLAYLIST_FILENAME,
+    PostBuildAction,
+    SiteUrl,
+    TranscodeProcesses,
+    WarningCategory,
+    WebhookEvent,
+    XSPF_PLAYLIST_FILENAME
+};
+use crate::{
+    asset_manifest,
+    bagit,
+    build_report,
+    dashboard,
+    debug,
+    deploy,
+    fulfillment,
+    gemtext,
+    json_api,
+    link_check,
+    loudness,
+    m3u,
+    render,
+    rendered_pages,
+    scripts,
+    server,
+    site_metadata,
+    styles,
+    util,
+    webhook,
+    xspf
+};
+use crate::render::{ArtistContext, CatalogContext, PlaylistContext};
+use crate::render::latest_release_widget::{LATEST_RELEASE_WIDGET_HTML_FILENAME, LATEST_RELEASE_WIDGET_JS_FILENAME};
+use crate::render::range_check::RANGE_CHECK_HTML_FILENAME;
+
+/// Configures and runs a build.
+///
+/// ```no_run
+/// use faircamp_core::{BuildStage, Builder};
+///
+/// let result = Builder::new()
+///     .catalog_dir("/home/alice/music/catalog")
+///     .progress(|stage: BuildStage| println!("{stage:?}"))
+///     .build();
+///
+/// match result {
+///     Ok(report) => println!("Built to {}", report.build_dir.display()),
+///     Err(failure) => eprintln!("{}", failure.reason)
+/// }
+/// ```
+pub struct Builder {
+    args: Args,
+    progress: Option<Box<dyn FnMut(BuildStage)>>
+}
+
+/// Returned by [Builder::build] when it failed
+#[derive(Debug)]
+pub struct BuildFailure {
+    pub reason: String,
+    /// What was built (and reported) up to the point of failure
+    pub report: BuildReport
+}
+
+/// Returned by [Builder::build] when it did not fail
+#[derive(Debug)]
+pub struct BuildReport {
+    pub build_dir: PathBuf,
+    /// Number of errors encountered (only non-zero with ignore_errors)
+    pub errors: usize,
+    /// Category key and message of each warning, in the order they were
+    /// reported
+    pub warning_messages: Vec<(&'static str, String)>,
+    pub warnings: usize
+}
+
+/// Reported through the progress callback when a build enters a new stage
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildStage {
+    ScanningCatalog,
+    WritingAssets,
+    Rendering,
+    MaintainingCache,
+    Deploying
+}
+
+/// Returned by [Builder::scan]
+#[derive(Debug, Serialize)]
+pub struct CatalogSummary {
+    pub releases: Vec<ReleaseSummary>,
+    pub title: String
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReleaseSummary {
+    /// Names of the main artists
+    pub artists: Vec<String>,
+    pub permalink: String,
+    pub title: String,
+    pub tracks: usize,
+    pub unlisted: bool
+}
+
+impl Builder {
+    /// Runs the build (and, if configured through the args, the cache
+    /// operations, debug output, deploy or preview that go along with it).
+    /// All messages are printed as the build progresses, on failure a short
+    /// summary of the reason is returned in addition to the report.
+    pub fn build(mut self) -> Result<BuildReport, BuildFailure> {
+        let args = &self.args;
+        let mut build = Build::new(args);
+
+        if !build.catalog_dir.is_dir() {
+            error!("Configured catalog directory does not exist - aborting build");
+            return Err(BuildFailure::new(&build, "The catalog directory does not exist"));
+        }
+
+        info!("You can safely terminate faircamp at any point (using Ctrl+C) - all progress is continuously saved and new builds always continue where the previous build left off.");
+
+        let mut cache = Cache::retrieve(&build);
+
+        if args.analyze_cache {
+            cache.report_stale();
+            return Ok(BuildReport::new(&build));
+        }
+
+        if args.optimize_cache {
+            cache.optimization = CacheOptimization::Immediate;
+            cache.maintain(&build);
+            return Ok(BuildReport::new(&build));
+        }
+
+        if args.wipe_all || args.wipe_build || args.wipe_cache {
+            if args.wipe_build || args.wipe_all {
+                info!("The build directory was wiped, as requested");
+                let _ = fs::remove_dir_all(&build.build_dir);
+            }
+            if args.wipe_cache || args.wipe_all {
+                info_cache!("The cache directory was wiped, as requested");
+                let _ = fs::remove_dir_all(&build.cache_dir);
+            }
+            info!("No further actions are performed due to requested wipe operation(s)");
+            return Ok(BuildReport::new(&build));
+        }
+
+        let build_report = args.build_report;
+        let check_links = args.check_links;
+        let debug = args.debug;
+        let export_bag = args.export_bag.clone();
+        let fulfillment_sheets = args.fulfillment_sheets;
+        let loudness_report = args.loudness_report;
+
+        cache.mark_all_stale(&build.build_begin);
+
+        self.report_progress(BuildStage::ScanningCatalog);
+
+        let scan_begin = Instant::now();
+
+        let mut catalog = match Catalog::read(&mut build, &mut cache) {
+            Ok(catalog) => catalog,
+            Err(()) => {
+                let reason = format!("The build was aborted because {} errors were encountered while reading the catalog", build.errors);
+                webhook::notify(&build, None, WebhookEvent::Build, Some(reason.as_str()));
+                return Err(BuildFailure::new(&build, reason));
+            }
+        };
+
+        build.timings.record("Scanning the catalog", scan_begin);
+
+        if let Some(git_scan) = &mut build.git_scan {
+            git_scan.write(&build.cache_dir);
+            info_stats!("{} unchanged directories were listed from the record of the last build (--git-scan)", git_scan.reused);
+        }
+
+        if check_links {
+            link_check::check_links(&mut build, &catalog);
+        }
+
+        if fulfillment_sheets {
+            fulfillment::write_sheets(&build, &catalog);
+        }
+
+        if loudness_report {
+            loudness::write_report(&build, &catalog);
+        }
+
+        if let Some(dir) = &export_bag {
+            if bagit::export(&build, &catalog, dir) == ExitCode::FAILURE {
+                return Err(BuildFailure::new(&build, "The catalog could not be exported as a BagIt bag"));
+            }
+            return Ok(BuildReport::new(&build));
+        }
+
+        if debug {
+            debug::debug_catalog(&catalog);
+            return Ok(BuildReport::new(&build));
+        }
+
+        if build.embeds_only {
+            if build.base_url.is_none() {
+                error!("Embeds can only be built when the catalog's base_url is set (e.g. 'base_url: https://example.com/music/') - aborting build");
+                let reason = "An embeds-only build requires the catalog's base_url to be set";
+                webhook::notify(&build, Some(&catalog), WebhookEvent::Build, Some(reason));
+                return Err(BuildFailure::new(&build, reason));
+            }
+
+            if !build.embeds_requested {
+                error!("An embeds-only build was requested, but embedding is not enabled for any release or track (e.g. through 'embedding: enabled' in the catalog manifest) - aborting build");
+                let reason = "An embeds-only build requires embedding to be enabled for at least one release or track";
+                webhook::notify(&build, Some(&catalog), WebhookEvent::Build, Some(reason));
+                return Err(BuildFailure::new(&build, reason));
+            }
+        }
+
+        util::ensure_empty_dir(&build.build_dir);
+
+        self.report_progress(BuildStage::WritingAssets);
+
+        // Generation of scripts depends on final image assets and paths being
+        // available, hence the assets (audio and image files) are the first
+        // thing we compute.
+        let assets_begin = Instant::now();
+        catalog.write_assets(&mut build, &mut cache);
+        build.timings.record("Writing assets", assets_begin);
+
+        self.report_progress(BuildStage::Rendering);
+
+        let rendering_begin = Instant::now();
+
+        // Rendering of the actual pages (html) depends on assets hashes
+        // (for css/favicon/js assets) being available, hence these are the
+        // second thing we compute.
+        scripts::generate(&mut build, &catalog);
+        styles::generate(&mut build, &catalog);
+        catalog.favicon.write(&mut build);
+
+        if build.embeds_only {
+            // Render the embeddable players and the index page listing them
+            let base_url = build.base_url_unchecked();
+
+            for release in &catalog.releases {
+                release.borrow().write_embed_pages(base_url, &build, &catalog);
+            }
+
+            let embed_index_html = render::embed_index::embed_index_html(base_url, &build, &catalog);
+            fs::write(build.build_dir.join("index.html"), embed_index_html).unwrap();
+            build.reserve_filename("index.html");
+
+            for release in &catalog.releases {
+                build.reserve_filename(release.borrow().permalink.slug.clone());
+            }
+        } else {
+            write_site(&mut build, &mut cache, &catalog);
+
+            if !catalog.languages.is_empty() {
+                write_language_sites(&mut build, &mut cache, &mut catalog);
+            }
+        }
+
+        if catalog.asset_manifest {
+            build.reserve_filename(asset_manifest::ASSET_MANIFEST_FILENAME);
+        }
+
+        // Must be the last step because we need to check for collisions against
+        // everything we wrote to the build directory ourselves beforehand.
+        if let Err(collisions) = catalog.write_user_assets(&mut build) {
+            let collisions_joined = collisions
+                .iter()
+                .map(|filename| format!("'{filename}'"))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            let message = formatdoc!(r#"
+                One or more filenames of your custom site assets collide with filenames already used by faircamp itself: {collisions_joined}
+                Please rename the respective site assets, making sure to update all references pointing to it (both in site_metadata and in your own files, if applies).
+            "#);
+
+            error!("{}", message);
+            webhook::notify(&build, Some(&catalog), WebhookEvent::Build, Some(message.as_str()));
+            return Err(BuildFailure::new(&build, message));
+        }
+
+        if catalog.label_mode && !build.embeds_only {
+            write_standalone_sites(&mut build, &mut cache, &mut catalog);
+        }
+
+        // Lists everything in the build directory, therefore this can only
+        // happen once the user's site assets are written too.
+        if catalog.asset_manifest {
+            asset_manifest::write(&mut build, &catalog);
+        }
+
+        if build.base_url.is_none() {
+            let mut not_generated = Vec::new();
+
+            if build.embeds_requested { not_generated.push("Embeds"); }
+            if catalog.opengraph { not_generated.push("Open Graph meta tags"); }
+            if catalog.feeds.any_requested() { not_generated.push("Feeds"); }
+            if catalog.json_api { not_generated.push("JSON API"); }
+            if catalog.latest_release_widget { not_generated.push("Latest release widget"); }
+            if catalog.m3u ||
+                catalog.artists.iter().any(|artist| artist.borrow().m3u) ||
+                catalog.releases.iter().any(|release| release.borrow().m3u) {
+                not_generated.push("M3U playlists");
+            }
+
+            if !not_generated.is_empty() {
+                let r_not_generated = not_generated.join(", ");
+                let warning = format!("No catalog.base_url specified, therefore the following could not be generated: {}", r_not_generated);
+                build.warning(WarningCategory::MissingBaseUrl, &warning);
+            }
+        }
+
+        build.timings.record("Rendering", rendering_begin);
+
+        self.report_progress(BuildStage::MaintainingCache);
+
+        cache.maintain(&build);
+
+        dashboard::write(&build, &catalog);
+
+        build.print_pending_releases();
+        build.print_stats();
+
+        if build_report {
+            build_report::write(&build, &catalog);
+        }
+
+        // With deploy, the timings are reported after deploying (so that the
+        // deploy can be included), with preview the timings need to be reported
+        // before the preview server starts blocking.
+        if !matches!(build.post_build_action, PostBuildAction::Deploy) {
+            build.timings.print();
+            build.timings.write(&build.catalog_dir);
+        }
+
+        // Errors that occur after reading the catalog (e.g. warnings that are
+        // escalated to errors through the catalog's warnings option) don't abort
+        // the build itself, but they make it fail before deploying/previewing.
+        if build.errors > 0 && !build.ignore_errors {
+            info!("The build failed because {} errors were encountered, therefore no deploy or preview was performed.", build.errors);
+            info!("You can run faircamp with --ignore-errors if you want to deploy or preview in spite of errors.");
+            let reason = format!("The build failed because {} errors were encountered", build.errors);
+            webhook::notify(&build, Some(&catalog), WebhookEvent::Build, Some(reason.as_str()));
+            return Err(BuildFailure::new(&build, reason));
+        }
+
+        if build.strict && build.warnings > 0 {
+            info!("The build failed because {} warnings were encountered in strict mode, therefore no deploy or preview was performed. Warnings by category:", build.warnings);
+            build.print_warnings_summary();
+            let reason = format!("The build failed because {} warnings were encountered in strict mode", build.warnings);
+            webhook::notify(&build, Some(&catalog), WebhookEvent::Build, Some(reason.as_str()));
+            return Err(BuildFailure::new(&build, reason));
+        }
+
+        webhook::notify(&build, Some(&catalog), WebhookEvent::Build, None);
+
+        match build.post_build_action {
+            PostBuildAction::None => (),
+            PostBuildAction::Deploy => {
+                if build.theming_widget {
+                    // TODO: But maybe someone *wants* to deploy it to a "live" page, e.g. to ask their bandmates for their color preferences? Follow up again :)
+                    error!("Aborting deploy because --theming-widget is enabled, we probably don't want that on the live page.");
+                    return Err(BuildFailure::new(&build, "The deploy was aborted because the theming widget is enabled"));
+                } else {
+                    self.report_progress(BuildStage::Deploying);
+                    let deploy_result = build.timings.measure("Deploying", || deploy::deploy(&build));
+
+                    if let Err(err) = &deploy_result {
+                        error!("{}", err);
+                    }
+
+                    webhook::notify(&build, Some(&catalog), WebhookEvent::Deploy, deploy_result.as_ref().err().map(|err| err.as_str()));
+
+                    build.timings.print();
+                    build.timings.write(&build.catalog_dir);
+
+                    if let Err(err) = deploy_result {
+                        return Err(BuildFailure::new(&build, err));
+                    }
+                }
+            }
+            PostBuildAction::Preview { ip, port } => {
+                if build.clean_urls || build.theming_widget {
+                    // Here we serve the preview through an actual http server. In
+                    // the case of clean urls, so that /foo/ gets resolved
+                    // to /foo/index.html. In the case of the theming widget,
+                    // because it can only retain its localStorage state across
+                    // pages if the origin (in this case http://localhost:xxxx/) is
+                    // stable (and not file://...).
+                    server::serve_preview(&build.build_dir, &build.cache_dir, ip, port);
+                } else {
+                    // We don't need an actively running server to preview a build
+                    // without clean urls, we can just open everything directly in
+                    // a browser.
+                    let local_file_url = build.build_dir.join("index.html");
+                    if webbrowser::open(&local_file_url.to_string_lossy()).is_err() {
+                        error!("Could not open browser for previewing the site");
+                        return Err(BuildFailure::new(&build, "The browser could not be opened for previewing the site"));
+                    }
+                }
+            }
+        }
+
+        Ok(BuildReport::new(&build))
+    }
+
+    /// Overrides the build directory (default is .faircamp_build/ inside
+    /// the catalog directory)
+    pub fn build_dir(mut self, build_dir: impl Into<PathBuf>) -> Builder {
+        self.args.build_dir = Some(build_dir.into());
+        self
+    }
+
+    /// Overrides the cache directory (default is .faircamp_cache/ inside
+    /// the catalog directory)
+    pub fn cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Builder {
+        self.args.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    /// Overrides the catalog directory (default is the current working
+    /// directory)
+    pub fn catalog_dir(mut self, catalog_dir: impl Into<PathBuf>) -> Builder {
+        self.args.catalog_dir = Some(catalog_dir.into());
+        self
+    }
+
+    /// Whether to generate clean urls (default is true)
+    pub fn clean_urls(mut self, clean_urls: bool) -> Builder {
+        self.args.no_clean_urls = !clean_urls;
+        self
+    }
+
+    /// Excludes all files and directories whose path contains the pattern
+    pub fn exclude_pattern(mut self, pattern: impl Into<String>) -> Builder {
+        self.args.exclude_patterns.push(pattern.into());
+        self
+    }
+
+    /// For full control, a builder can be configured with all options
+    /// available on the command line.
+    pub fn from_args(args: Args) -> Builder {
+        Builder {
+            args,
+            progress: None
+        }
+    }
+
+    /// Whether to continue building in spite of errors (default is false)
+    pub fn ignore_errors(mut self, ignore_errors: bool) -> Builder {
+        self.args.ignore_errors = ignore_errors;
+        self
+    }
+
+    /// Only includes files and directories whose path contains the pattern
+    pub fn include_pattern(mut self, pattern: impl Into<String>) -> Builder {
+        self.args.include_patterns.push(pattern.into());
+        self
+    }
+
+    /// Configured with the same defaults that apply when faircamp is
+    /// invoked without any arguments.
+    pub fn new() -> Builder {
+        Builder::from_args(Args::parse_from(["faircamp"]))
+    }
+
+    /// The callback is invoked each time the build enters a new stage
+    pub fn progress(mut self, callback: impl FnMut(BuildStage) + 'static) -> Builder {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Only scans the catalog (nothing is written to the build directory)
+    /// and summarizes what it contains.
+    pub fn scan(self) -> Result<CatalogSummary, BuildFailure> {
+        let mut build = Build::new(&self.args);
+
+        if !build.catalog_dir.is_dir() {
+            error!("Configured catalog directory does not exist - aborting scan");
+            return Err(BuildFailure::new(&build, "The catalog directory does not exist"));
+        }
+
+        let mut cache = Cache::retrieve(&build);
+
+        cache.mark_all_stale(&build.build_begin);
+
+        let catalog = match Catalog::read(&mut build, &mut cache) {
+            Ok(catalog) => catalog,
+            Err(()) => return Err(BuildFailure::new(&build, format!("The scan was aborted because {} errors were encountered while reading the catalog", build.errors)))
+        };
+
+        let releases = catalog.releases
+            .iter()
+            .map(|release| {
+                let release_ref = release.borrow();
+
+                ReleaseSummary {
+                    artists: release_ref.main_artists
+                        .iter()
+                        .map(|artist| artist.borrow().name.clone())
+                        .collect(),
+                    permalink: release_ref.permalink.slug.clone(),
+                    title: release_ref.title.clone(),
+                    tracks: release_ref.tracks.len(),
+                    unlisted: release_ref.unlisted
+                }
+            })
+            .collect();
+
+        Ok(CatalogSummary {
+            releases,
+            title: catalog.title()
+        })
+    }
+
+    /// Whether warnings make the build fail (default is false)
+    pub fn strict(mut self, strict: bool) -> Builder {
+        self.args.strict = strict;
+        self
+    }
+
+    /// Runs ffmpeg with the given niceness (0-19, default is to run it with
+    /// normal priority). Has no effect on Windows.
+    pub fn transcode_nice(mut self, nice: u8) -> Builder {
+        self.args.transcode_nice = Some(nice.min(19));
+        self
+    }
+
+    /// How many ffmpeg processes transcode audio at the same time, for all
+    /// formats or a single one (e.g. "flac=8".parse()). Can be called
+    /// multiple times, the default is the number of CPUs.
+    pub fn transcode_processes(mut self, processes: TranscodeProcesses) -> Builder {
+        self.args.transcode_processes.push(processes);
+        self
+    }
+
+    fn report_progress(&mut self, stage: BuildStage) {
+        if let Some(callback) = &mut self.progress {
+            callback(stage);
+        }
+    }
+}
+
+impl BuildFailure {
+    fn new(build: &Build, reason: impl Into<String>) -> BuildFailure {
+        BuildFailure {
+            reason: reason.into(),
+            report: BuildReport::new(build)
+        }
+    }
+}
+
+impl BuildReport {
+    fn new(build: &Build) -> BuildReport {
+        let warning_messages = build.warning_messages
+            .iter()
+            .map(|(category, message)| (category.key(), message.clone()))
+            .collect();
+
+        BuildReport {
+            build_dir: build.build_dir.clone(),
+            errors: build.errors,
+            warning_messages,
+            warnings: build.warnings
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Builder {
+        Builder::new()
+    }
+}
+
+/// Renders all pages (and the files that go along with them, like feeds and
+/// playlists) of the regular site, i.e. everything except for embeds-only
+/// builds.
+fn write_site(build: &mut Build, cache: &mut Cache, catalog: &Catalog) {
+    if build.base_url.is_some() {
+        // Render json api document for the catalog
+        if catalog.json_api {
+            let r_json = json_api::generate_for_catalog(build.base_url_unchecked(), build, catalog);
+            fs::write(build.build_dir.join(JSON_API_CATALOG_FILENAME), r_json).unwrap();
+            build.reserve_filename(JSON_API_CATALOG_FILENAME);
+        }
+
+        // Render M3U playlist
+        if catalog.m3u {
+            let r_m3u = m3u::generate_for_catalog(build, catalog);
+            fs::write(build.build_dir.join(M3U_PLAYLIST_FILENAME), r_m3u).unwrap();
+            build.reserve_filename(M3U_PLAYLIST_FILENAME);
+        }
+
+        if catalog.feeds.any_requested() {
+            // Render feed (xml) files (Atom, Generic RSS, Media RSS, Podcast RSS, as enabled)
+            catalog.feeds.generate(build, catalog);
+
+            // Render subscription choices page
+            let subscribe_permalink = catalog.subscribe_permalink.as_ref().unwrap();
+            let subscribe_dir = build.build_dir.join(subscribe_permalink);
+            util::ensure_dir_all(&subscribe_dir);
+            let subscribe_html = render::subscribe::subscribe_html(&CatalogContext::new(build, catalog));
+            fs::write(subscribe_dir.join("index.html"), subscribe_html).unwrap();
+            build.reserve_filename(subscribe_permalink);
+        }
+
+        // Render widget for presenting the latest release on external sites
+        if catalog.latest_release_widget {
+            if let Some(release) = catalog.latest_release() {
+                let base_url = build.base_url_unchecked();
+                let widget_html = render::latest_release_widget::latest_release_widget_html(base_url, build, &release.borrow());
+                let widget_js = render::latest_release_widget::latest_release_widget_js(base_url, build, catalog);
+
+                fs::write(build.build_dir.join(LATEST_RELEASE_WIDGET_HTML_FILENAME), widget_html).unwrap();
+                build.reserve_filename(LATEST_RELEASE_WIDGET_HTML_FILENAME);
+
+                fs::write(build.build_dir.join(LATEST_RELEASE_WIDGET_JS_FILENAME), widget_js).unwrap();
+                build.reserve_filename(LATEST_RELEASE_WIDGET_JS_FILENAME);
+            }
+        }
+    }
+
+    // Render homepage (page for all releases)
+    let index_html = render::index::index_html(&CatalogContext::new(build, catalog));
+    fs::write(build.build_dir.join("index.html"), &index_html).unwrap();
+    build.reserve_filename("index.html");
+
+    // Render further homepage pages (when the releases are paginated)
+    if let Some(pagination_permalink) = &catalog.pagination_permalink {
+        for page in 2..=catalog.home_page_count() {
+            let page_dir = build.build_dir.join(pagination_permalink).join(page.to_string());
+            util::ensure_dir_all(&page_dir);
+            let page_html = render::index::index_page_html(&CatalogContext::new(build, catalog), page);
+            fs::write(page_dir.join("index.html"), page_html).unwrap();
+        }
+
+        build.reserve_filename(pagination_permalink);
+    }
+
+    // Render alternative index views (when enabled)
+    if let Some(list_permalink) = &catalog.list_permalink {
+        let list_dir = build.build_dir.join(list_permalink);
+        util::ensure_dir_all(&list_dir);
+        let list_html = render::index_list::index_list_html(&CatalogContext::new(build, catalog));
+        fs::write(list_dir.join("index.html"), list_html).unwrap();
+        build.reserve_filename(list_permalink);
+    }
+
+    if let Some(timeline_permalink) = &catalog.timeline_permalink {
+        let timeline_dir = build.build_dir.join(timeline_permalink);
+        util::ensure_dir_all(&timeline_dir);
+        let timeline_html = render::index_timeline::index_timeline_html(&CatalogContext::new(build, catalog));
+        fs::write(timeline_dir.join("index.html"), timeline_html).unwrap();
+        build.reserve_filename(timeline_permalink);
+    }
+
+    // Render filtered index pages for release imprints and regions (when enabled)
+    if let Some(filter_permalink) = &catalog.filter_permalink {
+        for filter in &catalog.filters {
+            let filter_dir = build.build_dir.join(filter.path(filter_permalink));
+            util::ensure_dir_all(&filter_dir);
+            let filter_html = render::index_filter::index_filter_html(&CatalogContext::new(build, catalog), filter);
+            fs::write(filter_dir.join("index.html"), filter_html).unwrap();
+        }
+
+        build.reserve_filename(filter_permalink);
+    }
+
+    // Render archive pages for each year/decade in which releases were
+    // published, optionally with their own feeds (when enabled)
+    for page in &catalog.year_archive_pages {
+        let page_dir = build.build_dir.join(&page.slug);
+        util::ensure_dir_all(&page_dir);
+        let page_html = render::index_year_archive::index_year_archive_html(&CatalogContext::new(build, catalog), page);
+        fs::write(page_dir.join("index.html"), page_html).unwrap();
+
+        if catalog.year_archives.as_ref().is_some_and(|year_archives| year_archives.feeds) &&
+            build.base_url.is_some() {
+            Feeds::generate_year_archive(build, catalog, page);
+        }
+
+        build.reserve_filename(&page.slug);
+    }
+
+    // Render page that sends visitors to a random track (when enabled)
+    if let Some(discover_permalink) = &catalog.discover_permalink {
+        let discover_dir = build.build_dir.join(discover_permalink);
+        util::ensure_dir_all(&discover_dir);
+        let discover_html = render::discover::discover_html(&CatalogContext::new(build, catalog));
+        fs::write(discover_dir.join("index.html"), discover_html).unwrap();
+        build.reserve_filename(discover_permalink);
+    }
+
+    // Render page for searching the catalog (when enabled)
+    if let Some(search_permalink) = &catalog.search_permalink {
+        let search_dir = build.build_dir.join(search_permalink);
+        util::ensure_dir_all(&search_dir);
+        let search_html = render::search::search_html(&CatalogContext::new(build, catalog));
+        fs::write(search_dir.join("index.html"), search_html).unwrap();
+        build.reserve_filename(search_permalink);
+    }
+
+    // Render pages for each release (including playlists, track pages, embeds, etc.).
+    // Pages of releases for which nothing changed since the last build are
+    // linked in from the cache instead. With the theming widget enabled, the
+    // pages contain the build time, so they are always rendered.
+    let site_signature = rendered_pages::site_signature(build, catalog, &index_html);
+    let mut releases_reused = 0;
+
+    for release in &catalog.releases {
+        let release_begin = Instant::now();
+        let release_ref = release.borrow();
+
+        if build.theming_widget {
+            release_ref.write_pages_and_playlist_files(build, catalog);
+        } else {
+            let signature = release_ref.render_signature(build, site_signature);
+            let rendered_pages = cache.get_or_create_rendered_pages(signature);
+            let mut rendered_pages_mut = rendered_pages.borrow_mut();
+
+            if rendered_pages_mut.is_empty() {
+                let errors_before = build.errors;
+                let warnings_before = build.warnings;
+
+                rendered_pages_mut.render(build, |build| release_ref.write_pages_and_playlist_files(build, catalog));
+
+                // Pages whose rendering reported errors or warnings are not
+                // kept, so that these are reported again on the next build.
+                if build.errors == errors_before && build.warnings == warnings_before {
+                    rendered_pages_mut.persist_to_cache(&build.cache_dir);
+                } else {
+                    rendered_pages_mut.discard(&build.cache_dir);
+                }
+            } else {
+                rendered_pages_mut.link_into_build_dir(build);
+                releases_reused += 1;
+            }
+        }
+
+        release_ref.write_page_files(build);
+
+        build.reserve_filename(release_ref.permalink.slug.clone());
+        build.timings.record_release(&release_ref.title, release_begin);
+    }
+
+    if releases_reused > 0 {
+        info_stats!("The pages of {} unchanged releases were reused from the last build", releases_reused);
+    }
+
+    // Render pages for featured artists (these are populated only in label mode)
+    for artist in &catalog.featured_artists {
+        let artist_ref = artist.borrow();
+        let artist_dir = build.build_dir.join(&artist_ref.permalink.slug);
+
+        util::ensure_dir_all(&artist_dir);
+
+        if let Some(base_url) = &build.base_url {
+            // Render json api document
+            if catalog.json_api {
+                let r_json = json_api::generate_for_artist(&artist_ref, base_url, build, catalog);
+                fs::write(artist_dir.join(JSON_API_ARTIST_FILENAME), r_json).unwrap();
+            }
+
+            // Render m3u playlist
+            if artist_ref.m3u {
+                let r_m3u = m3u::generate_for_artist(&artist_ref, build);
+                fs::write(artist_dir.join(M3U_PLAYLIST_FILENAME), r_m3u).unwrap();
+            }
+        }
+
+        let artist_html = render::artist::artist_html(&ArtistContext::new(&artist_ref, build, catalog));
+        fs::write(artist_dir.join("index.html"), artist_html).unwrap();
+        site_metadata::write_page_assets(build, &artist_dir, &artist_ref.page_assets);
+        build.reserve_filename(artist_ref.permalink.slug.clone());
+    }
+
+    // Render pages for playlists (and their M3U/XSPF playlist files)
+    for playlist in &catalog.playlists {
+        let playlist_dir = build.build_dir.join(&playlist.permalink.slug);
+
+        util::ensure_dir_all(&playlist_dir);
+
+        if let Some(base_url) = &build.base_url {
+            let r_m3u = m3u::generate_for_playlist(build, playlist);
+            fs::write(playlist_dir.join(M3U_PLAYLIST_FILENAME), r_m3u).unwrap();
+
+            let r_xspf = xspf::generate_for_playlist(base_url, build, playlist);
+            fs::write(playlist_dir.join(XSPF_PLAYLIST_FILENAME), r_xspf).unwrap();
+        }
+
+        let playlist_html = render::playlist::playlist_html(&PlaylistContext::new(build, catalog, playlist));
+        fs::write(playlist_dir.join("index.html"), playlist_html).unwrap();
+        build.reserve_filename(playlist.permalink.slug.clone());
+    }
+
+    // Render text-only (gemtext) variant of the site
+    if catalog.gemtext {
+        gemtext::write(build, catalog);
+    }
+
+    // Render image descriptions page (when needed)
+    if build.missing_image_descriptions {
+        let t_image_descriptions_permalink = build.locale.image_descriptions_permalink().to_string();
+        let image_descriptions_dir = build.build_dir.join(&t_image_descriptions_permalink);
+        let image_descriptions_html = render::image_descriptions::image_descriptions_html(&CatalogContext::new(build, catalog));
+        fs::create_dir(&image_descriptions_dir).unwrap();
+        fs::write(image_descriptions_dir.join("index.html"), image_descriptions_html).unwrap();
+        build.reserve_filename(t_image_descriptions_permalink);
+    }
+
+    // Render diagnostics page for the host's range request support
+    if catalog.range_check {
+        if let Some(release) = catalog.latest_release() {
+            let range_check_html = render::range_check::range_check_html(build, &release.borrow());
+            fs::write(build.build_dir.join(RANGE_CHECK_HTML_FILENAME), range_check_html).unwrap();
+            build.reserve_filename(RANGE_CHECK_HTML_FILENAME);
+        } else {
+            info!("No range check page was generated because the catalog has no public release to test with");
+        }
+    }
+
+    // Render /.well-known/ files (webfinger, security.txt, funding.json)
+    catalog.well_known.write(build, catalog);
+
+    // Render ai.txt and robots.txt (when AI scraping is disallowed)
+    catalog.ai_scraping.write(build);
+}
+
+/// Writes the site once more for each additional language of the catalog
+/// (see the `languages` option), into a subdirectory of the build directory
+/// that is named after the language code (e.g. "de"). The regular site (in
+/// the catalog's main language) and the language sites all link to each
+/// other as language mirrors. Only the pages, the scripts (which contain
+/// translations) and the other language specific files are written for
+/// each language site, the assets (images, audio, downloads, styles and
+/// favicon) are written once, for the main site, and referenced there.
+fn write_language_sites(build: &mut Build, cache: &mut Cache, catalog: &mut Catalog) {
+    let main_language = build.locale.language.clone();
+    let main_site_url = build.base_url_unchecked().clone();
+
+    for language in catalog.languages.clone() {
+        if !build.reserve_filename(language.clone()) {
+            let error = format!("The subdirectory for the language '{language}' collides with a file or directory of the same name in the build directory (e.g. from a permalink), the language was therefore not built.");
+            build.error(&error);
+            continue;
+        }
+
+        let mut language_mirrors = catalog.language_mirrors
+            .iter()
+            .filter(|mirror| mirror.language != language)
+            .cloned()
+            .collect::<Vec<LanguageMirror>>();
+
+        language_mirrors.push(LanguageMirror::new(main_language.clone(), main_site_url.clone()));
+
+        let site_url = SiteUrl::parse(&main_site_url.join_prefix(&language)).unwrap();
+        let language_build_dir = build.build_dir.join(&language);
+
+        // The hashes of the shared styles and favicon stay valid, only those
+        // of the scripts are regenerated for the language.
+        let main_asset_hashes = build.asset_hashes.clone();
+        let main_base_url = build.base_url.replace(site_url);
+        let main_build_dir = mem::replace(&mut build.build_dir, language_build_dir);
+        let main_language_mirrors = mem::replace(&mut catalog.language_mirrors, language_mirrors);
+        let main_locale = mem::replace(&mut build.locale, Locale::from_code(&language));
+        let main_reserved_filenames = build.replace_reserved_filenames(HashSet::new());
+        let main_subscribe_permalink = catalog.subscribe_permalink.clone();
+
+        build.shared_assets_url = Some(main_site_url.clone());
+
+        // Permalink terms come from the translations (unless overridden),
+        // and can therefore differ between the languages.
+        catalog.permalink_terms.apply(build);
+        catalog.compute_subscribe_permalink(build);
+
+        // Texts translated through overlay manifests (e.g. release.de.eno)
+        catalog.switch_language(Some(&language));
+
+        util::ensure_empty_dir(&build.build_dir);
+
+        scripts::generate(build, catalog);
+
+        write_site(build, cache, catalog);
+
+        if catalog.asset_manifest {
+            build.reserve_filename(asset_manifest::ASSET_MANIFEST_FILENAME);
+        }
+
+        if let Err(collisions) = catalog.write_user_assets(build) {
+            let collisions_joined = collisions
+                .iter()
+                .map(|filename| format!("'{filename}'"))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            let error = format!("One or more filenames of your custom site assets collide with filenames already used by faircamp itself in the site for the language '{language}': {collisions_joined}");
+            build.error(&error);
+        }
+
+        if catalog.asset_manifest {
+            asset_manifest::write(build, catalog);
+        }
+
+        info!("The site for the language '{}' was written to {}", language, build.build_dir.display());
+
+        catalog.language_mirrors = main_language_mirrors;
+        catalog.subscribe_permalink = main_subscribe_permalink;
+        catalog.switch_language(None);
+
+        build.asset_hashes = main_asset_hashes;
+        build.base_url = main_base_url;
+        build.build_dir = main_build_dir;
+        build.locale = main_locale;
+        build.replace_reserved_filenames(main_reserved_filenames);
+        build.shared_assets_url = None;
+    }
+}
+
+/// In label mode, writes the standalone site of each artist that has the
+/// standalone_site option set, to a build directory next to the regular one
+/// (e.g. ".faircamp_build-alice" for the artist "alice"). The cache (and with
+/// it all transcodes and image assets) is shared with the regular site.
+fn write_standalone_sites(build: &mut Build, cache: &mut Cache, catalog: &mut Catalog) {
+    let standalone_artists = catalog.artists
+        .iter()
+        .filter(|artist| artist.borrow().standalone_site.is_some())
+        .cloned()
+        .collect::<Vec<ArtistRc>>();
+
+    for artist in &standalone_artists {
+        let (artist_slug, site_url) = {
+            let artist_ref = artist.borrow();
+            (artist_ref.permalink.slug.clone(), artist_ref.standalone_site.clone().unwrap())
+        };
+
+        let build_dir_name = build.build_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| String::from("build"));
+
+        let standalone_build_dir = build.build_dir.with_file_name(format!("{build_dir_name}-{artist_slug}"));
+
+        let main_asset_hashes = mem::replace(&mut build.asset_hashes, AssetHashes::new());
+        let main_base_url = build.base_url.replace(site_url);
+        let main_build_dir = mem::replace(&mut build.build_dir, standalone_build_dir);
+        let main_reserved_filenames = build.replace_reserved_filenames(HashSet::new());
+
+        // The sites for the catalog's additional languages present the
+        // entire catalog, standalone sites therefore do not link to them.
+        let standalone_language_mirrors = catalog.language_mirrors
+            .iter()
+            .filter(|mirror| !catalog.languages.contains(&mirror.language))
+            .cloned()
+            .collect::<Vec<LanguageMirror>>();
+        let main_language_mirrors = mem::replace(&mut catalog.language_mirrors, standalone_language_mirrors);
+
+        let scope = catalog.narrow_to_artist(artist);
+
+        util::ensure_empty_dir(&build.build_dir);
+
+        catalog.write_assets(build, cache);
+        scripts::generate(build, catalog);
+        styles::generate(build, catalog);
+        catalog.favicon.write(build);
+
+        write_site(build, cache, catalog);
+
+        if catalog.asset_manifest {
+            build.reserve_filename(asset_manifest::ASSET_MANIFEST_FILENAME);
+        }
+
+        if let Err(collisions) = catalog.write_user_assets(build) {
+            let collisions_joined = collisions
+                .iter()
+                .map(|filename| format!("'{filename}'"))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            let error = format!("One or more filenames of your custom site assets collide with filenames already used by faircamp itself in the standalone site of the artist '{artist_slug}': {collisions_joined}");
+            build.error(&error);
+        }
+
+        if catalog.asset_manifest {
+            asset_manifest::write(build, catalog);
+        }
+
+        info!("The standalone site of the artist '{}' was written to {}", artist_slug, build.build_dir.display());
+
+        catalog.restore_scope(scope);
+        catalog.language_mirrors = main_language_mirrors;
+
+        build.asset_hashes = main_asset_hashes;
+        build.base_url = main_base_url;
+        build.build_dir = main_build_dir;
+        build.replace_reserved_filenames(main_reserved_filenames);
+    }
+}
@@ -3,7 +3,6 @@
 
 use std::collections::HashMap;
 use std::fs;
-use std::hash::{DefaultHasher, Hash, Hasher};
 use std::mem;
 use std::path::Path;
 
@@ -15,19 +14,33 @@ use crate::{
     ArchivesRc,
     Asset,
     AudioMeta,
+    BUILD_REPORT_CACHE_FILENAME,
     Build,
     CoverGenerator,
+    DASHBOARD_CACHE_FILENAME,
     FileMeta,
+    GIT_SCAN_CACHE_FILENAME,
     Image,
     ImageRc,
     ImageRcView,
+    LINK_CHECK_CACHE_FILENAME,
     ProceduralCover,
     ProceduralCoverRc,
+    REMOTE_SOURCES_DIRNAME,
+    RENDERING_DIRNAME,
     Release,
+    RemoteDownloads,
+    RenderedPages,
+    RenderedPagesRc,
+    SECRET_LINK_KEY_CACHE_FILENAME,
     SourceHash,
     Transcodes,
     TranscodesRc,
     TranscodesRcView,
+    VisualLoop,
+    VisualLoopAssets,
+    VisualLoopAssetsRc,
+    WEBHOOK_CACHE_FILENAME,
     util
 };
 use crate::util::string_from_os;
@@ -38,7 +51,7 @@ use crate::util::string_from_os;
 /// purge and rebuild for site operators picking up the new version of
 /// faircamp. More granular cache data invalidation can also be performed at the
 /// manifest level, by updating the version included in the `CACHE_SERIALIZATION_KEY`
-/// constant of either of [Archives], [Image] and [Transcodes]. This latter
+/// constant of either of [Archives], [Image], [Transcodes] and [VisualLoopAssets]. This latter
 /// mechanism should always be preferred, as cache rebuilds are expensive for users!
 const CACHE_VERSION_MARKER: &str = "cache1.marker";
 
@@ -64,7 +77,12 @@ pub struct Cache {
     manifests: Vec<String>,
     pub optimization: CacheOptimization,
     pub procedural_covers: Vec<ProceduralCoverRc>,
-    pub transcodes: Vec<TranscodesRc>
+    /// Downloads of remote sources (see the track's remote_source option),
+    /// these are kept in their own directory with their own index.
+    pub remote_downloads: RemoteDownloads,
+    pub rendered_pages: Vec<RenderedPagesRc>,
+    pub transcodes: Vec<TranscodesRc>,
+    pub visual_loops: Vec<VisualLoopAssetsRc>
 }
 
 #[derive(Debug, PartialEq)]
@@ -153,6 +171,15 @@ fn report_stale_images(
             *unused_bytesize += asset.filesize_bytes;
         }
     }
+
+    if let Some(assets) = image_ref.cover_assets_uncropped
+        .as_ref()
+        .filter(|assets| assets.is_stale()) {
+        for asset in &assets.all() {
+            *num_unused += 1;
+            *unused_bytesize += asset.filesize_bytes;
+        }
+    }
 }
 
 fn report_stale_procedural_cover(
@@ -171,17 +198,61 @@ fn report_stale_procedural_cover(
     }
 }
 
+fn report_stale_rendered_pages(
+    rendered_pages: &RenderedPagesRc,
+    num_unused: &mut u32,
+    unused_bytesize: &mut u64
+) {
+    for file in &rendered_pages.borrow().files {
+        if file.asset.is_stale() {
+            *num_unused += 1;
+            *unused_bytesize += file.asset.filesize_bytes;
+        }
+    }
+}
+
 fn report_stale_transcodes(
     transcodes: &TranscodesRc,
     num_unused: &mut u32,
     unused_bytesize: &mut u64
 ) {
-    for transcode in &transcodes.borrow().formats {
+    let transcodes_ref = transcodes.borrow();
+
+    for clip in &transcodes_ref.clips {
+        if clip.asset.is_stale() {
+            *num_unused += 1;
+            *unused_bytesize += clip.asset.filesize_bytes;
+        }
+    }
+
+    for transcode in &transcodes_ref.formats {
         if transcode.asset.is_stale() {
             *num_unused += 1;
             *unused_bytesize += transcode.asset.filesize_bytes;
         }
     }
+
+    if let Some(asset) = &transcodes_ref.preview {
+        if asset.is_stale() {
+            *num_unused += 1;
+            *unused_bytesize += asset.filesize_bytes;
+        }
+    }
+}
+
+fn report_stale_visual_loop(
+    visual_loop: &VisualLoopAssetsRc,
+    num_unused: &mut u32,
+    unused_bytesize: &mut u64
+) {
+    let visual_loop_ref = visual_loop.borrow();
+
+    for asset in [&visual_loop_ref.poster, &visual_loop_ref.video] {
+        if asset.is_stale() {
+            *num_unused += 1;
+            *unused_bytesize += asset.filesize_bytes;
+        }
+    }
 }
 
 impl Cache {
@@ -216,10 +287,22 @@ impl Cache {
             self.maintain_procedural_cover(procedural_cover, build);
         }
 
+        let mut remote_downloads = mem::take(&mut self.remote_downloads);
+        remote_downloads.remove_obsolete(&build.cache_dir, |marked_stale| self.obsolete(build, marked_stale));
+        self.remote_downloads = remote_downloads;
+
+        for rendered_pages in &self.rendered_pages {
+            self.maintain_rendered_pages(rendered_pages, build);
+        }
+
         for transcodes in &self.transcodes {
             self.maintain_transcodes(transcodes, build);
         }
 
+        for visual_loop in &self.visual_loops {
+            self.maintain_visual_loop(visual_loop, build);
+        }
+
         if self.optimization == CacheOptimization::Manual {
             self.report_stale();
         }
@@ -342,6 +425,25 @@ impl Cache {
             None => ()
         }
 
+        match image_mut.cover_assets_uncropped
+            .as_ref()
+            .map(|assets| self.obsolete(build, &assets.marked_stale)) {
+            Some(true) => {
+                for asset in image_mut.cover_assets_uncropped.take().unwrap().all() {
+                    let _ = fs::remove_file(build.cache_dir.join(&asset.filename));
+                    info_cache!(
+                        "Removed cached image asset ({}) {} {}x{}.",
+                        "uncropped cover",
+                        &views_context,
+                        asset.edge_size,
+                        asset.height
+                    );
+                }
+            }
+            Some(false) => keep_container = true,
+            None => ()
+        }
+
         if keep_container {
             image_mut.persist_to_cache(&build.cache_dir);
         } else {
@@ -367,6 +469,26 @@ impl Cache {
         }
     }
 
+    /// The rendered pages of a release are only useful as a whole, so
+    /// as soon as one of them is obsolete, all of them are removed.
+    fn maintain_rendered_pages(&self, rendered_pages: &RenderedPagesRc, build: &Build) {
+        let rendered_pages_ref = rendered_pages.borrow();
+
+        if rendered_pages_ref.is_empty() {
+            let _ = fs::remove_file(rendered_pages_ref.manifest_path(&build.cache_dir));
+        } else if rendered_pages_ref.files.iter().any(|file| self.obsolete(build, &file.asset.marked_stale)) {
+            for file in &rendered_pages_ref.files {
+                let _ = fs::remove_file(build.cache_dir.join(&file.asset.filename));
+            }
+
+            let _ = fs::remove_file(rendered_pages_ref.manifest_path(&build.cache_dir));
+
+            info_cache!("Removed cached rendered pages with signature {}.", rendered_pages_ref.signature);
+        } else {
+            rendered_pages_ref.persist_to_cache(&build.cache_dir);
+        }
+    }
+
     fn maintain_transcodes(&self, transcodes: &TranscodesRc, build: &Build) {
         let mut transcodes_mut = transcodes.borrow_mut();
 
@@ -412,13 +534,49 @@ impl Cache {
             });
         }
 
-        if transcodes_mut.formats.is_empty() {
+        transcodes_mut.clips.retain(|clip| {
+            if self.obsolete(build, &clip.asset.marked_stale) {
+                let _ = fs::remove_file(build.cache_dir.join(&clip.asset.filename));
+                info_cache!("Removed cached format comparison clip ({}).", clip.format);
+                false
+            } else {
+                true
+            }
+        });
+
+        if transcodes_mut.preview.as_ref().is_some_and(|asset| self.obsolete(build, &asset.marked_stale)) {
+            if let Some(asset) = transcodes_mut.preview.take() {
+                let _ = fs::remove_file(build.cache_dir.join(&asset.filename));
+                info_cache!("Removed cached hover preview.");
+            }
+        }
+
+        if transcodes_mut.clips.is_empty() &&
+            transcodes_mut.formats.is_empty() &&
+            transcodes_mut.preview.is_none() {
             let _ = fs::remove_file(transcodes_mut.manifest_path(&build.cache_dir));
         } else {
             transcodes_mut.persist_to_cache(&build.cache_dir);
         }
     }
 
+    /// The poster and the video of a visual loop are only useful together,
+    /// so as soon as one of them is obsolete, both are removed.
+    fn maintain_visual_loop(&self, visual_loop: &VisualLoopAssetsRc, build: &Build) {
+        let visual_loop_ref = visual_loop.borrow();
+
+        if self.obsolete(build, &visual_loop_ref.poster.marked_stale) ||
+            self.obsolete(build, &visual_loop_ref.video.marked_stale) {
+            let _ = fs::remove_file(build.cache_dir.join(&visual_loop_ref.poster.filename));
+            let _ = fs::remove_file(build.cache_dir.join(&visual_loop_ref.video.filename));
+            let _ = fs::remove_file(visual_loop_ref.manifest_path(&build.cache_dir));
+
+            info_cache!("Removed cached visual loop with signature {}.", visual_loop_ref.signature);
+        } else {
+            visual_loop_ref.persist_to_cache(&build.cache_dir);
+        }
+    }
+
     pub fn mark_all_stale(&mut self, timestamp: &DateTime<Utc>) {
         for archives in self.archives.iter_mut() {
             archives.borrow_mut().mark_all_stale(timestamp);
@@ -432,9 +590,19 @@ impl Cache {
             procedural_cover.borrow_mut().mark_stale(timestamp);
         }
 
+        self.remote_downloads.mark_all_stale(timestamp);
+
+        for rendered_pages in self.rendered_pages.iter_mut() {
+            rendered_pages.borrow_mut().mark_all_stale(timestamp);
+        }
+
         for transcodes in self.transcodes.iter_mut() {
             transcodes.borrow_mut().mark_all_stale(timestamp);
         }
+
+        for visual_loop in self.visual_loops.iter_mut() {
+            visual_loop.borrow_mut().mark_all_stale(timestamp);
+        }
     }
 
     fn new() -> Cache {
@@ -445,7 +613,10 @@ impl Cache {
             manifests: Vec::new(),
             optimization: CacheOptimization::Default,
             procedural_covers: Vec::new(),
-            transcodes: Vec::new()
+            remote_downloads: RemoteDownloads::default(),
+            rendered_pages: Vec::new(),
+            transcodes: Vec::new(),
+            visual_loops: Vec::new()
         }
     }
 
@@ -477,8 +648,12 @@ impl Cache {
                 self.retrieve_image(build, &file_name);
             } else if file_name.ends_with(&format!(".{}.bincode", ProceduralCover::CACHE_SERIALIZATION_KEY)) {
                 self.retrieve_procedural_cover(build, &file_name);
+            } else if file_name.ends_with(&format!(".{}.bincode", RenderedPages::CACHE_SERIALIZATION_KEY)) {
+                self.retrieve_rendered_pages(build, &file_name);
             } else if file_name.ends_with(&format!(".{}.bincode", Transcodes::CACHE_SERIALIZATION_KEY)) {
                 self.retrieve_transcodes(build, &file_name);
+            } else if file_name.ends_with(&format!(".{}.bincode", VisualLoopAssets::CACHE_SERIALIZATION_KEY)) {
+                self.retrieve_visual_loop(build, &file_name);
             } else {
                 info!(
                     "Removing incompatible cache manifest {} - it was probably created with a different version of faircamp.",
@@ -500,7 +675,12 @@ impl Cache {
                 if let Ok(file_type) = dir_entry.file_type() {
                     let file_name = string_from_os(dir_entry.file_name());
 
-                    if file_type.is_dir() {
+                    if file_type.is_dir() && file_name == REMOTE_SOURCES_DIRNAME {
+                        // Managed separately (see RemoteDownloads)
+                    } else if file_type.is_dir() && file_name == RENDERING_DIRNAME {
+                        // Left over from a build that was interrupted while rendering
+                        let _ = fs::remove_dir_all(dir_entry.path());
+                    } else if file_type.is_dir() {
                         info!(
                             "Removing incompatible cache directory {} - it was probably created with a different version of faircamp.",
                             file_name
@@ -510,7 +690,13 @@ impl Cache {
 
                         if file_name.ends_with(".bincode") {
                             self.manifests.push(file_name);
-                        } else if file_name != CACHE_VERSION_MARKER {
+                        } else if file_name != BUILD_REPORT_CACHE_FILENAME &&
+                            file_name != CACHE_VERSION_MARKER &&
+                            file_name != DASHBOARD_CACHE_FILENAME &&
+                            file_name != GIT_SCAN_CACHE_FILENAME &&
+                            file_name != LINK_CHECK_CACHE_FILENAME &&
+                            file_name != SECRET_LINK_KEY_CACHE_FILENAME &&
+                            file_name != WEBHOOK_CACHE_FILENAME {
                             self.assets.insert(file_name, false);
                         }
                     } else {
@@ -549,10 +735,18 @@ impl Cache {
             report_stale_procedural_cover(procedural_cover, &mut num_unused, &mut unused_bytesize);
         }
 
+        for rendered_pages in &self.rendered_pages {
+            report_stale_rendered_pages(rendered_pages, &mut num_unused, &mut unused_bytesize);
+        }
+
         for transcodes in &self.transcodes {
             report_stale_transcodes(transcodes, &mut num_unused, &mut unused_bytesize);
         }
 
+        for visual_loop in &self.visual_loops {
+            report_stale_visual_loop(visual_loop, &mut num_unused, &mut unused_bytesize);
+        }
+
         if num_unused > 0 {
             info_cache!(
                 "{} cached assets were identified as obsolete - you can run 'faircamp --optimize-cache' to remove them and reclaim {} of disk space.",
@@ -581,6 +775,7 @@ impl Cache {
 
         cache.register_files(&build.cache_dir);
         cache.process_manifests(build);
+        cache.remote_downloads = RemoteDownloads::retrieve(&build.cache_dir);
         cache.remove_orphaned_assets(&build.cache_dir);
 
         cache
@@ -687,6 +882,19 @@ impl Cache {
                 }
             }
 
+            if let Some(cover_assets_uncropped) = image_mut.cover_assets_uncropped.as_mut() {
+                let all_assets = cover_assets_uncropped.all();
+
+                if all_assets.iter().all(|asset| self.assets.contains_key(&asset.filename)) {
+                    for asset in all_assets.iter() {
+                        *self.assets.get_mut(&asset.filename).unwrap() = true;
+                    }
+                } else {
+                    image_mut.cover_assets_uncropped = None;
+                    dead_references_removed = true;
+                }
+            }
+
             if let Some(feed_asset) = &image_mut.feed_asset {
                 if let Some(used) = self.assets.get_mut(&feed_asset.filename) {
                     *used = true;
@@ -699,6 +907,7 @@ impl Cache {
             if image_mut.artist_assets.is_some() ||
                 image_mut.background_asset.is_some() ||
                 image_mut.cover_assets.is_some() ||
+                image_mut.cover_assets_uncropped.is_some() ||
                 image_mut.feed_asset.is_some() {
                 if dead_references_removed {
                     // Persist corrections so we don't have to re-apply them next time around
@@ -753,6 +962,34 @@ impl Cache {
         }
     }
 
+    fn retrieve_rendered_pages(&mut self, build: &Build, file_name: &str) {
+        let manifest_path = build.cache_dir.join(file_name);
+
+        if let Some(rendered_pages) = RenderedPages::deserialize_cached(&manifest_path) {
+            let complete = rendered_pages.files
+                .iter()
+                .all(|file| self.assets.contains_key(&file.asset.filename));
+
+            if complete {
+                for file in &rendered_pages.files {
+                    self.assets.insert(file.asset.filename.clone(), true);
+                }
+
+                self.rendered_pages.push(RenderedPagesRc::new(rendered_pages));
+            } else {
+                // Incomplete pages are of no use, we throw away the manifest
+                // (the remaining files are then removed as orphaned assets)
+                let _ = fs::remove_file(&manifest_path);
+            }
+        } else {
+            info!(
+                "Removing incompatible rendered pages cache manifest ({}) - it was probably created with a different version of faircamp.",
+                file_name
+            );
+            let _ = fs::remove_file(&manifest_path);
+        }
+    }
+
     fn retrieve_transcodes(&mut self, build: &Build, file_name: &str) {
         let manifest_path = build.cache_dir.join(file_name);
 
@@ -775,6 +1012,16 @@ impl Cache {
 
             let mut dead_references_removed = false;
 
+            transcodes_mut.clips.retain(|clip| {
+                if let Some(used) = self.assets.get_mut(&clip.asset.filename) {
+                    *used = true;
+                    true
+                } else {
+                    dead_references_removed = true;
+                    false
+                }
+            });
+
             transcodes_mut.formats.retain(|transcode| {
                 if let Some(used) = self.assets.get_mut(&transcode.asset.filename) {
                     *used = true;
@@ -785,6 +1032,15 @@ impl Cache {
                 }
             });
 
+            if let Some(asset) = &transcodes_mut.preview {
+                if let Some(used) = self.assets.get_mut(&asset.filename) {
+                    *used = true;
+                } else {
+                    transcodes_mut.preview = None;
+                    dead_references_removed = true;
+                }
+            }
+
             if dead_references_removed {
                 // Persist corrections so we don't have to re-apply them next time around
                 transcodes_mut.persist_to_cache(&build.cache_dir);
@@ -807,6 +1063,31 @@ impl Cache {
         }
     }
 
+    fn retrieve_visual_loop(&mut self, build: &Build, file_name: &str) {
+        let manifest_path = build.cache_dir.join(file_name);
+
+        if let Some(visual_loop) = VisualLoopAssets::deserialize_cached(&manifest_path) {
+            if self.assets.contains_key(&visual_loop.poster.filename) &&
+                self.assets.contains_key(&visual_loop.video.filename) {
+                self.assets.insert(visual_loop.poster.filename.clone(), true);
+                self.assets.insert(visual_loop.video.filename.clone(), true);
+
+                self.visual_loops.push(VisualLoopAssetsRc::new(visual_loop));
+            } else {
+                // Without both files the visual loop is of no use, we throw
+                // away the manifest (the remaining file is then removed as
+                // an orphaned asset)
+                let _ = fs::remove_file(&manifest_path);
+            }
+        } else {
+            info!(
+                "Removing incompatible visual loop cache manifest ({}) - it was probably created with a different version of faircamp.",
+                file_name
+            );
+            let _ = fs::remove_file(&manifest_path);
+        }
+    }
+
     /// This basically checks "Do we have cached download archives with the
     /// hash signature that uniquely identifies the entire dependency graph
     /// of of the release?" (whether we have the image and transcodes in all
@@ -860,18 +1141,7 @@ impl Cache {
         max_tracks_in_release: usize,
         release: &Release
     ) -> ProceduralCoverRc {
-        // Compute a signature for the needed cover, factoring in all data
-        // that is used in the generation of the cover (cover generator,
-        // maximum number of tracks on any release in the catalog, peaks of
-        // the tracks).
-        let mut hasher = DefaultHasher::new();
-        cover_generator.hash(&mut hasher);
-        max_tracks_in_release.hash(&mut hasher);
-        release.theme.base.hash(&mut hasher);
-        for track in &release.tracks {
-            track.transcodes.borrow().hash.hash(&mut hasher);
-        }
-        let signature = hasher.finish();
+        let signature = release.procedural_cover_signature(cover_generator, max_tracks_in_release);
 
         // If we already have a cached procedural cover matching the signature, return it
         for procedural_cover in &self.procedural_covers {
@@ -896,6 +1166,20 @@ impl Cache {
         procedural_cover_rc
     }
 
+    /// Returns the rendered pages cached under the given signature, or
+    /// creates them (empty, to be rendered).
+    pub fn get_or_create_rendered_pages(&mut self, signature: u64) -> RenderedPagesRc {
+        for rendered_pages in &self.rendered_pages {
+            if rendered_pages.borrow().signature == signature {
+                return rendered_pages.clone();
+            }
+        }
+
+        let rendered_pages = RenderedPagesRc::new(RenderedPages::new(signature));
+        self.rendered_pages.push(rendered_pages.clone());
+        rendered_pages
+    }
+
     /// Obtain transcodes by either reviving a view or computing a new
     /// transcodes instance from scratch. This may fail when we create
     /// a new instance and the decoding fails somehow.
@@ -922,7 +1206,7 @@ impl Cache {
             }
         }
 
-        let source_meta = match AudioMeta::extract(build, extension, source_path) {
+        let source_meta = match build.timings.measure("Decoding", || AudioMeta::extract(build, extension, source_path)) {
             Ok(audio_meta) => audio_meta,
             Err(err) => return Err(err)
         };
@@ -994,4 +1278,28 @@ impl View {
     pub fn unmark_stale(&mut self) {
         self.marked_stale = None;
     }
+
+    /// This basically checks "Do we have a cached visual loop that was
+    /// encoded from the same source file(s) with the same parameters?" If
+    /// not, the loop is encoded and persisted to the cache.
+    pub fn get_or_create_visual_loop(
+        &mut self,
+        build: &Build,
+        visual_loop: &VisualLoop
+    ) -> Result<VisualLoopAssetsRc, String> {
+        let signature = visual_loop.signature(build)?;
+
+        for visual_loop_assets in &self.visual_loops {
+            if visual_loop_assets.borrow().signature == signature {
+                return Ok(visual_loop_assets.clone());
+            }
+        }
+
+        let visual_loop_assets = visual_loop.encode(build, signature)?;
+        visual_loop_assets.persist_to_cache(&build.cache_dir);
+
+        let visual_loop_assets_rc = VisualLoopAssetsRc::new(visual_loop_assets);
+        self.visual_loops.push(visual_loop_assets_rc.clone());
+        Ok(visual_loop_assets_rc)
+    }
 }
@@ -2,57 +2,111 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::mem;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
+use chrono::Datelike;
 use indoc::{formatdoc, indoc};
 use sanitize_filename::sanitize;
 
 use crate::{
+    AiScraping,
     Artist,
     ArtistRc,
     AssetIntent,
+    AudioFormat,
     Build,
     Cache,
+    COVER_SVG_FILENAME,
+    CoverAspectRatio,
     DescribedImage,
+    DownloadAccess,
     Extra,
     FairDir,
     Favicon,
     FeedImageAsset,
     Feeds,
     FileMeta,
+    FundingChannel,
+    Hero,
     HeuristicAudioMeta,
     HtmlAndStripped,
     ImageRcView,
+    IndexView,
+    LanguageMirror,
     Link,
+    Localizations,
+    Lyrics,
+    Permalink,
+    PermalinkConflicts,
+    PermalinkTerms,
     PermalinkUsage,
+    PhysicalEdition,
+    Playlist,
+    PlaylistTrack,
+    PREVIEW_FORMAT,
     ProceduralCover,
     ProceduralCoverAsset,
     Release,
+    ReleaseFilter,
+    ReleaseFilterKind,
     ReleaseRc,
+    ReleaseSection,
+    ShareButton,
     SiteAsset,
     SiteMetadata,
+    SiteUrl,
+    StreamingLimits,
+    StreamingQuality,
     TagMapping,
     Theme,
     Track,
     TRACK_NUMBERS,
     TranscodesRcView,
-    util
+    util,
+    VisualLoopSource,
+    WarningCategory,
+    WellKnown,
+    YearArchivePage,
+    YearArchives
 };
+use crate::image::is_svg;
 use crate::manifest::{self, LocalOptions, Overrides};
-use crate::util::{generic_hash, url_safe_hash_base64};
+use crate::text_check;
+use crate::util::{format_bytes, format_time, generic_hash, url_safe_hash_base64};
 
-const PERMALINK_CONFLICT_RESOLUTION_HINT: &str = "In order to resolve the conflict, explicitly specify non-conflicting permalinks for all involved artists/releases through manifests using the 'permalink: example' option.";
+/// Covers are displayed at up to 1280 pixels (e.g. when enlarged on the
+/// release page) and never upscaled, covers whose (shorter) edge is below
+/// this size therefore noticeably lack sharpness.
+const MIN_COVER_EDGE_SIZE: u32 = 800;
+
+const PERMALINK_CONFLICT_RESOLUTION_HINT: &str = "In order to resolve the conflict, explicitly specify non-conflicting permalinks for all involved artists/playlists/releases through manifests using the 'permalink: example' option.";
 
 #[derive(Debug)]
 pub struct Catalog {
+    /// Whether pages, audio and images may be scraped for training AI models
+    pub ai_scraping: AiScraping,
     /// Stores the primary artist for "single artist" catalogs
     pub artist: Option<ArtistRc>,
     /// All artists (main_artists + support_artists)
     pub artists: Vec<ArtistRc>,
+    /// Whether an assets-manifest.json listing all files of the built site
+    /// (with their size and SHA256 checksum) is written, so that mirrors can
+    /// verify they have a complete and intact copy of the site
+    pub asset_manifest: bool,
     pub copy_link: bool,
+    /// Whether the critical part of site.css should be inlined into each
+    /// page, with the full stylesheet being loaded deferred
+    pub critical_css: bool,
+    /// Whether a page that sends visitors to a random track is generated
+    /// (and linked to from the header and footer of all pages)
+    pub discover: bool,
+    /// Computed at run-time like subscribe_permalink (see there), only set if
+    /// the discover page is enabled.
+    pub discover_permalink: Option<String>,
     pub faircamp_signature: bool,
     pub favicon: Favicon,
     /// Whether support artists should get their own
@@ -61,9 +115,58 @@ pub struct Catalog {
     /// Those artists that get their own page
     pub featured_artists: Vec<ArtistRc>,
     pub feeds: Feeds,
+    /// Set when release filters are enabled and there is at least one
+    /// filter - the directory in which the filter pages are placed
+    pub filter_permalink: Option<String>,
+    /// The imprints and regions of all public releases (only computed
+    /// when release filters are enabled)
+    pub filters: Vec<ReleaseFilter>,
+    /// Channels through which the artist/label can be funded, surfaced as
+    /// machine-readable metadata (funding.json, link tags, podcast feed)
+    pub funding: Vec<FundingChannel>,
+    /// Whether a text-only (gemtext) variant of the site should be written
+    /// alongside the html pages, so the site can also be served over gemini
+    pub gemtext: bool,
+    /// An optional hero section rendered above the release grid on the homepage
+    pub hero: Option<Hero>,
     pub home_image: Option<DescribedImage>,
+    /// Whether hovering/focusing a release in the release grids plays back
+    /// a short excerpt of its first track
+    pub hover_previews: bool,
+    /// Whether the catalog health dashboard and the image descriptions page
+    /// suggest descriptions for images that are missing one
+    pub image_description_suggestions: bool,
+    /// Additional catalog directories (configured through the catalog's
+    /// `include_catalogs` option) whose artists, releases and playlists are
+    /// merged into this catalog. The paths always start with the catalog
+    /// directory (e.g. "[catalog_dir]/../other-catalog"), so that all files
+    /// inside them can be addressed relative to the catalog directory too.
+    pub included_catalogs: Vec<PathBuf>,
+    /// Alternative views of all releases that get their own page
+    pub index_views: Vec<IndexView>,
+    /// Whether machine-readable json documents describing the catalog, its
+    /// releases and its featured artists are written (see the json_api module)
+    pub json_api: bool,
     pub label_mode: bool,
+    /// Translated versions of the catalog published elsewhere, which all
+    /// pages link to (see the language_mirror module)
+    pub language_mirrors: Vec<LanguageMirror>,
+    /// Codes of the languages in which the site is additionally built, each
+    /// into a subdirectory named after the code (see builder.rs)
+    pub languages: Vec<String>,
+    /// Whether a standalone widget presenting the latest release should be
+    /// generated for inclusion on external sites
+    pub latest_release_widget: bool,
+    /// Whether a reduced player (player_legacy.js) is provided for older
+    /// browsers that can not run player.js
+    pub legacy_support: bool,
     pub links: Vec<Link>,
+    /// Computed at run-time like subscribe_permalink (see there), only set if
+    /// the list view is enabled.
+    pub list_permalink: Option<String>,
+    /// Translated more/synopsis/title texts (from catalog.[language].eno
+    /// overlays), swapped in while the language sites are built
+    pub localizations: Localizations,
     /// Whether an m3u playlist should be generated and provided for the entire catalog
     pub m3u: bool,
     pub main_artists: Vec<ArtistRc>,
@@ -74,7 +177,38 @@ pub struct Catalog {
     /// Whether to include Open Graph metadata tags on all major pages (pages not intended
     /// for sharing generally don't render Open graph tags)
     pub opengraph: bool,
+    /// Whether conflicting auto-generated permalinks are suffixed
+    /// automatically or reported as errors
+    pub permalink_conflicts: PermalinkConflicts,
+    /// Computed at run-time like subscribe_permalink (see there), only set if
+    /// the homepage is split into more than one page.
+    pub pagination_permalink: Option<String>,
+    /// Overrides for the permalink segments (downloads, unlock, ...)
+    /// that otherwise come from the translations
+    pub permalink_terms: PermalinkTerms,
+    /// Whether a json file with the player configuration (tracks, streaming
+    /// urls, waveform peaks, ...) is written next to each release page
+    pub player_config: bool,
+    /// Curated selections of tracks across releases (from playlist.eno
+    /// manifests), each rendered as its own page
+    pub playlists: Vec<Playlist>,
+    /// Whether a diagnostics page should be generated that tests if the
+    /// host answers range requests (which are needed for seeking) correctly
+    pub range_check: bool,
+    /// Whether filter chips for the imprints and regions of releases are
+    /// shown on the homepage (linking to pre-generated filtered listings)
+    pub release_filters: bool,
     pub releases: Vec<ReleaseRc>,
+    /// If set, the releases on the homepage are split into pages with
+    /// this many releases each
+    pub releases_per_page: Option<usize>,
+    /// Whether a search index and a page for searching the catalog are generated
+    pub search: bool,
+    /// Computed at run-time like subscribe_permalink (see there), only set if
+    /// search is enabled.
+    pub search_permalink: Option<String>,
+    /// Services for which share buttons are displayed on the homepage
+    pub share_buttons: Vec<ShareButton>,
     pub show_support_artists: bool,
     /// Files specified through the site_assets option that are meant to be
     /// included in the build, e.g. to reference/include them from custom
@@ -84,6 +218,8 @@ pub struct Catalog {
     /// specified through the site_metadata option that is injected into the
     /// <head>…</head> section on all rendered pages.
     pub site_metadata: Option<SiteMetadata>,
+    /// Tracks whose streaming files exceed these limits are reported
+    pub streaming_limits: StreamingLimits,
     /// The page presenting subscription choices for the catalog competes with all
     /// artist+release permalinks, therefore we do a run-time computation to
     /// determine a conflict-free permalink for it (which starts with our
@@ -93,7 +229,59 @@ pub struct Catalog {
     pub support_artists: Vec<ArtistRc>,
     pub synopsis: Option<String>,
     pub theme: Theme,
-    title: Option<String>
+    /// Computed at run-time like subscribe_permalink (see there), only set if
+    /// the timeline view is enabled.
+    pub timeline_permalink: Option<String>,
+    title: Option<String>,
+    /// Files written to /.well-known/ (webfinger, security.txt, funding.json)
+    pub well_known: WellKnown,
+    /// Computed at run-time from the dates of the public releases (years
+    /// first, then decades, each descending), only populated if
+    /// year_archives is enabled.
+    pub year_archive_pages: Vec<YearArchivePage>,
+    pub year_archives: Option<YearArchives>
+}
+
+/// The parts of a catalog in label mode that are swapped out while the
+/// standalone site of one of its artists is written (see
+/// Catalog::narrow_to_artist), so they can be restored afterwards.
+pub struct CatalogScope {
+    artist: Option<ArtistRc>,
+    featured_artists: Vec<ArtistRc>,
+    filter_permalink: Option<String>,
+    filters: Vec<ReleaseFilter>,
+    hero: Option<Hero>,
+    home_image: Option<DescribedImage>,
+    links: Vec<Link>,
+    more: Option<HtmlAndStripped>,
+    more_label: Option<String>,
+    playlists: Vec<Playlist>,
+    releases: Vec<ReleaseRc>,
+    synopsis: Option<String>,
+    theme: Theme,
+    title: Option<String>,
+    year_archive_pages: Vec<YearArchivePage>
+}
+
+/// Claims the slug if it is not yet used and returns None, otherwise claims
+/// and returns the first free variant of it with a numeric suffix, e.g.
+/// "example-2" or "example-3".
+fn claim_slug(used_slugs: &mut HashSet<String>, slug: &str) -> Option<String> {
+    if used_slugs.insert(slug.to_string()) {
+        return None;
+    }
+
+    let mut suffix = 2;
+
+    loop {
+        let suffixed_slug = format!("{slug}-{suffix}");
+
+        if used_slugs.insert(suffixed_slug.clone()) {
+            return Some(suffixed_slug);
+        }
+
+        suffix += 1;
+    }
 }
 
 /// Gets passed the images found in a release directory. Checks against a few
@@ -125,6 +313,22 @@ fn pick_best_cover_image(images: &[ImageRcView]) -> Option<DescribedImage> {
         .map(|cover_candidate| DescribedImage::new(None, cover_candidate.1.clone()))
 }
 
+/// Warns about covers whose (shorter) edge is below [MIN_COVER_EDGE_SIZE]
+fn warn_if_undersized_cover(build: &mut Build, source_edge_size: u32, source_path: &Path) {
+    // SVGs are rasterized at a size of our choosing
+    if source_edge_size < MIN_COVER_EDGE_SIZE && !is_svg(source_path) {
+        let warning = format!(
+            "The cover image {} is only {}x{} pixels (after cropping it to a square), it will look blurry when displayed at larger sizes. Use an image of at least {}x{} pixels, ideally 1280x1280 pixels or larger.",
+            source_path.display(),
+            source_edge_size,
+            source_edge_size,
+            MIN_COVER_EDGE_SIZE,
+            MIN_COVER_EDGE_SIZE
+        );
+        build.warning(WarningCategory::UndersizedCover, &warning);
+    }
+}
+
 // TODO: Optimize this (and also the related mechanism in styles.rs).
 //       Right now we see if we already generated the file (in build) to decide
 //       whether to go forward, but it would be more elegant/efficient another
@@ -152,6 +356,112 @@ pub fn write_background_image(build: &mut Build, image: &ImageRcView) {
 }
 
 impl Catalog {
+    /// The additional languages of the catalog (see the `languages` option)
+    /// are built into subdirectories of the site (e.g. /de/), which are then
+    /// linked to like any other language mirror. Requires the base_url, as
+    /// mirrors are linked to through absolute urls.
+    fn add_language_sites(&mut self, build: &mut Build) {
+        if self.languages.is_empty() {
+            return;
+        }
+
+        let Some(base_url) = build.base_url.clone() else {
+            let error = "The languages option requires catalog.base_url to be set, the additional languages were therefore not built.";
+            build.error(error);
+            self.languages.clear();
+            return;
+        };
+
+        self.languages.retain(|language| *language != build.locale.language);
+
+        for language in &self.languages {
+            if self.language_mirrors.iter().any(|mirror| mirror.language == *language) {
+                let error = format!("The language '{language}' is configured both in the languages and the language_mirrors option, it is only built into its own subdirectory ({}) and the mirror is ignored.", base_url.join_prefix(language));
+                build.error(&error);
+                self.language_mirrors.retain(|mirror| mirror.language != *language);
+            }
+
+            let url = SiteUrl::parse(&base_url.join_prefix(language)).unwrap();
+            self.language_mirrors.push(LanguageMirror::new(language.clone(), url));
+        }
+    }
+
+    /// Translated manifests (e.g. release.fr.eno) are only used on the sites
+    /// built for the catalog's `languages`, for any other language they
+    /// would silently go unused, so we report them.
+    fn check_localizations(&self, build: &mut Build) {
+        let mut unused = Vec::new();
+
+        let mut collect_unused = |localizations: &Localizations, context: String| {
+            for language in localizations.languages() {
+                if !self.languages.contains(language) {
+                    unused.push(format!("A translated manifest for the language '{language}' was provided for {context}, but it is not used because '{language}' is not listed in the catalog's languages option."));
+                }
+            }
+        };
+
+        collect_unused(&self.localizations, String::from("the catalog"));
+
+        for artist in &self.artists {
+            let artist_ref = artist.borrow();
+            collect_unused(&artist_ref.localizations, format!("the artist '{}'", artist_ref.name));
+        }
+
+        for release in &self.releases {
+            let release_ref = release.borrow();
+            collect_unused(&release_ref.localizations, format!("the release '{}'", release_ref.title));
+        }
+
+        for warning in unused {
+            build.warning(WarningCategory::IgnoredFile, &warning);
+        }
+    }
+
+    /// Reports tracks whose duration, or the estimated size of whose
+    /// streaming files, exceeds the catalog's streaming_limits.
+    fn check_streaming_limits(&self, build: &mut Build) {
+        let StreamingLimits { duration_minutes, size_megabytes } = self.streaming_limits;
+
+        for release in &self.releases {
+            let release_ref = release.borrow();
+
+            for track in &release_ref.tracks {
+                let duration_seconds = track.transcodes.borrow().source_meta.duration_seconds;
+                let estimated_size_bytes = track.streaming_quality.estimated_size_bytes(duration_seconds);
+
+                let mut exceeded = Vec::new();
+
+                if let Some(minutes) = duration_minutes {
+                    if duration_seconds > (minutes * 60) as f32 {
+                        exceeded.push(format!("its duration ({}) exceeds the limit of {} minutes", format_time(duration_seconds), minutes));
+                    }
+                }
+
+                if let Some(megabytes) = size_megabytes {
+                    if estimated_size_bytes > megabytes as u64 * 1024 * 1024 {
+                        exceeded.push(format!("its streaming files will be about {} in size, exceeding the limit of {}MB", format_bytes(estimated_size_bytes), megabytes));
+                    }
+                }
+
+                if !exceeded.is_empty() {
+                    let r_suggestion = match track.streaming_quality {
+                        StreamingQuality::Frugal => "",
+                        StreamingQuality::Standard => " switching to 'streaming_quality: frugal',"
+                    };
+
+                    let warning = format!(
+                        "The track '{}' (release '{}') might be hard to stream for visitors on slow or metered connections: {}. Consider{} splitting it up into several tracks, or - if this is intended - raising the thresholds through the catalog's 'streaming_limits' option.",
+                        track.title(),
+                        release_ref.title,
+                        exceeded.join(", and "),
+                        r_suggestion
+                    );
+                    build.warning(WarningCategory::StreamingLimit, &warning);
+                }
+            }
+        }
+    }
+
     /// Use the metadata we gathered for tracks and releases to compute
     /// the folder and file names we are going to create in our build
     /// directory.
@@ -197,23 +507,263 @@ impl Catalog {
         }
     }
 
-    /// If the subscribe page permalink we have in our translations collides with
-    /// any of the artist or release permalinks, we prepend underscores to it
-    /// until there is no collision anymore.
-    fn compute_subscribe_permalink(&mut self, build: &Build) {
-        let mut subscribe_slug = build.locale.translations.subscribe_permalink.to_string();
+    /// The discover page competes with all other pages at the root of the
+    /// site, so as for the subscribe page we prepend underscores to its
+    /// permalink until there is no collision anymore.
+    fn compute_discover_permalink(&mut self) {
+        if !self.discover {
+            return;
+        }
+
+        let slug = self.free_slug(
+            "discover",
+            &[&self.filter_permalink, &self.list_permalink, &self.subscribe_permalink, &self.timeline_permalink]
+        );
+
+        self.discover_permalink = Some(slug);
+    }
+
+    /// The pages of the enabled index views compete with all artist, release
+    /// and playlist permalinks (and the subscribe permalink), so as for the
+    /// subscribe page we prepend underscores to their permalinks until
+    /// there is no collision anymore.
+    fn compute_index_view_permalinks(&mut self) {
+        for index_view in self.index_views.clone() {
+            let slug = self.free_slug(index_view.desired_permalink(), &[&self.subscribe_permalink]);
+
+            match index_view {
+                IndexView::List => self.list_permalink = Some(slug),
+                IndexView::Timeline => self.timeline_permalink = Some(slug)
+            }
+        }
+    }
+
+    /// The pages after the first homepage page are placed in a directory
+    /// that competes with all other pages at the root of the site, so as
+    /// for the subscribe page we prepend underscores to its permalink until
+    /// there is no collision anymore.
+    fn compute_pagination_permalink(&mut self) {
+        if self.home_page_count() < 2 {
+            return;
+        }
+
+        let slug = self.free_slug(
+            "page",
+            &[&self.discover_permalink, &self.filter_permalink, &self.list_permalink, &self.search_permalink, &self.subscribe_permalink, &self.timeline_permalink]
+        );
+
+        self.pagination_permalink = Some(slug);
+    }
 
-        while self.featured_artists.iter().any(|artist| artist.borrow().permalink.slug == subscribe_slug) ||
-            self.releases.iter().any(|release| release.borrow().permalink.slug == subscribe_slug) {
-            subscribe_slug = format!("_{subscribe_slug}");
+    /// Collects the distinct imprints and regions of all public releases
+    /// into filters (when release filters are enabled) and determines the
+    /// permalink of the directory for the filter pages, prepending
+    /// underscores to it until it collides with no other page.
+    fn compute_release_filters(&mut self) {
+        if !self.release_filters {
+            return;
         }
 
+        let public_releases = self.public_releases();
+
+        for kind in [ReleaseFilterKind::Imprint, ReleaseFilterKind::Region] {
+            let mut labels = public_releases
+                .iter()
+                .filter_map(|release| kind.value(&release.borrow()).cloned())
+                .collect::<Vec<String>>();
+
+            labels.sort();
+            labels.dedup();
+            labels.sort_by_key(|label| label.to_lowercase());
+
+            let mut used_slugs = HashSet::new();
+
+            for label in labels {
+                let mut filter = ReleaseFilter::new(kind, label);
+
+                if let Some(suffixed_slug) = claim_slug(&mut used_slugs, &filter.slug) {
+                    filter.slug = suffixed_slug;
+                }
+
+                self.filters.push(filter);
+            }
+        }
+
+        if self.filters.is_empty() {
+            return;
+        }
+
+        let slug = self.free_slug(
+            "filter",
+            &[&self.list_permalink, &self.subscribe_permalink, &self.timeline_permalink]
+        );
+
+        self.filter_permalink = Some(slug);
+    }
+
+    /// The search page competes with all other pages at the root of the
+    /// site, so as for the subscribe page we prepend underscores to its
+    /// permalink until there is no collision anymore.
+    fn compute_search_permalink(&mut self) {
+        if !self.search {
+            return;
+        }
+
+        let slug = self.free_slug(
+            "search",
+            &[&self.discover_permalink, &self.filter_permalink, &self.list_permalink, &self.subscribe_permalink, &self.timeline_permalink]
+        );
+
+        self.search_permalink = Some(slug);
+    }
+
+    /// If the subscribe page permalink we have in our translations collides with
+    /// any of the artist, release or playlist permalinks, we prepend
+    /// underscores to it until there is no collision anymore.
+    pub fn compute_subscribe_permalink(&mut self, build: &Build) {
+        let subscribe_slug = self.free_slug(build.locale.subscribe_permalink(), &[]);
+
         self.subscribe_permalink = Some(subscribe_slug);
     }
 
-    pub fn get_or_create_release_archives(&mut self, cache: &mut Cache) {
+    /// Each year (and, if enabled, each decade) in which at least one public
+    /// release is dated gets its own archive page. Years can collide with
+    /// permalinks (e.g. a release titled "2024"), in which case we prepend
+    /// underscores to the archive page's slug until there is no collision
+    /// anymore.
+    fn compute_year_archive_pages(&mut self) {
+        let Some(year_archives) = &self.year_archives else { return };
+
+        let mut years = self.public_releases()
+            .iter()
+            .filter_map(|release| release.borrow().date.map(|date| date.year()))
+            .collect::<Vec<i32>>();
+
+        years.sort_by(|a, b| b.cmp(a));
+        years.dedup();
+
+        let mut pages = years
+            .iter()
+            .map(|year| YearArchivePage::year(*year))
+            .collect::<Vec<YearArchivePage>>();
+
+        if year_archives.decades {
+            let mut decades = years
+                .iter()
+                .map(|year| year - year.rem_euclid(10))
+                .collect::<Vec<i32>>();
+
+            decades.dedup();
+
+            pages.extend(decades.into_iter().map(YearArchivePage::decade));
+        }
+
+        for page in pages.iter_mut() {
+            page.slug = self.free_slug(&page.slug, &[]);
+        }
+
+        self.year_archive_pages = pages;
+    }
+
+    /// Public releases that share a title (compared case-insensitively, e.g.
+    /// several releases titled "Demo") get their release year appended to
+    /// their title wherever releases are listed side by side (release grids,
+    /// feeds, search). If the years don't tell them apart, their artists are
+    /// appended instead (or both, if only the combination is unique). Tracks
+    /// that share a title with a track on another public release get the
+    /// (disambiguated) title of their release appended in search results.
+    fn disambiguate_titles(&mut self) {
+        let public_releases = self.public_releases();
+
+        let mut releases_by_title: HashMap<String, Vec<&ReleaseRc>> = HashMap::new();
+
+        for release in &public_releases {
+            let title_key = release.borrow().title.trim().to_lowercase();
+            releases_by_title.entry(title_key).or_default().push(release);
+        }
+
+        let artists = |release: &ReleaseRc| -> String {
+            release.borrow().main_artists
+                .iter()
+                .map(|artist| artist.borrow().name.clone())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        let year = |release: &ReleaseRc| -> Option<i32> {
+            release.borrow().date.map(|date| date.year())
+        };
+
+        for releases in releases_by_title.values().filter(|releases| releases.len() > 1) {
+            for release in releases {
+                let release_artists = artists(release);
+                let release_year = year(release);
+
+                let unique = |matches: &dyn Fn(&ReleaseRc) -> bool| {
+                    releases.iter().filter(|other| matches(other)).count() == 1
+                };
+
+                let disambiguation = match release_year {
+                    Some(release_year) if unique(&|other| year(other) == Some(release_year)) => {
+                        Some(release_year.to_string())
+                    }
+                    _ if !release_artists.is_empty() && unique(&|other| artists(other) == release_artists) => {
+                        Some(release_artists)
+                    }
+                    Some(release_year) if !release_artists.is_empty() &&
+                        unique(&|other| year(other) == Some(release_year) && artists(other) == release_artists) => {
+                        Some(format!("{release_artists}, {release_year}"))
+                    }
+                    _ => None
+                };
+
+                release.borrow_mut().title_disambiguation = disambiguation;
+            }
+        }
+
+        let mut releases_by_track_title: HashMap<String, HashSet<usize>> = HashMap::new();
+
+        for (release_index, release) in public_releases.iter().enumerate() {
+            for track in &release.borrow().tracks {
+                let title_key = track.title().trim().to_lowercase();
+                releases_by_track_title.entry(title_key).or_default().insert(release_index);
+            }
+        }
+
+        for (release_index, release) in public_releases.iter().enumerate() {
+            let mut release_mut = release.borrow_mut();
+            let release_title = release_mut.title_disambiguated();
+
+            for track in release_mut.tracks.iter_mut() {
+                let title_key = track.title().trim().to_lowercase();
+
+                if releases_by_track_title[&title_key].iter().any(|index| *index != release_index) {
+                    track.title_disambiguation = Some(release_title.clone());
+                }
+            }
+        }
+    }
+
+    /// Prepends underscores to the desired slug until it collides neither
+    /// with any of the artist, release or playlist permalinks nor with any
+    /// of the given (already computed) page permalinks.
+    fn free_slug(&self, desired_slug: &str, page_permalinks: &[&Option<String>]) -> String {
+        let mut slug = desired_slug.to_string();
+
+        while self.featured_artists.iter().any(|artist| artist.borrow().permalink.slug == slug) ||
+            self.releases.iter().any(|release| release.borrow().permalink.slug == slug) ||
+            self.playlists.iter().any(|playlist| playlist.permalink.slug == slug) ||
+            page_permalinks
+                .iter()
+                .any(|permalink| permalink.as_ref().is_some_and(|permalink| *permalink == slug)) {
+            slug = format!("_{slug}");
+        }
+
+        slug
+    }
+
+    pub fn get_or_create_release_archives(&mut self, build: &Build, cache: &mut Cache) {
         for release in self.releases.iter_mut() {
-            release.borrow_mut().get_or_create_release_archives(cache);
+            release.borrow_mut().get_or_create_release_archives(build, cache);
         }
     }
 
@@ -341,36 +891,158 @@ impl Catalog {
         }
     }
 
+    /// Turns the catalog (which is in label mode) into the catalog of a
+    /// single artist (not in label mode), containing only the releases on
+    /// which the artist is a main artist, and presenting the artist's
+    /// image, texts, links and theme on the homepage. Playlists, release
+    /// filters and the hero are left out, as they are curated for the label
+    /// as a whole. The returned scope needs to be passed to
+    /// Catalog::restore_scope afterwards.
+    pub fn narrow_to_artist(&mut self, artist: &ArtistRc) -> CatalogScope {
+        let releases = self.releases
+            .iter()
+            .filter(|release| {
+                release.borrow().main_artists
+                    .iter()
+                    .any(|main_artist| ArtistRc::ptr_eq(main_artist, artist))
+            })
+            .cloned()
+            .collect();
+
+        // Without featured artists there are no artist pages, links to the
+        // artist then point to the homepage instead (which presents them),
+        // other artists are not linked to at all.
+        for featured_artist in &self.featured_artists {
+            featured_artist.borrow_mut().featured = false;
+        }
+
+        self.label_mode = false;
+
+        let artist_ref = artist.borrow();
+
+        CatalogScope {
+            artist: self.artist.replace(artist.clone()),
+            featured_artists: mem::take(&mut self.featured_artists),
+            filter_permalink: self.filter_permalink.take(),
+            filters: mem::take(&mut self.filters),
+            hero: self.hero.take(),
+            home_image: mem::replace(&mut self.home_image, artist_ref.image.clone()),
+            links: mem::replace(&mut self.links, artist_ref.links.clone()),
+            more: mem::replace(&mut self.more, artist_ref.more.clone()),
+            more_label: mem::replace(&mut self.more_label, artist_ref.more_label.clone()),
+            playlists: mem::take(&mut self.playlists),
+            releases: mem::replace(&mut self.releases, releases),
+            synopsis: mem::replace(&mut self.synopsis, artist_ref.synopsis.clone()),
+            theme: mem::replace(&mut self.theme, artist_ref.theme.clone()),
+            title: self.title.take(),
+            year_archive_pages: mem::take(&mut self.year_archive_pages)
+        }
+    }
+
     pub fn new() -> Catalog {
         Catalog {
+            ai_scraping: AiScraping::ALLOWED,
             artist: None,
             artists: Vec::new(),
+            asset_manifest: false,
             copy_link: true,
+            critical_css: false,
+            discover: false,
+            discover_permalink: None,
             faircamp_signature: true,
             favicon: Favicon::Default,
             feature_support_artists: false,
             featured_artists: Vec::new(),
             feeds: Feeds::DEFAULT,
+            filter_permalink: None,
+            filters: Vec::new(),
+            funding: Vec::new(),
+            gemtext: false,
+            hero: None,
             home_image: None,
+            hover_previews: false,
+            image_description_suggestions: false,
+            included_catalogs: Vec::new(),
+            index_views: Vec::new(),
+            json_api: false,
             label_mode: false,
+            language_mirrors: Vec::new(),
+            languages: Vec::new(),
+            latest_release_widget: false,
+            legacy_support: false,
             links: Vec::new(),
+            list_permalink: None,
+            localizations: Localizations::new(),
             m3u: false,
             main_artists: Vec::new(),
             more: None,
             more_label: None,
             opengraph: false,
+            pagination_permalink: None,
+            permalink_conflicts: PermalinkConflicts::Error,
+            permalink_terms: PermalinkTerms::default(),
+            player_config: false,
+            playlists: Vec::new(),
+            range_check: false,
+            release_filters: false,
             releases: Vec::new(),
+            releases_per_page: None,
+            search: false,
+            search_permalink: None,
+            share_buttons: Vec::new(),
             show_support_artists: false,
             site_assets: Vec::new(),
             site_metadata: None,
+            streaming_limits: StreamingLimits::DEFAULT,
             subscribe_permalink: None,
             support_artists: Vec::new(),
             synopsis: None,
             theme: Theme::new(),
-            title: None
+            timeline_permalink: None,
+            title: None,
+            well_known: WellKnown::new(),
+            year_archive_pages: Vec::new(),
+            year_archives: None
         }
     }
 
+    /// The public releases shown on the given page of the homepage (counting
+    /// from 1), the first page holding the most recent ones. Without
+    /// pagination all public releases are on the first page.
+    pub fn home_page_releases(&self, page: usize) -> Vec<ReleaseRc> {
+        let mut public_releases = self.public_releases();
+
+        let Some(releases_per_page) = self.releases_per_page else {
+            return public_releases;
+        };
+
+        // Sorted and sliced in ascending order, so that the release grid
+        // (which sorts again and lists in reverse) keeps releases with the
+        // same date in the same order as without pagination.
+        public_releases.sort_by_key(|release| release.borrow().date);
+
+        let end = public_releases.len().saturating_sub((page - 1) * releases_per_page);
+        let start = end.saturating_sub(releases_per_page);
+
+        public_releases.drain(start..end).collect()
+    }
+
+    /// How many pages the homepage is split into (always at least one)
+    pub fn home_page_count(&self) -> usize {
+        match self.releases_per_page {
+            Some(releases_per_page) => self.public_releases().len().div_ceil(releases_per_page).max(1),
+            None => 1
+        }
+    }
+
+    /// The public release with the most recent date (if any), which is the
+    /// same release that is listed first on the homepage.
+    pub fn latest_release(&self) -> Option<ReleaseRc> {
+        self.public_releases()
+            .into_iter()
+            .max_by_key(|release| release.borrow().date)
+    }
+
     pub fn public_releases(&self) -> Vec<ReleaseRc> {
         self.releases
             .iter()
@@ -388,6 +1060,14 @@ impl Catalog {
 
         catalog.read_catalog_dir(build, cache);
 
+        catalog.permalink_terms.apply(build);
+
+        if build.check_texts {
+            text_check::check_texts(build);
+        }
+
+        catalog.check_streaming_limits(build);
+
         if build.errors > 0 && !build.ignore_errors {
             info!("Build was aborted because {} errors were encountered while reading the catalog.", build.errors);
             info!("You can run faircamp with --ignore-errors if you want to build in spite of errors.");
@@ -402,7 +1082,12 @@ impl Catalog {
         }
 
         if catalog.home_image.as_ref().is_some_and(|described_image| described_image.description.is_none()) {
-            warn_discouraged!("The catalog home image is missing an image description.");
+            build.warning(WarningCategory::MissingImageDescription, "The catalog home image is missing an image description.");
+            build.missing_image_descriptions = true;
+        }
+
+        if catalog.hero.as_ref().and_then(|hero| hero.custom_image()).is_some_and(|described_image| described_image.description.is_none()) {
+            build.warning(WarningCategory::MissingImageDescription, "The catalog hero image is missing an image description.");
             build.missing_image_descriptions = true;
         }
 
@@ -434,7 +1119,8 @@ impl Catalog {
             for artist in &catalog.featured_artists {
                 let artist_ref = artist.borrow();
                 if artist_ref.image.as_ref().is_some_and(|described_image| described_image.description.is_none()) {
-                    warn_discouraged!("The image for artist '{}' is missing an image description.", artist_ref.name);
+                    let warning = format!("The image for artist '{}' is missing an image description.", artist_ref.name);
+                    build.warning(WarningCategory::MissingImageDescription, &warning);
                     build.missing_image_descriptions = true;
                 }
             }
@@ -442,15 +1128,19 @@ impl Catalog {
             catalog.set_artist();
         }
 
-        catalog.get_or_create_release_archives(cache);
+        if catalog.permalink_conflicts == PermalinkConflicts::Auto {
+            catalog.suffix_conflicting_permalinks(build);
+        }
 
         if !catalog.validate_permalinks(build) {
             warn!("The build has been aborted because permalink conflicts were found, this kind of error needs to be resolved and cannot be ignored.");
             return Err(());
         }
 
+        catalog.resolve_playlist_tracks(build);
+
         if let Some(site_metadata) = &mut catalog.site_metadata {
-            if let Err(missing_filenames) = site_metadata.resolve_filename_references(&catalog.site_assets) {
+            if let Err(missing_filenames) = site_metadata.resolve_filename_references(&[], &catalog.site_assets) {
                 for filename in &missing_filenames {
                     error!("The filename reference {{{}}} inside site_metadata could not be resolved.", filename)
                 }
@@ -460,8 +1150,23 @@ impl Catalog {
             }
         }
 
+        if !catalog.resolve_page_metadata() {
+            warn!("The build has been aborted because filenames in page_metadata could not be resolved, this kind of error needs to be resolved and cannot be ignored.");
+            return Err(());
+        }
+
+        catalog.add_language_sites(build);
+        catalog.check_localizations(build);
         catalog.compute_asset_basenames();
+        catalog.get_or_create_release_archives(build, cache);
         catalog.compute_subscribe_permalink(build);
+        catalog.compute_index_view_permalinks();
+        catalog.compute_release_filters();
+        catalog.compute_discover_permalink();
+        catalog.compute_search_permalink();
+        catalog.compute_pagination_permalink();
+        catalog.compute_year_archive_pages();
+        catalog.disambiguate_titles();
 
         catalog.unlist_artists();
 
@@ -487,11 +1192,13 @@ impl Catalog {
         if build.verbose {
             info!("Reading artist manifest {}", artist_manifest.display());
         }
+        let localizations = manifest::read_overlay_manifests(build, "artist", &fair_dir.overlay_manifests);
         manifest::read_artist_manifest(
             build,
             cache,
             self,
             &fair_dir.path,
+            localizations,
             artist_manifest,
             &mut overrides
         );
@@ -512,6 +1219,11 @@ impl Catalog {
 
         let fair_dir = FairDir::read(build, &build.catalog_dir.clone());
 
+        if fair_dir.playlist_manifest.is_some() {
+            let error = format!("A playlist.eno manifest may not be placed at the root of the catalog directory, however it was found there (at '{}'). Please move it into its own (playlist) directory", build.catalog_dir.display());
+            build.error(&error);
+        }
+
         if fair_dir.release_manifest.is_some() {
             let error = format!("A release.eno manifest may not be placed at the root of the catalog directory, however it was found there (at '{}'). Please move it into its own (release) directory", build.catalog_dir.display());
             build.error(&error);
@@ -538,23 +1250,28 @@ impl Catalog {
                 catalog_manifest,
                 &mut catalog_overrides
             );
+
+            self.localizations = manifest::read_overlay_manifests(build, "catalog", &fair_dir.overlay_manifests);
         }
 
         if let Some(artist_manifest) = &fair_dir.artist_manifest {
             if build.verbose {
                 info!("Reading artist manifest {}", artist_manifest.display());
             }
+            let localizations = manifest::read_overlay_manifests(build, "artist", &fair_dir.overlay_manifests);
             manifest::read_artist_manifest(
                 build,
                 cache,
                 self,
                 &fair_dir.path,
+                localizations,
                 artist_manifest,
                 &mut catalog_overrides
             );
         }
 
         self.copy_link = catalog_overrides.copy_link;
+        self.share_buttons = catalog_overrides.share_buttons.clone();
 
         if local_options.more.is_some() {
             self.more = local_options.more;
@@ -574,6 +1291,48 @@ impl Catalog {
         for dir_path in &fair_dir.dirs {
             self.read_unknown_dir(build, cache, &catalog_overrides, dir_path);
         }
+
+        for included_dir in &self.included_catalogs.clone() {
+            if build.verbose {
+                info!("Reading included catalog directory {}", included_dir.display());
+            }
+
+            let included_fair_dir = FairDir::read(build, included_dir);
+
+            // Included catalogs may well be built as standalone sites too
+            // (e.g. the catalog of a single artist in a collective), when
+            // merged only the catalog.eno of the main catalog applies.
+            if included_fair_dir.catalog_manifest.is_some() {
+                let warning = format!("The catalog.eno manifest of the included catalog '{}' is ignored, only the catalog.eno manifest of the main catalog applies when catalogs are merged", included_dir.display());
+                build.warning(WarningCategory::IgnoredFile, &warning);
+            }
+
+            self.read_unknown_fair_dir(build, cache, included_fair_dir, &catalog_overrides);
+        }
+    }
+
+    fn read_playlist_dir(&mut self, build: &mut Build, fair_dir: FairDir) {
+        if !fair_dir.audio_files.is_empty() {
+            let error = format!("Audio files were encountered in the playlist directory '{}' but will be ignored - playlists reference tracks of existing releases by their permalink and track number instead", fair_dir.path.display());
+            build.error(&error);
+        }
+
+        if !fair_dir.dirs.is_empty() {
+            let warning = format!("The playlist directory '{}' contains subdirectories, these are ignored", fair_dir.path.display());
+            build.warning(WarningCategory::IgnoredDirectory, &warning);
+        }
+
+        let playlist_manifest = fair_dir.playlist_manifest.as_ref().unwrap();
+
+        if build.verbose {
+            info!("Reading playlist manifest {}", playlist_manifest.display());
+        }
+        manifest::read_playlist_manifest(
+            build,
+            self,
+            &fair_dir.path,
+            playlist_manifest
+        );
     }
 
     fn read_release_dir(
@@ -615,11 +1374,12 @@ impl Catalog {
 
             let mutually_exclusive_manifests =
                 fair_subdir.artist_manifest.is_some() as usize +
+                fair_subdir.playlist_manifest.is_some() as usize +
                 fair_subdir.release_manifest.is_some() as usize +
                 fair_subdir.track_manifest.is_some() as usize;
 
             if mutually_exclusive_manifests > 1 {
-                let error = format!("A directory in a faircamp catalog may only ever contain a catalog.eno, release.eno or track.eno manifest (one of them), but the directory '{}' contains {mutually_exclusive_manifests} of these. The directory will be ignored until this is resolved.", fair_subdir.path.display());
+                let error = format!("A directory in a faircamp catalog may only ever contain an artist.eno, playlist.eno, release.eno or track.eno manifest (one of them), but the directory '{}' contains {mutually_exclusive_manifests} of these. The directory will be ignored until this is resolved.", fair_subdir.path.display());
                 build.error(&error);
                 continue;
             }
@@ -634,7 +1394,19 @@ impl Catalog {
                 continue;
             }
 
-            if fair_subdir.audio_files.len() == 1 {
+            if fair_subdir.playlist_manifest.is_some() {
+                self.read_playlist_dir(build, fair_subdir);
+                continue;
+            }
+
+            // A track directory without an audio file is still valid if
+            // the track manifest references a remote source instead - this
+            // is validated when reading the track directory.
+            let remote_track_dir =
+                fair_subdir.audio_files.is_empty() &&
+                fair_subdir.track_manifest.is_some();
+
+            if fair_subdir.audio_files.len() == 1 || remote_track_dir {
                 let result = self.read_track_dir(
                     build,
                     cache,
@@ -649,7 +1421,8 @@ impl Catalog {
                 continue;
             }
 
-            warn!("Ignoring release subdirectory '{}' - if you meant to make it a track directory it must contain exactly one audio file (plus a track.eno manifest and auxiliary files potentially)", fair_subdir.path.display());
+            let warning = format!("Ignoring release subdirectory '{}' - if you meant to make it a track directory it must contain exactly one audio file, or a track.eno manifest with a remote_source (plus auxiliary files potentially)", fair_subdir.path.display());
+            build.warning(WarningCategory::IgnoredDirectory, &warning);
         }
 
         for audio_path in &fair_dir.audio_files {
@@ -850,7 +1623,8 @@ impl Catalog {
             };
 
             if cover.as_ref().is_some_and(|described_image| described_image.description.is_none()) {
-                warn_discouraged!("The cover image for release '{}' is missing an image description.", title);
+                let warning = format!("The cover image for release '{}' is missing an image description.", title);
+                build.warning(WarningCategory::MissingImageDescription, &warning);
                 build.missing_image_descriptions = true;
             }
 
@@ -874,28 +1648,106 @@ impl Catalog {
                 extras.push(Extra::new(file_meta));
             }
 
-            let download_access = finalized_overrides.release_download_access.assemble(
+            let mut download_access = finalized_overrides.release_download_access.assemble(
                 finalized_overrides,
                 &finalized_overrides.release_price
             );
 
             let release_dir_relative_to_catalog = fair_dir.path.strip_prefix(&build.catalog_dir).unwrap().to_path_buf();
 
+            let mut embedding = finalized_overrides.embedding;
+            let mut permalink = local_options.permalink.take();
+            let mut unlisted = local_options.unlisted_release;
+
+            // Drafts are only built on request, and then kept off the
+            // homepage, feeds and search engines (which all applies to
+            // unlisted releases), so they can be shared for previewing.
+            if local_options.draft {
+                if !build.include_drafts {
+                    info!("Release '{title}' is a draft and therefore not included in the build (use --include-drafts to include it).");
+                    return;
+                }
+
+                unlisted = true;
+            }
+
+            // Until its publish date has come, a release is left out of the
+            // build, or (if so configured) built as unlisted.
+            if let Some(publish_date) = &local_options.publish_date {
+                if build.build_begin.date_naive() < publish_date.date {
+                    build.pending_releases.push((title.to_string(), publish_date.date));
+
+                    if !publish_date.unlisted_before {
+                        info!("Release '{title}' will be published on {}, until then it is not included in the build.", publish_date.date);
+                        return;
+                    }
+
+                    unlisted = true;
+                }
+            }
+
+            // A release with a secret link is published for streaming only,
+            // unlisted and under a permalink that is derived from the expiry
+            // date (and thereby also part of all its track urls). Once the
+            // link has expired the release is left out of the build.
+            if let Some(expires) = local_options.secret_link {
+                if build.build_begin.date_naive() > expires {
+                    info!("The secret link of release '{title}' expired on {expires}, the release is therefore not included in the build.");
+                    return;
+                }
+
+                let secret_permalink = Permalink::secret(build, expires, &release_dir_relative_to_catalog);
+
+                info!("Release '{title}' is available through its secret link /{}/ until {expires}.", secret_permalink.slug);
+
+                download_access = DownloadAccess::Disabled;
+                embedding = false;
+                permalink = Some(secret_permalink);
+                unlisted = true;
+
+                for track in release_tracks.iter_mut() {
+                    track.download_access = DownloadAccess::Disabled;
+                    track.embedding = false;
+                }
+            }
+
             let release = Release::new(
+                finalized_overrides.archive_cover.clone(),
+                finalized_overrides.archive_layout.clone(),
+                local_options.content_warning.take(),
                 finalized_overrides.copy_link,
                 cover,
-                local_options.release_date.take(),
+                finalized_overrides.cover_aspect_ratio,
+                finalized_overrides.cover_svg_original,
+                local_options.crossfade,
+                // Without an explicit date, the release is dated to the day it is published
+                local_options.release_date.take().or(local_options.publish_date.as_ref().map(|publish_date| publish_date.date)),
                 download_access,
                 finalized_overrides.release_downloads.clone(),
-                finalized_overrides.embedding,
+                local_options.draft,
+                local_options.embed_support_button,
+                embedding,
                 finalized_overrides.release_extras.clone(),
                 extras,
+                local_options.format_comparison.take(),
+                local_options.gapless,
+                local_options.imprint.take(),
+                local_options.release_layout.take().unwrap_or_else(|| ReleaseSection::DEFAULT.to_vec()),
                 mem::take(&mut local_options.links),
+                manifest::read_overlay_manifests(build, "release", &fair_dir.overlay_manifests),
                 finalized_overrides.m3u_enabled,
                 main_artists_to_map,
                 local_options.more.take(),
                 finalized_overrides.more_label.clone(),
-                local_options.permalink.take(),
+                local_options.noindex,
+                mem::take(&mut local_options.page_assets),
+                local_options.page_metadata.take(),
+                finalized_overrides.payment_pointer.clone(),
+                permalink,
+                mem::take(&mut local_options.physical_editions),
+                finalized_overrides.podcast_categories.clone(),
+                local_options.region.take(),
+                finalized_overrides.share_buttons.clone(),
                 release_dir_relative_to_catalog,
                 finalized_overrides.speed_controls,
                 support_artists_to_map,
@@ -904,7 +1756,9 @@ impl Catalog {
                 title.to_string(),
                 finalized_overrides.track_numbering.clone(),
                 release_tracks,
-                local_options.unlisted_release
+                unlisted,
+                local_options.upc.take(),
+                finalized_overrides.volume_normalization
             );
 
             self.releases.push(ReleaseRc::new(release));
@@ -933,7 +1787,9 @@ impl Catalog {
         let theme = overrides.theme.clone();
 
         Track::new(
+            mem::take(&mut local_options.annotations),
             artists_to_map,
+            mem::take(&mut local_options.chapters),
             overrides.copy_link,
             cover,
             download_access,
@@ -941,7 +1797,9 @@ impl Catalog {
             overrides.embedding,
             overrides.track_extras,
             extras,
+            local_options.isrc.take(),
             local_options.links,
+            local_options.lyrics.take(),
             local_options.more.take(),
             // TODO: There is a general design issue here: Overriding (= inheriting across
             // catalog/artist/release/track) the more_label makes sense from the perspective
@@ -952,13 +1810,21 @@ impl Catalog {
             // inheritance towards certain child nodes). But this needs to be carefully considered
             // as to stay manageable/compatible with potential future GUI usage.
             overrides.more_label.clone(),
+            local_options.noindex,
+            local_options.page_assets,
+            local_options.page_metadata.take(),
+            overrides.payment_pointer.clone(),
+            overrides.share_buttons.clone(),
             overrides.speed_controls,
             overrides.streaming_quality,
             local_options.synopsis.take(),
             overrides.tag_agenda.clone(),
             theme,
             local_options.title.take(),
-            transcodes
+            transcodes,
+            local_options.transcript.take(),
+            local_options.visual_loop.take(),
+            overrides.volume_normalization
         )
     }
 
@@ -988,10 +1854,41 @@ impl Catalog {
 
         let finalized_overrides = local_overrides.as_ref().unwrap_or(parent_overrides);
 
-        let audio_path = fair_dir.audio_files.first().unwrap();
+        let audio_path = match &local_options.remote_source {
+            Some(remote_source) => {
+                if !fair_dir.audio_files.is_empty() {
+                    let error = format!("The track directory '{}' contains an audio file, but its manifest also specifies a remote_source - the audio file will be ignored until this is resolved.", fair_dir.path.display());
+                    build.error(&error);
+                }
+
+                match remote_source.fetch(build, &mut cache.remote_downloads) {
+                    Ok(local_path) => local_path,
+                    Err(err) => {
+                        let error = format!("Skipping track directory '{}' because its remote source could not be fetched: {err}", fair_dir.path.display());
+                        build.error(&error);
+                        return None;
+                    }
+                }
+            }
+            None => match fair_dir.audio_files.first() {
+                Some(audio_path) => audio_path.clone(),
+                None => {
+                    let warning = format!("Ignoring track directory '{}' - it contains neither an audio file, nor does its track.eno manifest specify a remote_source", fair_dir.path.display());
+                    build.warning(WarningCategory::IgnoredDirectory, &warning);
+                    return None;
+                }
+            }
+        };
 
         let extension = audio_path.extension().unwrap().to_str().unwrap().to_lowercase().as_str().to_string();
-        let path_relative_to_catalog = audio_path.strip_prefix(&build.catalog_dir).unwrap();
+
+        // The local copies of remote sources are kept in the cache directory,
+        // which is not necessarily inside the catalog directory - their path
+        // stays absolute then (joining it onto the catalog directory later
+        // on leaves it unchanged).
+        let path_relative_to_catalog = audio_path
+            .strip_prefix(&build.catalog_dir)
+            .unwrap_or(&audio_path);
 
         if build.verbose {
             info!("Reading track {}", path_relative_to_catalog.display());
@@ -1006,6 +1903,15 @@ impl Catalog {
             }
         };
 
+        // The local copy of a remote source is named after its content hash,
+        // so if there is no title in the manifest or the audio metadata we
+        // fall back to the filename in the url instead.
+        if let Some(remote_source) = &local_options.remote_source {
+            if local_options.title.is_none() && transcodes.borrow().source_meta.title.is_none() {
+                local_options.title = remote_source.file_stem();
+            }
+        }
+
         // Process bare image paths into ImageRc representations
         let images: Vec<ImageRcView> = fair_dir.image_files
             .into_iter()
@@ -1043,7 +1949,8 @@ impl Catalog {
         };
 
         if cover.as_ref().is_some_and(|described_image| described_image.description.is_none()) {
-            warn_discouraged!("The cover image for track '{}' is missing an image description.", title);
+            let warning = format!("The cover image for track '{}' is missing an image description.", title);
+            build.warning(WarningCategory::MissingImageDescription, &warning);
             build.missing_image_descriptions = true;
         }
 
@@ -1061,12 +1968,63 @@ impl Catalog {
             extras.push(extra);
         }
 
+        // The source of a visual loop is neither an extra nor an unhandled
+        // subdirectory, it is processed separately.
+        let visual_loop_path = local_options.visual_loop
+            .as_ref()
+            .map(|visual_loop| match &visual_loop.source {
+                VisualLoopSource::ImageSequence { path, .. } |
+                VisualLoopSource::Video(path) => build.catalog_dir.join(path)
+            });
+
         for extra_path in fair_dir.extra_files {
+            if visual_loop_path.as_ref() == Some(&extra_path) {
+                continue;
+            }
+
             let path_relative_to_catalog = extra_path.strip_prefix(&build.catalog_dir).unwrap();
             let file_meta = FileMeta::new(build, path_relative_to_catalog);
             extras.push(Extra::new(file_meta));
         }
 
+        // Without lyrics given in the manifest, an .lrc file or a file named
+        // lyrics.txt in the track directory is used (and still offered for
+        // download as an extra, like any other file in the directory).
+        if local_options.lyrics.is_none() {
+            let lyrics_path = fair_dir.extra_files
+                .iter()
+                .find(|path| path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("lrc")))
+                .or_else(|| fair_dir.extra_files
+                    .iter()
+                    .find(|path| path.file_name().is_some_and(|file_name| file_name.eq_ignore_ascii_case("lyrics.txt"))));
+
+            if let Some(lyrics_path) = lyrics_path {
+                match Lyrics::read(lyrics_path) {
+                    Ok(lyrics) => local_options.lyrics = Some(lyrics),
+                    Err(err) => {
+                        let warning = format!("Ignoring lyrics file '{}' ({err})", lyrics_path.display());
+                        build.warning(WarningCategory::IgnoredFile, &warning);
+                    }
+                }
+            }
+        }
+
+        // The transcript is offered for download alongside the track, also
+        // when it is not placed inside the track directory itself.
+        if let Some(transcript) = &local_options.transcript {
+            if !extras.iter().any(|extra| extra.file_meta.path == transcript.path) {
+                let file_meta = FileMeta::new(build, &transcript.path);
+                extras.push(Extra::new(file_meta));
+            }
+        }
+
+        if let Some(visual_loop) = &local_options.visual_loop {
+            if visual_loop.description.is_none() {
+                let warning = format!("The visual loop for track '{}' is missing a description.", title);
+                build.warning(WarningCategory::MissingImageDescription, &warning);
+            }
+        }
+
         let track = self.read_track(
             cover,
             extras,
@@ -1076,6 +2034,10 @@ impl Catalog {
         );
 
         for dir_path in &fair_dir.dirs {
+            if visual_loop_path.as_ref() == Some(dir_path) {
+                continue;
+            }
+
             // TODO: We could consider supporting artist directories as
             // subdirectories of track directories, as that somehow would
             // make sense.
@@ -1100,13 +2062,29 @@ impl Catalog {
             build.error(&error);
         }
 
+        self.read_unknown_fair_dir(build, cache, fair_dir, parent_overrides);
+    }
+
+    /// Reads a directory whose role is yet to be determined by its content
+    /// (artist, playlist or release directory, or a directory that merely
+    /// groups other directories).
+    fn read_unknown_fair_dir(
+        &mut self,
+        build: &mut Build,
+        cache: &mut Cache,
+        fair_dir: FairDir,
+        parent_overrides: &Overrides
+    ) {
+        let path = fair_dir.path.clone();
+
         let mutually_exclusive_manifests =
             fair_dir.artist_manifest.is_some() as usize +
+            fair_dir.playlist_manifest.is_some() as usize +
             fair_dir.release_manifest.is_some() as usize +
             fair_dir.track_manifest.is_some() as usize;
 
         if mutually_exclusive_manifests > 1 {
-            let error = format!("A directory in a faircamp catalog may only ever contain a catalog.eno, release.eno or track.eno manifest (one of them), but the directory '{}' contains {mutually_exclusive_manifests} of these. The directory will be ignored until this is resolved.", path.display());
+            let error = format!("A directory in a faircamp catalog may only ever contain an artist.eno, playlist.eno, release.eno or track.eno manifest (one of them), but the directory '{}' contains {mutually_exclusive_manifests} of these. The directory will be ignored until this is resolved.", path.display());
             build.error(&error);
             return;
         }
@@ -1121,6 +2099,11 @@ impl Catalog {
             return;
         }
 
+        if fair_dir.playlist_manifest.is_some() {
+            self.read_playlist_dir(build, fair_dir);
+            return;
+        }
+
         if fair_dir.release_manifest.is_some() {
             self.read_release_dir(
                 build,
@@ -1152,6 +2135,106 @@ impl Catalog {
         }
     }
 
+    /// Resolves the filename references in the page_metadata of all artists,
+    /// releases and tracks, looking them up in their own page_assets first
+    /// and in the catalog's site_assets second. Prints an error for every
+    /// unresolved reference and returns false if there were any.
+    fn resolve_page_metadata(&self) -> bool {
+        let mut resolved = true;
+
+        let mut resolve = |page_assets: &[SiteAsset], page_metadata: &mut Option<SiteMetadata>, context: String| {
+            if let Some(page_metadata) = page_metadata {
+                if let Err(missing_filenames) = page_metadata.resolve_filename_references(page_assets, &self.site_assets) {
+                    for filename in &missing_filenames {
+                        error!("The filename reference {{{}}} inside the page_metadata of {} could not be resolved.", filename, context);
+                    }
+
+                    resolved = false;
+                }
+            }
+        };
+
+        for artist in &self.artists {
+            let mut artist_mut = artist.borrow_mut();
+            let artist_mut = &mut *artist_mut;
+            let context = format!("artist '{}'", artist_mut.name);
+            resolve(&artist_mut.page_assets, &mut artist_mut.page_metadata, context);
+        }
+
+        for release in &self.releases {
+            let mut release_mut = release.borrow_mut();
+            let release_mut = &mut *release_mut;
+            let context = format!("release '{}'", release_mut.title);
+            resolve(&release_mut.page_assets, &mut release_mut.page_metadata, context);
+
+            for track in release_mut.tracks.iter_mut() {
+                let context = format!("track '{}'", track.title());
+                resolve(&track.page_assets, &mut track.page_metadata, context);
+            }
+        }
+
+        resolved
+    }
+
+    /// Resolves the track references (release permalink and track number)
+    /// of all playlists. References that can not be resolved are reported
+    /// as errors and left out, as are playlists that end up without tracks.
+    fn resolve_playlist_tracks(&mut self, build: &mut Build) {
+        for playlist in self.playlists.iter_mut() {
+            for (release_slug, track_number) in &playlist.references {
+                let release = self.releases
+                    .iter()
+                    .find(|release| &release.borrow().permalink.slug == release_slug);
+
+                match release {
+                    Some(release) => {
+                        let num_tracks = release.borrow().tracks.len();
+                        if *track_number <= num_tracks {
+                            playlist.tracks.push(PlaylistTrack {
+                                release: release.clone(),
+                                track_number: *track_number
+                            });
+                        } else {
+                            let error = format!("The playlist '{}' references track number {track_number} of the release with the permalink '{release_slug}', but that release only has {num_tracks} tracks.", playlist.title);
+                            build.error(&error);
+                        }
+                    }
+                    None => {
+                        let error = format!("The playlist '{}' references a track of a release with the permalink '{release_slug}', but no release with that permalink exists (note that draft releases are only included with --include-drafts).", playlist.title);
+                        build.error(&error);
+                    }
+                }
+            }
+        }
+
+        self.playlists.retain(|playlist| !playlist.tracks.is_empty());
+    }
+
+    /// Restores the catalog to its original state after it was narrowed down
+    /// to a single artist through Catalog::narrow_to_artist.
+    pub fn restore_scope(&mut self, scope: CatalogScope) {
+        for featured_artist in &scope.featured_artists {
+            featured_artist.borrow_mut().featured = true;
+        }
+
+        self.artist = scope.artist;
+        self.featured_artists = scope.featured_artists;
+        self.filter_permalink = scope.filter_permalink;
+        self.filters = scope.filters;
+        self.hero = scope.hero;
+        self.home_image = scope.home_image;
+        self.label_mode = true;
+        self.links = scope.links;
+        self.more = scope.more;
+        self.more_label = scope.more_label;
+        self.playlists = scope.playlists;
+        self.releases = scope.releases;
+        self.synopsis = scope.synopsis;
+        self.theme = scope.theme;
+        self.title = scope.title;
+        self.year_archive_pages = scope.year_archive_pages;
+    }
+
     // TODO: Should we have a manifest option for setting the catalog.artist manually in edge cases?
     /// Uses a heuristic to determine the main artist of the faircamp site (used only
     /// when the site is in artist mode)
@@ -1196,6 +2279,127 @@ impl Catalog {
         self.title = Some(title);
     }
 
+    /// Used with `permalink_conflicts: auto` - auto-generated permalinks
+    /// that conflict with another permalink get a numeric suffix (-2, -3,
+    /// ...). User-assigned permalinks are never changed, and claim their
+    /// slug first. Releases are processed in the order of their source
+    /// directory and artists in the order of their name, so the outcome
+    /// does not depend on the order in which the catalog was read.
+    fn suffix_conflicting_permalinks(&mut self, build: &mut Build) {
+        let mut used_slugs = HashSet::new();
+
+        for release in &self.releases {
+            let release_ref = release.borrow();
+            if !release_ref.permalink.generated {
+                used_slugs.insert(release_ref.permalink.slug.clone());
+            }
+        }
+
+        for artist in &self.featured_artists {
+            let artist_ref = artist.borrow();
+            if !artist_ref.permalink.generated {
+                used_slugs.insert(artist_ref.permalink.slug.clone());
+            }
+        }
+
+        for playlist in &self.playlists {
+            if !playlist.permalink.generated {
+                used_slugs.insert(playlist.permalink.slug.clone());
+            }
+        }
+
+        let mut generated_releases = self.releases
+            .iter()
+            .filter(|release| release.borrow().permalink.generated)
+            .collect::<Vec<&ReleaseRc>>();
+
+        generated_releases.sort_by(|a, b| a.borrow().source_dir.cmp(&b.borrow().source_dir));
+
+        for release in generated_releases {
+            let mut release_mut = release.borrow_mut();
+
+            if let Some(suffixed_slug) = claim_slug(&mut used_slugs, &release_mut.permalink.slug) {
+                let warning = format!(
+                    "The auto-generated permalink '{}' of the release '{}' from directory '{}' conflicts with another permalink, it was changed to '{}' because permalink_conflicts is set to 'auto'. Assign a permalink to the release to make this permanent, as the suffix can change when releases are added or removed.",
+                    release_mut.permalink.slug,
+                    release_mut.title,
+                    release_mut.source_dir.display(),
+                    suffixed_slug
+                );
+                build.warning(WarningCategory::PermalinkConflict, &warning);
+
+                release_mut.permalink.slug = suffixed_slug;
+            }
+        }
+
+        let mut generated_artists = self.featured_artists
+            .iter()
+            .filter(|artist| artist.borrow().permalink.generated)
+            .collect::<Vec<&ArtistRc>>();
+
+        generated_artists.sort_by(|a, b| a.borrow().name.cmp(&b.borrow().name));
+
+        for artist in generated_artists {
+            let mut artist_mut = artist.borrow_mut();
+
+            if let Some(suffixed_slug) = claim_slug(&mut used_slugs, &artist_mut.permalink.slug) {
+                let warning = format!(
+                    "The auto-generated permalink '{}' of the artist '{}' conflicts with another permalink, it was changed to '{}' because permalink_conflicts is set to 'auto'. Assign a permalink to the artist to make this permanent, as the suffix can change when releases or artists are added or removed.",
+                    artist_mut.permalink.slug,
+                    artist_mut.name,
+                    suffixed_slug
+                );
+                build.warning(WarningCategory::PermalinkConflict, &warning);
+
+                artist_mut.permalink.slug = suffixed_slug;
+            }
+        }
+
+        let mut generated_playlists = self.playlists
+            .iter_mut()
+            .filter(|playlist| playlist.permalink.generated)
+            .collect::<Vec<&mut Playlist>>();
+
+        generated_playlists.sort_by(|a, b| a.source_dir.cmp(&b.source_dir));
+
+        for playlist in generated_playlists {
+            if let Some(suffixed_slug) = claim_slug(&mut used_slugs, &playlist.permalink.slug) {
+                let warning = format!(
+                    "The auto-generated permalink '{}' of the playlist '{}' from directory '{}' conflicts with another permalink, it was changed to '{}' because permalink_conflicts is set to 'auto'. Assign a permalink to the playlist to make this permanent, as the suffix can change when releases, artists or playlists are added or removed.",
+                    playlist.permalink.slug,
+                    playlist.title,
+                    playlist.source_dir.display(),
+                    suffixed_slug
+                );
+                build.warning(WarningCategory::PermalinkConflict, &warning);
+
+                playlist.permalink.slug = suffixed_slug;
+            }
+        }
+    }
+
+    /// Swaps in the translated texts (from e.g. release.de.eno overlays) of
+    /// the catalog, its artists and releases for the given language (one of
+    /// the catalog's `languages`), or restores the original texts if None is
+    /// passed.
+    pub fn switch_language(&mut self, language: Option<&str>) {
+        self.localizations.switch(language, &mut self.more, &mut self.synopsis, &mut self.title);
+
+        for artist in &self.artists {
+            let mut artist_mut = artist.borrow_mut();
+            let artist_mut = &mut *artist_mut;
+            artist_mut.localizations.switch(language, &mut artist_mut.more, &mut artist_mut.synopsis, &mut None);
+        }
+
+        for release in &self.releases {
+            let mut release_mut = release.borrow_mut();
+            let release_mut = &mut *release_mut;
+            let mut title = Some(mem::take(&mut release_mut.title));
+            release_mut.localizations.switch(language, &mut release_mut.more, &mut release_mut.synopsis, &mut title);
+            release_mut.title = title.unwrap();
+        }
+    }
+
     pub fn title(&self) -> String {
         if let Some(catalog_title) = &self.title {
             return catalog_title.to_string()
@@ -1238,6 +2442,7 @@ impl Catalog {
             } else {
                 let label = match usage {
                     PermalinkUsage::Artist(artist) => format!("artist '{}'", artist.borrow().name),
+                    PermalinkUsage::Playlist(playlist) => format!("playlist '{}'", playlist.title),
                     PermalinkUsage::Release(release) => format!("release '{}'", release.borrow().title)
                 };
 
@@ -1311,6 +2516,7 @@ impl Catalog {
                             resolve the conflict.
                         "#)
                     }
+                    PermalinkUsage::Playlist(_) |
                     PermalinkUsage::Release(_) => PERMALINK_CONFLICT_RESOLUTION_HINT
                 };
 
@@ -1332,13 +2538,34 @@ impl Catalog {
             }
         }
 
-        match generated_permalinks {
-            (None, None, None, 0) => (),
-            (Some(first), None, None, 0) => warn!("The {} has no user-assigned permalink, it is recommended to assign one.", first),
-            (Some(first), Some(second), None, 0) => warn!("The {} and the {} have no user-assigned permalinks, it is recommended to assign some.", first, second),
-            (Some(first), Some(second), Some(third), 0) => warn!("The {}, the {} and the {} have no user-assigned permalinks, it is recommended to assign some.", first, second, third),
-            (Some(first), Some(second), Some(third), further) => warn!("The {}, the {}, the {} and {} other things have no user-assigned permalinks, it is recommended to assign some.", first, second, third, further),
+        for playlist in &self.playlists {
+            if let Some(previous_usage) = used_permalinks.get(&playlist.permalink.slug) {
+                let generated_or_assigned = &playlist.permalink.generated_or_assigned_str();
+                let slug = &playlist.permalink.slug;
+                let title = &playlist.title;
+                let previous_usage_formatted = previous_usage.as_string();
+                let playlist_dir = playlist.source_dir.display();
+                let error = format!("The {generated_or_assigned} permalink '{slug}' of the playlist '{title}' from directory '{playlist_dir}' conflicts with the {previous_usage_formatted}\n{PERMALINK_CONFLICT_RESOLUTION_HINT}");
+                build.error(&error);
+                no_conflicts = false;
+            } else {
+                let usage = PermalinkUsage::Playlist(playlist);
+                if playlist.permalink.generated { add_generated_usage(&usage); }
+                used_permalinks.insert(playlist.permalink.slug.to_string(), usage);
+            }
+        }
+
+        let warning = match generated_permalinks {
+            (None, None, None, 0) => None,
+            (Some(first), None, None, 0) => Some(format!("The {} has no user-assigned permalink, it is recommended to assign one.", first)),
+            (Some(first), Some(second), None, 0) => Some(format!("The {} and the {} have no user-assigned permalinks, it is recommended to assign some.", first, second)),
+            (Some(first), Some(second), Some(third), 0) => Some(format!("The {}, the {} and the {} have no user-assigned permalinks, it is recommended to assign some.", first, second, third)),
+            (Some(first), Some(second), Some(third), further) => Some(format!("The {}, the {}, the {} and {} other things have no user-assigned permalinks, it is recommended to assign some.", first, second, third, further)),
             _ => unreachable!()
+        };
+
+        if let Some(warning) = warning {
+            build.warning(WarningCategory::MissingPermalink, &warning);
         }
 
         no_conflicts
@@ -1352,71 +2579,98 @@ impl Catalog {
             write_background_image(build, image);
         }
 
-        if let Some(described_image) = &self.home_image {
-            let mut image_mut = described_image.borrow_mut();
-            let source_path = &described_image.file_meta.path;
-            // Write home image as poster image for homepage
-            let poster_assets = image_mut.artist_assets(build, source_path);
-
-            for asset in &poster_assets.all() {
-                let target_filename = asset.target_filename();
-
-                util::hard_link_or_copy(
-                    build.cache_dir.join(&asset.filename),
-                    build.build_dir.join(&target_filename)
-                );
-
-                build.reserve_filename(target_filename);
-                build.stats.add_image(asset.filesize_bytes);
-            }
-
-            // Write home image as feed image
-            if build.base_url.is_some() && self.feeds.any_requested() {
+        // Embeds only need the audio files and theme assets of the releases
+        // and tracks they present - in an embeds-only build no home, hero and
+        // artist images (and further below, no covers and downloads) are
+        // written.
+        if !build.embeds_only {
+            if let Some(described_image) = &self.home_image {
+                let mut image_mut = described_image.borrow_mut();
                 let source_path = &described_image.file_meta.path;
-                let feed_image_asset = image_mut.feed_asset(build, source_path);
+                // Write home image as poster image for homepage
+                let poster_assets = image_mut.artist_assets(build, source_path);
 
-                util::hard_link_or_copy(
-                    build.cache_dir.join(&feed_image_asset.filename),
-                    build.build_dir.join(FeedImageAsset::TARGET_FILENAME)
-                );
+                for asset in &poster_assets.all() {
+                    let target_filename = asset.target_filename();
 
-                build.reserve_filename(FeedImageAsset::TARGET_FILENAME);
-                build.stats.add_image(feed_image_asset.filesize_bytes);
-            }
+                    util::hard_link_or_copy(
+                        build.cache_dir.join(&asset.filename),
+                        build.build_dir.join(&target_filename)
+                    );
 
-            image_mut.persist_to_cache(&build.cache_dir);
-        }
+                    build.reserve_filename(target_filename);
+                    build.stats.add_image(asset.filesize_bytes);
+                }
 
-        for artist in self.featured_artists.iter_mut() {
-            let artist_ref = artist.borrow();
+                // Write home image as feed image
+                if build.base_url.is_some() && self.feeds.any_requested() {
+                    let source_path = &described_image.file_meta.path;
+                    let feed_image_asset = image_mut.feed_asset(build, source_path);
+
+                    util::hard_link_or_copy(
+                        build.cache_dir.join(&feed_image_asset.filename),
+                        build.build_dir.join(FeedImageAsset::TARGET_FILENAME)
+                    );
 
-            if let Some(described_image) = &artist_ref.image {
-                // Write artist dir
-                let artist_dir = build.build_dir.join(&artist_ref.permalink.slug);
-                build.reserve_filename(artist_ref.permalink.slug.clone());
-                util::ensure_dir_all(&artist_dir);
+                    build.reserve_filename(FeedImageAsset::TARGET_FILENAME);
+                    build.stats.add_image(feed_image_asset.filesize_bytes);
+                }
 
-                // Write artist image as poster image
+                image_mut.persist_to_cache(&build.cache_dir);
+            }
 
+            if let Some(described_image) = self.hero.as_ref().and_then(|hero| hero.custom_image()) {
                 let mut image_mut = described_image.borrow_mut();
                 let source_path = &described_image.file_meta.path;
+                // Write hero image as poster image for homepage
                 let poster_assets = image_mut.artist_assets(build, source_path);
 
                 for asset in &poster_assets.all() {
+                    let target_filename = format!("{}{}", Hero::IMAGE_FILENAME_PREFIX, asset.target_filename());
+
                     util::hard_link_or_copy(
                         build.cache_dir.join(&asset.filename),
-                        artist_dir.join(asset.target_filename())
+                        build.build_dir.join(&target_filename)
                     );
 
+                    build.reserve_filename(target_filename);
                     build.stats.add_image(asset.filesize_bytes);
                 }
 
                 image_mut.persist_to_cache(&build.cache_dir);
             }
 
-            // Write artist theme background image
-            if let Some(image) = &artist_ref.theme.background_image {
-                write_background_image(build, image);
+            for artist in self.featured_artists.iter_mut() {
+                let artist_ref = artist.borrow();
+
+                if let Some(described_image) = &artist_ref.image {
+                    // Write artist dir
+                    let artist_dir = build.build_dir.join(&artist_ref.permalink.slug);
+                    build.reserve_filename(artist_ref.permalink.slug.clone());
+                    util::ensure_dir_all(&artist_dir);
+
+                    // Write artist image as poster image
+
+                    let mut image_mut = described_image.borrow_mut();
+                    let source_path = &described_image.file_meta.path;
+                    let poster_assets = image_mut.artist_assets(build, source_path);
+
+                    for asset in &poster_assets.all() {
+                        util::hard_link_or_copy(
+                            build.cache_dir.join(&asset.filename),
+                            artist_dir.join(asset.target_filename())
+                        );
+
+                        build.stats.add_image(asset.filesize_bytes);
+                    }
+
+                    image_mut.persist_to_cache(&build.cache_dir);
+                }
+
+                // Write artist theme background image
+                if let Some(image) = &artist_ref.theme.background_image {
+                    write_background_image(build, image);
+                }
             }
         }
 
@@ -1427,8 +2681,13 @@ impl Catalog {
             .unwrap_or(0);
 
         for release in &self.releases {
+            let release_begin = Instant::now();
             let mut release_mut = release.borrow_mut();
 
+            if build.embeds_only && !release_mut.embedding && !release_mut.tracks.iter().any(|track| track.embedding) {
+                continue;
+            }
+
             // Write release dir
             let release_dir = build.build_dir.join(&release_mut.permalink.slug);
             build.reserve_filename(release_mut.permalink.slug.clone());
@@ -1440,11 +2699,15 @@ impl Catalog {
             }
 
             // Write release cover image
-            if let Some(described_image) = &release_mut.cover {
+            if build.embeds_only {
+                // Not shown in embeds
+            } else if let Some(described_image) = &release_mut.cover {
                 let mut image_mut = described_image.borrow_mut();
                 let source_path = &described_image.file_meta.path;
                 let cover_assets = image_mut.cover_assets(build, source_path);
 
+                warn_if_undersized_cover(build, cover_assets.source_edge_size, source_path);
+
                 for asset in &cover_assets.all() {
                     util::hard_link_or_copy(
                         build.cache_dir.join(&asset.filename),
@@ -1454,6 +2717,29 @@ impl Catalog {
                     build.stats.add_image(asset.filesize_bytes);
                 }
 
+                // The square assets are still needed for listings and embeds
+                if release_mut.cover_aspect_ratio == CoverAspectRatio::Original {
+                    let cover_assets_uncropped = image_mut.cover_assets_uncropped(build, source_path);
+
+                    for asset in &cover_assets_uncropped.all() {
+                        util::hard_link_or_copy(
+                            build.cache_dir.join(&asset.filename),
+                            release_dir.join(asset.target_filename())
+                        );
+
+                        build.stats.add_image(asset.filesize_bytes);
+                    }
+                }
+
+                if release_mut.cover_svg().is_some() {
+                    util::hard_link_or_copy(
+                        build.catalog_dir.join(source_path),
+                        release_dir.join(COVER_SVG_FILENAME)
+                    );
+
+                    build.stats.add_image(described_image.file_meta.size);
+                }
+
                 image_mut.persist_to_cache(&build.cache_dir);
             } else {
                 let procedural_cover = cache.get_or_create_procedural_cover(
@@ -1485,18 +2771,93 @@ impl Catalog {
                 release_mut.procedural_cover = Some(procedural_cover);
             }
 
+            // Write photos of physical editions
+            if !build.embeds_only {
+                for (physical_edition, edition_number) in release_mut.physical_editions.iter().zip(1..) {
+                    for (described_image, photo_number) in physical_edition.photos.iter().zip(1..) {
+                        let mut image_mut = described_image.borrow_mut();
+                        let source_path = &described_image.file_meta.path;
+                        let photo_assets = image_mut.artist_assets(build, source_path);
+                        let prefix = PhysicalEdition::photo_filename_prefix(edition_number, photo_number);
+
+                        for asset in &photo_assets.all() {
+                            util::hard_link_or_copy(
+                                build.cache_dir.join(&asset.filename),
+                                release_dir.join(format!("{prefix}{}", asset.target_filename()))
+                            );
+
+                            build.stats.add_image(asset.filesize_bytes);
+                        }
+
+                        image_mut.persist_to_cache(&build.cache_dir);
+                    }
+                }
+            }
+
             // Prepare release cover image for optional embed usage
             let release_cover_path = release_mut.cover
                 .as_ref()
-                .map(|described_image| build.catalog_dir.join(&described_image.file_meta.path));
+                .map(|described_image| release_mut.archive_cover.embed_path(build, release_mut.cover_aspect_ratio, described_image));
 
+            let archive_cover = release_mut.archive_cover.clone();
+            let release_embedding = release_mut.embedding;
             let release_slug = release_mut.permalink.slug.clone();
 
             let tag_mappings: Vec<TagMapping> = release_mut.tracks.iter().zip(TRACK_NUMBERS)
                 .map(|(track, track_number)| TagMapping::new(&release_mut, track, track_number))
                 .collect();
 
-            for ((track, tag_mapping), track_number) in release_mut.tracks.iter_mut().zip(tag_mappings.iter()).zip(TRACK_NUMBERS) {
+            // Prepare track cover images for optional embed usage
+            let track_cover_paths: Vec<Option<PathBuf>> = release_mut.tracks
+                .iter()
+                .map(|track| {
+                    if build.embeds_only && !release_embedding && !track.embedding {
+                        return None;
+                    }
+
+                    track.cover
+                        .as_ref()
+                        .map(|described_image| archive_cover.embed_path(build, CoverAspectRatio::Square, described_image))
+                })
+                .collect();
+
+            // Transcode the streaming formats of the release's tracks ahead,
+            // running several ffmpeg processes at a time
+            let mut streaming_formats: Vec<AudioFormat> = Vec::new();
+            for track in &release_mut.tracks {
+                for streaming_format in track.streaming_quality.formats() {
+                    if !streaming_formats.contains(&streaming_format) {
+                        streaming_formats.push(streaming_format);
+                    }
+                }
+            }
+
+            for streaming_format in streaming_formats {
+                let tracks = release_mut.tracks
+                    .iter_mut()
+                    .zip(tag_mappings.iter())
+                    .zip(track_cover_paths.iter())
+                    .filter(|((track, _), _)| {
+                        (!build.embeds_only || release_embedding || track.embedding) &&
+                        track.streaming_quality.formats().contains(&streaming_format)
+                    })
+                    .map(|((track, tag_mapping), track_cover_path)| {
+                        (track, tag_mapping, track_cover_path.as_ref().or(release_cover_path.as_ref()))
+                    })
+                    .collect();
+
+                Track::transcode_in_parallel(build, AssetIntent::Deliverable, streaming_format, tracks);
+            }
+
+            for (((track, tag_mapping), track_cover_path), track_number) in release_mut.tracks
+                .iter_mut()
+                .zip(tag_mappings.iter())
+                .zip(track_cover_paths.iter())
+                .zip(TRACK_NUMBERS) {
+                if build.embeds_only && !release_embedding && !track.embedding {
+                    continue;
+                }
+
                 let track_dir = release_dir.join(track_number.to_string());
 
                 util::ensure_dir_all(&track_dir);
@@ -1507,11 +2868,15 @@ impl Catalog {
                 }
 
                 // Write track cover image
-                if let Some(described_image) = &track.cover {
+                if build.embeds_only {
+                    // Not shown in embeds
+                } else if let Some(described_image) = &track.cover {
                     let mut image_mut = described_image.borrow_mut();
                     let source_path = &described_image.file_meta.path;
                     let cover_assets = image_mut.cover_assets(build, source_path);
 
+                    warn_if_undersized_cover(build, cover_assets.source_edge_size, source_path);
+
                     for asset in &cover_assets.all() {
                         util::hard_link_or_copy(
                             build.cache_dir.join(&asset.filename),
@@ -1524,10 +2889,21 @@ impl Catalog {
                     image_mut.persist_to_cache(&build.cache_dir);
                 }
 
-                // Prepare track cover image for optional embed usage
-                let track_cover_path = track.cover
-                    .as_ref()
-                    .map(|described_image| build.catalog_dir.join(&described_image.file_meta.path));
+                // Write track visual loop (not shown in embeds)
+                if let Some(visual_loop) = track.visual_loop.as_ref().filter(|_| !build.embeds_only) {
+                    match cache.get_or_create_visual_loop(build, visual_loop) {
+                        Ok(visual_loop_assets) => {
+                            let mut visual_loop_assets_mut = visual_loop_assets.borrow_mut();
+
+                            visual_loop_assets_mut.write_to_build(build, &track_dir);
+                            visual_loop_assets_mut.unmark_stale();
+                        }
+                        Err(err) => {
+                            let error = format!("Could not generate the visual loop for track '{}': {err}", track.title());
+                            build.error(&error);
+                        }
+                    }
+                }
 
                 // Write track streaming audio files
                 for streaming_format in track.streaming_quality.formats() {
@@ -1574,7 +2950,47 @@ impl Catalog {
                 }
             }
 
-            release_mut.write_downloadable_files(build);
+            // Write hover preview clip (an excerpt of the first track)
+            if self.hover_previews && !build.embeds_only && !release_mut.tracks.is_empty() {
+                let track = &mut release_mut.tracks[0];
+
+                match track.create_preview(build) {
+                    Ok(()) => {
+                        let transcodes_ref = track.transcodes.borrow();
+                        let asset = transcodes_ref.preview.as_ref().unwrap();
+
+                        let hash = build.hash_with_salt(|hasher| {
+                            release_slug.hash(hasher);
+                            "preview".hash(hasher);
+                            asset.filename.hash(hasher);
+                        });
+
+                        let preview_filename = format!("preview-{hash}{}", PREVIEW_FORMAT.extension());
+
+                        util::hard_link_or_copy(
+                            build.cache_dir.join(&asset.filename),
+                            release_dir.join(&preview_filename)
+                        );
+
+                        build.stats.add_track(asset.filesize_bytes);
+
+                        transcodes_ref.persist_to_cache(&build.cache_dir);
+                        drop(transcodes_ref);
+
+                        release_mut.hover_preview = Some(preview_filename);
+                    }
+                    Err(err) => {
+                        let error = format!("Could not generate the hover preview for release '{}': {err}", release_mut.title);
+                        build.error(&error);
+                    }
+                }
+            }
+
+            if !build.embeds_only {
+                release_mut.write_downloadable_files(build);
+            }
+
+            build.timings.record_release(&release_mut.title, release_begin);
         }
     }
 
@@ -0,0 +1,34 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::util::parse_timecode;
+
+/// A named section of a track (e.g. a segment of a podcast episode or a
+/// piece within a long-form mix), configured through the track manifest's
+/// `chapters` option. Chapters are listed (with links to seek to them) on
+/// the track page and embedded into the transcoded audio files.
+#[derive(Clone, Debug)]
+pub struct Chapter {
+    /// Where the chapter starts
+    pub seconds: f32,
+    pub title: String
+}
+
+impl Chapter {
+    /// Parses a chapter given as a timecode followed by the title,
+    /// e.g. "12:30 Interview" (see [parse_timecode] for the timecode).
+    pub fn parse(value: &str) -> Option<Chapter> {
+        let (timecode, title) = value.trim().split_once(char::is_whitespace)?;
+        let seconds = parse_timecode(timecode)?;
+        let title = title.trim();
+
+        if title.is_empty() {
+            return None;
+        }
+
+        Some(Chapter {
+            seconds,
+            title: title.to_string()
+        })
+    }
+}
@@ -0,0 +1,48 @@
+// SPDX-FileCopyrightText: 2021-2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! The faircamp command-line interface, which handles the few things that
+//! are not a build (opening the manual, developer tools, the control
+//! server, watch mode) and otherwise hands over to the
+//! [Builder](crate::Builder).
+
+use std::process::ExitCode;
+
+use crate::{Args, ArgsCommand, Builder};
+use crate::{control_server, snapshot_test, test_catalog, watch};
+
+const MANUAL_URL: &str = "https://simonrepp.com/faircamp/manual/";
+
+pub fn run(args: Args) -> ExitCode {
+    if args.manual {
+        if webbrowser::open(MANUAL_URL).is_err() {
+            error!("Could not open browser for displaying the manual");
+            return ExitCode::FAILURE;
+        } else {
+            return ExitCode::SUCCESS;
+        }
+    }
+
+    if let Some(ArgsCommand::GenerateTestCatalog { dir, releases, tracks }) = &args.command {
+        return test_catalog::generate(dir, *releases, *tracks);
+    }
+
+    if let Some(ArgsCommand::ServeControl { port }) = &args.command {
+        return control_server::serve_control(&args, *port);
+    }
+
+    if let Some(ArgsCommand::SnapshotTest { fixture_dir, snapshot_dir, update }) = &args.command {
+        return snapshot_test::run(fixture_dir, snapshot_dir, *update);
+    }
+
+    if args.watch {
+        return watch::run(args);
+    }
+
+    // All messages (including those for failures) have already been
+    // printed during the build, so the failure summary is not needed here.
+    match Builder::from_args(args).build() {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,23 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+/// Configured through the release manifest's `content_warning` option.
+/// Before the release and track pages are shown, an interstitial presents
+/// the warning (and, optionally, an age gate), which visitors need to
+/// acknowledge. The acknowledgement is remembered in the browser (per
+/// release), so the interstitial is only shown once.
+#[derive(Clone, Debug)]
+pub struct ContentWarning {
+    /// If given, visitors need to confirm they are at least this old
+    pub minimum_age: Option<u8>,
+    pub text: String
+}
+
+impl ContentWarning {
+    pub fn new(minimum_age: Option<u8>, text: String) -> ContentWarning {
+        ContentWarning {
+            minimum_age,
+            text
+        }
+    }
+}
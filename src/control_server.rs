@@ -0,0 +1,370 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Through `faircamp serve-control` a local http interface (JSON over REST)
+//! is exposed, through which a desktop GUI or web admin can drive faircamp:
+//!
+//! - `GET /catalog` scans the catalog and lists its releases
+//! - `POST /build` starts a build in the background
+//! - `GET /progress?since=n` returns the progress events from the nth event
+//!   on - if there are none yet, the request is held open (long polling)
+//!   until there are, so clients can follow a build as it happens
+//! - `GET /warnings` returns the errors and warnings of the last build
+//!
+//! The server only binds to localhost and is not meant to be exposed to a
+//! network. As any website opened in a browser on the same machine could
+//! still send requests to it, every request needs to carry a token that is
+//! generated when the server starts (and printed to the terminal), as
+//! `Authorization: Bearer <token>` header. Browsers do not attach such a
+//! header to cross-origin requests without the server's consent.
+
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use actix_web::{App, HttpRequest, HttpResponse, HttpServer, web};
+use actix_web::http::header::AUTHORIZATION;
+use actix_web::rt::time::sleep;
+use nanoid::nanoid;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{Args, BuildStage, Builder};
+
+const CONTROL_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+const DEFAULT_CONTROL_PORT: u16 = 7070;
+
+/// How long a /progress request is held open at most when there are no new
+/// events, after which it is answered with an empty list of events.
+const LONG_POLL_SECONDS: u64 = 25;
+const LONG_POLL_INTERVAL_MILLISECONDS: u64 = 200;
+
+/// Only this many of the most recent progress events are kept, older ones
+/// are dropped (a server that runs for a long time would otherwise
+/// accumulate events without bounds).
+const MAX_EVENTS: usize = 1000;
+
+#[derive(Clone, Serialize)]
+struct BuildResult {
+    build: usize,
+    errors: usize,
+    /// The reason if the build failed
+    failure: Option<String>,
+    warnings: Vec<ReportedWarning>
+}
+
+#[derive(Serialize)]
+struct BuildStarted {
+    build: usize
+}
+
+struct Control {
+    /// Directory overrides faircamp was started with, applied to every
+    /// build and scan
+    build_dir: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    catalog_dir: Option<PathBuf>,
+    state: Mutex<ControlState>,
+    /// Generated on startup, required with every request
+    token: String
+}
+
+#[derive(Default)]
+struct ControlState {
+    building: bool,
+    /// Number of builds started so far, builds are numbered from 1
+    builds: usize,
+    /// The most recent events (at most MAX_EVENTS), the first of which is
+    /// the event with the number events_dropped
+    events: Vec<ProgressEvent>,
+    /// How many events were dropped from the beginning of events so far
+    events_dropped: usize,
+    last_result: Option<BuildResult>,
+    scanning: bool
+}
+
+#[derive(Serialize)]
+struct ErrorMessage {
+    error: String
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "snake_case", tag = "event")]
+enum ProgressEvent {
+    Finished { build: usize, failure: Option<String> },
+    Stage { build: usize, stage: BuildStage },
+    Started { build: usize }
+}
+
+#[derive(Deserialize)]
+struct ProgressQuery {
+    since: Option<usize>
+}
+
+#[derive(Serialize)]
+struct ProgressResponse {
+    building: bool,
+    events: Vec<ProgressEvent>,
+    /// The value of since for the next request
+    next: usize
+}
+
+#[derive(Clone, Serialize)]
+struct ReportedWarning {
+    category: &'static str,
+    message: String
+}
+
+impl Control {
+    fn builder(&self) -> Builder {
+        let mut builder = Builder::new();
+
+        if let Some(build_dir) = &self.build_dir {
+            builder = builder.build_dir(build_dir.clone());
+        }
+
+        if let Some(cache_dir) = &self.cache_dir {
+            builder = builder.cache_dir(cache_dir.clone());
+        }
+
+        if let Some(catalog_dir) = &self.catalog_dir {
+            builder = builder.catalog_dir(catalog_dir.clone());
+        }
+
+        builder
+    }
+
+    /// Whether the request carries the token of this session
+    fn authorized(&self, request: &HttpRequest) -> bool {
+        request.headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| token == self.token)
+    }
+}
+
+impl ControlState {
+    /// Number of events recorded so far (including dropped ones)
+    fn events_total(&self) -> usize {
+        self.events_dropped + self.events.len()
+    }
+
+    fn push_event(&mut self, event: ProgressEvent) {
+        self.events.push(event);
+
+        if self.events.len() > MAX_EVENTS {
+            let excess = self.events.len() - MAX_EVENTS;
+            self.events.drain(..excess);
+            self.events_dropped += excess;
+        }
+    }
+}
+
+impl ErrorMessage {
+    fn new(error: impl Into<String>) -> ErrorMessage {
+        ErrorMessage { error: error.into() }
+    }
+}
+
+async fn catalog(control: web::Data<Control>, request: HttpRequest) -> HttpResponse {
+    if !control.authorized(&request) {
+        return unauthorized();
+    }
+
+    // Scanning while building (or scanning twice at the same time) would
+    // interfere with the cache, so the check and the flag are set under the
+    // same lock.
+    {
+        let mut state = control.state.lock().unwrap();
+
+        if state.building || state.scanning {
+            return HttpResponse::Conflict().json(ErrorMessage::new("The catalog can not be scanned while a build or scan is running"));
+        }
+
+        state.scanning = true;
+    }
+
+    let control = control.into_inner();
+    let control_scanning = control.clone();
+
+    let result = web::block(move || control_scanning.builder().scan()).await;
+
+    control.state.lock().unwrap().scanning = false;
+
+    match result {
+        Ok(Ok(catalog_summary)) => HttpResponse::Ok().json(catalog_summary),
+        Ok(Err(failure)) => HttpResponse::UnprocessableEntity().json(ErrorMessage::new(failure.reason)),
+        Err(err) => HttpResponse::InternalServerError().json(ErrorMessage::new(err.to_string()))
+    }
+}
+
+async fn progress(
+    control: web::Data<Control>,
+    query: web::Query<ProgressQuery>,
+    request: HttpRequest
+) -> HttpResponse {
+    if !control.authorized(&request) {
+        return unauthorized();
+    }
+
+    let since = query.since.unwrap_or(0);
+    let deadline = Instant::now() + Duration::from_secs(LONG_POLL_SECONDS);
+
+    loop {
+        {
+            let state = control.state.lock().unwrap();
+
+            if state.events_total() > since || Instant::now() >= deadline {
+                // Events before events_dropped are not available anymore
+                let start = since.saturating_sub(state.events_dropped);
+                let events = state.events
+                    .get(start..)
+                    .unwrap_or_default()
+                    .to_vec();
+
+                return HttpResponse::Ok().json(ProgressResponse {
+                    building: state.building,
+                    events,
+                    next: state.events_total()
+                });
+            }
+        }
+
+        sleep(Duration::from_millis(LONG_POLL_INTERVAL_MILLISECONDS)).await;
+    }
+}
+
+#[actix_web::main]
+pub async fn serve_control(args: &Args, port_requested: Option<u16>) -> ExitCode {
+    let control = Arc::new(Control {
+        build_dir: args.build_dir.clone(),
+        cache_dir: args.cache_dir.clone(),
+        catalog_dir: args.catalog_dir.clone(),
+        state: Mutex::new(ControlState::default()),
+        token: nanoid!(32)
+    });
+
+    let token = control.token.clone();
+
+    let port = port_requested.unwrap_or(DEFAULT_CONTROL_PORT);
+
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::from(control.clone()))
+            .route("/build", web::post().to(start_build))
+            .route("/catalog", web::get().to(catalog))
+            .route("/progress", web::get().to(progress))
+            .route("/warnings", web::get().to(warnings))
+    })
+        .bind((CONTROL_IP, port));
+
+    let server = match server {
+        Ok(server) => server,
+        Err(err) => {
+            error!("Could not bind control server to {}:{} ({})", CONTROL_IP, port, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("Serving the control interface at http://{CONTROL_IP}:{port}");
+    println!("Send the header 'Authorization: Bearer {token}' with every request");
+    println!("Press Ctrl+C to shut down the control server");
+
+    match server.run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            error!("The control server stopped with an error ({})", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn start_build(control: web::Data<Control>, request: HttpRequest) -> HttpResponse {
+    if !control.authorized(&request) {
+        return unauthorized();
+    }
+
+    let control = control.into_inner();
+
+    // The check and the flag are set under the same lock, so two requests
+    // arriving at the same time can not both start a build.
+    let build_number = {
+        let mut state = control.state.lock().unwrap();
+
+        if state.building || state.scanning {
+            return HttpResponse::Conflict().json(ErrorMessage::new("A build or scan is already running"));
+        }
+
+        state.building = true;
+        state.builds += 1;
+
+        let build_number = state.builds;
+        state.push_event(ProgressEvent::Started { build: build_number });
+        build_number
+    };
+
+    // The build runs in its own thread (the builder is not Send, so it is
+    // created in there), which is supervised so that we also learn about
+    // builds that panic.
+    thread::spawn(move || {
+        let control_building = control.clone();
+
+        let outcome = thread::spawn(move || {
+            let control_progress = control_building.clone();
+
+            control_building
+                .builder()
+                .progress(move |stage| {
+                    let event = ProgressEvent::Stage { build: build_number, stage };
+                    control_progress.state.lock().unwrap().push_event(event);
+                })
+                .build()
+        }).join();
+
+        let (failure, report) = match outcome {
+            Ok(Ok(report)) => (None, Some(report)),
+            Ok(Err(failure)) => (Some(failure.reason), Some(failure.report)),
+            Err(_) => (Some(String::from("The build was aborted by an internal error")), None)
+        };
+
+        let mut state = control.state.lock().unwrap();
+
+        state.building = false;
+        state.push_event(ProgressEvent::Finished { build: build_number, failure: failure.clone() });
+        state.last_result = Some(BuildResult {
+            build: build_number,
+            errors: report.as_ref().map(|report| report.errors).unwrap_or(0),
+            failure,
+            warnings: report
+                .map(|report| {
+                    report.warning_messages
+                        .into_iter()
+                        .map(|(category, message)| ReportedWarning { category, message })
+                        .collect()
+                })
+                .unwrap_or_default()
+        });
+    });
+
+    HttpResponse::Accepted().json(BuildStarted { build: build_number })
+}
+
+fn unauthorized() -> HttpResponse {
+    HttpResponse::Unauthorized().json(ErrorMessage::new("The request did not carry the token of the control server (Authorization: Bearer <token>)"))
+}
+
+async fn warnings(control: web::Data<Control>, request: HttpRequest) -> HttpResponse {
+    if !control.authorized(&request) {
+        return unauthorized();
+    }
+
+    let state = control.state.lock().unwrap();
+
+    match &state.last_result {
+        Some(build_result) => HttpResponse::Ok().json(build_result),
+        None => HttpResponse::NotFound().json(ErrorMessage::new("No build has finished yet"))
+    }
+}
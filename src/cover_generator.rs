@@ -617,6 +617,8 @@ impl ProceduralCoverAsset {
     pub fn new(build: &Build, filename: String) -> ProceduralCoverAsset {
         let metadata = fs::metadata(build.cache_dir.join(&filename)).unwrap();
 
+        build.stats.add_generated(metadata.len());
+
         ProceduralCoverAsset {
             filename,
             filesize_bytes: metadata.len()
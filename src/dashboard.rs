@@ -0,0 +1,365 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! While the preview server runs, a catalog health dashboard is served under
+//! /_faircamp/. It summarizes what needs attention before publishing:
+//! Warnings, missing image descriptions, permalinks that are still generated
+//! from titles, how much of the build came from the cache, and for each
+//! release whether it looks ready to go out. With the catalog's
+//! image_description_suggestions option enabled, images without a
+//! description come with a suggested one (built from titles, names and
+//! dates in the language of the site) that can be edited and then copied
+//! into the respective manifest.
+//!
+//! The dashboard is written to the cache directory after each build (it is
+//! never written to the build directory, and therefore never deployed), from
+//! where the preview server reads it on each request, so that it stays up to
+//! date across rebuilds with --watch.
+
+use std::fs;
+use std::path::Path;
+
+use chrono::Datelike;
+use indoc::formatdoc;
+
+use crate::{Build, Catalog, DescribedImage, Release};
+use crate::render::image_descriptions::cover_description;
+use crate::util::{format_bytes, html_escape_outside_attribute};
+
+/// Stored in the cache directory, but not subject to cache maintenance.
+pub const DASHBOARD_CACHE_FILENAME: &str = "dashboard.html";
+
+/// The path under which the preview server serves the dashboard
+pub const DASHBOARD_PATH: &str = "/_faircamp/";
+
+fn check(passed: bool) -> &'static str {
+    match passed {
+        true => r#"<td class="passed">✓</td>"#,
+        false => r#"<td class="failed">✗</td>"#
+    }
+}
+
+fn dashboard_html(build: &Build, catalog: &Catalog) -> String {
+    let translations = &build.locale.translations;
+
+    let r_warnings = if build.warning_messages.is_empty() {
+        String::from("<p>No warnings were encountered.</p>")
+    } else {
+        let r_items = build.warning_messages
+            .iter()
+            .map(|(category, message)| {
+                format!(
+                    "<li><code>{key}</code> {message}</li>",
+                    key = category.key(),
+                    message = html_escape_outside_attribute(message)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("");
+
+        format!("<ul>{r_items}</ul>")
+    };
+
+    let r_descriptions = {
+        // Pairs of what the image is and (if enabled) a suggestion
+        let mut missing: Vec<(String, Option<String>)> = Vec::new();
+
+        let missing_description = |described_image: &Option<DescribedImage>| {
+            described_image.as_ref().is_some_and(|described_image| described_image.description.is_none())
+        };
+
+        let suggest = |
+            described_image: &Option<DescribedImage>,
+            description: String,
+            key: &str,
+            manifest_dir: Option<&Path>
+        | -> Option<String> {
+            match (catalog.image_description_suggestions, described_image) {
+                (true, Some(described_image)) => Some(suggestion(described_image, description, key, manifest_dir)),
+                _ => None
+            }
+        };
+
+        if missing_description(&catalog.home_image) {
+            missing.push((String::from("The catalog's home image"), None));
+        }
+
+        if catalog.hero.as_ref().and_then(|hero| hero.custom_image()).is_some_and(|described_image| described_image.description.is_none()) {
+            missing.push((String::from("The catalog's hero image"), None));
+        }
+
+        for artist in &catalog.featured_artists {
+            let artist_ref = artist.borrow();
+            if missing_description(&artist_ref.image) {
+                missing.push((
+                    format!("The image of the artist '{}'", artist_ref.name),
+                    suggest(&artist_ref.image, translations.image_of_xxx(&artist_ref.name), "image", None)
+                ));
+            }
+        }
+
+        for release in &catalog.releases {
+            let release_ref = release.borrow();
+            if missing_description(&release_ref.cover) {
+                let released = release_ref.date.map(|date| translations.released_xxx(&date.year().to_string()));
+                let description = cover_description(translations, &release_ref.title, &release_ref.main_artists, released);
+
+                missing.push((
+                    format!("The cover of the release '{}'", release_ref.title),
+                    suggest(&release_ref.cover, description, "cover", Some(release_ref.source_dir.as_path()))
+                ));
+            }
+
+            for physical_edition in &release_ref.physical_editions {
+                for described_image in &physical_edition.photos {
+                    if described_image.description.is_none() {
+                        missing.push((
+                            format!("A photo of the physical edition '{}' of the release '{}'", physical_edition.format, release_ref.title),
+                            None
+                        ));
+                    }
+                }
+            }
+
+            for track in &release_ref.tracks {
+                if missing_description(&track.cover) {
+                    let from_release = Some(translations.from_xxx(&release_ref.title));
+                    let description = cover_description(translations, &track.title(), &track.artists, from_release);
+
+                    missing.push((
+                        format!("The cover of the track '{}' on the release '{}'", track.title(), release_ref.title),
+                        suggest(&track.cover, description, "cover", None)
+                    ));
+                }
+            }
+        }
+
+        if missing.is_empty() {
+            String::from("<p>All images have a description.</p>")
+        } else {
+            let r_items = missing
+                .iter()
+                .map(|(item, suggestion)| match suggestion {
+                    Some(suggestion) => format!(
+                        r#"<li>{}<br><textarea cols="72" rows="3">{}</textarea></li>"#,
+                        html_escape_outside_attribute(item),
+                        html_escape_outside_attribute(suggestion)
+                    ),
+                    None => format!("<li>{}</li>", html_escape_outside_attribute(item))
+                })
+                .collect::<Vec<String>>()
+                .join("");
+
+            let r_suggestions_note = match catalog.image_description_suggestions {
+                true => formatdoc!(r#"
+                    <p>
+                        The suggested descriptions are only built from titles,
+                        names and dates. Check what each image actually shows,
+                        edit the description accordingly and then copy it into
+                        the respective manifest.
+                    </p>
+                "#),
+                false => String::new()
+            };
+
+            format!("{r_suggestions_note}<ul>{r_items}</ul>")
+        }
+    };
+
+    let r_permalinks = {
+        let mut generated = Vec::new();
+
+        for artist in &catalog.featured_artists {
+            let artist_ref = artist.borrow();
+            if artist_ref.permalink.generated {
+                generated.push((format!("Artist '{}'", artist_ref.name), artist_ref.permalink.slug.clone()));
+            }
+        }
+
+        for release in &catalog.releases {
+            let release_ref = release.borrow();
+            if release_ref.permalink.generated {
+                generated.push((format!("Release '{}'", release_ref.title), release_ref.permalink.slug.clone()));
+            }
+        }
+
+        for playlist in &catalog.playlists {
+            if playlist.permalink.generated {
+                generated.push((format!("Playlist '{}'", playlist.title), playlist.permalink.slug.clone()));
+            }
+        }
+
+        if generated.is_empty() {
+            String::from("<p>All permalinks are explicitly set.</p>")
+        } else {
+            let r_rows = generated
+                .iter()
+                .map(|(page, slug)| {
+                    format!(
+                        "<tr><td>{page}</td><td><code>/{slug}/</code></td></tr>",
+                        page = html_escape_outside_attribute(page),
+                        slug = html_escape_outside_attribute(slug)
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("");
+
+            formatdoc!(r#"
+                <p>
+                    These permalinks are generated from titles and names, so
+                    they change (and break existing links) when the title or
+                    name changes. Set them explicitly through the permalink
+                    option in the respective manifest to keep them stable.
+                </p>
+                <table>
+                    <tr>
+                        <th>Page</th>
+                        <th>Permalink</th>
+                    </tr>
+                    {r_rows}
+                </table>
+            "#)
+        }
+    };
+
+    let (num_generated, bytes_generated) = build.stats.generated();
+    let bytes_written = build.stats.bytes_written();
+    let r_cache = formatdoc!(r#"
+        <p>
+            {num_generated} assets ({generated}) were newly transcoded, resized
+            or zipped in this build, everything else that was written to the
+            build directory ({reused} of {written}) was reused from the cache.
+        </p>
+    "#,
+        generated = format_bytes(bytes_generated),
+        reused = format_bytes(bytes_written.saturating_sub(bytes_generated)),
+        written = format_bytes(bytes_written)
+    );
+
+    let r_releases = catalog.releases
+        .iter()
+        .map(|release| release_row(&release.borrow()))
+        .collect::<Vec<String>>()
+        .join("");
+
+    formatdoc!(r#"
+        <!DOCTYPE html>
+        <html>
+            <head>
+                <meta charset="utf-8">
+                <title>Catalog health</title>
+                <style>
+                    body {{ font-family: sans-serif; margin: 2rem; }}
+                    table {{ border-collapse: collapse; }}
+                    td, th {{ border: 1px solid #ccc; padding: .25rem .5rem; text-align: left; }}
+                    .failed {{ color: #a00; }}
+                    .passed {{ color: #070; }}
+                </style>
+            </head>
+            <body>
+                <h1>Catalog health</h1>
+                <p>
+                    Built on {date} with {errors} errors and {warnings} warnings.
+                    This page is only available in the preview and never deployed.
+                </p>
+                <h2>Releases</h2>
+                <table>
+                    <tr>
+                        <th>Release</th>
+                        <th>Published</th>
+                        <th>Cover</th>
+                        <th>Cover description</th>
+                        <th>Date</th>
+                        <th>Text</th>
+                        <th>Permalink set</th>
+                        <th>Ready</th>
+                    </tr>
+                    {r_releases}
+                </table>
+                <h2>Warnings</h2>
+                {r_warnings}
+                <h2>Missing image descriptions</h2>
+                {r_descriptions}
+                <h2>Permalinks</h2>
+                {r_permalinks}
+                <h2>Cache</h2>
+                {r_cache}
+            </body>
+        </html>
+    "#,
+        date = build.build_begin.format("%Y-%m-%d %H:%M:%S UTC"),
+        errors = build.errors,
+        warnings = build.warnings
+    )
+}
+
+/// A release is considered ready when it is not a draft, and it has a
+/// (described) cover, a date and some text (synopsis or more).
+fn release_row(release: &Release) -> String {
+    let has_cover = release.cover.is_some();
+    let has_cover_description = release.cover
+        .as_ref()
+        .is_some_and(|described_image| described_image.description.is_some());
+    let has_date = release.date.is_some();
+    let has_text = release.synopsis.is_some() || release.more.is_some();
+    let permalink_set = !release.permalink.generated;
+
+    let ready = !release.draft && has_cover && has_cover_description && has_date && has_text;
+
+    let published = match (release.draft, release.unlisted) {
+        (true, _) => "Draft",
+        (false, true) => "Unlisted",
+        (false, false) => "Listed"
+    };
+
+    formatdoc!(r#"
+        <tr>
+            <td><a href="/{slug}/">{title}</a></td>
+            <td>{published}</td>
+            {cover}
+            {cover_description}
+            {date}
+            {text}
+            {permalink}
+            {ready}
+        </tr>
+    "#,
+        cover = check(has_cover),
+        cover_description = check(has_cover_description),
+        date = check(has_date),
+        permalink = check(permalink_set),
+        ready = check(ready),
+        slug = release.permalink.slug,
+        text = check(has_text),
+        title = html_escape_outside_attribute(&release.title)
+    )
+}
+
+/// A manifest snippet that sets the image along with the suggested
+/// description. When the directory of the manifest is known, the file is
+/// given relative to it, otherwise only its filename is used.
+fn suggestion(
+    described_image: &DescribedImage,
+    description: String,
+    key: &str,
+    manifest_dir: Option<&Path>
+) -> String {
+    let path = &described_image.file_meta.path;
+
+    let file = match manifest_dir.and_then(|manifest_dir| path.strip_prefix(manifest_dir).ok()) {
+        Some(relative_path) => relative_path.to_string_lossy().to_string(),
+        None => path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default()
+    };
+
+    format!("{key}:\ndescription = {description}\nfile = {file}")
+}
+
+pub fn write(build: &Build, catalog: &Catalog) {
+    let html = dashboard_html(build, catalog);
+
+    if fs::create_dir_all(&build.cache_dir).is_ok() {
+        if let Err(err) = fs::write(build.cache_dir.join(DASHBOARD_CACHE_FILENAME), html) {
+            error!("The catalog health dashboard could not be written ({})", err);
+        }
+    }
+}
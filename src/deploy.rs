@@ -1,14 +1,19 @@
-// SPDX-FileCopyrightText: 2021-2022 Simon Repp
+// SPDX-FileCopyrightText: 2021-2025 Simon Repp
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
-use crate::{Build, rsync};
+use crate::{Build, rsync, sftp};
 
-pub fn deploy(build: &Build) {
+pub fn deploy(build: &Build) -> Result<(), String> {
     if let Some(destination) = &build.deploy_destination {
         info!("Deployment started");
-        rsync::sync(&build.build_dir, destination).unwrap();
+        if destination.starts_with("sftp://") {
+            sftp::sync(&build.build_dir, &build.cache_dir, destination)?;
+        } else {
+            rsync::sync(&build.build_dir, destination)?;
+        }
         info!("Deployment finished");
+        Ok(())
     } else {
-        error!("No deployment destination specified, provide one with --deploy-destination");
+        Err(String::from("No deployment destination specified, provide one with --deploy-destination or the catalog's deploy_destination option"))
     }
-}
\ No newline at end of file
+}
@@ -21,6 +21,8 @@ pub enum DownloadFormat {
     Opus48Kbps,
     Opus96Kbps,
     Opus128Kbps,
+    /// The source files, included verbatim (without transcoding)
+    Original,
     Wav
 }
 
@@ -39,6 +41,7 @@ impl DownloadFormat {
             DownloadFormat::Opus48Kbps => AudioFormat::Opus48Kbps,
             DownloadFormat::Opus96Kbps => AudioFormat::Opus96Kbps,
             DownloadFormat::Opus128Kbps => AudioFormat::Opus128Kbps,
+            DownloadFormat::Original => AudioFormat::Original,
             DownloadFormat::Wav => AudioFormat::Wav
         }
     }
@@ -56,6 +59,7 @@ impl DownloadFormat {
             DownloadFormat::Opus48Kbps => build.locale.translations.audio_format_opus_48.to_string(),
             DownloadFormat::Opus96Kbps => build.locale.translations.audio_format_opus_96.to_string(),
             DownloadFormat::Opus128Kbps => build.locale.translations.audio_format_opus_128.to_string(),
+            DownloadFormat::Original => build.locale.translations.audio_format_original.to_string(),
         }
     }
 
@@ -70,7 +74,8 @@ impl DownloadFormat {
             DownloadFormat::Alac => 7,
             DownloadFormat::Aac => 8,
             DownloadFormat::Wav => 9,
-            DownloadFormat::Aiff => 10
+            DownloadFormat::Aiff => 10,
+            DownloadFormat::Original => 11
         }
     }
 
@@ -85,6 +90,7 @@ impl DownloadFormat {
             "opus_48" => Some(DownloadFormat::Opus48Kbps),
             "opus_96" => Some(DownloadFormat::Opus96Kbps),
             "opus" | "opus_128" => Some(DownloadFormat::Opus128Kbps),
+            "original" => Some(DownloadFormat::Original),
             "wav" => Some(DownloadFormat::Wav),
             _ =>  None
         }
@@ -97,7 +103,9 @@ impl DownloadFormat {
             DownloadFormat::OggVorbis |
             DownloadFormat::Opus48Kbps |
             DownloadFormat::Opus96Kbps |
-            DownloadFormat::Opus128Kbps
+            DownloadFormat::Opus128Kbps |
+            // Depends on the source file, but never involves a conversion
+            DownloadFormat::Original
                 => false,
             DownloadFormat::Aiff |
             DownloadFormat::Alac |
@@ -111,6 +119,7 @@ impl DownloadFormat {
         match self {
             DownloadFormat::Aac |        // non-free technology
             DownloadFormat::Aiff |       // wasteful
+            DownloadFormat::Original |   // unknown format and size
             DownloadFormat::Wav          // wasteful
                 => false,
             DownloadFormat::Alac |
@@ -136,6 +145,7 @@ impl DownloadFormat {
             DownloadFormat::Opus48Kbps => "Opus 48Kbps",
             DownloadFormat::Opus96Kbps => "Opus 96Kbps",
             DownloadFormat::Opus128Kbps => "Opus 128Kbps",
+            DownloadFormat::Original => "Original",
             DownloadFormat::Wav => "WAV"
         }
     }
@@ -174,6 +184,7 @@ impl Display for DownloadFormat {
             DownloadFormat::Opus48Kbps => "Opus 48Kbps",
             DownloadFormat::Opus96Kbps => "Opus 96Kbps",
             DownloadFormat::Opus128Kbps => "Opus 128Kbps",
+            DownloadFormat::Original => "Original",
             DownloadFormat::Wav => "WAV"
         };
 
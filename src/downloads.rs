@@ -2,10 +2,38 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use std::ops::Range;
+use std::path::PathBuf;
 
 use iso_currency::Currency;
 
-use crate::Overrides;
+use crate::{Build, CoverAspectRatio, DescribedImage, Overrides};
+
+/// Controls which version of a cover goes into release archives (as
+/// cover.jpg) and is embedded into the audio files of downloads. By default
+/// archives contain the largest generated cover size, while audio files get
+/// the original image embedded.
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct ArchiveCover {
+    /// If set, the largest generated cover size whose longer edge does not
+    /// exceed this (in pixels) is used, both in archives and for embedding
+    pub max_edge_size: Option<u32>,
+    /// Additionally include the original cover image file in archives
+    pub original: bool
+}
+
+/// Controls the internal folder structure of release archives (zips). The
+/// default (all disabled) puts track files at the root of the archive, with
+/// track covers and track extras in "[track] (Extras)" subfolders.
+#[derive(Clone, Debug, Hash)]
+pub struct ArchiveLayout {
+    /// Group tracks into one subfolder per disc (when tags provide disc numbers)
+    pub disc_folders: bool,
+    /// Wrap all contents in a top-level "[Artist] - [Title]" folder
+    pub release_folder: bool,
+    /// Put each track in its own folder, alongside a cover.jpg (the track
+    /// cover or otherwise the release cover) and its extras
+    pub track_folders: bool
+}
 
 #[derive(Clone, Debug)]
 pub enum DownloadAccess {
@@ -51,6 +79,65 @@ pub struct Price {
     pub range: Range<f32>
 }
 
+impl ArchiveCover {
+    pub const DEFAULT: ArchiveCover = ArchiveCover {
+        max_edge_size: None,
+        original: false
+    };
+
+    /// Applies a single value as given in the manifest, which is either a
+    /// maximum edge size (e.g. "800"), "largest" or "original".
+    pub fn apply_value(&mut self, value: &str) -> Result<(), String> {
+        match value {
+            "largest" => self.max_edge_size = None,
+            "original" => self.original = true,
+            _ => match value.parse::<u32>() {
+                Ok(max_edge_size) if max_edge_size > 0 => self.max_edge_size = Some(max_edge_size),
+                _ => return Err(format!("The value '{value}' is not supported (allowed are: a maximum size in pixels, e.g. '800', 'largest' or 'original')"))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the path of the image file that is embedded as cover into
+    /// audio files. With a max_edge_size this is a generated cover asset in
+    /// the cache, otherwise the original image in the catalog.
+    pub fn embed_path(
+        &self,
+        build: &Build,
+        cover_aspect_ratio: CoverAspectRatio,
+        described_image: &DescribedImage
+    ) -> PathBuf {
+        match self.max_edge_size {
+            Some(max_edge_size) => {
+                let mut image_mut = described_image.borrow_mut();
+                let source_path = &described_image.file_meta.path;
+
+                let cover_assets = match cover_aspect_ratio {
+                    CoverAspectRatio::Original => image_mut.cover_assets_uncropped(build, source_path),
+                    CoverAspectRatio::Square => image_mut.cover_assets(build, source_path)
+                };
+
+                let filename = cover_assets.largest_up_to(max_edge_size).filename.clone();
+
+                image_mut.persist_to_cache(&build.cache_dir);
+
+                build.cache_dir.join(filename)
+            }
+            None => build.catalog_dir.join(&described_image.file_meta.path)
+        }
+    }
+}
+
+impl ArchiveLayout {
+    pub const FLAT: ArchiveLayout = ArchiveLayout {
+        disc_folders: false,
+        release_folder: false,
+        track_folders: false
+    };
+}
+
 impl DownloadAccessOption {
     /// Combines DownloadAccess with payment, price and/or unlock info
     /// in order to form the final DownloadAccess data.
@@ -1,12 +1,19 @@
 // SPDX-FileCopyrightText: 2025 Simon Repp
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::Build;
+use crate::{Build, WarningCategory};
+use crate::git_scan::{self, DirEntryKind};
+use crate::util::format_bytes;
 
-const SUPPORTED_AUDIO_EXTENSIONS: &[&str] = &["aif", "aifc", "aiff", "alac", "flac", "mp3", "ogg", "opus", "wav"];
-const SUPPORTED_IMAGE_EXTENSIONS: &[&str] = &["gif", "heif", "jpeg", "jpg", "png", "webp"];
+/// Extra files above this size (in megabytes) are reported, unless the
+/// catalog's `extra_size_warning` option sets a different threshold.
+pub const EXTRA_SIZE_WARNING_DEFAULT: u64 = 500;
+
+pub const SUPPORTED_AUDIO_EXTENSIONS: &[&str] = &["aif", "aifc", "aiff", "alac", "flac", "mp3", "ogg", "opus", "wav"];
+const SUPPORTED_IMAGE_EXTENSIONS: &[&str] = &["gif", "heif", "jpeg", "jpg", "png", "svg", "webp"];
 const UNSUPPORTED_AUDIO_EXTENSIONS: &[&str] = &["aac", "m4a"];
 
 /// Convenience wrapper to generically pre-scan directories for
@@ -19,7 +26,11 @@ pub struct FairDir {
     pub dirs: Vec<PathBuf>,
     pub extra_files: Vec<PathBuf>,
     pub image_files: Vec<PathBuf>,
+    /// Translations of an artist, catalog or release manifest in the same
+    /// directory, e.g. `release.de.eno` (see the localization module)
+    pub overlay_manifests: Vec<PathBuf>,
     pub path: PathBuf,
+    pub playlist_manifest: Option<PathBuf>,
     pub release_manifest: Option<PathBuf>,
     pub track_manifest: Option<PathBuf>
 }
@@ -33,128 +44,248 @@ impl FairDir {
             dirs: Vec::new(),
             extra_files: Vec::new(),
             image_files: Vec::new(),
+            overlay_manifests: Vec::new(),
             path: path.to_owned(),
+            playlist_manifest: None,
             release_manifest: None,
             track_manifest: None
         }
     }
 
+    /// Files that are not recognized as anything else are collected as
+    /// extras, unless the catalog's `extra_ignore` patterns exclude them.
+    /// Extras end up in downloads, so unusually large ones are reported.
+    fn push_extra(&mut self, build: &mut Build, path: PathBuf) {
+        let filename = path.file_name().unwrap().to_string_lossy().to_string();
+
+        if let Some(pattern) = build.extra_ignore_patterns.iter().find(|pattern| matches_pattern(pattern, &filename)) {
+            if build.verbose {
+                info!("Ignoring extra file {} (excluded by extra_ignore pattern '{}')", path.display(), pattern);
+            }
+            return
+        }
+
+        if let Some(megabytes) = build.extra_size_warning {
+            if let Ok(metadata) = fs::metadata(&path) {
+                if metadata.len() > megabytes * 1024 * 1024 {
+                    let warning = format!(
+                        "The extra file '{}' is {} in size, which exceeds the extra_size_warning threshold of {}MB. Extras are included in release/track downloads - if this file ended up there by accident, remove it or exclude it through the catalog's extra_ignore option.",
+                        path.display(),
+                        format_bytes(metadata.len()),
+                        megabytes
+                    );
+                    build.warning(WarningCategory::OversizedExtra, &warning);
+                }
+            }
+        }
+
+        self.extra_files.push(path);
+    }
+
     pub fn read(build: &mut Build, path: &Path) -> FairDir {
         let mut fair_dir = FairDir::new(path);
 
-        if let Ok(dir_entries) = path.read_dir() {
-            'dir_entry_iter: for dir_entry_result in dir_entries {
-                if let Ok(dir_entry) = dir_entry_result {
-                    if let Some(filename) = dir_entry.file_name().to_str() {
-                        if filename.starts_with('.') {
-                            if build.verbose {
-                                info!("Ignoring hidden file '{}'", filename);
-                            }
-                            continue
-                        }
+        let entries = match &mut build.git_scan {
+            Some(git_scan) => git_scan.list(path),
+            None => git_scan::list_dir(path)
+        };
+
+        'dir_entry_iter: for entry in entries {
+            if let Some(filename) = entry.file_name.to_str() {
+                if filename.starts_with('.') {
+                    if build.verbose {
+                        info!("Ignoring hidden file '{}'", filename);
                     }
+                    continue
+                }
+            }
 
-                    if let Ok(file_type) = dir_entry.file_type() {
-                        let path = dir_entry.path();
-
-                        if file_type.is_dir() {
-                            let dir_canonicalized = path.canonicalize().unwrap();
-                            for special_dir in &[&build.build_dir, &build.cache_dir] {
-                                if let Ok(special_dir_canonicalized) = special_dir.canonicalize() {
-                                    if dir_canonicalized == special_dir_canonicalized {
-                                        if build.verbose {
-                                            info!("Ignoring special directory {}", special_dir.display());
-                                        }
-                                        continue 'dir_entry_iter;
-                                    }
+            let path = path.join(&entry.file_name);
+
+            match entry.kind {
+                DirEntryKind::Dir => {
+                    let dir_canonicalized = path.canonicalize().unwrap();
+                    for special_dir in &[&build.build_dir, &build.cache_dir] {
+                        if let Ok(special_dir_canonicalized) = special_dir.canonicalize() {
+                            if dir_canonicalized == special_dir_canonicalized {
+                                if build.verbose {
+                                    info!("Ignoring special directory {}", special_dir.display());
                                 }
+                                continue 'dir_entry_iter;
                             }
+                        }
+                    }
 
-                            for exclude_pattern in &build.exclude_patterns {
-                                if let Some(dir_str) = path.to_str() {
-                                    if dir_str.contains(exclude_pattern) {
-                                        if build.verbose {
-                                            info!("Ignoring directory {} and all below (excluded by pattern '{}')", path.display(), exclude_pattern);
-                                        }
-                                        continue 'dir_entry_iter;
-                                    }
+                    for exclude_pattern in &build.exclude_patterns {
+                        if let Some(dir_str) = path.to_str() {
+                            if dir_str.contains(exclude_pattern) {
+                                if build.verbose {
+                                    info!("Ignoring directory {} and all below (excluded by pattern '{}')", path.display(), exclude_pattern);
                                 }
+                                continue 'dir_entry_iter;
                             }
+                        }
+                    }
 
-                            fair_dir.dirs.push(path);
-                        } else if file_type.is_file() {
-                            for exclude_pattern in &build.exclude_patterns {
-                                if let Some(dir_entry_str) = dir_entry.path().to_str() {
-                                    if dir_entry_str.contains(exclude_pattern) {
-                                        if build.verbose {
-                                            info!("Ignoring file {} (excluded by pattern '{}')", dir_entry.path().display(), exclude_pattern);
-                                        }
-                                        continue 'dir_entry_iter
-                                    }
+                    fair_dir.dirs.push(path);
+                }
+                DirEntryKind::File => {
+                    for exclude_pattern in &build.exclude_patterns {
+                        if let Some(dir_entry_str) = path.to_str() {
+                            if dir_entry_str.contains(exclude_pattern) {
+                                if build.verbose {
+                                    info!("Ignoring file {} (excluded by pattern '{}')", path.display(), exclude_pattern);
                                 }
+                                continue 'dir_entry_iter
                             }
+                        }
+                    }
 
-                            if !build.include_patterns.is_empty() {
-                                let mut include = false;
-
-                                for include_pattern in &build.include_patterns {
-                                    if let Some(dir_entry_str) = dir_entry.path().to_str() {
-                                        if dir_entry_str.contains(include_pattern) {
-                                            include = true;
-                                            break
-                                        }
-                                    }
-                                }
+                    if !build.include_patterns.is_empty() {
+                        let mut include = false;
 
-                                if !include {
-                                    if build.verbose {
-                                        info!("Ignoring file {} (matches no include pattern)", dir_entry.path().display());
-                                    }
-                                    continue 'dir_entry_iter
+                        for include_pattern in &build.include_patterns {
+                            if let Some(dir_entry_str) = path.to_str() {
+                                if dir_entry_str.contains(include_pattern) {
+                                    include = true;
+                                    break
                                 }
                             }
+                        }
 
-                            if path.ends_with("artist.eno") {
-                                fair_dir.artist_manifest = Some(path);
-                            } else if path.ends_with("catalog.eno") {
-                                fair_dir.catalog_manifest = Some(path);
-                            } else if path.ends_with("release.eno") {
-                                fair_dir.release_manifest = Some(path);
-                            } else if path.ends_with("track.eno") {
-                                fair_dir.track_manifest = Some(path);
-                            } else if let Some(extension) = path
-                                .extension()
-                                .and_then(|osstr|
-                                    osstr.to_str().map(|str|
-                                        str.to_lowercase().as_str().to_string()
-                                    )
-                                ) {
-                                if extension == "eno" {
-                                    let error = format!("A manifest named '{}' was encountered at '{}', but this name is not recognized (allowed ones are 'artist.eno', 'catalog.eno', 'release.eno', and 'track.eno')", path.file_name().unwrap().to_string_lossy(), path.display());
-                                    build.error(&error);
-                                } else if SUPPORTED_AUDIO_EXTENSIONS.contains(&&extension[..]) {
-                                    fair_dir.audio_files.push(path);
-                                } else if SUPPORTED_IMAGE_EXTENSIONS.contains(&&extension[..]) {
-                                    fair_dir.image_files.push(path);
-                                } else if UNSUPPORTED_AUDIO_EXTENSIONS.contains(&&extension[..]) {
-                                    let error = format!("Support for reading audio files with the extension '{extension}' from the catalog is not yet supported - please get in touch if you need this");
-                                    build.error(&error);
-                                } else {
-                                    fair_dir.extra_files.push(path);
-                                }
-                            } else {
-                                fair_dir.extra_files.push(path);
+                        if !include {
+                            if build.verbose {
+                                info!("Ignoring file {} (matches no include pattern)", path.display());
                             }
-                        } else if file_type.is_symlink() {
-                            warn!("Ignoring symlink '{}'", path.display());
+                            continue 'dir_entry_iter
+                        }
+                    }
+
+                    if path.ends_with("artist.eno") {
+                        fair_dir.artist_manifest = Some(path);
+                    } else if path.ends_with("catalog.eno") {
+                        fair_dir.catalog_manifest = Some(path);
+                    } else if path.ends_with("playlist.eno") {
+                        fair_dir.playlist_manifest = Some(path);
+                    } else if path.ends_with("release.eno") {
+                        fair_dir.release_manifest = Some(path);
+                    } else if path.ends_with("track.eno") {
+                        fair_dir.track_manifest = Some(path);
+                    } else if overlay_manifest_name(&path).is_some() {
+                        fair_dir.overlay_manifests.push(path);
+                    } else if let Some(extension) = path
+                        .extension()
+                        .and_then(|osstr|
+                            osstr.to_str().map(|str|
+                                str.to_lowercase().as_str().to_string()
+                            )
+                        ) {
+                        if extension == "eno" {
+                            let error = format!("A manifest named '{}' was encountered at '{}', but this name is not recognized (allowed ones are 'artist.eno', 'catalog.eno', 'playlist.eno', 'release.eno', and 'track.eno', as well as translations of artist, catalog and release manifests such as 'release.de.eno')", path.file_name().unwrap().to_string_lossy(), path.display());
+                            build.error(&error);
+                        } else if SUPPORTED_AUDIO_EXTENSIONS.contains(&&extension[..]) {
+                            fair_dir.audio_files.push(path);
+                        } else if SUPPORTED_IMAGE_EXTENSIONS.contains(&&extension[..]) {
+                            fair_dir.image_files.push(path);
+                        } else if UNSUPPORTED_AUDIO_EXTENSIONS.contains(&&extension[..]) {
+                            let error = format!("Support for reading audio files with the extension '{extension}' from the catalog is not yet supported - please get in touch if you need this");
+                            build.error(&error);
                         } else {
-                            warn!("Ignoring unsupported file '{}'", path.display());
+                            fair_dir.push_extra(build, path);
                         }
+                    } else {
+                        fair_dir.push_extra(build, path);
                     }
                 }
+                DirEntryKind::Other => {
+                    let warning = format!("Ignoring unsupported file '{}'", path.display());
+                    build.warning(WarningCategory::IgnoredFile, &warning);
+                }
+                DirEntryKind::Symlink => {
+                    let warning = format!("Ignoring symlink '{}'", path.display());
+                    build.warning(WarningCategory::IgnoredFile, &warning);
+                }
             }
         }
 
+        // Overlays only make sense next to the manifest they translate
+        fair_dir.overlay_manifests.retain(|path| {
+            let main_manifest = match overlay_manifest_name(path) {
+                Some("artist") => &fair_dir.artist_manifest,
+                Some("catalog") => &fair_dir.catalog_manifest,
+                _ => &fair_dir.release_manifest
+            };
+
+            if main_manifest.is_none() {
+                let error = format!("The translated manifest '{}' will be ignored, because there is no regular manifest next to it that it could translate (e.g. 'release.de.eno' needs a 'release.eno' in the same directory)", path.display());
+                build.error(&error);
+                return false
+            }
+
+            true
+        });
+
         fair_dir
     }
 }
+
+/// For a manifest filename like `release.de.eno` (a translation of
+/// `release.eno`) this returns the name of the manifest it translates
+/// ("artist", "catalog" or "release"), otherwise None.
+pub fn overlay_manifest_name(path: &Path) -> Option<&str> {
+    let filename = path.file_name()?.to_str()?;
+    let mut parts = filename.split('.');
+
+    let name = parts.next()?;
+    let language = parts.next()?;
+
+    if parts.next()? != "eno" || parts.next().is_some() {
+        return None
+    }
+
+    if !["artist", "catalog", "release"].contains(&name) ||
+        language.is_empty() ||
+        !language.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+        return None
+    }
+
+    Some(name)
+}
+
+/// Matches a filename against a pattern in which `*` stands for any number
+/// of characters and `?` for exactly one character (e.g. `*.psd`). The
+/// comparison ignores case, as do the extension checks above.
+fn matches_pattern(pattern: &str, filename: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let filename: Vec<char> = filename.to_lowercase().chars().collect();
+
+    let mut p = 0;
+    let mut f = 0;
+
+    // Where the last `*` in the pattern was, and up to where in the
+    // filename it currently matches, for backtracking
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while f < filename.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, f));
+                p += 1;
+            }
+            Some(character) if *character == '?' || *character == filename[f] => {
+                p += 1;
+                f += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_f)) => {
+                    backtrack = Some((star_p, star_f + 1));
+                    p = star_p + 1;
+                    f = star_f + 1;
+                }
+                None => return false
+            }
+        }
+    }
+
+    pattern[p..].iter().all(|character| *character == '*')
+}
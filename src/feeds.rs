@@ -1,7 +1,7 @@
 // SPDX-FileCopyrightText: 2025 Simon Repp
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
-use crate::{Build, Catalog};
+use crate::{Build, Catalog, YearArchivePage};
 
 use translations::Translations;
 
@@ -108,6 +108,12 @@ impl Feeds {
         }
     }
 
+    /// Generate the atom feed for a single year/decade archive page, writing
+    /// it into the directory of that page.
+    pub fn generate_year_archive(build: &Build, catalog: &Catalog, page: &YearArchivePage) {
+        atom::year_archive_atom(build, catalog, page);
+    }
+
     /// <link> tags to be placed in the <head> of the page to point browser to
     /// available feeds for the site.
     pub fn meta_link_tags(
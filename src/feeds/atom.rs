@@ -16,7 +16,9 @@ use crate::{
     FeedImageAsset,
     GENERATOR_INFO,
     Release,
-    SiteUrl
+    ReleaseRc,
+    SiteUrl,
+    YearArchivePage
 };
 use crate::util::html_escape_outside_attribute;
 
@@ -25,6 +27,57 @@ use super::Feeds;
 pub fn atom(build: &Build, catalog: &Catalog) {
     let base_url = build.base_url_unchecked();
     let atom_feed_url = base_url.join_file(Feeds::ATOM_FILENAME);
+    let site_url = base_url.index(build);
+
+    let xml = feed(
+        &atom_feed_url,
+        build,
+        catalog,
+        &catalog.public_releases(),
+        &site_url,
+        &catalog.title()
+    );
+
+    let path = build.build_dir.join(Feeds::ATOM_FILENAME);
+    fs::write(path, xml).unwrap();
+}
+
+/// The feed for a year/decade archive page, only containing the releases
+/// from that year/decade, written to e.g. `/2024/feed.atom`.
+pub fn year_archive_atom(build: &Build, catalog: &Catalog, page: &YearArchivePage) {
+    let base_url = build.base_url_unchecked();
+    let atom_feed_url = base_url.join_file(format!("{}/{}", page.slug, Feeds::ATOM_FILENAME));
+    let page_url = base_url.join_index(build, &page.slug);
+
+    let releases = catalog.public_releases()
+        .into_iter()
+        .filter(|release| page.matches(&release.borrow()))
+        .collect::<Vec<ReleaseRc>>();
+
+    let title = format!("{} – {}", catalog.title(), page.label());
+
+    let xml = feed(
+        &atom_feed_url,
+        build,
+        catalog,
+        &releases,
+        &page_url,
+        &title
+    );
+
+    let path = build.build_dir.join(&page.slug).join(Feeds::ATOM_FILENAME);
+    fs::write(path, xml).unwrap();
+}
+
+fn feed(
+    atom_feed_url: &str,
+    build: &Build,
+    catalog: &Catalog,
+    releases: &[ReleaseRc],
+    site_url: &str,
+    title: &str
+) -> String {
+    let base_url = build.base_url_unchecked();
 
     let author = if catalog.label_mode {
         String::new()
@@ -50,7 +103,7 @@ pub fn atom(build: &Build, catalog: &Catalog) {
     // to avoid statefulness, so this should be carefully weighed off)
     let build_begin = build.build_begin.to_rfc3339();
 
-    let entries = catalog.public_releases()
+    let entries = releases
         .iter()
         .map(|release| {
             entry(
@@ -68,14 +121,12 @@ pub fn atom(build: &Build, catalog: &Catalog) {
     // <icon>/icon.jpg</icon>
     // (see https://validator.w3.org/feed/docs/atom.html#optionalFeedElements)
 
-    let site_url = base_url.index(build);
-
     let logo = if let Some(home_image) = &catalog.home_image {
         let image_ref = home_image.borrow();
 
         let hash = image_ref.hash.as_url_safe_base64();
         let filename = FeedImageAsset::TARGET_FILENAME;
-        let url = base_url.join_file(format!("{filename}?{hash}"));
+        let url = build.asset_url().join_file(format!("{filename}?{hash}"));
 
         format!("<logo>{url}</logo>")
     } else {
@@ -89,12 +140,12 @@ pub fn atom(build: &Build, catalog: &Catalog) {
         String::new()
     };
 
-    let title_escaped = html_escape_outside_attribute(&catalog.title());
+    let title_escaped = html_escape_outside_attribute(title);
 
     let version_detailed = env!("FAIRCAMP_VERSION_DETAILED");
-    let xml = formatdoc!(r#"
+    formatdoc!(r#"
         <?xml version="1.0" encoding="utf-8"?>
-        <feed xmlns="http://www.w3.org/2005/Atom">
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/">
             {author}
             <generator uri="https://simonrepp.com/faircamp" version="{version_detailed}">
                 {GENERATOR_INFO}
@@ -107,10 +158,7 @@ pub fn atom(build: &Build, catalog: &Catalog) {
             <updated>{build_begin}</updated>
             {entries}
         </feed>
-    "#);
-
-    let path = build.build_dir.join(Feeds::ATOM_FILENAME);
-    fs::write(path, xml).unwrap();
+    "#)
 }
 
 fn entry(
@@ -182,13 +230,19 @@ fn entry(
         main_artists
     };
 
-    let artists_and_title = format!("{artists_list} – {}", release.title);
+    let artists_and_title = format!("{artists_list} – {}", release.title_disambiguated());
 
     // TODO: published (optional field which could either use the release `date` or a stateful/user-supplied publishing date we don't have yet)
     // "Contains the time of the initial creation or first availability of the entry."
     // <published>2003-12-13T09:17:51-08:00</published>
     // (see https://validator.w3.org/feed/docs/atom.html#optionalEntryElements)
 
+    // The UPC/EAN barcode of the release (if set), as a Dublin Core identifier
+    let identifier = match &release.upc {
+        Some(upc) => format!("<dc:identifier>{upc}</dc:identifier>"),
+        None => String::new()
+    };
+
     let release_url = base_url.join_index(build, release_slug);
 
     let summary = if let Some(synopsis) = &release.synopsis {
@@ -213,6 +267,7 @@ fn entry(
     formatdoc!(r#"
         <entry>
             {authors}
+            {identifier}
             <id>{release_url}</id>
             <link href="{release_url}" rel="alternate"/>
             <title>{title_escaped}</title>
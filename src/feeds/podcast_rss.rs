@@ -7,6 +7,7 @@
 //! "A Podcaster’s Guide to RSS" from apple as reference:
 //! https://help.apple.com/itc/podcasts_connect/#/itcb54353390
 
+use std::collections::HashMap;
 use std::fs;
 use std::hash::Hash;
 
@@ -18,9 +19,14 @@ use crate::{
     FeedImageAsset,
     Release,
     SiteUrl,
-    TagMapping
+    TagMapping,
+    funding
+};
+use crate::util::{
+    generic_hash,
+    html_escape_inside_attribute,
+    html_escape_outside_attribute
 };
-use crate::util::{generic_hash, html_escape_outside_attribute};
 
 use super::Feeds;
 use super::rss::rss;
@@ -32,10 +38,19 @@ const PODCAST_NAMESPACE_UUID: &str = "ead4c236-bf58-58c6-a2c6-a6b28d128cb6";
 pub fn item_extensions(
     base_url: &SiteUrl,
     build: &Build,
+    // Maps release permalink slugs to episode numbers
+    episode_numbers: &HashMap<String, usize>,
     release: &Release
 ) -> String {
     let mut extensions = Vec::new();
 
+    // category
+
+    for category in &release.podcast_categories {
+        let category_escaped = html_escape_outside_attribute(category);
+        extensions.push(format!("<category>{category_escaped}</category>"));
+    }
+
     // enclosure
 
     let release_slug = &release.permalink.slug;
@@ -62,7 +77,7 @@ pub fn item_extensions(
 
     let track_filename_urlencoded = urlencoding::encode(&track_filename);
     let filepath = format!("{release_slug}/{track_number}/{format_dir}/{track_hash}/{track_filename_urlencoded}");
-    let url = base_url.join_file(filepath);
+    let url = build.asset_url().join_file(filepath);
 
     let source_type = format.source_type();
 
@@ -79,10 +94,21 @@ pub fn item_extensions(
 
     // itunes:duration
 
-    let duration_seconds = transcodes_ref.source_meta.duration_seconds;
+    // Podcast apps expect whole seconds (or HH:MM:SS), fractional seconds
+    // are not accepted by all of them.
+    let duration_seconds = transcodes_ref.source_meta.duration_seconds.round() as u64;
     let itunes_duration = format!(r#"<itunes:duration>{duration_seconds}</itunes:duration>"#);
     extensions.push(itunes_duration);
 
+    // itunes:episode, itunes:episodeType
+
+    if let Some(episode_number) = episode_numbers.get(release_slug) {
+        let itunes_episode = format!(r#"<itunes:episode>{episode_number}</itunes:episode>"#);
+        extensions.push(itunes_episode);
+    }
+
+    extensions.push(String::from("<itunes:episodeType>full</itunes:episodeType>"));
+
     // itunes:image
 
     let image_filename = if let Some(described_image) = &release.cover {
@@ -105,11 +131,22 @@ pub fn item_extensions(
         release.procedural_cover_720_filename_unchecked()
     };
 
-    let image_url = base_url.join_file(format!("{release_slug}/{image_filename}"));
+    let image_url = build.asset_url().join_file(format!("{release_slug}/{image_filename}"));
     let itunes_image = format!(r#"<itunes:image href="{image_url}"/>"#);
 
     extensions.push(itunes_image);
 
+    // podcast:transcript
+
+    if let Some(transcript) = &track.transcript {
+        let filename = transcript.filename();
+        let mime_type = transcript.mime_type();
+        let transcript_url = base_url.join_file(format!("{release_slug}/{track_number}/{filename}"));
+        let podcast_transcript = format!(r#"<podcast:transcript type="{mime_type}" url="{transcript_url}"/>"#);
+
+        extensions.push(podcast_transcript);
+    }
+
     // TODO: Consider further items for future implementation:
     // https://github.com/Podcast-Standards-Project/PSP-1-Podcast-RSS-Specification?tab=readme-ov-file#required-item-elements
 
@@ -137,13 +174,34 @@ pub fn podcast_rss(build: &Build, catalog: &Catalog) {
 
         let hash = image_ref.hash.as_url_safe_base64();
         let filename = FeedImageAsset::TARGET_FILENAME;
-        let url = base_url.join_file(format!("{filename}?{hash}"));
+        let url = build.asset_url().join_file(format!("{filename}?{hash}"));
 
         let itunes_image = format!(r#"<itunes:image href="{url}"/>"#);
 
         extensions.push(itunes_image);
     }
 
+    // Apple's itunes:category only exists at the channel level, so the
+    // channel lists all categories that are used by any of the releases.
+    let mut categories: Vec<String> = Vec::new();
+    let public_releases = catalog.public_releases();
+
+    for release in &public_releases {
+        for category in &release.borrow().podcast_categories {
+            if !categories.contains(category) {
+                categories.push(category.clone());
+            }
+        }
+    }
+
+    for category in categories {
+        let category_escaped = html_escape_inside_attribute(&category);
+        let itunes_category = format!(r#"<itunes:category text="{category_escaped}"/>"#);
+        extensions.push(itunes_category);
+    }
+
+    extensions.extend(funding::podcast_funding_tags(catalog));
+
     let namespace_uuid = Uuid::parse_str(PODCAST_NAMESPACE_UUID).unwrap();
     let normalized_url = base_url.without_scheme_and_trailing_slash();
     let guid = Uuid::new_v5(&namespace_uuid, normalized_url.as_bytes());
@@ -160,13 +218,24 @@ pub fn podcast_rss(build: &Build, catalog: &Catalog) {
         r#"xmlns:podcast="https://podcastindex.org/namespace/1.0""#
     ];
 
+    // Episodes are numbered in the chronological order of their release
+    // dates (releases without a date come first, in catalog order).
+    let mut releases_asc_by_date = public_releases.clone();
+    releases_asc_by_date.sort_by_key(|release| release.borrow().date);
+
+    let episode_numbers = releases_asc_by_date
+        .iter()
+        .enumerate()
+        .map(|(index, release)| (release.borrow().permalink.slug.clone(), index + 1))
+        .collect::<HashMap<String, usize>>();
+
     let xml = rss(
         base_url,
         build,
         catalog,
         &channel_extensions,
         extra_namespaces,
-        &mut item_extensions,
+        &mut |base_url: &SiteUrl, build: &Build, release: &Release| item_extensions(base_url, build, &episode_numbers, release),
         &url
     );
 
@@ -74,7 +74,7 @@ pub fn rss(
         let feed_asset = image_ref.feed_asset_unchecked();
 
         let filename = FeedImageAsset::TARGET_FILENAME;
-        let url = base_url.join_file(format!("{filename}?{hash}"));
+        let url = build.asset_url().join_file(format!("{filename}?{hash}"));
         let edge_size = feed_asset.edge_size;
 
         let image_title = match &home_image.description {
@@ -108,7 +108,7 @@ pub fn rss(
     // https://www.rssboard.org/rss-profile#namespace-elements-atom-link
     formatdoc!(r#"
         <?xml version="1.0" encoding="UTF-8"?>
-        <rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" {extra_namespaces}>
+        <rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/" {extra_namespaces}>
             <channel>
                 <atom:link href="{feed_url}" rel="self" type="application/rss+xml"/>
                 <description>{description}</description>
@@ -154,7 +154,7 @@ fn item(
         main_artists
     };
 
-    let artists_and_title = format!("{artists_list} – {}", release.title);
+    let artists_and_title = format!("{artists_list} – {}", release.title_disambiguated());
 
     let description = if let Some(synopsis) = &release.synopsis {
         let synopsis_escaped = html_double_escape_outside_attribute(synopsis);
@@ -166,6 +166,12 @@ fn item(
         String::new()
     };
 
+    // The UPC/EAN barcode of the release (if set), as a Dublin Core identifier
+    let identifier = match &release.upc {
+        Some(upc) => format!("<dc:identifier>{upc}</dc:identifier>"),
+        None => String::new()
+    };
+
     let link = base_url.join_index(build, release_slug);
 
     let title = html_double_escape_outside_attribute(&artists_and_title);
@@ -177,6 +183,7 @@ fn item(
     formatdoc!(r#"
         <item>
             {description}
+            {identifier}
             <guid>{link}</guid>
             <link>{link}</link>
             <title>{title}</title>
@@ -2,6 +2,7 @@
 // SPDX-FileCopyrightText: 2023 Deborah Pickett
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
 
@@ -9,6 +10,7 @@ use crate::{
     AudioFormat,
     AudioFormatFamily,
     ImageEmbed,
+    TagChapters,
     TagMapping
 };
 
@@ -18,6 +20,47 @@ pub const FFMPEG_BINARY: &str = "ffmpeg";
 #[cfg(target_os = "windows")]
 pub const FFMPEG_BINARY: &str = "ffmpeg.exe";
 
+/// Applies custom codec options based on the target format
+fn apply_codec_flags(command: &mut Command, target_format: AudioFormat) {
+    match target_format {
+        AudioFormat::Aac => (),
+        AudioFormat::Aiff => (),
+        AudioFormat::Alac => {
+            command.arg("-vn");
+            command.arg("-codec:a").arg("alac");
+        }
+        AudioFormat::Flac => (),
+        AudioFormat::Mp3VbrV0 => {
+            command.arg("-codec:a").arg("libmp3lame");
+            command.arg("-qscale:a").arg("0");
+        }
+        AudioFormat::Mp3VbrV5 => {
+            command.arg("-codec:a").arg("libmp3lame");
+            command.arg("-qscale:a").arg("5");
+        }
+        AudioFormat::Mp3VbrV7 => {
+            command.arg("-codec:a").arg("libmp3lame");
+            command.arg("-qscale:a").arg("7");
+        }
+        AudioFormat::OggVorbis => (),
+        AudioFormat::Opus48Kbps => {
+            command.arg("-codec:a").arg("libopus");
+            command.arg("-b:a").arg("48k");
+        }
+        AudioFormat::Opus96Kbps => {
+            command.arg("-codec:a").arg("libopus");
+            command.arg("-b:a").arg("96k");
+        }
+        AudioFormat::Opus128Kbps => {
+            command.arg("-codec:a").arg("libopus");
+            command.arg("-b:a").arg("128k");
+        }
+        // Originals are copied verbatim and never pass through ffmpeg
+        AudioFormat::Original => unreachable!(),
+        AudioFormat::Wav => ()
+    }
+}
+
 /// FFmpeg does not always copy tags, this depends on the combination of
 /// source and target format for a specific transcode. This function applies
 /// extra flags to ensure tag copying in as many format combinations as
@@ -68,6 +111,47 @@ fn apply_tag_copy_flags(
     }
 }
 
+/// Returns the chapters in ffmpeg's metadata format, which is passed to
+/// ffmpeg as an additional input.
+/// (see https://ffmpeg.org/ffmpeg-formats.html#Metadata-2)
+fn chapters_ffmetadata(chapters: &TagChapters) -> String {
+    let escape = |value: &str| {
+        value
+            .replace('\\', "\\\\")
+            .replace('=', "\\=")
+            .replace(';', "\\;")
+            .replace('#', "\\#")
+            .replace('\n', "\\\n")
+    };
+
+    let mut ffmetadata = String::from(";FFMETADATA1\n");
+
+    for chapter in &chapters.0 {
+        ffmetadata.push_str(&format!(
+            "[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            chapter.start_ms,
+            chapter.end_ms,
+            escape(&chapter.title)
+        ));
+    }
+
+    ffmetadata
+}
+
+/// Whether ffmpeg's muxer for the format writes chapters.
+fn supports_chapters(target_format_family: AudioFormatFamily) -> bool {
+    match target_format_family {
+        AudioFormatFamily::Alac |
+        AudioFormatFamily::Flac |
+        AudioFormatFamily::Mp3 |
+        AudioFormatFamily::OggVorbis |
+        AudioFormatFamily::Opus => true,
+        AudioFormatFamily::Aac |
+        AudioFormatFamily::Aiff |
+        AudioFormatFamily::Wav => false
+    }
+}
+
 /// FFmpeg does not always write tags, this depends on the muxer used for
 // a specific format. This function applies extra flags to enable tag
 // writing for all formats.
@@ -97,13 +181,17 @@ pub fn transcode(
     output_file: &Path,
     source_format_family: AudioFormatFamily,
     target_format: AudioFormat,
-    tag_mapping: &TagMapping
+    tag_mapping: &TagMapping,
+    nice: Option<u8>
 ) -> Result<(), String> {
-    let mut command = Command::new(FFMPEG_BINARY);
+    let mut command = ffmpeg_command(nice);
     
     command.arg("-y");
     command.arg("-i").arg(input_file);
 
+    // Written next to the output file while transcoding, removed afterwards
+    let mut chapters_file = None;
+
     match tag_mapping {
         TagMapping::Copy => {
             let target_format_family = target_format.family();
@@ -111,9 +199,27 @@ pub fn transcode(
             apply_tag_copy_flags(&mut command, source_format_family, target_format_family);
             apply_tag_write_flags(&mut command, target_format_family);
         }
-        TagMapping::Custom { album, album_artist, artist, image, title, track } => {
-            if let Some(ImageEmbed::Write(_))  = image {
+        TagMapping::Custom { album, album_artist, artist, chapters, gapless, identifiers, image, lyrics, title, track } => {
+            let mut next_input_index = 1;
+
+            if let Some(ImageEmbed::Write(_) | ImageEmbed::WriteResized(..)) = image {
                 command.arg("-i").arg(cover_path.unwrap());
+                next_input_index += 1;
+            }
+
+            // Inputs need to be specified before any output options, hence
+            // the chapters are added as an input here already.
+            if !chapters.0.is_empty() && supports_chapters(target_format.family()) {
+                let path = output_file.with_extension("ffmetadata");
+
+                if let Err(err) = fs::write(&path, chapters_ffmetadata(chapters)) {
+                    return Err(format!("The chapters could not be written for ffmpeg to read them.\n\n{err}"));
+                }
+
+                command.arg("-i").arg(&path);
+                command.arg("-map_chapters").arg(next_input_index.to_string());
+
+                chapters_file = Some(path);
             }
 
             command.arg("-map_metadata").arg("-1");
@@ -130,12 +236,52 @@ pub fn transcode(
                 command.arg("-metadata").arg(format!("artist={}", artist));
             }
 
+            if gapless.0 {
+                // Written as the 'pgap' atom in mp4 containers, and as a
+                // TXXX frame in ID3 tags, which is how iTunes (and players
+                // following its conventions) recognize gapless albums.
+                match target_format.family() {
+                    AudioFormatFamily::Aac |
+                    AudioFormatFamily::Alac => {
+                        command.arg("-metadata").arg("gapless_playback=1");
+                    }
+                    AudioFormatFamily::Mp3 => {
+                        command.arg("-metadata").arg("iTunPGAP=1");
+                    }
+                    _ => ()
+                }
+            }
+
+            // ID3 tags have a dedicated frame for the ISRC (TSRC), the
+            // barcode is written as a TXXX frame named BARCODE (as does
+            // e.g. MusicBrainz Picard). Vorbis comments use ISRC and
+            // BARCODE fields. The mp4 and wav muxers of ffmpeg do not write
+            // either of them.
+            let (isrc_key, upc_key) = match target_format.family() {
+                AudioFormatFamily::Aiff |
+                AudioFormatFamily::Mp3 => (Some("TSRC"), Some("BARCODE")),
+                AudioFormatFamily::Flac |
+                AudioFormatFamily::OggVorbis |
+                AudioFormatFamily::Opus => (Some("ISRC"), Some("BARCODE")),
+                AudioFormatFamily::Aac |
+                AudioFormatFamily::Alac |
+                AudioFormatFamily::Wav => (None, None)
+            };
+
+            if let (Some(key), Some(isrc)) = (isrc_key, &identifiers.isrc) {
+                command.arg("-metadata").arg(format!("{key}={isrc}"));
+            }
+
+            if let (Some(key), Some(upc)) = (upc_key, &identifiers.upc) {
+                command.arg("-metadata").arg(format!("{key}={upc}"));
+            }
+
             match image {
                 Some(ImageEmbed::Copy) => {
                     command.arg("-c:v").arg("copy");
                     command.arg("-disposition:v:0").arg("attached_pic");
                 }
-                Some(ImageEmbed::Write(_)) => {
+                Some(ImageEmbed::Write(_) | ImageEmbed::WriteResized(..)) => {
                     match target_format.family() {
                         AudioFormatFamily::Aac => {
                             // Found no working example for adding cover art to AAC with ffmpeg so far.
@@ -191,6 +337,20 @@ pub fn transcode(
                 }
             }
 
+            if let Some(lyrics) = &lyrics.0 {
+                // With ID3 tags, ffmpeg only writes lyrics (as an USLT frame)
+                // for keys of the form "lyrics-[language]", "XXX" stands for
+                // an unknown language.
+                let key = match target_format.family() {
+                    AudioFormatFamily::Aac |
+                    AudioFormatFamily::Aiff |
+                    AudioFormatFamily::Mp3 => "lyrics-XXX",
+                    _ => "lyrics"
+                };
+
+                command.arg("-metadata").arg(format!("{key}={lyrics}"));
+            }
+
             if let Some(title) = title {
                 command.arg("-metadata").arg(format!("title={}", title));
             }
@@ -207,46 +367,23 @@ pub fn transcode(
         }
     }
 
-    // Apply custom codec options based on the target format
-    match target_format {
-        AudioFormat::Aac => (),
-        AudioFormat::Aiff => (),
-        AudioFormat::Alac => {
-            command.arg("-vn");
-            command.arg("-codec:a").arg("alac");
-        }
-        AudioFormat::Flac => (),
-        AudioFormat::Mp3VbrV0 => {
-            command.arg("-codec:a").arg("libmp3lame");
-            command.arg("-qscale:a").arg("0");
-        }
-        AudioFormat::Mp3VbrV5 => {
-            command.arg("-codec:a").arg("libmp3lame");
-            command.arg("-qscale:a").arg("5");
-        }
-        AudioFormat::Mp3VbrV7 => {
-            command.arg("-codec:a").arg("libmp3lame");
-            command.arg("-qscale:a").arg("7");
-        }
-        AudioFormat::OggVorbis => (),
-        AudioFormat::Opus48Kbps => {
-            command.arg("-codec:a").arg("libopus");
-            command.arg("-b:a").arg("48k");
-        }
-        AudioFormat::Opus96Kbps => {
-            command.arg("-codec:a").arg("libopus");
-            command.arg("-b:a").arg("96k");
-        }
-        AudioFormat::Opus128Kbps => {
-            command.arg("-codec:a").arg("libopus");
-            command.arg("-b:a").arg("128k");
-        }
-        AudioFormat::Wav => ()
+    // FLAC to FLAC only needs its tags rewritten, re-encoding the
+    // (lossless) audio would only cost time without any benefit.
+    if target_format == AudioFormat::Flac && source_format_family == AudioFormatFamily::Flac {
+        command.arg("-codec:a").arg("copy");
+    } else {
+        apply_codec_flags(&mut command, target_format);
     }
     
     command.arg(output_file);
 
-    match command.output() {
+    let result = command.output();
+
+    if let Some(path) = chapters_file {
+        let _ = fs::remove_file(path);
+    }
+
+    match result {
         Ok(output) => {
             if output.status.success() {
                 Ok(())
@@ -265,3 +402,142 @@ fn transcode_debug_output(output: Output) -> String {
 
     format!("stderr: {}\n\nstdout: {}", stderr, stdout)
 }
+
+/// Creates the command for running ffmpeg. If a niceness was requested
+/// (through --transcode-nice), ffmpeg is run through nice, which on Linux
+/// lowers its I/O priority accordingly too. On Windows there is no nice,
+/// there ffmpeg always runs with normal priority.
+fn ffmpeg_command(nice: Option<u8>) -> Command {
+    #[cfg(not(target_os = "windows"))]
+    if let Some(nice) = nice {
+        let mut command = Command::new("nice");
+
+        command.arg("-n").arg(nice.to_string());
+        command.arg(FFMPEG_BINARY);
+
+        return command;
+    }
+
+    #[cfg(target_os = "windows")]
+    let _ = nice;
+
+    Command::new(FFMPEG_BINARY)
+}
+
+/// Transcodes a short excerpt (without any tags or images) of the input
+/// file, used for the format comparison clips on download pages.
+pub fn transcode_excerpt(
+    input_file: &Path,
+    output_file: &Path,
+    target_format: AudioFormat,
+    start_seconds: f32,
+    duration_seconds: f32,
+    nice: Option<u8>
+) -> Result<(), String> {
+    let mut command = ffmpeg_command(nice);
+
+    command.arg("-y");
+    command.arg("-ss").arg(format!("{start_seconds:.3}"));
+    command.arg("-t").arg(format!("{duration_seconds:.3}"));
+    command.arg("-i").arg(input_file);
+    command.arg("-map_metadata").arg("-1");
+    command.arg("-vn");
+
+    apply_codec_flags(&mut command, target_format);
+
+    command.arg(output_file);
+
+    match command.output() {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(())
+            } else {
+                let ffmpeg_output = transcode_debug_output(output);
+                Err(format!("The ffmpeg child process returned an error exit code.\n\n{}", ffmpeg_output))
+            }
+        }
+        Err(err) => Err(format!("The ffmpeg child process could not be executed.\n\n{err}"))
+    }
+}
+
+/// Encodes a silent, looping-ready video for a visual loop (H.264 in MP4, as
+/// it plays back in all browsers). With concat_list the input is an ffmpeg
+/// concat demuxer file listing the frames of an image sequence, otherwise
+/// it is a video file. The video is cut off after max_duration_seconds and
+/// scaled down so that its longer edge does not exceed max_edge_size.
+pub fn encode_visual_loop(
+    input_file: &Path,
+    concat_list: bool,
+    output_file: &Path,
+    max_duration_seconds: f32,
+    max_edge_size: u32,
+    nice: Option<u8>
+) -> Result<(), String> {
+    let mut command = ffmpeg_command(nice);
+
+    command.arg("-y");
+
+    if concat_list {
+        command.arg("-f").arg("concat");
+        // The listed frames are referenced by absolute paths
+        command.arg("-safe").arg("0");
+    }
+
+    command.arg("-i").arg(input_file);
+    command.arg("-t").arg(format!("{max_duration_seconds:.3}"));
+    command.arg("-map_metadata").arg("-1");
+    command.arg("-an");
+
+    // Scale down to fit the maximum edge size (keeping the aspect ratio),
+    // then round to even dimensions as required by the yuv420p pixel format
+    command.arg("-vf").arg(format!(
+        "scale=w='min({max_edge_size},iw)':h='min({max_edge_size},ih)':force_original_aspect_ratio=decrease,scale=trunc(iw/2)*2:trunc(ih/2)*2"
+    ));
+
+    command.arg("-codec:v").arg("libx264");
+    command.arg("-crf").arg("28");
+    command.arg("-pix_fmt").arg("yuv420p");
+    command.arg("-movflags").arg("+faststart");
+
+    command.arg(output_file);
+
+    match command.output() {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(())
+            } else {
+                let ffmpeg_output = transcode_debug_output(output);
+                Err(format!("The ffmpeg child process returned an error exit code.\n\n{}", ffmpeg_output))
+            }
+        }
+        Err(err) => Err(format!("The ffmpeg child process could not be executed.\n\n{err}"))
+    }
+}
+
+/// Writes the first frame of the input video as a JPEG image
+pub fn extract_poster_frame(
+    input_file: &Path,
+    output_file: &Path,
+    nice: Option<u8>
+) -> Result<(), String> {
+    let mut command = ffmpeg_command(nice);
+
+    command.arg("-y");
+    command.arg("-i").arg(input_file);
+    command.arg("-frames:v").arg("1");
+    command.arg("-qscale:v").arg("3");
+
+    command.arg(output_file);
+
+    match command.output() {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(())
+            } else {
+                let ffmpeg_output = transcode_debug_output(output);
+                Err(format!("The ffmpeg child process returned an error exit code.\n\n{}", ffmpeg_output))
+            }
+        }
+        Err(err) => Err(format!("The ffmpeg child process could not be executed.\n\n{err}"))
+    }
+}
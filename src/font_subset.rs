@@ -0,0 +1,169 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Subsetting of custom theme fonts down to the characters that actually
+//! appear on the site. This is done through pyftsubset (part of fonttools,
+//! see https://github.com/fonttools/fonttools), which - like ffmpeg - is
+//! called as an external program. If it is not available, custom fonts are
+//! included in full, as before.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::{Build, Catalog, Link, Locale, Localizations};
+
+#[cfg(not(target_os = "windows"))]
+pub const PYFTSUBSET_BINARY: &str = "pyftsubset";
+
+#[cfg(target_os = "windows")]
+pub const PYFTSUBSET_BINARY: &str = "pyftsubset.exe";
+
+/// Printable ASCII is always retained, so that numbers, punctuation and
+/// (most) text typed by visitors, e.g. into the search, never falls back
+/// to another font.
+const ALWAYS_RETAINED: std::ops::RangeInclusive<char> = ' '..='~';
+
+/// Collect all characters that can appear on the pages of the catalog, that
+/// is all user-provided texts (titles, names, descriptions, link labels)
+/// plus all interface translations for the language the catalog is built in.
+/// The styles (and with them the fonts) are shared by the sites for the
+/// additional `languages`, so their translations and overlay texts (e.g.
+/// from release.de.eno) are included as well.
+pub fn catalog_characters(build: &Build, catalog: &Catalog) -> BTreeSet<char> {
+    let mut characters = BTreeSet::from_iter(ALWAYS_RETAINED);
+
+    let mut add = |text: &str| characters.extend(text.chars().filter(|character| !character.is_control()));
+
+    for (_key, translation, _multiline) in build.locale.translations.all_strings() {
+        add(**translation);
+    }
+
+    for language in &catalog.languages {
+        let locale = Locale::from_code(language);
+        for (_key, translation, _multiline) in locale.translations.all_strings() {
+            add(**translation);
+        }
+    }
+
+    add(&catalog.title());
+    if let Some(more) = &catalog.more { add(&more.stripped); }
+    if let Some(more_label) = &catalog.more_label { add(more_label); }
+    if let Some(synopsis) = &catalog.synopsis { add(synopsis); }
+    for link in &catalog.links { add(link_label(link)); }
+    for text in overlay_texts(&catalog.localizations) { add(text); }
+
+    for artist in &catalog.artists {
+        let artist_ref = artist.borrow();
+
+        add(&artist_ref.name);
+        if let Some(more) = &artist_ref.more { add(&more.stripped); }
+        if let Some(more_label) = &artist_ref.more_label { add(more_label); }
+        if let Some(synopsis) = &artist_ref.synopsis { add(synopsis); }
+        for link in &artist_ref.links { add(link_label(link)); }
+        for text in overlay_texts(&artist_ref.localizations) { add(text); }
+    }
+
+    for release in &catalog.releases {
+        let release_ref = release.borrow();
+
+        add(&release_ref.title);
+        if let Some(more) = &release_ref.more { add(&more.stripped); }
+        if let Some(more_label) = &release_ref.more_label { add(more_label); }
+        if let Some(synopsis) = &release_ref.synopsis { add(synopsis); }
+        for link in &release_ref.links { add(link_label(link)); }
+        for text in overlay_texts(&release_ref.localizations) { add(text); }
+
+        for track in &release_ref.tracks {
+            add(&track.title());
+            if let Some(more) = &track.more { add(&more.stripped); }
+            if let Some(more_label) = &track.more_label { add(more_label); }
+            if let Some(synopsis) = &track.synopsis { add(synopsis); }
+        }
+    }
+
+    characters
+}
+
+fn link_label(link: &Link) -> &str {
+    match link {
+        Link::Anchor { label, .. } => label,
+        Link::Full { label, .. } => label
+    }
+}
+
+/// The texts from the overlay manifests (e.g. release.de.eno)
+fn overlay_texts(localizations: &Localizations) -> Vec<&str> {
+    let mut texts = Vec::new();
+
+    for overlay in localizations.overlays() {
+        if let Some(more) = &overlay.more { texts.push(more.stripped.as_str()); }
+        if let Some(synopsis) = &overlay.synopsis { texts.push(synopsis.as_str()); }
+        if let Some(title) = &overlay.title { texts.push(title.as_str()); }
+    }
+
+    texts
+}
+
+/// Write a woff2 subset of the font at source_path to target_path, retaining
+/// only the given characters (as well as all layout features, so kerning and
+/// ligatures keep working).
+pub fn subset(
+    characters: &BTreeSet<char>,
+    source_path: &Path,
+    target_path: &Path
+) -> Result<(), String> {
+    let text_path = target_path.with_extension("txt");
+
+    fs::write(&text_path, String::from_iter(characters)).unwrap();
+
+    let mut command = Command::new(PYFTSUBSET_BINARY);
+
+    command.arg(source_path);
+    command.arg("--flavor=woff2");
+    command.arg("--layout-features=*");
+    command.arg(format!("--output-file={}", target_path.display()));
+    command.arg(format!("--text-file={}", text_path.display()));
+
+    let result = match command.output() {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(())
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(format!("The {PYFTSUBSET_BINARY} child process returned an error exit code.\n\n{stderr}"))
+            }
+        }
+        Err(_) => Err(format!("The {PYFTSUBSET_BINARY} child process could not be executed."))
+    };
+
+    let _ = fs::remove_file(&text_path);
+
+    result
+}
+
+/// Render the characters as a css unicode-range descriptor value, merging
+/// consecutive code points into ranges (e.g. "U+20-7E, U+E4, U+F6").
+pub fn unicode_range(characters: &BTreeSet<char>) -> String {
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+
+    for code_point in characters.iter().map(|character| *character as u32) {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == code_point => *end = code_point,
+            _ => ranges.push((code_point, code_point))
+        }
+    }
+
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                format!("U+{start:X}")
+            } else {
+                format!("U+{start:X}-{end:X}")
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
@@ -0,0 +1,144 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Short clips of the same excerpt of a track, transcoded to each download
+//! format of a release, so that visitors can compare them by ear on the
+//! download page before choosing a format.
+
+use std::path::Path;
+
+use crate::{
+    Asset,
+    AssetIntent,
+    AudioFormat,
+    Build,
+    Clip,
+    DownloadFormat,
+    Release,
+    ffmpeg,
+    util
+};
+use crate::util::generic_hash;
+
+/// The clips are written to this directory inside the release directory
+pub const FORMAT_COMPARISON_DIRNAME: &str = "format-comparison";
+
+const CLIP_DURATION_SECONDS: f32 = 15.0;
+
+/// Configured through the release manifest's `format_comparison` option.
+#[derive(Clone, Debug)]
+pub struct FormatComparison {
+    /// If not specified, the excerpt starts a third into the track
+    pub start_seconds: Option<f32>,
+    /// 1-based, as shown in the tracklist
+    pub track_number: usize
+}
+
+impl FormatComparison {
+    /// Browsers can not play back AIFF and ALAC (reliably), for those we
+    /// offer FLAC clips instead, which sound exactly the same as all three
+    /// are lossless. The same goes for original files, whose format is not
+    /// known in advance - a FLAC clip sounds exactly like the source.
+    fn clip_format(download_format: DownloadFormat) -> AudioFormat {
+        match download_format.as_audio_format() {
+            AudioFormat::Aiff |
+            AudioFormat::Alac |
+            AudioFormat::Original => AudioFormat::Flac,
+            audio_format => audio_format
+        }
+    }
+
+    pub fn clip_filename(download_format: DownloadFormat) -> String {
+        let dirname = download_format.as_audio_format().asset_dirname();
+        let extension = FormatComparison::clip_format(download_format).extension();
+        format!("{dirname}{extension}")
+    }
+
+    pub fn new(start_seconds: Option<f32>, track_number: usize) -> FormatComparison {
+        FormatComparison {
+            start_seconds,
+            track_number
+        }
+    }
+
+    /// Writes one clip per download format of the release to the release
+    /// directory. The clips are cached along with the transcodes of the
+    /// track, so they are only transcoded again when the track or the
+    /// excerpt changes.
+    pub fn write_clips(&self, build: &mut Build, release: &Release, release_dir: &Path) {
+        let Some(track) = self.track_number.checked_sub(1).and_then(|index| release.tracks.get(index)) else {
+            let error = format!(
+                "The format_comparison of release '{}' refers to track {}, but the release only has {} track(s) - no comparison clips were generated",
+                release.title,
+                self.track_number,
+                release.tracks.len()
+            );
+            build.error(&error);
+            return;
+        };
+
+        let track_duration = track.transcodes.borrow().source_meta.duration_seconds;
+        let duration_seconds = CLIP_DURATION_SECONDS.min(track_duration);
+        let latest_start = (track_duration - duration_seconds).max(0.0);
+        let start_seconds = self.start_seconds
+            .unwrap_or(track_duration / 3.0)
+            .min(latest_start);
+
+        // f32 does not implement Hash, we hash the bit patterns instead
+        let excerpt_signature = generic_hash(&(start_seconds.to_bits(), duration_seconds.to_bits()));
+
+        let comparison_dir = release_dir.join(FORMAT_COMPARISON_DIRNAME);
+        util::ensure_dir_all(&comparison_dir);
+
+        let source_path = build.catalog_dir.join(&track.transcodes.file_meta.path);
+
+        for download_format in &release.download_formats {
+            let clip_format = FormatComparison::clip_format(*download_format);
+            let mut transcodes_mut = track.transcodes.borrow_mut();
+
+            let cached_clip = transcodes_mut.clips
+                .iter_mut()
+                .find(|clip| clip.excerpt_signature == excerpt_signature && clip.format == clip_format);
+
+            let cached_filename = match cached_clip {
+                Some(clip) => {
+                    clip.asset.unmark_stale();
+                    clip.asset.filename.clone()
+                }
+                None => {
+                    let target_filename = format!("{}{}", util::uid(), clip_format.extension());
+
+                    info_transcoding!("{:?} to {} (format comparison clip)", track.transcodes.file_meta.path, clip_format);
+
+                    let result = build.timings.measure("Transcoding format comparison clips", || {
+                        ffmpeg::transcode_excerpt(
+                            &source_path,
+                            &build.cache_dir.join(&target_filename),
+                            clip_format,
+                            start_seconds,
+                            duration_seconds,
+                            build.transcode_nice
+                        )
+                    });
+
+                    if let Err(err) = result {
+                        let error = format!("Could not generate the {} format comparison clip for release '{}': {err}", download_format.user_label(), release.title);
+                        build.error(&error);
+                        continue;
+                    }
+
+                    let asset = Asset::new(build, target_filename.clone(), AssetIntent::Deliverable);
+                    transcodes_mut.clips.push(Clip::new(asset, excerpt_signature, clip_format));
+                    transcodes_mut.persist_to_cache(&build.cache_dir);
+
+                    target_filename
+                }
+            };
+
+            util::hard_link_or_copy(
+                build.cache_dir.join(&cached_filename),
+                comparison_dir.join(FormatComparison::clip_filename(*download_format))
+            );
+        }
+    }
+}
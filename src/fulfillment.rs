@@ -0,0 +1,228 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Through --fulfillment-sheets, a sheet is written for each release whose
+//! downloads (or track downloads) are protected by download codes or a
+//! paycurtain, listing all urls an operator needs to hand out downloads
+//! manually (e.g. after verifying a payment that was made off-site), along
+//! with a suggested email text. The sheets are written as CSV (for
+//! spreadsheets and mail merge) and Markdown (for reading) to a hidden
+//! directory in the catalog directory - they are not part of the build and
+//! therefore never deployed.
+
+use std::fs;
+use std::hash::Hash;
+
+use indoc::formatdoc;
+
+use crate::{Build, Catalog, DownloadAccess, Release, TRACK_NUMBERS};
+use crate::util;
+
+pub const FULFILLMENT_DIRNAME: &str = ".faircamp_fulfillment";
+
+/// One url that can be handed out to give someone access to downloads
+struct FulfillmentEntry {
+    /// "code" (a download code url that directly opens the downloads),
+    /// "unlock" (the page where a code can be entered) or "paycurtain" (the
+    /// download page that is otherwise reached after the paycurtain)
+    access: &'static str,
+    /// Only set for entries of the "code" access
+    code: Option<String>,
+    /// The release or track title
+    item: String,
+    url: String
+}
+
+/// Quotes the value for use as a CSV field (RFC 4180)
+fn csv_field(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+/// Collects the entries for a release or one of its tracks (track_number is
+/// given for tracks), which are located at path inside the build directory.
+fn entries(
+    build: &Build,
+    download_access: &DownloadAccess,
+    item: &str,
+    path: &str,
+    release: &Release,
+    track_number: Option<usize>
+) -> Vec<FulfillmentEntry> {
+    let t_downloads_permalink = build.locale.downloads_permalink();
+    let mut entries = Vec::new();
+
+    match download_access {
+        DownloadAccess::Code { download_codes, .. } => {
+            let t_unlock_permalink = build.locale.unlock_permalink();
+            let unlock_page_hash = build.hash_with_salt(|hasher| {
+                release.permalink.slug.hash(hasher);
+                if let Some(track_number) = track_number {
+                    track_number.hash(hasher);
+                }
+                t_unlock_permalink.hash(hasher);
+            });
+
+            entries.push(FulfillmentEntry {
+                access: "unlock",
+                code: None,
+                item: item.to_string(),
+                url: url(build, &format!("{path}/{t_unlock_permalink}/{unlock_page_hash}"))
+            });
+
+            for code in download_codes {
+                entries.push(FulfillmentEntry {
+                    access: "code",
+                    code: Some(code.clone()),
+                    item: item.to_string(),
+                    url: url(build, &format!("{path}/{t_downloads_permalink}/{code}"))
+                });
+            }
+        }
+        DownloadAccess::Paycurtain { payment_info: Some(_), .. } => {
+            let download_page_hash = build.hash_with_salt(|hasher| {
+                release.permalink.slug.hash(hasher);
+                if let Some(track_number) = track_number {
+                    track_number.hash(hasher);
+                }
+                t_downloads_permalink.hash(hasher);
+            });
+
+            entries.push(FulfillmentEntry {
+                access: "paycurtain",
+                code: None,
+                item: item.to_string(),
+                url: url(build, &format!("{path}/{t_downloads_permalink}/{download_page_hash}"))
+            });
+        }
+        DownloadAccess::Disabled |
+        DownloadAccess::External { .. } |
+        DownloadAccess::Free |
+        DownloadAccess::Paycurtain { payment_info: None, .. } => ()
+    }
+
+    entries
+}
+
+/// Absolute url if base_url is set, otherwise a path relative to the root
+/// of the site.
+fn url(build: &Build, path: &str) -> String {
+    match &build.base_url {
+        Some(base_url) => base_url.join_index(build, path),
+        None => format!("/{path}{}", build.index_suffix())
+    }
+}
+
+pub fn write_sheets(build: &Build, catalog: &Catalog) {
+    let fulfillment_dir = build.catalog_dir.join(FULFILLMENT_DIRNAME);
+    let mut sheets_written = 0;
+
+    util::ensure_empty_dir(&fulfillment_dir);
+
+    for release in &catalog.releases {
+        let release_ref = release.borrow();
+        let release_slug = &release_ref.permalink.slug;
+
+        let mut release_entries = Vec::new();
+
+        if !release_ref.download_formats.is_empty() ||
+           ((release_ref.extra_downloads.bundled || release_ref.extra_downloads.separate) && !release_ref.extras.is_empty()) {
+            release_entries.extend(entries(
+                build,
+                &release_ref.download_access,
+                &release_ref.title,
+                release_slug,
+                &release_ref,
+                None
+            ));
+        }
+
+        for (track, track_number) in release_ref.tracks.iter().zip(TRACK_NUMBERS) {
+            if !track.download_formats.is_empty() ||
+               (track.extra_downloads && !track.extras.is_empty()) {
+                let item = format!("{} {}", release_ref.track_numbering.format(track_number), track.title());
+                release_entries.extend(entries(
+                    build,
+                    &track.download_access,
+                    &item,
+                    &format!("{release_slug}/{track_number}"),
+                    &release_ref,
+                    Some(track_number)
+                ));
+            }
+        }
+
+        if release_entries.is_empty() {
+            continue;
+        }
+
+        let csv_rows = release_entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{},{},{},{}",
+                    csv_field(&entry.item),
+                    entry.access,
+                    csv_field(entry.code.as_deref().unwrap_or("")),
+                    csv_field(&entry.url)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let csv = format!("item,access,code,url\n{csv_rows}\n");
+
+        let markdown_rows = release_entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "| {} | {} | {} | <{}> |",
+                    entry.item.replace('|', "\\|"),
+                    entry.access,
+                    entry.code.as_deref().unwrap_or("–"),
+                    entry.url
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let release_title = &release_ref.title;
+        let markdown = formatdoc!(r#"
+            # Fulfillment sheet for "{release_title}"
+
+            Hand out one url per purchase:
+
+            - **code**: Opens the downloads right away, each code url should only be handed out once
+            - **unlock**: The page where a download code can be entered by hand
+            - **paycurtain**: The download page that is otherwise reached through the paycurtain
+
+            | Item | Access | Code | Url |
+            | --- | --- | --- | --- |
+            {markdown_rows}
+
+            ## Suggested email text
+
+            > Subject: Your download of "{release_title}"
+            >
+            > Hi,
+            >
+            > thank you for your support! Your payment was received, you can now download "{release_title}" here:
+            >
+            > [URL]
+            >
+            > (If the link does not open directly, go to the unlock page and enter the code [CODE] there.)
+            >
+            > All the best!
+        "#);
+
+        fs::write(fulfillment_dir.join(format!("{release_slug}.csv")), csv).unwrap();
+        fs::write(fulfillment_dir.join(format!("{release_slug}.md")), markdown).unwrap();
+
+        sheets_written += 1;
+    }
+
+    if build.base_url.is_none() && sheets_written > 0 {
+        info!("No base_url is set for the catalog, the urls in the fulfillment sheets are therefore relative to the root of your site");
+    }
+
+    info_stats!("{} fulfillment sheet(s) were written to {}", sheets_written, fulfillment_dir.display());
+}
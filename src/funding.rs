@@ -0,0 +1,94 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Machine-readable funding metadata, through which aggregators (podcast
+//! apps, funding platforms) and browsers can discover how to support the
+//! artist/label. This complements, but does not replace, human-facing
+//! payment_info and links.
+
+use crate::{Build, Catalog, SiteUrl};
+use crate::util::{html_escape_inside_attribute, html_escape_outside_attribute, json_escape};
+
+/// A single channel through which the artist/label can be funded,
+/// configured through the catalog's `funding` option.
+#[derive(Debug)]
+pub struct FundingChannel {
+    pub label: Option<String>,
+    pub url: String
+}
+
+impl FundingChannel {
+    /// The label if one was provided, otherwise the url without scheme.
+    pub fn display_label(&self) -> &str {
+        match &self.label {
+            Some(label) => label,
+            None => SiteUrl::pretty_display(&self.url)
+        }
+    }
+
+    pub fn new(label: Option<String>, url: String) -> FundingChannel {
+        FundingChannel {
+            label,
+            url
+        }
+    }
+}
+
+/// Renders a funding manifest following the structure of fundingjson.org
+/// (v1.0.0). We only know about the entity (the catalog) and its funding
+/// channels, therefore projects, plans and history are left empty.
+pub fn funding_json(build: &Build, catalog: &Catalog) -> String {
+    let channels = catalog.funding
+        .iter()
+        .enumerate()
+        .map(|(index, channel)| {
+            format!(
+                "      {{\n        \"address\": \"{}\",\n        \"description\": \"{}\",\n        \"guid\": \"channel-{}\",\n        \"type\": \"payment-provider\"\n      }}",
+                json_escape(&channel.url),
+                json_escape(channel.display_label()),
+                index + 1
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",\n");
+
+    let entity_type = match catalog.label_mode {
+        true => "organisation",
+        false => "individual"
+    };
+
+    let webpage_url = match &build.base_url {
+        Some(base_url) => format!(",\n    \"webpageUrl\": {{ \"url\": \"{}\" }}", json_escape(base_url.prefix())),
+        None => String::new()
+    };
+
+    format!(
+        "{{\n  \"entity\": {{\n    \"name\": \"{}\",\n    \"role\": \"owner\",\n    \"type\": \"{entity_type}\"{webpage_url}\n  }},\n  \"funding\": {{\n    \"channels\": [\n{channels}\n    ],\n    \"history\": [],\n    \"plans\": []\n  }},\n  \"projects\": [],\n  \"version\": \"v1.0.0\"\n}}\n",
+        json_escape(&catalog.title())
+    )
+}
+
+/// The <link rel="payment"> tags for the head of all pages.
+pub fn link_tags(catalog: &Catalog) -> String {
+    catalog.funding
+        .iter()
+        .map(|channel| {
+            let title_escaped = html_escape_inside_attribute(channel.display_label());
+            let url_escaped = html_escape_inside_attribute(&channel.url);
+            format!(r#"<link href="{url_escaped}" rel="payment" title="{title_escaped}">"#)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// The <podcast:funding> tags for the channel of the podcast feed.
+pub fn podcast_funding_tags(catalog: &Catalog) -> Vec<String> {
+    catalog.funding
+        .iter()
+        .map(|channel| {
+            let label_escaped = html_escape_outside_attribute(channel.display_label());
+            let url_escaped = html_escape_inside_attribute(&channel.url);
+            format!(r#"<podcast:funding url="{url_escaped}">{label_escaped}</podcast:funding>"#)
+        })
+        .collect()
+}
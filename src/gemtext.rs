@@ -0,0 +1,257 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+/// Gemtext format reference:
+/// - https://geminiprotocol.net/docs/gemtext-specification.gmi
+/// - https://en.wikipedia.org/wiki/Gemini_(protocol)
+///
+/// The text-only export mirrors the structure of the html site (homepage,
+/// release pages, artist pages) with an index.gmi file placed next to each
+/// index.html file, so that the build directory can be served both over
+/// http(s) and over gemini. All links are relative, hence no base_url is
+/// required for this export.
+
+use std::fs;
+use std::hash::Hash;
+
+use crate::{
+    Artist,
+    Build,
+    Catalog,
+    DownloadAccess,
+    Link,
+    Release,
+    TRACK_NUMBERS
+};
+use crate::util::format_time;
+
+pub const GEMTEXT_INDEX_FILENAME: &str = "index.gmi";
+
+/// Generate gemtext content for the artist page of a featured artist.
+pub fn generate_for_artist(artist: &Artist, catalog: &Catalog) -> String {
+    let mut lines = vec![format!("# {}", single_line(&artist.name))];
+
+    if let Some(more) = &artist.more {
+        lines.push(String::new());
+        lines.push(more.stripped.clone());
+    }
+
+    let public_releases = artist.public_releases();
+
+    if !public_releases.is_empty() {
+        lines.push(String::new());
+
+        for release in &public_releases {
+            let release_ref = release.borrow();
+            lines.push(release_link_line("../", &release_ref));
+        }
+    }
+
+    for link in &artist.links {
+        if let Some(r_link) = link_line(link) {
+            lines.push(String::new());
+            lines.push(r_link);
+        }
+    }
+
+    lines.push(String::new());
+    lines.push(format!("=> ../{GEMTEXT_INDEX_FILENAME} {}", single_line(&catalog.title())));
+
+    lines.join("\n") + "\n"
+}
+
+/// Generate gemtext content for the homepage, listing all public releases
+/// (and in label mode also all featured artists).
+pub fn generate_for_catalog(catalog: &Catalog) -> String {
+    let mut lines = vec![format!("# {}", single_line(&catalog.title()))];
+
+    if let Some(more) = &catalog.more {
+        lines.push(String::new());
+        lines.push(more.stripped.clone());
+    }
+
+    let public_releases = catalog.public_releases();
+
+    if !public_releases.is_empty() {
+        lines.push(String::new());
+
+        for release in &public_releases {
+            let release_ref = release.borrow();
+            lines.push(release_link_line("", &release_ref));
+        }
+    }
+
+    if catalog.label_mode {
+        let featured_artists = catalog.featured_artists
+            .iter()
+            .filter(|artist| !artist.borrow().unlisted)
+            .collect::<Vec<_>>();
+
+        if !featured_artists.is_empty() {
+            lines.push(String::new());
+
+            for artist in featured_artists {
+                let artist_ref = artist.borrow();
+                let artist_slug = &artist_ref.permalink.slug;
+                lines.push(format!("=> {artist_slug}/{GEMTEXT_INDEX_FILENAME} {}", single_line(&artist_ref.name)));
+            }
+        }
+    }
+
+    for link in &catalog.links {
+        if let Some(r_link) = link_line(link) {
+            lines.push(String::new());
+            lines.push(r_link);
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Generate gemtext content for a release, including the track listing with
+/// direct links to the streaming files and a link to the downloads (if they
+/// are freely available).
+pub fn generate_for_release(build: &Build, catalog: &Catalog, release: &Release) -> String {
+    let translations = &build.locale.translations;
+    let release_slug = &release.permalink.slug;
+    let release_asset_prefix = build.asset_prefix("", "../", release_slug);
+
+    let mut lines = vec![format!("# {}", single_line(&release.title))];
+
+    let artists = release_artists(release);
+    if !artists.is_empty() {
+        lines.push(artists);
+    }
+
+    if let Some(date) = release.date {
+        lines.push(date.format("%Y-%m-%d").to_string());
+    }
+
+    lines.push(String::new());
+
+    for (track, track_number) in release.tracks.iter().zip(TRACK_NUMBERS) {
+        let duration_seconds = track.transcodes.borrow().source_meta.duration_seconds;
+        let track_number_formatted = release.track_numbering.format(track_number);
+
+        // Track numbering can be disabled, hence the trim
+        let label = format!(
+            "{track_number_formatted} {} ({})",
+            single_line(&track.title()),
+            format_time(duration_seconds)
+        ).trim_start().to_string();
+
+        // We link the first streaming format, which is the most compatible one
+        match track.streaming_quality.formats().first() {
+            Some(format) => {
+                let format_dir = format.asset_dirname();
+                let format_extension = format.extension();
+
+                let track_filename = format!(
+                    "{basename}{format_extension}",
+                    basename = track.asset_basename.as_ref().unwrap()
+                );
+
+                let track_hash = build.hash_with_salt(|hasher| {
+                    release_slug.hash(hasher);
+                    track_number.hash(hasher);
+                    format_dir.hash(hasher);
+                    track_filename.hash(hasher);
+                });
+
+                let track_filename_urlencoded = urlencoding::encode(&track_filename);
+                lines.push(format!("=> {release_asset_prefix}{track_number}/{format_dir}/{track_hash}/{track_filename_urlencoded} {label}"));
+            }
+            None => lines.push(label)
+        }
+    }
+
+    if let Some(more) = &release.more {
+        lines.push(String::new());
+        lines.push(more.stripped.clone());
+    }
+
+    if let DownloadAccess::Free = &release.download_access {
+        if !release.download_formats.is_empty() {
+            let t_downloads_permalink = build.locale.downloads_permalink();
+            let download_page_hash = build.hash_with_salt(|hasher| {
+                release_slug.hash(hasher);
+                t_downloads_permalink.hash(hasher);
+            });
+
+            lines.push(String::new());
+            lines.push(format!("=> {t_downloads_permalink}/{download_page_hash}/index.html {}", translations.download));
+        }
+    }
+
+    for link in &release.links {
+        if let Some(r_link) = link_line(link) {
+            lines.push(String::new());
+            lines.push(r_link);
+        }
+    }
+
+    lines.push(String::new());
+    lines.push(format!("=> ../{GEMTEXT_INDEX_FILENAME} {}", single_line(&catalog.title())));
+
+    lines.join("\n") + "\n"
+}
+
+/// Anchors (which point to sections of html pages) and hidden links (which
+/// only serve verification purposes) are omitted from the text-only export.
+fn link_line(link: &Link) -> Option<String> {
+    match link {
+        Link::Anchor { .. } => None,
+        Link::Full { hidden: true, .. } => None,
+        Link::Full { label, url, .. } => Some(format!("=> {url} {}", single_line(label)))
+    }
+}
+
+fn release_artists(release: &Release) -> String {
+    release.main_artists
+        .iter()
+        .map(|artist| single_line(&artist.borrow().name))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// A link line for a release, as it is used on the homepage and artist pages
+fn release_link_line(root_prefix: &str, release: &Release) -> String {
+    let release_slug = &release.permalink.slug;
+    let artists = release_artists(release);
+
+    let label = match release.date {
+        Some(date) => format!("{} – {artists} ({})", single_line(&release.title), date.format("%Y")),
+        None => format!("{} – {artists}", single_line(&release.title))
+    };
+
+    format!("=> {root_prefix}{release_slug}/{GEMTEXT_INDEX_FILENAME} {label}")
+}
+
+/// Gemtext is line-based, so (user-provided) text that is used for
+/// headings or link labels must not contain line breaks.
+fn single_line(text: &str) -> String {
+    text.replace(['\n', '\r'], " ")
+}
+
+/// Writes the text-only variant of the site to the build directory. Must be
+/// called after all html pages were written, as it places its files into the
+/// already existing directories of releases and artists.
+pub fn write(build: &mut Build, catalog: &Catalog) {
+    let r_catalog = generate_for_catalog(catalog);
+    fs::write(build.build_dir.join(GEMTEXT_INDEX_FILENAME), r_catalog).unwrap();
+    build.reserve_filename(GEMTEXT_INDEX_FILENAME);
+
+    for release in &catalog.releases {
+        let release_ref = release.borrow();
+        let release_dir = build.build_dir.join(&release_ref.permalink.slug);
+        let r_release = generate_for_release(build, catalog, &release_ref);
+        fs::write(release_dir.join(GEMTEXT_INDEX_FILENAME), r_release).unwrap();
+    }
+
+    for artist in &catalog.featured_artists {
+        let artist_ref = artist.borrow();
+        let artist_dir = build.build_dir.join(&artist_ref.permalink.slug);
+        let r_artist = generate_for_artist(&artist_ref, catalog);
+        fs::write(artist_dir.join(GEMTEXT_INDEX_FILENAME), r_artist).unwrap();
+    }
+}
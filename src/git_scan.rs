@@ -0,0 +1,278 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Through --git-scan, catalogs that are kept in a git repository are
+//! scanned faster: The listings of all directories read during a build are
+//! recorded in the cache directory, together with the commit that was
+//! checked out. On the next build, git reports which paths changed since
+//! that commit (both committed and uncommitted changes, as well as untracked
+//! and ignored files), and only the directories containing such changes
+//! (and their parent directories) are read from disk again - all others are
+//! listed from the record. If the catalog is not in a git repository, or
+//! git can not report the changes (e.g. because the recorded commit no
+//! longer exists), the entire catalog is read from disk as usual.
+
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde_derive::{Deserialize, Serialize};
+
+/// Stored in the cache directory, but not subject to cache maintenance.
+pub const GIT_SCAN_CACHE_FILENAME: &str = "git_scan.cache";
+
+#[cfg(not(target_os = "windows"))]
+const GIT_BINARY: &str = "git";
+
+#[cfg(target_os = "windows")]
+const GIT_BINARY: &str = "git.exe";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DirEntryRecord {
+    pub file_name: OsString,
+    pub kind: DirEntryKind
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum DirEntryKind {
+    Dir,
+    File,
+    Other,
+    Symlink
+}
+
+/// All paths stored here are relative to the catalog directory.
+pub struct GitScan {
+    catalog_dir: PathBuf,
+    commit: String,
+    /// All changed files and directories (compared to the recorded commit),
+    /// these are recorded as well so that changes which are reverted before
+    /// the next build are still picked up then.
+    dirty_paths: HashSet<PathBuf>,
+    /// Listings gathered in this build, recorded for the next one
+    listings: HashMap<PathBuf, Vec<DirEntryRecord>>,
+    /// Listings from the last build that can be reused because nothing
+    /// changed inside the directory
+    reusable_listings: HashMap<PathBuf, Vec<DirEntryRecord>>,
+    /// Number of directories that were listed from the record
+    pub reused: usize
+}
+
+#[derive(Deserialize, Serialize)]
+struct GitScanRecord {
+    commit: String,
+    dirty_paths: HashSet<PathBuf>,
+    listings: HashMap<PathBuf, Vec<DirEntryRecord>>
+}
+
+impl DirEntryRecord {
+    pub fn new(file_name: OsString, kind: DirEntryKind) -> DirEntryRecord {
+        DirEntryRecord {
+            file_name,
+            kind
+        }
+    }
+}
+
+impl GitScan {
+    /// Lists the entries of the given directory, either from the record of
+    /// the last build (if nothing changed inside it), or by reading it from
+    /// disk. Either way the listing is recorded for the next build.
+    pub fn list(&mut self, dir: &Path) -> Vec<DirEntryRecord> {
+        let Ok(dir_relative) = dir.strip_prefix(&self.catalog_dir) else {
+            return list_dir(dir);
+        };
+
+        let entries = match self.reusable_listings.remove(dir_relative) {
+            Some(entries) => {
+                self.reused += 1;
+                entries
+            }
+            None => list_dir(dir)
+        };
+
+        self.listings.insert(dir_relative.to_path_buf(), entries.clone());
+
+        entries
+    }
+
+    /// Returns None (after explaining why) if the catalog is not inside a
+    /// git repository or git is not available, in which case the catalog
+    /// needs to be read from disk entirely.
+    pub fn new(cache_dir: &Path, catalog_dir: &Path) -> Option<GitScan> {
+        let Some(repository_dir) = git(catalog_dir, &["rev-parse", "--show-toplevel"]) else {
+            info!("The catalog is not inside a git repository (or git is not installed), --git-scan has no effect");
+            return None;
+        };
+
+        let repository_dir = PathBuf::from(repository_dir.trim());
+
+        // git reports paths relative to the (canonical) repository directory,
+        // we convert them to paths relative to the catalog directory
+        let Ok(catalog_dir_canonicalized) = catalog_dir.canonicalize() else {
+            return None;
+        };
+
+        let Ok(repository_dir_canonicalized) = repository_dir.canonicalize() else {
+            return None;
+        };
+
+        let relative_to_catalog = |path: &str| -> Option<PathBuf> {
+            repository_dir_canonicalized
+                .join(path)
+                .strip_prefix(&catalog_dir_canonicalized)
+                .ok()
+                .map(|path| path.to_path_buf())
+        };
+
+        // Without any commit there is nothing to compare against later on
+        let Some(commit) = git(catalog_dir, &["rev-parse", "HEAD"]) else {
+            info!("The git repository of the catalog has no commits yet, --git-scan has no effect");
+            return None;
+        };
+
+        let commit = commit.trim().to_string();
+
+        // Paths that are uncommitted, untracked or ignored (e.g. audio files
+        // that are deliberately kept out of the repository) are always
+        // considered changed, as git has no record of their history.
+        let mut dirty_paths = HashSet::new();
+
+        match git(&repository_dir, &["status", "--ignored=matching", "--porcelain", "--untracked-files=all", "-z"]) {
+            Some(status) => {
+                let mut entries = status.split('\0');
+
+                while let Some(entry) = entries.next() {
+                    if entry.len() < 4 {
+                        continue;
+                    }
+
+                    dirty_paths.extend(relative_to_catalog(&entry[3..]));
+
+                    // Renames and copies are followed by their original path
+                    if entry.starts_with('R') || entry.starts_with('C') {
+                        if let Some(original_path) = entries.next() {
+                            dirty_paths.extend(relative_to_catalog(original_path));
+                        }
+                    }
+                }
+            }
+            None => {
+                info!("The status of the catalog's git repository could not be determined, --git-scan has no effect");
+                return None;
+            }
+        }
+
+        let record = fs::read(cache_dir.join(GIT_SCAN_CACHE_FILENAME))
+            .ok()
+            .and_then(|bytes| bincode::deserialize::<GitScanRecord>(&bytes).ok());
+
+        let Some(record) = record else {
+            return Some(GitScan::unrecorded(catalog_dir, commit, dirty_paths));
+        };
+
+        if record.commit != commit {
+            let Some(diff) = git(&repository_dir, &["diff", "--name-only", "--no-renames", "-z", &record.commit, &commit]) else {
+                info!("The changes since the commit recorded at the last build could not be determined, the catalog is read entirely");
+                return Some(GitScan::unrecorded(catalog_dir, commit, dirty_paths));
+            };
+
+            for path in diff.split('\0').filter(|path| !path.is_empty()) {
+                dirty_paths.extend(relative_to_catalog(path));
+            }
+        }
+
+        let mut rescan_paths = dirty_paths.clone();
+        rescan_paths.extend(record.dirty_paths);
+
+        // A directory's listing is reused only if nothing changed inside of
+        // it, at any depth - if for instance a directory was deleted, the
+        // listing of its parent directory would otherwise still contain it.
+        // Status output can contain entire (untracked/ignored) directories,
+        // hence we also reject listings of directories below those.
+        let reusable_listings = record.listings
+            .into_iter()
+            .filter(|(dir, _entries)| {
+                !rescan_paths
+                    .iter()
+                    .any(|path| path.starts_with(dir) || dir.starts_with(path))
+            })
+            .collect();
+
+        Some(GitScan {
+            catalog_dir: catalog_dir.to_path_buf(),
+            commit,
+            dirty_paths,
+            listings: HashMap::new(),
+            reusable_listings,
+            reused: 0
+        })
+    }
+
+    fn unrecorded(catalog_dir: &Path, commit: String, dirty_paths: HashSet<PathBuf>) -> GitScan {
+        GitScan {
+            catalog_dir: catalog_dir.to_path_buf(),
+            commit,
+            dirty_paths,
+            listings: HashMap::new(),
+            reusable_listings: HashMap::new(),
+            reused: 0
+        }
+    }
+
+    /// Records the listings of this build for the next one
+    pub fn write(&mut self, cache_dir: &Path) {
+        let record = GitScanRecord {
+            commit: self.commit.clone(),
+            dirty_paths: std::mem::take(&mut self.dirty_paths),
+            listings: std::mem::take(&mut self.listings)
+        };
+
+        let serialized = bincode::serialize(&record).unwrap();
+
+        if fs::create_dir_all(cache_dir).is_ok() {
+            let _ = fs::write(cache_dir.join(GIT_SCAN_CACHE_FILENAME), serialized);
+        }
+    }
+}
+
+/// Runs git with the given arguments in the given directory, returning its
+/// output only if it succeeded.
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new(GIT_BINARY)
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout).ok()
+    } else {
+        None
+    }
+}
+
+/// Reads the entries of the directory from disk (this is also used when
+/// --git-scan is not enabled)
+pub fn list_dir(dir: &Path) -> Vec<DirEntryRecord> {
+    let mut entries = Vec::new();
+
+    if let Ok(dir_entries) = dir.read_dir() {
+        for dir_entry in dir_entries.flatten() {
+            let kind = match dir_entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => DirEntryKind::Dir,
+                Ok(file_type) if file_type.is_file() => DirEntryKind::File,
+                Ok(file_type) if file_type.is_symlink() => DirEntryKind::Symlink,
+                Ok(_) => DirEntryKind::Other,
+                Err(_) => continue
+            };
+
+            entries.push(DirEntryRecord::new(dir_entry.file_name(), kind));
+        }
+    }
+
+    entries
+}
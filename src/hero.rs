@@ -0,0 +1,54 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::DescribedImage;
+
+/// A prominent section on the homepage, rendered above the release grid,
+/// which can present an image, a headline, a short text and a
+/// call-to-action button.
+#[derive(Debug)]
+pub struct Hero {
+    pub button: Option<HeroButton>,
+    pub headline: Option<String>,
+    pub image: HeroImage,
+    pub subtext: Option<String>
+}
+
+#[derive(Debug)]
+pub struct HeroButton {
+    pub label: String,
+    pub target: HeroTarget
+}
+
+#[derive(Debug)]
+pub enum HeroImage {
+    Custom(DescribedImage),
+    /// The (possibly procedural) cover of the release that is listed first
+    /// on the homepage.
+    LatestReleaseCover,
+    None
+}
+
+#[derive(Debug)]
+pub enum HeroTarget {
+    LatestRelease,
+    /// An arbitrary url (absolute or relative), used as-is
+    Url(String)
+}
+
+impl Hero {
+    /// The hero image assets are written to the build root alongside those of
+    /// the home image, this prefix prevents their filenames from colliding.
+    pub const IMAGE_FILENAME_PREFIX: &str = "hero_";
+
+    /// The keyword that can be given for image and button_target in the
+    /// manifest to refer to the latest release.
+    pub const LATEST_RELEASE_KEY: &str = "latest_release";
+
+    pub fn custom_image(&self) -> Option<&DescribedImage> {
+        match &self.image {
+            HeroImage::Custom(described_image) => Some(described_image),
+            _ => None
+        }
+    }
+}
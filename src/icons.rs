@@ -20,6 +20,13 @@ pub fn buy(label: &str) -> String {
     "#)
 }
 
+/// A rounded rectangle with two rings on top and a grid of days inside
+pub const CALENDAR: &str =  indoc!(r#"
+    <svg aria-hidden="true" width="1em" height="1em" version="1.1" viewBox="0 0 64 64" xmlns="http://www.w3.org/2000/svg">
+        <path d="m20 5c-1.385 0-2.5 1.115-2.5 2.5v3.5h-3.5c-3.866 0-7 3.134-7 7v34c0 3.866 3.134 7 7 7h36c3.866 0 7-3.134 7-7v-34c0-3.866-3.134-7-7-7h-3.5v-3.5c0-1.385-1.115-2.5-2.5-2.5s-2.5 1.115-2.5 2.5v3.5h-19v-3.5c0-1.385-1.115-2.5-2.5-2.5zm-8 20h40v27c0 1.1046-0.89543 2-2 2h-36c-1.1046 0-2-0.89543-2-2zm6 7v6h6v-6zm11 0v6h6v-6zm11 0v6h6v-6zm-22 11v6h6v-6zm11 0v6h6v-6z"/>
+    </svg>
+"#);
+
 /// Two squares with rounded corners slightly shifted from each other diagonally,
 /// with one being in front of the other (the common symbol depiction for "copy").
 pub const COPY: &str =  indoc!(r#"
@@ -158,6 +165,25 @@ pub fn previous_track(label: &str) -> String {
     "#)
 }
 
+/// Three horizontal lines of which the lower two are shifted right, with a
+/// small triangle pointing at the first line (a list of upcoming tracks)
+pub fn queue(label: &str) -> String {
+    formatdoc!(r#"
+        <svg width="1em" height="1em" version="1.1" viewBox="0 0 64 64" xmlns="http://www.w3.org/2000/svg">
+            <title>{label}</title>
+            <path d="m6 8v16l12-8zm18 4.5h34v7h-34zm0 16h34v7h-34zm0 16h34v7h-34z"/>
+        </svg>
+    "#)
+}
+
+/// Three circles connected by two lines, forming an angle bracket pointing to
+/// the left (the common symbol depiction for "share")
+pub const SHARE: &str =  indoc!(r#"
+    <svg aria-hidden="true" width="1em" height="1em" version="1.1" viewBox="0 0 64 64" xmlns="http://www.w3.org/2000/svg">
+        <path d="m46 7c-3.866 0-7 3.134-7 7 0 0.5332 0.06216 1.0514 0.17383 1.5508l-16.145 10.379c-1.2308-1.1934-2.9068-1.9297-4.7617-1.9297h-0.26758c-3.866 0-7 3.134-7 7s3.134 7 7 7h0.26758c1.8549 0 3.5309-0.73631 4.7617-1.9297l16.145 10.379c-0.11167 0.49937-0.17383 1.0176-0.17383 1.5508 0 3.866 3.134 7 7 7s7-3.134 7-7-3.134-7-7-7c-1.8549 0-3.5309 0.73631-4.7617 1.9297l-16.145-10.379c0.11167-0.49937 0.17383-1.0176 0.17383-1.5508s-0.06216-1.0514-0.17383-1.5508l16.145-10.379c1.2308 1.1934 2.9068 1.9297 4.7617 1.9297 3.866 0 7-3.134 7-7s-3.134-7-7-7z"/>
+    </svg>
+"#);
+
 /// A square whose bottom-left corner is overlaid with an RSS icon (= two 45 degree arcs radiating from a dot)
 pub const STREAM: &str =  indoc!(r#"
     <svg aria-hidden="true" width="1em" height="1em" version="1.1" viewBox="0 0 64 64" xmlns="http://www.w3.org/2000/svg">
@@ -208,3 +234,13 @@ pub const VOLUME: &str =  indoc!(r#"
         <path class="strike_out" d="m32 12.601c-4.9983 0-9.9971 1.9066-13.811 5.7201-7.6271 7.6272-7.6271 19.994 0 27.621 7.6272 7.6271 19.994 7.6271 27.621 0 7.6271-7.6272 7.6271-19.994 0-27.621-3.8136-3.8135-8.8124-5.7201-13.811-5.7201zm0 6.5897c2.0475 0.0115 4.0624 0.51347 5.876 1.4639l-17.227 17.227c-0.95039-1.8136-1.4524-3.8285-1.4639-5.876-4.19e-4 -7.0776 5.7372-12.815 12.815-12.815zm11.353 6.9445c0.94888 1.8121 1.4502 3.8249 1.462 5.8704 4.19e-4 7.0776-5.7372 12.815-12.815 12.815-2.0454-0.011821-4.0583-0.51312-5.8704-1.462zm-22.165 12.74 0.0056 0.0056-0.0019 0.0019z"/>
     </svg>
 "#);
+
+/// Four level bars of equal height below a ceiling line
+pub fn volume_normalization(label: &str) -> String {
+    formatdoc!(r#"
+        <svg width="1em" height="1em" version="1.1" viewBox="0 0 64 64" xmlns="http://www.w3.org/2000/svg">
+            <title>{label}</title>
+            <path d="m6 22h7v36h-7zm15 0h7v36h-7zm15 0h7v36h-7zm15 0h7v36h-7zm-45-16h52v6h-52z"/>
+        </svg>
+    "#)
+}
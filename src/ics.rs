@@ -0,0 +1,105 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+/// iCalendar format reference:
+/// - https://datatracker.ietf.org/doc/html/rfc5545
+/// - https://en.wikipedia.org/wiki/ICalendar
+
+use std::hash::Hash;
+
+use chrono::{Days, NaiveDate};
+
+use crate::{Build, Catalog, GENERATOR_INFO, Release};
+
+pub const ICS_FILENAME: &str = "release.ics";
+
+/// Lines in iCalendar files must not be longer than 75 octets (excluding
+/// the line break), longer lines are folded onto continuation lines that
+/// start with a single space.
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Generate an iCalendar file containing a single all-day event for the
+/// date on which the release comes out.
+pub fn generate_for_release(
+    build: &Build,
+    catalog: &Catalog,
+    date: NaiveDate,
+    release: &Release
+) -> String {
+    let release_slug = &release.permalink.slug;
+
+    let uid_hash = build.hash_with_salt(|hasher| {
+        release_slug.hash(hasher);
+    });
+
+    let artists = release.main_artists
+        .iter()
+        .map(|artist| artist.borrow().name.clone())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let summary = if artists.is_empty() {
+        release.title.clone()
+    } else {
+        format!("{artists} – {}", release.title)
+    };
+
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        format!("PRODID:-//{}//EN", escape_text(GENERATOR_INFO)),
+        String::from("CALSCALE:GREGORIAN"),
+        String::from("BEGIN:VEVENT"),
+        format!("UID:{uid_hash}-{release_slug}@faircamp"),
+        format!("DTSTAMP:{}", build.build_begin.format("%Y%m%dT%H%M%SZ")),
+        format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+        format!("DTEND;VALUE=DATE:{}", date.checked_add_days(Days::new(1)).unwrap_or(date).format("%Y%m%d")),
+        format!("SUMMARY:{}", escape_text(&summary)),
+        format!("DESCRIPTION:{}", escape_text(&catalog.title()))
+    ];
+
+    if let Some(base_url) = &build.base_url {
+        lines.push(format!("URL:{}", base_url.join_index(build, release_slug)));
+    }
+
+    lines.push(String::from("TRANSP:TRANSPARENT"));
+    lines.push(String::from("END:VEVENT"));
+    lines.push(String::from("END:VCALENDAR"));
+
+    // Content lines are delimited by CRLF, including the last one
+    lines
+        .iter()
+        .map(|line| format!("{}\r\n", fold_line(line)))
+        .collect()
+}
+
+/// Escape characters that have a special meaning in TEXT property values
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line into multiple lines of at most 75 octets each,
+/// taking care to never split inside a multi-byte character.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut octets = 0;
+
+    for character in line.chars() {
+        let character_octets = character.len_utf8();
+
+        if octets + character_octets > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // The leading space of the continuation line counts as well
+            octets = 1;
+        }
+
+        folded.push(character);
+        octets += character_octets;
+    }
+
+    folded
+}
@@ -0,0 +1,84 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Industry identifiers that distributors and collecting societies require:
+//! The ISRC (International Standard Recording Code) of a track and the
+//! UPC/EAN barcode of a release. Both are set through the manifests, written
+//! into the tags of downloaded audio files and listed in the json api.
+
+/// Accepts an ISRC with or without hyphens/spaces (e.g. "DE-A12-25-00001"
+/// or "DEA122500001") and returns it in its canonical, compact form:
+/// Two letters (country), three letters/digits (registrant), two digits
+/// (year) and five digits (designation).
+pub fn normalize_isrc(value: &str) -> Option<String> {
+    let compact = value
+        .chars()
+        .filter(|c| *c != '-' && *c != ' ')
+        .collect::<String>()
+        .to_uppercase();
+
+    let chars: Vec<char> = compact.chars().collect();
+
+    let valid = chars.len() == 12 &&
+        chars[0..2].iter().all(|c| c.is_ascii_uppercase()) &&
+        chars[2..5].iter().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) &&
+        chars[5..12].iter().all(|c| c.is_ascii_digit());
+
+    match valid {
+        true => Some(compact),
+        false => None
+    }
+}
+
+/// Accepts a UPC-A (12 digits) or EAN-13 (13 digits) barcode, with or
+/// without spaces/hyphens, and returns its digits only if the check digit
+/// (the last one) is correct.
+pub fn normalize_upc(value: &str) -> Option<String> {
+    let compact = value
+        .chars()
+        .filter(|c| *c != '-' && *c != ' ')
+        .collect::<String>();
+
+    if !(compact.len() == 12 || compact.len() == 13) ||
+        !compact.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let digits: Vec<u32> = compact.chars().map(|c| c.to_digit(10).unwrap()).collect();
+    let (check_digit, payload) = digits.split_last().unwrap();
+
+    // Counting from the right, every other digit (starting with the one
+    // next to the check digit) is weighted with 3, the others with 1.
+    let sum: u32 = payload
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, digit)| if index % 2 == 0 { digit * 3 } else { *digit })
+        .sum();
+
+    match (10 - sum % 10) % 10 == *check_digit {
+        true => Some(compact),
+        false => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_isrc, normalize_upc};
+
+    #[test]
+    fn isrc() {
+        assert_eq!(normalize_isrc("DE-A12-25-00001").as_deref(), Some("DEA122500001"));
+        assert_eq!(normalize_isrc("usrc17607839").as_deref(), Some("USRC17607839"));
+        assert_eq!(normalize_isrc("US-RC1-76-0783"), None);
+        assert_eq!(normalize_isrc("12RC17607839"), None);
+    }
+
+    #[test]
+    fn upc() {
+        assert_eq!(normalize_upc("036000291452").as_deref(), Some("036000291452"));
+        assert_eq!(normalize_upc("4 006381 333931").as_deref(), Some("4006381333931"));
+        assert_eq!(normalize_upc("036000291453"), None);
+        assert_eq!(normalize_upc("03600029145"), None);
+    }
+}
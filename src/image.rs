@@ -24,15 +24,20 @@ use crate::util::url_safe_base64;
 
 mod artist;
 mod feed;
+mod metadata;
 mod processor;
 mod release;
+mod svg;
 
 use artist::{ArtistAsset, ArtistAssets};
 use processor::{ImageInMemory, ResizeMode};
 use release::{CoverAsset, CoverAssets};
 
+pub use release::CoverAspectRatio;
+
 pub use feed::{FeedImageAsset};
 pub use processor::ImageProcessor;
+pub use svg::is_svg;
 
 const BACKGROUND_MAX_EDGE_SIZE: u32 = 1280;
 const FEED_MAX_EDGE_SIZE: u32 = 920;
@@ -61,7 +66,13 @@ pub struct DescribedImage {
 pub struct Image {
     pub artist_assets: Option<ArtistAssets>,
     pub background_asset: Option<Asset>,
+    /// Whether the assets were computed with the artist and copyright
+    /// metadata of the source image preserved (see [Build::preserve_image_copyright])
+    pub copyright_metadata: bool,
     pub cover_assets: Option<CoverAssets>,
+    /// Only computed for covers of releases that retain the original aspect
+    /// ratio of their cover
+    pub cover_assets_uncropped: Option<CoverAssets>,
     pub feed_asset: Option<FeedImageAsset>,
     /// Hash of the file content of the source image, with this we
     /// can uniquely identify and re-associate the computed cache
@@ -107,20 +118,22 @@ impl Image {
     /// Increase version on each change to the data layout of [Image].
     /// This automatically informs the cache not to try to deserialize
     /// manifests that hold old, incompatible data.
-    pub const CACHE_SERIALIZATION_KEY: &'static str = "image2";
+    pub const CACHE_SERIALIZATION_KEY: &'static str = "image5";
 
     pub fn artist_assets(
         &mut self,
         build: &Build,
         source_path: &Path
     ) -> &mut ArtistAssets {
+        self.discard_assets_on_metadata_change(build);
+
         if let Some(assets) = self.artist_assets.as_mut() {
             assets.unmark_stale();
         } else {
             info_resizing!("{:?} for usage as an artist image", &source_path);
 
             let absolute_source_path = build.catalog_dir.join(source_path);
-            let image_in_memory = build.image_processor.open_opaque(&absolute_source_path);
+            let image_in_memory = build.timings.measure("Image processing", || build.image_processor.open_opaque(build, &absolute_source_path));
             let source_width = image_in_memory.width() as f32;
 
             // Compute fixed sizes.
@@ -233,16 +246,18 @@ impl Image {
         build: &Build,
         source_path: &Path
     ) -> &mut Asset {
+        self.discard_assets_on_metadata_change(build);
+
         if let Some(asset) = self.background_asset.as_mut() {
             asset.unmark_stale();
         } else {
             info_resizing!("{:?} for usage as a background image", &source_path);
 
             let absolute_source_path = build.catalog_dir.join(source_path);
-            let image_in_memory = build.image_processor.open_opaque(&absolute_source_path);
+            let image_in_memory = build.timings.measure("Image processing", || build.image_processor.open_opaque(build, &absolute_source_path));
 
             let resize_mode = ResizeMode::ContainInSquare { max_edge_size: BACKGROUND_MAX_EDGE_SIZE };
-            let (filename, _dimensions) = build.image_processor.resize_opaque(build, &image_in_memory, resize_mode);
+            let (filename, _dimensions) = build.timings.measure("Image processing", || build.image_processor.resize_opaque(build, &image_in_memory, resize_mode));
 
             self.background_asset.replace(Asset::new(build, filename, AssetIntent::Deliverable));
         }
@@ -256,14 +271,18 @@ impl Image {
         image_in_memory: &ImageInMemory,
         resize_mode: ResizeMode
     ) -> ArtistAsset {
-        let (filename, dimensions) = build.image_processor.resize_opaque(
-            build,
-            image_in_memory,
-            resize_mode
-        );
+        let (filename, dimensions) = build.timings.measure("Image processing", || {
+            build.image_processor.resize_opaque(
+                build,
+                image_in_memory,
+                resize_mode
+            )
+        });
 
         let metadata = fs::metadata(build.cache_dir.join(&filename)).unwrap();
 
+        build.stats.add_generated(metadata.len());
+
         ArtistAsset {
             filename,
             filesize_bytes: metadata.len(),
@@ -278,18 +297,23 @@ impl Image {
         image_in_memory: &ImageInMemory,
         resize_mode: ResizeMode
     ) -> CoverAsset {
-        let (filename, dimensions) = build.image_processor.resize_opaque(
-            build,
-            image_in_memory,
-            resize_mode
-        );
+        let (filename, dimensions) = build.timings.measure("Image processing", || {
+            build.image_processor.resize_opaque(
+                build,
+                image_in_memory,
+                resize_mode
+            )
+        });
 
         let metadata = fs::metadata(build.cache_dir.join(&filename)).unwrap();
 
+        build.stats.add_generated(metadata.len());
+
         CoverAsset {
             edge_size: dimensions.0,
             filename,
-            filesize_bytes: metadata.len()
+            filesize_bytes: metadata.len(),
+            height: dimensions.1
         }
     }
 
@@ -309,40 +333,44 @@ impl Image {
         build: &Build,
         source_path: &Path
     ) -> &mut CoverAssets {
+        self.discard_assets_on_metadata_change(build);
+
         if let Some(assets) = self.cover_assets.as_mut() {
             assets.unmark_stale();
         } else {
             info_resizing!("{:?} for usage as a cover image", source_path);
 
             let absolute_source_path = build.catalog_dir.join(source_path);
-            let image_in_memory = build.image_processor.open_opaque(&absolute_source_path);
-            let source_width = image_in_memory.width() as f32;
+            let image_in_memory = build.timings.measure("Image processing", || build.image_processor.open_opaque(build, &absolute_source_path));
+            // The square crop is only as large as the shorter edge, larger
+            // sizes would merely be (identical) copies of the source size.
+            let source_edge_size = std::cmp::min(image_in_memory.height(), image_in_memory.width()) as f32;
 
             let resize_mode_max_160 = ResizeMode::CoverSquare { edge_size: 160 };
             let max_160 = Image::compute_cover_asset(build, &image_in_memory, resize_mode_max_160);
 
-            let max_320 = if source_width > 160.0 * MIN_OVERSHOOT {
+            let max_320 = if source_edge_size > 160.0 * MIN_OVERSHOOT {
                 let resize_mode_max_320 = ResizeMode::CoverSquare { edge_size: 320 };
                 Some(Image::compute_cover_asset(build, &image_in_memory, resize_mode_max_320))
             } else {
                 None
             };
 
-            let max_480 = if source_width > 320.0 * MIN_OVERSHOOT {
+            let max_480 = if source_edge_size > 320.0 * MIN_OVERSHOOT {
                 let resize_mode_max_480 = ResizeMode::CoverSquare { edge_size: 480 };
                 Some(Image::compute_cover_asset(build, &image_in_memory, resize_mode_max_480))
             } else {
                 None
             };
 
-            let max_800 = if source_width > 480.0 * MIN_OVERSHOOT {
+            let max_800 = if source_edge_size > 480.0 * MIN_OVERSHOOT {
                 let resize_mode_max_800 = ResizeMode::CoverSquare { edge_size: 800 };
                 Some(Image::compute_cover_asset(build, &image_in_memory, resize_mode_max_800))
             } else {
                 None
             };
 
-            let max_1280 = if source_width > 800.0 * MIN_OVERSHOOT {
+            let max_1280 = if source_edge_size > 800.0 * MIN_OVERSHOOT {
                 let resize_mode_max_1280 = ResizeMode::CoverSquare { edge_size: 1280 };
                 Some(Image::compute_cover_asset(build, &image_in_memory, resize_mode_max_1280))
             } else {
@@ -355,7 +383,8 @@ impl Image {
                 max_320,
                 max_480,
                 max_800,
-                max_1280
+                max_1280,
+                source_edge_size: source_edge_size as u32
             };
 
             self.cover_assets.replace(cover_assets);
@@ -364,6 +393,76 @@ impl Image {
         self.cover_assets.as_mut().unwrap()
     }
 
+    /// Like cover_assets, but the image is only resized and never cropped,
+    /// thereby retaining its original aspect ratio. The sizes refer to the
+    /// longer edge of the image.
+    pub fn cover_assets_uncropped(
+        &mut self,
+        build: &Build,
+        source_path: &Path
+    ) -> &mut CoverAssets {
+        self.discard_assets_on_metadata_change(build);
+
+        if let Some(assets) = self.cover_assets_uncropped.as_mut() {
+            assets.unmark_stale();
+        } else {
+            info_resizing!("{:?} for usage as an uncropped cover image", source_path);
+
+            let absolute_source_path = build.catalog_dir.join(source_path);
+            let image_in_memory = build.timings.measure("Image processing", || build.image_processor.open_opaque(build, &absolute_source_path));
+            let source_edge_size = std::cmp::max(image_in_memory.height(), image_in_memory.width()) as f32;
+
+            let resize_mode_max_160 = ResizeMode::ContainInSquare { max_edge_size: 160 };
+            let max_160 = Image::compute_cover_asset(build, &image_in_memory, resize_mode_max_160);
+
+            let max_320 = if source_edge_size > 160.0 * MIN_OVERSHOOT {
+                let resize_mode_max_320 = ResizeMode::ContainInSquare { max_edge_size: 320 };
+                Some(Image::compute_cover_asset(build, &image_in_memory, resize_mode_max_320))
+            } else {
+                None
+            };
+
+            let max_480 = if source_edge_size > 320.0 * MIN_OVERSHOOT {
+                let resize_mode_max_480 = ResizeMode::ContainInSquare { max_edge_size: 480 };
+                Some(Image::compute_cover_asset(build, &image_in_memory, resize_mode_max_480))
+            } else {
+                None
+            };
+
+            let max_800 = if source_edge_size > 480.0 * MIN_OVERSHOOT {
+                let resize_mode_max_800 = ResizeMode::ContainInSquare { max_edge_size: 800 };
+                Some(Image::compute_cover_asset(build, &image_in_memory, resize_mode_max_800))
+            } else {
+                None
+            };
+
+            let max_1280 = if source_edge_size > 800.0 * MIN_OVERSHOOT {
+                let resize_mode_max_1280 = ResizeMode::ContainInSquare { max_edge_size: 1280 };
+                Some(Image::compute_cover_asset(build, &image_in_memory, resize_mode_max_1280))
+            } else {
+                None
+            };
+
+            let cover_assets = CoverAssets {
+                marked_stale: None,
+                max_160,
+                max_320,
+                max_480,
+                max_800,
+                max_1280,
+                source_edge_size: source_edge_size as u32
+            };
+
+            self.cover_assets_uncropped.replace(cover_assets);
+        }
+
+        self.cover_assets_uncropped.as_mut().unwrap()
+    }
+
+    pub fn cover_assets_uncropped_unchecked(&self) -> &CoverAssets {
+        self.cover_assets_uncropped.as_ref().unwrap()
+    }
+
     pub fn cover_assets_unchecked(&self) -> &CoverAssets {
         self.cover_assets.as_ref().unwrap()
     }
@@ -396,25 +495,44 @@ impl Image {
         }
     }
 
+    /// If the catalog's image_metadata setting changed since the assets
+    /// were computed, they are discarded so they get recomputed (the
+    /// cache removes the files of the discarded assets as orphans).
+    fn discard_assets_on_metadata_change(&mut self, build: &Build) {
+        if self.copyright_metadata != build.preserve_image_copyright {
+            self.artist_assets = None;
+            self.background_asset = None;
+            self.cover_assets = None;
+            self.cover_assets_uncropped = None;
+            self.feed_asset = None;
+
+            self.copyright_metadata = build.preserve_image_copyright;
+        }
+    }
+
     /// Gets or computes a feed asset for this image
     pub fn feed_asset(
         &mut self,
         build: &Build,
         source_path: &Path
     ) -> &mut FeedImageAsset {
+        self.discard_assets_on_metadata_change(build);
+
         if let Some(asset) = self.feed_asset.as_mut() {
             asset.unmark_stale();
         } else {
             info_resizing!("{:?} for usage as a feed image", &source_path);
 
             let absolute_source_path = build.catalog_dir.join(source_path);
-            let image_in_memory = build.image_processor.open_opaque(&absolute_source_path);
+            let image_in_memory = build.timings.measure("Image processing", || build.image_processor.open_opaque(build, &absolute_source_path));
 
-            let (filename, dimensions) = build.image_processor.resize_opaque(
-                build,
-                &image_in_memory,
-                ResizeMode::ContainInSquare { max_edge_size: FEED_MAX_EDGE_SIZE }
-            );
+            let (filename, dimensions) = build.timings.measure("Image processing", || {
+                build.image_processor.resize_opaque(
+                    build,
+                    &image_in_memory,
+                    ResizeMode::ContainInSquare { max_edge_size: FEED_MAX_EDGE_SIZE }
+                )
+            });
 
             let edge_size = dimensions.0; // square ratio
 
@@ -442,6 +560,7 @@ impl Image {
         if let Some(asset) = self.artist_assets.as_mut() { asset.mark_stale(timestamp); }
         if let Some(asset) = self.background_asset.as_mut() { asset.mark_stale(timestamp); }
         if let Some(asset) = self.cover_assets.as_mut() { asset.mark_stale(timestamp); }
+        if let Some(asset) = self.cover_assets_uncropped.as_mut() { asset.mark_stale(timestamp); }
         if let Some(asset) = self.feed_asset.as_mut() { asset.mark_stale(timestamp); }
 
         for view in self.views.iter_mut() {
@@ -453,7 +572,9 @@ impl Image {
         Image {
             artist_assets: None,
             background_asset: None,
+            copyright_metadata: false,
             cover_assets: None,
+            cover_assets_uncropped: None,
             feed_asset: None,
             hash,
             views: vec![View::new(file_meta)]
@@ -40,6 +40,8 @@ impl FeedImageAsset {
     ) -> FeedImageAsset {
         let metadata = fs::metadata(build.cache_dir.join(&filename)).unwrap();
 
+        build.stats.add_generated(metadata.len());
+
         FeedImageAsset {
             edge_size,
             filename,
@@ -0,0 +1,260 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! All image assets are written without any of the metadata (EXIF etc.)
+//! found in the source images, as this can contain private information
+//! (most prominently GPS coordinates of where a photo was taken). If the
+//! catalog is configured to do so (image_metadata: copyright), the artist
+//! and copyright fields of the source image are extracted here and written
+//! back into the image assets as a minimal EXIF segment - these are the
+//! only fields that are ever preserved.
+
+use std::fs;
+use std::path::Path;
+
+/// EXIF (TIFF) tag for the artist/creator of an image
+const TAG_ARTIST: u16 = 0x013B;
+/// EXIF (TIFF) tag for the copyright notice of an image
+const TAG_COPYRIGHT: u16 = 0x8298;
+/// EXIF (TIFF) field type for ASCII text
+const TYPE_ASCII: u16 = 2;
+
+/// The artist and copyright fields of an image, stored as tag and raw
+/// (ASCII) value, without the terminating NUL byte.
+pub struct CopyrightMetadata {
+    fields: Vec<(u16, Vec<u8>)>
+}
+
+impl CopyrightMetadata {
+    /// Reads the artist and copyright fields from the EXIF metadata of the
+    /// image file at the given path (supported for JPEG, PNG and WebP). Any
+    /// failure to read or interpret the metadata is treated as if there
+    /// were no such fields.
+    pub fn read(absolute_path: &Path) -> Option<CopyrightMetadata> {
+        let bytes = fs::read(absolute_path).ok()?;
+        let tiff = exif_tiff(&bytes)?;
+        let fields = tiff_ascii_fields(tiff, &[TAG_ARTIST, TAG_COPYRIGHT])?;
+
+        if fields.is_empty() {
+            None
+        } else {
+            Some(CopyrightMetadata { fields })
+        }
+    }
+
+    /// Inserts an EXIF segment containing only the artist and copyright
+    /// fields into the (metadata-free) JPEG file at the given path.
+    pub fn write(&self, jpeg_path: &Path) {
+        let jpeg = fs::read(jpeg_path).unwrap();
+
+        if !jpeg.starts_with(&[0xFF, 0xD8]) {
+            return;
+        }
+
+        let tiff = self.tiff();
+
+        // The segment length includes the two length bytes themselves
+        let segment_length = 2 + b"Exif\0\0".len() + tiff.len();
+        if segment_length > u16::MAX as usize {
+            return;
+        }
+
+        let mut segment = vec![0xFF, 0xE1];
+        segment.extend_from_slice(&(segment_length as u16).to_be_bytes());
+        segment.extend_from_slice(b"Exif\0\0");
+        segment.extend_from_slice(&tiff);
+
+        // The EXIF segment goes right after the start of image marker, or
+        // after the JFIF segment if there is one, as that needs to come
+        // first by its specification.
+        let insert_at = if jpeg.len() > 6 && jpeg[2..4] == [0xFF, 0xE0] {
+            4 + u16::from_be_bytes([jpeg[4], jpeg[5]]) as usize
+        } else {
+            2
+        };
+
+        if insert_at > jpeg.len() {
+            return;
+        }
+
+        let mut result = Vec::with_capacity(jpeg.len() + segment.len());
+        result.extend_from_slice(&jpeg[..insert_at]);
+        result.extend_from_slice(&segment);
+        result.extend_from_slice(&jpeg[insert_at..]);
+
+        fs::write(jpeg_path, result).unwrap();
+    }
+
+    /// Encodes the fields as a (big endian) TIFF structure with a single IFD
+    fn tiff(&self) -> Vec<u8> {
+        let mut fields = self.fields.iter().collect::<Vec<_>>();
+        fields.sort_by_key(|(tag, _)| *tag);
+
+        let entry_count = fields.len() as u16;
+        let ifd_size = 2 + fields.len() * 12 + 4;
+        let mut data_offset = 8 + ifd_size;
+
+        let mut entries = Vec::new();
+        let mut data = Vec::new();
+
+        for (tag, value) in fields {
+            let mut value_nul_terminated = value.clone();
+            value_nul_terminated.push(0);
+
+            entries.extend_from_slice(&tag.to_be_bytes());
+            entries.extend_from_slice(&TYPE_ASCII.to_be_bytes());
+            entries.extend_from_slice(&(value_nul_terminated.len() as u32).to_be_bytes());
+
+            if value_nul_terminated.len() <= 4 {
+                value_nul_terminated.resize(4, 0);
+                entries.extend_from_slice(&value_nul_terminated);
+            } else {
+                entries.extend_from_slice(&(data_offset as u32).to_be_bytes());
+
+                // Values are aligned to word boundaries
+                if value_nul_terminated.len() % 2 != 0 {
+                    value_nul_terminated.push(0);
+                }
+
+                data_offset += value_nul_terminated.len();
+                data.extend_from_slice(&value_nul_terminated);
+            }
+        }
+
+        let mut tiff = Vec::with_capacity(data_offset);
+        tiff.extend_from_slice(b"MM\0\x2A");
+        tiff.extend_from_slice(&8u32.to_be_bytes());
+        tiff.extend_from_slice(&entry_count.to_be_bytes());
+        tiff.extend_from_slice(&entries);
+        tiff.extend_from_slice(&0u32.to_be_bytes()); // No next IFD
+        tiff.extend_from_slice(&data);
+
+        tiff
+    }
+}
+
+/// Locates the EXIF metadata in a JPEG, PNG or WebP file, returning the
+/// TIFF structure it is made of.
+fn exif_tiff(bytes: &[u8]) -> Option<&[u8]> {
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        let mut position = 2;
+
+        while position + 4 <= bytes.len() {
+            if bytes[position] != 0xFF {
+                return None;
+            }
+
+            let marker = bytes[position + 1];
+
+            match marker {
+                // Fill byte
+                0xFF => position += 1,
+                // Markers without a length
+                0x01 | 0xD0..=0xD7 => position += 2,
+                // Start of scan/end of image, no metadata after this point
+                0xD9 | 0xDA => return None,
+                _ => {
+                    let length = u16::from_be_bytes([bytes[position + 2], bytes[position + 3]]) as usize;
+                    let segment = bytes.get((position + 4)..(position + 2 + length))?;
+
+                    if marker == 0xE1 {
+                        if let Some(tiff) = segment.strip_prefix(b"Exif\0\0") {
+                            return Some(tiff);
+                        }
+                    }
+
+                    position += 2 + length;
+                }
+            }
+        }
+    } else if bytes.starts_with(b"\x89PNG\r\n\x1A\n") {
+        let mut position = 8;
+
+        while position + 8 <= bytes.len() {
+            let length = u32::from_be_bytes(bytes[position..(position + 4)].try_into().unwrap()) as usize;
+            let chunk_type = &bytes[(position + 4)..(position + 8)];
+            let data = bytes.get((position + 8)..(position + 8 + length))?;
+
+            match chunk_type {
+                b"eXIf" => return Some(data),
+                b"IEND" => return None,
+                _ => position += 12 + length // length, type, data, crc
+            }
+        }
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        let mut position = 12;
+
+        while position + 8 <= bytes.len() {
+            let fourcc = &bytes[position..(position + 4)];
+            let length = u32::from_le_bytes(bytes[(position + 4)..(position + 8)].try_into().unwrap()) as usize;
+            let data = bytes.get((position + 8)..(position + 8 + length))?;
+
+            if fourcc == b"EXIF" {
+                // Some encoders include the JPEG-style prefix here as well
+                return Some(data.strip_prefix(b"Exif\0\0").unwrap_or(data));
+            }
+
+            // Chunks are padded to an even length
+            position += 8 + length + length % 2;
+        }
+    }
+
+    None
+}
+
+/// Reads the ASCII fields with the given tags from the first IFD of a TIFF
+/// structure. Returns None if the structure is malformed.
+fn tiff_ascii_fields(tiff: &[u8], tags: &[u16]) -> Option<Vec<(u16, Vec<u8>)>> {
+    let big_endian = match tiff.get(0..2)? {
+        b"II" => false,
+        b"MM" => true,
+        _ => return None
+    };
+
+    let read_u16 = |offset: usize| -> Option<u16> {
+        let bytes = tiff.get(offset..(offset + 2))?.try_into().ok()?;
+        Some(if big_endian { u16::from_be_bytes(bytes) } else { u16::from_le_bytes(bytes) })
+    };
+
+    let read_u32 = |offset: usize| -> Option<u32> {
+        let bytes = tiff.get(offset..(offset + 4))?.try_into().ok()?;
+        Some(if big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
+    };
+
+    if read_u16(2)? != 42 {
+        return None;
+    }
+
+    let ifd_offset = read_u32(4)? as usize;
+    let entry_count = read_u16(ifd_offset)? as usize;
+
+    let mut fields = Vec::new();
+
+    for index in 0..entry_count {
+        let entry_offset = ifd_offset + 2 + index * 12;
+        let tag = read_u16(entry_offset)?;
+
+        if !tags.contains(&tag) || read_u16(entry_offset + 2)? != TYPE_ASCII {
+            continue;
+        }
+
+        let count = read_u32(entry_offset + 4)? as usize;
+        let value_offset = if count <= 4 {
+            entry_offset + 8
+        } else {
+            read_u32(entry_offset + 8)? as usize
+        };
+
+        let mut value = tiff.get(value_offset..(value_offset + count))?.to_vec();
+
+        while value.last().is_some_and(|byte| *byte == 0 || *byte == b' ') {
+            value.pop();
+        }
+
+        if !value.is_empty() {
+            fields.push((tag, value));
+        }
+    }
+
+    Some(fields)
+}
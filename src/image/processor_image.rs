@@ -4,21 +4,28 @@
 use std::fs::File;
 use std::path::Path;
 
-use ::image::{DynamicImage, ImageFormat};
+use ::image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
 use ::image::imageops::FilterType;
 
 use crate::Build;
+use crate::image::metadata::CopyrightMetadata;
+use crate::image::svg;
 use crate::util;
 
 use super::ResizeMode;
 
 pub struct ImageInMemory {
+    copyright_metadata: Option<CopyrightMetadata>,
     dynamic_image: DynamicImage
 }
 
 pub struct ImageProcessor;
 
 impl ImageInMemory {
+    pub fn height(&self) -> u32 {
+        self.dynamic_image.height()
+    }
+
     pub fn width(&self) -> u32 {
         self.dynamic_image.width()
     }
@@ -31,9 +38,30 @@ impl ImageProcessor {
 
     /// Open an image and drop the alpha channel right away. This is used
     /// when we process images with jpeg as target format, i.e. for all
-    /// user-supplied images.
-    pub fn open_opaque(&self, absolute_path: &Path) -> ImageInMemory {
-        let unknown_representation = image::open(absolute_path).unwrap();
+    /// user-supplied images. The orientation stored in the image metadata
+    /// is applied to the pixels, as the metadata itself is not written to
+    /// the resized images.
+    pub fn open_opaque(&self, build: &Build, absolute_path: &Path) -> ImageInMemory {
+        let unknown_representation = if svg::is_svg(absolute_path) {
+            let png = svg::rasterize(absolute_path).unwrap();
+            image::load_from_memory_with_format(&png, ImageFormat::Png).unwrap()
+        } else {
+            let mut decoder = ImageReader::open(absolute_path)
+                .unwrap()
+                .with_guessed_format()
+                .unwrap()
+                .into_decoder()
+                .unwrap();
+            let orientation = decoder.orientation().ok();
+
+            let mut dynamic_image = DynamicImage::from_decoder(decoder).unwrap();
+
+            if let Some(orientation) = orientation {
+                dynamic_image.apply_orientation(orientation);
+            }
+
+            dynamic_image
+        };
 
         // Since image 0.25.0, alpha channels must be manually dropped before
         // saving to a format that does not support alpha channels. As we
@@ -42,7 +70,12 @@ impl ImageProcessor {
         // channels right after we open any image for further processing.
         let dynamic_image = DynamicImage::ImageRgb8(unknown_representation.into_rgb8());
 
-        ImageInMemory { dynamic_image }
+        let copyright_metadata = match build.preserve_image_copyright {
+            true => CopyrightMetadata::read(absolute_path),
+            false => None
+        };
+
+        ImageInMemory { copyright_metadata, dynamic_image }
     }
 
     // TODO: This was initially implemented to resize procedural covers (png format)
@@ -77,9 +110,12 @@ impl ImageProcessor {
             let output_filename = format!("{}.jpg", util::uid());
             let output_path = build.cache_dir.join(&output_filename);
 
-            let mut output_file = File::create(output_path).unwrap();
+            // The file is closed right away, as we might re-open it below
+            dynamic_image.write_to(&mut File::create(&output_path).unwrap(), ImageFormat::Jpeg).unwrap();
 
-            dynamic_image.write_to(&mut output_file, ImageFormat::Jpeg).unwrap();
+            if let Some(copyright_metadata) = &image_in_memory.copyright_metadata {
+                copyright_metadata.write(&output_path);
+            }
 
             (output_filename, result_dimensions)
         };
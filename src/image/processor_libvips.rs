@@ -7,6 +7,8 @@ use libvips::{VipsApp, VipsImage};
 use libvips::ops::{self, Interesting, SmartcropOptions};
 
 use crate::Build;
+use crate::image::metadata::CopyrightMetadata;
+use crate::image::svg;
 use crate::util;
 
 use super::ResizeMode;
@@ -14,6 +16,7 @@ use super::ResizeMode;
 const CROP_OPTIONS: SmartcropOptions = SmartcropOptions { interesting: Interesting::Centre };
 
 pub struct ImageInMemory {
+    pub copyright_metadata: Option<CopyrightMetadata>,
     pub vips_image: VipsImage
 }
 
@@ -22,6 +25,10 @@ pub struct ImageProcessor {
 }
 
 impl ImageInMemory {
+    pub fn height(&self) -> u32 {
+        self.vips_image.get_height() as u32
+    }
+
     pub fn width(&self) -> u32 {
         self.vips_image.get_width() as u32
     }
@@ -38,11 +45,25 @@ impl ImageProcessor {
 
     /// In the libvips implementation open_opaque and open_transparent are
     /// identical, only for the image crate implementation the differentation
-    /// is necessary.
-    pub fn open_opaque(&self, absolute_path: &Path) -> ImageInMemory {
-        let vips_image = VipsImage::new_from_file(&absolute_path.to_string_lossy()).unwrap();
+    /// is necessary. The orientation stored in the image metadata is applied
+    /// to the pixels, as the metadata itself is stripped when saving.
+    pub fn open_opaque(&self, build: &Build, absolute_path: &Path) -> ImageInMemory {
+        let vips_image = if svg::is_svg(absolute_path) {
+            // libvips can only load SVGs when it was built with librsvg, so
+            // we rasterize them ourselves.
+            let png = svg::rasterize(absolute_path).unwrap();
+            VipsImage::new_from_buffer(&png, "").unwrap()
+        } else {
+            let unrotated = VipsImage::new_from_file(&absolute_path.to_string_lossy()).unwrap();
+            ops::autorot(&unrotated).unwrap()
+        };
 
-        ImageInMemory { vips_image }
+        let copyright_metadata = match build.preserve_image_copyright {
+            true => CopyrightMetadata::read(absolute_path),
+            false => None
+        };
+
+        ImageInMemory { copyright_metadata, vips_image }
     }
 
     // TODO: This was initially implemented to resize procedural covers (png format)
@@ -82,13 +103,18 @@ impl ImageProcessor {
             };
 
             let target_filename = format!("{}.jpg", util::uid());
+            let target_path = build.cache_dir.join(&target_filename);
 
             match ops::jpegsave_with_opts(
                 vips_image,
-                &build.cache_dir.join(&target_filename).to_string_lossy(),
+                &target_path.to_string_lossy(),
                 &options
             ) {
-                Ok(_) => (),
+                Ok(_) => {
+                    if let Some(copyright_metadata) = &image_in_memory.copyright_metadata {
+                        copyright_metadata.write(&target_path);
+                    }
+                }
                 Err(_) => println!("error: {}", self.vips_app.error_buffer().unwrap())
             }
 
@@ -9,15 +9,28 @@ use super::ImgAttributes;
 /// A single, resized version of the cover image.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct CoverAsset {
-    /// Represents both height and width (covers have a square aspect ratio)
+    /// Represents the width, and for square covers (the default) also the
+    /// height of the image
     pub edge_size: u32,
     pub filename: String,
-    pub filesize_bytes: u64
+    pub filesize_bytes: u64,
+    /// Only differs from edge_size for uncropped covers that are not square
+    pub height: u32
+}
+
+/// Covers are cropped to a square aspect ratio by default, alternatively
+/// their original aspect ratio can be retained (for display on the release
+/// page and inclusion in release archives).
+#[derive(Clone, Copy, Debug, Hash, PartialEq)]
+pub enum CoverAspectRatio {
+    Original,
+    Square
 }
 
 /// Represents multiple, differently sized versions of a cover image, for
 /// display on different screen sizes and for inclusion in the release
-/// archive. (Numbers refer to the square edge size, both height and width)
+/// archive. (Numbers refer to the square edge size, both height and width -
+/// for uncropped covers they refer to the longer edge)
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct CoverAssets {
     pub marked_stale: Option<DateTime<Utc>>,
@@ -25,17 +38,27 @@ pub struct CoverAssets {
     pub max_320: Option<CoverAsset>,
     pub max_480: Option<CoverAsset>,
     pub max_800: Option<CoverAsset>,
-    pub max_1280: Option<CoverAsset>
+    pub max_1280: Option<CoverAsset>,
+    /// The edge size of the source image from which the sizes are derived
+    /// (the shorter edge for square covers, the longer edge for uncropped
+    /// covers). Sizes are never upscaled beyond it.
+    pub source_edge_size: u32
 }
 
 impl CoverAsset {
     /// The filename of this asset as we are writing it to the release/track
     /// directory at build time, e.g. something like "cover_480.jpg"
-    /// or "cover_240.jpg".
+    /// or "cover_240.jpg" (or "cover_480x640.jpg" for uncropped covers
+    /// that are not square).
     pub fn target_filename(&self) -> String {
         let edge_size = self.edge_size;
+        let height = self.height;
 
-        format!("cover_{edge_size}.jpg")
+        if height == edge_size {
+            format!("cover_{edge_size}.jpg")
+        } else {
+            format!("cover_{edge_size}x{height}.jpg")
+        }
     }
 }
 
@@ -103,6 +126,16 @@ impl CoverAssets {
         }
     }
 
+    /// Returns the largest asset whose longer edge does not exceed the given
+    /// size (or the smallest asset, if none of them fits).
+    pub fn largest_up_to(&self, max_edge_size: u32) -> &CoverAsset {
+        self.all()
+            .into_iter()
+            .rev()
+            .find(|asset| asset.edge_size.max(asset.height) <= max_edge_size)
+            .unwrap_or(&self.max_160)
+    }
+
     pub fn mark_stale(&mut self, timestamp: &DateTime<Utc>) {
         if self.marked_stale.is_none() {
             self.marked_stale = Some(*timestamp);
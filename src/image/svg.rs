@@ -0,0 +1,48 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Vector images (SVG) are rasterized before they enter the regular image
+//! processing, so that all other code can treat them like any other image.
+
+use std::fs;
+use std::path::Path;
+
+use resvg::tiny_skia::{Color, Pixmap, Transform};
+use resvg::usvg::{Options, Tree};
+
+/// SVGs are rasterized such that their longer edge has this size, which is
+/// large enough for all image assets we compute (the largest being 1280
+/// pixels, plus the overshoot margin that is required to compute it).
+const RASTER_EDGE_SIZE: f32 = 1600.0;
+
+pub fn is_svg(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"))
+}
+
+/// Renders the SVG at the given path onto a white background and returns
+/// the result encoded as PNG. Transparency is flattened right away, as
+/// all our image assets are opaque jpegs anyway.
+pub fn rasterize(absolute_path: &Path) -> Result<Vec<u8>, String> {
+    let data = fs::read(absolute_path).map_err(|err| err.to_string())?;
+
+    let mut options = Options::default();
+    options.resources_dir = absolute_path.parent().map(|parent| parent.to_path_buf());
+    options.fontdb_mut().load_system_fonts();
+
+    let tree = Tree::from_data(&data, &options).map_err(|err| err.to_string())?;
+
+    let size = tree.size();
+    let scale = RASTER_EDGE_SIZE / size.width().max(size.height());
+    let width = (size.width() * scale).round().max(1.0) as u32;
+    let height = (size.height() * scale).round().max(1.0) as u32;
+
+    let mut pixmap = Pixmap::new(width, height)
+        .ok_or_else(|| format!("Cannot rasterize the SVG at {width}x{height} pixels"))?;
+
+    pixmap.fill(Color::WHITE);
+    resvg::render(&tree, Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+
+    pixmap.encode_png().map_err(|err| err.to_string())
+}
@@ -0,0 +1,32 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+/// Alternative views of all releases in the catalog, which are generated
+/// (in addition to the cover grid on the homepage) when they are enabled
+/// through the `index_views` option in the catalog manifest.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IndexView {
+    /// A compact text list with the runtime of each release
+    List,
+    /// Releases grouped by year
+    Timeline
+}
+
+impl IndexView {
+    /// The slug we would like to use for the page of the view, this is
+    /// also the key that is used in the manifest.
+    pub fn desired_permalink(&self) -> &'static str {
+        match self {
+            IndexView::List => "list",
+            IndexView::Timeline => "timeline"
+        }
+    }
+
+    pub fn from_manifest_key(key: &str) -> Option<IndexView> {
+        match key {
+            "list" => Some(IndexView::List),
+            "timeline" => Some(IndexView::Timeline),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,275 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Machine-readable description of the catalog (catalog.json), its releases
+//! (release.json) and its featured artists (artist.json), so that external
+//! apps, mobile clients and aggregators can consume a faircamp site without
+//! scraping its html. The documents link to each other through their "json"
+//! fields. The structure is versioned through the "schema" field, which is
+//! bumped whenever a backwards-incompatible change is made.
+
+use std::hash::Hash;
+
+use crate::{
+    Artist,
+    ArtistRc,
+    Build,
+    Catalog,
+    DownloadAccess,
+    Release,
+    ReleaseRc,
+    SiteUrl,
+    TRACK_NUMBERS
+};
+use crate::m3u::{release_cover_url, track_format_url};
+use crate::util::{json_escape, json_optional_string, json_string_array};
+
+pub const JSON_API_ARTIST_FILENAME: &str = "artist.json";
+pub const JSON_API_CATALOG_FILENAME: &str = "catalog.json";
+pub const JSON_API_RELEASE_FILENAME: &str = "release.json";
+
+const JSON_API_SCHEMA: &str = "faircamp-json-api/1";
+
+/// Short reference to an artist, as used in the listings of the documents
+fn artist_reference(
+    artist: &ArtistRc,
+    base_url: &SiteUrl,
+    build: &Build,
+    catalog: &Catalog
+) -> String {
+    let artist_url = artist_url(artist, base_url, build, catalog);
+    let artist = artist.borrow();
+
+    let json_url = match artist.featured {
+        true => Some(base_url.join_file(format!("{}/{JSON_API_ARTIST_FILENAME}", artist.permalink.slug))),
+        false => None
+    };
+
+    format!(
+        "{{ \"json\": {}, \"name\": \"{}\", \"url\": {} }}",
+        json_optional_string(json_url.as_deref()),
+        json_escape(&artist.name),
+        json_optional_string(artist_url.as_deref())
+    )
+}
+
+fn artist_references(
+    artists: &[ArtistRc],
+    base_url: &SiteUrl,
+    build: &Build,
+    catalog: &Catalog
+) -> String {
+    let references = artists
+        .iter()
+        .map(|artist| artist_reference(artist, base_url, build, catalog))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!("[{references}]")
+}
+
+/// The page of an artist, which is their own page when they are featured,
+/// the homepage in a single artist catalog, or else an external page (if
+/// one was configured for them).
+fn artist_url(
+    artist: &ArtistRc,
+    base_url: &SiteUrl,
+    build: &Build,
+    catalog: &Catalog
+) -> Option<String> {
+    if !catalog.label_mode {
+        if let Some(catalog_artist) = &catalog.artist {
+            if ArtistRc::ptr_eq(artist, catalog_artist) {
+                return Some(base_url.index(build));
+            }
+        }
+    }
+
+    let artist_ref = artist.borrow();
+
+    if artist_ref.featured {
+        return Some(base_url.join_index(build, &artist_ref.permalink.slug));
+    }
+
+    artist_ref.external_page.clone()
+}
+
+/// How (and in which formats) a release can be downloaded, or null if
+/// downloads are not available. The url points to the same page that the
+/// download button on the release page leads to.
+fn download(
+    base_url: &SiteUrl,
+    build: &Build,
+    release: &Release
+) -> String {
+    let release_slug = &release.permalink.slug;
+    let translations = &build.locale.translations;
+
+    let page_url = |permalink: &str| -> String {
+        let page_hash = build.hash_with_salt(|hasher| {
+            release_slug.hash(hasher);
+            permalink.hash(hasher);
+        });
+
+        base_url.join_index(build, format!("{release_slug}/{permalink}/{page_hash}"))
+    };
+
+    let (access, url) = match &release.download_access {
+        DownloadAccess::Code { .. } if release.download_assets_available() => {
+            ("code", page_url(build.locale.unlock_permalink()))
+        }
+        DownloadAccess::External { link } => ("external", link.clone()),
+        DownloadAccess::Free if release.download_assets_available() => {
+            ("free", page_url(build.locale.downloads_permalink()))
+        }
+        DownloadAccess::Paycurtain { payment_info: Some(_), .. } if release.download_assets_available() => {
+            ("paycurtain", page_url(build.locale.purchase_permalink()))
+        }
+        _ => return String::from("null")
+    };
+
+    let formats = release.download_formats
+        .iter()
+        .map(|format| format.user_label().to_string())
+        .collect::<Vec<String>>();
+
+    format!(
+        "{{\n    \"access\": \"{access}\",\n    \"formats\": {},\n    \"url\": \"{}\"\n  }}",
+        json_string_array(&formats),
+        json_escape(&url)
+    )
+}
+
+/// Generate the complete content of the json document for a featured artist
+pub fn generate_for_artist(
+    artist: &Artist,
+    base_url: &SiteUrl,
+    build: &Build,
+    catalog: &Catalog
+) -> String {
+    let r_releases = release_references(&artist.public_releases(), base_url, build, catalog);
+
+    format!(
+        "{{\n  \"aliases\": {},\n  \"name\": \"{}\",\n  \"releases\": [\n{r_releases}\n  ],\n  \"schema\": \"{JSON_API_SCHEMA}\",\n  \"synopsis\": {},\n  \"url\": \"{}\"\n}}\n",
+        json_string_array(&artist.aliases),
+        json_escape(&artist.name),
+        json_optional_string(artist.synopsis.as_deref()),
+        json_escape(&base_url.join_index(build, &artist.permalink.slug))
+    )
+}
+
+/// Generate the complete content of the json document for the catalog,
+/// which lists all (public) releases and all artists.
+pub fn generate_for_catalog(
+    base_url: &SiteUrl,
+    build: &Build,
+    catalog: &Catalog
+) -> String {
+    let r_artists = catalog.artists
+        .iter()
+        .filter(|artist| !artist.borrow().unlisted)
+        .map(|artist| format!("    {}", artist_reference(artist, base_url, build, catalog)))
+        .collect::<Vec<String>>()
+        .join(",\n");
+
+    let r_releases = release_references(&catalog.public_releases(), base_url, build, catalog);
+
+    format!(
+        "{{\n  \"artists\": [\n{r_artists}\n  ],\n  \"releases\": [\n{r_releases}\n  ],\n  \"schema\": \"{JSON_API_SCHEMA}\",\n  \"synopsis\": {},\n  \"title\": \"{}\",\n  \"url\": \"{}\"\n}}\n",
+        json_optional_string(catalog.synopsis.as_deref()),
+        json_escape(&catalog.title()),
+        json_escape(&base_url.index(build))
+    )
+}
+
+/// Generate the complete content of the json document for a release,
+/// describing all its tracks along with their streaming urls.
+pub fn generate_for_release(
+    base_url: &SiteUrl,
+    build: &Build,
+    catalog: &Catalog,
+    release: &Release
+) -> String {
+    let release_slug = &release.permalink.slug;
+
+    let mut release_duration = 0.0;
+
+    let r_tracks = release.tracks
+        .iter()
+        .zip(TRACK_NUMBERS)
+        .map(|(track, track_number)| {
+            let duration_seconds = track.transcodes.borrow().source_meta.duration_seconds;
+            release_duration += duration_seconds;
+
+            let sources = track.streaming_quality
+                .formats()
+                .iter()
+                .map(|format| {
+                    format!(
+                        "        {{ \"type\": \"{}\", \"url\": \"{}\" }}",
+                        json_escape(format.source_type()),
+                        json_escape(&track_format_url(build, *format, release, track, track_number))
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(",\n");
+
+            format!(
+                "    {{\n      \"artists\": {},\n      \"duration\": {duration_seconds:.3},\n      \"isrc\": {},\n      \"number\": {track_number},\n      \"sources\": [\n{sources}\n      ],\n      \"title\": \"{}\",\n      \"url\": \"{}\"\n    }}",
+                artist_references(&track.artists, base_url, build, catalog),
+                json_optional_string(track.isrc.as_deref()),
+                json_escape(&track.title()),
+                json_escape(&base_url.join_index(build, format!("{release_slug}/{track_number}")))
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",\n");
+
+    let support_artists = match catalog.show_support_artists {
+        true => artist_references(&release.support_artists, base_url, build, catalog),
+        false => String::from("[]")
+    };
+
+    let date = release.date.map(|date| date.to_string());
+
+    format!(
+        "{{\n  \"artists\": {},\n  \"cover\": \"{}\",\n  \"date\": {},\n  \"download\": {},\n  \"duration\": {release_duration:.3},\n  \"schema\": \"{JSON_API_SCHEMA}\",\n  \"support_artists\": {support_artists},\n  \"synopsis\": {},\n  \"title\": \"{}\",\n  \"tracks\": [\n{r_tracks}\n  ],\n  \"upc\": {},\n  \"url\": \"{}\"\n}}\n",
+        artist_references(&release.main_artists, base_url, build, catalog),
+        json_escape(&release_cover_url(build, release)),
+        json_optional_string(date.as_deref()),
+        download(base_url, build, release),
+        json_optional_string(release.synopsis.as_deref()),
+        json_escape(&release.title),
+        json_optional_string(release.upc.as_deref()),
+        json_escape(&base_url.join_index(build, release_slug))
+    )
+}
+
+/// Short references to releases, as listed in the catalog and artist documents
+fn release_references(
+    releases: &[ReleaseRc],
+    base_url: &SiteUrl,
+    build: &Build,
+    catalog: &Catalog
+) -> String {
+    releases
+        .iter()
+        .map(|release| {
+            let release_ref = release.borrow();
+            let release_slug = &release_ref.permalink.slug;
+            let date = release_ref.date.map(|date| date.to_string());
+
+            format!(
+                "    {{\n      \"artists\": {},\n      \"cover\": \"{}\",\n      \"date\": {},\n      \"json\": \"{}\",\n      \"title\": \"{}\",\n      \"tracks\": {},\n      \"url\": \"{}\"\n    }}",
+                artist_references(&release_ref.main_artists, base_url, build, catalog),
+                json_escape(&release_cover_url(build, &release_ref)),
+                json_optional_string(date.as_deref()),
+                json_escape(&base_url.join_file(format!("{release_slug}/{JSON_API_RELEASE_FILENAME}"))),
+                json_escape(&release_ref.title),
+                release_ref.tracks.len(),
+                json_escape(&base_url.join_index(build, release_slug))
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",\n")
+}
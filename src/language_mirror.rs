@@ -0,0 +1,127 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Language mirrors are translated versions of the same catalog, built with
+//! a different `language` and published at another url. Pages link to their
+//! counterparts on the mirrors, both through alternate link tags in the head
+//! (with hreflang, for search engines and other clients) and through a
+//! language switcher in the footer (for visitors).
+
+use indoc::formatdoc;
+
+use crate::{Build, Catalog, Locale, SiteUrl};
+use crate::util::html_escape_inside_attribute;
+
+/// Configured through the catalog manifest's `language_mirrors` option.
+#[derive(Clone, Debug)]
+pub struct LanguageMirror {
+    /// Language code such as "de", "fr" etc.
+    pub language: String,
+    pub url: SiteUrl
+}
+
+/// A page that exists on all mirrors and can therefore be linked to
+/// directly. The page's path is the same on all mirrors, unless it contains
+/// a translated term, in which case it is derived from the mirror's language.
+#[derive(Clone, Debug)]
+pub enum MirroredPage {
+    Home,
+    /// The path of the page, relative to the root of the site
+    Page(String),
+    Subscribe
+}
+
+impl LanguageMirror {
+    pub fn new(language: String, url: SiteUrl) -> LanguageMirror {
+        LanguageMirror {
+            language,
+            url
+        }
+    }
+}
+
+impl MirroredPage {
+    fn url(&self, build: &Build, catalog: &Catalog, language: &str, site_url: &SiteUrl) -> String {
+        match self {
+            MirroredPage::Home => site_url.index(build),
+            MirroredPage::Page(path) => site_url.join_index(build, path),
+            MirroredPage::Subscribe => {
+                let subscribe_permalink = if language == build.locale.language {
+                    catalog.subscribe_permalink.clone().unwrap_or_default()
+                } else {
+                    Locale::from_code(language).subscribe_permalink().to_string()
+                };
+
+                site_url.join_index(build, subscribe_permalink)
+            }
+        }
+    }
+}
+
+/// The alternate link tags for the head of a page, which link to the
+/// page itself and all its counterparts on the mirrors.
+pub fn alternate_link_tags(build: &Build, catalog: &Catalog, mirrored_page: &MirroredPage) -> String {
+    let mut tags = Vec::new();
+
+    if let Some(base_url) = &build.base_url {
+        let language = &build.locale.language;
+        let url = mirrored_page.url(build, catalog, language, base_url);
+        tags.push(format!(r#"<link href="{url}" hreflang="{language}" rel="alternate">"#));
+    }
+
+    for mirror in &catalog.language_mirrors {
+        let language = &mirror.language;
+        let url = mirrored_page.url(build, catalog, language, &mirror.url);
+        tags.push(format!(r#"<link href="{url}" hreflang="{language}" rel="alternate">"#));
+    }
+
+    tags.join("\n")
+}
+
+/// Links to the same page on all mirrors (or to their homepage, for pages
+/// that are not mirrored), labelled with the native names of the languages.
+pub fn language_switcher(
+    build: &Build,
+    catalog: &Catalog,
+    mirrored_page: Option<&MirroredPage>
+) -> String {
+    let language = &build.locale.language;
+    let native_name = native_language_name(language);
+    let current = format!(r#"<span aria-current="true" lang="{language}">{native_name}</span>"#);
+
+    let links = catalog.language_mirrors
+        .iter()
+        .map(|mirror| {
+            let language = &mirror.language;
+            let native_name = native_language_name(language);
+            let url = match mirrored_page {
+                Some(mirrored_page) => mirrored_page.url(build, catalog, language, &mirror.url),
+                None => mirror.url.index(build)
+            };
+
+            format!(r#"<a href="{url}" hreflang="{language}" lang="{language}">{native_name}</a>"#)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let t_languages = html_escape_inside_attribute(&build.locale.translations.languages);
+
+    formatdoc!(r#"
+        <nav aria-label="{t_languages}" class="language_switcher">
+            {current}
+            {links}
+        </nav>
+    "#)
+}
+
+/// Returns the name of the language in the language itself (e.g. "Deutsch"
+/// for "de"), or the language code if faircamp does not know the language.
+pub fn native_language_name(code: &str) -> String {
+    let normalized_code = code.to_lowercase().replace('_', "-");
+
+    translations::all_languages()
+        .iter()
+        .find(|language| language.code.replace('_', "-") == normalized_code)
+        .map(|language| language.native_name.to_string())
+        .unwrap_or_else(|| code.to_string())
+}
@@ -0,0 +1,212 @@
+// SPDX-FileCopyrightText: 2021-2025 Simon Repp
+// SPDX-FileCopyrightText: 2025 Sandro Santilli
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! faircamp_core contains the entire faircamp build pipeline (scanning the
+//! catalog, writing assets, rendering the site), which can be driven
+//! programmatically through the [Builder], e.g. by graphical frontends or
+//! other tools. The faircamp binary itself is only a thin command-line
+//! wrapper around it (see [cli]).
+
+#[macro_use]
+mod message;
+
+mod ai_scraping;
+mod annotation;
+mod archives;
+mod args;
+mod artist;
+mod asset;
+mod asset_manifest;
+mod audio_format;
+mod audio_meta;
+mod bagit;
+mod build;
+mod build_report;
+mod builder;
+mod cache;
+mod catalog;
+mod chapter;
+pub mod cli;
+mod content_warning;
+mod control_server;
+mod cover_generator;
+mod dashboard;
+mod debug;
+mod decode;
+mod deploy;
+mod download_format;
+mod downloads;
+mod fair_dir;
+mod favicon;
+mod feeds;
+mod ffmpeg;
+mod font_subset;
+mod format_comparison;
+mod fulfillment;
+mod funding;
+mod gemtext;
+mod git_scan;
+mod hero;
+mod heuristic_audio_meta;
+mod icons;
+mod ics;
+mod identifiers;
+mod image;
+mod index_view;
+mod json_api;
+mod language_mirror;
+mod link;
+mod link_check;
+mod locale;
+mod localization;
+mod loudness;
+mod lyrics;
+mod m3u;
+mod manifest;
+mod markdown;
+mod minify;
+mod opengraph;
+mod permalink;
+mod physical_edition;
+mod player_config;
+mod playlist;
+mod publish_date;
+mod release;
+mod release_filter;
+mod release_section;
+mod remote_source;
+mod render;
+mod rendered_pages;
+mod rsync;
+mod server;
+mod sftp;
+mod share_button;
+mod site_metadata;
+mod site_url;
+mod snapshot_test;
+mod source_file_signature;
+mod streaming_quality;
+mod scripts;
+mod styles;
+mod tags;
+mod test_catalog;
+mod text_check;
+mod theme;
+mod timings;
+mod track;
+mod track_numbering;
+mod transcode_processes;
+mod transcodes;
+mod transcript;
+mod util;
+mod visual_loop;
+mod watch;
+mod web_monetization;
+mod webhook;
+mod well_known;
+mod xspf;
+mod year_archive;
+
+use ai_scraping::AiScraping;
+use annotation::Annotation;
+use archives::{Archive, Archives, ArchivesRc};
+pub use args::{Args, ArgsCommand};
+use artist::{Artist, ArtistRc};
+use asset::{Asset, AssetIntent};
+use audio_format::{AudioFormat, AudioFormatFamily};
+use audio_meta::AudioMeta;
+use build::{AssetHashes, Build, GENERATOR_INFO, PostBuildAction, WarningCategory, WarningLevel};
+use build_report::BUILD_REPORT_CACHE_FILENAME;
+pub use builder::{
+    BuildFailure,
+    BuildReport,
+    BuildStage,
+    Builder,
+    CatalogSummary,
+    ReleaseSummary
+};
+use cache::{Cache, CacheOptimization, View};
+use catalog::Catalog;
+use chapter::Chapter;
+use content_warning::ContentWarning;
+use dashboard::DASHBOARD_CACHE_FILENAME;
+use cover_generator::{CoverGenerator, ProceduralCover, ProceduralCoverAsset, ProceduralCoverRc};
+use download_format::DownloadFormat;
+use downloads::{ArchiveCover, ArchiveLayout, DownloadAccess, DownloadAccessOption, ExtraDownloads, Price};
+use fair_dir::{EXTRA_SIZE_WARNING_DEFAULT, FairDir, SUPPORTED_AUDIO_EXTENSIONS};
+use favicon::Favicon;
+use feeds::Feeds;
+use format_comparison::{FORMAT_COMPARISON_DIRNAME, FormatComparison};
+use funding::FundingChannel;
+use git_scan::{GIT_SCAN_CACHE_FILENAME, GitScan};
+use hero::{Hero, HeroButton, HeroImage, HeroTarget};
+use heuristic_audio_meta::HeuristicAudioMeta;
+use ics::ICS_FILENAME;
+use crate::image::{CoverAspectRatio, DescribedImage, FeedImageAsset, Image, ImageProcessor, ImageRc, ImageRcView, ImgAttributes};
+use index_view::IndexView;
+use json_api::{JSON_API_ARTIST_FILENAME, JSON_API_CATALOG_FILENAME, JSON_API_RELEASE_FILENAME};
+use language_mirror::{LanguageMirror, MirroredPage};
+use link::Link;
+use link_check::LINK_CHECK_CACHE_FILENAME;
+use locale::Locale;
+use localization::{Localizations, LocalizedContent};
+use loudness::Loudness;
+use lyrics::Lyrics;
+use m3u::M3U_PLAYLIST_FILENAME;
+use manifest::{LocalOptions, Overrides};
+use markdown::HtmlAndStripped;
+use opengraph::{OpenGraphAudio, OpenGraphImage, OpenGraphMeta};
+use permalink::{
+    Permalink,
+    PermalinkConflicts,
+    PermalinkTerms,
+    PermalinkUsage,
+    SECRET_LINK_KEY_CACHE_FILENAME
+};
+use physical_edition::{PhysicalEdition, Stock};
+use player_config::PLAYER_CONFIG_FILENAME;
+use playlist::{Playlist, PlaylistTrack};
+use publish_date::PublishDate;
+use release::{COVER_SVG_FILENAME, Extra, Release, ReleaseRc, TRACK_NUMBERS};
+use release_filter::{ReleaseFilter, ReleaseFilterKind};
+use release_section::ReleaseSection;
+use remote_source::{REMOTE_SOURCES_DIRNAME, RemoteDownloads, RemoteSource};
+use rendered_pages::{RENDERING_DIRNAME, RenderedPages, RenderedPagesRc};
+use share_button::ShareButton;
+use site_metadata::{SiteAsset, SiteMetadata, USER_CSS_FILENAME};
+use site_url::SiteUrl;
+use source_file_signature::{FileMeta, SourceHash};
+use streaming_quality::{StreamingLimits, StreamingQuality};
+use styles::PageType;
+use tags::{ImageEmbed, TagAgenda, TagChapters, TagLyrics, TagMapping};
+use text_check::ManifestText;
+use theme::{Theme, ThemeBase, ThemeFont, ThemeVarsHsl, ThemeVarsOklch};
+use timings::Timings;
+use track::Track;
+use track_numbering::TrackNumbering;
+pub use transcode_processes::TranscodeProcesses;
+use transcodes::{
+    Clip,
+    PREVIEW_DURATION_SECONDS,
+    PREVIEW_FORMAT,
+    Transcode,
+    Transcodes,
+    TranscodesRc,
+    TranscodesRcView
+};
+use transcript::Transcript;
+use visual_loop::{
+    VISUAL_LOOP_DIRNAME,
+    VISUAL_LOOP_POSTER_FILENAME,
+    VISUAL_LOOP_VIDEO_FILENAME,
+    VisualLoop,
+    VisualLoopAssets,
+    VisualLoopAssetsRc,
+    VisualLoopSource
+};
+use web_monetization::PaymentPointer;
+use webhook::{NotifyWebhook, WEBHOOK_CACHE_FILENAME, WebhookEvent};
+use well_known::{Webfinger, WellKnown};
+use xspf::XSPF_PLAYLIST_FILENAME;
+use year_archive::{YearArchivePage, YearArchives};
@@ -0,0 +1,217 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Through --check-links all external urls that are found in the manifests
+//! (links, payment info and external download access) are requested, and
+//! those that appear to be dead are reported as warnings, so they can be
+//! fixed before they are deployed. The requests are performed through curl
+//! (which needs to be installed) and urls that were found alive are not
+//! requested again for some days, for which the results are kept in the
+//! cache directory.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::process::Command;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{Build, Catalog, DownloadAccess, Link, WarningCategory};
+
+/// Stored in the cache directory, but not subject to cache maintenance.
+pub const LINK_CHECK_CACHE_FILENAME: &str = "link_check.cache";
+
+/// Urls that were found alive are only requested again after this time.
+const ALIVE_EXPIRY_DAYS: i64 = 7;
+
+const CURL_BINARY: &str = "curl";
+const TIMEOUT_SECONDS: u32 = 15;
+
+enum LinkStatus {
+    Alive,
+    Dead(String)
+}
+
+/// Checks all external urls in the catalog, reporting dead ones as warnings.
+pub fn check_links(build: &mut Build, catalog: &Catalog) {
+    let cache_path = build.cache_dir.join(LINK_CHECK_CACHE_FILENAME);
+
+    let mut alive_since: HashMap<String, DateTime<Utc>> = match fs::read(&cache_path) {
+        Ok(bytes) => bincode::deserialize(&bytes).unwrap_or_default(),
+        Err(_) => HashMap::new()
+    };
+
+    let expiry = Duration::days(ALIVE_EXPIRY_DAYS);
+    alive_since.retain(|_url, date_time| build.build_begin.signed_duration_since(*date_time) < expiry);
+
+    let urls = collect_urls(catalog);
+    let mut num_dead = 0;
+
+    info!("Checking {} external links", urls.len());
+
+    for (url, usages) in &urls {
+        if alive_since.contains_key(url) {
+            continue;
+        }
+
+        match check_url(url) {
+            LinkStatus::Alive => {
+                alive_since.insert(url.clone(), build.build_begin);
+            }
+            LinkStatus::Dead(reason) => {
+                let warning = format!(
+                    "The link {url} appears to be dead ({reason}) - it is used in {}",
+                    usages.join(", ")
+                );
+                build.warning(WarningCategory::DeadLink, &warning);
+                num_dead += 1;
+            }
+        }
+    }
+
+    // Only urls that are still in use are kept in the cache
+    alive_since.retain(|url, _date_time| urls.contains_key(url));
+
+    let serialized = bincode::serialize(&alive_since).unwrap();
+    fs::write(&cache_path, serialized).unwrap();
+
+    if num_dead == 0 {
+        info!("All {} external links appear to be alive", urls.len());
+    }
+}
+
+fn check_url(url: &str) -> LinkStatus {
+    let mut result = request(url, true);
+
+    // Quite a few servers don't support HEAD requests (or block them),
+    // for those we fall back to a (minimal) GET request.
+    if let Ok(403 | 405 | 501) = result {
+        result = request(url, false);
+    }
+
+    match result {
+        // 206 is returned for the partial GET request
+        Ok(status_code) if status_code < 400 => LinkStatus::Alive,
+        Ok(status_code) => LinkStatus::Dead(format!("HTTP {status_code}")),
+        Err(message) => LinkStatus::Dead(message)
+    }
+}
+
+/// Maps each external url in the catalog to a list of descriptions of
+/// where it is used (for reporting).
+fn collect_urls(catalog: &Catalog) -> BTreeMap<String, Vec<String>> {
+    let mut urls: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    let mut add = |url: &str, usage: String| {
+        if url.starts_with("http://") || url.starts_with("https://") {
+            let usages = urls.entry(url.to_string()).or_default();
+            if !usages.contains(&usage) {
+                usages.push(usage);
+            }
+        }
+    };
+
+    for link in &catalog.links {
+        if let Link::Full { url, .. } = link {
+            add(url, String::from("the catalog's links"));
+        }
+    }
+
+    for artist in &catalog.artists {
+        let artist_ref = artist.borrow();
+        for link in &artist_ref.links {
+            if let Link::Full { url, .. } = link {
+                add(url, format!("the links of artist '{}'", artist_ref.name));
+            }
+        }
+    }
+
+    for release in &catalog.releases {
+        let release_ref = release.borrow();
+        let release_title = &release_ref.title;
+
+        for link in &release_ref.links {
+            if let Link::Full { url, .. } = link {
+                add(url, format!("the links of release '{release_title}'"));
+            }
+        }
+
+        match &release_ref.download_access {
+            DownloadAccess::External { link } => {
+                add(link, format!("the external download access of release '{release_title}'"));
+            }
+            DownloadAccess::Paycurtain { payment_info: Some(payment_info), .. } => {
+                for url in hrefs(payment_info) {
+                    add(&url, format!("the payment info of release '{release_title}'"));
+                }
+            }
+            _ => ()
+        }
+
+        for track in &release_ref.tracks {
+            let track_title = track.title();
+
+            for link in &track.links {
+                if let Link::Full { url, .. } = link {
+                    add(url, format!("the links of track '{track_title}' (release '{release_title}')"));
+                }
+            }
+
+            match &track.download_access {
+                DownloadAccess::External { link } => {
+                    add(link, format!("the external download access of track '{track_title}' (release '{release_title}')"));
+                }
+                DownloadAccess::Paycurtain { payment_info: Some(payment_info), .. } => {
+                    for url in hrefs(payment_info) {
+                        add(&url, format!("the payment info of track '{track_title}' (release '{release_title}')"));
+                    }
+                }
+                _ => ()
+            }
+        }
+    }
+
+    urls
+}
+
+/// Extracts the targets of all links in a piece of html (as rendered
+/// from markdown by faircamp).
+fn hrefs(html: &str) -> Vec<String> {
+    html
+        .split(r#"href=""#)
+        .skip(1)
+        .filter_map(|after_href| after_href.split('"').next())
+        .map(|href| href.replace("&amp;", "&"))
+        .collect()
+}
+
+/// Performs a single request through curl (following redirects) and returns
+/// the final http status code, or an error message if no response was
+/// received at all.
+fn request(url: &str, head: bool) -> Result<u16, String> {
+    let mut command = Command::new(CURL_BINARY);
+
+    command.arg("--location");
+    command.arg("--max-time").arg(TIMEOUT_SECONDS.to_string());
+    command.arg("--silent");
+    command.arg("--write-out").arg("\n%{http_code}");
+
+    if head {
+        command.arg("--head");
+    } else {
+        // Only the first byte is requested so we don't download entire pages
+        command.arg("--range").arg("0-0");
+    }
+
+    command.arg(url);
+
+    match command.output() {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            match stdout.lines().last().and_then(|line| line.trim().parse::<u16>().ok()) {
+                Some(0) | None => Err(String::from("no response")),
+                Some(status_code) => Ok(status_code)
+            }
+        }
+        Err(err) => Err(format!("The {CURL_BINARY} child process could not be executed ({err})"))
+    }
+}
@@ -31,6 +31,8 @@ use translations::{
 };
 use translations::Translations;
 
+use crate::PermalinkTerms;
+
 pub struct Locale {
     /// Language code such as "en", "de" etc.
     /// This is notably used in the lang attribute on the html tag on all
@@ -38,6 +40,11 @@ pub struct Locale {
     /// see https://datatracker.ietf.org/doc/html/rfc5646 and/or the more general
     /// https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/lang).
     pub language: String,
+    /// Overrides for the permalink terms of the translations, set from the
+    /// catalog's `permalink_terms` option (see [PermalinkTerms::apply]).
+    /// Always read the terms through the accessors on [Locale], which take
+    /// these into account.
+    pub permalink_terms: PermalinkTerms,
     pub text_direction: TextDirection,
     pub translations: Translations,
 }
@@ -82,6 +89,14 @@ impl Locale {
         }
     }
 
+    pub fn downloads_permalink(&self) -> &str {
+        self.permalink_terms.downloads.as_deref().unwrap_or(*self.translations.downloads_permalink)
+    }
+
+    pub fn image_descriptions_permalink(&self) -> &str {
+        self.permalink_terms.image_descriptions.as_deref().unwrap_or(*self.translations.image_descriptions_permalink)
+    }
+
     pub fn keys() -> Locale {
         Locale::new("en", Translations::KEYS, TextDirection::Ltr)
     }
@@ -93,10 +108,33 @@ impl Locale {
     ) -> Locale {
         Locale {
             language: language.to_owned(),
+            permalink_terms: PermalinkTerms::default(),
             text_direction,
             translations
         }
     }
+
+    pub fn purchase_permalink(&self) -> &str {
+        self.permalink_terms.purchase.as_deref().unwrap_or(*self.translations.purchase_permalink)
+    }
+
+    pub fn subscribe_permalink(&self) -> &str {
+        self.permalink_terms.subscribe.as_deref().unwrap_or(*self.translations.subscribe_permalink)
+    }
+
+    /// Like [Translations::unlock_manual_instructions], but with the
+    /// permalink terms resolved through the overrides.
+    pub fn unlock_manual_instructions(&self, page_hash: &str, index_suffix: &str) -> String {
+        self.translations.unlock_manual_instructions
+            .replace("{downloads_permalink}", self.downloads_permalink())
+            .replace("{index_suffix}", index_suffix)
+            .replace("{page_hash}", page_hash)
+            .replace("{unlock_permalink}", self.unlock_permalink())
+    }
+
+    pub fn unlock_permalink(&self) -> &str {
+        self.permalink_terms.unlock.as_deref().unwrap_or(*self.translations.unlock_permalink)
+    }
 }
 
 impl TextDirection {
@@ -0,0 +1,99 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::collections::HashMap;
+
+use crate::HtmlAndStripped;
+
+/// The translated texts of an artist, the catalog or a release, as read
+/// from an overlay manifest that is placed next to the regular manifest
+/// (e.g. `release.de.eno` next to `release.eno`). Texts that are not given
+/// in the overlay fall back to those from the regular manifest.
+#[derive(Clone, Debug)]
+pub struct LocalizedContent {
+    pub more: Option<HtmlAndStripped>,
+    pub synopsis: Option<String>,
+    pub title: Option<String>
+}
+
+/// All overlays of an artist, the catalog or a release. While the site for
+/// one of the catalog's `languages` is built, the texts from the overlay for
+/// that language are swapped in, afterwards the original texts are restored.
+#[derive(Clone, Debug)]
+pub struct Localizations {
+    /// Holds the texts from the regular manifest while the texts from an
+    /// overlay are swapped in
+    original: Option<LocalizedContent>,
+    /// Keyed by language code (e.g. "de")
+    overlays: HashMap<String, LocalizedContent>
+}
+
+impl LocalizedContent {
+    pub fn new() -> LocalizedContent {
+        LocalizedContent {
+            more: None,
+            synopsis: None,
+            title: None
+        }
+    }
+}
+
+impl Localizations {
+    pub fn insert(&mut self, language: String, localized_content: LocalizedContent) {
+        self.overlays.insert(language, localized_content);
+    }
+
+    pub fn languages(&self) -> impl Iterator<Item = &String> {
+        self.overlays.keys()
+    }
+
+    pub fn new() -> Localizations {
+        Localizations {
+            original: None,
+            overlays: HashMap::new()
+        }
+    }
+
+    pub fn overlays(&self) -> impl Iterator<Item = &LocalizedContent> {
+        self.overlays.values()
+    }
+
+    /// Swaps in the texts from the overlay for the given language (if there
+    /// is one), or restores the texts from the regular manifest if None is
+    /// passed (or there is no overlay for the language).
+    pub fn switch(
+        &mut self,
+        language: Option<&str>,
+        more: &mut Option<HtmlAndStripped>,
+        synopsis: &mut Option<String>,
+        title: &mut Option<String>
+    ) {
+        if let Some(original) = self.original.take() {
+            *more = original.more;
+            *synopsis = original.synopsis;
+            *title = original.title;
+        }
+
+        let Some(overlay) = language.and_then(|language| self.overlays.get(language)) else {
+            return
+        };
+
+        self.original = Some(LocalizedContent {
+            more: more.clone(),
+            synopsis: synopsis.clone(),
+            title: title.clone()
+        });
+
+        if let Some(overlay_more) = &overlay.more {
+            *more = Some(overlay_more.clone());
+        }
+
+        if let Some(overlay_synopsis) = &overlay.synopsis {
+            *synopsis = Some(overlay_synopsis.clone());
+        }
+
+        if let Some(overlay_title) = &overlay.title {
+            *title = Some(overlay_title.clone());
+        }
+    }
+}
@@ -0,0 +1,391 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Measurement of integrated loudness and true peak according to ITU-R
+//! BS.1770 (the basis of EBU R 128), performed on the decoded samples while
+//! the waveform is computed anyway. Through --loudness-report the results
+//! are written to an internal (never deployed) report, which flags tracks
+//! that are much louder or quieter than the rest of their release - handy
+//! before publishing compilations that are put together from many sources.
+
+use std::f64::consts::PI;
+use std::fs;
+
+use indoc::formatdoc;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{Build, Catalog};
+use crate::decode::DecodeResult;
+use crate::util::html_escape_outside_attribute;
+
+pub const LOUDNESS_REPORT_FILENAME: &str = ".faircamp_loudness_report.html";
+
+/// Tracks whose integrated loudness differs from the median of their
+/// release by more than this (in LU) are flagged in the report.
+const DEVIATION_THRESHOLD_LU: f32 = 3.0;
+
+/// Gating blocks are 400ms long and overlap by 75%, we therefore sum up
+/// the signal in 100ms segments and combine four of them into a block.
+const SEGMENTS_PER_BLOCK: usize = 4;
+const SEGMENT_SECONDS: f64 = 0.1;
+
+/// Inter-sample peaks are determined by oversampling (by this factor) with
+/// a windowed sinc interpolation filter of this many taps on each side.
+const OVERSAMPLING: usize = 4;
+const INTERPOLATION_TAPS: isize = 8;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Loudness {
+    /// Integrated loudness in LUFS. None if the track is silent or shorter
+    /// than a single gating block (400ms).
+    pub integrated_lufs: Option<f32>,
+    /// True peak in dBTP (negative infinity for silent tracks)
+    pub true_peak_dbtp: f32
+}
+
+/// A second-order IIR filter (transposed direct form II)
+struct Biquad {
+    a1: f64,
+    a2: f64,
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    z1: f64,
+    z2: f64
+}
+
+impl Biquad {
+    /// The high-pass stage of the K-weighting filter (sample rate specific
+    /// coefficients, as derived in libebur128)
+    fn k_weighting_high_pass(sample_rate: u32) -> Biquad {
+        let f0 = 38.13547087602444;
+        let q = 0.5003270373238773;
+        let k = (PI * f0 / sample_rate as f64).tan();
+        let a0 = 1.0 + k / q + k * k;
+
+        Biquad {
+            a1: 2.0 * (k * k - 1.0) / a0,
+            a2: (1.0 - k / q + k * k) / a0,
+            b0: 1.0,
+            b1: -2.0,
+            b2: 1.0,
+            z1: 0.0,
+            z2: 0.0
+        }
+    }
+
+    /// The high-shelf stage of the K-weighting filter, which models the
+    /// acoustic effect of the head (sample rate specific coefficients, as
+    /// derived in libebur128)
+    fn k_weighting_high_shelf(sample_rate: u32) -> Biquad {
+        let f0 = 1681.974450955533;
+        let gain_db = 3.999843853973347;
+        let q = 0.7071752369554196;
+        let k = (PI * f0 / sample_rate as f64).tan();
+        let vh = 10f64.powf(gain_db / 20.0);
+        let vb = vh.powf(0.4996667741545416);
+        let a0 = 1.0 + k / q + k * k;
+
+        Biquad {
+            a1: 2.0 * (k * k - 1.0) / a0,
+            a2: (1.0 - k / q + k * k) / a0,
+            b0: (vh + vb * k / q + k * k) / a0,
+            b1: 2.0 * (k * k - vh) / a0,
+            b2: (vh - vb * k / q + k * k) / a0,
+            z1: 0.0,
+            z2: 0.0
+        }
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b0 * input + self.z1;
+        self.z1 = self.b1 * input - self.a1 * output + self.z2;
+        self.z2 = self.b2 * input - self.a2 * output;
+        output
+    }
+}
+
+impl Loudness {
+    pub fn measure(decode_result: &DecodeResult) -> Loudness {
+        Loudness {
+            integrated_lufs: integrated_lufs(decode_result),
+            true_peak_dbtp: true_peak_dbtp(decode_result)
+        }
+    }
+}
+
+/// Channel weights as defined in BS.1770 - for 5.1 material the LFE channel
+/// is ignored and the surround channels are weighted higher, everything
+/// else is weighted equally.
+fn channel_weight(channels: usize, channel: usize) -> f64 {
+    match (channels, channel) {
+        (6, 3) => 0.0,
+        (6, 4) | (6, 5) => 1.41,
+        _ => 1.0
+    }
+}
+
+fn format_dbtp(true_peak_dbtp: f32) -> String {
+    if true_peak_dbtp.is_finite() {
+        format!("{true_peak_dbtp:.1} dBTP")
+    } else {
+        String::from("-∞ dBTP")
+    }
+}
+
+fn format_lufs(integrated_lufs: Option<f32>) -> String {
+    match integrated_lufs {
+        Some(lufs) => format!("{lufs:.1} LUFS"),
+        None => String::from("–")
+    }
+}
+
+fn integrated_lufs(decode_result: &DecodeResult) -> Option<f32> {
+    let channels = decode_result.channels as usize;
+    let segment_length = (decode_result.sample_rate as f64 * SEGMENT_SECONDS).round() as usize;
+
+    if channels == 0 || segment_length == 0 {
+        return None;
+    }
+
+    let mut filters = (0..channels)
+        .map(|_| (
+            Biquad::k_weighting_high_shelf(decode_result.sample_rate),
+            Biquad::k_weighting_high_pass(decode_result.sample_rate)
+        ))
+        .collect::<Vec<(Biquad, Biquad)>>();
+
+    // The channel-weighted sum of squares of the K-weighted signal per
+    // segment (incomplete trailing segments are dropped, as their blocks
+    // would be incomplete as well)
+    let mut segments = Vec::new();
+    let mut segment_sum = 0.0;
+    let mut segment_frames = 0;
+
+    for frame in decode_result.samples.chunks_exact(channels) {
+        for (channel, sample) in frame.iter().enumerate() {
+            let (high_shelf, high_pass) = &mut filters[channel];
+            let filtered = high_pass.process(high_shelf.process(*sample as f64));
+            segment_sum += channel_weight(channels, channel) * filtered * filtered;
+        }
+
+        segment_frames += 1;
+
+        if segment_frames == segment_length {
+            segments.push(segment_sum);
+            segment_sum = 0.0;
+            segment_frames = 0;
+        }
+    }
+
+    let block_length = (segment_length * SEGMENTS_PER_BLOCK) as f64;
+    let block_powers = segments
+        .windows(SEGMENTS_PER_BLOCK)
+        .map(|block_segments| block_segments.iter().sum::<f64>() / block_length)
+        .collect::<Vec<f64>>();
+
+    let loudness = |power: f64| -0.691 + 10.0 * power.log10();
+
+    let gated_mean = |threshold: f64| {
+        let gated = block_powers
+            .iter()
+            .filter(|power| loudness(**power) > threshold)
+            .collect::<Vec<&f64>>();
+
+        if gated.is_empty() {
+            None
+        } else {
+            Some(gated.iter().copied().sum::<f64>() / gated.len() as f64)
+        }
+    };
+
+    // Absolute gate at -70 LUFS, then relative gate 10 LU below the loudness
+    // of the blocks that passed the absolute gate
+    let absolute_gated_power = gated_mean(-70.0)?;
+    let relative_gated_power = gated_mean(loudness(absolute_gated_power) - 10.0)?;
+
+    Some(loudness(relative_gated_power) as f32)
+}
+
+/// Determines the true peak by 4x oversampling around the loudest samples.
+/// Inter-sample peaks can only notably exceed the sample peak in the
+/// vicinity of loud samples, so in the interest of build time we only
+/// interpolate where one of the neighbouring samples is within 6dB of the
+/// sample peak.
+fn true_peak_dbtp(decode_result: &DecodeResult) -> f32 {
+    let channels = decode_result.channels as usize;
+    let samples = &decode_result.samples;
+
+    let sample_peak = samples.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+
+    if sample_peak == 0.0 || channels == 0 {
+        return f32::NEG_INFINITY;
+    }
+
+    let threshold = sample_peak / 2.0;
+    let frames = samples.len() / channels;
+
+    // Interpolation filter coefficients for each fractional offset
+    // (1/4, 2/4, 3/4) and tap, windowed with a hann window
+    let coefficients = (1..OVERSAMPLING)
+        .map(|phase| {
+            let offset = phase as f64 / OVERSAMPLING as f64;
+            (-INTERPOLATION_TAPS + 1..=INTERPOLATION_TAPS)
+                .map(|tap| {
+                    let t = offset - tap as f64;
+                    let sinc = if t == 0.0 { 1.0 } else { (PI * t).sin() / (PI * t) };
+                    let window = 0.5 + 0.5 * (PI * t / INTERPOLATION_TAPS as f64).cos();
+                    sinc * window
+                })
+                .collect::<Vec<f64>>()
+        })
+        .collect::<Vec<Vec<f64>>>();
+
+    let mut true_peak = sample_peak as f64;
+
+    for channel in 0..channels {
+        let sample = |frame: isize| -> f64 {
+            if frame < 0 || frame >= frames as isize {
+                0.0
+            } else {
+                samples[frame as usize * channels + channel] as f64
+            }
+        };
+
+        for frame in 0..frames as isize {
+            if sample(frame).abs() < threshold as f64 && sample(frame + 1).abs() < threshold as f64 {
+                continue;
+            }
+
+            for phase_coefficients in &coefficients {
+                let interpolated = (-INTERPOLATION_TAPS + 1..=INTERPOLATION_TAPS)
+                    .zip(phase_coefficients)
+                    .map(|(tap, coefficient)| sample(frame + tap) * coefficient)
+                    .sum::<f64>();
+
+                true_peak = true_peak.max(interpolated.abs());
+            }
+        }
+    }
+
+    (20.0 * true_peak.log10()) as f32
+}
+
+/// Writes the loudness report for all tracks in the catalog as html to the
+/// catalog directory (it is not part of the build and therefore never
+/// deployed).
+pub fn write_report(build: &Build, catalog: &Catalog) {
+    let mut flagged_tracks = 0;
+    let mut r_releases = String::new();
+
+    for release in &catalog.releases {
+        let release_ref = release.borrow();
+
+        let mut measured = release_ref.tracks
+            .iter()
+            .filter_map(|track| track.transcodes.borrow().source_meta.loudness.integrated_lufs)
+            .collect::<Vec<f32>>();
+
+        measured.sort_by(|a, b| a.total_cmp(b));
+
+        // Deviation can only be judged in comparison to other tracks
+        let median = if measured.len() > 1 {
+            let middle = measured.len() / 2;
+            if measured.len() % 2 == 0 {
+                Some((measured[middle - 1] + measured[middle]) / 2.0)
+            } else {
+                Some(measured[middle])
+            }
+        } else {
+            None
+        };
+
+        let r_tracks = release_ref.tracks
+            .iter()
+            .map(|track| {
+                let loudness = track.transcodes.borrow().source_meta.loudness.clone();
+
+                let deviation = match (loudness.integrated_lufs, median) {
+                    (Some(lufs), Some(median)) => Some(lufs - median),
+                    _ => None
+                };
+
+                let (flag_class, r_deviation) = match deviation {
+                    Some(deviation) if deviation.abs() > DEVIATION_THRESHOLD_LU => {
+                        flagged_tracks += 1;
+                        (r#" class="flagged""#, format!("{deviation:+.1} LU"))
+                    }
+                    Some(deviation) => ("", format!("{deviation:+.1} LU")),
+                    None => ("", String::from("–"))
+                };
+
+                formatdoc!(r#"
+                    <tr{flag_class}>
+                        <td>{title}</td>
+                        <td>{lufs}</td>
+                        <td>{deviation}</td>
+                        <td>{peak}</td>
+                    </tr>
+                "#,
+                    deviation = r_deviation,
+                    lufs = format_lufs(loudness.integrated_lufs),
+                    peak = format_dbtp(loudness.true_peak_dbtp),
+                    title = html_escape_outside_attribute(&track.title())
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("");
+
+        let r_median = match median {
+            Some(median) => format!(" (median {median:.1} LUFS)"),
+            None => String::new()
+        };
+
+        r_releases.push_str(&formatdoc!(r#"
+            <h2>{title}{r_median}</h2>
+            <table>
+                <tr>
+                    <th>Track</th>
+                    <th>Integrated loudness</th>
+                    <th>Deviation from median</th>
+                    <th>True peak</th>
+                </tr>
+                {r_tracks}
+            </table>
+        "#,
+            title = html_escape_outside_attribute(&release_ref.title)
+        ));
+    }
+
+    let html = formatdoc!(r#"
+        <!DOCTYPE html>
+        <html>
+            <head>
+                <meta charset="utf-8">
+                <title>Loudness report</title>
+                <style>
+                    body {{ font-family: sans-serif; margin: 2rem; }}
+                    table {{ border-collapse: collapse; }}
+                    td, th {{ border: 1px solid #ccc; padding: .25rem .5rem; text-align: left; }}
+                    .flagged {{ background-color: #fdd; }}
+                </style>
+            </head>
+            <body>
+                <h1>Loudness report</h1>
+                <p>
+                    Integrated loudness and true peak according to ITU-R BS.1770.
+                    Tracks deviating by more than {DEVIATION_THRESHOLD_LU} LU from
+                    the median of their release are highlighted.
+                </p>
+                {r_releases}
+            </body>
+        </html>
+    "#);
+
+    let path = build.catalog_dir.join(LOUDNESS_REPORT_FILENAME);
+
+    match fs::write(&path, html) {
+        Ok(()) => info_stats!("Loudness report ({} tracks flagged) was written to {}", flagged_tracks, path.display()),
+        Err(err) => error!("Loudness report could not be written to {} ({})", path.display(), err)
+    }
+}
@@ -0,0 +1,118 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Lyrics of a track, given either as plain text or in the LRC format (lines
+//! prefixed with timestamps, e.g. "[01:12.50]And so it goes"). They are shown
+//! on the track page - with LRC the line currently being sung is highlighted
+//! during playback - and written into the tags of the transcoded files.
+
+use std::fs;
+use std::path::Path;
+
+use crate::util::parse_timecode;
+
+/// Configured through the track manifest's `lyrics` option, or picked up
+/// from an .lrc file (or a file named lyrics.txt) in the track directory.
+#[derive(Clone, Debug)]
+pub struct Lyrics {
+    pub lines: Vec<LyricsLine>,
+    /// Whether all lines have a timestamp (i.e. the lyrics came from LRC)
+    pub synced: bool
+}
+
+/// An empty line separates stanzas in plain text lyrics, in synced
+/// lyrics it marks an instrumental passage.
+#[derive(Clone, Debug)]
+pub struct LyricsLine {
+    pub seconds: Option<f32>,
+    pub text: String
+}
+
+impl Lyrics {
+    /// Parses the lyrics as LRC if any line starts with a timestamp,
+    /// otherwise as plain text. Returns None if there is no text at all.
+    pub fn parse(content: &str) -> Option<Lyrics> {
+        let content = content.trim_start_matches('\u{feff}');
+
+        let lyrics = match parse_lrc(content) {
+            Some(lines) => Lyrics { lines, synced: true },
+            None => Lyrics {
+                lines: content
+                    .trim()
+                    .lines()
+                    .map(|line| LyricsLine { seconds: None, text: line.trim().to_string() })
+                    .collect(),
+                synced: false
+            }
+        };
+
+        lyrics.lines
+            .iter()
+            .any(|line| !line.text.is_empty())
+            .then_some(lyrics)
+    }
+
+    pub fn read(path: &Path) -> Result<Lyrics, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("The lyrics could not be read ({err})"))?;
+
+        Lyrics::parse(&content).ok_or_else(|| String::from("The lyrics contain no text"))
+    }
+
+    /// The lyrics as plain text (without timestamps), as written to tags.
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| line.text.as_str())
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+}
+
+/// Lines can have several timestamps (when they repeat), lines without a
+/// timestamp and metadata tags such as "[ar:Alice]" are skipped, except for
+/// "[offset:+250]", which shifts all timestamps (by milliseconds, positive
+/// values make the lines appear sooner).
+fn parse_lrc(content: &str) -> Option<Vec<LyricsLine>> {
+    let mut lines = Vec::new();
+    let mut offset_seconds = 0.0;
+
+    for line in content.lines() {
+        let mut remainder = line.trim();
+        let mut timestamps = Vec::new();
+
+        while let Some(tag) = remainder.strip_prefix('[') {
+            let Some(tag_end) = tag.find(']') else { break };
+            let tag_content = &tag[..tag_end];
+
+            if let Some(seconds) = parse_timecode(tag_content) {
+                timestamps.push(seconds);
+            } else if let Some(offset) = tag_content.strip_prefix("offset:") {
+                if let Ok(milliseconds) = offset.trim().parse::<f32>() {
+                    offset_seconds = milliseconds / 1000.0;
+                }
+            }
+
+            remainder = &tag[(tag_end + 1)..];
+        }
+
+        for seconds in timestamps {
+            lines.push(LyricsLine {
+                seconds: Some(seconds),
+                text: remainder.trim().to_string()
+            });
+        }
+    }
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    for line in lines.iter_mut() {
+        line.seconds = line.seconds.map(|seconds| (seconds - offset_seconds).max(0.0));
+    }
+
+    lines.sort_by(|a, b| a.seconds.unwrap().total_cmp(&b.seconds.unwrap()));
+
+    Some(lines)
+}
@@ -11,10 +11,11 @@ use indoc::formatdoc;
 
 use crate::{
     Artist,
+    AudioFormat,
     Build,
     Catalog,
+    Playlist,
     Release,
-    SiteUrl,
     Track,
     TRACK_NUMBERS
 };
@@ -23,38 +24,20 @@ pub const M3U_PLAYLIST_FILENAME: &str = "playlist.m3u";
 
 /// Generate complete content of an M3U playlist for all (public) releases of
 /// an artist.
-pub fn generate_for_artist(
-    artist: &Artist,
-    base_url: &SiteUrl,
-    build: &Build
-) -> String {
+pub fn generate_for_artist(artist: &Artist, build: &Build) -> String {
     let r_releases = artist.public_releases()
         .iter()
         .map(|release| {
             let release_ref = release.borrow();
-            let release_slug = &release_ref.permalink.slug;
             let release_title = &release_ref.title;
 
             let r_tracks = generate_tracks(
-                base_url,
                 build,
                 &release_ref,
                 &release_ref.tracks
             );
 
-            let release_cover_url = match &release_ref.cover {
-                Some(described_image) => {
-                    let image_ref = described_image.borrow();
-                    let file_name = image_ref.cover_assets_unchecked().playlist_image();
-                    let hash = image_ref.hash.as_url_safe_base64();
-
-                    base_url.join_file(format!("{release_slug}/{file_name}?{hash}"))
-                }
-                None => {
-                    let file_name = release_ref.procedural_cover_480_filename_unchecked();
-                    base_url.join_file(format!("{release_slug}/{file_name}"))
-                }
-            };
+            let release_cover_url = release_cover_url(build, &release_ref);
 
             formatdoc!(r#"
                 #EXTIMG:{release_cover_url}
@@ -72,7 +55,7 @@ pub fn generate_for_artist(
             let file_name = image_ref.artist_assets.as_ref().unwrap().playlist_image();
             let hash = image_ref.hash.as_url_safe_base64();
 
-            let artist_image_url = base_url.join_file(format!("{artist_slug}/{file_name}?{hash}"));
+            let artist_image_url = build.asset_url().join_file(format!("{artist_slug}/{file_name}?{hash}"));
 
             format!("#EXTIMG:{artist_image_url}")
         }
@@ -93,36 +76,21 @@ pub fn generate_for_artist(
 /// Generate complete content of an M3U playlist for all (public) releases of
 /// the catalog.
 pub fn generate_for_catalog(build: &Build, catalog: &Catalog) -> String {
-    let base_url = build.base_url_unchecked();
     let catalog_title = catalog.title();
 
     let r_releases = catalog.public_releases()
         .iter()
         .map(|release| {
             let release_ref = release.borrow();
-            let release_slug = &release_ref.permalink.slug;
             let release_title = &release_ref.title;
 
             let r_tracks = generate_tracks(
-                base_url,
                 build,
                 &release_ref,
                 &release_ref.tracks
             );
 
-            let release_cover_url = match &release_ref.cover {
-                Some(described_image) => {
-                    let image_ref = described_image.borrow();
-                    let file_name = image_ref.cover_assets_unchecked().playlist_image();
-                    let hash = image_ref.hash.as_url_safe_base64();
-
-                    base_url.join_file(format!("{release_slug}/{file_name}?{hash}"))
-                }
-                None => {
-                    let file_name = release_ref.procedural_cover_480_filename_unchecked();
-                    base_url.join_file(format!("{release_slug}/{file_name}"))
-                }
-            };
+            let release_cover_url = release_cover_url(build, &release_ref);
 
             formatdoc!(r#"
                 #EXTIMG:{release_cover_url}
@@ -140,7 +108,7 @@ pub fn generate_for_catalog(build: &Build, catalog: &Catalog) -> String {
             let file_name = image_ref.artist_assets.as_ref().unwrap().playlist_image();
             let hash = image_ref.hash.as_url_safe_base64();
 
-            let file_url = base_url.join_file(format!("{file_name}?{hash}"));
+            let file_url = build.asset_url().join_file(format!("{file_name}?{hash}"));
 
             format!("#EXTIMG:{file_url}")
         }
@@ -156,35 +124,48 @@ pub fn generate_for_catalog(build: &Build, catalog: &Catalog) -> String {
     "#)
 }
 
+/// Generate complete content of an M3U playlist for a (curated) playlist
+pub fn generate_for_playlist(build: &Build, playlist: &Playlist) -> String {
+    let playlist_title = &playlist.title;
+
+    let r_tracks = playlist.tracks
+        .iter()
+        .map(|playlist_track| {
+            let release_ref = playlist_track.release.borrow();
+            let release_title = &release_ref.title;
+            let track = playlist_track.track(&release_ref);
+
+            let r_track = generate_track(
+                build,
+                &release_ref,
+                track,
+                playlist_track.track_number
+            );
+
+            format!("#EXTALB:{release_title}\n{r_track}")
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    formatdoc!(r#"
+        #EXTM3U
+        #EXTENC:UTF-8
+        #PLAYLIST:{playlist_title}
+        {r_tracks}
+    "#)
+}
+
 /// Generate complete content of an M3U playlist for a release
-pub fn generate_for_release(
-    base_url: &SiteUrl,
-    build: &Build,
-    release: &Release
-) -> String {
-    let release_slug = &release.permalink.slug;
+pub fn generate_for_release(build: &Build, release: &Release) -> String {
     let release_title = &release.title;
 
     let r_tracks = generate_tracks(
-        base_url,
         build,
         release,
         &release.tracks
     );
 
-    let release_cover_url = match &release.cover {
-        Some(described_image) => {
-            let image_ref = described_image.borrow();
-            let file_name = image_ref.cover_assets_unchecked().playlist_image();
-            let hash = image_ref.hash.as_url_safe_base64();
-
-            base_url.join_file(format!("{release_slug}/{file_name}?{hash}"))
-        }
-        None => {
-            let file_name = release.procedural_cover_480_filename_unchecked();
-            base_url.join_file(format!("{release_slug}/{file_name}"))
-        }
-    };
+    let release_cover_url = release_cover_url(build, release);
 
     formatdoc!(r#"
         #EXTM3U
@@ -196,62 +177,113 @@ pub fn generate_for_release(
     "#)
 }
 
+/// Generate M3U playlist content for a single track, used for the tracks of
+/// a release as well as for playlists, which pick tracks across releases.
+fn generate_track(
+    build: &Build,
+    release: &Release,
+    track: &Track,
+    track_number: usize
+) -> String {
+    let track_number_formatted = release.track_numbering.format(track_number);
+
+    let artists = track.artists
+        .iter()
+        .map(|artist| artist.borrow().name.clone())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let track_title = track.title();
+    let title = match track_number_formatted.is_empty() {
+        true => format!("{artists} – {track_title}"),
+        false => format!("{artists} – {track_number_formatted} {track_title}")
+    };
+
+    let duration_seconds = track.transcodes.borrow().source_meta.duration_seconds as usize;
+
+    let extinf = format!("#EXTINF:{duration_seconds}, {title}");
+
+    let file_url = track_url(build, release, track, track_number);
+
+    format!("{extinf}\n{file_url}")
+}
+
 /// Generate M3U playlist content just for the tracks of a release, to be used
 /// as a reusable function for generating either a playlist for an release or
 /// for an entire catalog (multiple releases).
 pub fn generate_tracks(
-    base_url: &SiteUrl,
     build: &Build,
     release: &Release,
     tracks: &[Track]
 ) -> String {
-    let release_slug = &release.permalink.slug;
-
     tracks
         .iter()
         .zip(TRACK_NUMBERS)
-        .map(|(track, track_number)| {
-            let track_number_formatted = release.track_numbering.format(track_number);
+        .map(|(track, track_number)| generate_track(build, release, track, track_number))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
 
-            let artists = track.artists
-                .iter()
-                .map(|artist| artist.borrow().name.clone())
-                .collect::<Vec<String>>()
-                .join(", ");
+/// Absolute url of the image that represents a release in playlists (the
+/// cover, or the procedural cover as a fallback)
+pub fn release_cover_url(build: &Build, release: &Release) -> String {
+    let asset_url = build.asset_url();
+    let release_slug = &release.permalink.slug;
 
-            let track_title = track.title();
-            let title = match track_number_formatted.is_empty() {
-                true => format!("{artists} – {track_title}"),
-                false => format!("{artists} – {track_number_formatted} {track_title}")
-            };
+    match &release.cover {
+        Some(described_image) => {
+            let image_ref = described_image.borrow();
+            let file_name = image_ref.cover_assets_unchecked().playlist_image();
+            let hash = image_ref.hash.as_url_safe_base64();
 
-            let duration_seconds = track.transcodes.borrow().source_meta.duration_seconds as usize;
+            asset_url.join_file(format!("{release_slug}/{file_name}?{hash}"))
+        }
+        None => {
+            let file_name = release.procedural_cover_480_filename_unchecked();
+            asset_url.join_file(format!("{release_slug}/{file_name}"))
+        }
+    }
+}
 
-            let extinf = format!("#EXTINF:{duration_seconds}, {title}");
+/// Absolute url of the audio file of a track in a specific streaming format
+pub fn track_format_url(
+    build: &Build,
+    format: AudioFormat,
+    release: &Release,
+    track: &Track,
+    track_number: usize
+) -> String {
+    let release_slug = &release.permalink.slug;
 
-            let primary_streaming_format = track.streaming_quality.formats()[0];
-            let format_dir = primary_streaming_format.asset_dirname();
-            let format_extension = primary_streaming_format.extension();
+    let format_dir = format.asset_dirname();
+    let format_extension = format.extension();
 
-            let track_filename = format!(
-                "{basename}{format_extension}",
-                basename = track.asset_basename.as_ref().unwrap()
-            );
+    let track_filename = format!(
+        "{basename}{format_extension}",
+        basename = track.asset_basename.as_ref().unwrap()
+    );
 
-            let track_hash = build.hash_with_salt(|hasher| {
-                release_slug.hash(hasher);
-                track_number.hash(hasher);
-                format_dir.hash(hasher);
-                track_filename.hash(hasher);
-            });
+    let track_hash = build.hash_with_salt(|hasher| {
+        release_slug.hash(hasher);
+        track_number.hash(hasher);
+        format_dir.hash(hasher);
+        track_filename.hash(hasher);
+    });
 
-            let track_filename_urlencoded = urlencoding::encode(&track_filename);
-            let file_url = base_url.join_file(
-                format!("{release_slug}/{track_number}/{format_dir}/{track_hash}/{track_filename_urlencoded}")
-            );
+    let track_filename_urlencoded = urlencoding::encode(&track_filename);
 
-            format!("{extinf}\n{file_url}")
-        })
-        .collect::<Vec<String>>()
-        .join("\n")
+    build.asset_url().join_file(
+        format!("{release_slug}/{track_number}/{format_dir}/{track_hash}/{track_filename_urlencoded}")
+    )
+}
+
+/// Absolute url of the audio file of a track in its primary streaming format
+pub fn track_url(
+    build: &Build,
+    release: &Release,
+    track: &Track,
+    track_number: usize
+) -> String {
+    let primary_streaming_format = track.streaming_quality.formats()[0];
+    track_format_url(build, primary_streaming_format, release, track, track_number)
 }
@@ -8,18 +8,37 @@ use enolib::prelude::*;
 use enolib::{Attribute, Item};
 
 use crate::{
+    Annotation,
+    ArchiveCover,
+    ArchiveLayout,
+    Chapter,
+    ContentWarning,
+    CoverAspectRatio,
     DescribedImage,
     DownloadAccessOption,
     DownloadFormat,
     ExtraDownloads,
+    FormatComparison,
     HtmlAndStripped,
     Link,
+    Lyrics,
+    ManifestText,
+    PaymentPointer,
     Permalink,
+    PhysicalEdition,
     Price,
+    PublishDate,
+    ReleaseSection,
+    RemoteSource,
+    ShareButton,
+    SiteAsset,
+    SiteMetadata,
     StreamingQuality,
     TagAgenda,
     Theme,
-    TrackNumbering
+    TrackNumbering,
+    Transcript,
+    VisualLoop
 };
 
 const MAX_SYNOPSIS_CHARS: usize = 256;
@@ -28,9 +47,12 @@ mod artist;
 mod artist_catalog_release;
 mod artist_catalog_release_track;
 mod artist_release;
+mod artist_release_track;
 mod catalog;
 mod catalog_release;
 mod obsolete;
+mod overlay;
+mod playlist;
 mod release;
 mod release_track;
 mod track;
@@ -48,12 +70,18 @@ pub use artist_release::{
     ARTIST_RELEASE_OPTIONS,
     read_artist_release_option
 };
+pub use artist_release_track::{
+    ARTIST_RELEASE_TRACK_OPTIONS,
+    read_artist_release_track_option
+};
 pub use catalog::read_catalog_manifest;
 pub use catalog_release::{
     CATALOG_RELEASE_OPTIONS,
     read_catalog_release_option
 };
 pub use obsolete::{read_obsolete_option, read_obsolete_theme_attribute};
+pub use overlay::read_overlay_manifests;
+pub use playlist::read_playlist_manifest;
 pub use release::read_release_manifest;
 pub use release_track::{
     RELEASE_TRACK_OPTIONS,
@@ -66,19 +94,65 @@ pub use track::read_track_manifest;
 /// apply to one artist or release, thus it is a local option only.
 #[derive(Clone)]
 pub struct LocalOptions {
+    /// Used by track
+    pub annotations: Vec<Annotation>,
+    /// Used by track
+    pub chapters: Vec<Chapter>,
+    /// Used by release
+    pub content_warning: Option<ContentWarning>,
     /// Used by release and track
     pub cover: Option<DescribedImage>,
+    /// Used by release, in seconds
+    pub crossfade: Option<f32>,
+    /// Used by release, set through `status: draft`
+    pub draft: bool,
+    /// Used by release
+    pub embed_support_button: bool,
+    /// Used by release
+    pub format_comparison: Option<FormatComparison>,
+    /// Used by release
+    pub gapless: bool,
+    /// Used by release
+    pub imprint: Option<String>,
+    /// Used by track, in its canonical form (see the identifiers module)
+    pub isrc: Option<String>,
     pub links: Vec<Link>,
+    /// Used by track
+    pub lyrics: Option<Lyrics>,
     /// Used by artist, release and track
     pub more: Option<HtmlAndStripped>,
+    /// Used by artist, release and track
+    pub noindex: bool,
+    /// Used by artist, release and track
+    pub page_assets: Vec<SiteAsset>,
+    /// Used by artist, release and track
+    pub page_metadata: Option<SiteMetadata>,
     /// Used by artist and release
     pub permalink: Option<Permalink>,
+    /// Used by release
+    pub physical_editions: Vec<PhysicalEdition>,
+    /// Used by release
+    pub publish_date: Option<PublishDate>,
+    /// Used by release
+    pub region: Option<String>,
     pub release_date: Option<NaiveDate>,
+    /// Used by release, None means the default layout is used
+    pub release_layout: Option<Vec<ReleaseSection>>,
+    /// Used by track
+    pub remote_source: Option<RemoteSource>,
+    /// Used by release, the date on which the secret link expires
+    pub secret_link: Option<NaiveDate>,
     /// Used by artist, release and track
     pub synopsis: Option<String>,
     /// Used by release and track
     pub title: Option<String>,
-    pub unlisted_release: bool
+    /// Used by track
+    pub transcript: Option<Transcript>,
+    pub unlisted_release: bool,
+    /// Used by release, in its canonical form (see the identifiers module)
+    pub upc: Option<String>,
+    /// Used by track
+    pub visual_loop: Option<VisualLoop>
 }
 
 /// Options specified in a manifest that apply to everything in the same
@@ -89,17 +163,24 @@ pub struct LocalOptions {
 /// in a manifest further down the hierarchy, hence it is an override.
 #[derive(Clone)]
 pub struct Overrides {
+    pub archive_cover: ArchiveCover,
+    pub archive_layout: ArchiveLayout,
     pub copy_link: bool,
+    pub cover_aspect_ratio: CoverAspectRatio,
+    pub cover_svg_original: bool,
     pub download_codes: Vec<String>,
     pub embedding: bool,
     pub m3u_enabled: bool,
     pub more_label: Option<String>,
     pub payment_info: Option<String>,
+    pub payment_pointer: Option<PaymentPointer>,
+    pub podcast_categories: Vec<String>,
     pub release_artists: Vec<String>,
     pub release_download_access: DownloadAccessOption,
     pub release_downloads: Vec<DownloadFormat>,
     pub release_extras: ExtraDownloads,
     pub release_price: Price,
+    pub share_buttons: Vec<ShareButton>,
     pub speed_controls: bool,
     pub streaming_quality: StreamingQuality,
     pub tag_agenda: TagAgenda,
@@ -110,20 +191,44 @@ pub struct Overrides {
     pub track_extras: bool,
     pub track_numbering: TrackNumbering,
     pub track_price: Price,
-    pub unlock_info: Option<String>
+    pub unlock_info: Option<String>,
+    pub volume_normalization: bool
 }
 
 impl LocalOptions {
     pub fn new() -> LocalOptions {
         LocalOptions {
+            annotations: Vec::new(),
+            chapters: Vec::new(),
+            content_warning: None,
             cover: None,
+            crossfade: None,
+            draft: false,
+            embed_support_button: false,
+            format_comparison: None,
+            gapless: false,
+            imprint: None,
+            isrc: None,
             links: Vec::new(),
+            lyrics: None,
             more: None,
+            noindex: false,
+            page_assets: Vec::new(),
+            page_metadata: None,
             permalink: None,
+            physical_editions: Vec::new(),
+            publish_date: None,
+            region: None,
             release_date: None,
+            release_layout: None,
+            remote_source: None,
+            secret_link: None,
             synopsis: None,
             title: None,
-            unlisted_release: false
+            transcript: None,
+            unlisted_release: false,
+            upc: None,
+            visual_loop: None
         }
     }
 }
@@ -131,17 +236,24 @@ impl LocalOptions {
 impl Overrides {
     pub fn default() -> Overrides {
         Overrides {
+            archive_cover: ArchiveCover::DEFAULT,
+            archive_layout: ArchiveLayout::FLAT,
             copy_link: true,
+            cover_aspect_ratio: CoverAspectRatio::Square,
+            cover_svg_original: false,
             download_codes: Vec::new(),
             embedding: false,
             m3u_enabled: false,
             more_label: None,
             payment_info: None,
+            payment_pointer: None,
+            podcast_categories: Vec::new(),
             release_artists: Vec::new(),
             release_download_access: DownloadAccessOption::Free,
             release_downloads: Vec::new(),
             release_extras: ExtraDownloads::BUNDLED,
             release_price: Price::default(),
+            share_buttons: Vec::new(),
             speed_controls: false,
             streaming_quality: StreamingQuality::Standard,
             tag_agenda: TagAgenda::normalize(),
@@ -152,7 +264,8 @@ impl Overrides {
             track_extras: true,
             track_numbering: TrackNumbering::ArabicDotted,
             track_price: Price::default(),
-            unlock_info: None
+            unlock_info: None,
+            volume_normalization: false
         }
     }
 }
@@ -184,6 +297,23 @@ fn item_error_with_snippet(
     format!("Error in {}:{}:\n\n{}\n\n{}", manifest_path.display(), item.line_number, snippet, error)
 }
 
+/// Captures a synopsis/more text along with its location, so it can be
+/// checked with --check-texts once the entire catalog is read.
+fn manifest_text(
+    element: &Box<dyn SectionElement>,
+    manifest_path: &Path,
+    markdown: bool,
+    text: &str
+) -> ManifestText {
+    ManifestText {
+        location: format!("{}:{}", manifest_path.display(), element.line_number()),
+        markdown,
+        option: if markdown { "more" } else { "synopsis" },
+        snippet: element.snippet(),
+        text: text.to_string()
+    }
+}
+
 fn not_supported_error(
     manifest_name: &str,
     option_key: &str,
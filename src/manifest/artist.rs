@@ -15,13 +15,16 @@ use crate::{
     Catalog,
     DescribedImage,
     LocalOptions,
-    Overrides
+    Localizations,
+    Overrides,
+    SiteUrl
 };
 
 use super::{
     ARTIST_CATALOG_RELEASE_OPTIONS,
     ARTIST_CATALOG_RELEASE_TRACK_OPTIONS,
     ARTIST_RELEASE_OPTIONS,
+    ARTIST_RELEASE_TRACK_OPTIONS,
     attribute_error_with_snippet,
     element_error_with_snippet,
     not_supported_error,
@@ -29,6 +32,7 @@ use super::{
     read_artist_catalog_release_option,
     read_artist_catalog_release_track_option,
     read_artist_release_option,
+    read_artist_release_track_option,
     read_obsolete_option
 };
 
@@ -37,7 +41,9 @@ const ARTIST_OPTIONS: &[&str] = &[
     "aliases",
     "external_page",
     "image",
-    "name"
+    "name",
+    "release_timeline",
+    "standalone_site"
 ];
 
 pub fn read_artist_manifest(
@@ -45,6 +51,7 @@ pub fn read_artist_manifest(
     cache: &mut Cache,
     catalog: &mut Catalog,
     dir: &Path,
+    localizations: Localizations,
     manifest_path: &Path,
     overrides: &mut Overrides
 ) {
@@ -57,6 +64,8 @@ pub fn read_artist_manifest(
         }
     };
 
+    build.register_manifest(manifest_path, &content);
+
     let document = match enolib::parse_with_printer(&content, platform_printer()) {
         Ok(document) => document,
         Err(err) => {
@@ -73,6 +82,8 @@ pub fn read_artist_manifest(
     // By default we use the folder name as name
     let mut name = dir.file_name().unwrap().to_string_lossy().to_string();
     let mut image = None;
+    let mut release_timeline = false;
+    let mut standalone_site = None;
 
     for element in document.elements() {
         match element.key() {
@@ -188,9 +199,55 @@ pub fn read_artist_manifest(
                 let error = element_error_with_snippet(element, manifest_path, message);
                 build.error(&error);
             }
+            "release_timeline" => 'release_timeline: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => release_timeline = false,
+                                "enabled" => release_timeline = true,
+                                _ => {
+                                    let message = format!("This release_timeline setting was not recognized ('{value}'), supported values are 'disabled' and 'enabled'");
+                                    let error = element_error_with_snippet(element, manifest_path, &message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'release_timeline;
+                    }
+                }
+
+                let message = "release_timeline needs to be provided as a field with the value 'enabled' or 'disabled', e.g.: 'release_timeline: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "standalone_site" => 'standalone_site: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match SiteUrl::parse(value) {
+                                Ok(site_url) => standalone_site = Some(site_url),
+                                Err(err) => {
+                                    let message = format!("The standalone_site setting value '{value}' is not a valid URL: {err}");
+                                    let error = element_error_with_snippet(element, manifest_path, &message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'standalone_site;
+                    }
+                }
+
+                let message = "standalone_site needs to be provided as a field with a value (the base_url of the artist's standalone site), e.g.: 'standalone_site: https://alice.example.com'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
             _ if read_artist_catalog_release_option(build, element, manifest_path, overrides) => (),
             _ if read_artist_catalog_release_track_option(build, cache, element, &mut local_options, manifest_path, overrides) => (),
             _ if read_artist_release_option(build, element, &mut local_options, manifest_path, overrides) => (),
+            _ if read_artist_release_track_option(build, dir, element, &mut local_options, manifest_path) => (),
             other => {
                 let message = not_supported_error(
                     "artist.eno",
@@ -199,7 +256,8 @@ pub fn read_artist_manifest(
                         ARTIST_OPTIONS,
                         ARTIST_CATALOG_RELEASE_OPTIONS,
                         ARTIST_CATALOG_RELEASE_TRACK_OPTIONS,
-                        ARTIST_RELEASE_OPTIONS
+                        ARTIST_RELEASE_OPTIONS,
+                        ARTIST_RELEASE_TRACK_OPTIONS
                     ]
                 );
 
@@ -215,11 +273,18 @@ pub fn read_artist_manifest(
         external_page,
         image,
         mem::take(&mut local_options.links),
+        localizations,
         overrides.m3u_enabled,
         local_options.more.take(),
         overrides.more_label.clone(),
         &name,
+        local_options.noindex,
+        mem::take(&mut local_options.page_assets),
+        local_options.page_metadata.take(),
         local_options.permalink.take(),
+        release_timeline,
+        overrides.share_buttons.clone(),
+        standalone_site,
         local_options.synopsis.take(),
         overrides.theme.clone()
     );
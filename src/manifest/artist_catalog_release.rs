@@ -7,13 +7,17 @@ use enolib::SectionElement;
 use url::Url;
 
 use crate::{
+    ArchiveCover,
+    ArchiveLayout,
     Build,
+    CoverAspectRatio,
     DownloadAccessOption,
     DownloadFormat,
     ExtraDownloads,
     Overrides,
     Price,
-    TrackNumbering
+    TrackNumbering,
+    WarningCategory
 };
 
 use super::{
@@ -22,6 +26,11 @@ use super::{
 };
 
 pub const ARTIST_CATALOG_RELEASE_OPTIONS: &[&str] = &[
+    "archive_cover",
+    "archive_layout",
+    "cover_aspect_ratio",
+    "cover_svg",
+    "podcast_categories",
     "release_download_access",
     "release_downloads",
     "release_extras",
@@ -38,11 +47,142 @@ pub fn read_artist_catalog_release_option(
     overrides: &mut Overrides
 ) -> bool {
     match element.key() {
+        "archive_cover" => 'archive_cover: {
+            if let Ok(field) = element.as_field() {
+                if let Ok(result) = field.value() {
+                    if let Some(value) = result {
+                        let mut archive_cover = ArchiveCover::DEFAULT;
+
+                        match archive_cover.apply_value(value) {
+                            Ok(()) => overrides.archive_cover = archive_cover,
+                            Err(message) => {
+                                let error = element_error_with_snippet(element, manifest_path, &message);
+                                build.error(&error);
+                            }
+                        }
+                    }
+
+                    break 'archive_cover;
+                } else if let Ok(items) = field.items() {
+                    overrides.archive_cover = ArchiveCover::DEFAULT;
+
+                    for item in items.iter() {
+                        if let Some(value) = item.value() {
+                            if let Err(message) = overrides.archive_cover.apply_value(value) {
+                                let error = item_error_with_snippet(item, manifest_path, &message);
+                                build.error(&error);
+                            }
+                        }
+                    }
+
+                    break 'archive_cover;
+                }
+            }
+
+            let message = "archive_cover needs to be provided either as a field with a value (e.g. 'archive_cover: 800') or as a field with items, e.g.:\n\narchive_cover:\n- 1500\n- original\n\n(The available options are a maximum size in pixels, 'largest' and 'original')";
+            let error = element_error_with_snippet(element, manifest_path, message);
+            build.error(&error);
+        }
+        "archive_layout" => 'archive_layout: {
+            if let Ok(field) = element.as_field() {
+                if let Ok(result) = field.value() {
+                    if let Some(value) = result {
+                        let mut archive_layout = ArchiveLayout::FLAT;
+
+                        match value {
+                            "disc_folders" => archive_layout.disc_folders = true,
+                            "flat" => (),
+                            "release_folder" => archive_layout.release_folder = true,
+                            "track_folders" => archive_layout.track_folders = true,
+                            _ => {
+                                let message = format!("The value '{value}' is not supported (allowed are: 'disc_folders', 'flat', 'release_folder' or 'track_folders')");
+                                let error = element_error_with_snippet(element, manifest_path, &message);
+                                build.error(&error);
+                                break 'archive_layout;
+                            }
+                        }
+
+                        overrides.archive_layout = archive_layout;
+                    }
+
+                    break 'archive_layout;
+                } else if let Ok(items) = field.items() {
+                    overrides.archive_layout = ArchiveLayout::FLAT;
+
+                    for item in items.iter() {
+                        match item.value() {
+                            Some("disc_folders") => overrides.archive_layout.disc_folders = true,
+                            Some("flat") => overrides.archive_layout = ArchiveLayout::FLAT,
+                            Some("release_folder") => overrides.archive_layout.release_folder = true,
+                            Some("track_folders") => overrides.archive_layout.track_folders = true,
+                            Some(other) => {
+                                let message = format!("The value '{other}' is not supported (allowed are: 'disc_folders', 'flat', 'release_folder' or 'track_folders')");
+                                let error = item_error_with_snippet(item, manifest_path, &message);
+                                build.error(&error);
+                            }
+                            None => ()
+                        }
+                    }
+
+                    break 'archive_layout;
+                }
+            }
+
+            let message = "archive_layout needs to be provided either as a field with a value (e.g. 'archive_layout: release_folder') or as a field with items, e.g.:\n\narchive_layout:\n- disc_folders\n- release_folder\n- track_folders\n\n(The available options are 'disc_folders', 'flat', 'release_folder' and 'track_folders')";
+            let error = element_error_with_snippet(element, manifest_path, message);
+            build.error(&error);
+        }
+        "cover_aspect_ratio" => 'cover_aspect_ratio: {
+            if let Ok(field) = element.as_field() {
+                if let Ok(result) = field.value() {
+                    if let Some(value) = result {
+                        match value {
+                            "original" => overrides.cover_aspect_ratio = CoverAspectRatio::Original,
+                            "square" => overrides.cover_aspect_ratio = CoverAspectRatio::Square,
+                            _ => {
+                                let message = format!("The value '{value}' is not supported (allowed are: 'original' or 'square')");
+                                let error = element_error_with_snippet(element, manifest_path, &message);
+                                build.error(&error);
+                            }
+                        }
+                    }
+
+                    break 'cover_aspect_ratio;
+                }
+            }
+
+            let message = "cover_aspect_ratio needs to be provided as a field with the value 'original' or 'square', e.g.: 'cover_aspect_ratio: original'";
+            let error = element_error_with_snippet(element, manifest_path, message);
+            build.error(&error);
+        }
+        "cover_svg" => 'cover_svg: {
+            if let Ok(field) = element.as_field() {
+                if let Ok(result) = field.value() {
+                    if let Some(value) = result {
+                        match value {
+                            "original" => overrides.cover_svg_original = true,
+                            "rasterized" => overrides.cover_svg_original = false,
+                            _ => {
+                                let message = format!("The value '{value}' is not supported (allowed are: 'original' or 'rasterized')");
+                                let error = element_error_with_snippet(element, manifest_path, &message);
+                                build.error(&error);
+                            }
+                        }
+                    }
+
+                    break 'cover_svg;
+                }
+            }
+
+            let message = "cover_svg needs to be provided as a field with the value 'original' or 'rasterized', e.g.: 'cover_svg: original'";
+            let error = element_error_with_snippet(element, manifest_path, message);
+            build.error(&error);
+        }
         // TODO: 'downloads' was deprecated in favor of release_download_access in ~february 2025, eventually remove this temporary fallback handling in a future release
         "downloads" => 'downloads: {
             let message = "The 'downloads' option was split into two: 'release_download_access' and 'track_download_access'. Depending on whether you have release and/or track downloads configured you should now use one or both of the two - in terms of the value you can provide for each of them it still works exactly the same as 'downloads' though. For the time being 'downloads' will still work, but it won't forever - make sure to update this at some point.";
             let warning = element_error_with_snippet(element, manifest_path, message);
-            build.warning(&warning);
+            build.warning(WarningCategory::DeprecatedOption, &warning);
 
             if let Ok(field) = element.as_field() {
                 if let Ok(result) = field.value() {
@@ -97,7 +237,7 @@ pub fn read_artist_catalog_release_option(
         "extra_downloads" => 'extra_downloads: {
             let message = "The 'extra_downloads' option is now called 'release_extras' - it works exactly the same though. For the time being 'extra_downloads' will still work, but it won't forever - make sure to update at some point. Note that in addition to 'release_extras', there is now also a 'track_extras' option!";
             let warning = element_error_with_snippet(element, manifest_path, message);
-            build.warning(&warning);
+            build.warning(WarningCategory::DeprecatedOption, &warning);
 
             if let Ok(field) = element.as_field() {
                 if let Ok(result) = field.value() {
@@ -140,11 +280,33 @@ pub fn read_artist_catalog_release_option(
             let error = element_error_with_snippet(element, manifest_path, message);
             build.error(&error);
         }
+        "podcast_categories" => 'podcast_categories: {
+            if let Ok(field) = element.as_field() {
+                if let Ok(result) = field.value() {
+                    if let Some(value) = result {
+                        overrides.podcast_categories = vec![value.to_string()];
+                    }
+
+                    break 'podcast_categories;
+                } else if let Ok(items) = field.items() {
+                    overrides.podcast_categories = items
+                        .iter()
+                        .filter_map(|item| item.value().map(|value| value.to_string()))
+                        .collect();
+
+                    break 'podcast_categories;
+                }
+            }
+
+            let message = "podcast_categories needs to be provided either as a field with a value (e.g. 'podcast_categories: Music') or as a field with items, e.g.:\n\npodcast_categories:\n- Music\n- Arts";
+            let error = element_error_with_snippet(element, manifest_path, message);
+            build.error(&error);
+        }
         // TODO: 'price' was deprecated in favor of release_price in ~february 2025, eventually remove this temporary fallback handling in a future release
         "price" => 'price: {
             let message = "The 'price' option is now called 'release_price' - it works exactly the same though. For the time being 'price' will still work, but it won't forever - make sure to update at some point. Note that in addition to 'release_price', there is now also a 'track_price' option!";
             let warning = element_error_with_snippet(element, manifest_path, message);
-            build.warning(&warning);
+            build.warning(WarningCategory::DeprecatedOption, &warning);
 
             if let Ok(field) = element.as_field() {
                 if let Ok(result) = field.value() {
@@ -212,7 +374,7 @@ pub fn read_artist_catalog_release_option(
                         match DownloadFormat::from_manifest_key(value) {
                             Some(format) => overrides.release_downloads = vec![format],
                             None => {
-                                let message = format!("The download format '{value}' is not supported (All available formats: 'aac', 'aiff', 'alac', 'flac', 'mp3', 'ogg_vorbis', 'opus', 'opus_48', 'opus_96', 'opus_128' and 'wav')");
+                                let message = format!("The download format '{value}' is not supported (All available formats: 'aac', 'aiff', 'alac', 'flac', 'mp3', 'ogg_vorbis', 'opus', 'opus_48', 'opus_96', 'opus_128', 'original' and 'wav')");
                                 let error = element_error_with_snippet(element, manifest_path, &message);
                                 build.error(&error);
                             }
@@ -229,7 +391,7 @@ pub fn read_artist_catalog_release_option(
                                     match DownloadFormat::from_manifest_key(value) {
                                         Some(format) => Some(format),
                                         None => {
-                                            let message = format!("The download format '{value}' is not supported (All available formats: 'aac', 'aiff', 'alac', 'flac', 'mp3', 'ogg_vorbis', 'opus', 'opus_48', 'opus_96', 'opus_128' and 'wav')");
+                                            let message = format!("The download format '{value}' is not supported (All available formats: 'aac', 'aiff', 'alac', 'flac', 'mp3', 'ogg_vorbis', 'opus', 'opus_48', 'opus_96', 'opus_128', 'original' and 'wav')");
                                             let error = item_error_with_snippet(item, manifest_path, &message);
                                             build.error(&error);
                                             None
@@ -245,7 +407,7 @@ pub fn read_artist_catalog_release_option(
                 }
             }
 
-            let message = "release_downloads needs to be provided either as a field with a value (e.g. 'release_downloads: mp3') or as a field with items, e.g.:\n\nrelease_downloads:\n- mp3\n- flac\n- opus\n\n(All available formats: 'aac', 'aiff', 'alac', 'flac', 'mp3', 'ogg_vorbis', 'opus', 'opus_48', 'opus_96', 'opus_128' and 'wav')";
+            let message = "release_downloads needs to be provided either as a field with a value (e.g. 'release_downloads: mp3') or as a field with items, e.g.:\n\nrelease_downloads:\n- mp3\n- flac\n- opus\n\n(All available formats: 'aac', 'aiff', 'alac', 'flac', 'mp3', 'ogg_vorbis', 'opus', 'opus_48', 'opus_96', 'opus_128', 'original' and 'wav')";
             let error = element_error_with_snippet(element, manifest_path, message);
             build.error(&error);
         }
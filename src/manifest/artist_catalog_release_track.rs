@@ -16,12 +16,16 @@ use crate::{
     Link,
     LocalOptions,
     Overrides,
+    PaymentPointer,
     Permalink,
     Price,
+    ShareButton,
     StreamingQuality,
     TagAgenda,
+    Theme,
     ThemeBase,
-    ThemeFont
+    ThemeFont,
+    WarningCategory
 };
 use crate::markdown;
 use crate::util::html_escape_outside_attribute;
@@ -31,6 +35,7 @@ use super::{
     attribute_error_with_snippet,
     element_error_with_snippet,
     item_error_with_snippet,
+    manifest_text,
     read_obsolete_theme_attribute
 };
 
@@ -43,6 +48,8 @@ pub const ARTIST_CATALOG_RELEASE_TRACK_OPTIONS: &[&str] = &[
     "more",
     "more_label",
     "payment_info",
+    "payment_pointer",
+    "share_buttons",
     "speed_controls",
     "streaming_quality",
     "synopsis",
@@ -54,7 +61,8 @@ pub const ARTIST_CATALOG_RELEASE_TRACK_OPTIONS: &[&str] = &[
     "track_downloads",
     "track_extras",
     "track_price",
-    "unlock_info"
+    "unlock_info",
+    "volume_normalization"
 ];
 
 /// Try to read a single option from the passed element. Processes
@@ -306,6 +314,10 @@ pub fn read_artist_catalog_release_track_option(
         "more" => {
             if let Ok(embed) = element.as_embed() {
                 if let Some(value) = embed.value() {
+                    if build.check_texts {
+                        build.manifest_texts.push(manifest_text(element, manifest_path, true, value));
+                    }
+
                     local_options.more = Some(markdown::to_html_and_stripped(&build.base_url, value));
                 } else {
                     local_options.more = None;
@@ -342,6 +354,133 @@ pub fn read_artist_catalog_release_track_option(
                 build.error(&error);
             }
         }
+        "payment_pointer" => 'payment_pointer: {
+            if let Ok(field) = element.as_field() {
+                if let Ok(result) = field.value() {
+                    if let Some(value) = result {
+                        match PaymentPointer::parse(value, false) {
+                            Ok(payment_pointer) => overrides.payment_pointer = Some(payment_pointer),
+                            Err(err) => {
+                                let error = element_error_with_snippet(element, manifest_path, &err);
+                                build.error(&error);
+                            }
+                        }
+                    } else {
+                        overrides.payment_pointer = None;
+                    }
+
+                    break 'payment_pointer;
+                } else if let Ok(attributes) = field.attributes() {
+                    let mut address = None;
+                    let mut unlock_downloads = false;
+
+                    for attribute in attributes {
+                        match attribute.key() {
+                            "address" => {
+                                if let Some(value) = attribute.value() {
+                                    address = Some(value);
+                                }
+                            }
+                            "unlock_downloads" => {
+                                if let Some(value) = attribute.value() {
+                                    match value {
+                                        "disabled" => unlock_downloads = false,
+                                        "enabled" => unlock_downloads = true,
+                                        _ => {
+                                            let message = "This unlock_downloads setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                            let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                            build.error(&error);
+                                        }
+                                    }
+                                }
+                            }
+                            _ => {
+                                let message = "The key/name of this attribute was not recognized, only 'address' and 'unlock_downloads' are recognized inside a payment_pointer field";
+                                let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                build.error(&error);
+                            }
+                        }
+                    }
+
+                    match address {
+                        Some(address) => match PaymentPointer::parse(address, unlock_downloads) {
+                            Ok(payment_pointer) => overrides.payment_pointer = Some(payment_pointer),
+                            Err(err) => {
+                                let error = element_error_with_snippet(element, manifest_path, &err);
+                                build.error(&error);
+                            }
+                        }
+                        None => {
+                            let message = "payment_pointer needs an address attribute, e.g.: 'address = $wallet.example.com/alice'";
+                            let error = element_error_with_snippet(element, manifest_path, message);
+                            build.error(&error);
+                        }
+                    }
+
+                    break 'payment_pointer;
+                }
+            }
+
+            let message = indoc!("
+                payment_pointer needs to be provided either as a field with a value, or as a field with attributes, e.g.:
+
+                payment_pointer: $wallet.example.com/alice
+
+                payment_pointer:
+                address = $wallet.example.com/alice
+                unlock_downloads = enabled
+            ");
+            let error = element_error_with_snippet(element, manifest_path, message);
+            build.error(&error);
+        }
+        "share_buttons" => 'share_buttons: {
+            if let Ok(field) = element.as_field() {
+                if let Ok(result) = field.value() {
+                    if let Some(value) = result {
+                        match value {
+                            "disabled" => overrides.share_buttons = Vec::new(),
+                            "enabled" => overrides.share_buttons = ShareButton::ALL.to_vec(),
+                            _ => match ShareButton::from_manifest_key(value) {
+                                Some(share_button) => overrides.share_buttons = vec![share_button],
+                                None => {
+                                    let message = "This share_buttons setting was not recognized (supported values are 'disabled', 'enabled', 'email', 'mastodon', 'telegram' and 'whatsapp')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+                    }
+
+                    break 'share_buttons;
+                } else if let Ok(items) = field.items() {
+                    overrides.share_buttons = items
+                        .iter()
+                        .filter_map(|item| {
+                            match item.value() {
+                                Some(value) => {
+                                    match ShareButton::from_manifest_key(value) {
+                                        Some(share_button) => Some(share_button),
+                                        None => {
+                                            let message = format!("The share button '{value}' is not supported (available are 'email', 'mastodon', 'telegram' and 'whatsapp')");
+                                            let error = item_error_with_snippet(item, manifest_path, &message);
+                                            build.error(&error);
+                                            None
+                                        }
+                                    }
+                                }
+                                None => None
+                            }
+                        })
+                        .collect();
+
+                    break 'share_buttons;
+                }
+            }
+
+            let message = "share_buttons needs to be provided either as a field with a value (e.g. 'share_buttons: enabled') or as a field with items, e.g.:\n\nshare_buttons:\n- email\n- mastodon\n\n(Supported values are 'disabled', 'enabled', 'email', 'mastodon', 'telegram' and 'whatsapp')";
+            let error = element_error_with_snippet(element, manifest_path, message);
+            build.error(&error);
+        }
         "speed_controls" => 'speed_controls: {
             if let Ok(field) = element.as_field() {
                 if let Ok(result) = field.value() {
@@ -391,6 +530,10 @@ pub fn read_artist_catalog_release_track_option(
                 if let Some(value) = embed.value() {
                     let synopsis_chars = value.chars().count();
 
+                    if build.check_texts {
+                        build.manifest_texts.push(manifest_text(element, manifest_path, false, value));
+                    }
+
                     if synopsis_chars <= MAX_SYNOPSIS_CHARS {
                         let synopsis_escaped = html_escape_outside_attribute(value);
                         local_options.synopsis = Some(synopsis_escaped);
@@ -446,7 +589,42 @@ pub fn read_artist_catalog_release_track_option(
         }
         "theme" => 'theme: {
             if let Ok(field) = element.as_field() {
-                if let Ok(attributes) = field.attributes() {
+                if let Ok(result) = field.value() {
+                    // The theme is inherited as a whole by default, with
+                    // 'inherit except ...' individual attributes can be
+                    // reset to their defaults instead.
+                    if let Some(value) = result {
+                        let reset_keys = value
+                            .strip_prefix("inherit")
+                            .map(|remainder| remainder.trim_start())
+                            .and_then(|remainder| match remainder {
+                                "" => Some(""),
+                                _ => remainder
+                                    .strip_prefix("except")
+                                    .filter(|keys| keys.starts_with(char::is_whitespace))
+                            });
+
+                        match reset_keys {
+                            Some(keys) => {
+                                for key in keys.split(|character: char| character == ',' || character.is_whitespace()) {
+                                    if !key.is_empty() && !overrides.theme.reset_attribute(key) {
+                                        let supported = Theme::RESETTABLE_ATTRIBUTES.map(|key| format!("'{key}'")).join(", ");
+                                        let message = format!("The theme attribute '{key}' is not recognized (supported attributes are {supported})");
+                                        let error = element_error_with_snippet(element, manifest_path, &message);
+                                        build.error(&error);
+                                    }
+                                }
+                            }
+                            None => {
+                                let message = format!("Unsupported theme setting value '{value}' (supported is 'inherit', optionally followed by 'except' and a list of attributes, e.g. 'theme: inherit except background_image, base_hue')");
+                                let error = element_error_with_snippet(element, manifest_path, &message);
+                                build.error(&error);
+                            }
+                        }
+                    }
+
+                    break 'theme;
+                } else if let Ok(attributes) = field.attributes() {
                     for attribute in attributes {
                         match attribute.key() {
                             _ if read_obsolete_theme_attribute(build, attribute, manifest_path) => (),
@@ -565,7 +743,7 @@ pub fn read_artist_catalog_release_track_option(
                             "custom_font" => {
                                 let message = "The 'custom_font' option has been superseded by more flexible and generic functionality provided through the 'site_assets' and 'site_metadata' options. For the time being 'custom_font' will still work, but it won't forever - make sure to update at some point.";
                                 let warning = element_error_with_snippet(element, manifest_path, message);
-                                build.warning(&warning);
+                                build.warning(WarningCategory::DeprecatedOption, &warning);
 
                                 if let Some(Ok(relative_path)) = attribute.optional_value::<String>() {
                                     let absolute_path = manifest_path.parent().unwrap().join(&relative_path);
@@ -597,6 +775,31 @@ pub fn read_artist_catalog_release_track_option(
                                     }
                                 }
                             }
+                            "dyslexia_font" => {
+                                if let Some(value) = attribute.value() {
+                                    match value {
+                                        "disabled" => overrides.theme.dyslexia_font = false,
+                                        "enabled" => overrides.theme.dyslexia_font = true,
+                                        _ => {
+                                            let message = format!("Ignoring unsupported dyslexia_font setting value '{value}' (supported values are 'disabled' and 'enabled')");
+                                            let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                                            build.error(&error);
+                                        }
+                                    }
+                                }
+                            }
+                            "font_scale" => {
+                                if let Some(value) = attribute.value() {
+                                    match value.parse::<f32>().ok().filter(|multiplier| (0.5..=2.0).contains(multiplier)) {
+                                        Some(multiplier) => overrides.theme.font_scale = (multiplier * 100.0).round() as u16,
+                                        None => {
+                                            let message = format!("Unsupported value '{value}' for 'font_scale' (accepts a multiplier in the range 0.5-2.0, e.g. 1.25)");
+                                            let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                                            build.error(&error);
+                                        }
+                                    }
+                                }
+                            }
                             "round_corners" => {
                                 if let Some(value) = attribute.value() {
                                     match value {
@@ -619,7 +822,7 @@ pub fn read_artist_catalog_release_track_option(
                                         _ => {
                                             let message = "The functionality for using 'system_font' to specify arbitrary system fonts has been superseded by more flexible and generic functionality provided through the 'site_assets' and 'site_metadata' options. For the time being 'system_font' will still work the way you're using it, but it won't forever - make sure to update at some point.";
                                             let warning = element_error_with_snippet(element, manifest_path, message);
-                                            build.warning(&warning);
+                                            build.warning(WarningCategory::DeprecatedOption, &warning);
 
                                             ThemeFont::System(value.to_string())
                                         }
@@ -650,7 +853,7 @@ pub fn read_artist_catalog_release_track_option(
                                 }
                             }
                             other => {
-                                let message = format!("The attribute '{other}' is not recognized here (supported attributes are 'accent_brightening', 'accent_chroma', 'accent_hue', 'background_alpha', 'background_image', 'base', 'base_chroma', 'base_hue', 'cover_generator', 'dynamic_range', 'round_corners', 'system_font' and 'waveforms')");
+                                let message = format!("The attribute '{other}' is not recognized here (supported attributes are 'accent_brightening', 'accent_chroma', 'accent_hue', 'background_alpha', 'background_image', 'base', 'base_chroma', 'base_hue', 'cover_generator', 'dynamic_range', 'dyslexia_font', 'font_scale', 'round_corners', 'system_font' and 'waveforms')");
                                 let error = attribute_error_with_snippet(attribute, manifest_path, &message);
                                 build.error(&error);
                             }
@@ -661,7 +864,7 @@ pub fn read_artist_catalog_release_track_option(
                 }
             }
 
-            let message = "theme needs to be provided as a field with attributes, e.g.:\n\ntheme:\nbase = light\nwaveforms = absolute";
+            let message = "theme needs to be provided either as a field with attributes, e.g.:\n\ntheme:\nbase = light\nwaveforms = absolute\n\n... or as a field with a value that selectively resets inherited attributes, e.g.: 'theme: inherit except background_image'";
             let error = element_error_with_snippet(element, manifest_path, message);
             build.error(&error);
         }
@@ -741,7 +944,7 @@ pub fn read_artist_catalog_release_track_option(
                         match DownloadFormat::from_manifest_key(value) {
                             Some(format) => overrides.track_downloads = vec![format],
                             None => {
-                                let message = format!("The download format '{value}' is not supported (All available formats: 'aac', 'aiff', 'alac', 'flac', 'mp3', 'ogg_vorbis', 'opus', 'opus_48', 'opus_96', 'opus_128' and 'wav')");
+                                let message = format!("The download format '{value}' is not supported (All available formats: 'aac', 'aiff', 'alac', 'flac', 'mp3', 'ogg_vorbis', 'opus', 'opus_48', 'opus_96', 'opus_128', 'original' and 'wav')");
                                 let error = element_error_with_snippet(element, manifest_path, &message);
                                 build.error(&error);
                             }
@@ -758,7 +961,7 @@ pub fn read_artist_catalog_release_track_option(
                                     match DownloadFormat::from_manifest_key(value) {
                                         Some(format) => Some(format),
                                         None => {
-                                            let message = format!("The download format '{value}' is not supported (All available formats: 'aac', 'aiff', 'alac', 'flac', 'mp3', 'ogg_vorbis', 'opus', 'opus_48', 'opus_96', 'opus_128' and 'wav')");
+                                            let message = format!("The download format '{value}' is not supported (All available formats: 'aac', 'aiff', 'alac', 'flac', 'mp3', 'ogg_vorbis', 'opus', 'opus_48', 'opus_96', 'opus_128', 'original' and 'wav')");
                                             let error = item_error_with_snippet(item, manifest_path, &message);
                                             build.error(&error);
                                             None
@@ -774,7 +977,7 @@ pub fn read_artist_catalog_release_track_option(
                 }
             }
 
-            let message = "track_downloads needs to be provided either as a field with a value (e.g. 'track_downloads: mp3') or as a field with items, e.g.:\n\ntrack_downloads:\n- mp3\n- flac\n- opus\n\n(All available formats: 'aac', 'aiff', 'alac', 'flac', 'mp3', 'ogg_vorbis', 'opus', 'opus_48', 'opus_96', 'opus_128' and 'wav')";
+            let message = "track_downloads needs to be provided either as a field with a value (e.g. 'track_downloads: mp3') or as a field with items, e.g.:\n\ntrack_downloads:\n- mp3\n- flac\n- opus\n\n(All available formats: 'aac', 'aiff', 'alac', 'flac', 'mp3', 'ogg_vorbis', 'opus', 'opus_48', 'opus_96', 'opus_128', 'original' and 'wav')";
             let error = element_error_with_snippet(element, manifest_path, message);
             build.error(&error);
         }
@@ -834,6 +1037,29 @@ pub fn read_artist_catalog_release_track_option(
                 build.error(&error);
             }
         }
+        "volume_normalization" => 'volume_normalization: {
+            if let Ok(field) = element.as_field() {
+                if let Ok(result) = field.value() {
+                    if let Some(value) = result {
+                        match value {
+                            "disabled" => overrides.volume_normalization = false,
+                            "enabled" => overrides.volume_normalization = true,
+                            _ => {
+                                let message = format!("The value '{value}' is not supported (allowed are: 'enabled' or 'disabled'");
+                                let error = element_error_with_snippet(element, manifest_path, &message);
+                                build.error(&error);
+                            }
+                        }
+                    }
+
+                    break 'volume_normalization;
+                }
+            }
+
+            let message = "volume_normalization needs to be provided as a field with the value 'enabled' or 'disabled' (e.g. 'volume_normalization: enabled')";
+            let error = element_error_with_snippet(element, manifest_path, message);
+            build.error(&error);
+        }
         _ => return false
     }
 
@@ -0,0 +1,131 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::path::Path;
+
+use enolib::SectionElement;
+use indoc::indoc;
+
+use crate::{Build, LocalOptions, SiteAsset, SiteMetadata};
+
+use super::element_error_with_snippet;
+
+pub const ARTIST_RELEASE_TRACK_OPTIONS: &[&str] = &["page_assets", "page_metadata", "search_engines"];
+
+/// Try to read a single option from the passed element. Processes
+/// options that are present in artist, release and track manifests.
+pub fn read_artist_release_track_option(
+    build: &mut Build,
+    dir: &Path,
+    element: &Box<dyn SectionElement>,
+    local_options: &mut LocalOptions,
+    manifest_path: &Path
+) -> bool {
+    match element.key() {
+        "page_assets" => 'page_assets: {
+            if let Ok(field) = element.as_field() {
+                if let Ok(result) = field.value() {
+                    local_options.page_assets.clear();
+
+                    if let Some(value) = result {
+                        let absolute_path = dir.join(value);
+                        if absolute_path.exists() {
+                            let page_asset = SiteAsset::new(absolute_path);
+                            local_options.page_assets.push(page_asset);
+                        } else {
+                            let message = format!("The referenced file {} was not found", absolute_path.display());
+                            let error = element_error_with_snippet(element, manifest_path, &message);
+                            build.error(&error);
+                        }
+                    }
+
+                    break 'page_assets;
+                } else if let Ok(items) = field.items() {
+                    local_options.page_assets.clear();
+
+                    for item in items {
+                        if let Some(value) = item.value() {
+                            let absolute_path = dir.join(value);
+                            if absolute_path.exists() {
+                                let page_asset = SiteAsset::new(absolute_path);
+                                local_options.page_assets.push(page_asset);
+                            } else {
+                                let message = format!("The referenced file {} was not found", absolute_path.display());
+                                let error = element_error_with_snippet(element, manifest_path, &message);
+                                build.error(&error);
+                            }
+                        }
+                    }
+
+                    break 'page_assets;
+                }
+            }
+
+            let message = indoc!(r#"
+                page_assets needs to be provided as a field with a value or a field with items, for instance:
+
+                page_assets: visualizer.js
+
+                page_assets:
+                - visualizer.css
+                - visualizer.js
+            "#);
+            let error = element_error_with_snippet(element, manifest_path, message);
+            build.error(&error);
+        }
+        "page_metadata" => 'page_metadata: {
+            if let Ok(embed) = element.as_embed() {
+                if let Some(value) = embed.value() {
+                    match SiteMetadata::parse(value) {
+                        Ok(page_metadata) => {
+                            local_options.page_metadata = Some(page_metadata);
+                        }
+                        Err(err) => {
+                            let message = format!("The given page_metadata has (an) issue(s): {err}");
+                            let error = element_error_with_snippet(element, manifest_path, &message);
+                            build.error(&error);
+                        }
+                    }
+                }
+
+                break 'page_metadata;
+            }
+
+            let message = indoc!(r#"
+                page_metadata needs to be provided as an embed, for instance:
+
+                -- page_metadata
+                <script defer src="{{visualizer.js}}"></script>
+                -- page_metadata
+            "#);
+            let error = element_error_with_snippet(element, manifest_path, message);
+            build.error(&error);
+        }
+        "search_engines" => 'search_engines: {
+            if let Ok(field) = element.as_field() {
+                if let Ok(result) = field.value() {
+                    if let Some(value) = result {
+                        match value {
+                            "index" => local_options.noindex = false,
+                            "noindex" => local_options.noindex = true,
+                            _ => {
+                                let message = format!("The value '{value}' is not recognized for the search_engines option, allowed values are 'index' and 'noindex'");
+                                let error = element_error_with_snippet(element, manifest_path, &message);
+                                build.error(&error);
+                            }
+                        }
+                    }
+
+                    break 'search_engines;
+                }
+            }
+
+            let message = "search_engines needs to be provided as a field with a value, e.g.: 'search_engines: noindex'";
+            let error = element_error_with_snippet(element, manifest_path, message);
+            build.error(&error);
+        }
+        _ => return false
+    }
+
+    true
+}
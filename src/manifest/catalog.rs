@@ -2,11 +2,14 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use std::fs;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
+use enolib::SectionElement;
 use indoc::indoc;
+use url::Url;
 
 use crate::{
+    AiScraping,
     Build,
     Cache,
     CacheOptimization,
@@ -14,12 +17,28 @@ use crate::{
     DescribedImage,
     Favicon,
     Feeds,
+    FundingChannel,
+    Hero,
+    HeroButton,
+    HeroImage,
+    HeroTarget,
+    IndexView,
+    LanguageMirror,
     LocalOptions,
     Locale,
+    NotifyWebhook,
     Overrides,
+    Permalink,
+    PermalinkConflicts,
     SiteAsset,
     SiteMetadata,
-    SiteUrl
+    SiteUrl,
+    StreamingLimits,
+    WarningCategory,
+    WarningLevel,
+    Webfinger,
+    WebhookEvent,
+    YearArchives
 };
 use crate::util::uid;
 
@@ -43,23 +62,58 @@ use super::{
 /// as well, are tracked here. This has to correspond 1:1 with the keys that
 /// are matched against in `read_catalog_manifest`.
 const CATALOG_OPTIONS: &[&str] = &[
+    "ai_scraping",
+    "asset_manifest",
     "base_url",
     "cache_optimization",
+    "critical_css",
+    "deploy_destination",
+    "discover",
+    "extra_ignore",
+    "extra_size_warning",
     "faircamp_signature",
     "favicon",
     "feature_support_artists",
     "feeds",
     "freeze_download_urls",
+    "funding",
+    "funding_json",
+    "gemtext",
+    "hero",
     "home_image",
+    "hover_previews",
+    "image_description_suggestions",
+    "image_metadata",
+    "include_catalogs",
+    "index_views",
+    "json_api",
     "label_mode",
     "language",
+    "language_mirrors",
+    "languages",
+    "latest_release_widget",
+    "legacy_support",
     "m3u",
+    "minify",
+    "notify_webhook",
     "opengraph",
+    "permalink_conflicts",
+    "permalink_terms",
+    "player_config",
+    "range_check",
+    "release_filters",
+    "releases_per_page",
     "rotate_download_urls",
+    "search",
+    "security_txt",
     "show_support_artists",
     "site_assets",
     "site_metadata",
-    "title"
+    "streaming_limits",
+    "title",
+    "warnings",
+    "webfinger",
+    "year_archives"
 ];
 
 pub fn read_catalog_manifest(
@@ -80,6 +134,8 @@ pub fn read_catalog_manifest(
         }
     };
 
+    build.register_manifest(manifest_path, &content);
+
     let document = match enolib::parse_with_printer(&content, platform_printer()) {
         Ok(document) => document,
         Err(err) => {
@@ -90,9 +146,105 @@ pub fn read_catalog_manifest(
         }
     };
 
+    // The warnings option is read before all others, so that it also applies
+    // to warnings emitted while reading the rest of the manifest (e.g. for
+    // deprecated options).
+    for element in document.elements() {
+        if element.key() == "warnings" {
+            read_warnings_option(build, element, manifest_path);
+        }
+    }
+
     for element in document.elements() {
         match element.key() {
             _ if read_obsolete_option(build, element, manifest_path) => (),
+            "ai_scraping" => 'ai_scraping: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "allowed" => catalog.ai_scraping = AiScraping::ALLOWED,
+                                "disallowed" => catalog.ai_scraping = AiScraping::DISALLOWED,
+                                _ => {
+                                    let message = "This ai_scraping setting was not recognized (supported values are 'allowed' and 'disallowed', or use the attributes 'audio', 'images' and 'pages' to choose for each kind of content)";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'ai_scraping;
+                    } else if let Ok(attributes) = field.attributes() {
+                        for attribute in attributes {
+                            let Some(value) = attribute.value() else { continue };
+
+                            let allowed = match value {
+                                "allowed" => true,
+                                "disallowed" => false,
+                                _ => {
+                                    let message = "The value of this attribute needs to be either 'allowed' or 'disallowed'";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                    continue;
+                                }
+                            };
+
+                            match attribute.key() {
+                                "audio" => catalog.ai_scraping.audio = allowed,
+                                "images" => catalog.ai_scraping.images = allowed,
+                                "pages" => catalog.ai_scraping.pages = allowed,
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'audio', 'images' and 'pages' are recognized inside an ai_scraping field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'ai_scraping;
+                    }
+                }
+
+                let message = indoc!("
+                    ai_scraping needs to be provided as a field with the value 'allowed' or 'disallowed', or with attributes to choose for each kind of content, e.g.:
+
+                    ai_scraping: disallowed
+
+                    ai_scraping:
+                    audio = disallowed
+                    images = disallowed
+                    pages = allowed
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "asset_manifest" => 'asset_manifest: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.asset_manifest = false;
+                                }
+                                "enabled" => {
+                                    catalog.asset_manifest = true;
+                                }
+                                _ => {
+                                    let message = "This asset_manifest setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'asset_manifest;
+                    }
+                }
+
+                let message = "The asset_manifest option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'asset_manifest: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
             "base_url" => 'base_url: {
                 if let Ok(field) = element.as_field() {
                     if let Ok(result) = field.value() {
@@ -143,7 +295,7 @@ pub fn read_catalog_manifest(
             "disable_feed" => {
                 let message = "The 'disable_feed' option must now be specified as 'feeds: disabled'. For the time being 'disable_feed' will still work, but it won't forever - make sure to update at some point.";
                 let warning = element_error_with_snippet(element, manifest_path, message);
-                build.warning(&warning);
+                build.warning(WarningCategory::DeprecatedOption, &warning);
 
                 if element.is_flag() {
                     catalog.feeds = Feeds::DISABLED;
@@ -153,6 +305,138 @@ pub fn read_catalog_manifest(
                     build.error(&error);
                 }
             }
+            "critical_css" => 'critical_css: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.critical_css = false;
+                                }
+                                "enabled" => {
+                                    catalog.critical_css = true;
+                                }
+                                _ => {
+                                    let message = "This critical_css setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'critical_css;
+                    }
+                }
+
+                let message = "The critical_css option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'critical_css: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "deploy_destination" => 'deploy_destination: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            if value.starts_with("sftp://") && !Url::parse(value).is_ok_and(|url| url.host_str().is_some()) {
+                                let message = format!("The sftp url '{value}' is not valid, it should look like this: 'deploy_destination: sftp://user@example.com/var/www/music'");
+                                let error = element_error_with_snippet(element, manifest_path, &message);
+                                build.error(&error);
+                            } else if build.deploy_destination.is_none() {
+                                // --deploy-destination takes precedence
+                                build.deploy_destination = Some(value.to_string());
+                            }
+                        }
+
+                        break 'deploy_destination;
+                    }
+                }
+
+                let message = "deploy_destination needs to be provided as a field with a value, e.g.: 'deploy_destination: user@example.com:/var/www/music/' (rsync) or 'deploy_destination: sftp://user@example.com/var/www/music' (sftp)";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "discover" => 'discover: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.discover = false;
+                                }
+                                "enabled" => {
+                                    catalog.discover = true;
+                                }
+                                _ => {
+                                    let message = "This discover setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'discover;
+                    }
+                }
+
+                let message = "The discover option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'discover: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "extra_ignore" => 'extra_ignore: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(items) = field.items() {
+                        build.extra_ignore_patterns.clear();
+
+                        for item in items {
+                            let Some(value) = item.value() else { continue };
+
+                            if value.contains('/') || value.contains('\\') {
+                                let message = "extra_ignore patterns are matched against filenames only, they can not contain directory separators";
+                                let error = item_error_with_snippet(item, manifest_path, message);
+                                build.error(&error);
+                            } else {
+                                build.extra_ignore_patterns.push(value.to_string());
+                            }
+                        }
+
+                        break 'extra_ignore;
+                    }
+                }
+
+                let message = indoc!("
+                    extra_ignore needs to be provided as a field with items (filename patterns, in which * matches any number of characters and ? a single character), e.g.:
+
+                    extra_ignore:
+                    - *.psd
+                    - Thumbs.db
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "extra_size_warning" => 'extra_size_warning: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => build.extra_size_warning = None,
+                                _ => match value.parse::<u64>() {
+                                    Ok(number) if number > 0 => build.extra_size_warning = Some(number),
+                                    _ => {
+                                        let message = "This extra_size_warning setting was not recognized (supported values are a whole number of megabytes greater than zero, or 'disabled')";
+                                        let error = element_error_with_snippet(element, manifest_path, message);
+                                        build.error(&error);
+                                    }
+                                }
+                            }
+                        }
+
+                        break 'extra_size_warning;
+                    }
+                }
+
+                let message = "The extra_size_warning option needs to be provided as a field with a whole number (megabytes) or 'disabled' as value, e.g.: 'extra_size_warning: 1000'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
             "faircamp_signature" => 'faircamp_signature: {
                 if let Ok(field) = element.as_field() {
                     if let Ok(result) = field.value() {
@@ -321,145 +605,995 @@ pub fn read_catalog_manifest(
                 let error = element_error_with_snippet(element, manifest_path, message);
                 build.error(&error);
             }
-            "home_image" => 'home_image: {
+            "funding" => 'funding: {
                 if let Ok(field) = element.as_field() {
-                    if let Ok(attributes) = field.attributes() {
-                        let mut path_relative_to_catalog = None;
-                        let mut description = None;
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match Url::parse(value) {
+                                Ok(_) => catalog.funding.push(FundingChannel::new(None, value.to_string())),
+                                Err(err) => {
+                                    let message = format!("The url supplied for funding seems to be malformed ({err})");
+                                    let error = element_error_with_snippet(element, manifest_path, &message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'funding;
+                    } else if let Ok(attributes) = field.attributes() {
+                        let mut label = None;
+                        let mut url = None;
 
                         for attribute in attributes {
                             match attribute.key() {
-                                "description" => {
+                                "label" => {
                                     if let Some(value) = attribute.value() {
-                                        description = Some(value.to_string());
+                                        label = Some(value.to_string());
                                     }
                                 }
-                                "file" => {
-                                    // file is a path relative to the manifest
+                                "url" => {
                                     if let Some(value) = attribute.value() {
-                                        let absolute_path = dir.join(value);
-                                        if absolute_path.exists() {
-                                            path_relative_to_catalog = Some(absolute_path.strip_prefix(&build.catalog_dir).unwrap().to_path_buf());
-                                        } else {
-                                            let message = format!("The referenced file was not found ({})", absolute_path.display());
-                                            let error = attribute_error_with_snippet(attribute, manifest_path, &message);
-                                            build.error(&error);
+                                        match Url::parse(value) {
+                                            Ok(_) => url = Some(value.to_string()),
+                                            Err(err) => {
+                                                let message = format!("The url supplied for funding seems to be malformed ({err})");
+                                                let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                                                build.error(&error);
+                                            }
                                         }
-
                                     }
                                 }
                                 _ => {
-                                    let message = "The key/name of this attribute was not recognized, only 'description' and 'file' are recognized inside an home_image field";
-                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    let message = "The key/name of this attribute was not recognized, only 'label' and 'url' are recognized inside a funding field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
                                     build.error(&error);
                                 }
                             }
                         }
 
-                        if let Some(path) = path_relative_to_catalog {
-                            let image = cache.get_or_create_image(build, &path);
-                            catalog.home_image = Some(DescribedImage::new(description, image));
+                        if let Some(url) = url {
+                            catalog.funding.push(FundingChannel::new(label, url));
+                        } else {
+                            let message = indoc!("
+                                The url attribute is required for funding, e.g.:
+
+                                funding:
+                                label = Liberapay
+                                url = https://liberapay.com/example/
+                            ");
+                            let error = element_error_with_snippet(element, manifest_path, message);
+                            build.error(&error);
                         }
 
-                        break 'home_image;
+                        break 'funding;
                     }
                 }
 
                 let message = indoc!("
-                    home_image needs to be provided as a field with attributes, e.g.:
+                    funding needs to be provided either as a field with a value (the url), or as a field with attributes, e.g.:
 
-                    home_image:
-                    description = Alice, looking amused
-                    file = alice.jpg
+                    funding: https://liberapay.com/example/
+
+                    funding:
+                    label = Liberapay
+                    url = https://liberapay.com/example/
                 ");
                 let error = element_error_with_snippet(element, manifest_path, message);
                 build.error(&error);
             }
-            "label_mode" => {
-                if element.is_flag() {
-                    catalog.label_mode = true;
-                } else {
-                    let message = "label_mode needs to be provided as a flag, that is, exactly as 'label_mode' (without colon and without value)";
-                    let error = element_error_with_snippet(element, manifest_path, message);
-                    build.error(&error);
-                }
-            }
-            "language" => 'language: {
+            "funding_json" => 'funding_json: {
                 if let Ok(field) = element.as_field() {
                     if let Ok(result) = field.value() {
                         if let Some(value) = result {
-                            build.locale = Locale::from_code(value);
+                            let absolute_path = dir.join(value);
+                            if absolute_path.exists() {
+                                catalog.well_known.funding_json = Some(absolute_path);
+                            } else {
+                                let message = format!("The referenced file was not found ({})", absolute_path.display());
+                                let error = element_error_with_snippet(element, manifest_path, &message);
+                                build.error(&error);
+                            }
+                        } else {
+                            catalog.well_known.funding_json = None;
                         }
 
-                        break 'language;
+                        break 'funding_json;
                     }
                 }
 
-                let message = "language needs to be provided as a field with a value, e.g.: 'language: fr'";
+                let message = "funding_json needs to be provided as a field with a value, e.g.: 'funding_json: funding.json'";
                 let error = element_error_with_snippet(element, manifest_path, message);
                 build.error(&error);
             }
-            "m3u" => 'm3u: {
+            "gemtext" => 'gemtext: {
                 if let Ok(field) = element.as_field() {
                     if let Ok(result) = field.value() {
                         if let Some(value) = result {
                             match value {
-                                "catalog" => {
-                                    catalog.m3u = true;
-                                    overrides.m3u_enabled = false;
-                                }
                                 "disabled" => {
-                                    catalog.m3u = false;
-                                    overrides.m3u_enabled = false;
+                                    catalog.gemtext = false;
                                 }
                                 "enabled" => {
-                                    catalog.m3u = true;
-                                    overrides.m3u_enabled = true;
-                                }
-                                "releases" => {
-                                    catalog.m3u = false;
-                                    overrides.m3u_enabled = true;
+                                    catalog.gemtext = true;
                                 }
                                 _ => {
-                                    let message = "This m3u setting was not recognized (supported values are 'catalog', 'disabled', 'enabled' and 'releases')";
+                                    let message = "This gemtext setting was not recognized (supported values are 'disabled' and 'enabled')";
                                     let error = element_error_with_snippet(element, manifest_path, message);
                                     build.error(&error);
                                 }
                             }
                         }
 
-                        break 'm3u;
+                        break 'gemtext;
                     }
                 }
 
-                let message = "m3u needs to be provided as a field with the value 'catalog', 'disabled', 'enabled' or 'releases', e.g.: 'm3u: disable'";
+                let message = "The gemtext option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'gemtext: enabled'";
                 let error = element_error_with_snippet(element, manifest_path, message);
                 build.error(&error);
             }
-            "opengraph" => 'opengraph: {
+            "hero" => 'hero: {
                 if let Ok(field) = element.as_field() {
-                    if let Ok(result) = field.value() {
-                        if let Some(value) = result {
-                            match value {
-                                "disabled" => {
-                                    catalog.opengraph = false;
+                    if let Ok(attributes) = field.attributes() {
+                        let mut button_label = None;
+                        let mut button_target = None;
+                        let mut description = None;
+                        let mut headline = None;
+                        let mut image = HeroImage::None;
+                        let mut subtext = None;
+
+                        for attribute in attributes {
+                            match attribute.key() {
+                                "button_label" => {
+                                    if let Some(value) = attribute.value() {
+                                        button_label = Some(value.to_string());
+                                    }
                                 }
-                                "enabled" => {
-                                    catalog.opengraph = true;
+                                "button_target" => {
+                                    if let Some(value) = attribute.value() {
+                                        button_target = match value {
+                                            Hero::LATEST_RELEASE_KEY => Some(HeroTarget::LatestRelease),
+                                            url => Some(HeroTarget::Url(url.to_string()))
+                                        };
+                                    }
                                 }
-                                _ => {
-                                    let message = "This opengraph setting was not recognized (supported values are 'disabled' and 'enabled)";
-                                    let error = element_error_with_snippet(element, manifest_path, message);
-                                    build.error(&error);
+                                "description" => {
+                                    if let Some(value) = attribute.value() {
+                                        description = Some(value.to_string());
+                                    }
                                 }
-                            }
-                        }
-
-                        break 'opengraph;
-                    }
-                }
+                                "headline" => {
+                                    if let Some(value) = attribute.value() {
+                                        headline = Some(value.to_string());
+                                    }
+                                }
+                                "image" => {
+                                    // image is either the latest_release keyword or a path relative to the manifest
+                                    if let Some(value) = attribute.value() {
+                                        if value == Hero::LATEST_RELEASE_KEY {
+                                            image = HeroImage::LatestReleaseCover;
+                                        } else {
+                                            let absolute_path = dir.join(value);
+                                            if absolute_path.exists() {
+                                                let path_relative_to_catalog = absolute_path.strip_prefix(&build.catalog_dir).unwrap().to_path_buf();
+                                                let obtained_image = cache.get_or_create_image(build, &path_relative_to_catalog);
+                                                // The description is attached below, once all attributes are read
+                                                image = HeroImage::Custom(DescribedImage::new(None, obtained_image));
+                                            } else {
+                                                let message = format!("The referenced file was not found ({})", absolute_path.display());
+                                                let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                                                build.error(&error);
+                                            }
+                                        }
+                                    }
+                                }
+                                "subtext" => {
+                                    if let Some(value) = attribute.value() {
+                                        subtext = Some(value.to_string());
+                                    }
+                                }
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'button_label', 'button_target', 'description', 'headline', 'image' and 'subtext' are recognized inside a hero field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        if let HeroImage::Custom(described_image) = &mut image {
+                            described_image.description = description;
+                        }
+
+                        let button = match (button_label, button_target) {
+                            (Some(label), Some(target)) => Some(HeroButton { label, target }),
+                            (None, None) => None,
+                            _ => {
+                                let message = "The hero button needs both a button_label and a button_target, only one of them was provided";
+                                let error = element_error_with_snippet(element, manifest_path, message);
+                                build.error(&error);
+                                None
+                            }
+                        };
+
+                        catalog.hero = Some(Hero {
+                            button,
+                            headline,
+                            image,
+                            subtext
+                        });
+
+                        break 'hero;
+                    }
+                }
+
+                let message = indoc!("
+                    hero needs to be provided as a field with attributes, e.g.:
+
+                    hero:
+                    button_label = Listen now
+                    button_target = latest_release
+                    headline = Our new album is out!
+                    image = latest_release
+                    subtext = Ten tracks recorded live in a single night
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "home_image" => 'home_image: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(attributes) = field.attributes() {
+                        let mut path_relative_to_catalog = None;
+                        let mut description = None;
+
+                        for attribute in attributes {
+                            match attribute.key() {
+                                "description" => {
+                                    if let Some(value) = attribute.value() {
+                                        description = Some(value.to_string());
+                                    }
+                                }
+                                "file" => {
+                                    // file is a path relative to the manifest
+                                    if let Some(value) = attribute.value() {
+                                        let absolute_path = dir.join(value);
+                                        if absolute_path.exists() {
+                                            path_relative_to_catalog = Some(absolute_path.strip_prefix(&build.catalog_dir).unwrap().to_path_buf());
+                                        } else {
+                                            let message = format!("The referenced file was not found ({})", absolute_path.display());
+                                            let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                                            build.error(&error);
+                                        }
+
+                                    }
+                                }
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'description' and 'file' are recognized inside an home_image field";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        if let Some(path) = path_relative_to_catalog {
+                            let image = cache.get_or_create_image(build, &path);
+                            catalog.home_image = Some(DescribedImage::new(description, image));
+                        }
+
+                        break 'home_image;
+                    }
+                }
+
+                let message = indoc!("
+                    home_image needs to be provided as a field with attributes, e.g.:
+
+                    home_image:
+                    description = Alice, looking amused
+                    file = alice.jpg
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "hover_previews" => 'hover_previews: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.hover_previews = false;
+                                }
+                                "enabled" => {
+                                    catalog.hover_previews = true;
+                                }
+                                _ => {
+                                    let message = "This hover_previews setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'hover_previews;
+                    }
+                }
+
+                let message = "The hover_previews option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'hover_previews: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "image_description_suggestions" => 'image_description_suggestions: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.image_description_suggestions = false;
+                                }
+                                "enabled" => {
+                                    catalog.image_description_suggestions = true;
+                                }
+                                _ => {
+                                    let message = "This image_description_suggestions setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'image_description_suggestions;
+                    }
+                }
+
+                let message = "The image_description_suggestions option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'image_description_suggestions: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "image_metadata" => 'image_metadata: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "copyright" => {
+                                    build.preserve_image_copyright = true;
+                                }
+                                "strip" => {
+                                    build.preserve_image_copyright = false;
+                                }
+                                _ => {
+                                    let message = "This image_metadata setting was not recognized (supported values are 'copyright' and 'strip')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'image_metadata;
+                    }
+                }
+
+                let message = "The image_metadata option needs to be provided as a field with the value 'copyright' or 'strip', e.g.: 'image_metadata: copyright'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "include_catalogs" => 'include_catalogs: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(items) = field.items() {
+                        for item in items {
+                            let Some(value) = item.value() else { continue };
+
+                            match resolve_included_catalog(build, value) {
+                                Ok(included_dir) => {
+                                    if catalog.included_catalogs.contains(&included_dir) {
+                                        let message = format!("The catalog directory '{value}' is included more than once");
+                                        let error = item_error_with_snippet(item, manifest_path, &message);
+                                        build.error(&error);
+                                    } else {
+                                        catalog.included_catalogs.push(included_dir);
+                                    }
+                                }
+                                Err(err) => {
+                                    let message = format!("The catalog directory '{value}' can not be included: {err}");
+                                    let error = item_error_with_snippet(item, manifest_path, &message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'include_catalogs;
+                    }
+                }
+
+                let message = indoc!("
+                    include_catalogs needs to be provided as a field with items (paths to other catalog directories, relative to this one or absolute), e.g.:
+
+                    include_catalogs:
+                    - ../alice
+                    - ../bob
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "index_views" => 'index_views: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        catalog.index_views.clear();
+
+                        if let Some(value) = result {
+                            match IndexView::from_manifest_key(value) {
+                                Some(index_view) => catalog.index_views.push(index_view),
+                                None => {
+                                    let message = "This index view was not recognized (supported values are 'list' and 'timeline')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'index_views;
+                    } else if let Ok(items) = field.items() {
+                        catalog.index_views.clear();
+
+                        for item in items {
+                            if let Some(value) = item.value() {
+                                match IndexView::from_manifest_key(value) {
+                                    Some(index_view) => {
+                                        if !catalog.index_views.contains(&index_view) {
+                                            catalog.index_views.push(index_view);
+                                        }
+                                    }
+                                    None => {
+                                        let message = "This index view was not recognized (supported values are 'list' and 'timeline')";
+                                        let error = item_error_with_snippet(item, manifest_path, message);
+                                        build.error(&error);
+                                    }
+                                }
+                            }
+                        }
+
+                        break 'index_views;
+                    }
+                }
+
+                let message = indoc!("
+                    index_views needs to be provided as a field with a value or a field with items, e.g.:
+
+                    index_views: timeline
+
+                    index_views:
+                    - list
+                    - timeline
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "json_api" => 'json_api: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.json_api = false;
+                                }
+                                "enabled" => {
+                                    catalog.json_api = true;
+                                }
+                                _ => {
+                                    let message = "This json_api setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'json_api;
+                    }
+                }
+
+                let message = "The json_api option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'json_api: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "label_mode" => {
+                if element.is_flag() {
+                    catalog.label_mode = true;
+                } else {
+                    let message = "label_mode needs to be provided as a flag, that is, exactly as 'label_mode' (without colon and without value)";
+                    let error = element_error_with_snippet(element, manifest_path, message);
+                    build.error(&error);
+                }
+            }
+            "language" => 'language: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            build.locale = Locale::from_code(value);
+                        }
+
+                        break 'language;
+                    }
+                }
+
+                let message = "language needs to be provided as a field with a value, e.g.: 'language: fr'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "language_mirrors" => 'language_mirrors: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(attributes) = field.attributes() {
+                        catalog.language_mirrors.clear();
+
+                        for attribute in attributes {
+                            let Some(value) = attribute.value() else { continue };
+
+                            match SiteUrl::parse(value) {
+                                Ok(url) => {
+                                    let language = attribute.key().to_string();
+                                    catalog.language_mirrors.push(LanguageMirror::new(language, url));
+                                }
+                                Err(err) => {
+                                    let message = format!("The url '{value}' of this language mirror is not a valid URL: {err}");
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'language_mirrors;
+                    }
+                }
+
+                let message = indoc!("
+                    language_mirrors needs to be provided as a field with attributes (language code = url of the translated catalog), e.g.:
+
+                    language_mirrors:
+                    de = https://example.com/de/
+                    fr = https://fr.example.com/
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "languages" => 'languages: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(items) = field.items() {
+                        catalog.languages.clear();
+
+                        for item in items {
+                            let Some(value) = item.value() else { continue };
+
+                            if value.is_empty() || !value.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+                                let message = format!("'{value}' is not a valid language code (language codes consist of lowercase letters and hyphens only, e.g. 'de' or 'pt-pt')");
+                                let error = item_error_with_snippet(item, manifest_path, &message);
+                                build.error(&error);
+                            } else if !catalog.languages.iter().any(|language| language == value) {
+                                catalog.languages.push(value.to_string());
+                            }
+                        }
+
+                        break 'languages;
+                    }
+                }
+
+                let message = indoc!("
+                    languages needs to be provided as a field with items (language codes), e.g.:
+
+                    languages:
+                    - de
+                    - fr
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "latest_release_widget" => 'latest_release_widget: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.latest_release_widget = false;
+                                }
+                                "enabled" => {
+                                    catalog.latest_release_widget = true;
+                                }
+                                _ => {
+                                    let message = "This latest_release_widget setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'latest_release_widget;
+                    }
+                }
+
+                let message = "The latest_release_widget option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'latest_release_widget: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "legacy_support" => 'legacy_support: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.legacy_support = false;
+                                }
+                                "enabled" => {
+                                    catalog.legacy_support = true;
+                                }
+                                _ => {
+                                    let message = "This legacy_support setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'legacy_support;
+                    }
+                }
+
+                let message = "The legacy_support option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'legacy_support: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "m3u" => 'm3u: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "catalog" => {
+                                    catalog.m3u = true;
+                                    overrides.m3u_enabled = false;
+                                }
+                                "disabled" => {
+                                    catalog.m3u = false;
+                                    overrides.m3u_enabled = false;
+                                }
+                                "enabled" => {
+                                    catalog.m3u = true;
+                                    overrides.m3u_enabled = true;
+                                }
+                                "releases" => {
+                                    catalog.m3u = false;
+                                    overrides.m3u_enabled = true;
+                                }
+                                _ => {
+                                    let message = "This m3u setting was not recognized (supported values are 'catalog', 'disabled', 'enabled' and 'releases')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'm3u;
+                    }
+                }
+
+                let message = "m3u needs to be provided as a field with the value 'catalog', 'disabled', 'enabled' or 'releases', e.g.: 'm3u: disable'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "minify" => 'minify: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    build.minify = false;
+                                }
+                                "enabled" => {
+                                    build.minify = true;
+                                }
+                                _ => {
+                                    let message = "This minify setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'minify;
+                    }
+                }
+
+                let message = "The minify option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'minify: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "notify_webhook" => 'notify_webhook: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => build.notify_webhook = None,
+                                _ => match Url::parse(value) {
+                                    Ok(_) => {
+                                        build.notify_webhook = Some(NotifyWebhook {
+                                            events: vec![WebhookEvent::Build, WebhookEvent::Deploy],
+                                            url: value.to_string()
+                                        });
+                                    }
+                                    Err(err) => {
+                                        let message = format!("The url supplied for notify_webhook seems to be malformed ({err})");
+                                        let error = element_error_with_snippet(element, manifest_path, &message);
+                                        build.error(&error);
+                                    }
+                                }
+                            }
+                        }
+
+                        break 'notify_webhook;
+                    } else if let Ok(attributes) = field.attributes() {
+                        let mut events = vec![WebhookEvent::Build, WebhookEvent::Deploy];
+                        let mut url = None;
+
+                        for attribute in attributes {
+                            match attribute.key() {
+                                "after" => {
+                                    if let Some(value) = attribute.value() {
+                                        let parsed = value
+                                            .split(',')
+                                            .map(|key| WebhookEvent::from_key(key.trim()))
+                                            .collect::<Option<Vec<WebhookEvent>>>();
+
+                                        match parsed {
+                                            Some(parsed) => events = parsed,
+                                            None => {
+                                                let message = "The value of the after attribute was not recognized (supported are 'build', 'deploy' and 'build, deploy')";
+                                                let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                                build.error(&error);
+                                            }
+                                        }
+                                    }
+                                }
+                                "url" => {
+                                    if let Some(value) = attribute.value() {
+                                        match Url::parse(value) {
+                                            Ok(_) => url = Some(value.to_string()),
+                                            Err(err) => {
+                                                let message = format!("The url supplied for notify_webhook seems to be malformed ({err})");
+                                                let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                                                build.error(&error);
+                                            }
+                                        }
+                                    }
+                                }
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'after' and 'url' are recognized inside a notify_webhook field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        if let Some(url) = url {
+                            build.notify_webhook = Some(NotifyWebhook { events, url });
+                        } else {
+                            let message = indoc!("
+                                The url attribute is required for notify_webhook, e.g.:
+
+                                notify_webhook:
+                                after = deploy
+                                url = https://example.com/hooks/faircamp
+                            ");
+                            let error = element_error_with_snippet(element, manifest_path, message);
+                            build.error(&error);
+                        }
+
+                        break 'notify_webhook;
+                    }
+                }
+
+                let message = indoc!("
+                    notify_webhook needs to be provided either as a field with a value (the url), or as a field with attributes, e.g.:
+
+                    notify_webhook: https://example.com/hooks/faircamp
+
+                    notify_webhook:
+                    after = deploy
+                    url = https://example.com/hooks/faircamp
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "opengraph" => 'opengraph: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.opengraph = false;
+                                }
+                                "enabled" => {
+                                    catalog.opengraph = true;
+                                }
+                                _ => {
+                                    let message = "This opengraph setting was not recognized (supported values are 'disabled' and 'enabled)";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'opengraph;
+                    }
+                }
+
+                let message = "The opengraph option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'opengraph: disabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "permalink_conflicts" => 'permalink_conflicts: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match PermalinkConflicts::from_manifest_key(value) {
+                                Some(permalink_conflicts) => catalog.permalink_conflicts = permalink_conflicts,
+                                None => {
+                                    let message = "This permalink_conflicts setting was not recognized (supported values are 'auto' and 'error')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'permalink_conflicts;
+                    }
+                }
+
+                let message = "permalink_conflicts needs to be provided as a field with the value 'auto' or 'error', e.g.: 'permalink_conflicts: auto'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "permalink_terms" => 'permalink_terms: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(attributes) = field.attributes() {
+                        for attribute in attributes {
+                            let Some(value) = attribute.value() else { continue };
+
+                            // Only url-safe terms are accepted, the same rules
+                            // as for permalinks apply.
+                            let term = match Permalink::new(value) {
+                                Ok(permalink) => permalink.slug,
+                                Err(err) => {
+                                    let message = format!("There is a problem with the permalink term '{value}': {err}");
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                                    build.error(&error);
+                                    continue;
+                                }
+                            };
+
+                            match attribute.key() {
+                                "downloads" => catalog.permalink_terms.downloads = Some(term),
+                                "image_descriptions" => catalog.permalink_terms.image_descriptions = Some(term),
+                                "purchase" => catalog.permalink_terms.purchase = Some(term),
+                                "subscribe" => catalog.permalink_terms.subscribe = Some(term),
+                                "unlock" => catalog.permalink_terms.unlock = Some(term),
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'downloads', 'image_descriptions', 'purchase', 'subscribe' and 'unlock' are recognized inside a permalink_terms field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'permalink_terms;
+                    }
+                }
+
+                let message = indoc!("
+                    permalink_terms needs to be provided as a field with attributes (downloads, image_descriptions, purchase, subscribe, unlock), e.g.:
+
+                    permalink_terms:
+                    downloads = dl
+                    unlock = code
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "player_config" => 'player_config: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.player_config = false;
+                                }
+                                "enabled" => {
+                                    catalog.player_config = true;
+                                }
+                                _ => {
+                                    let message = "This player_config setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
 
-                let message = "The opengraph option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'opengraph: disabled'";
+                        break 'player_config;
+                    }
+                }
+
+                let message = "The player_config option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'player_config: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "range_check" => 'range_check: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.range_check = false;
+                                }
+                                "enabled" => {
+                                    catalog.range_check = true;
+                                }
+                                _ => {
+                                    let message = "This range_check setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'range_check;
+                    }
+                }
+
+                let message = "The range_check option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'range_check: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "release_filters" => 'release_filters: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.release_filters = false;
+                                }
+                                "enabled" => {
+                                    catalog.release_filters = true;
+                                }
+                                _ => {
+                                    let message = "This release_filters setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'release_filters;
+                    }
+                }
+
+                let message = "The release_filters option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'release_filters: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "releases_per_page" => 'releases_per_page: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => catalog.releases_per_page = None,
+                                _ => match value.parse::<usize>() {
+                                    Ok(number) if number > 0 => catalog.releases_per_page = Some(number),
+                                    _ => {
+                                        let message = "This releases_per_page setting was not recognized (supported values are a whole number greater than zero, or 'disabled')";
+                                        let error = element_error_with_snippet(element, manifest_path, message);
+                                        build.error(&error);
+                                    }
+                                }
+                            }
+                        }
+
+                        break 'releases_per_page;
+                    }
+                }
+
+                let message = "The releases_per_page option needs to be provided as a field with a whole number or 'disabled' as value, e.g.: 'releases_per_page: 24'";
                 let error = element_error_with_snippet(element, manifest_path, message);
                 build.error(&error);
             }
@@ -476,6 +1610,70 @@ pub fn read_catalog_manifest(
                     build.error(&error);
                 }
             }
+            "search" => 'search: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => {
+                                    catalog.search = false;
+                                }
+                                "enabled" => {
+                                    catalog.search = true;
+                                }
+                                _ => {
+                                    let message = "This search setting was not recognized (supported values are 'disabled' and 'enabled')";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'search;
+                    }
+                }
+
+                let message = "The search option needs to be provided as a field with the value 'disabled' or 'enabled', e.g.: 'search: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "security_txt" => 'security_txt: {
+                if let Ok(embed) = element.as_embed() {
+                    if let Some(value) = embed.value() {
+                        // RFC 9116 requires at least these two fields to be present
+                        let has_field = |name: &str| value
+                            .lines()
+                            .any(|line| line.trim_start().to_lowercase().starts_with(&format!("{name}:")));
+
+                        if has_field("contact") && has_field("expires") {
+                            let mut security_txt = value.to_string();
+                            if !security_txt.ends_with('\n') {
+                                security_txt.push('\n');
+                            }
+                            catalog.well_known.security_txt = Some(security_txt);
+                        } else {
+                            let message = "security_txt needs to contain at least a 'Contact:' and an 'Expires:' field (see RFC 9116)";
+                            let error = element_error_with_snippet(element, manifest_path, message);
+                            build.error(&error);
+                        }
+                    } else {
+                        catalog.well_known.security_txt = None;
+                    }
+
+                    break 'security_txt;
+                }
+
+                let message = indoc!("
+                    security_txt needs to be provided as an embed, for instance:
+
+                    -- security_txt
+                    Contact: mailto:security@example.com
+                    Expires: 2030-12-31T23:00:00.000Z
+                    -- security_txt
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
             "show_support_artists" => {
                 if element.is_flag() {
                     catalog.show_support_artists = true;
@@ -562,6 +1760,63 @@ pub fn read_catalog_manifest(
                 let error = element_error_with_snippet(element, manifest_path, message);
                 build.error(&error);
             }
+            "streaming_limits" => 'streaming_limits: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => catalog.streaming_limits = StreamingLimits::DISABLED,
+                                _ => {
+                                    let message = "This streaming_limits setting was not recognized (as a value only 'disabled' is supported, otherwise provide 'duration' and/or 'size' as attributes)";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'streaming_limits;
+                    } else if let Ok(attributes) = field.attributes() {
+                        for attribute in attributes {
+                            let Some(value) = attribute.value() else { continue };
+
+                            let limit = match value {
+                                "disabled" => None,
+                                _ => match value.parse::<u32>() {
+                                    Ok(number) => Some(number),
+                                    Err(_) => {
+                                        let message = "The value of this attribute needs to be a whole number (minutes for 'duration', megabytes for 'size') or 'disabled'";
+                                        let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                        build.error(&error);
+                                        continue;
+                                    }
+                                }
+                            };
+
+                            match attribute.key() {
+                                "duration" => catalog.streaming_limits.duration_minutes = limit,
+                                "size" => catalog.streaming_limits.size_megabytes = limit,
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'duration' and 'size' are recognized inside a streaming_limits field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'streaming_limits;
+                    }
+                }
+
+                let message = indoc!("
+                    streaming_limits needs to be provided as a field with attributes (duration in minutes, size in megabytes), or with the value 'disabled', e.g.:
+
+                    streaming_limits:
+                    duration = 180
+                    size = 150
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
             "title" => 'title: {
                 if let Ok(field) = element.as_field() {
                     if let Ok(result) = field.value() {
@@ -577,6 +1832,134 @@ pub fn read_catalog_manifest(
                 let error = element_error_with_snippet(element, manifest_path, message);
                 build.error(&error);
             }
+            // Already read before all other options (see above)
+            "warnings" => (),
+            "webfinger" => 'webfinger: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(attributes) = field.attributes() {
+                        let mut account = None;
+                        let mut actor = None;
+                        let mut profile = None;
+
+                        for attribute in attributes {
+                            match attribute.key() {
+                                "account" => {
+                                    if let Some(value) = attribute.value() {
+                                        if value.contains('@') {
+                                            account = Some(value.to_string());
+                                        } else {
+                                            let message = "The account needs to be given in the form 'name@example.com'";
+                                            let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                            build.error(&error);
+                                        }
+                                    }
+                                }
+                                "actor" => {
+                                    if let Some(value) = attribute.value() {
+                                        actor = Some(value.to_string());
+                                    }
+                                }
+                                "profile" => {
+                                    if let Some(value) = attribute.value() {
+                                        profile = Some(value.to_string());
+                                    }
+                                }
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'account', 'actor' and 'profile' are recognized inside a webfinger field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        match (account, actor) {
+                            (Some(account), Some(actor)) => {
+                                catalog.well_known.webfinger = Some(Webfinger::new(&account, actor, profile));
+                            }
+                            _ => {
+                                let message = "webfinger needs both an account and an actor attribute";
+                                let error = element_error_with_snippet(element, manifest_path, message);
+                                build.error(&error);
+                            }
+                        }
+
+                        break 'webfinger;
+                    }
+                }
+
+                let message = indoc!("
+                    webfinger needs to be provided as a field with attributes, e.g.:
+
+                    webfinger:
+                    account = music@example.com
+                    actor = https://social.example.com/users/music
+                    profile = https://social.example.com/@music
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "year_archives" => 'year_archives: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => catalog.year_archives = None,
+                                "enabled" => catalog.year_archives = Some(YearArchives { decades: false, feeds: false }),
+                                _ => {
+                                    let message = "This year_archives setting was not recognized (supported values are 'enabled' and 'disabled', or use the attributes 'decades' and 'feeds' to enable the archives along with these additions)";
+                                    let error = element_error_with_snippet(element, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'year_archives;
+                    } else if let Ok(attributes) = field.attributes() {
+                        let mut year_archives = YearArchives { decades: false, feeds: false };
+
+                        for attribute in attributes {
+                            let Some(value) = attribute.value() else { continue };
+
+                            let enabled = match value {
+                                "disabled" => false,
+                                "enabled" => true,
+                                _ => {
+                                    let message = "This setting was not recognized (supported values are 'enabled' and 'disabled')";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                    continue;
+                                }
+                            };
+
+                            match attribute.key() {
+                                "decades" => year_archives.decades = enabled,
+                                "feeds" => year_archives.feeds = enabled,
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'decades' and 'feeds' are recognized inside a year_archives field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        catalog.year_archives = Some(year_archives);
+
+                        break 'year_archives;
+                    }
+                }
+
+                let message = indoc!("
+                    year_archives needs to be provided either as a field with the value 'enabled' or 'disabled', or as a field with attributes (to also enable decade pages and feeds), e.g.:
+
+                    year_archives: enabled
+
+                    year_archives:
+                    decades = enabled
+                    feeds = enabled
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
             _ if read_artist_catalog_release_option(build, element, manifest_path, overrides) => (),
             _ if read_artist_catalog_release_track_option(build, cache, element, local_options, manifest_path, overrides) => (),
             _ if read_catalog_release_option(build, catalog, element, manifest_path) => (),
@@ -597,3 +1980,101 @@ pub fn read_catalog_manifest(
         }
     }
 }
+
+/// Resolves a directory given through include_catalogs (relative to the
+/// catalog directory, or absolute) to a path that starts with the catalog
+/// directory, e.g. "[catalog_dir]/../other-catalog". This way all paths
+/// inside the included catalog remain expressible relative to the catalog
+/// directory, which the cache and the asset handling build upon.
+fn resolve_included_catalog(build: &Build, value: &str) -> Result<PathBuf, String> {
+    let Ok(included_dir_canonicalized) = build.catalog_dir.join(value).canonicalize() else {
+        return Err(String::from("The directory does not exist or can not be accessed"));
+    };
+
+    if !included_dir_canonicalized.is_dir() {
+        return Err(String::from("The path does not point to a directory"));
+    }
+
+    let Ok(catalog_dir_canonicalized) = build.catalog_dir.canonicalize() else {
+        return Err(String::from("The catalog directory can not be accessed"));
+    };
+
+    if included_dir_canonicalized.starts_with(&catalog_dir_canonicalized) {
+        return Err(String::from("The directory lies inside the catalog directory, whose content is read anyway"));
+    }
+
+    if catalog_dir_canonicalized.starts_with(&included_dir_canonicalized) {
+        return Err(String::from("The directory contains the catalog directory itself"));
+    }
+
+    let catalog_dir_components: Vec<Component> = catalog_dir_canonicalized.components().collect();
+    let included_dir_components: Vec<Component> = included_dir_canonicalized.components().collect();
+
+    let common_components = catalog_dir_components
+        .iter()
+        .zip(&included_dir_components)
+        .take_while(|(catalog_dir_component, included_dir_component)| catalog_dir_component == included_dir_component)
+        .count();
+
+    // On Windows, two paths on different drives share no component at all
+    if common_components == 0 {
+        return Err(String::from("The directory needs to be located on the same drive as the catalog directory"));
+    }
+
+    let mut resolved = build.catalog_dir.clone();
+
+    for _ in common_components..catalog_dir_components.len() {
+        resolved.push("..");
+    }
+
+    for component in &included_dir_components[common_components..] {
+        resolved.push(component);
+    }
+
+    Ok(resolved)
+}
+
+fn read_warnings_option(
+    build: &mut Build,
+    element: &Box<dyn SectionElement>,
+    manifest_path: &Path
+) {
+    if let Ok(field) = element.as_field() {
+        if let Ok(attributes) = field.attributes() {
+            for attribute in attributes {
+                let Some(category) = WarningCategory::from_manifest_key(attribute.key()) else {
+                    let supported = WarningCategory::ALL_CATEGORIES.map(|category| format!("'{}'", category.key())).join(", ");
+                    let message = format!("The warning category '{}' was not recognized (supported categories are {supported})", attribute.key());
+                    let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                    build.error(&error);
+                    continue;
+                };
+
+                if let Some(value) = attribute.value() {
+                    match WarningLevel::from_manifest_key(value) {
+                        Some(level) => {
+                            build.warning_levels.insert(category, level);
+                        }
+                        None => {
+                            let message = format!("The warning level '{value}' was not recognized (supported levels are 'error', 'silence' and 'warning')");
+                            let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                            build.error(&error);
+                        }
+                    }
+                }
+            }
+
+            return;
+        }
+    }
+
+    let message = indoc!("
+        warnings needs to be provided as a field with attributes, each mapping a warning category to 'error', 'silence' or 'warning', e.g.:
+
+        warnings:
+        lossy_to_lossless = error
+        missing_image_description = silence
+    ");
+    let error = element_error_with_snippet(element, manifest_path, message);
+    build.error(&error);
+}
@@ -0,0 +1,159 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    Build,
+    Localizations,
+    LocalizedContent
+};
+use crate::fair_dir::overlay_manifest_name;
+use crate::markdown;
+use crate::util::html_escape_outside_attribute;
+
+use super::{
+    MAX_SYNOPSIS_CHARS,
+    element_error_with_snippet,
+    manifest_text,
+    not_supported_error,
+    platform_printer
+};
+
+const OVERLAY_OPTIONS: &[&str] = &[
+    "more",
+    "synopsis",
+    "title"
+];
+
+/// Reads all overlays (e.g. `release.de.eno`, `release.fr.eno`) that
+/// translate the given manifest ("artist", "catalog" or "release").
+pub fn read_overlay_manifests(
+    build: &mut Build,
+    manifest_name: &str,
+    overlay_manifests: &[PathBuf]
+) -> Localizations {
+    let mut localizations = Localizations::new();
+
+    for manifest_path in overlay_manifests {
+        if overlay_manifest_name(manifest_path) != Some(manifest_name) {
+            continue
+        }
+
+        if build.verbose {
+            info!("Reading translated manifest {}", manifest_path.display());
+        }
+
+        // The filename was validated when the directory was scanned, so the
+        // language code is always present
+        let filename = manifest_path.file_name().unwrap().to_string_lossy();
+        let language = filename.split('.').nth(1).unwrap().to_string();
+
+        if let Some(content) = read_overlay_manifest(build, manifest_name, manifest_path) {
+            localizations.insert(language, content);
+        }
+    }
+
+    localizations
+}
+
+fn read_overlay_manifest(
+    build: &mut Build,
+    manifest_name: &str,
+    manifest_path: &Path
+) -> Option<LocalizedContent> {
+    let content = match fs::read_to_string(manifest_path) {
+        Ok(content) => content,
+        Err(err) => {
+            let error = format!("Could not read manifest {} ({})", manifest_path.display(), err);
+            build.error(&error);
+            return None
+        }
+    };
+
+    build.register_manifest(manifest_path, &content);
+
+    let document = match enolib::parse_with_printer(&content, platform_printer()) {
+        Ok(document) => document,
+        Err(err) => {
+            let error = format!("Syntax error in {}:{} ({})", manifest_path.display(), err.line, err);
+            build.error(&error);
+            return None
+        }
+    };
+
+    let mut localized_content = LocalizedContent::new();
+
+    for element in document.elements() {
+        match element.key() {
+            "more" => {
+                if let Ok(embed) = element.as_embed() {
+                    if let Some(value) = embed.value() {
+                        if build.check_texts {
+                            build.manifest_texts.push(manifest_text(element, manifest_path, true, value));
+                        }
+
+                        localized_content.more = Some(markdown::to_html_and_stripped(&build.base_url, value));
+                    }
+                } else {
+                    let message = "The 'more' option needs to be provided as an embed, e.g.:\n-- more\nA long-form 'more' text\n--more";
+                    let error = element_error_with_snippet(element, manifest_path, message);
+                    build.error(&error);
+                }
+            }
+            "synopsis" => {
+                if let Ok(embed) = element.as_embed() {
+                    if let Some(value) = embed.value() {
+                        let synopsis_chars = value.chars().count();
+
+                        if build.check_texts {
+                            build.manifest_texts.push(manifest_text(element, manifest_path, false, value));
+                        }
+
+                        if synopsis_chars <= MAX_SYNOPSIS_CHARS {
+                            localized_content.synopsis = Some(html_escape_outside_attribute(value));
+                        } else {
+                            let message = format!("Synopsis is too long ({synopsis_chars}/{MAX_SYNOPSIS_CHARS} characters)");
+                            let error = element_error_with_snippet(element, manifest_path, &message);
+                            build.error(&error);
+                        }
+                    }
+                } else {
+                    let message = "synopsis needs to be provided as an embed, e.g.:\n-- synopsis\nThis is a synopsis\n--synopsis";
+                    let error = element_error_with_snippet(element, manifest_path, message);
+                    build.error(&error);
+                }
+            }
+            // Artists have a name instead of a title, which is not translated
+            "title" if manifest_name != "artist" => 'title: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            localized_content.title = Some(value.to_string());
+                        }
+
+                        break 'title;
+                    }
+                }
+
+                let message = "title needs to be provided as a field with a value, e.g.: 'title: Demotape'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            other => {
+                let supported_options: &[&str] = match manifest_name {
+                    "artist" => &["more", "synopsis"],
+                    _ => OVERLAY_OPTIONS
+                };
+
+                let translated_manifest = format!("translated {manifest_name}.eno");
+                let message = not_supported_error(&translated_manifest, other, &[supported_options]);
+                let error = element_error_with_snippet(element, manifest_path, &message);
+                build.error(&error);
+            }
+        }
+    }
+
+    Some(localized_content)
+}
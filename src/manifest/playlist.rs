@@ -0,0 +1,193 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::fs;
+use std::path::Path;
+
+use crate::{
+    Build,
+    Catalog,
+    Permalink,
+    Playlist
+};
+use crate::util::html_escape_outside_attribute;
+
+use super::{
+    MAX_SYNOPSIS_CHARS,
+    element_error_with_snippet,
+    item_error_with_snippet,
+    manifest_text,
+    not_supported_error,
+    platform_printer,
+    read_obsolete_option
+};
+
+const PLAYLIST_OPTIONS: &[&str] = &[
+    "permalink",
+    "synopsis",
+    "title",
+    "tracks"
+];
+
+pub fn read_playlist_manifest(
+    build: &mut Build,
+    catalog: &mut Catalog,
+    dir: &Path,
+    manifest_path: &Path
+) {
+    let content = match fs::read_to_string(manifest_path) {
+        Ok(content) => content,
+        Err(err) => {
+            let error = format!("Could not read manifest {} ({})", manifest_path.display(), err);
+            build.error(&error);
+            return
+        }
+    };
+
+    build.register_manifest(manifest_path, &content);
+
+    let document = match enolib::parse_with_printer(&content, platform_printer()) {
+        Ok(document) => document,
+        Err(err) => {
+            let error = format!("Syntax error in {}:{} ({})", manifest_path.display(), err.line, err);
+            build.error(&error);
+            return
+        }
+    };
+
+    let mut permalink = None;
+    let mut references = Vec::new();
+    let mut synopsis = None;
+    // By default we use the folder name as title
+    let mut title = dir.file_name().unwrap().to_string_lossy().to_string();
+
+    for element in document.elements() {
+        match element.key() {
+            _ if read_obsolete_option(build, element, manifest_path) => (),
+            "permalink" => 'permalink: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match Permalink::new(value) {
+                                Ok(custom_permalink) => permalink = Some(custom_permalink),
+                                Err(err) => {
+                                    let message = format!("There is a problem with the permalink '{value}': {err}");
+                                    let error = element_error_with_snippet(element, manifest_path, &message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'permalink;
+                    }
+                }
+
+                let message = "permalink needs to be provided as a field with a value, e.g.: 'permalink: such-perma-wow'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "synopsis" => {
+                if let Ok(embed) = element.as_embed() {
+                    if let Some(value) = embed.value() {
+                        let synopsis_chars = value.chars().count();
+
+                        if build.check_texts {
+                            build.manifest_texts.push(manifest_text(element, manifest_path, false, value));
+                        }
+
+                        if synopsis_chars <= MAX_SYNOPSIS_CHARS {
+                            synopsis = Some(html_escape_outside_attribute(value));
+                        } else {
+                            let message = format!("Synopsis is too long ({synopsis_chars}/{MAX_SYNOPSIS_CHARS} characters)");
+                            let error = element_error_with_snippet(element, manifest_path, &message);
+                            build.error(&error);
+                        }
+                    } else {
+                        synopsis = None;
+                    }
+                } else {
+                    let message = "synopsis needs to be provided as an embed, e.g.:\n-- synopsis\nThis is a synopsis\n--synopsis";
+                    let error = element_error_with_snippet(element, manifest_path, message);
+                    build.error(&error);
+                }
+            }
+            "title" => 'title: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            title = value.to_string();
+                        }
+
+                        break 'title;
+                    }
+                }
+
+                let message = "title needs to be provided as a field with a value, e.g.: 'title: Summer Sampler'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "tracks" => 'tracks: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(items) = field.items() {
+                        for item in items {
+                            if let Some(value) = item.value() {
+                                let reference = value
+                                    .rsplit_once('/')
+                                    .and_then(|(release_slug, track_number)| {
+                                        match track_number.parse::<usize>() {
+                                            Ok(track_number) if track_number > 0 => Some((release_slug.to_string(), track_number)),
+                                            _ => None
+                                        }
+                                    });
+
+                                match reference {
+                                    Some(reference) => references.push(reference),
+                                    None => {
+                                        let message = "A track on a playlist needs to be referenced by the permalink of its release and its track number, separated by a slash, e.g.: 'such-perma-wow/3'";
+                                        let error = item_error_with_snippet(item, manifest_path, message);
+                                        build.error(&error);
+                                    }
+                                }
+                            }
+                        }
+
+                        break 'tracks;
+                    }
+                }
+
+                let message = "tracks needs to be provided as a field containing items (release permalink and track number), e.g.:\n\ntracks:\n- such-perma-wow/3\n- another-release/1";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            other => {
+                let message = not_supported_error(
+                    "playlist.eno",
+                    other,
+                    &[PLAYLIST_OPTIONS]
+                );
+
+                let error = element_error_with_snippet(element, manifest_path, &message);
+                build.error(&error);
+            }
+        }
+    }
+
+    if references.is_empty() {
+        let error = format!("The playlist manifest {} does not reference any tracks (use the 'tracks' option to list them), the playlist will be ignored.", manifest_path.display());
+        build.error(&error);
+        return;
+    }
+
+    let permalink = permalink.unwrap_or_else(|| Permalink::generate(&title));
+    let source_dir = dir.strip_prefix(&build.catalog_dir).unwrap().to_path_buf();
+
+    let playlist = Playlist::new(
+        permalink,
+        references,
+        source_dir,
+        synopsis,
+        title
+    );
+
+    catalog.playlists.push(playlist);
+}
@@ -5,38 +5,71 @@ use std::fs;
 use std::path::Path;
 
 use chrono::NaiveDate;
+use indoc::indoc;
+use url::Url;
 
 use crate::{
     Build,
     Cache,
     Catalog,
+    ContentWarning,
+    DescribedImage,
+    FormatComparison,
     LocalOptions,
-    Overrides
+    Overrides,
+    PhysicalEdition,
+    PublishDate,
+    ReleaseSection,
+    Stock,
+    WarningCategory
 };
+use crate::identifiers::normalize_upc;
+use crate::util::parse_timecode;
 
 use super::{
     ARTIST_CATALOG_RELEASE_OPTIONS,
     ARTIST_CATALOG_RELEASE_TRACK_OPTIONS,
     ARTIST_RELEASE_OPTIONS,
+    ARTIST_RELEASE_TRACK_OPTIONS,
     CATALOG_RELEASE_OPTIONS,
     RELEASE_TRACK_OPTIONS,
+    attribute_error_with_snippet,
     element_error_with_snippet,
+    item_error_with_snippet,
     not_supported_error,
     platform_printer,
     read_artist_catalog_release_option,
     read_artist_catalog_release_track_option,
     read_artist_release_option,
+    read_artist_release_track_option,
     read_catalog_release_option,
     read_obsolete_option,
     read_release_track_option
 };
 
+/// Longer crossfades would eat up too much of (short) tracks
+const MAX_CROSSFADE_SECONDS: f32 = 30.0;
+
 const RELEASE_OPTIONS: &[&str] = &[
+    "content_warning",
+    "crossfade",
     "date",
+    "embed_support_button",
+    "format_comparison",
+    "gapless",
+    "imprint",
+    "label",
+    "layout",
+    "physical",
+    "publish_date",
+    "region",
     "release_artist",
     "release_artists",
+    "secret_link",
+    "status",
     "title",
-    "unlisted"
+    "unlisted",
+    "upc"
 ];
 
 pub fn read_release_manifest(
@@ -57,6 +90,8 @@ pub fn read_release_manifest(
         }
     };
 
+    build.register_manifest(manifest_path, &content);
+
     let document = match enolib::parse_with_printer(&content, platform_printer()) {
         Ok(document) => document,
         Err(err) => {
@@ -69,6 +104,99 @@ pub fn read_release_manifest(
     for element in document.elements() {
         match element.key() {
             _ if read_obsolete_option(build, element, manifest_path) => (),
+            "content_warning" => 'content_warning: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        match result {
+                            Some(value) => local_options.content_warning = Some(ContentWarning::new(None, value.to_string())),
+                            None => local_options.content_warning = None
+                        }
+
+                        break 'content_warning;
+                    } else if let Ok(attributes) = field.attributes() {
+                        let mut minimum_age = None;
+                        let mut text = None;
+
+                        for attribute in attributes {
+                            match attribute.key() {
+                                "age" => {
+                                    if let Some(value) = attribute.value() {
+                                        match value.parse::<u8>() {
+                                            Ok(age) if age > 0 => minimum_age = Some(age),
+                                            _ => {
+                                                let message = "The age needs to be given as the minimum age in years, e.g. '18'";
+                                                let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                                build.error(&error);
+                                            }
+                                        }
+                                    }
+                                }
+                                "text" => {
+                                    if let Some(value) = attribute.value() {
+                                        text = Some(value.to_string());
+                                    }
+                                }
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'age' and 'text' are recognized inside a content_warning field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        match text {
+                            Some(text) => local_options.content_warning = Some(ContentWarning::new(minimum_age, text)),
+                            None => {
+                                let message = "content_warning needs a text attribute describing what the warning is about, e.g. 'text = Explicit lyrics'";
+                                let error = element_error_with_snippet(element, manifest_path, message);
+                                build.error(&error);
+                            }
+                        }
+
+                        break 'content_warning;
+                    }
+                }
+
+                let message = indoc!("
+                    content_warning needs to be provided either as a field with a value, or as a field with attributes (to add an age gate), e.g.:
+
+                    content_warning: Contains flashing lights in the visuals
+
+                    content_warning:
+                    age = 18
+                    text = Explicit lyrics
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "crossfade" => 'crossfade: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            if value == "disabled" {
+                                local_options.crossfade = None;
+                            } else {
+                                match value.trim_end_matches('s').trim().parse::<f32>() {
+                                    Ok(seconds) if seconds > 0.0 && seconds <= MAX_CROSSFADE_SECONDS => {
+                                        local_options.crossfade = Some(seconds);
+                                    }
+                                    _ => {
+                                        let message = format!("The crossfade needs to be given in seconds (e.g. '3s'), up to a maximum of {MAX_CROSSFADE_SECONDS} seconds, or as 'disabled'");
+                                        let error = element_error_with_snippet(element, manifest_path, &message);
+                                        build.error(&error);
+                                    }
+                                }
+                            }
+                        }
+
+                        break 'crossfade;
+                    }
+                }
+
+                let message = "crossfade needs to be provided as a field with a duration in seconds, e.g.: 'crossfade: 3s'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
             "date" => 'date: {
                 if let Ok(field) = element.as_field() {
                     if let Ok(result) = field.value() {
@@ -93,6 +221,360 @@ pub fn read_release_manifest(
                 let error = element_error_with_snippet(element, manifest_path, message);
                 build.error(&error);
             }
+            "embed_support_button" => 'embed_support_button: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => local_options.embed_support_button = false,
+                                "enabled" => local_options.embed_support_button = true,
+                                _ => {
+                                    let message = format!("The value '{value}' is not recognized for the embed_support_button option, allowed values are 'enabled' and 'disabled'");
+                                    let error = element_error_with_snippet(element, manifest_path, &message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'embed_support_button;
+                    }
+                }
+
+                let message = "embed_support_button needs to be provided as a field with the value 'enabled' or 'disabled', e.g.: 'embed_support_button: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "format_comparison" => 'format_comparison: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        match result {
+                            Some("disabled") => local_options.format_comparison = None,
+                            Some("enabled") => local_options.format_comparison = Some(FormatComparison::new(None, 1)),
+                            Some(_) => {
+                                let message = "This format_comparison setting was not recognized (supported values are 'disabled' and 'enabled', or use attributes to choose the excerpt)";
+                                let error = element_error_with_snippet(element, manifest_path, message);
+                                build.error(&error);
+                            }
+                            None => ()
+                        }
+
+                        break 'format_comparison;
+                    } else if let Ok(attributes) = field.attributes() {
+                        let mut start_seconds = None;
+                        let mut track_number = 1;
+
+                        for attribute in attributes {
+                            match attribute.key() {
+                                "start" => {
+                                    if let Some(value) = attribute.value() {
+                                        match parse_timecode(value) {
+                                            Some(seconds) => start_seconds = Some(seconds),
+                                            None => {
+                                                let message = "The start needs to be given in seconds (e.g. '90') or as a timecode (e.g. '1:30')";
+                                                let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                                build.error(&error);
+                                            }
+                                        }
+                                    }
+                                }
+                                "track" => {
+                                    if let Some(value) = attribute.value() {
+                                        match value.parse::<usize>() {
+                                            Ok(number) if number > 0 => track_number = number,
+                                            _ => {
+                                                let message = "The track needs to be given as its number in the tracklist, e.g. '1' for the first track";
+                                                let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                                build.error(&error);
+                                            }
+                                        }
+                                    }
+                                }
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'start' and 'track' are recognized inside a format_comparison field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        local_options.format_comparison = Some(FormatComparison::new(start_seconds, track_number));
+
+                        break 'format_comparison;
+                    }
+                }
+
+                let message = indoc!("
+                    format_comparison needs to be provided either as a field with a value, or as a field with attributes, e.g.:
+
+                    format_comparison: enabled
+
+                    format_comparison:
+                    start = 1:30
+                    track = 2
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "gapless" => 'gapless: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match value {
+                                "disabled" => local_options.gapless = false,
+                                "enabled" => local_options.gapless = true,
+                                _ => {
+                                    let message = format!("The value '{value}' is not recognized for the gapless option, allowed values are 'enabled' and 'disabled'");
+                                    let error = element_error_with_snippet(element, manifest_path, &message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'gapless;
+                    }
+                }
+
+                let message = "gapless needs to be provided as a field with the value 'enabled' or 'disabled', e.g.: 'gapless: enabled'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            // 'label' is accepted as an alternative name, as this is what
+            // imprints are commonly called on sites that host several labels
+            "imprint" | "label" => 'imprint: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        local_options.imprint = result.map(|value| value.to_string());
+                        break 'imprint;
+                    }
+                }
+
+                let key = element.key();
+                let message = format!("{key} needs to be provided as a field with a value, e.g.: '{key}: Basement Records'");
+                let error = element_error_with_snippet(element, manifest_path, &message);
+                build.error(&error);
+            }
+            "layout" => 'layout: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(items) = field.items() {
+                        let mut layout = Vec::new();
+
+                        for item in items.iter() {
+                            if let Some(value) = item.value() {
+                                match ReleaseSection::from_manifest_key(value) {
+                                    Some(section) if layout.contains(&section) => {
+                                        let message = format!("The section '{value}' is listed more than once, only its first occurrence is used");
+                                        let warning = item_error_with_snippet(item, manifest_path, &message);
+                                        build.warning(WarningCategory::DuplicateLayoutSection, &warning);
+                                    }
+                                    Some(section) => layout.push(section),
+                                    None => {
+                                        let message = format!("The section '{value}' is not supported (allowed are: 'downloads', 'links', 'more', 'physical', 'synopsis' and 'tracklist')");
+                                        let error = item_error_with_snippet(item, manifest_path, &message);
+                                        build.error(&error);
+                                    }
+                                }
+                            }
+                        }
+
+                        local_options.release_layout = Some(layout);
+
+                        break 'layout;
+                    }
+                }
+
+                let message = "layout needs to be provided as a field with items, listing the sections of the release page in the order they should appear in (sections that are left out are not shown), e.g.:\n\nlayout:\n- downloads\n- more\n- tracklist\n\n(The available sections are 'downloads', 'links', 'more', 'physical', 'synopsis' and 'tracklist')";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "physical" => 'physical: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(attributes) = field.attributes() {
+                        let mut format = None;
+                        let mut order_url = None;
+                        let mut photos: Vec<DescribedImage> = Vec::new();
+                        // Whether the last photo already got its description
+                        let mut photo_described = true;
+                        let mut price = None;
+                        let mut stock = Stock::Available;
+
+                        for attribute in attributes {
+                            let Some(value) = attribute.value() else { continue };
+
+                            match attribute.key() {
+                                "description" => {
+                                    match photos.last_mut() {
+                                        Some(described_image) if !photo_described => {
+                                            described_image.description = Some(value.to_string());
+                                            photo_described = true;
+                                        }
+                                        _ => {
+                                            let message = "A description applies to the photo given right before it, but there is no photo (without a description) before this one";
+                                            let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                            build.error(&error);
+                                        }
+                                    }
+                                }
+                                "format" => format = Some(value.to_string()),
+                                "photo" => {
+                                    // photo is a path relative to the manifest
+                                    let absolute_path = dir.join(value);
+                                    if absolute_path.exists() {
+                                        let path_relative_to_catalog = absolute_path.strip_prefix(&build.catalog_dir).unwrap().to_path_buf();
+                                        let image = cache.get_or_create_image(build, &path_relative_to_catalog);
+                                        photos.push(DescribedImage::new(None, image));
+                                        photo_described = false;
+                                    } else {
+                                        let message = format!("The referenced file was not found ({})", absolute_path.display());
+                                        let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                                        build.error(&error);
+                                    }
+                                }
+                                "price" => price = Some(value.to_string()),
+                                "stock" => match Stock::from_manifest_key(value) {
+                                    Some(parsed_stock) => stock = parsed_stock,
+                                    None => {
+                                        let message = "This stock setting was not recognized (supported values are 'available' and 'sold_out')";
+                                        let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                        build.error(&error);
+                                    }
+                                }
+                                "url" => match Url::parse(value) {
+                                    Ok(_) => order_url = Some(value.to_string()),
+                                    Err(err) => {
+                                        let message = format!("This order url is somehow not valid ({err})");
+                                        let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                                        build.error(&error);
+                                    }
+                                }
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'description', 'format', 'photo', 'price', 'stock' and 'url' are recognized inside a physical field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        match format {
+                            Some(format) => {
+                                let physical_edition = PhysicalEdition::new(format, order_url, photos, price, stock);
+                                local_options.physical_editions.push(physical_edition);
+                            }
+                            None => {
+                                let message = "physical needs a format attribute describing the edition, e.g. 'format = Vinyl LP'";
+                                let error = element_error_with_snippet(element, manifest_path, message);
+                                build.error(&error);
+                            }
+                        }
+
+                        break 'physical;
+                    }
+                }
+
+                let message = indoc!("
+                    physical needs to be provided as a field with attributes (one field for each physical edition), e.g.:
+
+                    physical:
+                    format = Vinyl LP, 180g
+                    price = 25 € plus shipping
+                    stock = available
+                    url = https://example.com/shop/vinyl/
+                    photo = vinyl.jpg
+                    description = The record leaning against its sleeve
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "publish_date" => 'publish_date: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                                Ok(date) => local_options.publish_date = Some(PublishDate::new(date, false)),
+                                Err(err) => {
+                                    let message = format!("Invalid publish date '{value}': {err}");
+                                    let error = element_error_with_snippet(element, manifest_path, &message);
+                                    build.error(&error);
+                                }
+                            }
+                        } else {
+                            local_options.publish_date = None;
+                        }
+
+                        break 'publish_date;
+                    } else if let Ok(attributes) = field.attributes() {
+                        let mut date = None;
+                        let mut unlisted_before = false;
+
+                        for attribute in attributes {
+                            match attribute.key() {
+                                "before" => {
+                                    match attribute.value() {
+                                        Some("excluded") => unlisted_before = false,
+                                        Some("unlisted") => unlisted_before = true,
+                                        _ => {
+                                            let message = "The value of the before attribute needs to be either 'excluded' or 'unlisted'";
+                                            let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                            build.error(&error);
+                                        }
+                                    }
+                                }
+                                "date" => {
+                                    if let Some(value) = attribute.value() {
+                                        match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                                            Ok(parsed_date) => date = Some(parsed_date),
+                                            Err(err) => {
+                                                let message = format!("Invalid publish date '{value}': {err}");
+                                                let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                                                build.error(&error);
+                                            }
+                                        }
+                                    }
+                                }
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'before' and 'date' are recognized inside a publish_date field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        match date {
+                            Some(date) => local_options.publish_date = Some(PublishDate::new(date, unlisted_before)),
+                            None => {
+                                let message = "publish_date needs a date attribute following the pattern YYYY-MM-DD, e.g. 'date = 2025-12-31'";
+                                let error = element_error_with_snippet(element, manifest_path, message);
+                                build.error(&error);
+                            }
+                        }
+
+                        break 'publish_date;
+                    }
+                }
+
+                let message = indoc!("
+                    publish_date needs to be provided either as a field with a date following the pattern YYYY-MM-DD, or as a field with attributes (to build the release as unlisted before that date), e.g.:
+
+                    publish_date: 2025-12-31
+
+                    publish_date:
+                    before = unlisted
+                    date = 2025-12-31
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "region" => 'region: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        local_options.region = result.map(|value| value.to_string());
+                        break 'region;
+                    }
+                }
+
+                let message = "region needs to be provided as a field with a value, e.g.: 'region: Vienna'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
             "release_artist" => 'release_artist: {
                 if let Ok(field) = element.as_field() {
                     if let Ok(result) = field.value() {
@@ -124,6 +606,51 @@ pub fn read_release_manifest(
                 let error = element_error_with_snippet(element, manifest_path, message);
                 build.error(&error);
             }
+            "secret_link" => 'secret_link: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                                Ok(date) => local_options.secret_link = Some(date),
+                                Err(err) => {
+                                    let message = format!("Invalid expiry date '{value}' for the secret link: {err}");
+                                    let error = element_error_with_snippet(element, manifest_path, &message);
+                                    build.error(&error);
+                                }
+                            }
+                        } else {
+                            local_options.secret_link = None;
+                        }
+
+                        break 'secret_link;
+                    }
+                }
+
+                let message = "secret_link needs to be provided as a field with the date on which the link expires, following the pattern YYYY-MM-DD, e.g.: 'secret_link: 2025-12-31'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "status" => 'status: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        match result {
+                            Some("draft") => local_options.draft = true,
+                            Some("published") | None => local_options.draft = false,
+                            Some(other) => {
+                                let message = format!("The value '{other}' is not recognized for the status option, allowed values are 'draft' and 'published'");
+                                let error = element_error_with_snippet(element, manifest_path, &message);
+                                build.error(&error);
+                            }
+                        }
+
+                        break 'status;
+                    }
+                }
+
+                let message = "status needs to be provided as a field with the value 'draft' or 'published', e.g.: 'status: draft'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
             "title" => 'title: {
                 if let Ok(field) = element.as_field() {
                     if let Ok(result) = field.value() {
@@ -148,9 +675,32 @@ pub fn read_release_manifest(
                     build.error(&error);
                 }
             }
+            "upc" => 'upc: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match normalize_upc(value) {
+                                Some(upc) => local_options.upc = Some(upc),
+                                None => {
+                                    let message = format!("'{value}' is not a valid UPC/EAN barcode (it needs to consist of 12 (UPC) or 13 (EAN) digits, the last of which is the check digit)");
+                                    let error = element_error_with_snippet(element, manifest_path, &message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'upc;
+                    }
+                }
+
+                let message = "upc needs to be provided as a field with a value, e.g.: 'upc: 036000291452'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
             _ if read_artist_catalog_release_option(build, element, manifest_path, overrides) => (),
             _ if read_artist_catalog_release_track_option(build, cache, element, local_options, manifest_path, overrides) => (),
             _ if read_artist_release_option(build, element, local_options, manifest_path, overrides) => (),
+            _ if read_artist_release_track_option(build, dir, element, local_options, manifest_path) => (),
             _ if read_catalog_release_option(build, catalog, element, manifest_path) => (),
             _ if read_release_track_option(build, cache, dir, element, local_options, manifest_path) => (),
             other => {
@@ -162,6 +712,7 @@ pub fn read_release_manifest(
                         ARTIST_CATALOG_RELEASE_OPTIONS,
                         ARTIST_CATALOG_RELEASE_TRACK_OPTIONS,
                         ARTIST_RELEASE_OPTIONS,
+                        ARTIST_RELEASE_TRACK_OPTIONS,
                         CATALOG_RELEASE_OPTIONS,
                         RELEASE_TRACK_OPTIONS
                     ]
@@ -4,26 +4,47 @@
 use std::fs;
 use std::path::Path;
 
+use indoc::indoc;
+
 use crate::{
+    Annotation,
     Build,
     Cache,
+    Chapter,
     LocalOptions,
-    Overrides
+    Lyrics,
+    Overrides,
+    RemoteSource,
+    Transcript,
+    VisualLoop,
+    VisualLoopSource
 };
+use crate::identifiers::normalize_isrc;
 
 use super::{
     ARTIST_CATALOG_RELEASE_TRACK_OPTIONS,
+    ARTIST_RELEASE_TRACK_OPTIONS,
     RELEASE_TRACK_OPTIONS,
+    attribute_error_with_snippet,
     element_error_with_snippet,
+    item_error_with_snippet,
     not_supported_error,
     platform_printer,
     read_artist_catalog_release_track_option,
+    read_artist_release_track_option,
     read_obsolete_option,
     read_release_track_option
 };
 
 const TRACK_OPTIONS: &[&str] = &[
-    "title"
+    "annotations",
+    "chapters",
+    "isrc",
+    "lyrics",
+    "remote_source",
+    "title",
+    "transcript",
+    "visual_loop"
 ];
 
 pub fn read_track_manifest(
@@ -43,6 +64,8 @@ pub fn read_track_manifest(
         }
     };
 
+    build.register_manifest(manifest_path, &content);
+
     let document = match enolib::parse_with_printer(&content, platform_printer()) {
         Ok(document) => document,
         Err(err) => {
@@ -55,6 +78,163 @@ pub fn read_track_manifest(
     for element in document.elements() {
         match element.key() {
             _ if read_obsolete_option(build, element, manifest_path) => (),
+            "annotations" => 'annotations: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(items) = field.items() {
+                        local_options.annotations.clear();
+
+                        for item in items.iter() {
+                            if let Some(value) = item.value() {
+                                match Annotation::parse(value) {
+                                    Some(annotation) => local_options.annotations.push(annotation),
+                                    None => {
+                                        let message = "An annotation needs to be given as a timecode (e.g. '90' or '1:30') followed by the text, e.g. '1:30 Guitar solo'";
+                                        let error = item_error_with_snippet(item, manifest_path, message);
+                                        build.error(&error);
+                                    }
+                                }
+                            }
+                        }
+
+                        local_options.annotations.sort_by(|a, b| a.seconds.total_cmp(&b.seconds));
+
+                        break 'annotations;
+                    }
+                }
+
+                let message = "annotations needs to be provided as a field containing items, e.g.:\n\nannotations:\n- 1:30 Guitar solo\n- 3:02 Sample credit: Alice";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "chapters" => 'chapters: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(items) = field.items() {
+                        local_options.chapters.clear();
+
+                        for item in items.iter() {
+                            if let Some(value) = item.value() {
+                                match Chapter::parse(value) {
+                                    Some(chapter) => local_options.chapters.push(chapter),
+                                    None => {
+                                        let message = "A chapter needs to be given as a timecode (e.g. '0' or '12:30') followed by the title, e.g. '12:30 Interview'";
+                                        let error = item_error_with_snippet(item, manifest_path, message);
+                                        build.error(&error);
+                                    }
+                                }
+                            }
+                        }
+
+                        local_options.chapters.sort_by(|a, b| a.seconds.total_cmp(&b.seconds));
+
+                        break 'chapters;
+                    }
+                }
+
+                let message = "chapters needs to be provided as a field containing items, e.g.:\n\nchapters:\n- 0:00 Introduction\n- 12:30 Interview";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "isrc" => 'isrc: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            match normalize_isrc(value) {
+                                Some(isrc) => local_options.isrc = Some(isrc),
+                                None => {
+                                    let message = format!("'{value}' is not a valid ISRC (it needs to consist of a two letter country code, a three character registrant code, two digits for the year and five digits for the recording, e.g. 'DE-A12-25-00001')");
+                                    let error = element_error_with_snippet(element, manifest_path, &message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        break 'isrc;
+                    }
+                }
+
+                let message = "isrc needs to be provided as a field with a value, e.g.: 'isrc: DE-A12-25-00001'";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "lyrics" => {
+                if let Ok(embed) = element.as_embed() {
+                    local_options.lyrics = embed.value().and_then(Lyrics::parse);
+                } else {
+                    let message = "lyrics needs to be provided as an embed, e.g.:\n-- lyrics\nFirst line\nSecond line\n-- lyrics\n\n... lines can also be prefixed with timestamps (LRC format), e.g. '[00:12.50]First line'";
+                    let error = element_error_with_snippet(element, manifest_path, message);
+                    build.error(&error);
+                }
+            }
+            "remote_source" => 'remote_source: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        if let Some(value) = result {
+                            if valid_remote_source_url(value) {
+                                local_options.remote_source = Some(RemoteSource::new(None, value.to_string()));
+                            } else {
+                                let message = "The remote source url needs to start with https://, http:// or s3://";
+                                let error = element_error_with_snippet(element, manifest_path, message);
+                                build.error(&error);
+                            }
+                        }
+
+                        break 'remote_source;
+                    } else if let Ok(attributes) = field.attributes() {
+                        let mut sha256 = None;
+                        let mut url = None;
+
+                        for attribute in attributes {
+                            match attribute.key() {
+                                "sha256" => {
+                                    if let Some(value) = attribute.value() {
+                                        if value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+                                            sha256 = Some(value.to_string());
+                                        } else {
+                                            let message = "The sha256 checksum needs to be given as 64 hexadecimal characters";
+                                            let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                            build.error(&error);
+                                        }
+                                    }
+                                }
+                                "url" => {
+                                    if let Some(value) = attribute.value() {
+                                        if valid_remote_source_url(value) {
+                                            url = Some(value.to_string());
+                                        } else {
+                                            let message = "The remote source url needs to start with https://, http:// or s3://";
+                                            let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                            build.error(&error);
+                                        }
+                                    }
+                                }
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'sha256' and 'url' are recognized inside a remote_source field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        if let Some(url) = url {
+                            local_options.remote_source = Some(RemoteSource::new(sha256, url));
+                        }
+
+                        break 'remote_source;
+                    }
+                }
+
+                let message = indoc!("
+                    remote_source needs to be provided as a field with a value or with attributes, e.g.:
+
+                    remote_source: s3://my-masters/intro.flac
+
+                    remote_source:
+                    sha256 = 9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08
+                    url = https://example.com/masters/intro.flac
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
             "title" => 'title: {
                 if let Ok(field) = element.as_field() {
                     if let Ok(result) = field.value() {
@@ -70,7 +250,112 @@ pub fn read_track_manifest(
                 let error = element_error_with_snippet(element, manifest_path, message);
                 build.error(&error);
             }
+            "transcript" => 'transcript: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(result) = field.value() {
+                        // The transcript is a path relative to the manifest
+                        if let Some(value) = result {
+                            let absolute_path = dir.join(value);
+                            if absolute_path.is_file() {
+                                match Transcript::read(&build.catalog_dir, &absolute_path) {
+                                    Ok(transcript) => local_options.transcript = Some(transcript),
+                                    Err(err) => {
+                                        let message = format!("{err} ({})", absolute_path.display());
+                                        let error = element_error_with_snippet(element, manifest_path, &message);
+                                        build.error(&error);
+                                    }
+                                }
+                            } else {
+                                let message = format!("The referenced file was not found ({})", absolute_path.display());
+                                let error = element_error_with_snippet(element, manifest_path, &message);
+                                build.error(&error);
+                            }
+                        }
+
+                        break 'transcript;
+                    }
+                }
+
+                let message = "transcript needs to be provided as a field with a value, e.g.: 'transcript: transcript.vtt' (WebVTT) or 'transcript: transcript.txt' (plain text)";
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
+            "visual_loop" => 'visual_loop: {
+                if let Ok(field) = element.as_field() {
+                    if let Ok(attributes) = field.attributes() {
+                        let mut description = None;
+                        let mut framerate = VisualLoop::DEFAULT_FRAMERATE;
+                        let mut path_relative_to_catalog = None;
+
+                        for attribute in attributes {
+                            match attribute.key() {
+                                "description" => {
+                                    if let Some(value) = attribute.value() {
+                                        description = Some(value.to_string());
+                                    }
+                                }
+                                "file" => {
+                                    // file is a path relative to the manifest
+                                    if let Some(value) = attribute.value() {
+                                        let absolute_path = dir.join(value);
+                                        if absolute_path.exists() {
+                                            path_relative_to_catalog = Some(absolute_path.strip_prefix(&build.catalog_dir).unwrap().to_path_buf());
+                                        } else {
+                                            let message = format!("The referenced file or directory was not found ({})", absolute_path.display());
+                                            let error = attribute_error_with_snippet(attribute, manifest_path, &message);
+                                            build.error(&error);
+                                        }
+                                    }
+                                }
+                                "framerate" => {
+                                    if let Some(value) = attribute.value() {
+                                        match value.parse::<u32>() {
+                                            Ok(number) if (1..=60).contains(&number) => framerate = number,
+                                            _ => {
+                                                let message = "The framerate needs to be given as frames per second, in the range 1-60, e.g. '12'";
+                                                let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                                build.error(&error);
+                                            }
+                                        }
+                                    }
+                                }
+                                _ => {
+                                    let message = "The key/name of this attribute was not recognized, only 'description', 'file' and 'framerate' are recognized inside a visual_loop field";
+                                    let error = attribute_error_with_snippet(attribute, manifest_path, message);
+                                    build.error(&error);
+                                }
+                            }
+                        }
+
+                        if let Some(path) = path_relative_to_catalog {
+                            // A directory is an image sequence, a file is a video
+                            let source = if build.catalog_dir.join(&path).is_dir() {
+                                VisualLoopSource::ImageSequence { framerate, path }
+                            } else {
+                                VisualLoopSource::Video(path)
+                            };
+
+                            local_options.visual_loop = Some(VisualLoop::new(description, source));
+                        }
+
+                        break 'visual_loop;
+                    }
+                }
+
+                let message = indoc!("
+                    visual_loop needs to be provided as a field with attributes, e.g.:
+
+                    visual_loop:
+                    description = Rain running down a window
+                    file = rain.mp4
+
+                    (file can also point to a directory of images, which are played back at 'framerate = 12' frames per second by default)
+                ");
+                let error = element_error_with_snippet(element, manifest_path, message);
+                build.error(&error);
+            }
             _ if read_artist_catalog_release_track_option(build, cache, element, local_options, manifest_path, overrides) => (),
+            _ if read_artist_release_track_option(build, dir, element, local_options, manifest_path) => (),
             _ if read_release_track_option(build, cache, dir, element, local_options, manifest_path) => (),
             other => {
                 let message = not_supported_error(
@@ -78,6 +363,7 @@ pub fn read_track_manifest(
                     other,
                     &[
                         ARTIST_CATALOG_RELEASE_TRACK_OPTIONS,
+                        ARTIST_RELEASE_TRACK_OPTIONS,
                         RELEASE_TRACK_OPTIONS,
                         TRACK_OPTIONS
                     ]
@@ -89,3 +375,9 @@ pub fn read_track_manifest(
         }
     }
 }
+
+fn valid_remote_source_url(url: &str) -> bool {
+    url.starts_with("http://") ||
+    url.starts_with("https://") ||
+    url.starts_with("s3://")
+}
@@ -0,0 +1,140 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Lightweight minification of the html, css and js we generate at build
+//! time, enabled through the catalog's `minify` option. The static parts of
+//! our css/js assets are already minified at compile time (when faircamp is
+//! built with the "minify" feature), so this only needs to take care of the
+//! content we generate dynamically. The approach is intentionally
+//! conservative: We only remove what is guaranteed to carry no meaning
+//! (indentation, empty lines, comments) and never restructure anything.
+
+/// Removes whitespace and comments that carry no meaning from css.
+/// Whitespace runs are collapsed to a single space, and removed entirely
+/// around braces, semicolons and commas, as well as after colons. Content
+/// inside strings is left untouched.
+pub fn css(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut pending_whitespace = false;
+
+    while let Some(character) = chars.next() {
+        match character {
+            '"' | '\'' => {
+                if pending_whitespace && !output.is_empty() {
+                    output.push(' ');
+                }
+                pending_whitespace = false;
+
+                output.push(character);
+
+                while let Some(string_character) = chars.next() {
+                    output.push(string_character);
+
+                    if string_character == '\\' {
+                        if let Some(escaped) = chars.next() {
+                            output.push(escaped);
+                        }
+                    } else if string_character == character {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+
+                let mut previous = None;
+                for comment_character in chars.by_ref() {
+                    if previous == Some('*') && comment_character == '/' {
+                        break;
+                    }
+                    previous = Some(comment_character);
+                }
+            }
+            '{' | '}' | ';' | ',' | ':' => {
+                // Whitespace before a colon can be significant in selectors
+                // (e.g. "div :first-child"), so we only remove it after one.
+                if character == ':' && pending_whitespace && !output.is_empty() {
+                    output.push(' ');
+                }
+                pending_whitespace = false;
+
+                // A trailing semicolon before a closing brace is redundant
+                if character == '}' && output.ends_with(';') {
+                    output.pop();
+                }
+
+                output.push(character);
+
+                while chars.peek().is_some_and(|next| next.is_whitespace()) {
+                    chars.next();
+                }
+            }
+            _ if character.is_whitespace() => {
+                pending_whitespace = true;
+            }
+            _ => {
+                if pending_whitespace && !output.is_empty() && !output.ends_with(['{', '}', ';', ',', ':']) {
+                    output.push(' ');
+                }
+                pending_whitespace = false;
+
+                output.push(character);
+            }
+        }
+    }
+
+    output
+}
+
+/// Removes indentation and empty lines from html. Line breaks themselves are
+/// kept, as they can be significant between inline elements. The content of
+/// <pre> and <textarea> elements is left untouched.
+pub fn html(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut preformatted = false;
+
+    for line in input.lines() {
+        if preformatted {
+            output.push_str(line);
+            output.push('\n');
+        } else {
+            let trimmed = line.trim();
+
+            if !trimmed.is_empty() {
+                output.push_str(trimmed);
+                output.push('\n');
+            }
+        }
+
+        let line_lowercase = line.to_lowercase();
+        let opening = line_lowercase.rfind("<pre").max(line_lowercase.rfind("<textarea"));
+        let closing = line_lowercase.rfind("</pre>").max(line_lowercase.rfind("</textarea>"));
+
+        match (opening, closing) {
+            (Some(opening), Some(closing)) => preformatted = opening > closing,
+            (Some(_), None) => preformatted = true,
+            (None, Some(_)) => preformatted = false,
+            (None, None) => ()
+        }
+    }
+
+    output
+}
+
+/// Removes indentation, empty lines and full-line comments from js. Line
+/// breaks are kept so that automatic semicolon insertion is never affected.
+pub fn js(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+
+    for line in input.lines() {
+        let trimmed = line.trim();
+
+        if !trimmed.is_empty() && !trimmed.starts_with("//") {
+            output.push_str(trimmed);
+            output.push('\n');
+        }
+    }
+
+    output
+}
@@ -4,6 +4,22 @@
 use crate::{Build, Catalog};
 use crate::util::html_escape_inside_attribute;
 
+/// Dimensions (in pixels) of the embedded track player, as announced in
+/// twitter player cards (see EMBED_INLINE_STYLE in render.rs)
+const PLAYER_HEIGHT: u32 = 50;
+const PLAYER_WIDTH: u32 = 480;
+
+/// Audio for pages of tracks with embedding enabled, which allows social
+/// apps that support it to show an inline playable card for shared links.
+pub struct OpenGraphAudio {
+    /// MIME type of the streaming file, e.g. "audio/mpeg"
+    pub content_type: String,
+    /// Url of the embed page (rendered inside twitter player cards)
+    pub player_url: String,
+    /// Url of the primary streaming file
+    pub url: String
+}
+
 pub struct OpenGraphImage {
     pub height: u32,
     pub url: String,
@@ -12,6 +28,7 @@ pub struct OpenGraphImage {
 
 /// For the Open Graph specification see https://ogp.me/
 pub struct OpenGraphMeta {
+    audio: Option<OpenGraphAudio>,
     description: Option<String>,
     image: Option<OpenGraphImage>,
     image_alt: Option<String>,
@@ -20,6 +37,10 @@ pub struct OpenGraphMeta {
 }
 
 impl OpenGraphMeta {
+    pub fn audio(&mut self, audio: OpenGraphAudio) {
+        self.audio = Some(audio);
+    }
+
     pub fn description(&mut self, description: &str) {
         self.description = Some(description.to_string());
     }
@@ -34,6 +55,7 @@ impl OpenGraphMeta {
 
     pub fn new(title: String, url: String) -> OpenGraphMeta {
         OpenGraphMeta {
+            audio: None,
             description: None,
             image: None,
             image_alt: None,
@@ -45,6 +67,15 @@ impl OpenGraphMeta {
     pub fn tags(&self, build: &Build, catalog: &Catalog) -> String {
         let mut tags = Vec::new();
 
+        if let Some(audio) = &self.audio {
+            let content_type_escaped = html_escape_inside_attribute(&audio.content_type);
+            tags.push(format!(r#"<meta property="og:audio" content="{}"/>"#, audio.url));
+            if audio.url.starts_with("https://") {
+                tags.push(format!(r#"<meta property="og:audio:secure_url" content="{}"/>"#, audio.url));
+            }
+            tags.push(format!(r#"<meta property="og:audio:type" content="{content_type_escaped}"/>"#));
+        }
+
         if let Some(description) = &self.description {
             let description_escaped = html_escape_inside_attribute(description);
             tags.push(format!(r#"<meta property="og:description" content="{description_escaped}"/>"#));
@@ -72,6 +103,24 @@ impl OpenGraphMeta {
         tags.push(String::from(r#"<meta property="og:type" content="website"/>"#));
         tags.push(format!(r#"<meta property="og:url" content="{}"/>"#, self.url));
 
+        // Player cards are only rendered for players served over https, and
+        // require an image (see https://developer.x.com/en/docs/x-for-websites/cards/overview/player-card)
+        if let Some(audio) = &self.audio {
+            if audio.player_url.starts_with("https://") {
+                if let Some(image) = &self.image {
+                    let content_type_escaped = html_escape_inside_attribute(&audio.content_type);
+                    tags.push(String::from(r#"<meta name="twitter:card" content="player"/>"#));
+                    tags.push(format!(r#"<meta name="twitter:image" content="{}"/>"#, image.url));
+                    tags.push(format!(r#"<meta name="twitter:player" content="{}"/>"#, audio.player_url));
+                    tags.push(format!(r#"<meta name="twitter:player:height" content="{PLAYER_HEIGHT}"/>"#));
+                    tags.push(format!(r#"<meta name="twitter:player:stream" content="{}"/>"#, audio.url));
+                    tags.push(format!(r#"<meta name="twitter:player:stream:content_type" content="{content_type_escaped}"/>"#));
+                    tags.push(format!(r#"<meta name="twitter:player:width" content="{PLAYER_WIDTH}"/>"#));
+                    tags.push(format!(r#"<meta name="twitter:title" content="{title_escaped}"/>"#));
+                }
+            }
+        }
+
         tags.join("\n")
     }
 }
@@ -1,11 +1,50 @@
 // SPDX-FileCopyrightText: 2022-2025 Simon Repp
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use std::fs;
+use std::hash::Hash;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use nanoid::nanoid;
 use slug::slugify;
 
-use crate::{ArtistRc, ReleaseRc};
+use crate::{
+    ArtistRc,
+    Build,
+    FORMAT_COMPARISON_DIRNAME,
+    Playlist,
+    ReleaseRc,
+    VISUAL_LOOP_DIRNAME
+};
 use crate::util::uid;
 
+/// How conflicts between permalinks (two pages competing for the same url)
+/// are handled, configured through the catalog's `permalink_conflicts` option.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PermalinkConflicts {
+    /// Auto-generated permalinks that are in conflict get a numeric suffix
+    /// (-2, -3, ...), only conflicts between user-assigned permalinks remain
+    /// an error.
+    Auto,
+    /// Every conflict is an error that aborts the build (the default)
+    Error
+}
+
+/// Holds the random key that is mixed into the slugs of secret links. It is
+/// stored in the cache directory, so it is never deployed along with the site.
+pub const SECRET_LINK_KEY_CACHE_FILENAME: &str = "secret_link.key";
+
+/// Directories that are placed alongside the downloads, purchase and unlock
+/// pages inside release and track directories (track directories themselves
+/// are numeric, these are checked for separately).
+const RESERVED_RELEASE_DIRNAMES: &[&str] = &[
+    "embed",
+    "extras",
+    FORMAT_COMPARISON_DIRNAME,
+    VISUAL_LOOP_DIRNAME
+];
+
 #[derive(Clone, Debug)]
 pub struct Permalink {
     pub generated: bool,
@@ -14,9 +53,34 @@ pub struct Permalink {
 
 pub enum PermalinkUsage<'a> {
     Artist(&'a ArtistRc),
+    Playlist(&'a Playlist),
     Release(&'a ReleaseRc)
 }
 
+/// Overrides for the reserved permalink segments that otherwise come from
+/// the translations for the catalog's language (e.g. the "downloads" in
+/// /my-release/downloads/...), configured through the catalog's
+/// `permalink_terms` option. This allows sites to keep an existing url
+/// scheme regardless of the language the interface is in.
+#[derive(Clone, Debug, Default)]
+pub struct PermalinkTerms {
+    pub downloads: Option<String>,
+    pub image_descriptions: Option<String>,
+    pub purchase: Option<String>,
+    pub subscribe: Option<String>,
+    pub unlock: Option<String>
+}
+
+impl PermalinkConflicts {
+    pub fn from_manifest_key(key: &str) -> Option<PermalinkConflicts> {
+        match key {
+            "auto" => Some(PermalinkConflicts::Auto),
+            "error" => Some(PermalinkConflicts::Error),
+            _ => None
+        }
+    }
+}
+
 impl Permalink {
     pub fn generate(non_slug: &str) -> Permalink {
         Permalink {
@@ -42,6 +106,27 @@ impl Permalink {
         }
     }
 
+    /// For releases that are shared through a secret link, the slug is a
+    /// hash of the release directory and the expiry date, combined with a
+    /// random key that is generated on first use and kept in the cache
+    /// (see [SECRET_LINK_KEY_CACHE_FILENAME]). Without the key, the slug can
+    /// therefore not be derived from anything that is publicly known.
+    pub fn secret(build: &Build, expires: NaiveDate, release_dir: &Path) -> Permalink {
+        let key = secret_link_key(&build.cache_dir);
+
+        let slug = build.hash_with_salt(|hasher| {
+            release_dir.hash(hasher);
+            "secret_link".hash(hasher);
+            key.hash(hasher);
+            expires.hash(hasher);
+        });
+
+        Permalink {
+            generated: false,
+            slug
+        }
+    }
+
     pub fn uid() -> Permalink {
         Permalink {
             generated: false,
@@ -50,6 +135,49 @@ impl Permalink {
     }
 }
 
+impl PermalinkTerms {
+    /// Stores the overrides in the build's locale (where they take precedence
+    /// over the permalink translations), then checks that the resulting
+    /// terms do not collide with each other or with other directories placed
+    /// alongside them. This needs to happen after the catalog manifest has
+    /// been read in its entirety, as the language (and thereby the locale
+    /// that is overridden) can be set anywhere in it.
+    pub fn apply(&self, build: &mut Build) {
+        build.locale.permalink_terms = self.clone();
+
+        let locale = &build.locale;
+
+        let mut errors = Vec::new();
+
+        // These are placed inside release and track directories
+        let release_terms = [
+            ("downloads", locale.downloads_permalink()),
+            ("purchase", locale.purchase_permalink()),
+            ("unlock", locale.unlock_permalink())
+        ];
+
+        for (index, (key, term)) in release_terms.iter().enumerate() {
+            if let Some((other_key, _)) = release_terms[..index].iter().find(|(_, other_term)| other_term == term) {
+                errors.push(format!("The permalink terms for '{other_key}' and '{key}' are both '{term}', they need to be different from each other (use the catalog's permalink_terms option to change one of them)."));
+            } else if RESERVED_RELEASE_DIRNAMES.iter().any(|dirname| dirname == term) || term.chars().all(|c| c.is_ascii_digit()) {
+                errors.push(format!("The permalink term '{term}' (for '{key}') can not be used, as it collides with directories that faircamp itself places inside release and track directories."));
+            }
+        }
+
+        // These are placed at the root of the site
+        let image_descriptions_term = locale.image_descriptions_permalink();
+        let subscribe_term = locale.subscribe_permalink();
+
+        if image_descriptions_term == subscribe_term {
+            errors.push(format!("The permalink terms for 'image_descriptions' and 'subscribe' are both '{subscribe_term}', they need to be different from each other (use the catalog's permalink_terms option to change one of them)."));
+        }
+
+        for error in errors {
+            build.error(&error);
+        }
+    }
+}
+
 impl PermalinkUsage<'_> {
     pub fn as_string(&self) -> String {
         match self {
@@ -63,6 +191,16 @@ impl PermalinkUsage<'_> {
 
                 format!("The artist '{name}' also has the {generated_or_assigned} permalink '{slug}'")
             }
+            PermalinkUsage::Playlist(playlist) => {
+                let permalink = &playlist.permalink;
+
+                let generated_or_assigned = permalink.generated_or_assigned_str();
+                let playlist_dir = playlist.source_dir.display();
+                let slug = &permalink.slug;
+                let title = &playlist.title;
+
+                format!("The playlist '{title}' from directory '{playlist_dir}' also has the {generated_or_assigned} permalink '{slug}'")
+            }
             PermalinkUsage::Release(release) => {
                 let release_ref = release.borrow();
                 let permalink = &release_ref.permalink;
@@ -77,3 +215,19 @@ impl PermalinkUsage<'_> {
         }
     }
 }
+
+/// Reads the key for secret links from the cache, or generates and stores it
+/// there if there is none yet.
+fn secret_link_key(cache_dir: &Path) -> String {
+    let path = cache_dir.join(SECRET_LINK_KEY_CACHE_FILENAME);
+
+    if let Ok(key) = fs::read_to_string(&path) {
+        if !key.is_empty() {
+            return key;
+        }
+    }
+
+    let key = nanoid!(32);
+    fs::write(&path, &key).unwrap();
+    key
+}
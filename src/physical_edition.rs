@@ -0,0 +1,62 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::DescribedImage;
+
+/// Configured through `physical` fields in the release manifest (one for
+/// each edition, e.g. a vinyl and a tape edition), presented in their own
+/// section on the release page. Faircamp does not handle orders itself, it
+/// only links to where the edition can be ordered.
+#[derive(Clone, Debug)]
+pub struct PhysicalEdition {
+    /// E.g. "Vinyl LP, 180g, gatefold sleeve"
+    pub format: String,
+    /// External page (or e.g. a mailto: link) through which the edition
+    /// can be ordered
+    pub order_url: Option<String>,
+    pub photos: Vec<DescribedImage>,
+    /// Free text, e.g. "25 € plus shipping"
+    pub price: Option<String>,
+    pub stock: Stock
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stock {
+    Available,
+    SoldOut
+}
+
+impl PhysicalEdition {
+    pub fn new(
+        format: String,
+        order_url: Option<String>,
+        photos: Vec<DescribedImage>,
+        price: Option<String>,
+        stock: Stock
+    ) -> PhysicalEdition {
+        PhysicalEdition {
+            format,
+            order_url,
+            photos,
+            price,
+            stock
+        }
+    }
+
+    /// The photos are written to the release directory with this prefix in
+    /// front of the asset filenames, e.g. "physical_1_2_" for the second
+    /// photo of the first edition (numbers start at 1).
+    pub fn photo_filename_prefix(edition_number: usize, photo_number: usize) -> String {
+        format!("physical_{edition_number}_{photo_number}_")
+    }
+}
+
+impl Stock {
+    pub fn from_manifest_key(key: &str) -> Option<Stock> {
+        match key {
+            "available" => Some(Stock::Available),
+            "sold_out" => Some(Stock::SoldOut),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,110 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Machine-readable player configuration of a release (tracks, streaming
+//! urls, durations, waveform peaks, theme colors), for people who build
+//! native apps or custom players against their faircamp site. The structure
+//! is versioned through the "schema" field, which is bumped whenever a
+//! backwards-incompatible change is made.
+
+use crate::{Build, Release, SiteUrl, TRACK_NUMBERS};
+use crate::m3u::{release_cover_url, track_format_url};
+use crate::util::{json_escape, json_optional_number, json_string_array};
+
+pub const PLAYER_CONFIG_FILENAME: &str = "player.json";
+
+const PLAYER_CONFIG_SCHEMA: &str = "faircamp-player-config/1";
+
+/// Generate the complete content of the player configuration for a release
+pub fn generate_for_release(
+    base_url: &SiteUrl,
+    build: &Build,
+    release: &Release
+) -> String {
+    let release_slug = &release.permalink.slug;
+    let theme = &release.theme;
+
+    let release_artists = release.main_artists
+        .iter()
+        .map(|artist| artist.borrow().name.clone())
+        .collect::<Vec<String>>();
+
+    let tracks = release.tracks
+        .iter()
+        .zip(TRACK_NUMBERS)
+        .map(|(track, track_number)| {
+            let track_artists = track.artists
+                .iter()
+                .map(|artist| artist.borrow().name.clone())
+                .collect::<Vec<String>>();
+
+            let transcodes_ref = track.transcodes.borrow();
+
+            let annotations = track.annotations
+                .iter()
+                .map(|annotation| {
+                    format!(
+                        "{{ \"text\": \"{}\", \"time\": {:.3} }}",
+                        json_escape(&annotation.text),
+                        annotation.seconds
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            let peaks = transcodes_ref.source_meta.peaks
+                .iter()
+                .map(|peak| format!("{:.3}", peak.min(1.0)))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            let sources = track.streaming_quality
+                .formats()
+                .iter()
+                .map(|format| {
+                    format!(
+                        "        {{ \"type\": \"{}\", \"url\": \"{}\" }}",
+                        json_escape(format.source_type()),
+                        json_escape(&track_format_url(build, *format, release, track, track_number))
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(",\n");
+
+            let gain = match track.volume_normalization {
+                true => track.normalization_gain().map(|gain| format!("{gain:.3}")),
+                false => None
+            };
+
+            format!(
+                "    {{\n      \"annotations\": [{annotations}],\n      \"artists\": {},\n      \"duration\": {:.3},\n      \"gain\": {},\n      \"number\": {track_number},\n      \"peaks\": [{peaks}],\n      \"sources\": [\n{sources}\n      ],\n      \"title\": \"{}\",\n      \"url\": \"{}\"\n    }}",
+                json_string_array(&track_artists),
+                transcodes_ref.source_meta.duration_seconds,
+                json_optional_number(gain),
+                json_escape(&track.title()),
+                json_escape(&base_url.join_index(build, format!("{release_slug}/{track_number}")))
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",\n");
+
+    let r_theme = format!(
+        "{{\n    \"accent_brightening\": {},\n    \"accent_chroma\": {},\n    \"accent_hue\": {},\n    \"base\": \"{}\",\n    \"base_chroma\": {},\n    \"base_hue\": {},\n    \"dynamic_range\": {},\n    \"waveforms\": {}\n  }}",
+        theme.accent_brightening,
+        json_optional_number(theme.accent_chroma),
+        json_optional_number(theme.accent_hue),
+        theme.base.to_key(),
+        theme.base_chroma,
+        theme.base_hue,
+        theme.dynamic_range,
+        theme.waveforms
+    );
+
+    format!(
+        "{{\n  \"artists\": {},\n  \"cover\": \"{}\",\n  \"schema\": \"{PLAYER_CONFIG_SCHEMA}\",\n  \"theme\": {r_theme},\n  \"title\": \"{}\",\n  \"tracks\": [\n{tracks}\n  ],\n  \"url\": \"{}\"\n}}\n",
+        json_string_array(&release_artists),
+        json_escape(&release_cover_url(build, release)),
+        json_escape(&release.title),
+        json_escape(&base_url.join_index(build, release_slug))
+    )
+}
@@ -0,0 +1,70 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::path::PathBuf;
+
+use crate::{Permalink, Release, ReleaseRc, Track};
+
+/// A curated selection of tracks across releases (e.g. a label sampler or
+/// a mood playlist), defined through a playlist.eno manifest and rendered
+/// as its own page with a combined player.
+#[derive(Debug)]
+pub struct Playlist {
+    pub permalink: Permalink,
+    /// The tracks as listed in the manifest - pairs of release permalink and
+    /// (1-based) track number. These can only be resolved into `tracks` once
+    /// the entire catalog is read and all release permalinks are final.
+    pub references: Vec<(String, usize)>,
+    /// Relative path of the playlist directory in the catalog directory.
+    /// This is used to augment permalink conflict errors with additional
+    /// info for resolving the conflict.
+    pub source_dir: PathBuf,
+    pub synopsis: Option<String>,
+    pub title: String,
+    pub tracks: Vec<PlaylistTrack>
+}
+
+/// A track on a playlist, addressed through its release
+#[derive(Debug)]
+pub struct PlaylistTrack {
+    pub release: ReleaseRc,
+    /// 1-based, as shown in the tracklist of the release
+    pub track_number: usize
+}
+
+impl Playlist {
+    pub fn longest_track_duration(&self) -> f32 {
+        let mut longest_track_duration = 0.0;
+        for playlist_track in &self.tracks {
+            let release_ref = playlist_track.release.borrow();
+            let duration_seconds = &playlist_track.track(&release_ref).transcodes.borrow().source_meta.duration_seconds;
+            if *duration_seconds > longest_track_duration {
+                longest_track_duration = *duration_seconds;
+            }
+        }
+        longest_track_duration
+    }
+
+    pub fn new(
+        permalink: Permalink,
+        references: Vec<(String, usize)>,
+        source_dir: PathBuf,
+        synopsis: Option<String>,
+        title: String
+    ) -> Playlist {
+        Playlist {
+            permalink,
+            references,
+            source_dir,
+            synopsis,
+            title,
+            tracks: Vec::new()
+        }
+    }
+}
+
+impl PlaylistTrack {
+    pub fn track<'a>(&self, release: &'a Release) -> &'a Track {
+        &release.tracks[self.track_number - 1]
+    }
+}
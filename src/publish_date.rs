@@ -0,0 +1,25 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use chrono::NaiveDate;
+
+/// Configured through the release manifest's `publish_date` option. Until
+/// the date is reached, the release is left out of the build entirely, or
+/// (if so configured) built as an unlisted release, so that it can already
+/// be shared with a few people ahead of its publication.
+#[derive(Clone, Debug)]
+pub struct PublishDate {
+    pub date: NaiveDate,
+    /// Whether the release is built as unlisted before the date (instead of
+    /// being left out of the build)
+    pub unlisted_before: bool
+}
+
+impl PublishDate {
+    pub fn new(date: NaiveDate, unlisted_before: bool) -> PublishDate {
+        PublishDate {
+            date,
+            unlisted_before
+        }
+    }
+}
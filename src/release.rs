@@ -8,9 +8,10 @@ use std::fs;
 use std::fs::File;
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::io::prelude::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::ops::RangeFrom;
+use std::time::Instant;
 
 use chrono::NaiveDate;
 use sanitize_filename::sanitize;
@@ -18,9 +19,12 @@ use serde_derive::{Serialize, Deserialize};
 use zip::{CompressionMethod, ZipWriter};
 use zip::write::SimpleFileOptions;
 
-use crate::M3U_PLAYLIST_FILENAME;
+use crate::{ICS_FILENAME, JSON_API_RELEASE_FILENAME, M3U_PLAYLIST_FILENAME, PLAYER_CONFIG_FILENAME};
+use crate::image::is_svg;
 use crate::{
     Archive,
+    ArchiveCover,
+    ArchiveLayout,
     ArchivesRc,
     ArtistRc,
     Asset,
@@ -28,22 +32,45 @@ use crate::{
     Build,
     Cache,
     Catalog,
+    ContentWarning,
+    CoverAspectRatio,
+    CoverGenerator,
     DescribedImage,
     DownloadAccess,
     DownloadFormat,
     ExtraDownloads,
     FileMeta,
+    FormatComparison,
     HtmlAndStripped,
     Link,
+    Locale,
+    Localizations,
+    PaymentPointer,
     Permalink,
+    PhysicalEdition,
     ProceduralCoverRc,
+    ReleaseSection,
+    ShareButton,
+    SiteAsset,
+    SiteMetadata,
+    SiteUrl,
     TagMapping,
     Theme,
     Track,
-    TrackNumbering
+    TrackNumbering,
+    WarningCategory
 };
-use crate::{m3u, render, util};
-use crate::util::{deduplicate_filename, generic_hash};
+use crate::{ics, json_api, m3u, player_config, render, site_metadata, util};
+use crate::render::ReleaseContext;
+use crate::util::{deduplicate_filename, format_bytes, generic_hash};
+
+/// The filename under which an SVG cover is shipped as is (see
+/// [Release::cover_svg])
+pub const COVER_SVG_FILENAME: &str = "cover.svg";
+
+/// Original downloads of tracks larger than this (in bytes) trigger a
+/// warning, as they might take visitors very long to download.
+const ORIGINAL_DOWNLOAD_WARNING_SIZE: u64 = 250 * 1024 * 1024;
 
 /// An unbounded iterator returning track numbers (1, 2, 3, ..) which
 /// we generally use with ".zip(TRACK_NUMBERS)" to augment an iteration
@@ -67,6 +94,15 @@ fn deduplicate_extra_filename(candidate_filename: &str, used_filenames: &HashSet
     filename
 }
 
+/// The filename under which the original (unprocessed) cover image is
+/// included in release archives, e.g. "cover_original.png".
+fn original_cover_filename(source_path: &Path) -> String {
+    match source_path.extension() {
+        Some(extension) => format!("cover_original.{}", extension.to_string_lossy().to_lowercase()),
+        None => String::from("cover_original")
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Extra {
     pub file_meta: FileMeta,
@@ -81,20 +117,61 @@ pub struct Release {
     /// archives depends on [Release] itself). Eventually this is guaranteed to
     /// exist though, in the later phases of the build process.
     pub archives: Option<ArchivesRc>,
+    /// Cover size(s) included in the release archives and embedded into
+    /// the audio files of downloads
+    pub archive_cover: ArchiveCover,
+    /// Folder structure inside the release archives
+    pub archive_layout: ArchiveLayout,
     /// Generated when we gathered all artist and title metadata.
     /// Used to compute the download asset filenames.
     pub asset_basename: Option<String>,
+    /// Acknowledged by visitors in an interstitial before the release and
+    /// track pages are shown
+    pub content_warning: Option<ContentWarning>,
     pub copy_link: bool,
     pub cover: Option<DescribedImage>,
+    /// Whether the cover is cropped to a square on the release page and in
+    /// the release archive, or retains its original aspect ratio
+    pub cover_aspect_ratio: CoverAspectRatio,
+    /// Whether an SVG cover is (additionally to the rasterized versions)
+    /// shipped as is, and used for display on the release page
+    pub cover_svg_original: bool,
+    /// Seconds by which the end of a track and the beginning of the next
+    /// one overlap in the player
+    pub crossfade: Option<f32>,
     pub date: Option<NaiveDate>,
     pub download_access: DownloadAccess,
     pub download_formats: Vec<DownloadFormat>,
+    /// Set through `status: draft`, draft releases are only built with
+    /// --include-drafts, and then marked as a draft preview on their pages
+    pub draft: bool,
+    /// Whether the embedded players show a button that links to the
+    /// purchase page (or the release page) on the site
+    pub embed_support_button: bool,
     pub embedding: bool,
     pub extra_downloads: ExtraDownloads,
     /// Additional files that are included in the download archive,
     /// such as additional images, liner notes, etc.
     pub extras: Vec<Extra>,
+    /// Clips of an excerpt in all download formats, for comparing them on
+    /// the download page
+    pub format_comparison: Option<FormatComparison>,
+    /// Whether the player plays the tracks without pauses in between, and
+    /// downloads are tagged for gapless playback
+    pub gapless: bool,
+    /// Filename (inside the release directory) of the clip that is played
+    /// back when hovering/focusing the release on the homepage, set when
+    /// the clip is written (only if hover previews are enabled).
+    pub hover_preview: Option<String>,
+    /// The label/imprint the release was published on, used for the
+    /// release filters on the homepage (on sites hosting several labels)
+    pub imprint: Option<String>,
+    /// Order (and visibility) of the sections on the release page
+    pub layout: Vec<ReleaseSection>,
     pub links: Vec<Link>,
+    /// Translated more/synopsis/title texts (from release.[language].eno
+    /// overlays), swapped in while the language sites are built
+    pub localizations: Localizations,
     /// The artists that are the principal authors of a release ("Album Artist" in tag lingo)
     pub main_artists: Vec<ArtistRc>,
     /// The order in which we encounter artists and releases when reading the
@@ -110,9 +187,29 @@ pub struct Release {
     /// Optional custom label for the button that (by default) says "More" on the
     /// release page and points to additional long-form content for the release.
     pub more_label: Option<String>,
+    /// Whether search engines are asked not to index the release page
+    /// and its track pages (through the search_engines option)
+    pub noindex: bool,
+    /// Files that are written next to the release page, to be referenced
+    /// from page_metadata
+    pub page_assets: Vec<SiteAsset>,
+    /// Markup that is injected into the <head> of the release page only
+    pub page_metadata: Option<SiteMetadata>,
+    /// For Web Monetization of the streaming pages
+    pub payment_pointer: Option<PaymentPointer>,
     pub permalink: Permalink,
+    /// Vinyl, tape, CD, etc. editions, presented in their own section on
+    /// the release page
+    pub physical_editions: Vec<PhysicalEdition>,
+    /// Emitted as categories of the release's item in the podcast rss feed
+    pub podcast_categories: Vec<String>,
     /// Lazily generated when there is no regular cover
     pub procedural_cover: Option<ProceduralCoverRc>,
+    /// Geographic region (scene, city, country, ...) the release is
+    /// associated with, also used for the release filters on the homepage
+    pub region: Option<String>,
+    /// Services for which share buttons are displayed on the release page
+    pub share_buttons: Vec<ShareButton>,
     /// Relative path of the release directory in the catalog directory.
     /// This is used to augment permalink conflict errors with additional
     /// info for resolving the conflict.
@@ -126,6 +223,10 @@ pub struct Release {
     pub synopsis: Option<String>,
     pub theme: Theme,
     pub title: String,
+    /// Set when another (public) release has the same title, in which case
+    /// the artists or year of the release are appended to its title where
+    /// releases are listed side by side (see Catalog::disambiguate_titles)
+    pub title_disambiguation: Option<String>,
     pub track_numbering: TrackNumbering,
     /// The order of tracks (and derived from this the track numbers) are
     /// authoritative, i.e. when the release is constructed, tracks are
@@ -136,7 +237,13 @@ pub struct Release {
     /// increase in steps of 1 some unexpected or random track ordering and numbering
     /// might happen, but this is somewhat impossible to avoid.
     pub tracks: Vec<Track>,
-    pub unlisted: bool
+    pub unlisted: bool,
+    /// Set through the release manifest's upc option (UPC or EAN barcode),
+    /// written into the tags of transcodes
+    pub upc: Option<String>,
+    /// Whether players apply the pre-computed gain of each track (and let
+    /// the visitor toggle this) so that all tracks play at a similar loudness
+    pub volume_normalization: bool
 }
 
 #[derive(Clone, Debug)]
@@ -177,62 +284,94 @@ impl Release {
             .map(|described_image| described_image.borrow().cover_160_filename_unchecked())
     }
 
+    /// Returns the cover if it is an SVG that should be shipped as is (see
+    /// cover_svg_original), in which case it is written to the release
+    /// directory as [COVER_SVG_FILENAME].
+    pub fn cover_svg(&self) -> Option<&DescribedImage> {
+        self.cover
+            .as_ref()
+            .filter(|described_image| self.cover_svg_original && is_svg(&described_image.file_meta.path))
+    }
+
+    /// Computes the signature by which cached release archives are found.
+    /// This must depend on exactly what ends up in the archives (audio, tags,
+    /// covers, extras and the names of all files and folders inside the
+    /// archive) and on nothing else - content that only appears on the
+    /// release page (synopsis, theme, links, etc.) must never cause
+    /// archives to be zipped anew.
+    ///
     /// It is critical that every last detail of this hashing implementation
     /// stays the same - unless explicitly needed of course - because this signature
     /// makes or breaks finding cached archives.
-    pub fn get_or_create_release_archives(&mut self, cache: &mut Cache) {
-        match &self.download_access {
-            DownloadAccess::Code { .. } |
-            DownloadAccess::Free |
-            DownloadAccess::Paycurtain { .. } => {
-                if self.download_assets_available() {
-                    let mut hasher = DefaultHasher::new();
+    pub fn archive_signature(&self, locale: &Locale) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        if let Some(described_image) = &self.cover {
+            // The image description is not used for building release archives,
+            // so we only hash the image itself
+            described_image.hash(&mut hasher);
+            self.cover_aspect_ratio.hash(&mut hasher);
+        }
 
-                    // TODO: Consider further if there are aspects of the dependency graph missing
-                    //       that need to be included in the hash signature.
-                    // TODO: Are the filenames represented at all? Should they? (With which filename
-                    //       the tracks and extras and cover are written into the zip)
+        // Only hashed when set, so that archives built before the option
+        // existed are still found in the cache.
+        if self.archive_cover != ArchiveCover::DEFAULT {
+            self.archive_cover.hash(&mut hasher);
+        }
 
-                    if let Some(described_image) = &self.cover {
-                        // The image description is not used for building release archives,
-                        // so we only hash the image itself
-                        described_image.hash(&mut hasher);
-                    }
+        if self.extra_downloads.bundled && !self.extras.is_empty() {
+            // There is no relevant order for extras, they are just included in the zip as
+            // files. For hashing we need to ensure a stable order, and as there is no such
+            // guarantee coming from where they are initialized, we sort them here.
+            let mut extras_sorted = self.extras.clone();
+            extras_sorted.sort_by(|a, b| a.sanitized_filename.cmp(&b.sanitized_filename));
+            extras_sorted.hash(&mut hasher);
+        }
 
-                    if self.extra_downloads.bundled && !self.extras.is_empty() {
-                        // There is no relevant order for extras, they are just included in the zip as
-                        // files. For hashing we need to ensure a stable order, and as there is no such
-                        // guarantee coming from where they are initialized, we sort them here.
-                        let mut extras_sorted = self.extras.clone();
-                        extras_sorted.sort_by(|a, b| a.sanitized_filename.cmp(&b.sanitized_filename));
-                        extras_sorted.hash(&mut hasher);
-                    }
+        self.archive_layout.hash(&mut hasher);
 
-                    self.title.hash(&mut hasher);
+        // The release folder and all track filenames inside the archive are
+        // derived from the asset basenames.
+        self.asset_basename.hash(&mut hasher);
 
-                    // TODO: TrackNumbering could also be part of signature (how the files are numbered in the filename!)
-                    for (track, track_number) in self.tracks.iter().zip(TRACK_NUMBERS) {
-                        let tag_mapping = TagMapping::new(self, track, track_number);
+        // Labels used for disc folders and track extra folders
+        locale.translations.disc_xxx("").hash(&mut hasher);
+        (*locale.translations.extras).hash(&mut hasher);
 
-                        tag_mapping.hash(&mut hasher);
-                        track.transcodes.borrow().hash.hash(&mut hasher);
+        for (track, track_number) in self.tracks.iter().zip(TRACK_NUMBERS) {
+            let tag_mapping = TagMapping::new(self, track, track_number);
 
-                        if let Some(described_image) = &track.cover {
-                            // The image description is not used so we only hash the image itself
-                            described_image.hash(&mut hasher);
-                        }
+            tag_mapping.hash(&mut hasher);
+            track.asset_basename.hash(&mut hasher);
+            track.transcodes.borrow().hash.hash(&mut hasher);
 
-                        if self.extra_downloads.bundled && track.extra_downloads && !track.extras.is_empty() {
-                            // There is no relevant order for extras, they are just included in the zip as
-                            // files. For hashing we need to ensure a stable order, and as there is no such
-                            // guarantee coming from where they are initialized, we sort them here.
-                            let mut extras_sorted = track.extras.clone();
-                            extras_sorted.sort_by(|a, b| a.sanitized_filename.cmp(&b.sanitized_filename));
-                            extras_sorted.hash(&mut hasher);
-                        }
-                    }
+            if let Some(described_image) = &track.cover {
+                // The image description is not used so we only hash the image itself
+                described_image.hash(&mut hasher);
+            }
 
-                    let signature = hasher.finish();
+            if self.extra_downloads.bundled && track.extra_downloads && !track.extras.is_empty() {
+                // There is no relevant order for extras, they are just included in the zip as
+                // files. For hashing we need to ensure a stable order, and as there is no such
+                // guarantee coming from where they are initialized, we sort them here.
+                let mut extras_sorted = track.extras.clone();
+                extras_sorted.sort_by(|a, b| a.sanitized_filename.cmp(&b.sanitized_filename));
+                extras_sorted.hash(&mut hasher);
+            }
+        }
+
+        hasher.finish()
+    }
+
+    /// Must be called after asset basenames were computed, as these are
+    /// part of the archive signature.
+    pub fn get_or_create_release_archives(&mut self, build: &Build, cache: &mut Cache) {
+        match &self.download_access {
+            DownloadAccess::Code { .. } |
+            DownloadAccess::Free |
+            DownloadAccess::Paycurtain { .. } => {
+                if self.download_assets_available() {
+                    let signature = self.archive_signature(&build.locale);
 
                     self.archives = Some(cache.get_or_create_archives(signature));
                 }
@@ -258,21 +397,57 @@ impl Release {
         longest_track_duration
     }
 
+    /// Whether a direct link to the downloads is revealed while Web
+    /// Monetization is active (only applies to downloads that are otherwise
+    /// behind a paycurtain - the download page of code-protected downloads
+    /// must never be linked to from a public page).
+    pub fn monetization_unlocks_downloads(&self) -> bool {
+        self.payment_pointer
+            .as_ref()
+            .is_some_and(|payment_pointer| payment_pointer.unlock_downloads) &&
+        self.download_assets_available() &&
+        matches!(
+            self.download_access,
+            DownloadAccess::Paycurtain { payment_info: Some(_), .. }
+        )
+    }
+
     pub fn new(
+        archive_cover: ArchiveCover,
+        archive_layout: ArchiveLayout,
+        content_warning: Option<ContentWarning>,
         copy_link: bool,
         cover: Option<DescribedImage>,
+        cover_aspect_ratio: CoverAspectRatio,
+        cover_svg_original: bool,
+        crossfade: Option<f32>,
         date: Option<NaiveDate>,
         download_access: DownloadAccess,
         download_formats: Vec<DownloadFormat>,
+        draft: bool,
+        embed_support_button: bool,
         embedding: bool,
         extra_downloads: ExtraDownloads,
         extras: Vec<Extra>,
+        format_comparison: Option<FormatComparison>,
+        gapless: bool,
+        imprint: Option<String>,
+        layout: Vec<ReleaseSection>,
         links: Vec<Link>,
+        localizations: Localizations,
         m3u: bool,
         main_artists_to_map: Vec<String>,
         more: Option<HtmlAndStripped>,
         more_label: Option<String>,
+        noindex: bool,
+        page_assets: Vec<SiteAsset>,
+        page_metadata: Option<SiteMetadata>,
+        payment_pointer: Option<PaymentPointer>,
         permalink: Option<Permalink>,
+        physical_editions: Vec<PhysicalEdition>,
+        podcast_categories: Vec<String>,
+        region: Option<String>,
+        share_buttons: Vec<ShareButton>,
         source_dir: PathBuf,
         speed_controls: bool,
         support_artists_to_map: Vec<String>,
@@ -281,29 +456,53 @@ impl Release {
         title: String,
         track_numbering: TrackNumbering,
         tracks: Vec<Track>,
-        unlisted: bool
+        unlisted: bool,
+        upc: Option<String>,
+        volume_normalization: bool
     ) -> Release {
         let permalink = permalink.unwrap_or_else(|| Permalink::generate(&title));
 
         Release {
             archives: None,
+            archive_cover,
+            archive_layout,
             asset_basename: None,
+            content_warning,
             copy_link,
             cover,
+            cover_aspect_ratio,
+            cover_svg_original,
+            crossfade,
             date,
             download_access,
             download_formats,
+            draft,
+            embed_support_button,
             embedding,
             extra_downloads,
             extras,
+            format_comparison,
+            gapless,
+            hover_preview: None,
+            imprint,
+            layout,
             links,
+            localizations,
             m3u,
             main_artists: Vec::new(),
             main_artists_to_map,
             more,
             more_label,
+            noindex,
+            page_assets,
+            page_metadata,
+            payment_pointer,
             permalink,
+            physical_editions,
+            podcast_categories,
             procedural_cover: None,
+            region,
+            share_buttons,
             source_dir,
             speed_controls,
             support_artists: Vec::new(),
@@ -311,9 +510,12 @@ impl Release {
             synopsis,
             theme,
             title,
+            title_disambiguation: None,
             track_numbering,
             tracks,
-            unlisted
+            unlisted,
+            upc,
+            volume_normalization
         }
     }
 
@@ -344,10 +546,108 @@ impl Release {
             .filename_720()
     }
 
+    /// Computes the signature by which cached procedural covers are found.
+    /// This must depend on exactly the data the cover generators draw with
+    /// (the generator, the theme base, the durations and peaks of the tracks
+    /// and - only for the generator that scales with it - the maximum number
+    /// of tracks on any release in the catalog) and on nothing else, so that
+    /// e.g. editing the synopsis never causes covers to be generated anew.
+    pub fn procedural_cover_signature(
+        &self,
+        cover_generator: &CoverGenerator,
+        max_tracks_in_release: usize
+    ) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        cover_generator.hash(&mut hasher);
+
+        if matches!(cover_generator, CoverGenerator::LooneyTunes) {
+            max_tracks_in_release.hash(&mut hasher);
+        }
+
+        self.theme.base.hash(&mut hasher);
+
+        self.tracks.len().hash(&mut hasher);
+        for track in &self.tracks {
+            let source_meta = &track.transcodes.borrow().source_meta;
+
+            // f32 does not implement Hash, we hash the bit patterns instead
+            source_meta.duration_seconds.to_bits().hash(&mut hasher);
+            for peak in &source_meta.peaks {
+                peak.to_bits().hash(&mut hasher);
+            }
+        }
+
+        hasher.finish()
+    }
+
     pub fn procedural_cover_unchecked(&self) -> &ProceduralCoverRc {
         self.procedural_cover.as_ref().unwrap()
     }
 
+    /// Computes the signature by which the cached rendered pages of the
+    /// release are found. Everything that is configured through manifests
+    /// (as well as build settings and the state of the catalog as a whole)
+    /// is covered by site_signature, here we add what comes from the files
+    /// in the release directory: audio, covers, extras and page assets, and
+    /// the source files of visual loops (which are encoded while rendering).
+    pub fn render_signature(&self, build: &Build, site_signature: u64) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        site_signature.hash(&mut hasher);
+
+        self.permalink.slug.hash(&mut hasher);
+        self.source_dir.hash(&mut hasher);
+
+        // Upcoming releases are rendered differently until their release date
+        if self.scheduled_date(build).is_some() {
+            build.build_begin.date_naive().hash(&mut hasher);
+        }
+
+        if let Some(described_image) = &self.cover {
+            described_image.hash(&mut hasher);
+        }
+
+        self.extras.hash(&mut hasher);
+
+        for page_asset in &self.page_assets {
+            page_asset.hash.hash(&mut hasher);
+        }
+
+        for physical_edition in &self.physical_editions {
+            for described_image in &physical_edition.photos {
+                described_image.hash(&mut hasher);
+            }
+        }
+
+        for track in &self.tracks {
+            track.transcodes.file_meta.hash(&mut hasher);
+            track.transcodes.borrow().hash.hash(&mut hasher);
+
+            if let Some(described_image) = &track.cover {
+                described_image.hash(&mut hasher);
+            }
+
+            track.extras.hash(&mut hasher);
+
+            for page_asset in &track.page_assets {
+                page_asset.hash.hash(&mut hasher);
+            }
+
+            if let Some(transcript) = &track.transcript {
+                transcript.content.hash(&mut hasher);
+            }
+        }
+
+        hasher.finish()
+    }
+
+    /// Returns the release date if it lies in the future (relative to the
+    /// time of the build), i.e. if the release is still upcoming.
+    pub fn scheduled_date(&self, build: &Build) -> Option<NaiveDate> {
+        self.date.filter(|date| *date > build.build_begin.date_naive())
+    }
+
     pub fn shortest_track_duration(&self) -> f32 {
         let mut shortest_track_duration = f32::INFINITY;
         for track in &self.tracks {
@@ -359,6 +659,15 @@ impl Release {
         shortest_track_duration
     }
 
+    /// The title, with the disambiguation appended if there is one (e.g.
+    /// "Demo (2019)"), for use where releases are listed side by side.
+    pub fn title_disambiguated(&self) -> String {
+        match &self.title_disambiguation {
+            Some(disambiguation) => format!("{} ({disambiguation})", self.title),
+            None => self.title.clone()
+        }
+    }
+
     /// Returns true if there is at least one track on this release on
     /// which the artist(s) differ from the other tracks.
     pub fn varying_track_artists(&self) -> bool {
@@ -382,12 +691,81 @@ impl Release {
     /// downloads (separate track audio files, covers and extras) to the build
     /// directory.
     pub fn write_downloadable_files(&mut self, build: &mut Build) {
+        let release_originals = self.download_formats.contains(&DownloadFormat::Original) &&
+            !matches!(self.download_access, DownloadAccess::Disabled | DownloadAccess::External { .. });
+
+        for track in &self.tracks {
+            let track_originals = track.download_formats.contains(&DownloadFormat::Original) &&
+                !matches!(track.download_access, DownloadAccess::Disabled | DownloadAccess::External { .. });
+            let size = track.transcodes.file_meta.size;
+
+            if (release_originals || track_originals) && size > ORIGINAL_DOWNLOAD_WARNING_SIZE {
+                let warning = format!(
+                    "Track {} is offered as an original download, which is a {} file - consider offering a compressed format (e.g. 'flac') instead, or additionally.",
+                    &track.transcodes.file_meta.path.display(),
+                    format_bytes(size)
+                );
+                build.warning(WarningCategory::OversizedOriginal, &warning);
+            }
+        }
+
         let tag_mappings: Vec<TagMapping> = self.tracks
             .iter()
             .zip(TRACK_NUMBERS)
             .map(|(track, track_number)| TagMapping::new(self, track, track_number))
             .collect();
 
+        // Transcode track downloads ahead, running several ffmpeg processes
+        // at a time, so that below they only need to be copied.
+        let mut download_formats: Vec<DownloadFormat> = Vec::new();
+        for track in &self.tracks {
+            if !matches!(track.download_access, DownloadAccess::Code { .. } | DownloadAccess::Free | DownloadAccess::Paycurtain { .. }) {
+                continue;
+            }
+
+            for download_format in &track.download_formats {
+                if !download_formats.contains(download_format) {
+                    download_formats.push(*download_format);
+                }
+            }
+        }
+
+        for download_format in download_formats {
+            let mut pending = Vec::new();
+
+            for (track, tag_mapping) in self.tracks.iter_mut().zip(tag_mappings.iter()) {
+                if !matches!(track.download_access, DownloadAccess::Code { .. } | DownloadAccess::Free | DownloadAccess::Paycurtain { .. }) ||
+                    !track.download_formats.contains(&download_format) ||
+                    track.transcodes.borrow().has(download_format.as_audio_format(), generic_hash(&tag_mapping)) {
+                    continue;
+                }
+
+                if download_format.is_lossless() && !track.transcodes.borrow().source_meta.lossless {
+                    let warning = format!(
+                        "Track {} comes from a lossy source format, offering it in a lossless download format is somewhat wasteful and misleading to those who will download it.",
+                        &track.transcodes.file_meta.path.display()
+                    );
+                    build.warning(WarningCategory::LossyToLossless, &warning);
+                }
+
+                let cover_path = match &track.cover {
+                    Some(described_image) => Some(self.archive_cover.embed_path(build, CoverAspectRatio::Square, described_image)),
+                    None => self.cover
+                        .as_ref()
+                        .map(|described_image| self.archive_cover.embed_path(build, self.cover_aspect_ratio, described_image))
+                };
+
+                pending.push((track, tag_mapping, cover_path));
+            }
+
+            let tracks = pending
+                .iter_mut()
+                .map(|(track, tag_mapping, cover_path)| (&mut **track, *tag_mapping, cover_path.as_ref()))
+                .collect();
+
+            Track::transcode_in_parallel(build, AssetIntent::Deliverable, download_format.as_audio_format(), tracks);
+        }
+
         // Transcode and copy track downloads
         for ((track, tag_mapping), track_number) in self.tracks.iter_mut().zip(tag_mappings.iter()).zip(TRACK_NUMBERS) {
             match track.download_access {
@@ -399,16 +777,12 @@ impl Release {
                     for download_format in track_download_formats {
                         // Transcode track to download format (to cache) if not yet available
                         if !track.transcodes.borrow().has(download_format.as_audio_format(), generic_hash(&tag_mapping)) {
-                            if download_format.is_lossless() && !track.transcodes.borrow().source_meta.lossless {
-                                warn_discouraged!(
-                                    "Track {} comes from a lossy source format, offering it in a lossless download format is somewhat wasteful and misleading to those who will download it.",
-                                    &track.transcodes.file_meta.path.display()
-                                );
-                            }
-
-                            let cover_path = track.cover.as_ref().or(self.cover.as_ref())
-                                .as_ref()
-                                .map(|described_image| build.catalog_dir.join(&described_image.file_meta.path));
+                            let cover_path = match &track.cover {
+                                Some(described_image) => Some(self.archive_cover.embed_path(build, CoverAspectRatio::Square, described_image)),
+                                None => self.cover
+                                    .as_ref()
+                                    .map(|described_image| self.archive_cover.embed_path(build, self.cover_aspect_ratio, described_image))
+                            };
 
                             track.transcode_as(
                                 download_format.as_audio_format(),
@@ -431,7 +805,7 @@ impl Release {
                         let track_filename = format!(
                             "{basename}{extension}",
                             basename = track.asset_basename.as_ref().unwrap(),
-                            extension = download_format.as_audio_format().extension()
+                            extension = track.download_extension(download_format.as_audio_format())
                         );
 
                         let hash = build.hash_with_salt(|hasher| {
@@ -517,6 +891,8 @@ impl Release {
                             download_format.as_audio_format()
                         );
 
+                        let zipping_begin = Instant::now();
+
                         let zip_file = File::create(build.cache_dir.join(&cached_archive_filename)).unwrap();
                         let mut zip_writer = ZipWriter::new(zip_file);
                         let options = SimpleFileOptions::default()
@@ -527,18 +903,40 @@ impl Release {
 
                         let mut used_filenames_release_level = HashSet::new();
 
+                        // All paths inside the archive are prefixed with this
+                        let root_dir = if self.archive_layout.release_folder {
+                            let release_dir = format!("{}/", self.asset_basename.as_ref().unwrap());
+                            zip_writer.add_directory(&release_dir, options).unwrap();
+                            release_dir
+                        } else {
+                            String::new()
+                        };
+
+                        // Disc folders are only used if the tracks actually span multiple discs
+                        let disc_numbers = self.tracks
+                            .iter()
+                            .map(|track| track.transcodes.borrow().source_meta.disc_number)
+                            .collect::<HashSet<Option<u32>>>();
+                        let disc_folders = self.archive_layout.disc_folders && disc_numbers.len() > 1;
+                        let mut added_disc_dirs = HashSet::new();
+
                         for (track, tag_mapping) in self.tracks.iter_mut().zip(tag_mappings.iter()) {
                             // Transcode track to download format (to cache) if not yet available
                             if !track.transcodes.borrow().has(download_format.as_audio_format(), generic_hash(&tag_mapping)) {
                                 if download_format.is_lossless() && !track.transcodes.borrow().source_meta.lossless {
-                                    warn_discouraged!(
+                                    let warning = format!(
                                         "Track {} comes from a lossy source format, offering it in a lossless download format is somewhat wasteful and misleading to those who will download it.",
                                         &track.transcodes.file_meta.path.display()
                                     );
+                                    build.warning(WarningCategory::LossyToLossless, &warning);
                                 }
 
-                                let cover_path = track.cover.as_ref().or(self.cover.as_ref())
-                                    .map(|described_image| build.catalog_dir.join(&described_image.file_meta.path));
+                                let cover_path = match &track.cover {
+                                    Some(described_image) => Some(self.archive_cover.embed_path(build, CoverAspectRatio::Square, described_image)),
+                                    None => self.cover
+                                        .as_ref()
+                                        .map(|described_image| self.archive_cover.embed_path(build, self.cover_aspect_ratio, described_image))
+                                };
 
                                 track.transcode_as(
                                     download_format.as_audio_format(),
@@ -554,14 +952,34 @@ impl Release {
                             let transcodes_ref = track.transcodes.borrow();
                             let transcode = transcodes_ref.get_unchecked(download_format.as_audio_format(), generic_hash(&tag_mapping));
 
+                            let track_basename = track.asset_basename.as_ref().unwrap();
+
+                            let disc_dir = match transcodes_ref.source_meta.disc_number {
+                                Some(disc_number) if disc_folders => {
+                                    let disc_dir = format!("{}/", build.locale.translations.disc_xxx(&disc_number.to_string()));
+                                    if added_disc_dirs.insert(disc_dir.clone()) {
+                                        zip_writer.add_directory(format!("{root_dir}{disc_dir}"), options).unwrap();
+                                    }
+                                    disc_dir
+                                }
+                                _ => String::new()
+                            };
+
+                            let track_dir = if self.archive_layout.track_folders {
+                                let track_dir = format!("{disc_dir}{track_basename}/");
+                                zip_writer.add_directory(format!("{root_dir}{track_dir}"), options).unwrap();
+                                track_dir
+                            } else {
+                                disc_dir.clone()
+                            };
+
                             let filename = format!(
-                                "{basename}{extension}",
-                                basename = track.asset_basename.as_ref().unwrap(),
-                                extension = download_format.as_audio_format().extension()
+                                "{track_basename}{extension}",
+                                extension = track.download_extension(download_format.as_audio_format())
                             );
 
-                            zip_writer.start_file(&*filename, options).unwrap();
-                            used_filenames_release_level.insert(filename);
+                            zip_writer.start_file(format!("{root_dir}{track_dir}{filename}"), options).unwrap();
+                            used_filenames_release_level.insert(format!("{track_dir}{filename}"));
 
                             let mut zip_inner_file = File::open(
                                 build.cache_dir.join(&transcode.asset.filename)
@@ -573,21 +991,31 @@ impl Release {
 
                             track.transcodes.borrow().persist_to_cache(&build.cache_dir);
 
-                            // Write track cover and/or extras to a subdirectory named like the track
-                            if track.cover.is_some() ||
-                                (self.extra_downloads.bundled && track.extra_downloads && !track.extras.is_empty()) {
+                            // Write track cover and/or extras - into the track's own
+                            // folder if there is one, otherwise to a subdirectory
+                            // named like the track. In track folders we fall back
+                            // to the release cover, so that every folder has one.
+                            let track_cover = match self.archive_layout.track_folders {
+                                true => track.cover.as_ref().or(self.cover.as_ref()),
+                                false => track.cover.as_ref()
+                            };
+                            let track_extras = self.extra_downloads.bundled && track.extra_downloads && !track.extras.is_empty();
+
+                            if track_cover.is_some() || track_extras {
                                 let mut used_filenames_track_level = HashSet::new();
 
-                                let t_extras = &build.locale.translations.extras;
-                                let extra_dirname = format!(
-                                    "{basename} ({t_extras})",
-                                    basename = track.asset_basename.as_ref().unwrap()
-                                );
-
-                                zip_writer.add_directory(&extra_dirname, options).unwrap();
+                                let extra_dir = if self.archive_layout.track_folders {
+                                    used_filenames_track_level.insert(filename.clone());
+                                    track_dir.clone()
+                                } else {
+                                    let t_extras = &build.locale.translations.extras;
+                                    let extra_dir = format!("{disc_dir}{track_basename} ({t_extras})/");
+                                    zip_writer.add_directory(format!("{root_dir}{extra_dir}"), options).unwrap();
+                                    extra_dir
+                                };
 
                                 // Write track cover
-                                if let Some(described_image) = &mut track.cover {
+                                if let Some(described_image) = track_cover {
                                     let mut image_mut = described_image.borrow_mut();
                                     let source_path = &described_image.file_meta.path;
 
@@ -602,32 +1030,56 @@ impl Release {
                                     // removal.
                                     let cover_assets = image_mut.cover_assets(build, source_path);
 
+                                    let cover_asset = match self.archive_cover.max_edge_size {
+                                        Some(max_edge_size) => cover_assets.largest_up_to(max_edge_size),
+                                        None => cover_assets.largest()
+                                    };
+
                                     let cover_filename = String::from("cover.jpg");
-                                    let cover_path = format!("{extra_dirname}/{cover_filename}");
+                                    let cover_path = format!("{root_dir}{extra_dir}{cover_filename}");
 
                                     zip_writer.start_file(cover_path, options).unwrap();
                                     used_filenames_track_level.insert(cover_filename);
 
                                     let mut zip_inner_file = File::open(
-                                        build.cache_dir.join(&cover_assets.largest().filename)
+                                        build.cache_dir.join(&cover_asset.filename)
                                     ).unwrap();
 
                                     zip_inner_file.read_to_end(&mut buffer).unwrap();
                                     zip_writer.write_all(&buffer).unwrap();
                                     buffer.clear();
 
+                                    // The original is only included for the track's
+                                    // own cover, not for the release cover that
+                                    // track folders fall back to.
+                                    if self.archive_cover.original && track.cover.is_some() {
+                                        let original_filename = original_cover_filename(source_path);
+                                        let original_path = format!("{root_dir}{extra_dir}{original_filename}");
+
+                                        zip_writer.start_file(original_path, options).unwrap();
+                                        used_filenames_track_level.insert(original_filename);
+
+                                        let mut zip_inner_file = File::open(
+                                            build.catalog_dir.join(source_path)
+                                        ).unwrap();
+
+                                        zip_inner_file.read_to_end(&mut buffer).unwrap();
+                                        zip_writer.write_all(&buffer).unwrap();
+                                        buffer.clear();
+                                    }
+
                                     image_mut.persist_to_cache(&build.cache_dir);
                                 }
 
                                 // Write track extras
-                                if self.extra_downloads.bundled && track.extra_downloads {
+                                if track_extras {
                                     for extra in &track.extras {
                                         let extra_filename = deduplicate_extra_filename(
                                             &extra.sanitized_filename,
                                             &used_filenames_track_level
                                         );
 
-                                        let extra_path = format!("{extra_dirname}/{extra_filename}");
+                                        let extra_path = format!("{root_dir}{extra_dir}{extra_filename}");
 
                                         zip_writer.start_file(extra_path, options).unwrap();
                                         used_filenames_track_level.insert(extra_filename);
@@ -658,21 +1110,44 @@ impl Release {
                             // AssetIntent::Intermediate, i.e. immediately
                             // beginning its decay in the cache for future
                             // removal.
-                            let cover_assets = image_mut.cover_assets(build, source_path);
+                            let cover_assets = match self.cover_aspect_ratio {
+                                CoverAspectRatio::Original => image_mut.cover_assets_uncropped(build, source_path),
+                                CoverAspectRatio::Square => image_mut.cover_assets(build, source_path)
+                            };
+
+                            let cover_asset = match self.archive_cover.max_edge_size {
+                                Some(max_edge_size) => cover_assets.largest_up_to(max_edge_size),
+                                None => cover_assets.largest()
+                            };
 
                             let cover_filename = String::from("cover.jpg");
 
-                            zip_writer.start_file(&*cover_filename, options).unwrap();
+                            zip_writer.start_file(format!("{root_dir}{cover_filename}"), options).unwrap();
                             used_filenames_release_level.insert(cover_filename);
 
                             let mut zip_inner_file = File::open(
-                                build.cache_dir.join(&cover_assets.largest().filename)
+                                build.cache_dir.join(&cover_asset.filename)
                             ).unwrap();
 
                             zip_inner_file.read_to_end(&mut buffer).unwrap();
                             zip_writer.write_all(&buffer).unwrap();
                             buffer.clear();
 
+                            if self.archive_cover.original {
+                                let original_filename = original_cover_filename(source_path);
+
+                                zip_writer.start_file(format!("{root_dir}{original_filename}"), options).unwrap();
+                                used_filenames_release_level.insert(original_filename);
+
+                                let mut zip_inner_file = File::open(
+                                    build.catalog_dir.join(source_path)
+                                ).unwrap();
+
+                                zip_inner_file.read_to_end(&mut buffer).unwrap();
+                                zip_writer.write_all(&buffer).unwrap();
+                                buffer.clear();
+                            }
+
                             image_mut.persist_to_cache(&build.cache_dir);
                         }
 
@@ -683,7 +1158,7 @@ impl Release {
                                     &used_filenames_release_level
                                 );
 
-                                zip_writer.start_file(&*extra_filename, options).unwrap();
+                                zip_writer.start_file(format!("{root_dir}{extra_filename}"), options).unwrap();
                                 used_filenames_release_level.insert(extra_filename);
 
                                 let mut zip_inner_file = File::open(
@@ -703,6 +1178,8 @@ impl Release {
                             }
                             Err(err) => panic!("{}", err)
                         };
+
+                        build.timings.record("Zipping", zipping_begin);
                     }
 
                     // Copy the zip archive (from cache) to the build
@@ -771,18 +1248,61 @@ impl Release {
         }
     }
 
+    /// Renders the embeddable players for the release and its tracks (where
+    /// embedding is enabled). In a regular build the pages that present the
+    /// embed codes are rendered alongside them, in an embeds-only build the
+    /// embed codes are instead listed on the index page of the build.
+    pub fn write_embed_pages(&self, base_url: &SiteUrl, build: &Build, catalog: &Catalog) {
+        let release_dir = build.build_dir.join(&self.permalink.slug);
+
+        // Render release embed pages
+        if self.embedding {
+            let release_embed_codes_dir = release_dir.join("embed");
+
+            if !build.embeds_only {
+                let release_embed_codes_html = render::release_embed_codes::release_embed_codes_html(base_url, &ReleaseContext::new(build, catalog, self));
+                util::ensure_dir_all_and_write_index(&release_embed_codes_dir, &release_embed_codes_html);
+            }
+
+            let release_embed_dir = release_embed_codes_dir.join("all");
+            let release_embed_html = render::release_embed::release_embed_html(base_url, build, catalog, self);
+            util::ensure_dir_all_and_write_index(&release_embed_dir, &release_embed_html);
+        }
+
+        // Render track embed pages
+        for (track, track_number) in self.tracks.iter().zip(TRACK_NUMBERS) {
+            if track.embedding {
+                if !build.embeds_only {
+                    let track_embed_codes_dir = release_dir.join(track_number.to_string()).join("embed");
+                    let track_embed_codes_html = render::track_embed_codes::track_embed_codes_html(base_url, &ReleaseContext::new(build, catalog, self).track(track, track_number));
+                    util::ensure_dir_all_and_write_index(&track_embed_codes_dir, &track_embed_codes_html);
+                }
+
+                let track_embed_dir = release_dir.join("embed").join(track_number.to_string());
+                let track_embed_html = render::track_embed::track_embed_html(base_url, build, self, track, track_number);
+                util::ensure_dir_all_and_write_index(&track_embed_dir, &track_embed_html);
+            }
+        }
+    }
+
     pub fn write_pages_and_playlist_files(&self, build: &mut Build, catalog: &Catalog) {
         // Render release page
         let release_dir = build.build_dir.join(&self.permalink.slug);
-        let release_html = render::release::release_html(build, catalog, self);
+        let release_html = render::release::release_html(&ReleaseContext::new(build, catalog, self));
         util::ensure_dir_all_and_write_index(&release_dir, &release_html);
 
+        // Render calendar file for upcoming releases
+        if let Some(date) = self.scheduled_date(build) {
+            let r_ics = ics::generate_for_release(build, catalog, date, self);
+            fs::write(release_dir.join(ICS_FILENAME), r_ics).unwrap();
+        }
+
         // Render release download/purchase/unlock page
         if !self.download_formats.is_empty() ||
            ((self.extra_downloads.bundled || self.extra_downloads.separate) && !self.extras.is_empty()) {
             match &self.download_access {
                 DownloadAccess::Code { download_codes, unlock_info } => {
-                    let t_unlock_permalink = *build.locale.translations.unlock_permalink;
+                    let t_unlock_permalink = build.locale.unlock_permalink();
                     let unlock_page_hash = build.hash_with_salt(|hasher| {
                         self.permalink.slug.hash(hasher);
                         t_unlock_permalink.hash(hasher);
@@ -793,11 +1313,11 @@ impl Release {
                         .join(t_unlock_permalink)
                         .join(unlock_page_hash);
 
-                    let unlock_html = render::release_unlock::release_unlock_html(build, catalog, self, unlock_info);
+                    let unlock_html = render::release_unlock::release_unlock_html(&ReleaseContext::new(build, catalog, self), unlock_info);
                     util::ensure_dir_all_and_write_index(&unlock_page_dir, &unlock_html);
 
-                    let download_html = render::release_download::release_download_html(build, catalog, self);
-                    let t_downloads_permalink = *build.locale.translations.downloads_permalink;
+                    let download_html = render::release_download::release_download_html(&ReleaseContext::new(build, catalog, self));
+                    let t_downloads_permalink = build.locale.downloads_permalink();
 
                     let download_dir = build.build_dir
                         .join(&self.permalink.slug)
@@ -807,12 +1327,23 @@ impl Release {
                         let code_dir = download_dir.join(code);
                         util::ensure_dir_all_and_write_index(&code_dir, &download_html);
                     }
+
+                    // The paycurtain already writes this page, with download
+                    // codes it's only needed for Web Monetization.
+                    if self.monetization_unlocks_downloads() {
+                        let download_page_hash = build.hash_with_salt(|hasher| {
+                            self.permalink.slug.hash(hasher);
+                            t_downloads_permalink.hash(hasher);
+                        });
+
+                        util::ensure_dir_all_and_write_index(&download_dir.join(download_page_hash), &download_html);
+                    }
                 }
                 DownloadAccess::Disabled => (),
                 DownloadAccess::External { .. } => (),
                 DownloadAccess::Free  => {
-                    let download_html = render::release_download::release_download_html(build, catalog, self);
-                    let t_downloads_permalink = *build.locale.translations.downloads_permalink;
+                    let download_html = render::release_download::release_download_html(&ReleaseContext::new(build, catalog, self));
+                    let t_downloads_permalink = build.locale.downloads_permalink();
 
                     let download_page_hash = build.hash_with_salt(|hasher| {
                         self.permalink.slug.hash(hasher);
@@ -828,7 +1359,7 @@ impl Release {
                 }
                 DownloadAccess::Paycurtain { payment_info, price } => {
                     if let Some(payment_info) = payment_info {
-                        let t_purchase_permalink = *build.locale.translations.purchase_permalink;
+                        let t_purchase_permalink = build.locale.purchase_permalink();
                         let purchase_page_hash = build.hash_with_salt(|hasher| {
                             self.permalink.slug.hash(hasher);
                             t_purchase_permalink.hash(hasher);
@@ -839,11 +1370,11 @@ impl Release {
                             .join(t_purchase_permalink)
                             .join(purchase_page_hash);
 
-                        let purchase_html = render::release_purchase::release_purchase_html(build, catalog, payment_info, price, self);
+                        let purchase_html = render::release_purchase::release_purchase_html(&ReleaseContext::new(build, catalog, self), payment_info, price);
                         util::ensure_dir_all_and_write_index(&purchase_page_dir, &purchase_html);
 
-                        let download_html = render::release_download::release_download_html(build, catalog, self);
-                        let t_downloads_permalink = *build.locale.translations.downloads_permalink;
+                        let download_html = render::release_download::release_download_html(&ReleaseContext::new(build, catalog, self));
+                        let t_downloads_permalink = build.locale.downloads_permalink();
 
                         let download_page_hash = build.hash_with_salt(|hasher| {
                             self.permalink.slug.hash(hasher);
@@ -857,54 +1388,44 @@ impl Release {
 
                         util::ensure_dir_all_and_write_index(&download_page_dir, &download_html);
                     } else {
-                        warn!(
+                        let warning = format!(
                             "No payment info specified for release '{}', no purchase/download option will be displayed for this release.",
                             self.title
                         );
+                        build.warning(WarningCategory::MissingPaymentInfo, &warning);
                     }
                 }
             }
         }
 
         if let Some(base_url) = &build.base_url {
+            // Render json api document
+            if catalog.json_api {
+                let r_json = json_api::generate_for_release(base_url, build, catalog, self);
+                fs::write(release_dir.join(JSON_API_RELEASE_FILENAME), r_json).unwrap();
+            }
+
             // Render m3u playlist
             if self.m3u {
-                let r_m3u = m3u::generate_for_release(base_url, build, self);
+                let r_m3u = m3u::generate_for_release(build, self);
                 fs::write(release_dir.join(M3U_PLAYLIST_FILENAME), r_m3u).unwrap();
             }
 
-            // Render release embed pages
-            if self.embedding {
-                let release_embed_codes_dir = release_dir.join("embed");
-                let release_embed_codes_html = render::release_embed_codes::release_embed_codes_html(base_url, build, catalog, self);
-                util::ensure_dir_all_and_write_index(&release_embed_codes_dir, &release_embed_codes_html);
-
-                if self.embedding {
-                    let release_embed_dir = release_embed_codes_dir.join("all");
-                    let release_embed_html = render::release_embed::release_embed_html(base_url, build, catalog, self);
-                    util::ensure_dir_all_and_write_index(&release_embed_dir, &release_embed_html);
-                }
+            // Render player configuration
+            if catalog.player_config {
+                let r_player_config = player_config::generate_for_release(base_url, build, self);
+                fs::write(release_dir.join(PLAYER_CONFIG_FILENAME), r_player_config).unwrap();
             }
 
-            // Render track embed pages
-            for (track, track_number) in self.tracks.iter().zip(TRACK_NUMBERS) {
-                if track.embedding {
-                    let track_embed_codes_dir = release_dir.join(track_number.to_string()).join("embed");
-                    let track_embed_codes_html = render::track_embed_codes::track_embed_codes_html(base_url, build, catalog, self, track, track_number);
-                    util::ensure_dir_all_and_write_index(&track_embed_codes_dir, &track_embed_codes_html);
-
-                    let track_embed_dir = release_dir.join("embed").join(track_number.to_string());
-                    let track_embed_html = render::track_embed::track_embed_html(base_url, build, self, track, track_number);
-                    util::ensure_dir_all_and_write_index(&track_embed_dir, &track_embed_html);
-                }
-            }
+            self.write_embed_pages(base_url, build, catalog);
         }
 
         // Render pages for each track
         for (track, track_number) in self.tracks.iter().zip(TRACK_NUMBERS) {
             // Render track page
             let track_dir = release_dir.join(track_number.to_string());
-            let track_html = render::track::track_html(build, catalog, self, track, track_number);
+
+            let track_html = render::track::track_html(&ReleaseContext::new(build, catalog, self).track(track, track_number));
             util::ensure_dir_all_and_write_index(&track_dir, &track_html);
 
             // Render track download/purchase/unlock page
@@ -912,7 +1433,7 @@ impl Release {
                (track.extra_downloads && !track.extras.is_empty()) {
                 match &track.download_access {
                     DownloadAccess::Code { download_codes, unlock_info } => {
-                        let t_unlock_permalink = *build.locale.translations.unlock_permalink;
+                        let t_unlock_permalink = build.locale.unlock_permalink();
 
                         let unlock_page_hash = build.hash_with_salt(|hasher| {
                             self.permalink.slug.hash(hasher);
@@ -926,18 +1447,11 @@ impl Release {
                             .join(t_unlock_permalink)
                             .join(unlock_page_hash);
 
-                        let unlock_html = render::track_unlock::track_unlock_html(
-                            build,
-                            catalog,
-                            self,
-                            track,
-                            track_number,
-                            unlock_info
-                        );
+                        let unlock_html = render::track_unlock::track_unlock_html(&ReleaseContext::new(build, catalog, self).track(track, track_number), unlock_info);
                         util::ensure_dir_all_and_write_index(&unlock_page_dir, &unlock_html);
 
-                        let download_html = render::track_download::track_download_html(build, catalog, self, track, track_number);
-                        let t_downloads_permalink = *build.locale.translations.downloads_permalink;
+                        let download_html = render::track_download::track_download_html(&ReleaseContext::new(build, catalog, self).track(track, track_number));
+                        let t_downloads_permalink = build.locale.downloads_permalink();
 
                         let download_dir = build.build_dir
                             .join(&self.permalink.slug)
@@ -948,12 +1462,24 @@ impl Release {
                             let code_dir = download_dir.join(code);
                             util::ensure_dir_all_and_write_index(&code_dir, &download_html);
                         }
+
+                        // The paycurtain already writes this page, with download
+                        // codes it's only needed for Web Monetization.
+                        if track.monetization_unlocks_downloads() {
+                            let download_page_hash = build.hash_with_salt(|hasher| {
+                                self.permalink.slug.hash(hasher);
+                                track_number.hash(hasher);
+                                t_downloads_permalink.hash(hasher);
+                            });
+
+                            util::ensure_dir_all_and_write_index(&download_dir.join(download_page_hash), &download_html);
+                        }
                     }
                     DownloadAccess::Disabled => (),
                     DownloadAccess::External { .. } => (),
                     DownloadAccess::Free  => {
-                        let download_html = render::track_download::track_download_html(build, catalog, self, track, track_number);
-                        let t_downloads_permalink = *build.locale.translations.downloads_permalink;
+                        let download_html = render::track_download::track_download_html(&ReleaseContext::new(build, catalog, self).track(track, track_number));
+                        let t_downloads_permalink = build.locale.downloads_permalink();
 
                         let download_page_hash = build.hash_with_salt(|hasher| {
                             self.permalink.slug.hash(hasher);
@@ -971,7 +1497,7 @@ impl Release {
                     }
                     DownloadAccess::Paycurtain { payment_info, price } => {
                         if let Some(payment_info) = payment_info {
-                            let t_purchase_permalink = *build.locale.translations.purchase_permalink;
+                            let t_purchase_permalink = build.locale.purchase_permalink();
                             let purchase_page_hash = build.hash_with_salt(|hasher| {
                                 self.permalink.slug.hash(hasher);
                                 track_number.hash(hasher);
@@ -984,19 +1510,11 @@ impl Release {
                                 .join(t_purchase_permalink)
                                 .join(purchase_page_hash);
 
-                            let purchase_html = render::track_purchase::track_purchase_html(
-                                build,
-                                catalog,
-                                payment_info,
-                                price,
-                                self,
-                                track,
-                                track_number
-                            );
+                            let purchase_html = render::track_purchase::track_purchase_html(&ReleaseContext::new(build, catalog, self).track(track, track_number), payment_info, price);
                             util::ensure_dir_all_and_write_index(&purchase_page_dir, &purchase_html);
 
-                            let download_html = render::track_download::track_download_html(build, catalog, self, track, track_number);
-                            let t_downloads_permalink = *build.locale.translations.downloads_permalink;
+                            let download_html = render::track_download::track_download_html(&ReleaseContext::new(build, catalog, self).track(track, track_number));
+                            let t_downloads_permalink = build.locale.downloads_permalink();
 
                             let download_page_hash = build.hash_with_salt(|hasher| {
                                 self.permalink.slug.hash(hasher);
@@ -1012,16 +1530,49 @@ impl Release {
 
                             util::ensure_dir_all_and_write_index(&download_page_dir, &download_html);
                         } else {
-                            warn!(
+                            let warning = format!(
                                 "No payment info specified for track '{}', no purchase/download option will be displayed for this track.",
                                 self.title
                             );
+                            build.warning(WarningCategory::MissingPaymentInfo, &warning);
                         }
                     }
                 }
             }
         }
     }
+
+    /// Writes the files that accompany the pages of the release and its
+    /// tracks (format comparison clips, transcripts and page assets). These
+    /// are not part of the cached rendered pages (see RenderedPages), so
+    /// they are written on every build.
+    pub fn write_page_files(&self, build: &mut Build) {
+        let release_dir = build.build_dir.join(&self.permalink.slug);
+
+        util::ensure_dir_all(&release_dir);
+
+        // Clips for comparing the download formats by ear
+        if let Some(format_comparison) = &self.format_comparison {
+            if !self.download_formats.is_empty() &&
+                !matches!(self.download_access, DownloadAccess::Disabled | DownloadAccess::External { .. }) {
+                format_comparison.write_clips(build, self, &release_dir);
+            }
+        }
+
+        for (track, track_number) in self.tracks.iter().zip(TRACK_NUMBERS) {
+            let track_dir = release_dir.join(track_number.to_string());
+
+            util::ensure_dir_all(&track_dir);
+
+            if let Some(transcript) = &track.transcript {
+                transcript.write(&track_dir);
+            }
+
+            site_metadata::write_page_assets(build, &track_dir, &track.page_assets);
+        }
+
+        site_metadata::write_page_assets(build, &release_dir, &self.page_assets);
+    }
 }
 
 impl ReleaseRc {
@@ -1039,3 +1590,170 @@ impl ReleaseRc {
         }
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    use clap::Parser;
+
+    use crate::{
+        ArchiveCover,
+        ArchiveLayout,
+        Args,
+        Build,
+        CoverAspectRatio,
+        CoverGenerator,
+        DownloadAccess,
+        Extra,
+        ExtraDownloads,
+        FileMeta,
+        Link,
+        Locale,
+        Localizations,
+        ReleaseSection,
+        Theme,
+        ThemeBase,
+        TrackNumbering
+    };
+
+    use super::Release;
+
+    pub fn test_release() -> Release {
+        let mut release = Release::new(
+            ArchiveCover::DEFAULT,
+            ArchiveLayout::FLAT,
+            None,
+            true,
+            None,
+            CoverAspectRatio::Square,
+            false,
+            None,
+            None,
+            DownloadAccess::Free,
+            Vec::new(),
+            false,
+            false,
+            false,
+            ExtraDownloads::BUNDLED,
+            Vec::new(),
+            None,
+            false,
+            None,
+            ReleaseSection::DEFAULT.to_vec(),
+            Vec::new(),
+            Localizations::new(),
+            false,
+            Vec::new(),
+            None,
+            None,
+            false,
+            Vec::new(),
+            None,
+            None,
+            None,
+            Vec::new(),
+            Vec::new(),
+            None,
+            Vec::new(),
+            PathBuf::from("release"),
+            false,
+            Vec::new(),
+            None,
+            Theme::new(),
+            String::from("Release"),
+            TrackNumbering::ArabicDotted,
+            Vec::new(),
+            false,
+            None,
+            false
+        );
+
+        release.asset_basename = Some(String::from("Artist - Release"));
+
+        release
+    }
+
+    #[test]
+    fn archive_signature_ignores_page_content() {
+        let locale = Locale::default();
+        let signature = test_release().archive_signature(&locale);
+
+        let mut release = test_release();
+        release.copy_link = false;
+        release.embedding = true;
+        release.layout.reverse();
+        release.links.push(Link::Anchor { id: String::from("#credits"), label: String::from("Credits") });
+        release.more_label = Some(String::from("Liner notes"));
+        release.speed_controls = true;
+        release.synopsis = Some(String::from("A changed synopsis"));
+        release.theme.base_hue = 180;
+        release.theme.round_corners = true;
+        release.theme.waveforms = false;
+
+        assert_eq!(signature, release.archive_signature(&locale));
+    }
+
+    #[test]
+    fn archive_signature_reflects_archive_content() {
+        let locale = Locale::default();
+        let signature = test_release().archive_signature(&locale);
+
+        let mut release = test_release();
+        release.archive_layout.release_folder = true;
+        assert_ne!(signature, release.archive_signature(&locale));
+
+        let mut release = test_release();
+        release.asset_basename = Some(String::from("Artist - Renamed Release"));
+        assert_ne!(signature, release.archive_signature(&locale));
+    }
+
+    #[test]
+    fn render_signature_reflects_site_and_source_files() {
+        let build = Build::new(&Args::parse_from(["faircamp"]));
+        let signature = test_release().render_signature(&build, 0);
+
+        assert_ne!(signature, test_release().render_signature(&build, 1));
+
+        let mut release = test_release();
+        release.extras.push(Extra::new(FileMeta {
+            modified: SystemTime::UNIX_EPOCH,
+            path: PathBuf::from("release/liner_notes.pdf"),
+            size: 1024
+        }));
+        assert_ne!(signature, release.render_signature(&build, 0));
+
+        let mut release = test_release();
+        release.source_dir = PathBuf::from("renamed_release");
+        assert_ne!(signature, release.render_signature(&build, 0));
+    }
+
+    #[test]
+    fn procedural_cover_signature_ignores_page_content() {
+        let signature = test_release().procedural_cover_signature(&CoverGenerator::BestRillen, 4);
+
+        let mut release = test_release();
+        release.synopsis = Some(String::from("A changed synopsis"));
+        release.theme.base_hue = 180;
+        release.theme.round_corners = true;
+        release.title = String::from("Renamed Release");
+
+        assert_eq!(signature, release.procedural_cover_signature(&CoverGenerator::BestRillen, 4));
+
+        // Only the looney tunes generator scales with the track count of other releases
+        assert_eq!(signature, release.procedural_cover_signature(&CoverGenerator::BestRillen, 12));
+    }
+
+    #[test]
+    fn procedural_cover_signature_reflects_visual_inputs() {
+        let signature = test_release().procedural_cover_signature(&CoverGenerator::LooneyTunes, 4);
+
+        assert_ne!(signature, test_release().procedural_cover_signature(&CoverGenerator::Blocks, 4));
+        assert_ne!(signature, test_release().procedural_cover_signature(&CoverGenerator::LooneyTunes, 12));
+
+        let mut release = test_release();
+        release.theme.base = ThemeBase::Light;
+        assert_ne!(signature, release.procedural_cover_signature(&CoverGenerator::LooneyTunes, 4));
+    }
+}
@@ -0,0 +1,65 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use slug::slugify;
+
+use crate::Release;
+
+/// The release metadata a filter is based on
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReleaseFilterKind {
+    Imprint,
+    Region
+}
+
+/// Shown as a chip above the releases on the homepage (when enabled through
+/// the catalog's `release_filters` option), linking to a pre-generated index
+/// page that lists only the public releases with a specific imprint or
+/// region. Filter pages are written to `{filter_permalink}/{kind}/{slug}/`.
+#[derive(Clone, Debug)]
+pub struct ReleaseFilter {
+    pub kind: ReleaseFilterKind,
+    /// The imprint or region as written in the manifests
+    pub label: String,
+    pub slug: String
+}
+
+impl ReleaseFilter {
+    pub fn matches(&self, release: &Release) -> bool {
+        self.kind.value(release).is_some_and(|value| *value == self.label)
+    }
+
+    pub fn new(kind: ReleaseFilterKind, label: String) -> ReleaseFilter {
+        let slug = slugify(&label);
+
+        ReleaseFilter {
+            kind,
+            label,
+            slug
+        }
+    }
+
+    /// Path of the filter page, relative to the site root
+    pub fn path(&self, filter_permalink: &str) -> String {
+        format!("{filter_permalink}/{}/{}", self.kind.dirname(), self.slug)
+    }
+}
+
+impl ReleaseFilterKind {
+    /// Name of the directory (inside the filter directory) in which the
+    /// pages of all filters of this kind are placed
+    pub fn dirname(&self) -> &'static str {
+        match self {
+            ReleaseFilterKind::Imprint => "imprint",
+            ReleaseFilterKind::Region => "region"
+        }
+    }
+
+    /// The imprint or region of the release (if it has one)
+    pub fn value<'a>(&self, release: &'a Release) -> Option<&'a String> {
+        match self {
+            ReleaseFilterKind::Imprint => release.imprint.as_ref(),
+            ReleaseFilterKind::Region => release.region.as_ref()
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+/// The sections of a release page whose order (and visibility) can be
+/// configured through the `layout` option in a release manifest.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReleaseSection {
+    /// The primary actions (listen, download and more buttons)
+    Downloads,
+    /// The secondary actions (copy link, share, embed, m3u and custom links)
+    Links,
+    /// The long-form description (and the full list of artists)
+    More,
+    /// The physical editions (vinyl, tape, etc.) with their photos
+    Physical,
+    Synopsis,
+    Tracklist
+}
+
+impl ReleaseSection {
+    /// The order in which sections appear when no layout is configured
+    pub const DEFAULT: [ReleaseSection; 6] = [
+        ReleaseSection::Downloads,
+        ReleaseSection::Synopsis,
+        ReleaseSection::Links,
+        ReleaseSection::Tracklist,
+        ReleaseSection::Physical,
+        ReleaseSection::More
+    ];
+
+    pub fn from_manifest_key(key: &str) -> Option<ReleaseSection> {
+        match key {
+            "downloads" => Some(ReleaseSection::Downloads),
+            "links" => Some(ReleaseSection::Links),
+            "more" => Some(ReleaseSection::More),
+            "physical" => Some(ReleaseSection::Physical),
+            "synopsis" => Some(ReleaseSection::Synopsis),
+            "tracklist" => Some(ReleaseSection::Tracklist),
+            _ => None
+        }
+    }
+
+    /// Compact sections can be placed in the column next to the cover, the
+    /// others always span the width of the page.
+    pub fn is_compact(&self) -> bool {
+        match self {
+            ReleaseSection::Downloads |
+            ReleaseSection::Links |
+            ReleaseSection::Synopsis => true,
+            ReleaseSection::More |
+            ReleaseSection::Physical |
+            ReleaseSection::Tracklist => false
+        }
+    }
+}
@@ -0,0 +1,288 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Through the `remote_source` option in a track manifest, the source audio
+//! of a track can be kept outside of the catalog directory, e.g. in object
+//! storage. Remote sources are downloaded at build time (https urls through
+//! curl, s3 urls through the AWS CLI) into a directory inside the cache
+//! directory, where they are stored under their content hash (so that
+//! identical files are only stored once), and from where they are processed
+//! like any other audio file in the catalog. A url is only downloaded again
+//! when the file is missing or no longer matches the checksum given for it
+//! in the manifest. Like all other cached assets, downloads that are not
+//! used anymore are removed according to the cache optimization strategy.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{Build, SUPPORTED_AUDIO_EXTENSIONS, util};
+use crate::bagit::sha256_file;
+
+/// Created inside the cache directory
+pub const REMOTE_SOURCES_DIRNAME: &str = "remote-sources";
+
+#[cfg(not(target_os = "windows"))]
+const AWS_BINARY: &str = "aws";
+
+#[cfg(target_os = "windows")]
+const AWS_BINARY: &str = "aws.exe";
+
+#[cfg(not(target_os = "windows"))]
+const CURL_BINARY: &str = "curl";
+
+#[cfg(target_os = "windows")]
+const CURL_BINARY: &str = "curl.exe";
+
+/// Maps urls to the (content hash based) filenames they were downloaded to
+const INDEX_FILENAME: &str = "index1.bincode";
+
+/// A downloaded remote source in the cache
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct RemoteDownload {
+    /// Named after the sha256 checksum of the file, plus extension
+    filename: String,
+    marked_stale: Option<DateTime<Utc>>
+}
+
+/// All downloaded remote sources in the cache, indexed by url.
+#[derive(Debug, Default)]
+pub struct RemoteDownloads {
+    downloads: HashMap<String, RemoteDownload>
+}
+
+/// Configured through the track manifest's `remote_source` option.
+#[derive(Clone, Debug)]
+pub struct RemoteSource {
+    /// If given, the downloaded file must have this (hex encoded) sha256
+    /// checksum, otherwise the build fails.
+    pub sha256: Option<String>,
+    /// An https:// or s3:// url
+    pub url: String
+}
+
+impl RemoteSource {
+    /// The lowercase audio file extension at the end of the url, if it is
+    /// one that faircamp supports.
+    pub fn extension(&self) -> Option<String> {
+        let path = self.url
+            .split(['?', '#'])
+            .next()
+            .unwrap_or(&self.url);
+
+        Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_lowercase())
+            .filter(|extension| SUPPORTED_AUDIO_EXTENSIONS.contains(&extension.as_str()))
+    }
+
+    /// The filename (without extension) at the end of the url, which is used
+    /// as the fallback title of the track (the local copy is named after its
+    /// content hash, which would not make for a good title).
+    pub fn file_stem(&self) -> Option<String> {
+        let path = self.url
+            .split(['?', '#'])
+            .next()
+            .unwrap_or(&self.url);
+
+        Path::new(path)
+            .file_stem()
+            .and_then(|file_stem| file_stem.to_str())
+            .map(|file_stem| match urlencoding::decode(file_stem) {
+                Ok(decoded) => decoded.into_owned(),
+                Err(_) => file_stem.to_string()
+            })
+    }
+
+    /// Downloads the remote source to the cache (unless it is already
+    /// available there) and returns the path of the local copy.
+    pub fn fetch(&self, build: &Build, remote_downloads: &mut RemoteDownloads) -> Result<PathBuf, String> {
+        let Some(extension) = self.extension() else {
+            return Err(format!(
+                "The remote source url {} needs to end with the extension of a supported audio format ({})",
+                self.url,
+                SUPPORTED_AUDIO_EXTENSIONS.join(", ")
+            ));
+        };
+
+        let remote_sources_dir = build.cache_dir.join(REMOTE_SOURCES_DIRNAME);
+
+        if let Some(download) = remote_downloads.downloads.get_mut(&self.url) {
+            let matches_checksum = match &self.sha256 {
+                Some(sha256) => download.filename.starts_with(&sha256.to_lowercase()),
+                None => true
+            };
+
+            let local_path = remote_sources_dir.join(&download.filename);
+
+            if matches_checksum && local_path.exists() {
+                download.marked_stale = None;
+                return Ok(local_path);
+            }
+        }
+
+        fs::create_dir_all(&remote_sources_dir)
+            .map_err(|err| format!("The directory {} could not be created ({err})", remote_sources_dir.display()))?;
+
+        let download_path = remote_sources_dir.join(format!("download.{extension}"));
+
+        info!("Downloading remote source {}", self.url);
+
+        if self.url.starts_with("s3://") {
+            download_s3(&self.url, &download_path)?;
+        } else {
+            download_https(&self.url, &download_path)?;
+        }
+
+        let (sha256, _size) = sha256_file(&download_path)
+            .map_err(|err| format!("The download of {} could not be read ({err})", self.url))?;
+
+        if let Some(expected_sha256) = &self.sha256 {
+            if !sha256.eq_ignore_ascii_case(expected_sha256) {
+                let _ = fs::remove_file(&download_path);
+                return Err(format!(
+                    "The download of {} does not match the sha256 checksum given in the manifest (expected {expected_sha256}, got {sha256})",
+                    self.url
+                ));
+            }
+        }
+
+        let filename = format!("{sha256}.{extension}");
+
+        let local_path = remote_sources_dir.join(&filename);
+
+        fs::rename(&download_path, &local_path)
+            .map_err(|err| format!("The download of {} could not be stored ({err})", self.url))?;
+
+        let download = RemoteDownload { filename, marked_stale: None };
+        remote_downloads.downloads.insert(self.url.clone(), download);
+        remote_downloads.persist_to_cache(&build.cache_dir);
+
+        Ok(local_path)
+    }
+
+    pub fn new(sha256: Option<String>, url: String) -> RemoteSource {
+        RemoteSource {
+            sha256,
+            url
+        }
+    }
+}
+
+impl RemoteDownloads {
+    pub fn mark_all_stale(&mut self, timestamp: &DateTime<Utc>) {
+        for download in self.downloads.values_mut() {
+            if download.marked_stale.is_none() {
+                download.marked_stale = Some(*timestamp);
+            }
+        }
+    }
+
+    pub fn persist_to_cache(&self, cache_dir: &Path) {
+        let remote_sources_dir = cache_dir.join(REMOTE_SOURCES_DIRNAME);
+
+        if self.downloads.is_empty() {
+            let _ = fs::remove_dir_all(remote_sources_dir);
+        } else {
+            util::ensure_dir_all(&remote_sources_dir);
+            let serialized = bincode::serialize(&self.downloads).unwrap();
+            fs::write(remote_sources_dir.join(INDEX_FILENAME), serialized).unwrap();
+        }
+    }
+
+    /// Removes the downloads that are considered obsolete (as decided by the
+    /// passed closure, which is given their `marked_stale` field), as well as
+    /// any files in the directory that are not listed in the index (e.g. left
+    /// over from an interrupted download).
+    pub fn remove_obsolete(
+        &mut self,
+        cache_dir: &Path,
+        obsolete: impl Fn(&Option<DateTime<Utc>>) -> bool
+    ) {
+        let remote_sources_dir = cache_dir.join(REMOTE_SOURCES_DIRNAME);
+
+        self.downloads.retain(|url, download| {
+            if obsolete(&download.marked_stale) {
+                let _ = fs::remove_file(remote_sources_dir.join(&download.filename));
+                info_cache!("Removed cached download of remote source {}.", url);
+                false
+            } else {
+                true
+            }
+        });
+
+        if let Ok(dir_entries) = remote_sources_dir.read_dir() {
+            for dir_entry in dir_entries.flatten() {
+                let file_name = dir_entry.file_name().to_string_lossy().to_string();
+
+                if file_name != INDEX_FILENAME &&
+                    !self.downloads.values().any(|download| download.filename == file_name) {
+                    let _ = fs::remove_file(dir_entry.path());
+                }
+            }
+        }
+
+        self.persist_to_cache(cache_dir);
+    }
+
+    pub fn retrieve(cache_dir: &Path) -> RemoteDownloads {
+        let remote_sources_dir = cache_dir.join(REMOTE_SOURCES_DIRNAME);
+
+        let mut downloads: HashMap<String, RemoteDownload> = match fs::read(remote_sources_dir.join(INDEX_FILENAME)) {
+            Ok(bytes) => bincode::deserialize(&bytes).unwrap_or_default(),
+            Err(_) => HashMap::new()
+        };
+
+        // Downloads whose file went missing are downloaded again when needed
+        downloads.retain(|_url, download| remote_sources_dir.join(&download.filename).exists());
+
+        RemoteDownloads { downloads }
+    }
+}
+
+fn download_https(url: &str, download_path: &Path) -> Result<(), String> {
+    let mut command = Command::new(CURL_BINARY);
+
+    command.arg("--fail");
+    command.arg("--location");
+    command.arg("--output").arg(download_path);
+    command.arg("--silent");
+    command.arg("--show-error");
+    command.arg(url);
+
+    run(command, url)
+}
+
+/// Credentials and region are picked up by the AWS CLI as usual (through
+/// its configuration files or environment variables).
+fn download_s3(url: &str, download_path: &Path) -> Result<(), String> {
+    let mut command = Command::new(AWS_BINARY);
+
+    command.arg("s3").arg("cp");
+    command.arg("--only-show-errors");
+    command.arg(url);
+    command.arg(download_path);
+
+    run(command, url)
+}
+
+fn run(mut command: Command, url: &str) -> Result<(), String> {
+    let program = command.get_program().to_string_lossy().to_string();
+
+    match command.output() {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(())
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(format!("The download of {url} failed ({})", stderr.trim()))
+            }
+        }
+        Err(err) => Err(format!("The {program} child process could not be executed, it needs to be installed for downloading {url} ({err})"))
+    }
+}
@@ -4,6 +4,7 @@
 
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
+use std::hash::Hash;
 
 use indoc::{formatdoc, indoc};
 
@@ -13,30 +14,47 @@ use crate::{
     ArtistRc,
     Build,
     Catalog,
+    COVER_SVG_FILENAME,
+    CoverAspectRatio,
     DescribedImage,
+    DownloadAccess,
     ImgAttributes,
     Link,
     Release,
+    ReleaseFilter,
     ReleaseRc,
+    ShareButton,
+    SiteUrl,
     Track
 };
 use crate::icons;
 use crate::util::{
     format_bytes,
+    format_time,
     html_double_escape_inside_attribute,
     html_escape_inside_attribute,
     html_escape_outside_attribute
 };
 
 pub mod artist;
+pub mod discover;
+pub mod embed_index;
 pub mod image_descriptions;
 pub mod index;
+pub mod index_filter;
+pub mod index_list;
+pub mod index_timeline;
+pub mod index_year_archive;
+pub mod latest_release_widget;
+pub mod playlist;
+pub mod range_check;
 pub mod release;
 pub mod release_download;
 pub mod release_embed;
 pub mod release_embed_codes;
 pub mod release_purchase;
 pub mod release_unlock;
+pub mod search;
 pub mod subscribe;
 pub mod track;
 pub mod track_download;
@@ -47,9 +65,76 @@ pub mod track_unlock;
 
 mod embed_layout;
 mod layout;
+mod page_context;
 
 use embed_layout::EmbedLayout;
 use layout::Layout;
+pub use page_context::{
+    ArtistContext,
+    CatalogContext,
+    PageContext,
+    PlaylistContext,
+    ReleaseContext,
+    TrackContext
+};
+
+/// The inline style of the iframe through which a player is embedded
+const EMBED_INLINE_STYLE: &str = "border: none; height: 49.6px; min-width: 480px;";
+
+/// Plays back the hover preview of a release (after a short delay, so that
+/// merely moving the pointer across the grid stays silent) while it is
+/// hovered or focused through the keyboard. Visitors who prefer reduced data
+/// usage or reduced motion get no previews unless they explicitly enable
+/// them through the toggle, which is remembered in localStorage either way.
+const HOVER_PREVIEWS_SCRIPT: &str = indoc!(r#"
+    <script>
+        (() => {
+            const toggle = document.querySelector('.hover_previews_toggle');
+            const persisted = localStorage.getItem('faircampHoverPreviews');
+            const reducedData = navigator.connection?.saveData ||
+                window.matchMedia('(prefers-reduced-data: reduce)').matches;
+            const reducedMotion = window.matchMedia('(prefers-reduced-motion: reduce)').matches;
+
+            let enabled = persisted === null ? !(reducedData || reducedMotion) : persisted === 'enabled';
+            let timeout = null;
+
+            const audio = new Audio();
+            const volume = parseFloat(localStorage.getItem('faircampVolume'));
+            if (volume >= 0 && volume <= 1) { audio.volume = volume; }
+
+            const start = release => {
+                clearTimeout(timeout);
+                timeout = setTimeout(() => {
+                    audio.src = release.dataset.preview;
+                    audio.play().catch(() => {});
+                }, 400);
+            };
+
+            const stop = () => {
+                clearTimeout(timeout);
+                audio.pause();
+            };
+
+            toggle.setAttribute('aria-pressed', enabled);
+            toggle.hidden = false;
+            toggle.addEventListener('click', () => {
+                enabled = !enabled;
+                localStorage.setItem('faircampHoverPreviews', enabled ? 'enabled' : 'disabled');
+                toggle.setAttribute('aria-pressed', enabled);
+                if (!enabled) { stop(); }
+            });
+
+            for (const release of document.querySelectorAll('.release[data-preview]')) {
+                release.addEventListener('mouseenter', () => { if (enabled) { start(release); } });
+                release.addEventListener('mouseleave', stop);
+                release.addEventListener('focusin', event => {
+                    if (enabled && event.target.matches(':focus-visible')) { start(release); }
+                });
+                release.addEventListener('focusout', stop);
+            }
+        })();
+    </script>
+"#);
 
 /// Static reusable markup for a speed button we put into the release, track
 /// and embedded players
@@ -144,10 +229,11 @@ fn compact_release_identifier(
     };
     let artists = list_release_artists(build, index_suffix, root_prefix, catalog, artists_truncation, release);
     let release_title_escaped = html_escape_outside_attribute(&release.title);
+    let release_asset_prefix = build.asset_prefix(release_prefix, root_prefix, &release.permalink.slug);
     let cover = release_cover_image_tiny_decorative(
         release,
         release_link,
-        release_prefix
+        &release_asset_prefix
     );
 
     format!(r#"
@@ -176,6 +262,7 @@ fn compact_track_identifier(
     root_prefix: &str,
     track: &Track,
     track_link: &str,
+    track_number: usize,
     track_prefix: &str
 ) -> String {
     let artists_truncation = Truncation::Truncate {
@@ -184,12 +271,15 @@ fn compact_track_identifier(
     };
     let artists = list_track_artists(build, index_suffix, root_prefix, catalog, artists_truncation, track);
     let track_title_escaped = html_escape_outside_attribute(&track.title());
+    let release_slug = &release.permalink.slug;
+    let release_asset_prefix = build.asset_prefix(release_prefix, root_prefix, release_slug);
+    let track_asset_prefix = build.asset_prefix(track_prefix, root_prefix, &format!("{release_slug}/{track_number}"));
     let cover = track_cover_image_tiny_decorative(
         release,
-        release_prefix,
+        &release_asset_prefix,
         track,
         track_link,
-        track_prefix
+        &track_asset_prefix
     );
 
     format!(r#"
@@ -221,6 +311,21 @@ pub fn copy_button(content_key: &str, content_value: &str, label: &str) -> Strin
     "##)
 }
 
+/// Button for the docked player that copies a link pointing to the current
+/// playback position (track and timecode). The content value is the url of
+/// the page if it is known at build time (otherwise it's left empty and
+/// determined at runtime), the track/timecode parameters are appended by the
+/// player script when the button is clicked.
+pub fn copy_timecode_link_button(content_value: &str, translations: &Translations) -> String {
+    let copy_icon = icons::COPY;
+    let t_copy_link_at_current_time = &translations.copy_link_at_current_time;
+    formatdoc!(r#"
+        <button aria-label="{t_copy_link_at_current_time}" class="copy_timecode_link" data-content="{content_value}" title="{t_copy_link_at_current_time}">
+            <span class="icon">{copy_icon}</span>
+        </button>
+    "#)
+}
+
 fn cover_tile_image(
     build: &Build,
     release_prefix: &str,
@@ -228,6 +333,8 @@ fn cover_tile_image(
     release: &Release,
     href: &str
 ) -> String {
+    let release_prefix = &build.asset_prefix(release_prefix, root_prefix, &release.permalink.slug);
+
     match &release.cover {
         Some(described_image) => {
             let image_ref = described_image.borrow();
@@ -305,6 +412,69 @@ fn download_entry(href: String, label: &str, size: u64) -> String {
     )
 }
 
+/// Covers the release and track pages of releases with a content warning
+/// until the visitor acknowledges it, which is remembered per release in
+/// localStorage. Without javascript the warning is only shown on top of the
+/// page, without covering it.
+fn content_warning_interstitial(build: &Build, release: &Release) -> String {
+    let Some(content_warning) = &release.content_warning else {
+        return String::new();
+    };
+
+    let translations = &build.locale.translations;
+    let release_slug = &release.permalink.slug;
+    let t_content_warning = &translations.content_warning;
+    let text_escaped = html_escape_outside_attribute(&content_warning.text);
+
+    let (r_age, t_acknowledge) = match content_warning.minimum_age {
+        Some(age) => (
+            format!("<p>{}</p>", translations.content_warning_age_xxx(&age.to_string())),
+            translations.content_warning_confirm_age_xxx(&age.to_string())
+        ),
+        None => (String::new(), translations.r#continue.to_string())
+    };
+
+    formatdoc!(r#"
+        <div aria-labelledby="content_warning_title" aria-modal="true" class="content_warning" data-release="{release_slug}" role="dialog">
+            <div>
+                <h2 id="content_warning_title">{t_content_warning}</h2>
+                <p>{text_escaped}</p>
+                {r_age}
+                <button>{t_acknowledge}</button>
+            </div>
+        </div>
+        <script>
+            (() => {{
+                const contentWarning = document.querySelector('.content_warning');
+                const key = `faircampContentWarning:${{contentWarning.dataset.release}}`;
+
+                if (localStorage.getItem(key) === 'acknowledged') {{
+                    contentWarning.remove();
+                    return;
+                }}
+
+                document.body.classList.add('content_warning_active');
+
+                const button = contentWarning.querySelector('button');
+                button.addEventListener('click', () => {{
+                    localStorage.setItem(key, 'acknowledged');
+                    document.body.classList.remove('content_warning_active');
+                    contentWarning.remove();
+                }});
+                button.focus();
+            }})();
+        </script>
+    "#)
+}
+
+/// Shown on top of the release and track pages of draft releases (which are
+/// only built with --include-drafts), so a preview is never mistaken for
+/// the published page.
+fn draft_banner(build: &Build) -> String {
+    let t_draft_preview = &build.locale.translations.draft_preview;
+    format!(r#"<div class="draft_banner" role="note">{t_draft_preview}</div>"#)
+}
+
 /// Returns a two-field tuple where the fields have the following use:
 /// .0 => Intended to be directly copied via copy-to-clipboard
 /// .1 => Intended to be rendered to the page, so people can copy it themselves.
@@ -313,13 +483,10 @@ fn download_entry(href: String, label: &str, size: u64) -> String {
 /// https://developer.mozilla.org/en-US/docs/Web/HTML/Element/iframe#accessibility_concerns
 fn embed_code(embed_url: &str, title: &str) -> (String, String) {
     let title_double_escaped = html_double_escape_inside_attribute(title);
-    let title_escaped = html_escape_inside_attribute(title);
 
-    let inline_style = "border: none; height: 49.6px; min-width: 480px;";
+    let inline_style = EMBED_INLINE_STYLE;
 
-    let copy_code = html_escape_inside_attribute(
-        &format!(r#"<iframe loading="lazy" src="{embed_url}" style="{inline_style}" title="{title_escaped}"></iframe>"#)
-    );
+    let copy_code = html_escape_inside_attribute(&embed_iframe(embed_url, title));
 
     let display_code = formatdoc!(r#"
         <div class="embed_code_wrapper">
@@ -335,6 +502,51 @@ fn embed_code(embed_url: &str, title: &str) -> (String, String) {
     (copy_code, display_code)
 }
 
+/// The iframe through which a player is embedded on another site
+fn embed_iframe(embed_url: &str, title: &str) -> String {
+    let title_escaped = html_escape_inside_attribute(title);
+
+    format!(r#"<iframe loading="lazy" src="{embed_url}" style="{EMBED_INLINE_STYLE}" title="{title_escaped}"></iframe>"#)
+}
+
+/// If enabled for the release, a small link shown in the embedded players
+/// that leads visitors on other sites to where they can support the artist:
+/// The purchase page (or external purchase link) if there is one, otherwise
+/// the release page. In an embeds-only build there is no page we could
+/// refer to, so it is never shown there.
+fn embed_support_button(base_url: &SiteUrl, build: &Build, release: &Release) -> String {
+    if !release.embed_support_button || build.embeds_only {
+        return String::new();
+    }
+
+    let release_slug = &release.permalink.slug;
+
+    let href = match &release.download_access {
+        DownloadAccess::External { link } => link.clone(),
+        DownloadAccess::Paycurtain { payment_info: Some(_), .. } if release.download_assets_available() => {
+            let t_purchase_permalink = build.locale.purchase_permalink();
+            let page_hash = build.hash_with_salt(|hasher| {
+                release.permalink.slug.hash(hasher);
+                t_purchase_permalink.hash(hasher);
+            });
+
+            base_url.join_index(build, format!("{release_slug}/{t_purchase_permalink}/{page_hash}"))
+        }
+        _ => base_url.join_index(build, release_slug)
+    };
+
+    let href_escaped = html_escape_inside_attribute(&href);
+    let t_support_the_artist = &build.locale.translations.support_the_artist;
+    let buy_icon = icons::buy(t_support_the_artist);
+
+    formatdoc!(r#"
+        <a class="support" href="{href_escaped}" target="_blank">
+            {buy_icon}
+            <span>{t_support_the_artist}</span>
+        </a>
+    "#)
+}
+
 /// Generic link with icon as we render it in the "actions" section on various
 /// pages
 fn link_action(link: &Link, translations: &Translations) -> String {
@@ -504,6 +716,12 @@ fn list_track_artists(
                 continue;
             }
 
+            // In an embeds-only build there are no artist pages to link to
+            if build.embeds_only {
+                items.push((name_chars, name_escaped));
+                continue;
+            }
+
             if artist_ref.featured {
                 let permalink = &artist_ref.permalink.slug;
                 let artist_link = format!(r#"<a href="{root_prefix}{permalink}{index_suffix}">{name_escaped}</a>"#);
@@ -526,6 +744,33 @@ fn list_track_artists(
     truncate_artist_list(build, catalog, items, truncation)
 }
 
+/// A link to the downloads page that stays hidden until the browser reports
+/// that Web Monetization is active (see PaymentPointer::head_tags).
+fn monetization_unlock_link(href: &str, label: &str) -> String {
+    let download_icon = icons::DOWNLOAD;
+
+    formatdoc!(r#"
+        <a class="monetization_unlock" hidden href="{href}">
+            {download_icon}
+            <span>{label}</span>
+        </a>
+    "#)
+}
+
+/// The data attribute through which the player obtains the gain it applies
+/// to a track when volume normalization is active (empty if normalization is
+/// disabled for the track or its loudness is unknown).
+fn normalization_gain_attribute(track: &Track) -> String {
+    if !track.volume_normalization {
+        return String::new();
+    }
+
+    match track.normalization_gain() {
+        Some(gain) => format!(r#" data-gain="{gain:.3}""#),
+        None => String::new()
+    }
+}
+
 /// These are rendered alongside the release player and provide prepared and translated
 /// icons for the client side script to use.
 pub fn player_icon_templates(translations: &Translations) -> String {
@@ -546,6 +791,35 @@ pub fn player_icon_templates(translations: &Translations) -> String {
     "#)
 }
 
+/// The queue panel of the release and playlist players, listing the tracks
+/// in the order they will be played back. The items are rendered (and can
+/// be reordered and removed) by the client side script.
+fn queue_panel(translations: &Translations) -> String {
+    let t_queue = &translations.queue;
+    let t_reset_queue = &translations.reset_queue;
+
+    formatdoc!(r#"
+        <div class="queue" hidden id="queue">
+            <div class="queue_header">
+                <span>{t_queue}</span>
+                <button class="reset_queue">{t_reset_queue}</button>
+            </div>
+            <ol></ol>
+        </div>
+    "#)
+}
+
+/// Expands/collapses the queue panel
+fn queue_toggle(translations: &Translations) -> String {
+    let queue_icon = icons::queue(&translations.queue);
+
+    formatdoc!(r#"
+        <button aria-controls="queue" aria-expanded="false" class="queue_toggle">
+            {queue_icon}
+        </button>
+    "#)
+}
+
 /// Used on release/tracks pages to display a large-size cover for the release
 fn release_cover_image(
     build: &Build,
@@ -553,6 +827,8 @@ fn release_cover_image(
     release_prefix: &str,
     root_prefix: &str,
 ) -> String {
+    let release_prefix = &build.asset_prefix(release_prefix, root_prefix, &release.permalink.slug);
+
     match &release.cover {
         Some(described_image) => {
             let image_ref = described_image.borrow();
@@ -564,27 +840,46 @@ fn release_cover_image(
 
             let hash = image_ref.hash.as_url_safe_base64();
 
-            let ImgAttributes { src: thumb_src, srcset: thumb_srcset } = image_ref.cover_assets
-                .as_ref()
-                .unwrap()
-                .img_attributes_up_to_480(&hash, release_prefix);
+            let (cover_ref, img_class) = match release.cover_aspect_ratio {
+                CoverAspectRatio::Original => (image_ref.cover_assets_uncropped_unchecked(), r#"class="uncropped""#),
+                CoverAspectRatio::Square => (image_ref.cover_assets_unchecked(), "")
+            };
+
+            // An SVG cover that is shipped as is scales to any size, so it
+            // replaces all rasterized versions on the release page.
+            let (thumb_src, thumb_srcset_attributes, overlay_src, overlay_srcset_attributes) = match release.cover_svg() {
+                Some(_) => {
+                    let svg_src = format!("{release_prefix}{COVER_SVG_FILENAME}?{hash}");
+                    (svg_src.clone(), String::new(), svg_src, String::new())
+                }
+                None => {
+                    let ImgAttributes { src: thumb_src, srcset: thumb_srcset } = cover_ref
+                        .img_attributes_up_to_480(&hash, release_prefix);
+                    let ImgAttributes { src: overlay_src, srcset: overlay_srcset } = cover_ref
+                        .img_attributes_up_to_1280(&hash, release_prefix);
+
+                    (
+                        thumb_src,
+                        format!(r#"sizes="(min-width: 20rem) 20rem, calc(100vw - 2rem)" srcset="{thumb_srcset}""#),
+                        overlay_src,
+                        format!(r#"sizes="calc(100vmin - 4rem)" srcset="{overlay_srcset}""#)
+                    )
+                }
+            };
 
             let thumbnail = formatdoc!(r#"
                 <a class="image" href="{thumb_src}" target="_blank">
                     <img
                         {alt}
-                        sizes="(min-width: 20rem) 20rem, calc(100vw - 2rem)"
+                        {img_class}
                         src="{thumb_src}"
-                        srcset="{thumb_srcset}">
+                        {thumb_srcset_attributes}>
                 </a>
             "#);
 
-            let cover_ref = image_ref.cover_assets_unchecked();
-
-            let ImgAttributes { src: overlay_src, srcset: overlay_srcset } = cover_ref
-                .img_attributes_up_to_1280(&hash, release_prefix);
-
-            let largest_edge_size = cover_ref.largest().edge_size;
+            let largest_asset = cover_ref.largest();
+            let largest_height = largest_asset.height;
+            let largest_width = largest_asset.edge_size;
 
             let t_close = &build.locale.translations.close;
             let overlay = formatdoc!(r#"
@@ -594,12 +889,12 @@ fn release_cover_image(
                     </form>
                     <img
                         {alt}
-                        height="{largest_edge_size}"
+                        {img_class}
+                        height="{largest_height}"
                         loading="lazy"
-                        sizes="calc(100vmin - 4rem)"
                         src="{overlay_src}"
-                        srcset="{overlay_srcset}"
-                        width="{largest_edge_size}">
+                        {overlay_srcset_attributes}
+                        width="{largest_width}">
                 </dialog>
                 <script>
                     const overlay = document.querySelector('dialog#overlay');
@@ -675,6 +970,101 @@ fn release_cover_image_tiny_decorative(
     "#)
 }
 
+/// Filter chips for the imprints and regions of releases, rendered above
+/// the releases on the homepage and on each filter page. `active` is the
+/// filter of the page the chips are rendered on (None for the homepage).
+fn release_filters(
+    build: &Build,
+    catalog: &Catalog,
+    index_suffix: &str,
+    root_prefix: &str,
+    active: Option<&ReleaseFilter>
+) -> String {
+    let Some(filter_permalink) = &catalog.filter_permalink else {
+        return String::new();
+    };
+
+    let translations = &build.locale.translations;
+
+    let all_current = if active.is_none() { r#" aria-current="page""# } else { "" };
+    let t_all_releases = &translations.all_releases;
+
+    let r_filters = catalog.filters
+        .iter()
+        .map(|filter| {
+            let current = if active.is_some_and(|active| active.kind == filter.kind && active.slug == filter.slug) {
+                r#" aria-current="page""#
+            } else {
+                ""
+            };
+            let href = format!("{root_prefix}{}{index_suffix}", filter.path(filter_permalink));
+            let label_escaped = html_escape_outside_attribute(&filter.label);
+
+            format!(r#"<a{current} href="{href}">{label_escaped}</a>"#)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let t_filter_releases = &translations.filter_releases;
+
+    formatdoc!(r#"
+        <nav aria-label="{t_filter_releases}" class="release_filters">
+            <a{all_current} href="{root_prefix}">{t_all_releases}</a>
+            {r_filters}
+        </nav>
+    "#)
+}
+
+/// Renders the total runtime of a release, followed by the approximate size
+/// of the release archive in each available download format (if downloads
+/// are offered through faircamp itself), e.g. "42 minutes · FLAC 312MB · MP3 98MB"
+fn release_runtime_and_sizes(release: &Release, translations: &Translations) -> String {
+    let total_seconds = release.tracks
+        .iter()
+        .map(|track| track.transcodes.borrow().source_meta.duration_seconds)
+        .sum::<f32>();
+
+    // Rounded only once, so that e.g. 59:59.6 rolls over to one hour
+    let total_minutes = (total_seconds / 60.0).round() as u32;
+
+    let hours = total_minutes / 60;
+    let minutes = total_minutes % 60;
+
+    let runtime = match (hours, minutes) {
+        (0, minutes) => translations.xxx_minutes(&minutes.max(1).to_string()),
+        (hours, 0) => translations.xxx_hours(&hours.to_string()),
+        (hours, minutes) => format!(
+            "{} {}",
+            translations.xxx_hours(&hours.to_string()),
+            translations.xxx_minutes(&minutes.to_string())
+        )
+    };
+
+    let mut entries = vec![runtime];
+
+    match &release.download_access {
+        DownloadAccess::Code { .. } |
+        DownloadAccess::Free |
+        DownloadAccess::Paycurtain { .. } => {
+            if let Some(archives) = &release.archives {
+                let archives_ref = archives.borrow();
+
+                let mut download_formats_sorted = release.download_formats.clone();
+                download_formats_sorted.sort_by_key(|format| format.download_rank());
+
+                for download_format in download_formats_sorted {
+                    let size = archives_ref.get_unchecked(download_format).asset.filesize_bytes;
+                    entries.push(format!("{} {}", download_format.user_label(), format_bytes(size)));
+                }
+            }
+        }
+        DownloadAccess::Disabled |
+        DownloadAccess::External { .. } => ()
+    }
+
+    entries.join(" · ")
+}
+
 fn releases(
     build: &Build,
     index_suffix: &str,
@@ -686,7 +1076,7 @@ fn releases(
 
     releases_desc_by_date.sort_by_key(|release| release.borrow().date);
 
-    releases_desc_by_date
+    let r_releases = releases_desc_by_date
         .iter()
         .rev()
         .map(|release| {
@@ -715,10 +1105,18 @@ fn releases(
                 &release_ref,
                 &href
             );
-            let release_title_escaped = html_escape_outside_attribute(&release_ref.title);
+            let release_title_escaped = html_escape_outside_attribute(&release_ref.title_disambiguated());
+
+            let data_preview = match &release_ref.hover_preview {
+                Some(filename) => {
+                    let release_asset_prefix = build.asset_prefix(&release_prefix, root_prefix, permalink);
+                    format!(r#" data-preview="{release_asset_prefix}{filename}""#)
+                }
+                None => String::new()
+            };
 
             formatdoc!(r#"
-                <div class="release">
+                <div class="release"{data_preview}>
                     {cover}
                     <a href="{href}">
                         {release_title_escaped}
@@ -728,17 +1126,88 @@ fn releases(
             "#)
         })
         .collect::<Vec<String>>()
-        .join("\n")
+        .join("\n");
+
+    if catalog.hover_previews {
+        let t_hover_previews = &build.locale.translations.hover_previews;
+
+        formatdoc!(r#"
+            <button aria-pressed="true" class="hover_previews_toggle" hidden>{t_hover_previews}</button>
+            {r_releases}
+            {HOVER_PREVIEWS_SCRIPT}
+        "#)
+    } else {
+        r_releases
+    }
+}
+
+/// Share buttons (plain share intent links, no third party scripts are
+/// involved) as we render them in the "actions" section on various pages.
+/// Mastodon is special in that visitors need to be asked for the domain of
+/// their instance first, which is done through a small inline script.
+fn share_actions(
+    share_buttons: &[ShareButton],
+    title: &str,
+    translations: &Translations,
+    url: &str
+) -> Vec<String> {
+    let share_icon = icons::SHARE;
+
+    share_buttons
+        .iter()
+        .map(|share_button| {
+            let label = html_escape_outside_attribute(&share_button.label(translations));
+
+            match share_button.url(title, url) {
+                Some(share_url) => {
+                    let share_url_escaped = html_escape_inside_attribute(&share_url);
+                    let target = match share_button {
+                        ShareButton::Email => "",
+                        _ => r#"rel="noopener noreferrer" target="_blank""#
+                    };
+
+                    formatdoc!(r#"
+                        <a href="{share_url_escaped}" {target}>
+                            {share_icon}
+                            <span>{label}</span>
+                        </a>
+                    "#)
+                }
+                None => {
+                    let t_mastodon_instance_prompt = html_escape_inside_attribute(&translations.mastodon_instance_prompt);
+                    let text_urlencoded = ShareButton::text_urlencoded(title, url);
+
+                    formatdoc!(r#"
+                        <button data-prompt="{t_mastodon_instance_prompt}" data-share-mastodon="{text_urlencoded}">
+                            {share_icon}
+                            <span>{label}</span>
+                        </button>
+                        <script>
+                            document.currentScript.previousElementSibling.addEventListener('click', event => {{
+                                const button = event.currentTarget;
+                                const instance = prompt(button.dataset.prompt, localStorage.getItem('faircampMastodonInstance') ?? '');
+                                if (instance) {{
+                                    const domain = instance.trim().replace(/^https?:\/\//, '').replace(/\/.*$/, '');
+                                    localStorage.setItem('faircampMastodonInstance', domain);
+                                    window.open(`https://${{domain}}/share?text=${{button.dataset.shareMastodon}}`, '_blank', 'noopener');
+                                }}
+                            }});
+                        </script>
+                    "#)
+                }
+            }
+        })
+        .collect()
 }
 
 /// Used on track pages to display a large-size cover for the track
 fn track_cover_image(
     build: &Build,
     cover: &DescribedImage,
-    root_prefix: &str
+    root_prefix: &str,
+    track_prefix: &str
 ) -> String {
     let image_ref = cover.image.borrow();
-    let track_prefix = "";
 
     let alt = match &cover.description {
         Some(description) => format!(r#"alt="{}""#, html_escape_inside_attribute(description)),
@@ -941,6 +1410,18 @@ fn volume_controls(translations: &Translations) -> String {
     "#)
 }
 
+/// A button that lets the visitor toggle volume normalization, which is on
+/// by default (the player restores the visitor's last choice on load).
+fn volume_normalization_toggle(translations: &Translations) -> String {
+    let volume_normalization_icon = icons::volume_normalization(&translations.volume_normalization);
+
+    formatdoc!(r#"
+        <button aria-pressed="true" class="volume_normalization">
+            {volume_normalization_icon}
+        </button>
+    "#)
+}
+
 fn waveform(track: &Track) -> String {
     let peaks_base64 = track.transcodes.borrow().source_meta.peaks
         .iter()
@@ -980,12 +1461,26 @@ fn waveform(track: &Track) -> String {
         .collect::<Vec<String>>()
         .join("");
 
+    // Annotations are positioned relative to the width of the waveform
+    let duration_seconds = track.transcodes.borrow().source_meta.duration_seconds;
+    let r_annotations = track.annotations
+        .iter()
+        .filter(|annotation| annotation.seconds < duration_seconds)
+        .map(|annotation| {
+            let label = html_escape_inside_attribute(&format!("{} {}", format_time(annotation.seconds), annotation.text));
+            let left = annotation.seconds / duration_seconds * 100.0;
+            format!(r#"<span aria-label="{label}" class="annotation" role="img" style="left: {left:.3}%;" title="{label}"></span>"#)
+        })
+        .collect::<Vec<String>>()
+        .join("");
+
     formatdoc!(r#"
         <svg data-peaks="{peaks_base64}">
             <path class="seek"/>
             <path class="playback"/>
             <path class="base"/>
         </svg>
+        {r_annotations}
     "#)
 }
 
@@ -1000,7 +1495,7 @@ fn wrap_undescribed_image(
 
     let visual_impairment_icon = icons::visual_impairment(&build.locale.translations.visual_impairment);
 
-    let t_image_descriptions_permalink = &build.locale.translations.image_descriptions_permalink;
+    let t_image_descriptions_permalink = build.locale.image_descriptions_permalink();
     let t_missing_image_description_note = &build.locale.translations.missing_image_description_note;
     formatdoc!(r#"
         <div aria-hidden="true" class="{extra_class} undescribed_wrapper">
@@ -1,33 +1,48 @@
 // SPDX-FileCopyrightText: 2022-2025 Simon Repp
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use chrono::Datelike;
 use indoc::formatdoc;
 
 use crate::{
-    Artist,
-    Build,
-    Catalog,
-    OpenGraphMeta
+    MirroredPage,
+    OpenGraphMeta,
+    PageType,
+    ReleaseRc
 };
 use crate::icons;
 use crate::util::html_escape_outside_attribute;
 
-use super::Layout;
+use super::{ArtistContext, Layout};
 use super::{
     artist_image,
     copy_button,
     link_action,
     releases,
+    share_actions,
     unlisted_badge
 };
 
-pub fn artist_html(artist: &Artist, build: &Build, catalog: &Catalog) -> String {
+/// Horizontal space each year occupies on the release timeline (in px)
+const TIMELINE_YEAR_WIDTH: usize = 64;
+/// Vertical space between releases from the same year on the release timeline (in px)
+const TIMELINE_ROW_HEIGHT: usize = 16;
+
+pub fn artist_html(context: &ArtistContext) -> String {
+    let &ArtistContext { artist, build, catalog } = context;
     let index_suffix = build.index_suffix();
     let root_prefix = "../";
     let translations = &build.locale.translations;
 
     let mut layout = Layout::new();
 
+    layout.set_mirrored_page(MirroredPage::Page(artist.permalink.slug.clone()));
+    layout.set_page_type(PageType::Listing);
+
+    if let Some(page_metadata) = &artist.page_metadata {
+        layout.add_page_metadata(page_metadata.render(root_prefix));
+    }
+
     let artist_name_escaped = html_escape_outside_attribute(&artist.name);
 
     let mut actions = Vec::new();
@@ -75,6 +90,12 @@ pub fn artist_html(artist: &Artist, build: &Build, catalog: &Catalog) -> String
         actions.push(r_copy_link);
     }
 
+    if let Some(base_url) = &build.base_url {
+        let artist_url = base_url.join_index(build, &artist.permalink.slug);
+        let r_share_actions = share_actions(&artist.share_buttons, &artist.name, translations, &artist_url);
+        actions.extend(r_share_actions);
+    }
+
     for link in &artist.links {
         let r_link = link_action(link, translations);
         actions.push(r_link);
@@ -94,7 +115,7 @@ pub fn artist_html(artist: &Artist, build: &Build, catalog: &Catalog) -> String
 
     let r_artist_image = match &artist.image {
         Some(artist_image_unpacked) => artist_image(
-            "",
+            &build.asset_prefix("", root_prefix, &artist.permalink.slug),
             build,
             artist_image_unpacked,
             root_prefix
@@ -118,6 +139,11 @@ pub fn artist_html(artist: &Artist, build: &Build, catalog: &Catalog) -> String
         &public_releases
     );
 
+    let r_release_timeline = match artist.release_timeline {
+        true => release_timeline(index_suffix, root_prefix, &public_releases, &translations.timeline),
+        false => String::new()
+    };
+
     let synopsis = match &artist.synopsis {
         Some(synopsis) => {
             formatdoc!(r#"
@@ -140,6 +166,7 @@ pub fn artist_html(artist: &Artist, build: &Build, catalog: &Catalog) -> String
                 </div>
             </div>
         </div>
+        {r_release_timeline}
         <div class="page">
             <div class="page_grid">
                 <div>
@@ -150,7 +177,7 @@ pub fn artist_html(artist: &Artist, build: &Build, catalog: &Catalog) -> String
         {r_more}
     "##);
 
-    if artist.unlisted {
+    if artist.unlisted || artist.noindex {
         layout.no_indexing();
     }
 
@@ -166,7 +193,7 @@ pub fn artist_html(artist: &Artist, build: &Build, catalog: &Catalog) -> String
             }
 
             if let Some(described_image) = &artist.image {
-                let artist_prefix = base_url.join_prefix(artist_slug);
+                let artist_prefix = build.asset_url().join_prefix(artist_slug);
                 let opengraph_image = described_image
                     .borrow()
                     .artist_opengraph_image(&artist_prefix);
@@ -184,10 +211,105 @@ pub fn artist_html(artist: &Artist, build: &Build, catalog: &Catalog) -> String
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &artist.theme,
         &artist.name
     )
 }
+
+/// An svg rendering of the artist's releases on a horizontal axis of years,
+/// each release being a dot that links to the release page. Releases from
+/// the same year are stacked on top of each other, years without releases
+/// are kept as gaps. Undated releases are not shown, if there are no dated
+/// releases at all an empty string is returned.
+fn release_timeline(
+    index_suffix: &str,
+    root_prefix: &str,
+    public_releases: &[ReleaseRc],
+    t_timeline: &str
+) -> String {
+    let mut releases_asc_by_date = public_releases
+        .iter()
+        .filter(|release| release.borrow().date.is_some())
+        .collect::<Vec<&ReleaseRc>>();
+
+    if releases_asc_by_date.is_empty() {
+        return String::new();
+    }
+
+    releases_asc_by_date.sort_by_key(|release| release.borrow().date);
+
+    let first_year = releases_asc_by_date.first().unwrap().borrow().date.unwrap().year();
+    let last_year = releases_asc_by_date.last().unwrap().borrow().date.unwrap().year();
+
+    // Each entry is a release with its year and its stacking level within that year
+    let mut stacked: Vec<(i32, usize, &ReleaseRc)> = Vec::new();
+
+    for release in releases_asc_by_date {
+        let year = release.borrow().date.unwrap().year();
+        let level = match stacked.last() {
+            Some((previous_year, previous_level, _)) if *previous_year == year => previous_level + 1,
+            _ => 0
+        };
+        stacked.push((year, level, release));
+    }
+
+    let max_level = stacked.iter().map(|(_, level, _)| *level).max().unwrap();
+
+    let axis_y = 12 + (max_level + 1) * TIMELINE_ROW_HEIGHT;
+    let height = axis_y + 24;
+    let width = (last_year - first_year + 1) as usize * TIMELINE_YEAR_WIDTH;
+
+    let year_x = |year: i32| (year - first_year) as usize * TIMELINE_YEAR_WIDTH + TIMELINE_YEAR_WIDTH / 2;
+
+    let r_years = (first_year..=last_year)
+        .map(|year| {
+            let x = year_x(year);
+            let label_y = axis_y + 18;
+            let tick_y = axis_y + 4;
+            formatdoc!(r#"
+                <line x1="{x}" x2="{x}" y1="{axis_y}" y2="{tick_y}"/>
+                <text text-anchor="middle" x="{x}" y="{label_y}">{year}</text>
+            "#)
+        })
+        .collect::<Vec<String>>()
+        .join("");
+
+    let r_releases = stacked
+        .iter()
+        .map(|(year, level, release)| {
+            let release_ref = release.borrow();
+            let permalink = &release_ref.permalink.slug;
+            let date = release_ref.date.unwrap();
+            let release_title_escaped = html_escape_outside_attribute(&release_ref.title);
+
+            let cx = year_x(*year);
+            let cy = axis_y - (level + 1) * TIMELINE_ROW_HEIGHT + TIMELINE_ROW_HEIGHT / 2;
+
+            formatdoc!(r#"
+                <a href="{root_prefix}{permalink}{index_suffix}">
+                    <title>{release_title_escaped} ({date})</title>
+                    <circle cx="{cx}" cy="{cy}" r="6"/>
+                </a>
+            "#)
+        })
+        .collect::<Vec<String>>()
+        .join("");
+
+    formatdoc!(r#"
+        <div class="page">
+            <div class="page_center">
+                <div class="release_timeline">
+                    <h2>{t_timeline}</h2>
+                    <div class="release_timeline_scroll">
+                        <svg height="{height}" viewBox="0 0 {width} {height}" width="{width}" xmlns="http://www.w3.org/2000/svg">
+                            <line class="axis" x1="0" x2="{width}" y1="{axis_y}" y2="{axis_y}"/>
+                            {r_years}
+                            {r_releases}
+                        </svg>
+                    </div>
+                </div>
+            </div>
+        </div>
+    "#)
+}
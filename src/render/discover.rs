@@ -0,0 +1,73 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use indoc::formatdoc;
+
+use crate::TRACK_NUMBERS;
+
+use super::{CatalogContext, Layout};
+
+/// A page that sends visitors to a random track of the catalog. The list of
+/// tracks is generated at build time, the pick happens in the browser. It is
+/// weighted such that every release is equally likely to be picked first
+/// (regardless of its number of tracks), then one of its tracks is picked.
+pub fn discover_html(context: &CatalogContext) -> String {
+    let &CatalogContext { build, catalog } = context;
+    let index_suffix = build.index_suffix();
+    let root_prefix = "../";
+
+    let mut layout = Layout::new();
+
+    layout.no_indexing();
+
+    let releases_json = catalog.public_releases()
+        .iter()
+        .map(|release| {
+            let release_ref = release.borrow();
+            let release_slug = &release_ref.permalink.slug;
+
+            let track_urls = release_ref.tracks
+                .iter()
+                .zip(TRACK_NUMBERS)
+                .map(|(_track, track_number)| format!(r#""{root_prefix}{release_slug}/{track_number}{index_suffix}""#))
+                .collect::<Vec<String>>()
+                .join(",");
+
+            format!("[{track_urls}]")
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let t_discover = &build.locale.translations.discover;
+    let t_discover_text = &build.locale.translations.discover_text;
+
+    let body = formatdoc!(r#"
+        <div class="page">
+            <div class="page_center">
+                <div style="max-width: 28rem;">
+                    <h1 style="margin-bottom: 2rem;">{t_discover}</h1>
+                    <p>{t_discover_text}</p>
+                </div>
+            </div>
+        </div>
+        <script>
+            (() => {{
+                const releases = [{releases_json}];
+                if (releases.length === 0) return;
+                const tracks = releases[Math.floor(Math.random() * releases.length)];
+                location.replace(tracks[Math.floor(Math.random() * tracks.length)]);
+            }})();
+        </script>
+    "#);
+
+    let catalog_title = catalog.title();
+
+    let page_title = format!("{t_discover} – {catalog_title}");
+
+    layout.render(
+        &body,
+        context,
+        root_prefix,
+        &page_title
+    )
+}
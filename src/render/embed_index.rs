@@ -0,0 +1,119 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! In an embeds-only build (--embeds-only) there is no regular site, the
+//! index page of the build instead lists the embed codes of all players
+//! that were built, each along with a preview of the player itself.
+
+use indoc::formatdoc;
+
+use crate::{
+    Build,
+    Catalog,
+    GENERATOR_INFO,
+    SiteUrl,
+    TRACK_NUMBERS
+};
+use crate::minify;
+use crate::util::html_escape_outside_attribute;
+
+use super::embed_iframe;
+
+pub fn embed_index_html(base_url: &SiteUrl, build: &Build, catalog: &Catalog) -> String {
+    let translations = &build.locale.translations;
+
+    let embed_entry = |label: &str, path: String, title: &str| -> String {
+        let embed_url = base_url.join_index(build, path);
+        let iframe = embed_iframe(&embed_url, &translations.audio_player_widget_for_xxx(title));
+        let iframe_escaped = html_escape_outside_attribute(&iframe);
+
+        formatdoc!(r#"
+            <div class="embed">
+                <h3>{label}</h3>
+                <pre>{iframe_escaped}</pre>
+                {iframe}
+            </div>
+        "#)
+    };
+
+    let mut r_releases = String::new();
+
+    for release in &catalog.releases {
+        let release_ref = release.borrow();
+        let release_slug = &release_ref.permalink.slug;
+
+        let mut r_embeds = String::new();
+
+        if release_ref.embedding {
+            r_embeds.push_str(&embed_entry(
+                *translations.embed_entire_release,
+                format!("{release_slug}/embed/all"),
+                &release_ref.title
+            ));
+        }
+
+        for (track, track_number) in release_ref.tracks.iter().zip(TRACK_NUMBERS) {
+            if track.embedding {
+                let track_title = track.title();
+                let label = format!(
+                    "{} {}",
+                    release_ref.track_numbering.format(track_number),
+                    html_escape_outside_attribute(&track_title)
+                );
+
+                r_embeds.push_str(&embed_entry(
+                    &label,
+                    format!("{release_slug}/embed/{track_number}"),
+                    &track_title
+                ));
+            }
+        }
+
+        if r_embeds.is_empty() {
+            continue;
+        }
+
+        let release_title_escaped = html_escape_outside_attribute(&release_ref.title);
+
+        r_releases.push_str(&formatdoc!(r#"
+            <h2>{release_title_escaped}</h2>
+            {r_embeds}
+        "#));
+    }
+
+    let catalog_title_escaped = html_escape_outside_attribute(&catalog.title());
+    let dir_attribute = if build.locale.text_direction.is_rtl() { r#"dir="rtl""# } else { "" };
+    let lang = &build.locale.language;
+    let t_embed = &translations.embed;
+    let theme_css_hash = build.asset_hashes.theme_css
+        .get(&catalog.theme.stylesheet_filename())
+        .unwrap();
+    let theme_stylesheet_filename = catalog.theme.stylesheet_filename();
+
+    let html = formatdoc!(r#"
+        <!DOCTYPE html>
+        <html {dir_attribute} lang="{lang}">
+            <head>
+                <title>{t_embed} – {catalog_title_escaped}</title>
+                <meta charset="utf-8">
+                <meta name="generator" content="{GENERATOR_INFO}">
+                <meta name="robots" content="noindex, nofollow">
+                <meta name="viewport" content="width=device-width, initial-scale=1">
+                <link href="{theme_stylesheet_filename}?{theme_css_hash}" rel="stylesheet">
+                <style>
+                    body {{ background: var(--bg-1); color: var(--fg-1); margin: 2rem auto; max-width: 60rem; padding: 0 1rem; }}
+                    h2 {{ margin-top: 3rem; }}
+                    h3 {{ color: var(--fg-3); font-size: 1rem; font-weight: normal; }}
+                    pre {{ background: var(--bg-2); overflow-x: auto; padding: .5rem; user-select: all; white-space: pre-wrap; word-break: break-all; }}
+                    .embed {{ margin-bottom: 2rem; }}
+                </style>
+            </head>
+            <body>
+                <h1>{t_embed} – {catalog_title_escaped}</h1>
+                {r_releases}
+            </body>
+        </html>
+    "#);
+
+    if build.minify { minify::html(&html) } else { html }
+}
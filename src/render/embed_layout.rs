@@ -10,7 +10,7 @@ use crate::{
     SiteUrl,
     Theme
 };
-use crate::icons;
+use crate::{icons, minify};
 use crate::util::html_escape_outside_attribute;
 
 use super::player_icon_templates;
@@ -31,6 +31,7 @@ impl EmbedLayout {
         let dir_attribute = if build.locale.text_direction.is_rtl() { r#"dir="rtl""# } else { "" };
 
         let display_link_url = SiteUrl::pretty_display(link_url);
+        let root_asset_prefix = build.asset_prefix(root_prefix, root_prefix, "");
         let embeds_css_hash = AssetHashes::EMBEDS_CSS;
         let embeds_js_hash = build.asset_hashes.embeds_js.as_ref().unwrap();
 
@@ -47,11 +48,25 @@ impl EmbedLayout {
 
         let title_escaped = html_escape_outside_attribute(title);
 
-        let t_javascript_is_disabled_listen_at_xxx = translations
-            .javascript_is_disabled_listen_at_xxx(
-                &format!(r#"<a href="{link_url}">{external_icon} {display_link_url}</a>"#)
-            );
-        formatdoc!(r#"
+        // In an embeds-only build there is no page we could refer to
+        let r_js_notice = if build.embeds_only {
+            String::new()
+        } else {
+            let t_javascript_is_disabled_listen_at_xxx = translations
+                .javascript_is_disabled_listen_at_xxx(
+                    &format!(r#"<a href="{link_url}">{external_icon} {display_link_url}</a>"#)
+                );
+
+            formatdoc!(r#"
+                <aside class="js_notice">
+                    <div>
+                        {t_javascript_is_disabled_listen_at_xxx}
+                    </div>
+                </aside>
+            "#)
+        };
+
+        let html = formatdoc!(r#"
             <!DOCTYPE html>
             <html {dir_attribute} lang="{lang}">
                 <head>
@@ -60,8 +75,8 @@ impl EmbedLayout {
                     <meta name="generator" content="{GENERATOR_INFO}">
                     <meta name="robots" content="noindex, nofollow">
                     <meta name="viewport" content="width=device-width, initial-scale=1">
-                    <link href="{root_prefix}{theme_stylesheet_filename}?{theme_css_hash}" rel="stylesheet">
-                    <link href="{root_prefix}embeds.css?{embeds_css_hash}" rel="stylesheet">
+                    <link href="{root_asset_prefix}{theme_stylesheet_filename}?{theme_css_hash}" rel="stylesheet">
+                    <link href="{root_asset_prefix}embeds.css?{embeds_css_hash}" rel="stylesheet">
                     <script defer src="{root_prefix}embeds.js?{embeds_js_hash}"></script>
                 </head>
                 <body>
@@ -69,14 +84,12 @@ impl EmbedLayout {
                     <main>
                         {body}
                     </main>
-                    <aside class="js_notice">
-                        <div>
-                            {t_javascript_is_disabled_listen_at_xxx}
-                        </div>
-                    </aside>
+                    {r_js_notice}
                     {templates}
                 </body>
             </html>
-        "#)
+        "#);
+
+        if build.minify { minify::html(&html) } else { html }
     }
 }
@@ -1,28 +1,65 @@
 // SPDX-FileCopyrightText: 2022-2025 Simon Repp
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use chrono::Datelike;
 use indoc::formatdoc;
+use translations::Translations;
 
-use crate::{Build, Catalog};
+use crate::{ArtistRc, Catalog, DescribedImage};
+use crate::util::html_escape_outside_attribute;
 
-use super::Layout;
+use super::{CatalogContext, Layout};
 
-pub fn image_descriptions_html(build: &Build, catalog: &Catalog) -> String {
+/// E.g. "Cover of Ape Affairs by Heston Exchange, released 2019"
+pub fn cover_description(
+    translations: &Translations,
+    title: &str,
+    artists: &[ArtistRc],
+    context: Option<String>
+) -> String {
+    let mut description = if artists.is_empty() {
+        translations.cover_of_xxx(title)
+    } else {
+        let names = artists
+            .iter()
+            .map(|artist| artist.borrow().name.clone())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        translations.cover_of_xxx_by_xxx(title, &names)
+    };
+
+    if let Some(context) = context {
+        description.push_str(&format!(", {context}"));
+    }
+
+    description
+}
+
+pub fn image_descriptions_html(context: &CatalogContext) -> String {
+    let &CatalogContext { build, catalog } = context;
     let root_prefix = "../";
+    let translations = &build.locale.translations;
 
     let mut layout = Layout::new();
 
     layout.no_indexing();
 
-    let t_image_descriptions = &build.locale.translations.image_descriptions;
-    let t_image_descriptions_guide = &build.locale.translations.image_descriptions_guide;
-    
+    let t_image_descriptions = &translations.image_descriptions;
+    let t_image_descriptions_guide = &translations.image_descriptions_guide;
+
+    let r_suggestions = match catalog.image_description_suggestions {
+        true => suggestions(catalog, translations),
+        false => String::new()
+    };
+
     let body = formatdoc!(r#"
         <div class="page">
             <div class="page_center">
                 <div style="max-width: 28rem;">
                     <h1 style="margin-bottom: 2rem;">{t_image_descriptions}</h1>
                     {t_image_descriptions_guide}
+                    {r_suggestions}
                 </div>
             </div>
         </div>
@@ -30,10 +67,59 @@ pub fn image_descriptions_html(build: &Build, catalog: &Catalog) -> String {
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &catalog.theme,
         t_image_descriptions
     )
 }
+
+/// Lists suggested descriptions (built from titles, names and dates) for
+/// the artist images and covers on public pages that have no description.
+fn suggestions(catalog: &Catalog, translations: &Translations) -> String {
+    let mut descriptions = Vec::new();
+
+    let missing_description = |described_image: &Option<DescribedImage>| {
+        described_image.as_ref().is_some_and(|described_image| described_image.description.is_none())
+    };
+
+    for artist in &catalog.featured_artists {
+        let artist_ref = artist.borrow();
+        if missing_description(&artist_ref.image) {
+            descriptions.push(translations.image_of_xxx(&artist_ref.name));
+        }
+    }
+
+    for release in catalog.public_releases() {
+        let release_ref = release.borrow();
+        if missing_description(&release_ref.cover) {
+            let released = release_ref.date.map(|date| translations.released_xxx(&date.year().to_string()));
+            descriptions.push(cover_description(translations, &release_ref.title, &release_ref.main_artists, released));
+        }
+
+        for track in &release_ref.tracks {
+            if missing_description(&track.cover) {
+                let from_release = Some(translations.from_xxx(&release_ref.title));
+                descriptions.push(cover_description(translations, &track.title(), &track.artists, from_release));
+            }
+        }
+    }
+
+    if descriptions.is_empty() {
+        return String::new();
+    }
+
+    let r_items = descriptions
+        .iter()
+        .map(|description| format!("<li>{}</li>", html_escape_outside_attribute(description)))
+        .collect::<Vec<String>>()
+        .join("");
+
+    let t_image_description_suggestions = &translations.image_description_suggestions;
+    let t_image_description_suggestions_hint = &translations.image_description_suggestions_hint;
+
+    formatdoc!(r#"
+        <h2 style="margin-top: 2rem;">{t_image_description_suggestions}</h2>
+        <p>{t_image_description_suggestions_hint}</p>
+        <ul>{r_items}</ul>
+    "#)
+}
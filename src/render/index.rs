@@ -7,33 +7,195 @@ use crate::M3U_PLAYLIST_FILENAME;
 use crate::{
     Build,
     Catalog,
-    OpenGraphMeta
+    Hero,
+    HeroImage,
+    HeroTarget,
+    ImgAttributes,
+    MirroredPage,
+    OpenGraphMeta,
+    PageType
 };
 use crate::icons;
-use crate::util::html_escape_outside_attribute;
+use crate::util::{html_escape_inside_attribute, html_escape_outside_attribute};
 
-use super::Layout;
+use super::{CatalogContext, Layout};
 use super::{
     artist_image,
     copy_button,
     link_action,
-    releases
+    release_filters,
+    releases,
+    share_actions,
+    wrap_undescribed_image
 };
 
-pub fn index_html(build: &Build, catalog: &Catalog) -> String {
+/// Renders the hero section that is displayed above the release grid.
+/// References to the latest release (for the image or the button target)
+/// are silently omitted when the catalog has no public releases.
+fn hero_section(
+    build: &Build,
+    catalog: &Catalog,
+    hero: &Hero,
+    index_suffix: &str,
+    root_prefix: &str
+) -> String {
+    let latest_release = catalog.latest_release();
+
+    let r_image = match &hero.image {
+        HeroImage::Custom(described_image) => {
+            let image_ref = described_image.borrow();
+
+            let alt = match &described_image.description {
+                Some(description) => format!(r#"alt="{}""#, html_escape_inside_attribute(description)),
+                None => String::new()
+            };
+
+            let hash = image_ref.hash.as_url_safe_base64();
+            let root_asset_prefix = build.asset_prefix(root_prefix, root_prefix, "");
+            let prefix = format!("{root_asset_prefix}{}", Hero::IMAGE_FILENAME_PREFIX);
+
+            let ImgAttributes { src, srcset } = image_ref.artist_assets
+                .as_ref()
+                .unwrap()
+                .img_attributes_fluid(&hash, &prefix);
+
+            let image = formatdoc!(r#"
+                <img
+                    {alt}
+                    class="hero_image"
+                    sizes="(min-width: 50rem) 30rem, 100vw"
+                    src="{src}"
+                    srcset="{srcset}">
+            "#);
+
+            if described_image.description.is_some() {
+                image
+            } else {
+                wrap_undescribed_image(build, root_prefix, &image, "", "hero_image")
+            }
+        }
+        HeroImage::LatestReleaseCover => match &latest_release {
+            Some(release) => {
+                let release_ref = release.borrow();
+                let release_slug = &release_ref.permalink.slug;
+                let release_prefix = build.asset_prefix(&format!("{root_prefix}{release_slug}/"), root_prefix, release_slug);
+
+                match &release_ref.cover {
+                    Some(described_image) => {
+                        let image_ref = described_image.borrow();
+
+                        let alt = match &described_image.description {
+                            Some(description) => format!(r#"alt="{}""#, html_escape_inside_attribute(description)),
+                            None => String::new()
+                        };
+
+                        let hash = image_ref.hash.as_url_safe_base64();
+
+                        let ImgAttributes { src, srcset } = image_ref.cover_assets_unchecked()
+                            .img_attributes_up_to_1280(&hash, &release_prefix);
+
+                        let image = formatdoc!(r#"
+                            <img
+                                {alt}
+                                class="hero_image"
+                                sizes="(min-width: 50rem) 30rem, 100vw"
+                                src="{src}"
+                                srcset="{srcset}">
+                        "#);
+
+                        if described_image.description.is_some() {
+                            image
+                        } else {
+                            wrap_undescribed_image(build, root_prefix, &image, "", "hero_image")
+                        }
+                    }
+                    None => {
+                        let ImgAttributes { src, srcset } = release_ref.procedural_cover_unchecked()
+                            .borrow()
+                            .img_attributes_all_sizes(&release_prefix);
+
+                        formatdoc!(r#"
+                            <img
+                                aria-hidden="true"
+                                class="hero_image procedural"
+                                sizes="(min-width: 50rem) 30rem, 100vw"
+                                src="{src}"
+                                srcset="{srcset}">
+                        "#)
+                    }
+                }
+            }
+            None => String::new()
+        }
+        HeroImage::None => String::new()
+    };
+
+    let r_headline = match &hero.headline {
+        Some(headline) => format!("<h2>{}</h2>", html_escape_outside_attribute(headline)),
+        None => String::new()
+    };
+
+    let r_subtext = match &hero.subtext {
+        Some(subtext) => format!("<p>{}</p>", html_escape_outside_attribute(subtext)),
+        None => String::new()
+    };
+
+    let r_button = match &hero.button {
+        Some(button) => {
+            let href = match &button.target {
+                HeroTarget::LatestRelease => latest_release.as_ref().map(|release| {
+                    let release_ref = release.borrow();
+                    let release_slug = &release_ref.permalink.slug;
+                    format!("{root_prefix}{release_slug}{index_suffix}")
+                }),
+                HeroTarget::Url(url) => Some(html_escape_inside_attribute(url))
+            };
+
+            match href {
+                Some(href) => {
+                    let label_escaped = html_escape_outside_attribute(&button.label);
+                    format!(r#"<a class="button" href="{href}">{label_escaped}</a>"#)
+                }
+                None => String::new()
+            }
+        }
+        None => String::new()
+    };
+
+    formatdoc!(r#"
+        <div class="page">
+            <div class="page_center">
+                <div class="hero">
+                    {r_image}
+                    <div class="hero_text">
+                        {r_headline}
+                        {r_subtext}
+                        {r_button}
+                    </div>
+                </div>
+            </div>
+        </div>
+    "#)
+}
+
+pub fn index_html(context: &CatalogContext) -> String {
+    let &CatalogContext { build, catalog } = context;
     let index_suffix = build.index_suffix();
     let root_prefix = "";
     let translations = &build.locale.translations;
     
     let mut layout = Layout::new();
 
+    layout.set_mirrored_page(MirroredPage::Home);
+    layout.set_page_type(PageType::Listing);
+
     let catalog_title = catalog.title();
 
     let title_escaped = html_escape_outside_attribute(&catalog_title);
 
     let home_image = match &catalog.home_image {
         Some(home_image) => artist_image(
-            root_prefix,
+            &build.asset_prefix(root_prefix, root_prefix, ""),
             build,
             home_image,
             root_prefix
@@ -88,6 +250,11 @@ pub fn index_html(build: &Build, catalog: &Catalog) -> String {
         actions.push(r_copy_link);
     }
 
+    if let Some(base_url) = &build.base_url {
+        let r_share_actions = share_actions(&catalog.share_buttons, &catalog_title, translations, &base_url.index(build));
+        actions.extend(r_share_actions);
+    }
+
     if build.base_url.is_some() {
         if catalog.feeds.any_requested() {
             let t_subscribe = &translations.subscribe;
@@ -119,6 +286,48 @@ pub fn index_html(build: &Build, catalog: &Catalog) -> String {
         }
     }
 
+    if let Some(timeline_permalink) = &catalog.timeline_permalink {
+        let t_timeline = &translations.timeline;
+        let calendar_icon = icons::CALENDAR;
+
+        let timeline_link = format!(r#"
+            <a href="{root_prefix}{timeline_permalink}{index_suffix}">
+                {calendar_icon}
+                <span>{t_timeline}</span>
+            </a>
+        "#);
+
+        actions.push(timeline_link);
+    }
+
+    if let Some(list_permalink) = &catalog.list_permalink {
+        let t_list = &translations.list;
+        let browse_icon = icons::BROWSE;
+
+        let list_link = format!(r#"
+            <a href="{root_prefix}{list_permalink}{index_suffix}">
+                {browse_icon}
+                <span>{t_list}</span>
+            </a>
+        "#);
+
+        actions.push(list_link);
+    }
+
+    if let Some(search_permalink) = &catalog.search_permalink {
+        let t_search = &translations.search;
+        let browse_icon = icons::BROWSE;
+
+        let search_link = format!(r#"
+            <a href="{root_prefix}{search_permalink}{index_suffix}">
+                {browse_icon}
+                <span>{t_search}</span>
+            </a>
+        "#);
+
+        actions.push(search_link);
+    }
+
     for link in &catalog.links {
         let r_link = link_action(link, translations);
         actions.push(r_link);
@@ -136,15 +345,24 @@ pub fn index_html(build: &Build, catalog: &Catalog) -> String {
         "#)
     };
 
-    let public_releases = catalog.public_releases();
+    let home_page_releases = catalog.home_page_releases(1);
     let r_releases = releases(
         build,
         index_suffix,
         root_prefix,
         catalog,
-        &public_releases
+        &home_page_releases
     );
 
+    let r_pagination = pagination(build, catalog, index_suffix, 1, root_prefix);
+
+    let r_release_filters = release_filters(build, catalog, index_suffix, root_prefix, None);
+
+    let r_hero = match &catalog.hero {
+        Some(hero) => hero_section(build, catalog, hero, index_suffix, root_prefix),
+        None => String::new()
+    };
+
     let synopsis = match &catalog.synopsis {
         Some(synopsis) => {
             formatdoc!(r#"
@@ -167,10 +385,13 @@ pub fn index_html(build: &Build, catalog: &Catalog) -> String {
                 </div>
             </div>
         </div>
+        {r_hero}
         <div class="page">
             <div class="page_grid">
                 <div>
+                    {r_release_filters}
                     {r_releases}
+                    {r_pagination}
                 </div>
             </div>
         </div>
@@ -189,7 +410,7 @@ pub fn index_html(build: &Build, catalog: &Catalog) -> String {
             if let Some(described_image) = &catalog.home_image {
                 let opengraph_image = described_image
                     .borrow()
-                    .artist_opengraph_image(base_url.prefix());
+                    .artist_opengraph_image(build.asset_url().prefix());
 
                 meta.image(opengraph_image);
 
@@ -204,10 +425,139 @@ pub fn index_html(build: &Build, catalog: &Catalog) -> String {
 
     layout.render(
         &body,
+        context,
+        root_prefix,
+        &catalog_title
+    )
+}
+
+/// The pages after the first one when the homepage is split into several
+/// pages (see the releases_per_page option), placed at
+/// /[pagination_permalink]/[page]/. They only list their share of the
+/// releases, the hero, actions and long-form text remain on the first page.
+pub fn index_page_html(context: &CatalogContext, page: usize) -> String {
+    let &CatalogContext { build, catalog } = context;
+    let index_suffix = build.index_suffix();
+    let root_prefix = "../../";
+    let translations = &build.locale.translations;
+
+    let pagination_permalink = catalog.pagination_permalink.as_ref().unwrap();
+    let path = format!("{pagination_permalink}/{page}");
+
+    let mut layout = Layout::new();
+
+    layout.set_mirrored_page(MirroredPage::Page(path.clone()));
+    layout.set_page_type(PageType::Listing);
+
+    let home_page_releases = catalog.home_page_releases(page);
+    let r_releases = releases(
         build,
+        index_suffix,
+        root_prefix,
         catalog,
+        &home_page_releases
+    );
+
+    let r_pagination = pagination(build, catalog, index_suffix, page, root_prefix);
+    let r_release_filters = release_filters(build, catalog, index_suffix, root_prefix, None);
+
+    let t_page_xxx_of_xxx = translations.page_xxx_of_xxx(&page.to_string(), &catalog.home_page_count().to_string());
+    let title = format!("{} – {t_page_xxx_of_xxx}", catalog.title());
+    let title_escaped = html_escape_outside_attribute(&title);
+
+    let body = formatdoc!(r#"
+        <div class="page">
+            <div class="page_grid">
+                <div>
+                    <h1>{title_escaped}</h1>
+                    {r_release_filters}
+                    {r_releases}
+                    {r_pagination}
+                </div>
+            </div>
+        </div>
+    "#);
+
+    if catalog.opengraph {
+        if let Some(base_url) = &build.base_url {
+            let page_url = base_url.join_index(build, &path);
+            let mut meta = OpenGraphMeta::new(title.clone(), page_url);
+
+            if let Some(synopsis) = &catalog.synopsis {
+                meta.description(synopsis);
+            }
+
+            if let Some(described_image) = &catalog.home_image {
+                let opengraph_image = described_image
+                    .borrow()
+                    .artist_opengraph_image(build.asset_url().prefix());
+
+                meta.image(opengraph_image);
+
+                if let Some(description) = &described_image.description {
+                    meta.image_alt(description);
+                }
+            }
+
+            layout.add_opengraph_meta(meta);
+        }
+    }
+
+    layout.render(
+        &body,
+        context,
         root_prefix,
-        &catalog.theme,
-        &catalog_title
+        &title
     )
 }
+
+/// Links to the previous and next page of the homepage, rendered only when
+/// the homepage is split into several pages.
+fn pagination(
+    build: &Build,
+    catalog: &Catalog,
+    index_suffix: &str,
+    page: usize,
+    root_prefix: &str
+) -> String {
+    let Some(pagination_permalink) = &catalog.pagination_permalink else {
+        return String::new();
+    };
+
+    let page_count = catalog.home_page_count();
+    let translations = &build.locale.translations;
+
+    let page_href = |page: usize| -> String {
+        match page {
+            1 => format!("{root_prefix}.{index_suffix}"),
+            _ => format!("{root_prefix}{pagination_permalink}/{page}{index_suffix}")
+        }
+    };
+
+    let r_previous = match page {
+        1 => String::new(),
+        _ => {
+            let href = page_href(page - 1);
+            let t_previous_page = &translations.previous_page;
+            format!(r#"<a href="{href}" rel="prev">← {t_previous_page}</a>"#)
+        }
+    };
+
+    let r_next = if page < page_count {
+        let href = page_href(page + 1);
+        let t_next_page = &translations.next_page;
+        format!(r#"<a href="{href}" rel="next">{t_next_page} →</a>"#)
+    } else {
+        String::new()
+    };
+
+    let t_page_xxx_of_xxx = translations.page_xxx_of_xxx(&page.to_string(), &page_count.to_string());
+
+    formatdoc!(r#"
+        <nav class="pagination">
+            {r_previous}
+            <span>{t_page_xxx_of_xxx}</span>
+            {r_next}
+        </nav>
+    "#)
+}
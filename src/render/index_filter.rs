@@ -0,0 +1,56 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use indoc::formatdoc;
+
+use crate::{PageType, ReleaseFilter};
+use crate::util::html_escape_outside_attribute;
+
+use super::{CatalogContext, Layout, release_filters, releases};
+
+/// The cover grid of only those public releases that have a specific
+/// imprint or region, linked to from the filter chips on the homepage.
+pub fn index_filter_html(context: &CatalogContext, filter: &ReleaseFilter) -> String {
+    let &CatalogContext { build, catalog } = context;
+    let index_suffix = build.index_suffix();
+    let root_prefix = "../../../";
+
+    let mut layout = Layout::new();
+
+    layout.set_page_type(PageType::Listing);
+
+    let filtered_releases = catalog.public_releases()
+        .into_iter()
+        .filter(|release| filter.matches(&release.borrow()))
+        .collect::<Vec<_>>();
+
+    let r_releases = releases(
+        build,
+        index_suffix,
+        root_prefix,
+        catalog,
+        &filtered_releases
+    );
+
+    let r_release_filters = release_filters(build, catalog, index_suffix, root_prefix, Some(filter));
+
+    let label_escaped = html_escape_outside_attribute(&filter.label);
+    let body = formatdoc!(r#"
+        <div class="page">
+            <div class="page_grid">
+                <div>
+                    <h1>{label_escaped}</h1>
+                    {r_release_filters}
+                    {r_releases}
+                </div>
+            </div>
+        </div>
+    "#);
+
+    layout.render(
+        &body,
+        context,
+        root_prefix,
+        &filter.label
+    )
+}
@@ -0,0 +1,93 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use chrono::Datelike;
+use indoc::formatdoc;
+
+use crate::{MirroredPage, PageType};
+use crate::util::{format_time, html_escape_outside_attribute};
+
+use super::{CatalogContext, Layout, Truncation, list_release_artists};
+
+/// A compact text list of all public releases (most recent first), which is
+/// more practical than the cover grid for catalogs with many releases.
+pub fn index_list_html(context: &CatalogContext) -> String {
+    let &CatalogContext { build, catalog } = context;
+    let index_suffix = build.index_suffix();
+    let root_prefix = "../";
+    let translations = &build.locale.translations;
+
+    let mut layout = Layout::new();
+
+    layout.set_mirrored_page(MirroredPage::Page(catalog.list_permalink.clone().unwrap()));
+    layout.set_page_type(PageType::Listing);
+
+    let mut releases_desc_by_date = catalog.public_releases();
+
+    releases_desc_by_date.sort_by_key(|release| release.borrow().date);
+
+    let r_rows = releases_desc_by_date
+        .iter()
+        .rev()
+        .map(|release| {
+            let release_ref = release.borrow();
+            let permalink = &release_ref.permalink.slug;
+
+            let href = format!("{root_prefix}{permalink}{index_suffix}");
+
+            let artists_truncation = Truncation::Truncate {
+                max_chars: 40,
+                others_link: format!("{href}#more")
+            };
+            let artists = list_release_artists(build, index_suffix, root_prefix, catalog, artists_truncation, &release_ref);
+
+            let release_title_escaped = html_escape_outside_attribute(&release_ref.title_disambiguated());
+
+            let year = match release_ref.date {
+                Some(date) => date.year().to_string(),
+                None => String::new()
+            };
+
+            let duration_seconds = release_ref.tracks
+                .iter()
+                .map(|track| track.transcodes.borrow().source_meta.duration_seconds)
+                .sum::<f32>();
+            let duration_formatted = format_time(duration_seconds);
+
+            formatdoc!(r#"
+                <tr>
+                    <td><a href="{href}">{release_title_escaped}</a></td>
+                    <td class="artists">{artists}</td>
+                    <td class="year">{year}</td>
+                    <td class="time">{duration_formatted}</td>
+                </tr>
+            "#)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let t_list = &translations.list;
+    let body = formatdoc!(r#"
+        <div class="page">
+            <div class="page_center">
+                <div>
+                    <h1>{t_list}</h1>
+                    <table class="index_list">
+                        {r_rows}
+                    </table>
+                </div>
+            </div>
+        </div>
+    "#);
+
+    let catalog_title = catalog.title();
+
+    let page_title = format!("{t_list} – {catalog_title}");
+
+    layout.render(
+        &body,
+        context,
+        root_prefix,
+        &page_title
+    )
+}
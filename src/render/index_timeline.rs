@@ -0,0 +1,98 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use chrono::Datelike;
+use indoc::formatdoc;
+
+use crate::{MirroredPage, PageType};
+
+use super::{CatalogContext, Layout, compact_release_identifier};
+
+/// All public releases grouped by year (most recent first), releases without
+/// a date are listed last.
+pub fn index_timeline_html(context: &CatalogContext) -> String {
+    let &CatalogContext { build, catalog } = context;
+    let index_suffix = build.index_suffix();
+    let root_prefix = "../";
+    let translations = &build.locale.translations;
+
+    let mut layout = Layout::new();
+
+    layout.set_mirrored_page(MirroredPage::Page(catalog.timeline_permalink.clone().unwrap()));
+    layout.set_page_type(PageType::Listing);
+
+    let mut releases_desc_by_date = catalog.public_releases();
+
+    releases_desc_by_date.sort_by_key(|release| release.borrow().date);
+    releases_desc_by_date.reverse();
+
+    // Each group is a year (None for undated releases) and its rendered releases
+    let mut groups: Vec<(Option<i32>, Vec<String>)> = Vec::new();
+
+    for release in &releases_desc_by_date {
+        let release_ref = release.borrow();
+        let permalink = &release_ref.permalink.slug;
+
+        let release_link = format!("{root_prefix}{permalink}{index_suffix}");
+        let release_prefix = format!("{root_prefix}{permalink}/");
+
+        let r_release = compact_release_identifier(
+            build,
+            catalog,
+            index_suffix,
+            &release_ref,
+            &release_link,
+            &release_prefix,
+            root_prefix
+        );
+
+        let year = release_ref.date.map(|date| date.year());
+
+        match groups.last_mut() {
+            Some((group_year, group_releases)) if *group_year == year => group_releases.push(r_release),
+            _ => groups.push((year, vec![r_release]))
+        }
+    }
+
+    // Sorting by date puts undated releases first, after reversing they are
+    // last, which is where we want them.
+    let r_groups = groups
+        .into_iter()
+        .map(|(year, group_releases)| {
+            let heading = match year {
+                Some(year) => year.to_string(),
+                None => translations.undated.to_string()
+            };
+            let joined = group_releases.join("\n");
+
+            formatdoc!(r#"
+                <h2>{heading}</h2>
+                {joined}
+            "#)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let t_timeline = &translations.timeline;
+    let body = formatdoc!(r#"
+        <div class="page">
+            <div class="page_center">
+                <div class="index_timeline">
+                    <h1>{t_timeline}</h1>
+                    {r_groups}
+                </div>
+            </div>
+        </div>
+    "#);
+
+    let catalog_title = catalog.title();
+
+    let page_title = format!("{t_timeline} – {catalog_title}");
+
+    layout.render(
+        &body,
+        context,
+        root_prefix,
+        &page_title
+    )
+}
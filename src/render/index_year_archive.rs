@@ -0,0 +1,79 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use indoc::formatdoc;
+
+use crate::{Feeds, MirroredPage, PageType, YearArchivePage};
+
+use super::{CatalogContext, Layout, releases};
+
+/// The cover grid of only those public releases that were released in a
+/// specific year (or decade), linked to from the site footer and from the
+/// release pages.
+pub fn index_year_archive_html(context: &CatalogContext, page: &YearArchivePage) -> String {
+    let &CatalogContext { build, catalog } = context;
+    let index_suffix = build.index_suffix();
+    let root_prefix = "../";
+    let translations = &build.locale.translations;
+
+    let mut layout = Layout::new();
+
+    layout.set_mirrored_page(MirroredPage::Page(page.slug.clone()));
+    layout.set_page_type(PageType::Listing);
+
+    if catalog.year_archives.as_ref().is_some_and(|year_archives| year_archives.feeds) &&
+        build.base_url.is_some() {
+        let filename = Feeds::ATOM_FILENAME;
+        layout.add_feed_link(format!(r#"<link rel="alternate" type="application/atom+xml" title="Atom" href="{filename}">"#));
+    }
+
+    let archive_releases = catalog.public_releases()
+        .into_iter()
+        .filter(|release| page.matches(&release.borrow()))
+        .collect::<Vec<_>>();
+
+    let r_releases = releases(
+        build,
+        index_suffix,
+        root_prefix,
+        catalog,
+        &archive_releases
+    );
+
+    let r_pages = catalog.year_archive_pages
+        .iter()
+        .map(|other_page| {
+            let current = if other_page.slug == page.slug { r#" aria-current="page""# } else { "" };
+            let label = other_page.label();
+            let slug = &other_page.slug;
+
+            format!(r#"<a{current} href="{root_prefix}{slug}{index_suffix}">{label}</a>"#)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let label = page.label();
+    let t_all_releases = &translations.all_releases;
+    let t_archive = &translations.archive;
+    let body = formatdoc!(r#"
+        <div class="page">
+            <div class="page_grid">
+                <div>
+                    <h1>{label}</h1>
+                    <nav aria-label="{t_archive}" class="release_filters">
+                        <a href="{root_prefix}">{t_all_releases}</a>
+                        {r_pages}
+                    </nav>
+                    {r_releases}
+                </div>
+            </div>
+        </div>
+    "#);
+
+    layout.render(
+        &body,
+        context,
+        root_prefix,
+        &label
+    )
+}
@@ -0,0 +1,240 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! A tiny standalone widget presenting the latest release of the catalog,
+//! meant to be included on external sites (e.g. an artist's personal
+//! homepage). It consists of a self-contained html page and a script that
+//! injects that page as an iframe wherever it is included. As both are
+//! regenerated on each build, the widget always shows the latest release.
+
+use std::hash::Hash;
+
+use indoc::formatdoc;
+
+use crate::{
+    Build,
+    Catalog,
+    GENERATOR_INFO,
+    Release,
+    SiteUrl,
+    TRACK_NUMBERS
+};
+use crate::icons;
+use crate::util::{html_escape_inside_attribute, html_escape_outside_attribute};
+
+pub const LATEST_RELEASE_WIDGET_HTML_FILENAME: &str = "latest-release.html";
+pub const LATEST_RELEASE_WIDGET_JS_FILENAME: &str = "latest-release.js";
+
+/// Inline style applied to the iframe that the widget script injects
+const IFRAME_INLINE_STYLE: &str = "border: none; height: 6rem; max-width: 100%; width: 24rem;";
+
+pub fn latest_release_widget_html(
+    base_url: &SiteUrl,
+    build: &Build,
+    release: &Release
+) -> String {
+    let translations = &build.locale.translations;
+    let release_slug = &release.permalink.slug;
+    let release_asset_prefix = build.asset_prefix(&format!("{release_slug}/"), "", release_slug);
+
+    let dir_attribute = if build.locale.text_direction.is_rtl() { r#"dir="rtl""# } else { "" };
+    let lang = &build.locale.language;
+
+    let release_url = base_url.join_index(build, release_slug);
+
+    let cover = match &release.cover {
+        Some(described_image) => {
+            let alt = match &described_image.description {
+                Some(description) => format!(r#"alt="{}""#, html_escape_inside_attribute(description)),
+                None => String::new()
+            };
+            let filename = described_image.borrow().cover_160_filename_unchecked();
+            format!(r#"<img {alt} src="{release_asset_prefix}{filename}">"#)
+        }
+        None => {
+            let filename = release.procedural_cover_120_filename_unchecked();
+            format!(r#"<img aria-hidden="true" src="{release_asset_prefix}{filename}">"#)
+        }
+    };
+
+    let artists = release.main_artists
+        .iter()
+        .map(|artist| html_escape_outside_attribute(&artist.borrow().name))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let audio_elements = release.tracks
+        .iter()
+        .zip(TRACK_NUMBERS)
+        .map(|(track, track_number)| {
+            let audio_sources = track.streaming_quality
+                .formats()
+                .iter()
+                .map(|format| {
+                    let format_dir = format.asset_dirname();
+                    let format_extension = format.extension();
+
+                    let track_filename = format!(
+                        "{basename}{format_extension}",
+                        basename = track.asset_basename.as_ref().unwrap()
+                    );
+
+                    let track_hash = build.hash_with_salt(|hasher| {
+                        release_slug.hash(hasher);
+                        track_number.hash(hasher);
+                        format_dir.hash(hasher);
+                        track_filename.hash(hasher);
+                    });
+
+                    let track_filename_urlencoded = urlencoding::encode(&track_filename);
+                    let src = format!("{release_asset_prefix}{track_number}/{format_dir}/{track_hash}/{track_filename_urlencoded}");
+
+                    let source_type = format.source_type();
+                    format!(r#"<source src="{src}" type="{source_type}">"#)
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            formatdoc!(r#"
+                <audio preload="none">
+                    {audio_sources}
+                </audio>
+            "#)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let pause_icon = icons::pause(&translations.pause);
+    let play_icon = icons::play(&translations.play);
+
+    let theme_css_hash = build.asset_hashes.theme_css
+        .get(&release.theme.stylesheet_filename())
+        .unwrap();
+    let theme_stylesheet_filename = release.theme.stylesheet_filename();
+    let root_asset_prefix = build.asset_prefix("", "", "");
+
+    let release_title_escaped = html_escape_outside_attribute(&release.title);
+    let t_listen = &translations.listen;
+
+    formatdoc!(r#"
+        <!DOCTYPE html>
+        <html {dir_attribute} lang="{lang}">
+            <head>
+                <title>{release_title_escaped}</title>
+                <meta charset="utf-8">
+                <meta name="generator" content="{GENERATOR_INFO}">
+                <meta name="robots" content="noindex, nofollow">
+                <meta name="viewport" content="width=device-width, initial-scale=1">
+                <link href="{root_asset_prefix}{theme_stylesheet_filename}?{theme_css_hash}" rel="stylesheet">
+                <style>
+                    body {{
+                        align-items: center;
+                        background-color: var(--bg-1);
+                        box-sizing: border-box;
+                        color: var(--fg-1);
+                        display: flex;
+                        gap: .8rem;
+                        height: 100vh;
+                        margin: 0;
+                        padding: .4rem;
+                    }}
+                    .cover img {{
+                        border-radius: var(--cover-border-radius);
+                        display: block;
+                        height: 4.8rem;
+                        width: 4.8rem;
+                    }}
+                    .info {{
+                        display: flex;
+                        flex-direction: column;
+                        min-width: 0;
+                    }}
+                    .info > * {{
+                        overflow: hidden;
+                        text-overflow: ellipsis;
+                        white-space: nowrap;
+                    }}
+                    .info a {{ color: var(--fg-1); text-decoration: none; }}
+                    .info .artists {{ color: var(--fg-3); }}
+                    button {{
+                        background: none;
+                        border: none;
+                        color: var(--fg-1);
+                        cursor: pointer;
+                        flex-shrink: 0;
+                        font-size: 2.4rem;
+                        margin-inline-start: auto;
+                        padding: 0;
+                    }}
+                    button .pause, body.playing button .play {{ display: none; }}
+                    body.playing button .pause {{ display: inline; }}
+                </style>
+            </head>
+            <body>
+                <a class="cover" href="{release_url}" target="_blank">
+                    {cover}
+                </a>
+                <div class="info">
+                    <span>{t_listen}</span>
+                    <a href="{release_url}" target="_blank">{release_title_escaped}</a>
+                    <span class="artists">{artists}</span>
+                </div>
+                <button>
+                    <span class="pause">{pause_icon}</span>
+                    <span class="play">{play_icon}</span>
+                </button>
+                {audio_elements}
+                <script>
+                    const audioElements = [...document.querySelectorAll('audio')];
+                    let currentIndex = 0;
+
+                    document.querySelector('button').addEventListener('click', () => {{
+                        const audio = audioElements[currentIndex];
+                        if (audio.paused) {{
+                            audio.play();
+                        }} else {{
+                            audio.pause();
+                        }}
+                    }});
+
+                    audioElements.forEach((audio, index) => {{
+                        audio.addEventListener('pause', () => document.body.classList.remove('playing'));
+                        audio.addEventListener('play', () => document.body.classList.add('playing'));
+                        audio.addEventListener('ended', () => {{
+                            currentIndex = (index + 1) % audioElements.length;
+                            if (currentIndex > 0) {{
+                                audioElements[currentIndex].play();
+                            }}
+                        }});
+                    }});
+                </script>
+            </body>
+        </html>
+    "#)
+}
+
+/// The script that visitors include on their own page in order to display
+/// the widget there, e.g. via
+/// `<script src="https://example.com/latest-release.js"></script>`
+pub fn latest_release_widget_js(
+    base_url: &SiteUrl,
+    build: &Build,
+    catalog: &Catalog
+) -> String {
+    let widget_url = base_url.join_file(LATEST_RELEASE_WIDGET_HTML_FILENAME);
+    let title = build.locale.translations.audio_player_widget_for_xxx(&catalog.title());
+    let title_escaped = title
+        .replace('\\', "\\\\")
+        .replace('\'', "\\'");
+
+    formatdoc!(r#"
+        (() => {{
+            const iframe = document.createElement('iframe');
+            iframe.loading = 'lazy';
+            iframe.src = '{widget_url}';
+            iframe.style.cssText = '{IFRAME_INLINE_STYLE}';
+            iframe.title = '{title_escaped}';
+            document.currentScript.insertAdjacentElement('afterend', iframe);
+        }})();
+    "#)
+}
@@ -8,16 +8,19 @@ use crate::{
     Build,
     Catalog,
     GENERATOR_INFO,
+    MirroredPage,
     OpenGraphMeta,
-    Theme
+    PageType,
+    PaymentPointer,
+    USER_CSS_FILENAME
 };
-use crate::icons;
+use crate::{funding, icons, language_mirror, minify};
 use crate::util::{
     html_escape_inside_attribute,
     html_escape_outside_attribute
 };
 
-use super::player_icon_templates;
+use super::{PageContext, player_icon_templates};
 
 pub struct Layout {
     breadcrumb: Option<String>,
@@ -25,13 +28,29 @@ pub struct Layout {
     /// page and append templates for icons (copy/failed/success) used at
     /// runtime to the end of the page.
     clipboard_script: bool,
+    /// Alternate link tag for a feed that only covers this page (e.g. the
+    /// feed of a year archive page), in addition to the site-wide feeds
+    feed_link: Option<String>,
+    /// Set for pages that have a counterpart on each language mirror (if
+    /// any are configured), which is then linked to from this page.
+    mirrored_page: Option<MirroredPage>,
+    /// Rendered Web Monetization tags for the head of streaming pages
+    monetization: Option<String>,
     /// If true we inject noindex/nofollow meta into the head of the page
     no_indexing: bool,
     opengraph_meta: Option<OpenGraphMeta>,
+    /// Rendered page_metadata of the artist, release or track that this page
+    /// presents, injected into the head after all site-wide metadata.
+    page_metadata: Option<String>,
+    /// Determines which critical css is inlined into the page (if the
+    /// critical_css option is enabled for the catalog)
+    page_type: PageType,
     /// If true we inject a script tag for player.js into the head of the page
     /// and append templates for icons (loading/pause/play) used at runtime
     /// to the end of the page.
-    player_script: bool
+    player_script: bool,
+    /// If true we inject a script tag for search.js into the head of the page
+    search_script: bool
 }
 
 fn theming_widget(build: &Build, catalog: &Catalog) -> String {
@@ -178,21 +197,43 @@ impl Layout {
         self.clipboard_script = true;
     }
 
+    pub fn add_feed_link(&mut self, feed_link: String) {
+        self.feed_link = Some(feed_link);
+    }
+
+    pub fn add_monetization(&mut self, payment_pointer: &PaymentPointer) {
+        self.monetization = Some(payment_pointer.head_tags());
+    }
+
     pub fn add_opengraph_meta(&mut self, opengraph_meta: OpenGraphMeta) {
         self.opengraph_meta = Some(opengraph_meta);
     }
 
+    pub fn add_page_metadata(&mut self, page_metadata: String) {
+        self.page_metadata = Some(page_metadata);
+    }
+
     pub fn add_player_script(&mut self) {
         self.player_script = true;
     }
 
+    pub fn add_search_script(&mut self) {
+        self.search_script = true;
+    }
+
     pub fn new() -> Layout {
         Layout {
             breadcrumb: None,
             clipboard_script: false,
+            feed_link: None,
+            mirrored_page: None,
+            monetization: None,
             no_indexing: false,
             opengraph_meta: None,
-            player_script: false
+            page_metadata: None,
+            page_type: PageType::Subpage,
+            player_script: false,
+            search_script: false
         }
     }
 
@@ -206,12 +247,13 @@ impl Layout {
     pub fn render(
         &self,
         body: &str,
-        build: &Build,
-        catalog: &Catalog,
+        context: &impl PageContext,
         root_prefix: &str,
-        theme: &Theme,
         title: &str
     ) -> String {
+        let build = context.build();
+        let catalog = context.catalog();
+        let theme = context.theme();
         let index_suffix = build.index_suffix();
         let mut templates = String::new();
         let translations = &build.locale.translations;
@@ -228,6 +270,34 @@ impl Layout {
             add_extra_meta(&feed_tags);
         }
 
+        if let Some(feed_link) = &self.feed_link {
+            add_extra_meta(feed_link);
+        }
+
+        let r_language_switcher = if catalog.language_mirrors.is_empty() {
+            String::new()
+        } else {
+            if let Some(mirrored_page) = &self.mirrored_page {
+                let alternate_link_tags = language_mirror::alternate_link_tags(build, catalog, mirrored_page);
+                add_extra_meta(&alternate_link_tags);
+            }
+
+            language_mirror::language_switcher(build, catalog, self.mirrored_page.as_ref())
+        };
+
+        // The visitor's choice is restored right at the start of the body
+        // (before anything is rendered) to avoid a flash of the regular font.
+        let (r_dyslexia_font_script, r_dyslexia_font_toggle) = if theme.dyslexia_font {
+            let t_dyslexia_friendly_font = &translations.dyslexia_friendly_font;
+
+            (
+                String::from("<script>if (localStorage.getItem('faircampDyslexiaFont')) document.body.classList.add('dyslexia_font');</script>"),
+                format!(r#"<button aria-pressed="false" class="dyslexia_font_toggle">{t_dyslexia_friendly_font}</button>"#)
+            )
+        } else {
+            (String::new(), String::new())
+        };
+
         let dir_attribute = if build.locale.text_direction.is_rtl() { r#"dir="rtl""# } else { "" };
 
         let faircamp_signature = if catalog.faircamp_signature {
@@ -280,14 +350,83 @@ impl Layout {
 
         if self.player_script {
             let player_js_hash = build.asset_hashes.player_js.as_ref().unwrap();
-            let player_script_tag = format!(r#"<script defer src="{root_prefix}player.js?{player_js_hash}"></script>"#);
 
-            add_extra_meta(&player_script_tag);
+            // Browsers that don't support modules additionally run the
+            // reduced legacy player (nomodule). player.js uses syntax that
+            // none of these browsers can parse, so it never runs alongside.
+            if catalog.legacy_support {
+                let player_legacy_js_hash = build.asset_hashes.player_legacy_js.as_ref().unwrap();
+                let player_script_tags = formatdoc!(r#"
+                    <script defer src="{root_prefix}player.js?{player_js_hash}"></script>
+                    <script defer nomodule src="{root_prefix}player_legacy.js?{player_legacy_js_hash}"></script>
+                "#);
+
+                add_extra_meta(&player_script_tags);
+            } else {
+                let player_script_tag = format!(r#"<script defer src="{root_prefix}player.js?{player_js_hash}"></script>"#);
+
+                add_extra_meta(&player_script_tag);
+            }
 
             templates.push_str(&player_icon_templates(translations));
         }
 
+        if self.search_script {
+            let search_js_hash = build.asset_hashes.search_js.as_ref().unwrap();
+            let search_script_tag = format!(r#"<script defer src="{root_prefix}search.js?{search_js_hash}"></script>"#);
+
+            add_extra_meta(&search_script_tag);
+        }
+
         let browse_icon = icons::BROWSE;
+        let t_browse = &translations.browse;
+
+        let header_browse = formatdoc!(r#"
+            <button class="browse">
+                {t_browse}
+                {browse_icon}
+            </button>
+        "#);
+
+        // With the discover page enabled, the link to it is placed next to
+        // the browse button, both in the header and footer.
+        let (footer_discover, header_actions) = match &catalog.discover_permalink {
+            Some(discover_permalink) => {
+                let t_discover = &translations.discover;
+                let discover_link = format!(r#"<a class="discover" href="{root_prefix}{discover_permalink}{index_suffix}">{t_discover}</a>"#);
+                let header_actions = format!(r#"<span class="header_actions">{discover_link}{header_browse}</span>"#);
+                (discover_link, header_actions)
+            }
+            None => (String::new(), header_browse)
+        };
+
+        // With year archives enabled, the footer links to all decade pages
+        // (if enabled), or otherwise to all year pages.
+        let r_year_archives = if catalog.year_archive_pages.is_empty() {
+            String::new()
+        } else {
+            let decades = catalog.year_archives.as_ref().is_some_and(|year_archives| year_archives.decades);
+
+            let links = catalog.year_archive_pages
+                .iter()
+                .filter(|page| page.is_decade() == decades)
+                .map(|page| {
+                    let label = page.label();
+                    let slug = &page.slug;
+                    format!(r#"<a href="{root_prefix}{slug}{index_suffix}">{label}</a>"#)
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            let t_archive = &translations.archive;
+
+            formatdoc!(r#"
+                <nav aria-label="{t_archive}" class="year_archives">
+                    {links}
+                </nav>
+            "#)
+        };
+
         let browser_js_hash = build.asset_hashes.browser_js.as_ref().unwrap();
         let catalog_title = html_escape_outside_attribute(&catalog.title());
 
@@ -296,7 +435,13 @@ impl Layout {
             add_extra_meta(r#"<meta name="robots" content="noindex, nofollow">"#);
         }
 
-        if let Some(favicon_tags) = catalog.favicon.header_tags(build, root_prefix) {
+        if let Some(ai_scraping_tag) = catalog.ai_scraping.meta_tag() {
+            add_extra_meta(&ai_scraping_tag);
+        }
+
+        let root_asset_prefix = build.asset_prefix(root_prefix, root_prefix, "");
+
+        if let Some(favicon_tags) = catalog.favicon.header_tags(build, &root_asset_prefix) {
             add_extra_meta(&favicon_tags);
         }
 
@@ -309,6 +454,18 @@ impl Layout {
         }
 
         let site_css_hash = build.asset_hashes.site_css.as_ref().unwrap();
+
+        // With critical css available we inline it and load the full site
+        // stylesheet without blocking rendering (falling back to a regular
+        // stylesheet link when javascript is disabled).
+        let site_css_tags = match build.critical_css.get(&self.page_type) {
+            Some(critical_css) => formatdoc!(r#"
+                <style>{critical_css}</style>
+                <link as="style" href="{root_asset_prefix}site.css?{site_css_hash}" onload="this.onload=null;this.rel='stylesheet'" rel="preload">
+                <noscript><link href="{root_asset_prefix}site.css?{site_css_hash}" rel="stylesheet"></noscript>
+            "#),
+            None => format!(r#"<link href="{root_asset_prefix}site.css?{site_css_hash}" rel="stylesheet">"#)
+        };
         let theme_css_hash = build.asset_hashes.theme_css.get(&theme.stylesheet_filename()).unwrap();
         let theme_stylesheet_filename = theme.stylesheet_filename();
 
@@ -322,18 +479,50 @@ impl Layout {
 
         let close_icon = icons::failure(&translations.close);
 
-        let t_browse = &translations.browse;
         let t_javascript_is_disabled_text = &translations.javascript_is_disabled_text;
         let t_search = &translations.search;
         let t_skip_to_main_content = &translations.skip_to_main_content;
 
+        if let Some(monetization) = &self.monetization {
+            add_extra_meta(monetization);
+        }
+
+        if !catalog.funding.is_empty() {
+            add_extra_meta(&funding::link_tags(catalog));
+        }
+
         // User-supplied site metadata is appended last in order to guarantee
         // its precendence when overriding (e.g.) native styles.
         if let Some(site_metadata) = &catalog.site_metadata {
             add_extra_meta(&site_metadata.render(root_prefix));
         }
 
-        formatdoc!(r##"
+        if let Some(user_css) = catalog.site_assets
+            .iter()
+            .find(|site_asset| site_asset.filename == USER_CSS_FILENAME) {
+            let user_css_hash = user_css.hash.as_url_safe_base64();
+            let href = format!("{root_prefix}{USER_CSS_FILENAME}?{user_css_hash}");
+
+            // When site.css is deferred (see site_css_tags), user.css is
+            // deferred in the same way, otherwise it would be applied before
+            // the styles it is meant to override.
+            let user_css_tags = if build.critical_css.contains_key(&self.page_type) {
+                formatdoc!(r#"
+                    <link as="style" href="{href}" onload="this.onload=null;this.rel='stylesheet'" rel="preload">
+                    <noscript><link href="{href}" rel="stylesheet"></noscript>
+                "#)
+            } else {
+                format!(r#"<link href="{href}" rel="stylesheet">"#)
+            };
+
+            add_extra_meta(&user_css_tags);
+        }
+
+        if let Some(page_metadata) = &self.page_metadata {
+            add_extra_meta(page_metadata);
+        }
+
+        let html = formatdoc!(r##"
             <!DOCTYPE html>
             <html {dir_attribute} lang="{lang}">
                 <head>
@@ -342,13 +531,14 @@ impl Layout {
                     <meta name="description" content="{title_escaped_inside_attribute}">
                     <meta name="generator" content="{GENERATOR_INFO}">
                     <meta name="viewport" content="width=device-width, initial-scale=1">
-                    <link href="{root_prefix}{theme_stylesheet_filename}?{theme_css_hash}" rel="stylesheet">
-                    <link href="{root_prefix}site.css?{site_css_hash}" rel="stylesheet">
+                    <link href="{root_asset_prefix}{theme_stylesheet_filename}?{theme_css_hash}" rel="stylesheet">
+                    {site_css_tags}
                     <script defer src="{root_prefix}browser.js?{browser_js_hash}"></script>
                     {extra_meta}
                 </head>
                 <body>
                     <script>document.body.classList.add('js_enabled');</script>
+                    {r_dyslexia_font_script}
                     <a class="skip_to_content" href="#content">{t_skip_to_main_content}</a>
                     <div class="layout">
                         <header>
@@ -359,10 +549,7 @@ impl Layout {
                                 </a>
                                 {breadcrumb}
                             </div>
-                            <button class="browse">
-                                {t_browse}
-                                {browse_icon}
-                            </button>
+                            {header_actions}
                         </header>
                         <main id="content">
                             {body}
@@ -371,11 +558,15 @@ impl Layout {
                             <span>
                                 <a href="{root_prefix}">{catalog_title}</a>
                                 <button class="browse">{browse_icon} {t_browse}</button>
+                                {footer_discover}
                             </span>
+                            {r_year_archives}
+                            {r_dyslexia_font_toggle}
+                            {r_language_switcher}
                             {faircamp_signature}
                         </footer>
                     </div>
-                    <div id="browser" data-root-prefix="{root_prefix}">
+                    <div id="browser" data-root-asset-prefix="{root_asset_prefix}" data-root-prefix="{root_prefix}">
                         <div>
                             <input autocomplete="off" placeholder="{t_search}" type="search">
                             <div role="status"></div>
@@ -390,6 +581,16 @@ impl Layout {
                     {templates}
                 </body>
             </html>
-        "##)
+        "##);
+
+        if build.minify { minify::html(&html) } else { html }
+    }
+
+    pub fn set_mirrored_page(&mut self, mirrored_page: MirroredPage) {
+        self.mirrored_page = Some(mirrored_page);
+    }
+
+    pub fn set_page_type(&mut self, page_type: PageType) {
+        self.page_type = page_type;
     }
 }
@@ -0,0 +1,185 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Typed contexts for each kind of page that is rendered with the shared
+//! [Layout](super::Layout). A page function receives the context for its
+//! kind of page, and the layout obtains everything it needs (build, catalog,
+//! theme) through the [PageContext] trait, so that new kinds of pages only
+//! need a new context type and don't have to assemble head, navigation and
+//! footer themselves.
+
+use crate::{Artist, Build, Catalog, Playlist, Release, Theme, Track};
+
+/// What the shared layout needs to know about any page it renders
+pub trait PageContext {
+    fn build(&self) -> &Build;
+    fn catalog(&self) -> &Catalog;
+    /// The theme the page is rendered with
+    fn theme(&self) -> &Theme;
+}
+
+/// For pages that belong to an artist (the artist page)
+#[derive(Clone, Copy)]
+pub struct ArtistContext<'a> {
+    pub artist: &'a Artist,
+    pub build: &'a Build,
+    pub catalog: &'a Catalog
+}
+
+/// For pages that belong to the catalog as a whole (homepage, list and
+/// timeline views, image descriptions, subscription choices)
+#[derive(Clone, Copy)]
+pub struct CatalogContext<'a> {
+    pub build: &'a Build,
+    pub catalog: &'a Catalog
+}
+
+/// For the page of a playlist (which is rendered with the catalog theme, as
+/// its tracks are drawn from different releases)
+#[derive(Clone, Copy)]
+pub struct PlaylistContext<'a> {
+    pub build: &'a Build,
+    pub catalog: &'a Catalog,
+    pub playlist: &'a Playlist
+}
+
+/// For pages that belong to a release (release page, download, purchase
+/// and unlock pages, embed codes)
+#[derive(Clone, Copy)]
+pub struct ReleaseContext<'a> {
+    pub build: &'a Build,
+    pub catalog: &'a Catalog,
+    pub release: &'a Release
+}
+
+/// For pages that belong to a track (track page, download, purchase and
+/// unlock pages, embed codes)
+#[derive(Clone, Copy)]
+pub struct TrackContext<'a> {
+    pub build: &'a Build,
+    pub catalog: &'a Catalog,
+    pub release: &'a Release,
+    pub track: &'a Track,
+    /// 1-based, as shown in the tracklist
+    pub track_number: usize
+}
+
+impl<'a> ArtistContext<'a> {
+    pub fn new(artist: &'a Artist, build: &'a Build, catalog: &'a Catalog) -> ArtistContext<'a> {
+        ArtistContext {
+            artist,
+            build,
+            catalog
+        }
+    }
+}
+
+impl PageContext for ArtistContext<'_> {
+    fn build(&self) -> &Build {
+        self.build
+    }
+
+    fn catalog(&self) -> &Catalog {
+        self.catalog
+    }
+
+    fn theme(&self) -> &Theme {
+        &self.artist.theme
+    }
+}
+
+impl<'a> CatalogContext<'a> {
+    pub fn new(build: &'a Build, catalog: &'a Catalog) -> CatalogContext<'a> {
+        CatalogContext {
+            build,
+            catalog
+        }
+    }
+}
+
+impl PageContext for CatalogContext<'_> {
+    fn build(&self) -> &Build {
+        self.build
+    }
+
+    fn catalog(&self) -> &Catalog {
+        self.catalog
+    }
+
+    fn theme(&self) -> &Theme {
+        &self.catalog.theme
+    }
+}
+
+impl<'a> PlaylistContext<'a> {
+    pub fn new(build: &'a Build, catalog: &'a Catalog, playlist: &'a Playlist) -> PlaylistContext<'a> {
+        PlaylistContext {
+            build,
+            catalog,
+            playlist
+        }
+    }
+}
+
+impl PageContext for PlaylistContext<'_> {
+    fn build(&self) -> &Build {
+        self.build
+    }
+
+    fn catalog(&self) -> &Catalog {
+        self.catalog
+    }
+
+    fn theme(&self) -> &Theme {
+        &self.catalog.theme
+    }
+}
+
+impl<'a> ReleaseContext<'a> {
+    pub fn new(build: &'a Build, catalog: &'a Catalog, release: &'a Release) -> ReleaseContext<'a> {
+        ReleaseContext {
+            build,
+            catalog,
+            release
+        }
+    }
+
+    /// The context for one of the release's track pages
+    pub fn track(&self, track: &'a Track, track_number: usize) -> TrackContext<'a> {
+        TrackContext {
+            build: self.build,
+            catalog: self.catalog,
+            release: self.release,
+            track,
+            track_number
+        }
+    }
+}
+
+impl PageContext for ReleaseContext<'_> {
+    fn build(&self) -> &Build {
+        self.build
+    }
+
+    fn catalog(&self) -> &Catalog {
+        self.catalog
+    }
+
+    fn theme(&self) -> &Theme {
+        &self.release.theme
+    }
+}
+
+impl PageContext for TrackContext<'_> {
+    fn build(&self) -> &Build {
+        self.build
+    }
+
+    fn catalog(&self) -> &Catalog {
+        self.catalog
+    }
+
+    fn theme(&self) -> &Theme {
+        &self.track.theme
+    }
+}
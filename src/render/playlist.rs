@@ -0,0 +1,308 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::hash::Hash;
+
+use indoc::formatdoc;
+
+use crate::{M3U_PLAYLIST_FILENAME, MirroredPage, XSPF_PLAYLIST_FILENAME};
+use crate::{OpenGraphMeta, PageType};
+use crate::icons;
+use crate::util::{format_time, html_escape_outside_attribute};
+
+use super::{Layout, PlaylistContext, Truncation};
+use super::{
+    copy_button,
+    list_track_artists,
+    normalization_gain_attribute,
+    queue_panel,
+    queue_toggle,
+    share_actions,
+    volume_controls,
+    waveform
+};
+
+/// The page of a playlist, featuring a combined tracklist and streaming
+/// player for tracks from different releases, and links to the playlist
+/// files (M3U/XSPF).
+pub fn playlist_html(context: &PlaylistContext) -> String {
+    let &PlaylistContext { build, catalog, playlist } = context;
+    let index_suffix = build.index_suffix();
+    let root_prefix = "../";
+    let translations = &build.locale.translations;
+
+    let mut layout = Layout::new();
+
+    layout.add_player_script();
+    layout.set_mirrored_page(MirroredPage::Page(playlist.permalink.slug.clone()));
+    layout.set_page_type(PageType::Release);
+
+    let longest_track_duration = playlist.longest_track_duration();
+
+    let t_play = &translations.play;
+    let play_icon = icons::play(t_play);
+
+    let root_asset_prefix = build.asset_prefix(root_prefix, root_prefix, "");
+
+    let t_playback_position = &translations.playback_position;
+    let r_tracks = playlist.tracks
+        .iter()
+        .enumerate()
+        .map(|(index, playlist_track)| {
+            let release_ref = playlist_track.release.borrow();
+            let release_slug = &release_ref.permalink.slug;
+            let track = playlist_track.track(&release_ref);
+            let track_number = playlist_track.track_number;
+
+            let audio_sources = track.streaming_quality
+                .formats()
+                .iter()
+                .map(|format| {
+                    let format_dir = format.asset_dirname();
+                    let format_extension = format.extension();
+
+                    let track_filename = format!(
+                        "{basename}{format_extension}",
+                        basename = track.asset_basename.as_ref().unwrap()
+                    );
+
+                    let track_hash = build.hash_with_salt(|hasher| {
+                        release_slug.hash(hasher);
+                        track_number.hash(hasher);
+                        format_dir.hash(hasher);
+                        track_filename.hash(hasher);
+                    });
+
+                    let track_filename_urlencoded = urlencoding::encode(&track_filename);
+                    let src = format!("{root_asset_prefix}{release_slug}/{track_number}/{format_dir}/{track_hash}/{track_filename_urlencoded}");
+
+                    let source_type = format.source_type();
+                    format!(r#"<source src="{src}" type="{source_type}">"#)
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            let duration_seconds = track.transcodes.borrow().source_meta.duration_seconds;
+            let track_title = track.title();
+
+            let r_gain = normalization_gain_attribute(track);
+            let track_duration_formatted = format_time(duration_seconds);
+            let track_title_escaped = html_escape_outside_attribute(&track_title);
+
+            // The position on the playlist, not the track number on its release
+            let playlist_number = index + 1;
+
+            let r_waveform = if catalog.theme.waveforms {
+                let waveform_svg = waveform(track);
+
+                formatdoc!(r#"
+                    <div class="waveform">
+                        {waveform_svg}
+                        <input aria-label="{t_playback_position}" aria-valuetext="" autocomplete="off" max="{duration_seconds}" min="0" step="any" type="range" value="0">
+                        <div class="decoration"></div>
+                    </div>
+                "#)
+            } else {
+                String::new()
+            };
+
+            // Tracks on a playlist stem from different releases, so the
+            // artists are always listed.
+            let artists_truncation = Truncation::Truncate {
+                max_chars: 80,
+                others_link: format!("{root_prefix}{release_slug}/{track_number}/")
+            };
+            let artists_truncated = list_track_artists(build, index_suffix, root_prefix, catalog, artists_truncation, track);
+
+            let r_cover_micro = if let Some(filename) = track.cover_160_filename() {
+                format!(r#"<img aria-hidden="true" src="{root_asset_prefix}{release_slug}/{track_number}/{filename}">"#)
+            } else if let Some(filename) = release_ref.cover_160_filename() {
+                format!(r#"<img aria-hidden="true" src="{root_asset_prefix}{release_slug}/{filename}">"#)
+            } else {
+                let filename = release_ref.procedural_cover_120_filename_unchecked();
+                format!(r#"<img aria-hidden="true" class="procedural" src="{root_asset_prefix}{release_slug}/{filename}">"#)
+            };
+
+            formatdoc!(r#"
+                <div class="track" data-duration="{duration_seconds}"{r_gain}>
+                    <button class="track_playback" tabindex="-1">
+                        <span class="icon">
+                            {play_icon}
+                        </span>
+                        {r_cover_micro}
+                    </button>
+                    <div>
+                        <div>
+                            <span class="number">{playlist_number}</span>
+                            <a class="title" href="{root_prefix}{release_slug}/{track_number}{index_suffix}">{track_title_escaped}</a>
+                        </div>
+                        <div class="artists">{artists_truncated}</div>
+                        {r_waveform}
+                        <audio controls preload="none">
+                            {audio_sources}
+                        </audio>
+                    </div>
+                    <div>
+                        <span class="time">{track_duration_formatted}</span>
+                    </div>
+                </div>
+            "#)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let playlist_title_escaped = html_escape_outside_attribute(&playlist.title);
+
+    let t_listen = &translations.listen;
+    let listen_button = formatdoc!(r#"
+        <button class="emphasized listen">
+            <span class="icon">{play_icon}</span>
+            <span class="label">{t_listen}</span>
+        </button>
+    "#);
+
+    let mut actions = Vec::new();
+
+    if catalog.copy_link {
+        layout.add_clipboard_script();
+
+        let (content_key, content_value) = match &build.base_url {
+            Some(base_url) => ("content", base_url.join_index(build, &playlist.permalink.slug)),
+            None => ("dynamic-url", String::new())
+        };
+
+        let r_copy_link = copy_button(content_key, &content_value, &translations.copy_link);
+        actions.push(r_copy_link);
+    }
+
+    if let Some(base_url) = &build.base_url {
+        let playlist_url = base_url.join_index(build, &playlist.permalink.slug);
+        let r_share_actions = share_actions(&catalog.share_buttons, &playlist.title, translations, &playlist_url);
+        actions.extend(r_share_actions);
+
+        let stream_icon = icons::STREAM;
+
+        let t_m3u_playlist = &translations.m3u_playlist;
+        actions.push(formatdoc!(r#"
+            <a href="{M3U_PLAYLIST_FILENAME}">
+                {stream_icon}
+                <span>{t_m3u_playlist}</span>
+            </a>
+        "#));
+
+        let t_xspf_playlist = &translations.xspf_playlist;
+        actions.push(formatdoc!(r#"
+            <a download href="{XSPF_PLAYLIST_FILENAME}">
+                {stream_icon}
+                <span>{t_xspf_playlist}</span>
+            </a>
+        "#));
+    }
+
+    let r_actions = if actions.is_empty() {
+        String::new()
+    } else {
+        let joined = actions.join("");
+
+        formatdoc!(r#"
+            <div class="actions">
+                {joined}
+            </div>
+        "#)
+    };
+
+    let synopsis = match &playlist.synopsis {
+        Some(synopsis) => {
+            formatdoc!(r#"
+                <div style="margin-bottom: 1rem; margin-top: 1rem;">
+                    {synopsis}
+                </div>
+            "#)
+        }
+        None => String::new()
+    };
+
+    let compact_tall = if catalog.theme.waveforms { "tall" } else { "" };
+    let relative_waveforms = if catalog.theme.relative_waveforms { "" } else { "data-disable-relative-waveforms " };
+    // A queue only makes sense with more than one track
+    let (r_queue_panel, r_queue_toggle) = if playlist.tracks.len() > 1 {
+        (queue_panel(translations), queue_toggle(translations))
+    } else {
+        (String::new(), String::new())
+    };
+    let r_volume_controls = volume_controls(translations);
+    let t_playlist = &translations.playlist;
+
+    let next_track_icon = icons::next_track(&translations.next_track);
+    let body = formatdoc!(r##"
+        <div class="page">
+            <div class="page_split">
+                <div class="abstract">
+                    <h1>{playlist_title_escaped}</h1>
+                    <div class="release_artists">{t_playlist}</div>
+                    {synopsis}
+                    <div class="actions primary">
+                        {listen_button}
+                    </div>
+                    {r_actions}
+                </div>
+            </div>
+        </div>
+        <div class="page">
+            <div class="page_center">
+                <div class="{compact_tall} tracks" data-longest-duration="{longest_track_duration}" {relative_waveforms}>
+                    {r_tracks}
+                </div>
+            </div>
+        </div>
+        <div class="docked_player tall">
+            {r_queue_panel}
+            <div class="timeline">
+                <input aria-label="{t_playback_position}" aria-valuetext="" autocomplete="off" max="" min="0" step="any" type="range" value="0">
+                <div class="base"></div>
+                <div class="progress" style="width: 0%;"></div>
+            </div>
+            <div class="elements">
+                <button class="playback">
+                    {play_icon}
+                </button>
+                <button class="next_track">
+                    {next_track_icon}
+                </button>
+                {r_volume_controls}
+                {r_queue_toggle}
+                <span class="track_info">
+                    <span class="number"></span>
+                    <span class="title_wrapper"></span>
+                </span>
+                <span class="error" role="alert"></span>
+                <span class="time">
+                    <span class="current"></span>
+                    <span>/</span>
+                    <span class="total"></span>
+                </span>
+            </div>
+        </div>
+        <div aria-label="" class="docked_player_status" role="status"></div>
+    "##);
+
+    if catalog.opengraph {
+        if let Some(base_url) = &build.base_url {
+            let playlist_url = base_url.join_index(build, &playlist.permalink.slug);
+            let mut meta = OpenGraphMeta::new(playlist.title.clone(), playlist_url);
+
+            if let Some(synopsis) = &playlist.synopsis {
+                meta.description(synopsis);
+            }
+
+            layout.add_opengraph_meta(meta);
+        }
+    }
+
+    layout.render(
+        &body,
+        context,
+        root_prefix,
+        &playlist.title
+    )
+}
@@ -0,0 +1,171 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! A standalone diagnostics page for site operators, which tests whether the
+//! host the site is deployed to answers http range requests correctly. Some
+//! (mostly cheap) hosts ignore or mangle range requests, which silently
+//! breaks seeking in the player. The page requests a few byte ranges of the
+//! streaming files of one track and reports what the host sent back. It is
+//! not linked from anywhere and excluded from indexing, and as it is only
+//! meant for the operator, it is not translated.
+
+use std::hash::Hash;
+
+use indoc::formatdoc;
+
+use crate::{Build, GENERATOR_INFO, Release, TRACK_NUMBERS};
+use crate::util::html_escape_outside_attribute;
+
+pub const RANGE_CHECK_HTML_FILENAME: &str = "range-check.html";
+
+/// The size of each requested range in bytes
+const RANGE_BYTES: usize = 1024;
+
+pub fn range_check_html(build: &Build, release: &Release) -> String {
+    let release_slug = &release.permalink.slug;
+
+    // Only the first track is tested, all streaming files are served the
+    // same way by the host.
+    let sources = release.tracks
+        .iter()
+        .zip(TRACK_NUMBERS)
+        .take(1)
+        .flat_map(|(track, track_number)| {
+            track.streaming_quality
+                .formats()
+                .iter()
+                .map(|format| {
+                    let format_dir = format.asset_dirname();
+                    let format_extension = format.extension();
+
+                    let track_filename = format!(
+                        "{basename}{format_extension}",
+                        basename = track.asset_basename.as_ref().unwrap()
+                    );
+
+                    let track_hash = build.hash_with_salt(|hasher| {
+                        release_slug.hash(hasher);
+                        track_number.hash(hasher);
+                        format_dir.hash(hasher);
+                        track_filename.hash(hasher);
+                    });
+
+                    let track_filename_urlencoded = urlencoding::encode(&track_filename);
+                    format!("'{release_slug}/{track_number}/{format_dir}/{track_hash}/{track_filename_urlencoded}'")
+                })
+                .collect::<Vec<String>>()
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let release_title_escaped = html_escape_outside_attribute(&release.title);
+
+    formatdoc!(r#"
+        <!DOCTYPE html>
+        <html lang="en">
+            <head>
+                <title>Range request check</title>
+                <meta charset="utf-8">
+                <meta name="generator" content="{GENERATOR_INFO}">
+                <meta name="robots" content="noindex, nofollow">
+                <meta name="viewport" content="width=device-width, initial-scale=1">
+                <style>
+                    body {{
+                        font-family: sans-serif;
+                        line-height: 1.5;
+                        margin: 2rem auto;
+                        max-width: 40rem;
+                        padding: 0 1rem;
+                    }}
+                    code {{ word-break: break-all; }}
+                    .failed {{ color: #b00020; }}
+                    .passed {{ color: #1b7a2e; }}
+                </style>
+            </head>
+            <body>
+                <h1>Range request check</h1>
+                <p>
+                    Seeking in faircamp's player relies on your host answering
+                    range requests (requests for only a part of a file) with
+                    status 206 and exactly the requested bytes. This page tests
+                    that with the streaming files of the first track of your
+                    latest release ("{release_title_escaped}").
+                </p>
+                <p>
+                    If the checks fail, seeking will not work reliably for your
+                    visitors - ask your host to support range requests for audio
+                    files, or consider moving to another host.
+                </p>
+                <ul class="results"></ul>
+                <script>
+                    const RANGE_BYTES = {RANGE_BYTES};
+                    const results = document.querySelector('.results');
+
+                    function report(url, problems) {{
+                        const item = document.createElement('li');
+                        const code = document.createElement('code');
+                        code.textContent = url;
+                        item.append(code, document.createElement('br'));
+                        if (problems.length === 0) {{
+                            item.className = 'passed';
+                            item.append('Passed - range requests are answered correctly');
+                        }} else {{
+                            item.className = 'failed';
+                            item.append('Failed - ' + problems.join(', '));
+                        }}
+                        results.append(item);
+                    }}
+
+                    async function requestRange(url, start) {{
+                        const end = start + RANGE_BYTES - 1;
+                        const response = await fetch(url, {{
+                            cache: 'no-store',
+                            headers: {{ Range: `bytes=${{start}}-${{end}}` }}
+                        }});
+                        const bytes = (await response.arrayBuffer()).byteLength;
+                        const contentRange = response.headers.get('Content-Range');
+                        const problems = [];
+
+                        if (response.status !== 206) {{
+                            problems.push(`the range ${{start}}-${{end}} was answered with status ${{response.status}} instead of 206`);
+                        }}
+                        if (bytes !== RANGE_BYTES) {{
+                            problems.push(`${{bytes}} instead of ${{RANGE_BYTES}} bytes were returned for the range ${{start}}-${{end}}`);
+                        }}
+
+                        const match = contentRange && contentRange.match(/^bytes (\d+)-(\d+)\/(\d+)$/);
+                        if (!match || Number(match[1]) !== start || Number(match[2]) !== end) {{
+                            problems.push(`the Content-Range header for the range ${{start}}-${{end}} is ${{contentRange ? `'${{contentRange}}'` : 'missing'}}`);
+                        }}
+
+                        return {{ problems, total: match ? Number(match[3]) : null }};
+                    }}
+
+                    async function check(url) {{
+                        try {{
+                            const first = await requestRange(url, 0);
+                            const problems = first.problems;
+
+                            // Some hosts only get the start of a file right,
+                            // so we also request a range from the middle.
+                            if (first.total !== null && first.total > RANGE_BYTES * 2) {{
+                                const middle = await requestRange(url, Math.floor(first.total / 2));
+                                problems.push(...middle.problems);
+                            }}
+
+                            report(url, problems);
+                        }} catch (error) {{
+                            report(url, [`the file could not be requested (${{error}}) - note that this page only works when opened on your deployed site`]);
+                        }}
+                    }}
+
+                    (async () => {{
+                        for (const url of [{sources}]) {{
+                            await check(url);
+                        }}
+                    }})();
+                </script>
+            </body>
+        </html>
+    "#)
+}
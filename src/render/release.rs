@@ -5,33 +5,51 @@ use std::hash::Hash;
 
 use indoc::formatdoc;
 
-use crate::{M3U_PLAYLIST_FILENAME, TRACK_NUMBERS};
+use crate::{ICS_FILENAME, M3U_PLAYLIST_FILENAME, TRACK_NUMBERS};
 use crate::{
     Build,
-    Catalog,
     DownloadAccess,
+    ImgAttributes,
+    MirroredPage,
     OpenGraphMeta,
-    Release
+    PageType,
+    PhysicalEdition,
+    Release,
+    ReleaseFilterKind,
+    ReleaseSection,
+    Stock
 };
 use crate::icons;
-use crate::util::{format_time, html_escape_outside_attribute};
+use crate::util::{format_time, html_escape_inside_attribute, html_escape_outside_attribute};
 
 use super::SPEED_CONTROLS;
-use super::{Layout, Truncation};
+use super::{Layout, ReleaseContext, Truncation};
 use super::{
+    content_warning_interstitial,
     copy_button,
+    copy_timecode_link_button,
+    draft_banner,
     link_action,
     list_release_artists,
     list_track_artists,
+    monetization_unlock_link,
+    normalization_gain_attribute,
+    queue_panel,
+    queue_toggle,
     release_cover_image,
+    release_runtime_and_sizes,
+    share_actions,
     unlisted_badge,
     volume_controls,
-    waveform
+    volume_normalization_toggle,
+    waveform,
+    wrap_undescribed_image
 };
 
 /// The actual release page, featuring the track listing and streaming player, links
 /// to downloads, embeds, description, etc.
-pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> String {
+pub fn release_html(context: &ReleaseContext) -> String {
+    let &ReleaseContext { build, catalog, release } = context;
     let index_suffix = build.index_suffix();
     let root_prefix = "../";
     let translations = &build.locale.translations;
@@ -39,11 +57,21 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
     let mut layout = Layout::new();
 
     layout.add_player_script();
+    layout.set_mirrored_page(MirroredPage::Page(release.permalink.slug.clone()));
+    layout.set_page_type(PageType::Release);
+
+    if let Some(page_metadata) = &release.page_metadata {
+        layout.add_page_metadata(page_metadata.render(root_prefix));
+    }
+
+    if let Some(payment_pointer) = &release.payment_pointer {
+        layout.add_monetization(payment_pointer);
+    }
 
     let download_link = match &release.download_access {
         DownloadAccess::Code { .. } => {
             if release.download_assets_available() {
-                let t_unlock_permalink = &translations.unlock_permalink;
+                let t_unlock_permalink = build.locale.unlock_permalink();
                 let page_hash = build.hash_with_salt(|hasher| {
                     release.permalink.slug.hash(hasher);
                     t_unlock_permalink.hash(hasher);
@@ -75,7 +103,7 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
         }
         DownloadAccess::Free => {
             if release.download_assets_available() {
-                let t_downloads_permalink = &translations.downloads_permalink;
+                let t_downloads_permalink = build.locale.downloads_permalink();
                 let page_hash = build.hash_with_salt(|hasher| {
                     release.permalink.slug.hash(hasher);
                     t_downloads_permalink.hash(hasher);
@@ -95,7 +123,7 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
         }
         DownloadAccess::Paycurtain { payment_info, .. } => {
             if release.download_assets_available() && payment_info.is_some() {
-                let t_purchase_permalink = &translations.purchase_permalink;
+                let t_purchase_permalink = build.locale.purchase_permalink();
                 let page_hash = build.hash_with_salt(|hasher| {
                     release.permalink.slug.hash(hasher);
                     t_purchase_permalink.hash(hasher);
@@ -124,6 +152,8 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
 
     let varying_track_artists = release.varying_track_artists();
 
+    let release_asset_prefix = build.asset_prefix("", root_prefix, &release.permalink.slug);
+
     let t_playback_position = &translations.playback_position;
     let r_tracks = release.tracks
         .iter()
@@ -149,7 +179,7 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
                     });
 
                     let track_filename_urlencoded = urlencoding::encode(&track_filename);
-                    let src = format!("{track_number}/{format_dir}/{track_hash}/{track_filename_urlencoded}");
+                    let src = format!("{release_asset_prefix}{track_number}/{format_dir}/{track_hash}/{track_filename_urlencoded}");
 
                     let source_type = format.source_type();
                     format!(r#"<source src="{src}" type="{source_type}">"#)
@@ -160,6 +190,7 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
             let duration_seconds = track.transcodes.borrow().source_meta.duration_seconds;
             let track_title = track.title();
 
+            let r_gain = normalization_gain_attribute(track);
             let track_duration_formatted = format_time(duration_seconds);
             let track_number_formatted = release.track_numbering.format(track_number);
             let track_title_escaped = html_escape_outside_attribute(&track_title);
@@ -191,12 +222,12 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
             };
 
             let r_cover_micro = if let Some(filename) = track.cover_160_filename() {
-                format!(r#"<img aria-hidden="true" src="{track_number}/{filename}">"#)
+                format!(r#"<img aria-hidden="true" src="{release_asset_prefix}{track_number}/{filename}">"#)
             } else if let Some(filename) = release.cover_160_filename() {
-                format!(r#"<img aria-hidden="true" src="{filename}">"#)
+                format!(r#"<img aria-hidden="true" src="{release_asset_prefix}{filename}">"#)
             } else {
                 let filename = release.procedural_cover_120_filename_unchecked();
-                format!(r#"<img aria-hidden="true" class="procedural" src="{filename}">"#)
+                format!(r#"<img aria-hidden="true" class="procedural" src="{release_asset_prefix}{filename}">"#)
             };
 
             let r_more = if track.more.is_some() {
@@ -210,7 +241,7 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
             };
 
             formatdoc!(r#"
-                <div class="track" data-duration="{duration_seconds}">
+                <div class="track" data-duration="{duration_seconds}"{r_gain}>
                     <button class="track_playback" tabindex="-1">
                         <span class="icon">
                             {play_icon}
@@ -256,14 +287,33 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
         primary_actions.push(download_link);
     }
 
+    if release.monetization_unlocks_downloads() {
+        let t_downloads_permalink = build.locale.downloads_permalink();
+        let page_hash = build.hash_with_salt(|hasher| {
+            release.permalink.slug.hash(hasher);
+            t_downloads_permalink.hash(hasher);
+        });
+
+        let href = format!("{t_downloads_permalink}/{page_hash}{index_suffix}");
+        primary_actions.push(monetization_unlock_link(&href, &translations.download));
+    }
+
+    // When the more section is not part of the layout, there is nothing to
+    // link to, so we also show the full list of artists right away.
+    let more_in_layout = release.layout.contains(&ReleaseSection::More);
+
     let artists = list_release_artists(build, index_suffix, root_prefix, catalog, Truncation::Pass, release);
-    let artists_truncation = Truncation::Truncate {
-        max_chars: 80,
-        others_link: String::from("#more")
+    let artists_truncation = if more_in_layout {
+        Truncation::Truncate {
+            max_chars: 80,
+            others_link: String::from("#more")
+        }
+    } else {
+        Truncation::Pass
     };
     let artists_truncated = list_release_artists(build, index_suffix, root_prefix, catalog, artists_truncation, release);
 
-    let r_more = if release.more.is_some() || artists_truncated.truncated {
+    let r_more = if more_in_layout && (release.more.is_some() || artists_truncated.truncated) {
         let more_label = match &release.more_label {
             Some(label) => label,
             None => *translations.more
@@ -314,6 +364,48 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
         "#)
     };
 
+    let r_notify_me = match release.scheduled_date(build) {
+        Some(date) => {
+            let t_release_scheduled = translations.release_scheduled_for_xxx(&date.format("%Y-%m-%d").to_string());
+            let t_add_to_calendar = &translations.add_to_calendar;
+            let calendar_icon = icons::CALENDAR;
+
+            let mut notify_actions = vec![formatdoc!(r#"
+                <a download href="{ICS_FILENAME}">
+                    {calendar_icon}
+                    <span>{t_add_to_calendar}</span>
+                </a>
+            "#)];
+
+            if build.base_url.is_some() && catalog.feeds.any_requested() {
+                let t_subscribe = &translations.subscribe;
+                let feed_icon = icons::feed(&translations.feed);
+                let subscribe_slug = catalog.subscribe_permalink.as_ref().unwrap();
+
+                notify_actions.push(formatdoc!(r#"
+                    <a href="{root_prefix}{subscribe_slug}{index_suffix}">
+                        {feed_icon}
+                        <span>{t_subscribe}</span>
+                    </a>
+                "#));
+            }
+
+            let joined = notify_actions.join("");
+
+            formatdoc!(r#"
+                <div class="notify_me">
+                    <p>{t_release_scheduled}</p>
+                    <div class="actions">
+                        {joined}
+                    </div>
+                </div>
+            "#)
+        }
+        None => String::new()
+    };
+
+    let mut r_copy_timecode_link = String::new();
+
     if release.copy_link {
         layout.add_clipboard_script();
 
@@ -324,6 +416,14 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
 
         let r_copy_link = copy_button(content_key, &content_value, &translations.copy_link);
         secondary_actions.push(r_copy_link);
+
+        r_copy_timecode_link = copy_timecode_link_button(&content_value, translations);
+    }
+
+    if let Some(base_url) = &build.base_url {
+        let release_url = base_url.join_index(build, &release.permalink.slug);
+        let r_share_actions = share_actions(&release.share_buttons, &release.title, translations, &release_url);
+        secondary_actions.extend(r_share_actions);
     }
 
     if build.base_url.is_some() {
@@ -375,6 +475,13 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
 
     let relative_waveforms = if release.theme.relative_waveforms { "" } else { "data-disable-relative-waveforms " };
 
+    // Picked up by the player when advancing from one track to the next
+    let continuous_playback = match (release.crossfade, release.gapless) {
+        (Some(seconds), _) => format!(r#"data-crossfade="{seconds}" "#),
+        (None, true) => String::from("data-gapless "),
+        (None, false) => String::new()
+    };
+
     let release_title_with_unlisted_badge = if release.unlisted {
         format!("{release_title_escaped} {}", unlisted_badge(build))
     } else {
@@ -383,6 +490,44 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
 
     let cover = release_cover_image(build, release, "", root_prefix);
 
+    // Imprint and region link to their filter pages if these exist, the
+    // year of the release links to its year archive page (if enabled)
+    let mut imprint_and_region = [ReleaseFilterKind::Imprint, ReleaseFilterKind::Region]
+        .iter()
+        .filter_map(|kind| {
+            let value = kind.value(release)?;
+            let value_escaped = html_escape_outside_attribute(value);
+            let filter = catalog.filters
+                .iter()
+                .find(|filter| filter.kind == *kind && filter.label == *value);
+
+            match (filter, &catalog.filter_permalink) {
+                (Some(filter), Some(filter_permalink)) => {
+                    let href = format!("{root_prefix}{}{index_suffix}", filter.path(filter_permalink));
+                    Some(format!(r#"<a href="{href}">{value_escaped}</a>"#))
+                }
+                _ => Some(value_escaped)
+            }
+        })
+        .collect::<Vec<String>>();
+
+    if let Some(year_page) = catalog.year_archive_pages
+        .iter()
+        .find(|page| !page.is_decade() && page.matches(release)) {
+        let label = year_page.label();
+        let slug = &year_page.slug;
+        imprint_and_region.push(format!(r#"<a href="{root_prefix}{slug}{index_suffix}">{label}</a>"#));
+    }
+
+    let r_imprint_and_region = if imprint_and_region.is_empty() {
+        String::new()
+    } else {
+        let joined = imprint_and_region.join(" · ");
+        format!(r#"<div class="release_imprint">{joined}</div>"#)
+    };
+
+    let r_runtime_and_sizes = release_runtime_and_sizes(release, translations);
+
     let synopsis = match &release.synopsis {
         Some(synopsis) => {
             formatdoc!(r#"
@@ -403,31 +548,105 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
     };
 
     let speed_controls = if release.speed_controls { SPEED_CONTROLS } else { "" };
+    // A queue only makes sense with more than one track
+    let (r_queue_panel, r_queue_toggle) = if release.tracks.len() > 1 {
+        (queue_panel(translations), queue_toggle(translations))
+    } else {
+        (String::new(), String::new())
+    };
     let r_volume_controls = volume_controls(translations);
+    let r_volume_normalization = if release.volume_normalization { volume_normalization_toggle(translations) } else { String::new() };
+
+    // The tracklist is always rendered, as the player relies on it, but it is
+    // hidden if it is not part of the layout.
+    let tracklist_in_layout = release.layout.contains(&ReleaseSection::Tracklist);
+    let hidden = if tracklist_in_layout { "" } else { " hidden" };
+    let r_tracklist = formatdoc!(r#"
+        <div class="page"{hidden}>
+            <div class="page_center">
+                <div class="{compact_tall} tracks" data-longest-duration="{longest_track_duration}" {continuous_playback}{relative_waveforms}>
+                    {r_tracks}
+                </div>
+            </div>
+        </div>
+    "#);
+
+    let r_downloads = format!("{r_primary_actions}{r_notify_me}");
+
+    let r_physical = if release.physical_editions.is_empty() {
+        String::new()
+    } else {
+        physical_editions_section(build, release, root_prefix)
+    };
+
+    // Compact sections at the start of the layout are placed next to the
+    // cover, everything from the first full-width section onwards is placed
+    // below (with compact sections wrapped to span the page there as well).
+    let mut r_abstract_sections = String::new();
+    let mut r_page_sections = String::new();
+    let mut next_to_cover = true;
+
+    if !release.layout.contains(&ReleaseSection::Downloads) {
+        r_abstract_sections.push_str(&r_notify_me);
+    }
+
+    for section in &release.layout {
+        let r_section = match section {
+            ReleaseSection::Downloads => &r_downloads,
+            ReleaseSection::Links => &r_secondary_actions,
+            ReleaseSection::More => &r_more,
+            ReleaseSection::Physical => &r_physical,
+            ReleaseSection::Synopsis => &synopsis,
+            ReleaseSection::Tracklist => &r_tracklist
+        };
+
+        if !section.is_compact() {
+            next_to_cover = false;
+        }
+
+        if next_to_cover {
+            r_abstract_sections.push_str(r_section);
+        } else if section.is_compact() {
+            if !r_section.is_empty() {
+                r_page_sections.push_str(&formatdoc!(r#"
+                    <div class="page">
+                        <div class="page_center">
+                            {r_section}
+                        </div>
+                    </div>
+                "#));
+            }
+        } else {
+            r_page_sections.push_str(r_section);
+        }
+    }
+
+    if !tracklist_in_layout {
+        r_page_sections.push_str(&r_tracklist);
+    }
+
+    let r_content_warning = content_warning_interstitial(build, release);
+    let r_draft_banner = if release.draft { draft_banner(build) } else { String::new() };
 
     let next_track_icon = icons::next_track(&translations.next_track);
     let body = formatdoc!(r##"
+        {r_content_warning}
+        {r_draft_banner}
         <div class="page">
             <div class="page_split">
                 <div class="cover">{cover}</div>
                 <div class="abstract">
                     <h1>{release_title_with_unlisted_badge}</h1>
                     <div class="release_artists">{artists_truncated}</div>
-                    {r_primary_actions}
-                    {synopsis}
-                    {r_secondary_actions}
+                    {r_imprint_and_region}
+                    <div class="runtime_and_sizes">{r_runtime_and_sizes}</div>
+                    {r_abstract_sections}
                 </div>
             </div>
         </div>
-        <div class="page">
-            <div class="page_center">
-                <div class="{compact_tall} tracks" data-longest-duration="{longest_track_duration}" {relative_waveforms}>
-                    {r_tracks}
-                </div>
-            </div>
-        </div>
-        {r_more}
+        {r_page_sections}
         <div class="docked_player {tall}">
+            {r_queue_panel}
             <div class="timeline">
                 <input aria-label="{t_playback_position}" aria-valuetext="" autocomplete="off" max="" min="0" step="any" type="range" value="0">
                 <div class="base"></div>
@@ -441,22 +660,26 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
                     {next_track_icon}
                 </button>
                 {speed_controls}
+                {r_volume_normalization}
                 {r_volume_controls}
+                {r_queue_toggle}
                 <span class="track_info">
                     <span class="number"></span>
                     <span class="title_wrapper"></span>
                 </span>
+                <span class="error" role="alert"></span>
                 <span class="time">
                     <span class="current"></span>
                     <span>/</span>
                     <span class="total"></span>
                 </span>
+                {r_copy_timecode_link}
             </div>
         </div>
         <div aria-label="" class="docked_player_status" role="status"></div>
     "##);
 
-    if release.unlisted {
+    if release.unlisted || release.noindex {
         layout.no_indexing();
     }
 
@@ -471,7 +694,7 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
             }
 
             if let Some(described_image) = &release.cover {
-                let release_prefix = base_url.join_prefix(release_slug);
+                let release_prefix = build.asset_url().join_prefix(release_slug);
                 let opengraph_image = described_image
                     .borrow()
                     .cover_opengraph_image_unchecked(&release_prefix);
@@ -492,10 +715,124 @@ pub fn release_html(build: &Build, catalog: &Catalog, release: &Release) -> Stri
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &release.theme,
         &release.title
     )
 }
+
+/// The physical editions of the release, each with its photos, format,
+/// price, stock status and (as long as it is available) a link to order it.
+fn physical_editions_section(build: &Build, release: &Release, root_prefix: &str) -> String {
+    let release_asset_prefix = build.asset_prefix("", root_prefix, &release.permalink.slug);
+    let translations = &build.locale.translations;
+
+    let r_editions = release.physical_editions
+        .iter()
+        .zip(1..)
+        .map(|(physical_edition, edition_number)| {
+            let r_photos = physical_edition.photos
+                .iter()
+                .zip(1..)
+                .map(|(described_image, photo_number)| {
+                    let image_ref = described_image.borrow();
+
+                    let alt = match &described_image.description {
+                        Some(description) => format!(r#"alt="{}""#, html_escape_inside_attribute(description)),
+                        None => String::new()
+                    };
+
+                    let hash = image_ref.hash.as_url_safe_base64();
+                    let prefix = format!(
+                        "{release_asset_prefix}{}",
+                        PhysicalEdition::photo_filename_prefix(edition_number, photo_number)
+                    );
+
+                    let ImgAttributes { src, srcset } = image_ref.artist_assets
+                        .as_ref()
+                        .unwrap()
+                        .img_attributes_fluid(&hash, &prefix);
+
+                    let image = formatdoc!(r#"
+                        <img
+                            {alt}
+                            class="physical_photo"
+                            loading="lazy"
+                            sizes="(min-width: 50rem) 20rem, 100vw"
+                            src="{src}"
+                            srcset="{srcset}">
+                    "#);
+
+                    if described_image.description.is_some() {
+                        image
+                    } else {
+                        wrap_undescribed_image(build, root_prefix, &image, "", "physical_photo")
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join("");
+
+            let r_photos = if r_photos.is_empty() {
+                String::new()
+            } else {
+                format!(r#"<div class="physical_photos">{r_photos}</div>"#)
+            };
+
+            let format_escaped = html_escape_outside_attribute(&physical_edition.format);
+
+            let r_price = match &physical_edition.price {
+                Some(price) => format!(
+                    r#"<div class="physical_price">{}</div>"#,
+                    html_escape_outside_attribute(price)
+                ),
+                None => String::new()
+            };
+
+            let r_stock = match physical_edition.stock {
+                Stock::Available => format!(r#"<div class="physical_stock">{}</div>"#, translations.in_stock),
+                Stock::SoldOut => format!(r#"<div class="physical_stock sold_out">{}</div>"#, translations.sold_out)
+            };
+
+            let r_order = match (&physical_edition.order_url, physical_edition.stock) {
+                (Some(order_url), Stock::Available) => {
+                    let external_icon = icons::external(&translations.external_link);
+                    let t_order = &translations.order;
+                    let order_url_escaped = html_escape_inside_attribute(order_url);
+
+                    formatdoc!(r#"
+                        <div class="actions">
+                            <a href="{order_url_escaped}" target="_blank">{external_icon} <span>{t_order}</span></a>
+                        </div>
+                    "#)
+                }
+                _ => String::new()
+            };
+
+            formatdoc!(r#"
+                <div class="physical_edition">
+                    {r_photos}
+                    <div class="physical_info">
+                        <h3>{format_escaped}</h3>
+                        {r_price}
+                        {r_stock}
+                        {r_order}
+                    </div>
+                </div>
+            "#)
+        })
+        .collect::<Vec<String>>()
+        .join("");
+
+    let t_physical_editions = &translations.physical_editions;
+
+    formatdoc!(r#"
+        <div class="page">
+            <div class="page_center">
+                <div class="physical_editions">
+                    <h2>{t_physical_editions}</h2>
+                    {r_editions}
+                </div>
+            </div>
+        </div>
+    "#)
+}
@@ -6,23 +6,22 @@ use std::hash::Hash;
 use indoc::formatdoc;
 
 use crate::{
-    Build,
-    Catalog,
+    CoverAspectRatio,
+    DownloadAccess,
     DownloadFormat,
-    Release
+    FORMAT_COMPARISON_DIRNAME,
+    FormatComparison,
+    TRACK_NUMBERS
 };
 use crate::util::html_escape_outside_attribute;
 
-use super::Layout;
+use super::{Layout, ReleaseContext};
 use super::{compact_release_identifier, download_entry};
 
 /// The download page itself, providing direct links to the (zip) archive
 /// files and/or individual tracks download links.
-pub fn release_download_html(
-    build: &Build,
-    catalog: &Catalog,
-    release: &Release
-) -> String {
+pub fn release_download_html(context: &ReleaseContext) -> String {
+    let &ReleaseContext { build, catalog, release } = context;
     let index_suffix = build.index_suffix();
     let root_prefix = "../../../";
 
@@ -62,6 +61,8 @@ pub fn release_download_html(
         root_prefix,
     );
 
+    let release_asset_prefix = build.asset_prefix(release_prefix, root_prefix, &release.permalink.slug);
+
     let release_downloads = if !release_formats_sorted.is_empty() {
         let release_downloads = release_formats_sorted
             .iter()
@@ -82,7 +83,7 @@ pub fn release_download_html(
                 let format_dir = download_format.as_audio_format().asset_dirname().to_string();
 
                 download_entry(
-                    format!("{release_prefix}{format_dir}/{archive_hash}/{archive_filename_urlencoded}"),
+                    format!("{release_asset_prefix}{format_dir}/{archive_hash}/{archive_filename_urlencoded}"),
                     download_format.user_label(),
                     archives.borrow().get_unchecked(*download_format).asset.filesize_bytes
                 )
@@ -102,10 +103,14 @@ pub fn release_download_html(
     let extra_downloads = if release.extra_downloads.separate && (release.cover.is_some() || !release.extras.is_empty()) {
         let cover_entry = if let Some(described_image) = &release.cover {
             let image_ref = described_image.borrow();
-            let largest_cover_asset = image_ref.cover_assets_unchecked().largest();
+            let cover_assets = match release.cover_aspect_ratio {
+                CoverAspectRatio::Original => image_ref.cover_assets_uncropped_unchecked(),
+                CoverAspectRatio::Square => image_ref.cover_assets_unchecked()
+            };
+            let largest_cover_asset = cover_assets.largest();
             let filename = largest_cover_asset.target_filename();
             download_entry(
-                format!("{release_prefix}{filename}"),
+                format!("{release_asset_prefix}{filename}"),
                 &build.locale.translations.cover_image,
                 largest_cover_asset.filesize_bytes
             )
@@ -126,7 +131,7 @@ pub fn release_download_html(
                     let extra_filename_urlencoded = urlencoding::encode(&extra.sanitized_filename);
 
                     download_entry(
-                        format!("{release_prefix}extras/{extra_hash}/{extra_filename_urlencoded}"),
+                        format!("{release_asset_prefix}extras/{extra_hash}/{extra_filename_urlencoded}"),
                         &extra.sanitized_filename,
                         extra.file_meta.size
                     )
@@ -150,6 +155,229 @@ pub fn release_download_html(
         String::new()
     };
 
+    // Only tracks with free downloads are offered for selection, as their
+    // download links are public on their track pages anyway - listing tracks
+    // with restricted access here would leak their download links.
+    let selectable_tracks = release.tracks
+        .iter()
+        .zip(TRACK_NUMBERS)
+        .filter(|(track, _track_number)| {
+            matches!(track.download_access, DownloadAccess::Free) && !track.download_formats.is_empty()
+        })
+        .collect::<Vec<_>>();
+
+    let track_selection = if selectable_tracks.len() > 1 {
+        let mut selection_formats_sorted: Vec<DownloadFormat> = Vec::new();
+        for (track, _track_number) in &selectable_tracks {
+            for download_format in &track.download_formats {
+                if !selection_formats_sorted.contains(download_format) {
+                    selection_formats_sorted.push(*download_format);
+                }
+            }
+        }
+        selection_formats_sorted.sort_by_key(|format| format.download_rank());
+
+        let format_buttons = selection_formats_sorted
+            .iter()
+            .enumerate()
+            .map(|(index, download_format)| {
+                let format_dir = download_format.as_audio_format().asset_dirname();
+                let pressed = if index == 0 { "true" } else { "false" };
+                let user_label = download_format.user_label();
+                formatdoc!(r#"
+                    <button aria-pressed="{pressed}" data-format="{format_dir}" type="button">{user_label}</button>
+                "#)
+            })
+            .collect::<Vec<String>>()
+            .join("");
+
+        let track_checkboxes = selectable_tracks
+            .iter()
+            .map(|(track, track_number)| {
+                let format_attributes = track.download_formats
+                    .iter()
+                    .map(|download_format| {
+                        let track_filename = format!(
+                            "{basename}{extension}",
+                            basename = track.asset_basename.as_ref().unwrap(),
+                            extension = track.download_extension(download_format.as_audio_format())
+                        );
+
+                        let track_hash = build.hash_with_salt(|hasher| {
+                            release.permalink.slug.hash(hasher);
+                            track_number.hash(hasher);
+                            download_format.as_audio_format().asset_dirname().hash(hasher);
+                            track_filename.hash(hasher);
+                        });
+
+                        let format_dir = download_format.as_audio_format().asset_dirname();
+                        let track_filename_urlencoded = urlencoding::encode(&track_filename);
+
+                        format!(r#"data-{format_dir}="{release_asset_prefix}{track_number}/{format_dir}/{track_hash}/{track_filename_urlencoded}""#)
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" ");
+
+                let track_number_formatted = release.track_numbering.format(*track_number);
+                let track_title_escaped = html_escape_outside_attribute(&track.title());
+
+                formatdoc!(r#"
+                    <label>
+                        <input autocomplete="off" {format_attributes} type="checkbox">
+                        <span class="track_number">{track_number_formatted}</span> {track_title_escaped}
+                    </label>
+                "#)
+            })
+            .collect::<Vec<String>>()
+            .join("");
+
+        let t_download_selected = &build.locale.translations.download_selected;
+        let t_individual_tracks = &build.locale.translations.individual_tracks;
+        let t_select_all = &build.locale.translations.select_all;
+        formatdoc!(r#"
+            <div class="track_selection" hidden>
+                <div class="download_group">{t_individual_tracks}</div>
+                <label>
+                    <input autocomplete="off" class="select_all" type="checkbox">
+                    {t_select_all}
+                </label>
+                {track_checkboxes}
+                <div class="buttons">
+                    {format_buttons}
+                </div>
+                <button class="download_selected" disabled type="button">{t_download_selected}</button>
+            </div>
+            <script>
+                (() => {{
+                    const selection = document.querySelector('.track_selection');
+                    const downloadButton = selection.querySelector('.download_selected');
+                    const formatButtons = [...selection.querySelectorAll('.buttons button')];
+                    const selectAll = selection.querySelector('.select_all');
+                    const trackCheckboxes = [...selection.querySelectorAll('input:not(.select_all)')];
+                    let format = formatButtons[0].dataset.format;
+                    let downloading = false;
+
+                    // Tracks that are not available in the chosen format
+                    // can not be selected.
+                    const update = () => {{
+                        for (const checkbox of trackCheckboxes) {{
+                            checkbox.disabled = !checkbox.hasAttribute(`data-${{format}}`);
+                        }}
+                        const available = trackCheckboxes.filter(checkbox => !checkbox.disabled);
+                        const selected = available.filter(checkbox => checkbox.checked);
+                        selectAll.checked = selected.length > 0 && selected.length === available.length;
+                        downloadButton.disabled = downloading || selected.length === 0;
+                    }};
+
+                    for (const button of formatButtons) {{
+                        button.addEventListener('click', () => {{
+                            format = button.dataset.format;
+                            for (const other of formatButtons) {{
+                                other.setAttribute('aria-pressed', other === button ? 'true' : 'false');
+                            }}
+                            update();
+                        }});
+                    }}
+
+                    selectAll.addEventListener('change', () => {{
+                        for (const checkbox of trackCheckboxes) {{
+                            if (!checkbox.disabled) {{ checkbox.checked = selectAll.checked; }}
+                        }}
+                        update();
+                    }});
+
+                    for (const checkbox of trackCheckboxes) {{
+                        checkbox.addEventListener('change', update);
+                    }}
+
+                    // The selected tracks are downloaded one after another,
+                    // as browsers tend to ignore downloads that are started
+                    // in quick succession.
+                    downloadButton.addEventListener('click', async () => {{
+                        const urls = trackCheckboxes
+                            .filter(checkbox => checkbox.checked && !checkbox.disabled)
+                            .map(checkbox => checkbox.getAttribute(`data-${{format}}`));
+
+                        downloading = true;
+                        update();
+
+                        for (const url of urls) {{
+                            const link = document.createElement('a');
+                            link.download = '';
+                            link.href = url;
+                            document.body.append(link);
+                            link.click();
+                            link.remove();
+                            await new Promise(resolve => setTimeout(resolve, 1000));
+                        }}
+
+                        downloading = false;
+                        update();
+                    }});
+
+                    update();
+                    selection.hidden = false;
+                }})();
+            </script>
+        "#)
+    } else {
+        String::new()
+    };
+
+    let format_comparison = if release.format_comparison.is_some() && !release_formats_sorted.is_empty() {
+        let buttons = release_formats_sorted
+            .iter()
+            .map(|download_format| {
+                let clip_filename = FormatComparison::clip_filename(*download_format);
+                let user_label = download_format.user_label();
+                formatdoc!(r#"
+                    <button aria-pressed="false" data-src="{release_prefix}{FORMAT_COMPARISON_DIRNAME}/{clip_filename}" type="button">{user_label}</button>
+                "#)
+            })
+            .collect::<Vec<String>>()
+            .join("");
+
+        let t_compare_formats = &build.locale.translations.compare_formats;
+        let t_format_comparison_hint = &build.locale.translations.format_comparison_hint;
+        formatdoc!(r#"
+            <div class="download_group">{t_compare_formats}</div>
+
+            <div class="format_comparison">
+                <span>{t_format_comparison_hint}</span>
+                <div class="buttons">
+                    {buttons}
+                </div>
+                <audio controls preload="none"></audio>
+            </div>
+            <script>
+                (() => {{
+                    const comparison = document.querySelector('.format_comparison');
+                    const audio = comparison.querySelector('audio');
+                    const buttons = comparison.querySelectorAll('button');
+                    for (const button of buttons) {{
+                        button.addEventListener('click', () => {{
+                            // Switching the format keeps the playback position,
+                            // so the same moment can be heard in each format
+                            const currentTime = audio.currentTime;
+                            const resume = !audio.paused || audio.currentSrc === '';
+                            audio.src = button.dataset.src;
+                            audio.addEventListener('loadedmetadata', () => {{
+                                audio.currentTime = currentTime;
+                                if (resume) {{ audio.play(); }}
+                            }}, {{ once: true }});
+                            audio.load();
+                            for (const other of buttons) {{
+                                other.setAttribute('aria-pressed', other === button ? 'true' : 'false');
+                            }}
+                        }});
+                    }}
+                }})();
+            </script>
+        "#)
+    } else {
+        String::new()
+    };
+
     let t_downloads = &build.locale.translations.downloads;
     let body = formatdoc!(
         r##"
@@ -160,7 +388,9 @@ pub fn release_download_html(
 
                         {compact_release_identifier_rendered}
                         {release_downloads}
+                        {track_selection}
                         {extra_downloads}
+                        {format_comparison}
 
                         <div class="download_hints" id="hints">
                             {download_hints}
@@ -180,10 +410,8 @@ pub fn release_download_html(
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &release.theme,
         &page_title
     )
 }
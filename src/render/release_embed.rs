@@ -17,7 +17,7 @@ use crate::util::{html_escape_inside_attribute, html_escape_outside_attribute};
 
 use super::SPEED_CONTROLS;
 use super::{EmbedLayout, Truncation};
-use super::{list_track_artists, volume_controls};
+use super::{embed_support_button, list_track_artists, volume_controls};
 
 pub fn release_embed_html(
     base_url: &SiteUrl,
@@ -28,6 +28,7 @@ pub fn release_embed_html(
     let index_suffix = build.index_suffix();
     let release_prefix = "../../";
     let root_prefix = "../../../";
+    let release_asset_prefix = build.asset_prefix(release_prefix, root_prefix, &release.permalink.slug);
     let translations = &build.locale.translations;
 
     let varying_track_artists = release.varying_track_artists();
@@ -56,7 +57,7 @@ pub fn release_embed_html(
                     });
 
                     let track_filename_urlencoded = urlencoding::encode(&track_filename);
-                    let src = format!("{release_prefix}{track_number}/{format_dir}/{track_hash}/{track_filename_urlencoded}");
+                    let src = format!("{release_asset_prefix}{track_number}/{format_dir}/{track_hash}/{track_filename_urlencoded}");
 
                     let source_type = format.source_type();
                     format!(r#"<source src="{src}" type="{source_type}">"#)
@@ -83,12 +84,19 @@ pub fn release_embed_html(
                 false => String::new()
             };
 
+            // In an embeds-only build there are no track pages to link to
+            let r_title = if build.embeds_only {
+                format!(r#"<span class="title" title="{track_title_attribute_escaped}">{track_title_escaped}</span>"#)
+            } else {
+                format!(r#"<a class="title" href="{release_prefix}{track_number}{index_suffix}" target="_parent" title="{track_title_attribute_escaped}">{track_title_escaped}</a>"#)
+            };
+
             formatdoc!(r#"
                 <div class="track" data-duration="{track_duration_seconds}">
                     <div class="track_header">
                         <span class="number">{track_number_formatted}</span>
                         <span>
-                            <a class="title" href="{release_prefix}{track_number}{index_suffix}" target="_parent" title="{track_title_attribute_escaped}">{track_title_escaped}</a>{track_artists}
+                            {r_title}{track_artists}
                         </span>
                     </div>
                     <audio controls preload="none">
@@ -104,6 +112,7 @@ pub fn release_embed_html(
     let tall = if release.varying_track_artists() { "tall" } else { "" };
 
     let speed_controls = if release.speed_controls { SPEED_CONTROLS } else { "" };
+    let r_support_button = embed_support_button(base_url, build, release);
     let r_volume_controls = volume_controls(translations);
 
     let next_track_icon = icons::next_track(&translations.next_track);
@@ -139,6 +148,7 @@ pub fn release_embed_html(
                     <span>/</span>
                     <span class="total"></span>
                 </span>
+                {r_support_button}
             </div>
         </div>
     "##);
@@ -3,24 +3,18 @@
 
 use indoc::formatdoc;
 
-use crate::{
-    Build,
-    Catalog,
-    Release,
-    SiteUrl
-};
+use crate::SiteUrl;
 use crate::util::html_escape_outside_attribute;
 
-use super::Layout;
+use super::{Layout, ReleaseContext};
 use super::{compact_release_identifier, copy_button, embed_code};
 
 /// Renders the page that lets the visitor copy embed codes for the release.
 pub fn release_embed_codes_html(
     base_url: &SiteUrl,
-    build: &Build,
-    catalog: &Catalog,
-    release: &Release
+    context: &ReleaseContext
 ) -> String {
+    let &ReleaseContext { build, catalog, release } = context;
     let index_suffix = build.index_suffix();
     let release_prefix = "../";
     let root_prefix = "../../";
@@ -83,10 +77,8 @@ pub fn release_embed_codes_html(
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &release.theme,
         &page_title
     )
 }
@@ -6,25 +6,19 @@ use std::ops::Range;
 
 use indoc::formatdoc;
 
-use crate::{
-    Build,
-    Catalog,
-    Price,
-    Release
-};
+use crate::Price;
 use crate::util::html_escape_outside_attribute;
 
-use super::Layout;
+use super::{Layout, ReleaseContext};
 use super::compact_release_identifier;
 
 /// Renders content for pages found under /[release_permalink]/[purchase_permalink]/[hash]/index.html
 pub fn release_purchase_html(
-    build: &Build,
-    catalog: &Catalog,
+    context: &ReleaseContext,
     payment_info: &str,
-    price: &Price,
-    release: &Release
+    price: &Price
 ) -> String {
+    let &ReleaseContext { build, catalog, release } = context;
     let index_suffix = build.index_suffix();
     let release_prefix = "../../";
     let root_prefix = "../../../";
@@ -94,7 +88,7 @@ pub fn release_purchase_html(
         r_price_input = price_input(&price.range, &placeholder);
     }
 
-    let t_downloads_permalink = &build.locale.translations.downloads_permalink;
+    let t_downloads_permalink = build.locale.downloads_permalink();
     let download_page_hash = build.hash_with_salt(|hasher| {
         release.permalink.slug.hash(hasher);
         t_downloads_permalink.hash(hasher);
@@ -228,10 +222,8 @@ pub fn release_purchase_html(
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &release.theme,
         &page_title
     )
 }
@@ -5,23 +5,17 @@ use std::hash::Hash;
 
 use indoc::formatdoc;
 
-use crate::{
-    Build,
-    Catalog,
-    Release
-};
 use crate::util::html_escape_outside_attribute;
 
-use super::Layout;
+use super::{Layout, ReleaseContext};
 use super::compact_release_identifier;
 
 /// Renders content for pages found under /[release_permalink]/[unlock_permalink]/[hash]/index.html
 pub fn release_unlock_html(
-    build: &Build,
-    catalog: &Catalog,
-    release: &Release,
+    context: &ReleaseContext,
     unlock_info: &Option<String>
 ) -> String {
+    let &ReleaseContext { build, catalog, release } = context;
     let index_suffix = build.index_suffix();
     let release_prefix = "../../";
     let root_prefix = "../../../";
@@ -35,17 +29,17 @@ pub fn release_unlock_html(
         .map(|text| text.to_string())
         .unwrap_or(build.locale.translations.default_unlock_info.to_string());
 
-    let t_unlock_permalink = &build.locale.translations.unlock_permalink;
+    let t_unlock_permalink = build.locale.unlock_permalink();
     let page_hash = build.hash_with_salt(|hasher| {
         release.permalink.slug.hash(hasher);
         t_unlock_permalink.hash(hasher);
     });
 
     let t_download_code_seems_incorrect = &build.locale.translations.download_code_seems_incorrect;
-    let t_downloads_permalink = &build.locale.translations.downloads_permalink;
+    let t_downloads_permalink = build.locale.downloads_permalink();
     let t_enter_code_here = &build.locale.translations.enter_code_here;
     let t_unlock = &build.locale.translations.unlock;
-    let t_unlock_manual_instructions = &build.locale.translations.unlock_manual_instructions(&page_hash, index_suffix);
+    let t_unlock_manual_instructions = &build.locale.unlock_manual_instructions(&page_hash, index_suffix);
     let content = formatdoc!(r#"
         <div class="unlock_scripted">
             {custom_or_default_unlock_info}
@@ -115,10 +109,8 @@ pub fn release_unlock_html(
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &release.theme,
         &page_title
     )
 }
@@ -0,0 +1,52 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use indoc::formatdoc;
+
+use crate::MirroredPage;
+
+use super::{CatalogContext, Layout};
+
+/// A page for searching all public releases, their tracks and the featured
+/// artists. The search index and the matching are provided by search.js
+/// (see scripts/search.rs), the page itself only holds the search field
+/// and the (initially empty) list of results.
+pub fn search_html(context: &CatalogContext) -> String {
+    let &CatalogContext { build, catalog } = context;
+    let root_prefix = "../";
+    let translations = &build.locale.translations;
+
+    let mut layout = Layout::new();
+
+    layout.add_search_script();
+    layout.no_indexing();
+    layout.set_mirrored_page(MirroredPage::Page(catalog.search_permalink.clone().unwrap()));
+
+    let t_search = &translations.search;
+
+    let body = formatdoc!(r#"
+        <div class="page">
+            <div class="page_center">
+                <div class="search">
+                    <h1>{t_search}</h1>
+                    <form data-root-prefix="{root_prefix}" id="search" role="search">
+                        <input aria-label="{t_search}" autocomplete="off" name="q" type="search">
+                    </form>
+                    <p id="search_status" role="status"></p>
+                    <ol id="search_results"></ol>
+                </div>
+            </div>
+        </div>
+    "#);
+
+    let catalog_title = catalog.title();
+
+    let page_title = format!("{t_search} – {catalog_title}");
+
+    layout.render(
+        &body,
+        context,
+        root_prefix,
+        &page_title
+    )
+}
@@ -5,13 +5,12 @@ use indoc::formatdoc;
 use translations::Translations;
 
 use crate::{
-    Build,
-    Catalog,
     Feeds,
+    MirroredPage,
     SiteUrl
 };
 
-use super::Layout;
+use super::{CatalogContext, Layout};
 use super::copy_button;
 
 pub fn feed_choice(
@@ -42,7 +41,8 @@ pub fn feed_choice(
 
 /// The subscription choices page, providing direct links to the feed files,
 /// info about the feed types, copy buttons, etc.
-pub fn subscribe_html(build: &Build, catalog: &Catalog) -> String {
+pub fn subscribe_html(context: &CatalogContext) -> String {
+    let &CatalogContext { build, catalog } = context;
     let base_url = build.base_url_unchecked();
     let root_prefix = "../";
     let translations = &build.locale.translations;
@@ -50,6 +50,7 @@ pub fn subscribe_html(build: &Build, catalog: &Catalog) -> String {
     let mut layout = Layout::new();
 
     layout.add_clipboard_script();
+    layout.set_mirrored_page(MirroredPage::Subscribe);
     layout.no_indexing();
 
     let mut feed_choices = Vec::new();
@@ -124,10 +125,8 @@ pub fn subscribe_html(build: &Build, catalog: &Catalog) -> String {
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &catalog.theme,
         &page_title
     )
 }
@@ -6,49 +6,67 @@ use std::hash::Hash;
 use indoc::formatdoc;
 
 use crate::{
-    Build,
-    Catalog,
     DownloadAccess,
+    MirroredPage,
+    OpenGraphAudio,
     OpenGraphMeta,
-    Release,
-    Track
+    PageType,
+    VISUAL_LOOP_DIRNAME,
+    VISUAL_LOOP_POSTER_FILENAME,
+    VISUAL_LOOP_VIDEO_FILENAME
 };
 use crate::icons;
-use crate::util::{format_time, html_escape_outside_attribute};
+use crate::m3u;
+use crate::util::{format_time, html_escape_inside_attribute, html_escape_outside_attribute};
 
 use super::SPEED_CONTROLS;
-use super::{Layout, Truncation};
+use super::{Layout, TrackContext, Truncation};
 use super::{
+    content_warning_interstitial,
     copy_button,
+    copy_timecode_link_button,
+    draft_banner,
     link_action,
     list_track_artists,
+    monetization_unlock_link,
+    normalization_gain_attribute,
     release_cover_image,
+    share_actions,
     track_cover_image,
     volume_controls,
+    volume_normalization_toggle,
     waveform
 };
 
-pub fn track_html(
-    build: &Build,
-    catalog: &Catalog,
-    release: &Release,
-    track: &Track,
-    track_number: usize
-) -> String {
+pub fn track_html(context: &TrackContext) -> String {
+    let &TrackContext { build, catalog, release, track, track_number } = context;
     let index_suffix = build.index_suffix();
     let release_slug = &release.permalink.slug;
     let root_prefix = "../../";
     let translations = &build.locale.translations;
 
+    let release_asset_prefix = build.asset_prefix("../", root_prefix, release_slug);
+    let track_asset_prefix = build.asset_prefix("", root_prefix, &format!("{release_slug}/{track_number}"));
+
     let mut layout = Layout::new();
 
     layout.add_clipboard_script();
     layout.add_player_script();
+    layout.set_mirrored_page(MirroredPage::Page(format!("{release_slug}/{track_number}")));
+    layout.set_page_type(PageType::Release);
+
+    if let Some(page_metadata) = &track.page_metadata {
+        layout.add_page_metadata(page_metadata.render(root_prefix));
+    }
+
+    if let Some(payment_pointer) = &track.payment_pointer {
+        layout.add_monetization(payment_pointer);
+    }
 
     let download_link = match &track.download_access {
         DownloadAccess::Code { .. } => {
             if track.download_assets_available() {
-                let t_unlock_permalink = &translations.unlock_permalink;
+                let t_unlock_permalink = build.locale.unlock_permalink();
                 let page_hash = build.hash_with_salt(|hasher| {
                     release_slug.hash(hasher);
                     track_number.hash(hasher);
@@ -82,7 +100,7 @@ pub fn track_html(
         }
         DownloadAccess::Free => {
             if track.download_assets_available() {
-                let t_downloads_permalink = &translations.downloads_permalink;
+                let t_downloads_permalink = build.locale.downloads_permalink();
                 let page_hash = build.hash_with_salt(|hasher| {
                     release_slug.hash(hasher);
                     track_number.hash(hasher);
@@ -104,7 +122,7 @@ pub fn track_html(
         }
         DownloadAccess::Paycurtain { payment_info, .. } => {
             if track.download_assets_available() && payment_info.is_some() {
-                let t_purchase_permalink = &translations.purchase_permalink;
+                let t_purchase_permalink = build.locale.purchase_permalink();
                 let page_hash = build.hash_with_salt(|hasher| {
                     release_slug.hash(hasher);
                     track_number.hash(hasher);
@@ -145,7 +163,7 @@ pub fn track_html(
             });
 
             let track_filename_urlencoded = urlencoding::encode(&track_filename);
-            let src = format!("{format_dir}/{track_hash}/{track_filename_urlencoded}");
+            let src = format!("{track_asset_prefix}{format_dir}/{track_hash}/{track_filename_urlencoded}");
 
             let source_type = format.source_type();
              format!(r#"<source src="{src}" type="{source_type}">"#)
@@ -179,17 +197,18 @@ pub fn track_html(
     };
 
     let r_cover_micro = if let Some(filename) = track.cover_160_filename() {
-        format!(r#"<img aria-hidden="true" src="{filename}">"#)
+        format!(r#"<img aria-hidden="true" src="{track_asset_prefix}{filename}">"#)
     } else if let Some(filename) = release.cover_160_filename() {
-        format!(r#"<img aria-hidden="true" src="../{filename}">"#)
+        format!(r#"<img aria-hidden="true" src="{release_asset_prefix}{filename}">"#)
     } else {
         let filename = release.procedural_cover_120_filename_unchecked();
-        format!(r#"<img aria-hidden="true" class="procedural" src="../{filename}">"#)
+        format!(r#"<img aria-hidden="true" class="procedural" src="{release_asset_prefix}{filename}">"#)
     };
 
     let play_icon = icons::play(&translations.play);
+    let r_gain = normalization_gain_attribute(track);
     let r_track = formatdoc!(r#"
-        <div class="track" data-duration="{duration_seconds}">
+        <div class="track" data-duration="{duration_seconds}"{r_gain}>
             <button class="track_playback" tabindex="-1">
                 <span class="icon">
                     {play_icon}
@@ -228,6 +247,18 @@ pub fn track_html(
         primary_actions.push(download_link);
     }
 
+    if track.monetization_unlocks_downloads() {
+        let t_downloads_permalink = build.locale.downloads_permalink();
+        let page_hash = build.hash_with_salt(|hasher| {
+            release_slug.hash(hasher);
+            track_number.hash(hasher);
+            t_downloads_permalink.hash(hasher);
+        });
+
+        let href = format!("{t_downloads_permalink}/{page_hash}{index_suffix}");
+        primary_actions.push(monetization_unlock_link(&href, &translations.downloads));
+    }
+
     let artists = list_track_artists(build, index_suffix, root_prefix, catalog, Truncation::Pass, track);
     let artists_truncation = Truncation::Truncate {
         max_chars: 80,
@@ -288,6 +319,8 @@ pub fn track_html(
 
     let mut secondary_actions = Vec::new();
 
+    let mut r_copy_timecode_link = String::new();
+
     if track.copy_link {
         let (content_key, content_value) = match &build.base_url {
             Some(base_url) => {
@@ -298,6 +331,14 @@ pub fn track_html(
 
         let r_copy_link = copy_button(content_key, &content_value, &translations.copy_link);
         secondary_actions.push(r_copy_link);
+
+        r_copy_timecode_link = copy_timecode_link_button(&content_value, translations);
+    }
+
+    if let Some(base_url) = &build.base_url {
+        let track_url = base_url.join_index(build, format!("{release_slug}/{track_number}"));
+        let r_share_actions = share_actions(&track.share_buttons, &track.title(), translations, &track_url);
+        secondary_actions.extend(r_share_actions);
     }
 
     if track.embedding && build.base_url.is_some() {
@@ -338,11 +379,25 @@ pub fn track_html(
     let relative_waveforms = if track.theme.relative_waveforms { "" } else { "data-disable-relative-waveforms " };
     let track_duration = track.transcodes.borrow().source_meta.duration_seconds;
 
-    let cover = if let Some(described_image) = &track.cover {
+    let cover = if let Some(visual_loop) = &track.visual_loop {
+        // Only the poster is referenced here, player.js replaces it with the
+        // loop itself if the visitor's motion and data preferences allow it
+        let alt = match &visual_loop.description {
+            Some(description) => format!(r#"alt="{}""#, html_escape_inside_attribute(description)),
+            None => String::new()
+        };
+
+        formatdoc!(r#"
+            <div class="visual_loop" data-src="{track_asset_prefix}{VISUAL_LOOP_DIRNAME}/{VISUAL_LOOP_VIDEO_FILENAME}">
+                <img {alt} src="{track_asset_prefix}{VISUAL_LOOP_DIRNAME}/{VISUAL_LOOP_POSTER_FILENAME}">
+            </div>
+        "#)
+    } else if let Some(described_image) = &track.cover {
         track_cover_image(
             build,
             described_image,
-            root_prefix
+            root_prefix,
+            &track_asset_prefix
         )
     } else {
         release_cover_image(
@@ -368,8 +423,135 @@ pub fn track_html(
 
     let speed_controls = if track.speed_controls { SPEED_CONTROLS } else { "" };
     let r_volume_controls = volume_controls(translations);
+    let r_volume_normalization = if track.volume_normalization { volume_normalization_toggle(translations) } else { String::new() };
+
+    // Each chapter links to its start, which the player picks up as a
+    // request to seek there (see parseParams in player.js).
+    let r_chapters = if track.chapters.is_empty() {
+        String::new()
+    } else {
+        let r_items = track.chapters
+            .iter()
+            .filter(|chapter| chapter.seconds < duration_seconds)
+            .map(|chapter| {
+                let seconds = chapter.seconds as u32;
+                let time = format_time(chapter.seconds);
+                let title_escaped = html_escape_outside_attribute(&chapter.title);
+                format!(r##"<li><a href="#t={seconds}"><span class="time">{time}</span> {title_escaped}</a></li>"##)
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let t_chapters = &translations.chapters;
+
+        formatdoc!(r#"
+            <div class="page">
+                <div class="page_center">
+                    <div class="chapters">
+                        <h2>{t_chapters}</h2>
+                        <ol>
+                            {r_items}
+                        </ol>
+                    </div>
+                </div>
+            </div>
+        "#)
+    };
+
+    let r_lyrics = match &track.lyrics {
+        Some(lyrics) if lyrics.synced => {
+            let r_lines = lyrics.lines
+                .iter()
+                .map(|line| {
+                    let seconds = line.seconds.unwrap_or(0.0);
+                    let text_escaped = html_escape_outside_attribute(&line.text);
+                    format!(r#"<p data-time="{seconds}">{text_escaped}</p>"#)
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            let t_lyrics = &translations.lyrics;
+
+            formatdoc!(r#"
+                <div class="page">
+                    <div class="page_center">
+                        <div class="lyrics synced">
+                            <h2>{t_lyrics}</h2>
+                            {r_lines}
+                        </div>
+                    </div>
+                </div>
+            "#)
+        }
+        Some(lyrics) => {
+            let r_stanzas = lyrics.text()
+                .split("\n\n")
+                .filter(|stanza| !stanza.trim().is_empty())
+                .map(|stanza| {
+                    let stanza_escaped = html_escape_outside_attribute(stanza.trim()).replace('\n', "<br>");
+                    format!("<p>{stanza_escaped}</p>")
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            let t_lyrics = &translations.lyrics;
+
+            formatdoc!(r#"
+                <div class="page">
+                    <div class="page_center">
+                        <div class="lyrics">
+                            <h2>{t_lyrics}</h2>
+                            {r_stanzas}
+                        </div>
+                    </div>
+                </div>
+            "#)
+        }
+        None => String::new()
+    };
+
+    let r_transcript = match &track.transcript {
+        Some(transcript) => {
+            let r_cues = transcript.cues
+                .iter()
+                .map(|cue| {
+                    let text_escaped = html_escape_outside_attribute(&cue.text).replace('\n', "<br>");
+                    match cue.seconds {
+                        Some(seconds) => {
+                            let time = format_time(seconds);
+                            format!(r#"<p><span class="time">{time}</span> {text_escaped}</p>"#)
+                        }
+                        None => format!("<p>{text_escaped}</p>")
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            let filename = transcript.filename();
+            let t_download = &translations.download;
+            let t_transcript = &translations.transcript;
+
+            formatdoc!(r#"
+                <div class="page">
+                    <div class="page_center">
+                        <details class="transcript">
+                            <summary>{t_transcript}</summary>
+                            {r_cues}
+                            <a download href="{filename}">{t_download}</a>
+                        </details>
+                    </div>
+                </div>
+            "#)
+        }
+        None => String::new()
+    };
+
+    let r_content_warning = content_warning_interstitial(build, release);
+    let r_draft_banner = if release.draft { draft_banner(build) } else { String::new() };
 
     let body = formatdoc!(r##"
+        {r_content_warning}
+        {r_draft_banner}
         <div class="page">
             <div class="page_split">
                 <div class="cover">{cover}</div>
@@ -393,6 +575,9 @@ pub fn track_html(
                 </div>
             </div>
         </div>
+        {r_chapters}
+        {r_lyrics}
+        {r_transcript}
         {r_more}
         <div class="docked_player">
             <div class="timeline">
@@ -405,15 +590,18 @@ pub fn track_html(
                     {play_icon}
                 </button>
                 {speed_controls}
+                {r_volume_normalization}
                 {r_volume_controls}
                 <span class="track_info">
                     <span class="title_wrapper"></span>
                 </span>
+                <span class="error" role="alert"></span>
                 <span class="time">
                     <span class="current"></span>
                     <span>/</span>
                     <span class="total"></span>
                 </span>
+                {r_copy_timecode_link}
             </div>
         </div>
         <div aria-label="" class="docked_player_status" role="status"></div>
@@ -424,7 +612,7 @@ pub fn track_html(
     layout.add_breadcrumb(format!(r#"<a href="..{index_suffix}">{release_title_escaped}</a>"#));
 
     // TODO: Track-level unlisted properties?
-    if release.unlisted {
+    if release.unlisted || release.noindex || track.noindex {
         layout.no_indexing();
     }
 
@@ -438,7 +626,7 @@ pub fn track_html(
             }
 
             if let Some(described_image) = &track.cover {
-                let track_prefix = base_url.join_prefix(format!("{release_slug}/{track_number}"));
+                let track_prefix = build.asset_url().join_prefix(format!("{release_slug}/{track_number}"));
                 let opengraph_image = described_image
                     .borrow()
                     .cover_opengraph_image_unchecked(&track_prefix);
@@ -449,7 +637,7 @@ pub fn track_html(
                     meta.image_alt(description);
                 }
             } else if let Some(described_image) = &release.cover {
-                let release_prefix = base_url.join_prefix(release_slug);
+                let release_prefix = build.asset_url().join_prefix(release_slug);
                 let opengraph_image = described_image
                     .borrow()
                     .cover_opengraph_image_unchecked(&release_prefix);
@@ -464,16 +652,24 @@ pub fn track_html(
             // fallback here? (also applies for the same spot in the release
             // page render code)
 
+            if track.embedding {
+                let primary_streaming_format = track.streaming_quality.formats()[0];
+
+                meta.audio(OpenGraphAudio {
+                    content_type: primary_streaming_format.source_type().to_string(),
+                    player_url: base_url.join_index(build, format!("{release_slug}/embed/{track_number}")),
+                    url: m3u::track_url(build, release, track, track_number)
+                });
+            }
+
             layout.add_opengraph_meta(meta);
         }
     }
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &track.theme,
         &track_title
     )
 }
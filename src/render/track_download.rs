@@ -6,27 +6,18 @@ use std::hash::Hash;
 use indoc::formatdoc;
 
 use crate::{
-    Build,
-    Catalog,
     DownloadFormat,
-    Release,
-    TagMapping,
-    Track
+    TagMapping
 };
 use crate::util::{generic_hash, html_escape_outside_attribute};
 
-use super::Layout;
+use super::{Layout, TrackContext};
 use super::{compact_track_identifier, download_entry};
 
 /// The download page itself, providing direct links to the (zip) archive
 /// files and/or individual tracks download links.
-pub fn track_download_html(
-    build: &Build,
-    catalog: &Catalog,
-    release: &Release,
-    track: &Track,
-    track_number: usize
-) -> String {
+pub fn track_download_html(context: &TrackContext) -> String {
+    let &TrackContext { build, catalog, release, track, track_number } = context;
     let index_suffix = build.index_suffix();
     let root_prefix = "../../../../";
 
@@ -66,9 +57,14 @@ pub fn track_download_html(
         root_prefix,
         track,
         &track_link,
+        track_number,
         track_prefix
     );
 
+    let release_slug = &release.permalink.slug;
+    let release_asset_prefix = build.asset_prefix(release_prefix, root_prefix, release_slug);
+    let track_asset_prefix = build.asset_prefix(track_prefix, root_prefix, &format!("{release_slug}/{track_number}"));
+
     let extra_downloads = if track.cover.is_some() || release.cover.is_some() || (track.extra_downloads && !track.extras.is_empty()) {
         let cover_entry = if let Some(described_image) = &track.cover {
             let image_ref = described_image.borrow();
@@ -76,7 +72,7 @@ pub fn track_download_html(
             let filename = largest_cover_asset.target_filename();
 
             download_entry(
-                format!("{track_prefix}{filename}"),
+                format!("{track_asset_prefix}{filename}"),
                 &build.locale.translations.cover_image,
                 largest_cover_asset.filesize_bytes
             )
@@ -86,7 +82,7 @@ pub fn track_download_html(
             let filename = largest_cover_asset.target_filename();
 
             download_entry(
-                format!("{release_prefix}{filename}"),
+                format!("{release_asset_prefix}{filename}"),
                 &build.locale.translations.cover_image,
                 largest_cover_asset.filesize_bytes
             )
@@ -108,7 +104,7 @@ pub fn track_download_html(
                     let extra_filename_urlencoded = urlencoding::encode(&extra.sanitized_filename);
 
                     download_entry(
-                        format!("{track_prefix}extras/{extra_hash}/{extra_filename_urlencoded}"),
+                        format!("{track_asset_prefix}extras/{extra_hash}/{extra_filename_urlencoded}"),
                         &extra.sanitized_filename,
                         extra.file_meta.size
                     )
@@ -143,7 +139,7 @@ pub fn track_download_html(
                 let track_filename = format!(
                     "{basename}{extension}",
                     basename = track.asset_basename.as_ref().unwrap(),
-                    extension = download_format.as_audio_format().extension()
+                    extension = track.download_extension(download_format.as_audio_format())
                 );
 
                 let track_hash = build.hash_with_salt(|hasher| {
@@ -157,7 +153,7 @@ pub fn track_download_html(
                 let track_filename_urlencoded = urlencoding::encode(&track_filename);
 
                 download_entry(
-                    format!("{track_prefix}{format_dir}/{track_hash}/{track_filename_urlencoded}"),
+                    format!("{track_asset_prefix}{format_dir}/{track_hash}/{track_filename_urlencoded}"),
                     download_format.user_label(),
                     track.transcodes.borrow().get_unchecked(download_format.as_audio_format(), generic_hash(&tag_mapping)).asset.filesize_bytes
                 )
@@ -210,10 +206,8 @@ pub fn track_download_html(
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &track.theme,
         &page_title
     )
 }
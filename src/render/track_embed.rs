@@ -16,7 +16,7 @@ use crate::util::{html_escape_inside_attribute, html_escape_outside_attribute};
 
 use super::SPEED_CONTROLS;
 use super::EmbedLayout;
-use super::volume_controls;
+use super::{embed_support_button, volume_controls};
 
 pub fn track_embed_html(
     base_url: &SiteUrl,
@@ -27,6 +27,7 @@ pub fn track_embed_html(
 ) -> String {
     let release_prefix = "../../";
     let root_prefix = "../../../";
+    let release_asset_prefix = build.asset_prefix(release_prefix, root_prefix, &release.permalink.slug);
     let translations = &build.locale.translations;
 
     let audio_sources = track.streaming_quality
@@ -49,7 +50,7 @@ pub fn track_embed_html(
             });
 
             let source_type = format.source_type();
-            let src = format!("{release_prefix}{track_number}/{format_dir}/{track_hash}/{track_filename}");
+            let src = format!("{release_asset_prefix}{track_number}/{format_dir}/{track_hash}/{track_filename}");
 
             format!(r#"<source src="{src}" type="{source_type}">"#)
         })
@@ -74,6 +75,7 @@ pub fn track_embed_html(
     "#);
 
     let speed_controls = if track.speed_controls { SPEED_CONTROLS } else { "" };
+    let r_support_button = embed_support_button(base_url, build, release);
     let r_volume_controls = volume_controls(translations);
 
     let play_icon = icons::play(&translations.play);
@@ -100,6 +102,7 @@ pub fn track_embed_html(
                     <span>/</span>
                     <span class="total"></span>
                 </span>
+                {r_support_button}
             </div>
         </div>
     "##);
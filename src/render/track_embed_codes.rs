@@ -3,27 +3,18 @@
 
 use indoc::formatdoc;
 
-use crate::{
-    Build,
-    Catalog,
-    Release,
-    SiteUrl,
-    Track
-};
+use crate::SiteUrl;
 use crate::util::html_escape_outside_attribute;
 
-use super::Layout;
+use super::{Layout, TrackContext};
 use super::{compact_track_identifier, copy_button, embed_code};
 
 /// Renders the page that lets the visitor copy embed codes for the track.
 pub fn track_embed_codes_html(
     base_url: &SiteUrl,
-    build: &Build,
-    catalog: &Catalog,
-    release: &Release,
-    track: &Track,
-    track_number: usize
+    context: &TrackContext
 ) -> String {
+    let &TrackContext { build, catalog, release, track, track_number } = context;
     let index_suffix = build.index_suffix();
     let release_prefix = "../../";
     let root_prefix = "../../../";
@@ -46,6 +37,7 @@ pub fn track_embed_codes_html(
         root_prefix,
         track,
         &track_link,
+        track_number,
         track_prefix
     );
 
@@ -93,10 +85,8 @@ pub fn track_embed_codes_html(
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &track.theme,
         &page_title
     )
 }
@@ -6,28 +6,19 @@ use std::ops::Range;
 
 use indoc::formatdoc;
 
-use crate::{
-    Build,
-    Catalog,
-    Price,
-    Release,
-    Track
-};
+use crate::Price;
 use crate::util::html_escape_outside_attribute;
 
-use super::Layout;
+use super::{Layout, TrackContext};
 use super::compact_track_identifier;
 
 /// Renders content for pages found under /[release_permalink]/[track_number]/[purchase_permalink]/[hash]/index.html
 pub fn track_purchase_html(
-    build: &Build,
-    catalog: &Catalog,
+    context: &TrackContext,
     payment_info: &str,
-    price: &Price,
-    release: &Release,
-    track: &Track,
-    track_number: usize
+    price: &Price
 ) -> String {
+    let &TrackContext { build, catalog, release, track, track_number } = context;
     let index_suffix = build.index_suffix();
     let release_prefix = "../../../";
     let root_prefix = "../../../../";
@@ -98,7 +89,7 @@ pub fn track_purchase_html(
         r_price_input = price_input(&price.range, &placeholder);
     }
 
-    let t_downloads_permalink = &build.locale.translations.downloads_permalink;
+    let t_downloads_permalink = build.locale.downloads_permalink();
     let download_page_hash = build.hash_with_salt(|hasher| {
         release.permalink.slug.hash(hasher);
         track_number.hash(hasher);
@@ -210,6 +201,7 @@ pub fn track_purchase_html(
         root_prefix,
         track,
         &track_link,
+        track_number,
         track_prefix
     );
 
@@ -236,10 +228,8 @@ pub fn track_purchase_html(
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &track.theme,
         &page_title
     )
 }
@@ -5,26 +5,17 @@ use std::hash::Hash;
 
 use indoc::formatdoc;
 
-use crate::{
-    Build,
-    Catalog,
-    Release,
-    Track
-};
 use crate::util::html_escape_outside_attribute;
 
-use super::Layout;
+use super::{Layout, TrackContext};
 use super::compact_track_identifier;
 
 /// Renders content for pages found under /[release_permalink]/[track_number]/[unlock_permalink]/[hash]/index.html
 pub fn track_unlock_html(
-    build: &Build,
-    catalog: &Catalog,
-    release: &Release,
-    track: &Track,
-    track_number: usize,
+    context: &TrackContext,
     unlock_info: &Option<String>
 ) -> String {
+    let &TrackContext { build, catalog, release, track, track_number } = context;
     let index_suffix = build.index_suffix();
     let release_prefix = "../../../";
     let root_prefix = "../../../../";
@@ -39,7 +30,7 @@ pub fn track_unlock_html(
         .map(|text| text.to_string())
         .unwrap_or(build.locale.translations.default_unlock_info.to_string());
 
-    let t_unlock_permalink = &build.locale.translations.unlock_permalink;
+    let t_unlock_permalink = build.locale.unlock_permalink();
     let page_hash = build.hash_with_salt(|hasher| {
         release.permalink.slug.hash(hasher);
         track_number.hash(hasher);
@@ -47,10 +38,10 @@ pub fn track_unlock_html(
     });
 
     let t_download_code_seems_incorrect = &build.locale.translations.download_code_seems_incorrect;
-    let t_downloads_permalink = &build.locale.translations.downloads_permalink;
+    let t_downloads_permalink = build.locale.downloads_permalink();
     let t_enter_code_here = &build.locale.translations.enter_code_here;
     let t_unlock = &build.locale.translations.unlock;
-    let t_unlock_manual_instructions = &build.locale.translations.unlock_manual_instructions(&page_hash, index_suffix);
+    let t_unlock_manual_instructions = &build.locale.unlock_manual_instructions(&page_hash, index_suffix);
     let content = formatdoc!(r#"
         <div class="unlock_scripted">
             {custom_or_default_unlock_info}
@@ -97,6 +88,7 @@ pub fn track_unlock_html(
         root_prefix,
         track,
         &track_link,
+        track_number,
         track_prefix
     );
 
@@ -123,10 +115,8 @@ pub fn track_unlock_html(
 
     layout.render(
         &body,
-        build,
-        catalog,
+        context,
         root_prefix,
-        &track.theme,
         &page_title
     )
 }
@@ -0,0 +1,216 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Rendered pages of a release (release/track pages, download and embed
+//! pages, playlist files, etc.), cached as a whole under a signature that is
+//! computed from everything that goes into rendering them. When nothing
+//! changed since the last build, the pages are linked into the build
+//! directory from the cache instead of being rendered again.
+
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+use serde_derive::{Serialize, Deserialize};
+
+use crate::{Asset, AssetIntent, Build, Catalog, util};
+use crate::util::url_safe_base64;
+
+/// Pages are rendered into this directory inside the cache directory before
+/// they are moved into the cache and linked into the build directory.
+pub const RENDERING_DIRNAME: &str = "rendering";
+
+/// Only files with these extensions are kept as rendered pages (html pages,
+/// calendar files, json documents and playlists). Anything else that ends
+/// up in the rendering directory (assets are expected to be written outside
+/// of rendering, see Release::write_page_files) is moved into the build
+/// directory as is, without being cached.
+const RENDERED_EXTENSIONS: &[&str] = &["html", "ics", "json", "m3u"];
+
+/// A single rendered file, stored in the cache as an asset.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RenderedFile {
+    pub asset: Asset,
+    /// The path of the file in the build directory (relative to it)
+    pub path: PathBuf
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RenderedPages {
+    pub files: Vec<RenderedFile>,
+    /// This is a hash computed from everything that goes into rendering the
+    /// pages of a release (see Release::render_signature).
+    pub signature: u64
+}
+
+#[derive(Clone, Debug)]
+pub struct RenderedPagesRc {
+    rendered_pages: Rc<RefCell<RenderedPages>>,
+}
+
+/// Computes the part of the render signature that all releases share: The
+/// faircamp version, the build settings and the content of all manifests
+/// that influence rendering, as well as the catalog's homepage, which
+/// reflects the state of the catalog as a whole (releases, artists, theme,
+/// etc.).
+pub fn site_signature(build: &Build, catalog: &Catalog, index_html: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    env!("FAIRCAMP_VERSION_DISPLAY").hash(&mut hasher);
+
+    build.asset_hashes.browser_js.hash(&mut hasher);
+    build.asset_hashes.embeds_js.hash(&mut hasher);
+    build.asset_hashes.favicon_custom.hash(&mut hasher);
+    build.asset_hashes.player_js.hash(&mut hasher);
+    build.asset_hashes.player_legacy_js.hash(&mut hasher);
+    build.asset_hashes.site_css.hash(&mut hasher);
+
+    let mut theme_css = build.asset_hashes.theme_css.iter().collect::<Vec<(&String, &String)>>();
+    theme_css.sort();
+    theme_css.hash(&mut hasher);
+
+    build.base_url.as_ref().map(|base_url| base_url.prefix()).hash(&mut hasher);
+    build.clean_urls.hash(&mut hasher);
+    build.locale.language.hash(&mut hasher);
+    build.manifest_signature.hash(&mut hasher);
+    build.minify.hash(&mut hasher);
+    build.url_salt.hash(&mut hasher);
+
+    for artist in &catalog.featured_artists {
+        artist.borrow().permalink.slug.hash(&mut hasher);
+    }
+
+    for release in &catalog.releases {
+        let release_ref = release.borrow();
+        release_ref.permalink.slug.hash(&mut hasher);
+        release_ref.title.hash(&mut hasher);
+    }
+
+    index_html.hash(&mut hasher);
+
+    hasher.finish()
+}
+
+impl RenderedPages {
+    /// Increase version on each change to the data layout of [RenderedPages].
+    /// This automatically informs the cache not to try to deserialize
+    /// manifests that hold old, incompatible data.
+    pub const CACHE_SERIALIZATION_KEY: &'static str = "pages2";
+
+    pub fn deserialize_cached(path: &Path) -> Option<RenderedPages> {
+        match fs::read(path) {
+            Ok(bytes) => bincode::deserialize::<RenderedPages>(&bytes).ok(),
+            Err(_) => None
+        }
+    }
+
+    /// Removes the files from the cache again (those linked into the build
+    /// directory stay intact), so that the pages are rendered anew next time.
+    pub fn discard(&mut self, cache_dir: &Path) {
+        for file in self.files.drain(..) {
+            let _ = fs::remove_file(cache_dir.join(&file.asset.filename));
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Links all files from the cache into the build directory.
+    pub fn link_into_build_dir(&mut self, build: &Build) {
+        for file in self.files.iter_mut() {
+            let target = build.build_dir.join(&file.path);
+            util::ensure_dir_all(target.parent().unwrap());
+            util::hard_link_or_copy(build.cache_dir.join(&file.asset.filename), target);
+            file.asset.unmark_stale();
+        }
+    }
+
+    pub fn manifest_path(&self, cache_dir: &Path) -> PathBuf {
+        let manifest_filename = format!("{}.{}.bincode", url_safe_base64(self.signature), RenderedPages::CACHE_SERIALIZATION_KEY);
+        cache_dir.join(manifest_filename)
+    }
+
+    pub fn mark_all_stale(&mut self, timestamp: &DateTime<Utc>) {
+        for file in self.files.iter_mut() {
+            file.asset.mark_stale(timestamp);
+        }
+    }
+
+    pub fn new(signature: u64) -> RenderedPages {
+        RenderedPages {
+            files: Vec::new(),
+            signature
+        }
+    }
+
+    pub fn persist_to_cache(&self, cache_dir: &Path) {
+        let serialized = bincode::serialize(self).unwrap();
+        fs::write(self.manifest_path(cache_dir), serialized).unwrap();
+    }
+
+    /// Calls render with the build directory temporarily pointing to a
+    /// directory inside the cache, moves the pages that were rendered there
+    /// into the cache and then links them into the actual build directory.
+    pub fn render(&mut self, build: &mut Build, render: impl FnOnce(&mut Build)) {
+        let rendering_dir = build.cache_dir.join(RENDERING_DIRNAME);
+
+        util::ensure_empty_dir(&rendering_dir);
+
+        let build_dir = mem::replace(&mut build.build_dir, rendering_dir.clone());
+        render(build);
+        build.build_dir = build_dir;
+
+        let mut paths = Vec::new();
+        util::collect_files(&rendering_dir, &rendering_dir, &mut paths);
+
+        let (page_paths, other_paths): (Vec<PathBuf>, Vec<PathBuf>) = paths
+            .into_iter()
+            .partition(|path| {
+                path.extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| RENDERED_EXTENSIONS.contains(&extension))
+            });
+
+        for path in other_paths {
+            let target = build.build_dir.join(&path);
+            util::ensure_dir_all(target.parent().unwrap());
+            fs::rename(rendering_dir.join(&path), target).unwrap();
+        }
+
+        self.files = page_paths
+            .into_iter()
+            .map(|path| {
+                let filename = format!("{}.page", util::uid());
+                fs::rename(rendering_dir.join(&path), build.cache_dir.join(&filename)).unwrap();
+                let asset = Asset::new(build, filename, AssetIntent::Deliverable);
+                RenderedFile { asset, path }
+            })
+            .collect();
+
+        let _ = fs::remove_dir_all(&rendering_dir);
+
+        self.link_into_build_dir(build);
+    }
+}
+
+impl RenderedPagesRc {
+    pub fn borrow(&self) -> Ref<'_, RenderedPages> {
+        self.rendered_pages.borrow()
+    }
+
+    pub fn borrow_mut(&self) -> RefMut<'_, RenderedPages> {
+        self.rendered_pages.borrow_mut()
+    }
+
+    pub fn new(rendered_pages: RenderedPages) -> RenderedPagesRc {
+        RenderedPagesRc {
+            rendered_pages: Rc::new(RefCell::new(rendered_pages))
+        }
+    }
+}
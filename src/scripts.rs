@@ -7,11 +7,24 @@ mod browser;
 mod clipboard;
 mod embeds;
 mod player;
+mod player_legacy;
+mod search;
 
 pub fn generate(build: &mut Build, catalog: &Catalog) {
-    browser::generate_browser_js(build, catalog);
-    clipboard::generate_clipboard_js(build);
-    player::generate_player_js(build);
+    // Embeds-only builds have no site pages that would use these
+    if !build.embeds_only {
+        browser::generate_browser_js(build, catalog);
+        clipboard::generate_clipboard_js(build);
+        player::generate_player_js(build);
+
+        if catalog.legacy_support {
+            player_legacy::generate_player_legacy_js(build);
+        }
+
+        if catalog.search {
+            search::generate_search_js(build, catalog);
+        }
+    }
 
     if build.embeds_requested {
         embeds::generate_embeds_js(build);
@@ -6,7 +6,7 @@ use std::fs;
 use indoc::formatdoc;
 
 use crate::{ArtistRc, Build, Catalog};
-use crate::TRACK_NUMBERS;
+use crate::{minify, TRACK_NUMBERS};
 use crate::util::url_safe_hash_base64;
 
 use super::js_escape_inside_single_quoted_string;
@@ -51,7 +51,7 @@ pub fn generate_browser_js(build: &mut Build, catalog: &Catalog) {
                         String::new()
                     };
 
-                    let track_title_escaped = js_escape_inside_single_quoted_string(&track.title());
+                    let track_title_escaped = js_escape_inside_single_quoted_string(&track.title_disambiguated());
 
                     let r_artists = if catalog.label_mode {
                         let joined = track.artists
@@ -100,7 +100,7 @@ pub fn generate_browser_js(build: &mut Build, catalog: &Catalog) {
                 let src = release_ref.procedural_cover_120_filename_unchecked();
                 format!("coverProcedural: '{src}',")
             };
-            let release_title_escaped = js_escape_inside_single_quoted_string(&release_ref.title);
+            let release_title_escaped = js_escape_inside_single_quoted_string(&release_ref.title_disambiguated());
 
             formatdoc!(r#"
                 {{
@@ -167,6 +167,10 @@ pub fn generate_browser_js(build: &mut Build, catalog: &Catalog) {
 
     js.push_str(BROWSER_JS);
 
+    if build.minify {
+        js = minify::js(&js);
+    }
+
     build.asset_hashes.browser_js = Some(url_safe_hash_base64(&js));
 
     fs::write(
@@ -5,7 +5,7 @@ use std::fs;
 
 use indoc::formatdoc;
 
-use crate::Build;
+use crate::{Build, minify};
 use crate::util::url_safe_hash_base64;
 
 const EMBEDS_JS: &str = include_str!(env!("FAIRCAMP_EMBEDS_JS"));
@@ -33,6 +33,10 @@ pub fn generate_embeds_js(build: &mut Build) {
 
     js.push_str(EMBEDS_JS);
 
+    if build.minify {
+        js = minify::js(&js);
+    }
+
     build.asset_hashes.embeds_js = Some(url_safe_hash_base64(&js));
 
     fs::write(
@@ -5,7 +5,7 @@ use std::fs;
 
 use indoc::formatdoc;
 
-use crate::Build;
+use crate::{Build, minify};
 use crate::util::url_safe_hash_base64;
 
 use super::js_escape_inside_single_quoted_string;
@@ -15,12 +15,17 @@ const PLAYER_JS_FILENAME: &str = "player.js";
 
 pub fn generate_player_js(build: &mut Build) {
     let t_listen = &build.locale.translations.listen;
+    let t_move_down = js_escape_inside_single_quoted_string(&build.locale.translations.move_down);
+    let t_move_up = js_escape_inside_single_quoted_string(&build.locale.translations.move_up);
     let t_mute = &build.locale.translations.mute;
     let t_pause = &build.locale.translations.pause;
     let t_playback_position = &build.locale.translations.playback_position;
     let t_player_closed = &build.locale.translations.player_closed;
+    let t_player_error_retrying = js_escape_inside_single_quoted_string(&build.locale.translations.player_error_retrying);
+    let t_player_error_unavailable = js_escape_inside_single_quoted_string(&build.locale.translations.player_error_unavailable);
     let t_player_open_playing_xxx = js_escape_inside_single_quoted_string(&build.locale.translations.player_open_playing_xxx);
     let t_player_open_with_xxx = js_escape_inside_single_quoted_string(&build.locale.translations.player_open_with_xxx);
+    let t_remove_from_queue = js_escape_inside_single_quoted_string(&build.locale.translations.remove_from_queue);
     let t_unmute = &build.locale.translations.unmute;
     let t_volume = &build.locale.translations.volume;
     let t_xxx_hours = &build.locale.translations.xxx_hours;
@@ -29,12 +34,17 @@ pub fn generate_player_js(build: &mut Build) {
     let mut js = formatdoc!("
         const PLAYER_JS_T = {{
             listen: '{t_listen}',
+            moveDown: '{t_move_down}',
+            moveUp: '{t_move_up}',
             mute: '{t_mute}',
             pause: '{t_pause}',
             playbackPosition: '{t_playback_position}',
             playerClosed: '{t_player_closed}',
+            playerErrorRetrying: '{t_player_error_retrying}',
+            playerErrorUnavailable: '{t_player_error_unavailable}',
             playerOpenPlayingXxx: title => '{t_player_open_playing_xxx}'.replace('{{title}}', title),
             playerOpenWithXxx: title => '{t_player_open_with_xxx}'.replace('{{title}}', title),
+            removeFromQueue: '{t_remove_from_queue}',
             unmute: '{t_unmute}',
             volume: '{t_volume}',
             xxxHours: hours => '{t_xxx_hours}'.replace('{{xxx}}', hours),
@@ -45,6 +55,10 @@ pub fn generate_player_js(build: &mut Build) {
 
     js.push_str(PLAYER_JS);
 
+    if build.minify {
+        js = minify::js(&js);
+    }
+
     build.asset_hashes.player_js = Some(url_safe_hash_base64(&js));
 
     fs::write(
@@ -0,0 +1,43 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::fs;
+
+use indoc::formatdoc;
+
+use crate::{Build, minify};
+use crate::util::url_safe_hash_base64;
+
+use super::js_escape_inside_single_quoted_string;
+
+const PLAYER_LEGACY_JS: &str = include_str!(env!("FAIRCAMP_PLAYER_LEGACY_JS"));
+const PLAYER_LEGACY_JS_FILENAME: &str = "player_legacy.js";
+
+/// The reduced player loaded by older browsers instead of player.js (only
+/// generated when the catalog's legacy_support option is enabled). Unlike
+/// in player.js, the translations are provided in older (ES5) syntax.
+pub fn generate_player_legacy_js(build: &mut Build) {
+    let t_listen = js_escape_inside_single_quoted_string(&build.locale.translations.listen);
+    let t_pause = js_escape_inside_single_quoted_string(&build.locale.translations.pause);
+    let mut js = formatdoc!("
+        var PLAYER_LEGACY_JS_T = {{
+            listen: '{t_listen}',
+            pause: '{t_pause}'
+        }};
+    ");
+
+    js.push_str(PLAYER_LEGACY_JS);
+
+    if build.minify {
+        js = minify::js(&js);
+    }
+
+    build.asset_hashes.player_legacy_js = Some(url_safe_hash_base64(&js));
+
+    fs::write(
+        build.build_dir.join(PLAYER_LEGACY_JS_FILENAME),
+        js
+    ).unwrap();
+
+    build.reserve_filename(PLAYER_LEGACY_JS_FILENAME);
+}
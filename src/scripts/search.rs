@@ -0,0 +1,124 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::fs;
+
+use indoc::formatdoc;
+
+use crate::{ArtistRc, Build, Catalog, TRACK_NUMBERS};
+use crate::minify;
+use crate::util::url_safe_hash_base64;
+
+use super::js_escape_inside_single_quoted_string;
+
+const SEARCH_JS: &str = include_str!(env!("FAIRCAMP_SEARCH_JS"));
+const SEARCH_JS_FILENAME: &str = "search.js";
+
+fn artist_names(artists: &[ArtistRc]) -> String {
+    artists
+        .iter()
+        .map(|artist| artist.borrow().name.clone())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// A compact index entry with single letter keys (see FIELDS in search.js),
+/// empty fields are omitted. All whitespace (including line breaks) is
+/// collapsed to single spaces.
+fn entry(fields: &[(&str, &str)], url: &str) -> String {
+    let r_fields = fields
+        .iter()
+        .filter(|(_key, value)| !value.trim().is_empty())
+        .map(|(key, value)| {
+            let collapsed = value.split_whitespace().collect::<Vec<&str>>().join(" ");
+            format!("{key}:'{}'", js_escape_inside_single_quoted_string(&collapsed))
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+
+    format!("{{{r_fields},u:'{url}'}}")
+}
+
+/// Writes search.js, which holds the search index (titles, artists,
+/// keywords and synopses of all public releases, their tracks and the
+/// featured artists) along with the fuzzy matcher used on the search page.
+pub fn generate_search_js(build: &mut Build, catalog: &Catalog) {
+    let mut entries = Vec::new();
+
+    for release in &catalog.public_releases() {
+        let release_ref = release.borrow();
+        let release_slug = &release_ref.permalink.slug;
+        let release_title = release_ref.title_disambiguated();
+
+        let mut keywords = Vec::new();
+        keywords.extend(release_ref.imprint.clone());
+        keywords.extend(release_ref.region.clone());
+        keywords.extend(release_ref.podcast_categories.clone());
+
+        entries.push(entry(
+            &[
+                ("a", &artist_names(&release_ref.main_artists)),
+                ("k", &keywords.join(" ")),
+                ("s", release_ref.synopsis.as_deref().unwrap_or("")),
+                ("t", &release_title)
+            ],
+            &format!("{release_slug}/")
+        ));
+
+        for (track, track_number) in release_ref.tracks.iter().zip(TRACK_NUMBERS) {
+            entries.push(entry(
+                &[
+                    ("a", &artist_names(&track.artists)),
+                    ("r", &release_title),
+                    ("t", &track.title())
+                ],
+                &format!("{release_slug}/{track_number}/")
+            ));
+        }
+    }
+
+    for artist in &catalog.featured_artists {
+        let artist_ref = artist.borrow();
+
+        if artist_ref.unlisted {
+            continue;
+        }
+
+        entries.push(entry(
+            &[
+                ("s", artist_ref.synopsis.as_deref().unwrap_or("")),
+                ("t", &artist_ref.name)
+            ],
+            &format!("{}/", artist_ref.permalink.slug)
+        ));
+    }
+
+    let r_entries = entries.join(",\n");
+
+    let t_nothing_found_for_xxx = js_escape_inside_single_quoted_string(&build.locale.translations.nothing_found_for_xxx);
+    let t_showing_xxx_results_for_xxx = js_escape_inside_single_quoted_string(&build.locale.translations.showing_xxx_results_for_xxx);
+    let mut js = formatdoc!(r#"
+        const SEARCH_JS_T = {{
+            nothingFoundForXxx: query => '{t_nothing_found_for_xxx}'.replace('{{query}}', query),
+            showingXxxResultsForXxx: (count, query) => '{t_showing_xxx_results_for_xxx}'.replace('{{count}}', count).replace('{{query}}', query)
+        }};
+        const SEARCH_INDEX = [
+        {r_entries}
+        ];
+    "#);
+
+    js.push_str(SEARCH_JS);
+
+    if build.minify {
+        js = minify::js(&js);
+    }
+
+    build.asset_hashes.search_js = Some(url_safe_hash_base64(&js));
+
+    fs::write(
+        build.build_dir.join(SEARCH_JS_FILENAME),
+        js
+    ).unwrap();
+
+    build.reserve_filename(SEARCH_JS_FILENAME);
+}
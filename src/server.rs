@@ -3,10 +3,13 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use actix_files::Files;
-use actix_web::{App, HttpServer};
+use actix_web::{App, HttpResponse, HttpServer, web};
+use std::fs;
 use std::net::{IpAddr, Ipv4Addr};
 use std::path::{Path, PathBuf};
 
+use crate::dashboard::{DASHBOARD_CACHE_FILENAME, DASHBOARD_PATH};
+
 const DEFAULT_PREVIEW_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
 const DEFAULT_PREVIEW_PORT: u16 = 8080;
 
@@ -16,11 +19,37 @@ const DEFAULT_PREVIEW_PORT: u16 = 8080;
 /// than all ports taken.
 const MAX_PORT_ATTEMPTS: u16 = 10;
 
+/// Besides the build directory, the catalog health dashboard is served
+/// under DASHBOARD_PATH, read anew from the cache directory on each request
+/// (see the dashboard module).
 #[actix_web::main]
-pub async fn serve_preview(build_dir: &Path, ip_requested: Option<IpAddr>, port_requested: Option<u16>) {
+pub async fn serve_preview(
+    build_dir: &Path,
+    cache_dir: &Path,
+    ip_requested: Option<IpAddr>,
+    port_requested: Option<u16>
+) {
+    let dashboard_path = cache_dir.join(DASHBOARD_CACHE_FILENAME);
+
     let bind_server = |build_dir_moving: PathBuf, ip: IpAddr, port: u16| {
+        let dashboard_path = dashboard_path.clone();
+
         HttpServer::new(move || {
+            let dashboard_path = dashboard_path.clone();
+
             App::new()
+                .route(DASHBOARD_PATH, web::get().to(move || {
+                    let dashboard_path = dashboard_path.clone();
+                    async move {
+                        match fs::read_to_string(&dashboard_path) {
+                            Ok(html) => HttpResponse::Ok()
+                                .content_type("text/html; charset=utf-8")
+                                .body(html),
+                            Err(_) => HttpResponse::NotFound()
+                                .body("The catalog health dashboard is not available (yet), it is written after each build.")
+                        }
+                    }
+                }))
                 .service(
                     Files::new("/", &build_dir_moving)
                         .redirect_to_slash_directory()
@@ -67,6 +96,7 @@ pub async fn serve_preview(build_dir: &Path, ip_requested: Option<IpAddr>, port_
     let url = format!("http://{ip}:{port_bound}");
 
     println!("Serving the site preview at {url} (open this address in your browser)");
+    println!("The catalog health dashboard is available at {url}{DASHBOARD_PATH}");
     println!("Press Ctrl+C to shut down the preview server (e.g. to perform another build)");
 
     let open_browser = || async {
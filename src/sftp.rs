@@ -0,0 +1,278 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Deployment to hosts that only offer SFTP (and not rsync over SSH), used
+//! when the deploy destination is given as an sftp:// url. This relies on the
+//! sftp client of OpenSSH (run in batch mode), so the same ssh configuration,
+//! keys and agent are used as with rsync.
+//!
+//! Only changed files are uploaded: After each deploy we keep a state file
+//! in the cache directory that lists the size and sha256 checksum of every
+//! deployed file. On the next deploy this is compared against the files in
+//! the build directory, then only new and changed files are uploaded and
+//! files that are not part of the site anymore are removed. The state never
+//! leaves the local machine, as the paths it lists include the secret ones
+//! (e.g. those of download and unlock pages).
+//!
+//! Unlike rsync (by default) we compare the content of the files and not
+//! their modification time: Many files in the build directory are written
+//! anew on every build even when their content did not change (e.g. page
+//! assets, or pages whose rendering was not cached), which would otherwise
+//! get uploaded again each time.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Output};
+use url::Url;
+
+use crate::bagit::sha256_file;
+use crate::util;
+use crate::util::url_safe_hash_base64;
+
+/// Kept in the cache directory, followed by a hash of the destination (so
+/// that deploying the same site to several destinations works as well)
+const DEPLOY_STATE_FILENAME_PREFIX: &str = "sftp_deploy_state";
+
+/// Where the sftp url points to, e.g. for "sftp://alice@example.com:2222/~/www"
+/// this is user "alice", host "example.com", port 2222 and path "www".
+struct SftpDestination {
+    host: String,
+    /// A path starting with "~/" in the url is relative to the home
+    /// directory of the user, otherwise the path is absolute
+    path: String,
+    port: Option<u16>,
+    user: Option<String>
+}
+
+impl SftpDestination {
+    fn parse(destination: &str) -> Result<SftpDestination, String> {
+        let url = Url::parse(destination)
+            .map_err(|err| format!("The deploy destination '{destination}' is not a valid sftp url ({err})"))?;
+
+        let Some(host) = url.host_str() else {
+            return Err(format!("The deploy destination '{destination}' contains no host, it should look like this: sftp://user@example.com/var/www/music"));
+        };
+
+        // E.g. spaces in the path are percent-encoded in the url
+        let url_path = urlencoding::decode(url.path())
+            .map(|decoded| decoded.into_owned())
+            .unwrap_or_else(|_| url.path().to_string());
+
+        let path = match url_path.strip_prefix("/~/") {
+            Some(relative_path) => relative_path.to_string(),
+            None if url_path == "/~" => String::from("."),
+            None => url_path
+        };
+
+        let user = match url.username() {
+            "" => None,
+            username => Some(username.to_string())
+        };
+
+        Ok(SftpDestination {
+            host: host.to_string(),
+            path,
+            port: url.port(),
+            user
+        })
+    }
+
+    fn target(&self) -> String {
+        match &self.user {
+            Some(user) => format!("{user}@{}", self.host),
+            None => self.host.clone()
+        }
+    }
+}
+
+/// Size and sha256 checksum of a file, e.g. "48213-9f86d081884c7d65…"
+fn fingerprint(path: &Path) -> Result<String, String> {
+    let (sha256, size) = sha256_file(path).map_err(|err| err.to_string())?;
+
+    Ok(format!("{size}-{sha256}"))
+}
+
+/// The parent directories of all given paths (e.g. "a" and "a/b" for the
+/// path "a/b/c.html"), without the root directory itself.
+fn parent_dirs(paths: &BTreeMap<String, String>) -> BTreeSet<String> {
+    let mut dirs = BTreeSet::new();
+
+    for path in paths.keys() {
+        let mut parent = Path::new(path).parent();
+
+        while let Some(dir) = parent {
+            if dir.as_os_str().is_empty() { break; }
+            dirs.insert(remote_path(dir));
+            parent = dir.parent();
+        }
+    }
+
+    dirs
+}
+
+/// Quotes an argument for an sftp batch file
+fn quote(argument: &str) -> String {
+    let escaped = argument
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+
+    format!("\"{escaped}\"")
+}
+
+/// Parses the deploy state file, each line of which holds the fingerprint
+/// (see [fingerprint]) and path of a deployed file, separated by two spaces.
+fn read_deploy_state(path: &Path) -> BTreeMap<String, String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return BTreeMap::new();
+    };
+
+    content
+        .lines()
+        .filter_map(|line| line.split_once("  "))
+        .map(|(fingerprint, path)| (path.to_string(), fingerprint.to_string()))
+        .collect()
+}
+
+/// Paths on the server are always separated by forward slashes,
+/// independent of the platform faircamp runs on.
+fn remote_path(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+fn run_batch(destination: &SftpDestination, batch_path: &Path) -> Result<(), String> {
+    let mut command = Command::new("sftp");
+
+    command.arg("-b");
+    command.arg(batch_path);
+
+    if let Some(port) = destination.port {
+        command.arg("-P");
+        command.arg(port.to_string());
+    }
+
+    command.arg(destination.target());
+
+    info!("Deploying with command {:?}", command);
+
+    match command.output() {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(())
+            } else {
+                let sftp_output = sftp_debug_output(output);
+                Err(format!("The sftp child process returned an error exit code.\n\n{}", sftp_output))
+            }
+        }
+        Err(_) => Err("The sftp child process could not be executed (is an OpenSSH sftp client installed?).".to_string())
+    }
+}
+
+fn sftp_debug_output(output: Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    format!("stderr: {}\n\nstdout: {}", stderr, stdout)
+}
+
+/// The batch file is written to the cache directory and removed again after
+/// the deploy, the deploy state stays there for the next deploy.
+pub fn sync(source_dir: &Path, cache_dir: &Path, destination: &str) -> Result<(), String> {
+    let state_path = cache_dir.join(format!("{DEPLOY_STATE_FILENAME_PREFIX}_{}", url_safe_hash_base64(&destination)));
+    let destination = SftpDestination::parse(destination)?;
+
+    let batch_path = cache_dir.join("sftp_batch");
+
+    let result = sync_with_batch_file(
+        source_dir,
+        &destination,
+        &batch_path,
+        &state_path
+    );
+
+    let _ = fs::remove_file(&batch_path);
+
+    result
+}
+
+fn sync_with_batch_file(
+    source_dir: &Path,
+    destination: &SftpDestination,
+    batch_path: &Path,
+    state_path: &Path
+) -> Result<(), String> {
+    // Without a state (first deploy, or the cache was cleared) everything is
+    // uploaded, files on the server that are not part of the site anymore
+    // then can't be known and are left in place.
+    let previous_state = read_deploy_state(state_path);
+
+    let mut paths: Vec<PathBuf> = Vec::new();
+    util::collect_files(source_dir, source_dir, &mut paths);
+
+    let mut state = BTreeMap::new();
+
+    for path in &paths {
+        let fingerprint = fingerprint(&source_dir.join(path))
+            .map_err(|err| format!("The file '{}' could not be read for the deploy ({err})", path.display()))?;
+        state.insert(remote_path(path), fingerprint);
+    }
+
+    let previous_dirs = parent_dirs(&previous_state);
+    let dirs = parent_dirs(&state);
+
+    let mut batch = Vec::new();
+
+    batch.push(format!("-mkdir {}", quote(&destination.path)));
+    batch.push(format!("cd {}", quote(&destination.path)));
+
+    // Sorted, parent directories come before their subdirectories
+    for dir in dirs.difference(&previous_dirs) {
+        batch.push(format!("-mkdir {}", quote(dir)));
+    }
+
+    let mut uploads = 0;
+
+    for (path, fingerprint) in &state {
+        if previous_state.get(path) != Some(fingerprint) {
+            let local_path = source_dir.join(path);
+            batch.push(format!("put {} {}", quote(&local_path.to_string_lossy()), quote(path)));
+            uploads += 1;
+        }
+    }
+
+    let mut removals = 0;
+
+    for path in previous_state.keys() {
+        if !state.contains_key(path) {
+            batch.push(format!("-rm {}", quote(path)));
+            removals += 1;
+        }
+    }
+
+    // Reversed, subdirectories are removed before their parent directories
+    for dir in previous_dirs.difference(&dirs).rev() {
+        batch.push(format!("-rmdir {}", quote(dir)));
+    }
+
+    info!("Uploading {uploads} new or changed files and removing {removals} files that are not part of the site anymore ({} files are unchanged)", state.len() - uploads);
+
+    let batch_content = batch.join("\n") + "\n";
+    fs::write(batch_path, batch_content).unwrap();
+    run_batch(destination, batch_path)?;
+
+    // The new state is only written once everything went through, so an
+    // interrupted deploy is simply picked up again on the next attempt.
+    let state_content = state
+        .iter()
+        .map(|(path, fingerprint)| format!("{fingerprint}  {path}\n"))
+        .collect::<String>();
+    fs::write(state_path, state_content).unwrap();
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use translations::Translations;
+
+/// Services for which share buttons can be displayed on pages. These are
+/// all implemented through plain links ("share intents") that are opened
+/// in the browser, i.e. no third party scripts are ever included and no
+/// tracking of any kind takes place.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShareButton {
+    Email,
+    /// Mastodon has no central instance, so visitors are asked for the
+    /// domain of their own instance when they click the button.
+    Mastodon,
+    Telegram,
+    Whatsapp
+}
+
+impl ShareButton {
+    pub const ALL: [ShareButton; 4] = [
+        ShareButton::Email,
+        ShareButton::Mastodon,
+        ShareButton::Telegram,
+        ShareButton::Whatsapp
+    ];
+
+    pub fn from_manifest_key(key: &str) -> Option<ShareButton> {
+        match key {
+            "email" => Some(ShareButton::Email),
+            "mastodon" => Some(ShareButton::Mastodon),
+            "telegram" => Some(ShareButton::Telegram),
+            "whatsapp" => Some(ShareButton::Whatsapp),
+            _ => None
+        }
+    }
+
+    pub fn label(&self, translations: &Translations) -> String {
+        match self {
+            ShareButton::Email => translations.share_via_email.to_string(),
+            ShareButton::Mastodon => translations.share_on_xxx("Mastodon"),
+            ShareButton::Telegram => translations.share_on_xxx("Telegram"),
+            ShareButton::Whatsapp => translations.share_on_xxx("WhatsApp")
+        }
+    }
+
+    /// Url-encoded text that is shared (title and url of the shared page).
+    pub fn text_urlencoded(title: &str, url: &str) -> String {
+        urlencoding::encode(&format!("{title} {url}")).to_string()
+    }
+
+    /// Returns the share intent url for the given title and url of the
+    /// page that is shared. For Mastodon there is no static url (as it
+    /// requires the visitor's instance), so None is returned.
+    pub fn url(&self, title: &str, url: &str) -> Option<String> {
+        let title_urlencoded = urlencoding::encode(title);
+        let url_urlencoded = urlencoding::encode(url);
+
+        match self {
+            ShareButton::Email => Some(format!("mailto:?subject={title_urlencoded}&body={url_urlencoded}")),
+            ShareButton::Mastodon => None,
+            ShareButton::Telegram => Some(format!("https://t.me/share/url?url={url_urlencoded}&text={title_urlencoded}")),
+            ShareButton::Whatsapp => Some(format!("https://wa.me/?text={}", ShareButton::text_urlencoded(title, url)))
+        }
+    }
+}
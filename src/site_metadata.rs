@@ -2,11 +2,16 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use std::mem;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::SourceHash;
+use crate::{Build, SourceHash, util};
 
-#[derive(Debug)]
+/// A site asset with this filename is automatically included as the last
+/// stylesheet on every page, so that it takes precedence over all styles
+/// that faircamp generates (including the theme's custom properties).
+pub const USER_CSS_FILENAME: &str = "user.css";
+
+#[derive(Clone, Debug)]
 pub struct SiteAsset {
     pub filename: String,
     pub hash: SourceHash,
@@ -16,14 +21,20 @@ pub struct SiteAsset {
 /// Represents site metadata - mostly arbitrary html that is injected into the
 /// <head>…</head> of the page, which may contain dynamically interpolatable
 /// references to filenames - in a parsed, tokenized form.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SiteMetadata {
     tokens: Vec<Token>
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 enum Token {
     Markup(String),
+    /// A page asset is written right next to the page that references it,
+    /// so unlike site assets it is referenced without the root prefix.
+    ResolvedPageAsset {
+        filename: String,
+        hash: String
+    },
     ResolvedSiteAsset {
         filename: String,
         hash: String
@@ -96,6 +107,9 @@ impl SiteMetadata {
             .iter()
             .map(|token| {
                 match token {
+                    Token::ResolvedPageAsset { filename, hash } => {
+                        format!("{filename}?{hash}")
+                    }
                     Token::ResolvedSiteAsset { filename, hash } => {
                         format!("{root_prefix}{filename}?{hash}")
                     }
@@ -109,13 +123,25 @@ impl SiteMetadata {
 
     /// Returns Ok(()) if all referenced filenames in the site metadata can be
     /// resolved, otherwise returns an Err(Vec<…>) containing all missing
-    /// filenames.
-    pub fn resolve_filename_references(&mut self, site_assets: &[SiteAsset]) -> Result<(), Vec<String>> {
+    /// filenames. Page assets (only passed when resolving the page_metadata
+    /// of an artist, release or track) take precedence over site assets.
+    pub fn resolve_filename_references(
+        &mut self,
+        page_assets: &[SiteAsset],
+        site_assets: &[SiteAsset]
+    ) -> Result<(), Vec<String>> {
         let mut missing_files = Vec::new();
 
         for token in self.tokens.iter_mut() {
             if let Token::UnresolvedSiteAsset(filename) = token {
-                if let Some(site_asset) = site_assets
+                if let Some(page_asset) = page_assets
+                    .iter()
+                    .find(|page_asset| page_asset.filename == *filename) {
+                    *token = Token::ResolvedPageAsset {
+                        filename: mem::take(filename),
+                        hash: page_asset.hash.as_url_safe_base64(),
+                    }
+                } else if let Some(site_asset) = site_assets
                     .iter()
                     .find(|site_asset| site_asset.filename == *filename) {
                     *token = Token::ResolvedSiteAsset {
@@ -134,3 +160,18 @@ impl SiteMetadata {
         }
     }
 }
+
+/// Writes the page assets of an artist, release or track into the directory
+/// of its page.
+pub fn write_page_assets(build: &mut Build, dir: &Path, page_assets: &[SiteAsset]) {
+    for page_asset in page_assets {
+        let target = dir.join(&page_asset.filename);
+
+        if target.exists() {
+            let error = format!("The page asset {} could not be written to {} because a file generated by faircamp already exists there, please rename the page asset.", page_asset.path.display(), target.display());
+            build.error(&error);
+        } else {
+            util::hard_link_or_copy(&page_asset.path, &target);
+        }
+    }
+}
@@ -5,6 +5,7 @@ use url::Url;
 
 use crate::Build;
 
+#[derive(Clone, Debug)]
 pub struct SiteUrl {
     /// Guaranteed to end on trailing slash
     normalized: String
@@ -43,6 +44,15 @@ impl SiteUrl {
         }
     }
 
+    /// The path of the url (e.g. "/music/" for https://example.com/music/),
+    /// always starting and ending with a slash.
+    pub fn path(&self) -> String {
+        match Url::parse(&self.normalized) {
+            Ok(url) => url.path().to_string(),
+            Err(_) => String::from("/")
+        }
+    }
+
     pub fn parse(input: &str) -> Result<SiteUrl, String> {
         // Ensure the url has a trailing slash so that further url
         // construction during build is done correctly.
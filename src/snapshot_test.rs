@@ -0,0 +1,232 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Developer-facing regression harness for the render modules: A fixture
+//! catalog is built (by running this very faircamp binary on it) and all
+//! generated html pages are compared against previously recorded snapshots,
+//! so that refactorings can be validated against unintended changes in the
+//! markup. With the screenshots feature, pages are additionally captured
+//! through a headless chromium and compared as well.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+use crate::util;
+
+/// Name of the directory inside the fixture directory that is used as
+/// build directory (the cache directory is left at its default location).
+const BUILD_DIRNAME: &str = ".faircamp_snapshot_build";
+
+#[cfg(feature = "screenshots")]
+const CHROMIUM_BINARY: &str = "chromium";
+
+/// Written into the snapshot directory when snapshots are recorded. Recording
+/// snapshots clears the snapshot directory first, which is refused for
+/// directories that don't contain this file, so that a mistyped path can't
+/// wipe an unrelated directory.
+const SNAPSHOT_MARKER_FILENAME: &str = ".faircamp_snapshots";
+
+/// Screenshots are stored in this subdirectory of the snapshot directory
+#[cfg(feature = "screenshots")]
+const SCREENSHOTS_DIRNAME: &str = "screenshots";
+
+/// Builds the fixture catalog, then either records its pages as new
+/// snapshots (update) or compares them against the existing snapshots.
+pub fn run(fixture_dir: &Path, snapshot_dir: &Path, update: bool) -> ExitCode {
+    let build_dir = fixture_dir.join(BUILD_DIRNAME);
+
+    if update &&
+        !snapshot_dir.join(SNAPSHOT_MARKER_FILENAME).exists() &&
+        fs::read_dir(snapshot_dir).map(|mut entries| entries.next().is_some()).unwrap_or(false) {
+        error!(
+            "The directory {} is not empty and does not contain snapshots recorded by faircamp (no {SNAPSHOT_MARKER_FILENAME} file found), refusing to clear it",
+            snapshot_dir.display()
+        );
+        return ExitCode::FAILURE;
+    }
+
+    let current_exe = match std::env::current_exe() {
+        Ok(current_exe) => current_exe,
+        Err(err) => {
+            error!("Could not determine the path of the faircamp binary ({err})");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    info!("Building the fixture catalog at {}", fixture_dir.display());
+
+    let output = Command::new(current_exe)
+        .arg("--build-dir").arg(&build_dir)
+        .arg("--catalog-dir").arg(fixture_dir)
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => (),
+        Ok(output) => {
+            error!(
+                "The build of the fixture catalog failed:\n\n{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            return ExitCode::FAILURE;
+        }
+        Err(err) => {
+            error!("The faircamp child process could not be executed ({err})");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let pages = html_files(&build_dir);
+
+    if update {
+        util::ensure_empty_dir(snapshot_dir);
+        fs::write(snapshot_dir.join(SNAPSHOT_MARKER_FILENAME), "").unwrap();
+
+        for page in &pages {
+            let snapshot_path = snapshot_dir.join(page);
+            util::ensure_dir_all(snapshot_path.parent().unwrap());
+            fs::copy(build_dir.join(page), snapshot_path).unwrap();
+        }
+
+        #[cfg(feature = "screenshots")]
+        for page in &pages {
+            let screenshot_path = screenshot_path(snapshot_dir, page);
+            if let Err(err) = screenshot(&build_dir.join(page), &screenshot_path) {
+                error!("{}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+
+        info!("Recorded snapshots of {} pages in {}", pages.len(), snapshot_dir.display());
+        return ExitCode::SUCCESS;
+    }
+
+    let snapshots = html_files(snapshot_dir);
+    let mut num_differences = 0;
+
+    for removed in snapshots.difference(&pages) {
+        error!("Page removed: {}", removed.display());
+        num_differences += 1;
+    }
+
+    for added in pages.difference(&snapshots) {
+        error!("Page added: {}", added.display());
+        num_differences += 1;
+    }
+
+    for page in pages.intersection(&snapshots) {
+        let built = fs::read_to_string(build_dir.join(page)).unwrap();
+        let recorded = fs::read_to_string(snapshot_dir.join(page)).unwrap();
+
+        if let Some((line_number, built_line, recorded_line)) = first_difference(&built, &recorded) {
+            error!(
+                "Page changed: {} (first difference in line {line_number})\n- {recorded_line}\n+ {built_line}",
+                page.display()
+            );
+            num_differences += 1;
+        }
+    }
+
+    #[cfg(feature = "screenshots")]
+    for page in pages.intersection(&snapshots) {
+        let recorded_path = screenshot_path(snapshot_dir, page);
+        let built_path = screenshot_path(&build_dir, page);
+
+        if let Err(err) = screenshot(&build_dir.join(page), &built_path) {
+            error!("{}", err);
+            return ExitCode::FAILURE;
+        }
+
+        if fs::read(&built_path).ok() != fs::read(&recorded_path).ok() {
+            error!(
+                "Screenshot changed: {} (compare {} with {})",
+                page.display(),
+                recorded_path.display(),
+                built_path.display()
+            );
+            num_differences += 1;
+        }
+    }
+
+    if num_differences > 0 {
+        error!("{num_differences} difference(s) to the snapshots were found - if they are intended, record new snapshots with --update");
+        ExitCode::FAILURE
+    } else {
+        info!("All {} pages match their snapshots", pages.len());
+        ExitCode::SUCCESS
+    }
+}
+
+/// Returns the (1-based) number of the first line that differs between
+/// the two texts, along with the differing lines (empty if one of the
+/// texts ends earlier).
+fn first_difference<'a>(built: &'a str, recorded: &'a str) -> Option<(usize, &'a str, &'a str)> {
+    let mut built_lines = built.lines();
+    let mut recorded_lines = recorded.lines();
+    let mut line_number = 1;
+
+    loop {
+        match (built_lines.next(), recorded_lines.next()) {
+            (None, None) => return None,
+            (built_line, recorded_line) if built_line != recorded_line => {
+                return Some((line_number, built_line.unwrap_or(""), recorded_line.unwrap_or("")));
+            }
+            _ => line_number += 1
+        }
+    }
+}
+
+/// Paths (relative to dir) of all html files inside dir (recursively)
+fn html_files(dir: &Path) -> BTreeSet<PathBuf> {
+    let mut files = BTreeSet::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current_dir) = pending.pop() {
+        let Ok(dir_entries) = current_dir.read_dir() else { continue };
+
+        for dir_entry in dir_entries.flatten() {
+            let path = dir_entry.path();
+
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|extension| extension == "html") {
+                files.insert(path.strip_prefix(dir).unwrap().to_path_buf());
+            }
+        }
+    }
+
+    files
+}
+
+/// Captures the page at page_path with a headless chromium
+#[cfg(feature = "screenshots")]
+fn screenshot(page_path: &Path, screenshot_path: &Path) -> Result<(), String> {
+    util::ensure_dir_all(screenshot_path.parent().unwrap());
+
+    let page_path = page_path.canonicalize().unwrap();
+
+    let output = Command::new(CHROMIUM_BINARY)
+        .arg("--disable-gpu")
+        .arg("--headless")
+        .arg("--hide-scrollbars")
+        .arg(format!("--screenshot={}", screenshot_path.display()))
+        .arg("--window-size=1280,1024")
+        .arg(format!("file://{}", page_path.display()))
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => Err(format!(
+            "The {CHROMIUM_BINARY} child process returned an error exit code.\n\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        )),
+        Err(err) => Err(format!("The {CHROMIUM_BINARY} child process could not be executed ({err})"))
+    }
+}
+
+#[cfg(feature = "screenshots")]
+fn screenshot_path(dir: &Path, page: &Path) -> PathBuf {
+    dir.join(SCREENSHOTS_DIRNAME).join(page.with_extension("png"))
+}
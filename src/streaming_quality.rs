@@ -3,6 +3,16 @@
 
 use crate::AudioFormat;
 
+/// Thresholds beyond which the streaming files of a track are considered
+/// unreasonably large for visitors (think of a three hour DJ set), which is
+/// then reported as a warning. Set through the catalog's streaming_limits
+/// option, either limit can be disabled (None).
+#[derive(Clone, Copy, Debug)]
+pub struct StreamingLimits {
+    pub duration_minutes: Option<u32>,
+    pub size_megabytes: Option<u32>
+}
+
 /// Used to store the streaming quality configuration per release.
 /// During processing this enum is also called upon to obtain the
 /// concrete audio formats needed for a certain streaming quality.
@@ -12,13 +22,40 @@ pub enum StreamingQuality {
     Standard
 }
 
+impl StreamingLimits {
+    pub const DEFAULT: StreamingLimits = StreamingLimits {
+        duration_minutes: Some(120),
+        size_megabytes: Some(100)
+    };
+
+    pub const DISABLED: StreamingLimits = StreamingLimits {
+        duration_minutes: None,
+        size_megabytes: None
+    };
+}
+
 impl StreamingQuality {
+    /// Estimates the size of the larger of the two streaming files (the mp3
+    /// fallback) for a track of the given duration, based on the average
+    /// bitrate of the mp3 format (see the ranges documented on AudioFormat).
+    pub fn estimated_size_bytes(&self, duration_seconds: f32) -> u64 {
+        let average_kbps = match self {
+            StreamingQuality::Frugal => 100.0,
+            StreamingQuality::Standard => 135.0
+        };
+
+        (duration_seconds * average_kbps * 1000.0 / 8.0) as u64
+    }
+
     /// Returns both streaming formats (we always render two) for iteration.
     /// [0] is the primary format (opus) which we preferentially offer for
     /// streaming through the website. [1] is the secondary format(mp3) which
     /// serves as a compatibility fallback for streaming through the website,
     /// but is used as the (only) format for podcast rss provision, as opus
-    /// is not all supported in that context.
+    /// is not all supported in that context. The order is significant: It is
+    /// rendered as the order of <source> elements, which the player script
+    /// uses as its order of preference when negotiating a format with the
+    /// browser (see negotiateSources in player.js/embeds.js).
     pub fn formats(&self) -> [AudioFormat; 2] {
         match self {
             StreamingQuality::Frugal => [
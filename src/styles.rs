@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use indoc::formatdoc;
+use std::collections::BTreeSet;
 use std::fs;
 
 use crate::{
@@ -12,6 +13,7 @@ use crate::{
     ThemeFont,
     ThemeVarsHsl
 };
+use crate::{font_subset, minify};
 use crate::util::url_safe_hash_base64;
 
 const BARLOW_FONT_FILENAME: &str = "barlow-v12-latin-regular.woff2";
@@ -26,29 +28,191 @@ const THEMING_WIDGET_CSS: &str = include_str!(env!("FAIRCAMP_THEMING_WIDGET_CSS"
 const SITE_CSS: &str = include_str!(env!("FAIRCAMP_SITE_CSS"));
 const SITE_CSS_FILENAME: &str = "site.css";
 
+/// Selectors whose rules are part of the critical css for every page type.
+/// We match on the leading compound of a selector only (e.g. ".actions" for
+/// ".actions:not(.primary) button"), which is coarse, but reliably picks up
+/// everything that shapes the top of a page (including the rules that hide
+/// elements which are only revealed later through scripting).
+const CRITICAL_SELECTORS_COMMON: &[&str] = &[
+    "*",
+    ":root",
+    ".actions",
+    ".button",
+    ".cover",
+    ".divider",
+    ".hcenter_unconstrained",
+    ".image",
+    ".js_notice",
+    ".large_type",
+    ".layout",
+    ".page",
+    ".page_center",
+    ".page_grid",
+    ".page_more",
+    ".page_split",
+    ".skip_to_content",
+    ".text",
+    ".vpad",
+    "#browser",
+    "a",
+    "body",
+    "button",
+    "form",
+    "h1",
+    "h2",
+    "header",
+    "html",
+    "img",
+    "input",
+    "main",
+    "pre",
+    "svg"
+];
+const CRITICAL_SELECTORS_LISTING: &[&str] = &[
+    ".artist",
+    ".artists",
+    ".cover_listing",
+    ".hero",
+    ".hero_image",
+    ".hero_text",
+    ".home_image",
+    ".index_list",
+    ".index_timeline",
+    ".release",
+    ".release_compact",
+    ".unlisted"
+];
+const CRITICAL_SELECTORS_RELEASE: &[&str] = &[
+    ".abstract",
+    ".artists",
+    ".notify_me",
+    ".release_artists",
+    ".release_info",
+    ".runtime_and_sizes",
+    ".speed",
+    ".track",
+    ".tracks",
+    ".unlisted",
+    ".volume",
+    ".waveform",
+    "audio"
+];
+
+/// The subset is first written to this file, which is then renamed to
+/// include the hash of the subset (e.g. "custom-subset-[hash].woff2"), so
+/// that themes with different custom fonts each get their own subset.
+const CUSTOM_FONT_SUBSET_TEMP_FILENAME: &str = "custom-subset.woff2.tmp";
+
+/// Fonts designed or widely recommended for readers with dyslexia, picked up
+/// if the visitor has one of them installed, with a fallback to commonly
+/// available sans-serif fonts with clearly distinguishable letterforms.
+const DYSLEXIA_FONT_STACK: &str = r#""OpenDyslexic", "Atkinson Hyperlegible", Lexend, Verdana, Tahoma, sans-serif"#;
+
 const FALLBACK_FONT_STACK_SANS: &str = r#"-apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, Oxygen-Sans, Ubuntu, Cantarell, "Helvetica Neue", sans-serif"#;
 const FONT_ELEMENTS_SELECTOR: &str = "body, button, input";
 
+/// The types of pages for which we extract separate critical css, because
+/// what is visible before scrolling differs significantly between them.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PageType {
+    /// Homepage and artist pages, which mainly list releases
+    Listing,
+    /// Release and track pages, which present tracks and the player
+    Release,
+    /// All other pages (downloads, purchase, embed codes, etc.)
+    Subpage
+}
+
+impl PageType {
+    pub const ALL: [PageType; 3] = [
+        PageType::Listing,
+        PageType::Release,
+        PageType::Subpage
+    ];
+
+    fn is_critical(&self, selector: &str) -> bool {
+        let compound = leading_compound(selector);
+
+        let specific: &[&str] = match self {
+            PageType::Listing => CRITICAL_SELECTORS_LISTING,
+            PageType::Release => CRITICAL_SELECTORS_RELEASE,
+            PageType::Subpage => &[]
+        };
+
+        CRITICAL_SELECTORS_COMMON.contains(&compound) || specific.contains(&compound)
+    }
+}
+
+/// Returns those rules of the (minified) css that are critical for the given
+/// page type. Rules inside conditional group rules (@media, @supports) are
+/// filtered recursively, all other at-rules (e.g. @keyframes) are omitted.
+fn extract_critical_css(css: &str, page_type: PageType) -> String {
+    let mut critical = String::new();
+    let mut remaining = css;
+
+    while let Some(open) = remaining.find('{') {
+        let prelude = remaining[..open].trim();
+
+        let mut close = None;
+        let mut depth = 0;
+        for (index, character) in remaining[open..].char_indices() {
+            match character {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        close = Some(open + index);
+                        break;
+                    }
+                }
+                _ => ()
+            }
+        }
+
+        let Some(close) = close else { break };
+        let block = &remaining[(open + 1)..close];
+
+        if prelude.starts_with("@media") || prelude.starts_with("@supports") {
+            let critical_block = extract_critical_css(block, page_type);
+            if !critical_block.is_empty() {
+                critical.push_str(&format!("{prelude}{{{critical_block}}}"));
+            }
+        } else if !prelude.starts_with('@') &&
+            prelude.split(',').any(|selector| page_type.is_critical(selector)) {
+            critical.push_str(&format!("{prelude}{{{block}}}"));
+        }
+
+        remaining = &remaining[(close + 1)..];
+    }
+
+    critical
+}
+
 pub fn generate(build: &mut Build, catalog: &Catalog) {
     if build.embeds_requested {
         generate_embeds_css(build);
     }
 
-    generate_site_css(build);
+    // Embeds-only builds have no site pages that would use it
+    if !build.embeds_only {
+        generate_site_css(build, catalog);
+    }
 
-    generate_theme_css(build, &catalog.theme);
+    let characters = font_subset::catalog_characters(build, catalog);
+
+    generate_theme_css(build, &characters, &catalog.theme);
 
     for artist in &catalog.featured_artists {
-        generate_theme_css(build, &artist.borrow().theme);
+        generate_theme_css(build, &characters, &artist.borrow().theme);
     }
 
     for release in &catalog.releases {
         let release_ref = release.borrow();
 
-        generate_theme_css(build, &release_ref.theme);
+        generate_theme_css(build, &characters, &release_ref.theme);
 
         for track in &release_ref.tracks {
-            generate_theme_css(build, &track.theme);
+            generate_theme_css(build, &characters, &track.theme);
         }
     }
 }
@@ -62,7 +226,7 @@ fn generate_embeds_css(build: &mut Build) {
     build.reserve_filename(EMBEDS_CSS_FILENAME);
 }
 
-fn generate_site_css(build: &mut Build) {
+fn generate_site_css(build: &mut Build, catalog: &Catalog) {
     let mut css = String::from(SITE_CSS);
 
     if build.missing_image_descriptions {
@@ -73,8 +237,23 @@ fn generate_site_css(build: &mut Build) {
         css.push_str(THEMING_WIDGET_CSS);
     }
 
+    if build.minify {
+        css = minify::css(&css);
+    }
+
     build.asset_hashes.site_css = Some(url_safe_hash_base64(&css));
 
+    if catalog.critical_css {
+        // Extraction relies on the absence of comments and superfluous
+        // whitespace, hence we always work on minified css here.
+        let css_minified = minify::css(&css);
+
+        for page_type in PageType::ALL {
+            let critical_css = extract_critical_css(&css_minified, page_type);
+            build.critical_css.insert(page_type, critical_css);
+        }
+    }
+
     fs::write(
         build.build_dir.join(SITE_CSS_FILENAME),
         css
@@ -83,7 +262,13 @@ fn generate_site_css(build: &mut Build) {
     build.reserve_filename(SITE_CSS_FILENAME);
 }
 
-fn generate_theme_css(build: &mut Build, theme: &Theme) {
+/// The characters are those used throughout the catalog, custom fonts are
+/// subset to these if possible.
+fn generate_theme_css(
+    build: &mut Build,
+    characters: &BTreeSet<char>,
+    theme: &Theme
+) {
     let stylesheet_filename = theme.stylesheet_filename();
 
     if build.asset_hashes.theme_css.contains_key(&stylesheet_filename) {
@@ -91,13 +276,42 @@ fn generate_theme_css(build: &mut Build, theme: &Theme) {
     }
 
     let font_declaration = match &theme.font {
-        ThemeFont::Custom { extension, path } => {
+        ThemeFont::Custom { extension, path } => 'custom: {
+            let subset_temp_path = build.build_dir.join(CUSTOM_FONT_SUBSET_TEMP_FILENAME);
+
+            match font_subset::subset(characters, path, &subset_temp_path) {
+                Ok(()) => {
+                    let subset_hash = url_safe_hash_base64(&fs::read(&subset_temp_path).unwrap());
+                    let subset_filename = format!("custom-subset-{subset_hash}.woff2");
+
+                    fs::rename(&subset_temp_path, build.build_dir.join(&subset_filename)).unwrap();
+                    build.reserve_filename(subset_filename.clone());
+
+                    let unicode_range = font_subset::unicode_range(characters);
+
+                    break 'custom formatdoc!(r#"
+                        @font-face {{
+                            font-family: 'Custom';
+                            font-style: normal;
+                            font-weight: 1 1000;
+                            src: url('{subset_filename}') format('woff2');
+                            unicode-range: {unicode_range};
+                        }}
+                        :root {{ --font-family: 'Custom'; }}
+                    "#);
+                }
+                Err(err) => {
+                    let reason = err.lines().next().unwrap_or("");
+                    info!("The custom font {} is included in full, as it could not be subset ({}). Installing fonttools (which provides {}) enables subsetting, which can drastically reduce the size of the font.", path.display(), reason, font_subset::PYFTSUBSET_BINARY);
+                }
+            }
+
             let filename = format!("custom.{}", extension);
 
             fs::copy(path, build.build_dir.join(&filename)).unwrap();
 
             build.reserve_filename(filename.clone());
-            
+
             formatdoc!(r#"
                 @font-face {{
                     font-family: 'Custom';
@@ -105,7 +319,7 @@ fn generate_theme_css(build: &mut Build, theme: &Theme) {
                     font-weight: 1 1000;
                     src: url('{filename}') format('{extension}');
                 }}
-                {FONT_ELEMENTS_SELECTOR} {{ font-family: 'Custom'; }}
+                :root {{ --font-family: 'Custom'; }}
             "#)
         }
         ThemeFont::Default => {
@@ -124,17 +338,17 @@ fn generate_theme_css(build: &mut Build, theme: &Theme) {
                     font-weight: 400;
                     src: local('Barlow'), url('{BARLOW_FONT_FILENAME}') format('woff2');
                 }}
-                {FONT_ELEMENTS_SELECTOR} {{ font-family: 'Barlow', {FALLBACK_FONT_STACK_SANS}; }}
+                :root {{ --font-family: 'Barlow', {FALLBACK_FONT_STACK_SANS}; }}
             "#)
         }
         ThemeFont::SystemMono => {
-            format!(r#"{FONT_ELEMENTS_SELECTOR} {{ font-family: SFMono-Regular, Menlo, Monaco, Consolas, "Liberation Mono", "Courier New", monospace; }}"#)
+            format!(r#":root {{ --font-family: SFMono-Regular, Menlo, Monaco, Consolas, "Liberation Mono", "Courier New", monospace; }}"#)
         }
         ThemeFont::SystemSans => {
-            format!(r#"{FONT_ELEMENTS_SELECTOR} {{ font-family: {FALLBACK_FONT_STACK_SANS}; }}"#)
+            format!(r#":root {{ --font-family: {FALLBACK_FONT_STACK_SANS}; }}"#)
         }
         ThemeFont::System(fonts) => {
-            format!("{FONT_ELEMENTS_SELECTOR} {{ font-family: {}; }}", fonts)
+            format!(":root {{ --font-family: {}; }}", fonts)
         }
     };
 
@@ -142,6 +356,31 @@ fn generate_theme_css(build: &mut Build, theme: &Theme) {
 
     css.push_str(&font_declaration);
 
+    // The font is applied through a custom property (like all other theme
+    // variables), so it can be overridden in user.css
+    css.push_str(&format!("{FONT_ELEMENTS_SELECTOR} {{ font-family: var(--font-family); }}\n"));
+
+    // Sizes throughout the site are given in rem/em, so scaling the root
+    // font size scales all text and the layout along with it.
+    if theme.font_scale != 100 {
+        let font_scale = theme.font_scale;
+        css.push_str(&format!(":root {{ font-size: {font_scale}%; }}\n"));
+    }
+
+    // The dyslexia_font class is put on the body when the visitor turned
+    // on the dyslexia-friendly font through the toggle in the footer.
+    if theme.dyslexia_font {
+        css.push_str(&formatdoc!(r#"
+            body.dyslexia_font,
+            .dyslexia_font button,
+            .dyslexia_font input {{
+                font-family: {DYSLEXIA_FONT_STACK};
+                letter-spacing: .04em;
+                word-spacing: .12em;
+            }}
+        "#));
+    }
+
     if let Some(image) = &theme.background_image {
         let image_ref = image.borrow();
         let filename = &image_ref.background_asset.as_ref().unwrap().filename;
@@ -169,7 +408,11 @@ fn generate_theme_css(build: &mut Build, theme: &Theme) {
 
         css.push_str(&background_override);
     }
-    
+
+    if build.minify {
+        css = minify::css(&css);
+    }
+
     build.asset_hashes.theme_css.insert(stylesheet_filename.clone(), url_safe_hash_base64(&css));
 
     fs::write(
@@ -206,3 +449,18 @@ fn generate_vars(theme: &Theme) -> String {
         }}
     "#)
 }
+
+/// Returns the leading compound selector of a complex selector, e.g.
+/// "header" for "header > div a:focus-visible".
+fn leading_compound(selector: &str) -> &str {
+    let selector = selector.trim();
+
+    let end = selector
+        .char_indices()
+        .skip(1)
+        .find(|(_, character)| matches!(character, ' ' | '#' | '+' | '.' | ':' | '>' | '[' | '~'))
+        .map(|(index, _)| index)
+        .unwrap_or(selector.len());
+
+    &selector[..end]
+}
@@ -1,6 +1,8 @@
 // SPDX-FileCopyrightText: 2024-2025 Simon Repp
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use std::hash::{Hash, Hasher};
+
 use serde_derive::{Deserialize, Serialize};
 
 use crate::{
@@ -13,10 +15,48 @@ use crate::{
 /// This is the final mapping of a cover image to be embedded into an output audio file.
 /// It only stores a source hash without any path information because faircamp anyway
 /// knows from where to get the cover image (= from the release struct).
+/// WriteResized additionally stores the maximum edge size set through the
+/// archive_cover option, as the resized cover then needs to be embedded.
 #[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
 pub enum ImageEmbed {
     Copy,
-    Write(SourceHash)
+    Write(SourceHash),
+    WriteResized(SourceHash, u32)
+}
+
+/// A chapter as it is written to an output audio file, with its start and
+/// end in milliseconds.
+#[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
+pub struct TagChapter {
+    pub end_ms: u64,
+    pub start_ms: u64,
+    pub title: String
+}
+
+/// The chapters written to an output audio file. Hashing skips these
+/// entirely if there are none, so that the signature of transcodes without
+/// chapters is the same as it was before chapters were supported (sparing
+/// everyone from having all their tracks transcoded anew).
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TagChapters(pub Vec<TagChapter>);
+
+/// The (plain text) lyrics written to an output audio file. Like with
+/// [TagChapters], hashing skips these entirely if there are none.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TagLyrics(pub Option<String>);
+
+/// Whether an output audio file is flagged for gapless playback. Like with
+/// [TagChapters], hashing skips this entirely if it is not set.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TagGapless(pub bool);
+
+/// The ISRC of the track and the UPC/EAN barcode of the release written to
+/// an output audio file. Like with [TagChapters], hashing skips these
+/// entirely if neither is set.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TagIdentifiers {
+    pub isrc: Option<String>,
+    pub upc: Option<String>
 }
 
 /// Set behavior for a single tag:
@@ -58,7 +98,11 @@ pub enum TagMapping {
         album: Option<String>,
         album_artist: Option<String>,
         artist: Option<String>,
+        chapters: TagChapters,
+        gapless: TagGapless,
+        identifiers: TagIdentifiers,
         image: Option<ImageEmbed>,
+        lyrics: TagLyrics,
         title: Option<String>,
         /// Track number
         track: Option<usize>
@@ -66,6 +110,39 @@ pub enum TagMapping {
     Remove
 }
 
+impl Hash for TagChapters {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if !self.0.is_empty() {
+            self.0.hash(state);
+        }
+    }
+}
+
+impl Hash for TagGapless {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if self.0 {
+            self.0.hash(state);
+        }
+    }
+}
+
+impl Hash for TagIdentifiers {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if self.isrc.is_some() || self.upc.is_some() {
+            self.isrc.hash(state);
+            self.upc.hash(state);
+        }
+    }
+}
+
+impl Hash for TagLyrics {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if let Some(lyrics) = &self.0 {
+            lyrics.hash(state);
+        }
+    }
+}
+
 impl TagAction {
     pub fn from_key(key: &str) -> Result<TagAction, String> {
         match key {
@@ -221,20 +298,60 @@ impl TagMapping {
                     }
                 };
 
+                // Chapters, the gapless flag, identifiers and lyrics are
+                // always written along with the other tags (unless all tags
+                // are copied or removed).
+                let chapters_mapped = {
+                    let duration_ms = (track.transcodes.borrow().source_meta.duration_seconds * 1000.0) as u64;
+                    let starts_ms = track.chapters
+                        .iter()
+                        .map(|chapter| (chapter.seconds * 1000.0) as u64)
+                        .collect::<Vec<u64>>();
+
+                    // Each chapter ends where the next one starts (or where
+                    // the track ends), chapters starting beyond the end of
+                    // the track are left out.
+                    let chapters = track.chapters
+                        .iter()
+                        .zip(starts_ms.iter())
+                        .enumerate()
+                        .filter(|(_, (_, start_ms))| **start_ms < duration_ms)
+                        .map(|(index, (chapter, start_ms))| TagChapter {
+                            end_ms: starts_ms.get(index + 1).copied().unwrap_or(duration_ms).min(duration_ms),
+                            start_ms: *start_ms,
+                            title: chapter.title.clone()
+                        })
+                        .collect();
+
+                    TagChapters(chapters)
+                };
+
+                let gapless_mapped = TagGapless(release.gapless);
+
+                let identifiers_mapped = TagIdentifiers {
+                    isrc: track.isrc.clone(),
+                    upc: release.upc.clone()
+                };
+
                 let image_mapped = match image_action {
                     TagAction::Copy => Some(ImageEmbed::Copy),
                     TagAction::Remove => None,
                     TagAction::Rewrite => {
-                        if let Some(described_image) = &track.cover {
-                            Some(ImageEmbed::Write(described_image.borrow().hash.clone()))
-                        } else if let Some(described_image) = &release.cover {
-                            Some(ImageEmbed::Write(described_image.borrow().hash.clone()))
-                        } else {
-                            None
-                        }
+                        track.cover
+                            .as_ref()
+                            .or(release.cover.as_ref())
+                            .map(|described_image| {
+                                let hash = described_image.borrow().hash.clone();
+                                match release.archive_cover.max_edge_size {
+                                    Some(max_edge_size) => ImageEmbed::WriteResized(hash, max_edge_size),
+                                    None => ImageEmbed::Write(hash)
+                                }
+                            })
                     }
                 };
 
+                let lyrics_mapped = TagLyrics(track.lyrics.as_ref().map(|lyrics| lyrics.text()));
+
                 let title_mapped = match title_actiion {
                     TagAction::Copy => track.transcodes.borrow().source_meta.title.clone(),
                     TagAction::Remove => None,
@@ -264,7 +381,11 @@ impl TagMapping {
                     album: album_mapped,
                     album_artist: album_artist_mapped,
                     artist: artist_mapped,
+                    chapters: chapters_mapped,
+                    gapless: gapless_mapped,
+                    identifiers: identifiers_mapped,
                     image: image_mapped,
+                    lyrics: lyrics_mapped,
                     title: title_mapped,
                     track: track_mapped
                 }
@@ -0,0 +1,232 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Developer-facing generator for synthetic catalogs, used to write
+//! integration tests and to benchmark builds against a reproducible catalog
+//! of arbitrary size. All audio (sine waves) and images (solid colors) are
+//! synthesized by ffmpeg, so nothing besides ffmpeg is required. The output
+//! is deterministic - the same arguments always produce the same catalog.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, ExitCode};
+
+use indoc::formatdoc;
+
+use crate::ffmpeg::FFMPEG_BINARY;
+use crate::util;
+
+/// Artist names cycled through for the generated releases, covering plain
+/// ascii, diacritics, non-latin scripts, right-to-left text and emoji.
+const ARTIST_NAMES: &[&str] = &[
+    "Test Artist",
+    "Ælfgifu & the Ünïcödé Øchestra",
+    "東京テストバンド",
+    "תזמורת הבדיקה",
+    "Ensemble 🎻🎺"
+];
+
+/// Audio formats cycled through for the generated tracks
+const AUDIO_EXTENSIONS: &[&str] = &["flac", "mp3", "ogg", "wav"];
+
+/// Cover sizes cycled through for the generated releases. None means the
+/// release gets no cover (a procedural cover is used). Non-square and tiny
+/// covers are included on purpose.
+const COVER_SIZES: &[Option<(u32, u32)>] = &[
+    Some((1400, 1400)),
+    None,
+    Some((1600, 900)),
+    Some((900, 1600)),
+    Some((120, 120))
+];
+
+/// The regular tracks are kept short so that generating and building large
+/// catalogs stays fast.
+const TRACK_DURATION_SECONDS: u32 = 10;
+
+/// A single track (the last one of the first release) is made very long,
+/// which results in a large (about 300 MB) wav file.
+const HUGE_TRACK_DURATION_SECONDS: u32 = 30 * 60;
+
+/// Release titles cycled through for the generated releases (the release
+/// number is always appended, so titles stay unique).
+const RELEASE_TITLES: &[&str] = &[
+    "Test Release",
+    "Ünïcödé Äłbüm",
+    "テストアルバム",
+    "Quotes \"&\" <Brackets>",
+    "A Release With An Exceptionally Long Title That Goes On And On And Keeps Going Well Beyond Any Reasonable Length"
+];
+
+pub fn generate(dir: &Path, releases: usize, tracks: usize) -> ExitCode {
+    if dir.exists() && fs::read_dir(dir).map(|mut entries| entries.next().is_some()).unwrap_or(true) {
+        error!("The directory {} is not empty, refusing to generate a test catalog in it", dir.display());
+        return ExitCode::FAILURE;
+    }
+
+    util::ensure_dir_all(dir);
+
+    let catalog_manifest = formatdoc!("
+        title: Test Catalog
+
+        -- more
+        A synthetic catalog generated with `faircamp generate-test-catalog`,
+        containing {releases} releases with up to {tracks} tracks each.
+        -- more
+    ");
+
+    fs::write(dir.join("catalog.eno"), catalog_manifest).unwrap();
+
+    for release_index in 0..releases {
+        let release_number = release_index + 1;
+
+        let artist = ARTIST_NAMES[release_index % ARTIST_NAMES.len()];
+        let title = format!("{} {release_number}", RELEASE_TITLES[release_index % RELEASE_TITLES.len()]);
+
+        info_generating!("Test release {}/{} ({})", release_number, releases, title);
+
+        let release_dir = dir.join(format!("{release_number:04} {}", sanitize_dirname(&title)));
+        util::ensure_dir_all(&release_dir);
+
+        // Every fifth release has no manifest, so its metadata is entirely
+        // derived from the audio file tags (or the lack thereof).
+        if release_index % 5 != 4 {
+            let day = release_index % 28 + 1;
+            let month = release_index / 28 % 12 + 1;
+
+            let release_manifest = formatdoc!("
+                date: 2020-{month:02}-{day:02}
+                release_artist: {artist}
+                release_downloads:
+                - flac
+                - mp3
+                title: {title}
+            ");
+
+            fs::write(release_dir.join("release.eno"), release_manifest).unwrap();
+        }
+
+        if let Some((width, height)) = COVER_SIZES[release_index % COVER_SIZES.len()] {
+            if let Err(err) = synthesize_cover(&release_dir.join("cover.jpg"), release_index, width, height) {
+                error!("{}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+
+        // Releases vary in their number of tracks (between 1 and the
+        // requested number), and every seventh release has no tags at all.
+        let num_tracks = tracks - release_index % tracks.max(1);
+        let tagged = release_index % 7 != 6;
+
+        for track_index in 0..num_tracks {
+            let track_number = track_index + 1;
+            let track_title = format!("Track {track_number} – Sine {}Hz", sine_frequency(track_index));
+
+            let (duration_seconds, extension) = if release_index == 0 && track_number == num_tracks {
+                (HUGE_TRACK_DURATION_SECONDS, "wav")
+            } else {
+                (TRACK_DURATION_SECONDS, AUDIO_EXTENSIONS[(release_index + track_index) % AUDIO_EXTENSIONS.len()])
+            };
+
+            let track_path = release_dir.join(format!("{track_number:02} {}.{extension}", sanitize_dirname(&track_title)));
+
+            let tags = if tagged {
+                Some(Tags { album: &title, artist, title: &track_title, track_number })
+            } else {
+                None
+            };
+
+            if let Err(err) = synthesize_track(&track_path, duration_seconds, sine_frequency(track_index), tags) {
+                error!("{}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    info!("Generated a test catalog with {} releases in {}", releases, dir.display());
+
+    ExitCode::SUCCESS
+}
+
+struct Tags<'a> {
+    album: &'a str,
+    artist: &'a str,
+    title: &'a str,
+    track_number: usize
+}
+
+/// Replaces characters that are problematic in file names on some
+/// platforms, everything else (including unicode) is kept on purpose.
+fn sanitize_dirname(name: &str) -> String {
+    name.replace(['"', '*', '/', ':', '<', '>', '?', '\\', '|'], "_")
+}
+
+/// Frequencies for the tracks of a release, ascending by semitones from 220Hz
+fn sine_frequency(track_index: usize) -> u32 {
+    (220.0 * 2f32.powf(track_index as f32 / 12.0)).round() as u32
+}
+
+fn synthesize_cover(
+    path: &Path,
+    release_index: usize,
+    width: u32,
+    height: u32
+) -> Result<(), String> {
+    // Spread the hue of the covers evenly, so releases are distinguishable
+    let hue = release_index * 47 % 360;
+
+    let mut command = Command::new(FFMPEG_BINARY);
+
+    command.arg("-y");
+    command.arg("-f").arg("lavfi");
+    command.arg("-i").arg(format!("color=c=red:s={width}x{height}"));
+    command.arg("-vf").arg(format!("hue=h={hue}"));
+    command.arg("-frames:v").arg("1");
+    command.arg(path);
+
+    run(command)
+}
+
+fn synthesize_track(
+    path: &Path,
+    duration_seconds: u32,
+    frequency: u32,
+    tags: Option<Tags>
+) -> Result<(), String> {
+    let mut command = Command::new(FFMPEG_BINARY);
+
+    command.arg("-y");
+    command.arg("-f").arg("lavfi");
+    command.arg("-i").arg(format!("sine=frequency={frequency}:duration={duration_seconds}"));
+    command.arg("-ac").arg("2");
+
+    match tags {
+        Some(tags) => {
+            command.arg("-metadata").arg(format!("album={}", tags.album));
+            command.arg("-metadata").arg(format!("artist={}", tags.artist));
+            command.arg("-metadata").arg(format!("title={}", tags.title));
+            command.arg("-metadata").arg(format!("track={}", tags.track_number));
+        }
+        None => {
+            command.arg("-map_metadata").arg("-1");
+        }
+    }
+
+    command.arg(path);
+
+    run(command)
+}
+
+fn run(mut command: Command) -> Result<(), String> {
+    match command.output() {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(())
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(format!("The {FFMPEG_BINARY} child process returned an error exit code.\n\n{stderr}"))
+            }
+        }
+        Err(_) => Err(format!("The {FFMPEG_BINARY} child process could not be executed."))
+    }
+}
@@ -0,0 +1,197 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Through --check-texts the synopsis and more texts in all manifests are
+//! checked for common issues (text apparently written in another language
+//! than the catalog's, double spaces, unbalanced markdown emphasis, overly
+//! long synopsis lines), which are reported as warnings. The texts are
+//! collected while the manifests are read, but only checked once the entire
+//! catalog is read, because only then the catalog language is known.
+
+use crate::{Build, WarningCategory};
+
+/// Synopses are shown in compact spaces (e.g. next to the release cover),
+/// lines longer than this are hard to read there.
+const MAX_SYNOPSIS_LINE_CHARS: usize = 160;
+
+/// A text language is only guessed from texts with at least this many words
+const MIN_WORDS_FOR_LANGUAGE: usize = 8;
+
+/// Very common words for some languages, used to guess in which language a
+/// text is written. Words that are common in more than one of these
+/// languages (e.g. "de", "la", "en") are intentionally left out.
+const STOPWORDS: &[(&str, &str, &[&str])] = &[
+    ("de", "German", &["auch", "auf", "das", "dem", "den", "der", "die", "ein", "eine", "für", "ich", "ist", "mit", "nicht", "sind", "und", "von", "wir"]),
+    ("en", "English", &["and", "are", "been", "for", "from", "has", "have", "it", "of", "our", "that", "the", "their", "they", "this", "were", "with", "you"]),
+    ("es", "Spanish", &["el", "esta", "está", "esto", "hay", "las", "los", "muy", "nuestro", "pero", "sus", "también", "y"]),
+    ("fr", "French", &["au", "avec", "dans", "est", "et", "le", "les", "nous", "pas", "pour", "qui", "sur", "une"]),
+    ("it", "Italian", &["alla", "anche", "che", "degli", "della", "di", "gli", "il", "nel", "questa", "questo", "sono", "è"]),
+    ("nl", "Dutch", &["deze", "een", "het", "maar", "met", "naar", "niet", "ook", "van", "voor", "wij", "zijn"]),
+    ("pt", "Portuguese", &["com", "em", "muito", "mas", "não", "nosso", "os", "pelo", "são", "também", "um", "uma"])
+];
+
+/// A synopsis or more text, as collected from a manifest
+pub struct ManifestText {
+    /// Manifest path and line number of the option
+    pub location: String,
+    /// Whether the text is markdown (more) or plain text (synopsis)
+    pub markdown: bool,
+    /// Name of the option the text was given with
+    pub option: &'static str,
+    pub snippet: String,
+    pub text: String
+}
+
+/// Checks all collected texts and reports the issues found as warnings
+pub fn check_texts(build: &mut Build) {
+    let language = build.locale.language
+        .split('-')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    let manifest_texts = std::mem::take(&mut build.manifest_texts);
+
+    for manifest_text in &manifest_texts {
+        let issues = manifest_text.issues(&language);
+
+        if !issues.is_empty() {
+            let r_issues = issues
+                .iter()
+                .map(|issue| format!("- {issue}"))
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            let warning = format!(
+                "Possible issues with the {} text in {}:\n\n{}\n\n{}",
+                manifest_text.option,
+                manifest_text.location,
+                manifest_text.snippet,
+                r_issues
+            );
+            build.warning(WarningCategory::TextIssue, &warning);
+        }
+    }
+}
+
+/// Guesses the language of the text, returning the code and name of the
+/// guessed language, if the text clearly appears to be written in another
+/// language than the catalog language. Catalog languages for which we have
+/// no stopwords are never questioned.
+fn guess_other_language(language: &str, text: &str) -> Option<(&'static str, &'static str)> {
+    let words = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<String>>();
+
+    if words.len() < MIN_WORDS_FOR_LANGUAGE {
+        return None;
+    }
+
+    let hits = |stopwords: &[&str]| {
+        words
+            .iter()
+            .filter(|word| stopwords.contains(&word.as_str()))
+            .count()
+    };
+
+    let (_, _, catalog_stopwords) = STOPWORDS.iter().find(|(code, _, _)| *code == language)?;
+    let catalog_hits = hits(catalog_stopwords);
+
+    let (code, name, best_hits) = STOPWORDS
+        .iter()
+        .map(|(code, name, stopwords)| (*code, *name, hits(stopwords)))
+        .max_by_key(|(_, _, hits)| *hits)?;
+
+    if code != language && best_hits >= 3 && best_hits >= 2 * catalog_hits {
+        Some((code, name))
+    } else {
+        None
+    }
+}
+
+impl ManifestText {
+    fn issues(&self, language: &str) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        if let Some((code, name)) = guess_other_language(language, &self.text) {
+            issues.push(format!("The text appears to be written in {name} ({code}), but the catalog language is '{language}'"));
+        }
+
+        for (index, line) in self.text.lines().enumerate() {
+            let line_number = index + 1;
+
+            // Leading indentation and trailing spaces (line breaks in
+            // markdown) are not considered.
+            if line.trim().contains("  ") {
+                issues.push(format!("Line {line_number} of the text contains a double space"));
+            }
+
+            let line_chars = line.chars().count();
+            if !self.markdown && line_chars > MAX_SYNOPSIS_LINE_CHARS {
+                issues.push(format!("Line {line_number} of the text is {line_chars} characters long, consider breaking it up (at most {MAX_SYNOPSIS_LINE_CHARS} characters are recommended)"));
+            }
+        }
+
+        if self.markdown {
+            issues.extend(unbalanced_emphasis(&self.text));
+        }
+
+        issues
+    }
+}
+
+/// Emphasis can not span paragraphs in markdown, therefore we check each
+/// paragraph on its own, ignoring code spans and list markers.
+fn unbalanced_emphasis(text: &str) -> Vec<String> {
+    let mut issues = Vec::new();
+    let mut paragraph = String::new();
+    let mut paragraph_line_number = 1;
+
+    let mut check_paragraph = |paragraph: &str, line_number: usize| {
+        let without_code = paragraph
+            .split('`')
+            .step_by(2)
+            .collect::<Vec<&str>>()
+            .join("");
+
+        for marker in ["**", "__"] {
+            if without_code.matches(marker).count() % 2 != 0 {
+                issues.push(format!("The paragraph starting in line {line_number} of the text contains an unbalanced emphasis marker ('{marker}')"));
+            }
+        }
+
+        let single_asterisks = without_code
+            .lines()
+            .map(|line| {
+                let line = line.trim_start();
+                let without_list_marker = line.strip_prefix("* ").unwrap_or(line);
+                without_list_marker.replace("**", "").matches('*').count()
+            })
+            .sum::<usize>();
+
+        if single_asterisks % 2 != 0 {
+            issues.push(format!("The paragraph starting in line {line_number} of the text contains an unbalanced emphasis marker ('*')"));
+        }
+    };
+
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            if !paragraph.is_empty() {
+                check_paragraph(&paragraph, paragraph_line_number);
+                paragraph.clear();
+            }
+            paragraph_line_number = index + 2;
+        } else {
+            paragraph.push_str(line);
+            paragraph.push('\n');
+        }
+    }
+
+    if !paragraph.is_empty() {
+        check_paragraph(&paragraph, paragraph_line_number);
+    }
+
+    issues
+}
@@ -31,7 +31,12 @@ pub struct Theme {
     pub base_hue: u16,
     pub cover_generator: CoverGenerator,
     pub dynamic_range: u8,
+    /// Whether visitors are offered a toggle to switch to a
+    /// dyslexia-friendly font
+    pub dyslexia_font: bool,
     pub font: ThemeFont,
+    /// Percentage by which all text (and the layout with it) is scaled
+    pub font_scale: u16,
     pub relative_waveforms: bool,
     pub round_corners: bool,
     pub waveforms: bool
@@ -75,6 +80,27 @@ pub struct ThemeVarsOklch {
 }
 
 impl Theme {
+    /// The attributes that can be reset to their default value through
+    /// `theme: inherit except ...` in a manifest
+    pub const RESETTABLE_ATTRIBUTES: [&'static str; 16] = [
+        "accent_brightening",
+        "accent_chroma",
+        "accent_hue",
+        "background_alpha",
+        "background_image",
+        "base",
+        "base_chroma",
+        "base_hue",
+        "cover_generator",
+        "custom_font",
+        "dynamic_range",
+        "dyslexia_font",
+        "font_scale",
+        "round_corners",
+        "system_font",
+        "waveforms"
+    ];
+
     pub fn new() -> Theme {
         Theme {
             accent_brightening: 50,
@@ -87,7 +113,9 @@ impl Theme {
             base: ThemeBase::Dark,
             cover_generator: CoverGenerator::LooneyTunes,
             dynamic_range: 0,
+            dyslexia_font: false,
             font: ThemeFont::Default,
+            font_scale: 100,
             relative_waveforms: true,
             round_corners: false,
             waveforms: true
@@ -184,6 +212,38 @@ impl Theme {
         }
     }
 
+    /// Resets a single attribute (identified by its manifest key) to its
+    /// default value, leaving all other (inherited) attributes untouched.
+    /// Returns false if the key is not recognized.
+    pub fn reset_attribute(&mut self, key: &str) -> bool {
+        let default = Theme::new();
+
+        match key {
+            "accent_brightening" => self.accent_brightening = default.accent_brightening,
+            "accent_chroma" => self.accent_chroma = default.accent_chroma,
+            "accent_hue" => self.accent_hue = default.accent_hue,
+            "background_alpha" => self.background_alpha = default.background_alpha,
+            "background_image" => self.background_image = default.background_image,
+            "base" => self.base = default.base,
+            "base_chroma" => self.base_chroma = default.base_chroma,
+            "base_hue" => self.base_hue = default.base_hue,
+            "cover_generator" => self.cover_generator = default.cover_generator,
+            "custom_font" |
+            "system_font" => self.font = default.font,
+            "dynamic_range" => self.dynamic_range = default.dynamic_range,
+            "dyslexia_font" => self.dyslexia_font = default.dyslexia_font,
+            "font_scale" => self.font_scale = default.font_scale,
+            "round_corners" => self.round_corners = default.round_corners,
+            "waveforms" => {
+                self.relative_waveforms = default.relative_waveforms;
+                self.waveforms = default.waveforms;
+            }
+            _ => return false
+        }
+
+        true
+    }
+
     pub fn stylesheet_filename(&self) -> String {
         format!("theme-{}.css", url_safe_hash_base64(self))
     }
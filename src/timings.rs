@@ -0,0 +1,131 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Wall-clock time measurement of the build phases (and of the time spent on
+//! each release), enabled through --timings. The results are printed at the
+//! end of the build and written to a json file, so that performance can be
+//! compared across faircamp versions.
+//!
+//! Measurements happen deep inside the build (e.g. when transcoding a single
+//! track), where we usually only have shared access to Build, hence the
+//! interior mutability.
+
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::util::json_escape;
+
+pub const TIMINGS_FILENAME: &str = ".faircamp_timings.json";
+
+pub struct Timings {
+    enabled: bool,
+    /// Accumulated time per phase, in the order in which the phases were
+    /// first encountered. Note that some phases are part of others (e.g.
+    /// decoding happens while scanning the catalog, transcoding while
+    /// writing assets), their times therefore overlap.
+    phases: RefCell<Vec<(String, Duration)>>,
+    /// Accumulated time per release (by release title), in the order in
+    /// which the releases were first encountered
+    releases: RefCell<Vec<(String, Duration)>>
+}
+
+impl Timings {
+    fn add(entries: &RefCell<Vec<(String, Duration)>>, key: &str, duration: Duration) {
+        let mut entries_mut = entries.borrow_mut();
+
+        match entries_mut.iter_mut().find(|(existing_key, _)| existing_key == key) {
+            Some((_, accumulated)) => *accumulated += duration,
+            None => entries_mut.push((key.to_string(), duration))
+        }
+    }
+
+    /// Runs the closure and adds the time it took to the given phase
+    pub fn measure<T>(&self, phase: &str, closure: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return closure();
+        }
+
+        let begin = Instant::now();
+        let result = closure();
+        Timings::add(&self.phases, phase, begin.elapsed());
+
+        result
+    }
+
+    pub fn new(enabled: bool) -> Timings {
+        Timings {
+            enabled,
+            phases: RefCell::new(Vec::new()),
+            releases: RefCell::new(Vec::new())
+        }
+    }
+
+    /// Accumulated time per phase (empty unless enabled)
+    pub fn phases(&self) -> Vec<(String, Duration)> {
+        self.phases.borrow().clone()
+    }
+
+    pub fn print(&self) {
+        if !self.enabled {
+            return;
+        }
+
+        for (phase, duration) in self.phases.borrow().iter() {
+            info_stats!("{} took {:.3}s", phase, duration.as_secs_f64());
+        }
+
+        for (release_title, duration) in self.releases.borrow().iter() {
+            info_stats!("Release '{}' took {:.3}s", release_title, duration.as_secs_f64());
+        }
+    }
+
+    /// Adds the time elapsed since begin to the given phase. This is an
+    /// alternative to measure() for places where the measured code needs
+    /// mutable access to Build.
+    pub fn record(&self, phase: &str, begin: Instant) {
+        if self.enabled {
+            Timings::add(&self.phases, phase, begin.elapsed());
+        }
+    }
+
+    /// Adds the time elapsed since begin to the given release
+    pub fn record_release(&self, release_title: &str, begin: Instant) {
+        if self.enabled {
+            Timings::add(&self.releases, release_title, begin.elapsed());
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let json_entries = |entries: &Vec<(String, Duration)>| {
+            entries
+                .iter()
+                .map(|(key, duration)| format!(r#"    "{}": {:.6}"#, json_escape(key), duration.as_secs_f64()))
+                .collect::<Vec<String>>()
+                .join(",\n")
+        };
+
+        let phases = json_entries(&self.phases.borrow());
+        let releases = json_entries(&self.releases.borrow());
+
+        format!(
+            "{{\n  \"version\": \"{version}\",\n  \"phases\": {{\n{phases}\n  }},\n  \"releases\": {{\n{releases}\n  }}\n}}\n",
+            version = env!("FAIRCAMP_VERSION_DETAILED")
+        )
+    }
+
+    /// Writes the timings (in seconds) as json to the given directory
+    pub fn write(&self, dir: &Path) {
+        if !self.enabled {
+            return;
+        }
+
+        let path = dir.join(TIMINGS_FILENAME);
+
+        match fs::write(&path, self.to_json()) {
+            Ok(()) => info_stats!("Timings were written to {}", path.display()),
+            Err(err) => error!("Timings could not be written to {} ({})", path.display(), err)
+        }
+    }
+}
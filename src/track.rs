@@ -2,14 +2,20 @@
 // SPDX-FileCopyrightText: 2023 Deborah Pickett
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
 
 use crate::{
+    Annotation,
     ArtistRc,
     Asset,
     AssetIntent,
     AudioFormat,
+    AudioFormatFamily,
     Build,
+    Chapter,
     DescribedImage,
     DownloadAccess,
     DownloadFormat,
@@ -18,18 +24,39 @@ use crate::{
     HeuristicAudioMeta,
     HtmlAndStripped,
     Link,
+    Lyrics,
+    PaymentPointer,
+    PREVIEW_DURATION_SECONDS,
+    PREVIEW_FORMAT,
+    ShareButton,
+    SiteAsset,
+    SiteMetadata,
     StreamingQuality,
     TagAgenda,
     TagMapping,
     Theme,
     Transcode,
+    TranscodeProcesses,
     TranscodesRcView,
-    util
+    Transcript,
+    util,
+    VisualLoop
 };
 use crate::util::generic_hash;
 
+/// With volume normalization, the player adjusts the playback volume of each
+/// track such that its integrated loudness comes out at this level (in LUFS).
+const NORMALIZATION_TARGET_LUFS: f32 = -14.0;
+
+/// Quiet tracks are only amplified as far as their true peak stays below
+/// this level (in dBTP), so that normalization never introduces clipping.
+const NORMALIZATION_TRUE_PEAK_CEILING_DBTP: f32 = -1.0;
+
 #[derive(Debug)]
 pub struct Track {
+    /// Shown as markers on the waveform, set through the track manifest's
+    /// annotations option
+    pub annotations: Vec<Annotation>,
     /// The final mapped artists (including metadata). Used in assembling the final page.
     pub artists: Vec<ArtistRc>,
     /// Names/aliases that should be mapped to this track, coming from the
@@ -39,6 +66,8 @@ pub struct Track {
     /// Generated when we gathered all artist and title metadata.
     /// Used to compute the download/stream asset filenames.
     pub asset_basename: Option<String>,
+    /// Set through the track manifest's chapters option, sorted by time
+    pub chapters: Vec<Chapter>,
     pub copy_link: bool,
     pub cover: Option<DescribedImage>,
     pub download_access: DownloadAccess,
@@ -48,12 +77,29 @@ pub struct Track {
     pub extras: Vec<Extra>,
     // TODO: Re-check if we need this post-creation (if not we don't need to store it on Track)
     pub heuristic_audio_meta: Option<HeuristicAudioMeta>,
+    /// Set through the track manifest's isrc option (in its canonical form),
+    /// written into the tags of transcodes
+    pub isrc: Option<String>,
     pub links: Vec<Link>,
+    /// Shown on the track page and written into the tags of transcodes
+    pub lyrics: Option<Lyrics>,
     pub more: Option<HtmlAndStripped>,
     /// Optional custom label for the button that (by default) says "More" on the
     /// track page and points to additional long-form content for the track.
     /// For tracks this label is also displayed in the track list on a release page.
     pub more_label: Option<String>,
+    /// Whether search engines are asked not to index the track page
+    /// (through the search_engines option)
+    pub noindex: bool,
+    /// Files that are written next to the track page, to be referenced
+    /// from page_metadata
+    pub page_assets: Vec<SiteAsset>,
+    /// Markup that is injected into the <head> of the track page only
+    pub page_metadata: Option<SiteMetadata>,
+    /// For Web Monetization of the streaming pages
+    pub payment_pointer: Option<PaymentPointer>,
+    /// Services for which share buttons are displayed on the track page
+    pub share_buttons: Vec<ShareButton>,
     /// Whether players should offer speed controls for this track
     pub speed_controls: bool,
     pub streaming_quality: StreamingQuality,
@@ -68,7 +114,19 @@ pub struct Track {
     /// file name itself (either as heuristic audio meta or taking the raw
     /// file name).
     title: Option<String>,
-    pub transcodes: TranscodesRcView
+    /// Set when a track on another (public) release has the same title, in
+    /// which case the title of the release is appended to the track's title
+    /// in search results (see Catalog::disambiguate_titles)
+    pub title_disambiguation: Option<String>,
+    pub transcodes: TranscodesRcView,
+    /// Shown as an expandable section on the track page, set through the
+    /// track manifest's transcript option
+    pub transcript: Option<Transcript>,
+    /// Shown in place of the cover on the track page
+    pub visual_loop: Option<VisualLoop>,
+    /// Whether players apply the pre-computed gain of this track (and let
+    /// the visitor toggle this) so that all tracks play at a similar loudness
+    pub volume_normalization: bool
 }
 
 impl Track {
@@ -80,13 +138,98 @@ impl Track {
             .map(|described_image| described_image.borrow().cover_160_filename_unchecked())
     }
 
+    /// Transcodes the hover preview of the track (to the cache), unless it
+    /// is already available there.
+    pub fn create_preview(&mut self, build: &Build) -> Result<(), String> {
+        let mut transcodes_mut = self.transcodes.borrow_mut();
+
+        if let Some(asset) = transcodes_mut.preview.as_mut() {
+            asset.unmark_stale();
+            return Ok(());
+        }
+
+        let track_duration = transcodes_mut.source_meta.duration_seconds;
+        let duration_seconds = PREVIEW_DURATION_SECONDS.min(track_duration);
+        let start_seconds = (track_duration / 3.0).min(track_duration - duration_seconds);
+
+        let target_filename = format!("{}{}", util::uid(), PREVIEW_FORMAT.extension());
+
+        let source_path = build.catalog_dir.join(&self.transcodes.file_meta.path);
+        let target_path = build.cache_dir.join(&target_filename);
+
+        info_transcoding!("{:?} to {} (hover preview)", self.transcodes.file_meta.path, PREVIEW_FORMAT);
+        build.timings.measure("Transcoding hover previews", || {
+            ffmpeg::transcode_excerpt(
+                &source_path,
+                &target_path,
+                PREVIEW_FORMAT,
+                start_seconds,
+                duration_seconds,
+                build.transcode_nice
+            )
+        })?;
+
+        let asset = Asset::new(build, target_filename, AssetIntent::Deliverable);
+        transcodes_mut.preview = Some(asset);
+
+        Ok(())
+    }
+
+    /// The file extension (including the dot) of this track's download in
+    /// the given format. For the original format this is the (lowercased)
+    /// extension of the source file.
+    pub fn download_extension(&self, format: AudioFormat) -> String {
+        match format {
+            AudioFormat::Original => match self.transcodes.file_meta.path.extension() {
+                Some(extension) => format!(".{}", extension.to_string_lossy().to_lowercase()),
+                None => String::new()
+            }
+            _ => format.extension().to_string()
+        }
+    }
+
     pub fn download_assets_available(&self) -> bool {
         !self.download_formats.is_empty() ||
         (self.extra_downloads && !self.extras.is_empty())
     }
 
+    /// Whether a direct link to the downloads is revealed while Web
+    /// Monetization is active (only applies to downloads that are otherwise
+    /// behind a paycurtain - the download page of code-protected downloads
+    /// must never be linked to from a public page).
+    pub fn monetization_unlocks_downloads(&self) -> bool {
+        self.payment_pointer
+            .as_ref()
+            .is_some_and(|payment_pointer| payment_pointer.unlock_downloads) &&
+        self.download_assets_available() &&
+        matches!(
+            self.download_access,
+            DownloadAccess::Paycurtain { payment_info: Some(_), .. }
+        )
+    }
+
+    /// The linear gain factor the player applies to this track when volume
+    /// normalization is active. None if the loudness of the track could not
+    /// be determined (e.g. because it is silent).
+    pub fn normalization_gain(&self) -> Option<f32> {
+        let transcodes_ref = self.transcodes.borrow();
+        let loudness = &transcodes_ref.source_meta.loudness;
+
+        let integrated_lufs = loudness.integrated_lufs?;
+        let mut gain_db = NORMALIZATION_TARGET_LUFS - integrated_lufs;
+
+        if loudness.true_peak_dbtp.is_finite() {
+            let headroom_db = (NORMALIZATION_TRUE_PEAK_CEILING_DBTP - loudness.true_peak_dbtp).max(0.0);
+            gain_db = gain_db.min(headroom_db);
+        }
+
+        Some(10f32.powf(gain_db / 20.0))
+    }
+
     pub fn new(
+        annotations: Vec<Annotation>,
         artists_to_map: Vec<String>,
+        chapters: Vec<Chapter>,
         copy_link: bool,
         cover: Option<DescribedImage>,
         download_access: DownloadAccess,
@@ -94,21 +237,33 @@ impl Track {
         embedding: bool,
         extra_downloads: bool,
         extras: Vec<Extra>,
+        isrc: Option<String>,
         links: Vec<Link>,
+        lyrics: Option<Lyrics>,
         more: Option<HtmlAndStripped>,
         more_label: Option<String>,
+        noindex: bool,
+        page_assets: Vec<SiteAsset>,
+        page_metadata: Option<SiteMetadata>,
+        payment_pointer: Option<PaymentPointer>,
+        share_buttons: Vec<ShareButton>,
         speed_controls: bool,
         streaming_quality: StreamingQuality,
         synopsis: Option<String>,
         tag_agenda: TagAgenda,
         theme: Theme,
         title: Option<String>,
-        transcodes: TranscodesRcView
+        transcodes: TranscodesRcView,
+        transcript: Option<Transcript>,
+        visual_loop: Option<VisualLoop>,
+        volume_normalization: bool
     ) -> Track {
         Track {
+            annotations,
             artists: Vec::new(),
             artists_to_map,
             asset_basename: None,
+            chapters,
             copy_link,
             cover,
             download_access,
@@ -117,16 +272,27 @@ impl Track {
             extra_downloads,
             extras,
             heuristic_audio_meta: None,
+            isrc,
             links,
+            lyrics,
             more,
             more_label,
+            noindex,
+            page_assets,
+            page_metadata,
+            payment_pointer,
+            share_buttons,
             speed_controls,
             streaming_quality,
             synopsis,
             tag_agenda,
             title,
+            title_disambiguation: None,
             transcodes,
-            theme
+            theme,
+            transcript,
+            visual_loop,
+            volume_normalization
         }
     }
 
@@ -150,6 +316,14 @@ impl Track {
         }
     }
 
+    /// The title, with the disambiguation appended if there is one
+    pub fn title_disambiguated(&self) -> String {
+        match &self.title_disambiguation {
+            Some(disambiguation) => format!("{} ({disambiguation})", self.title()),
+            None => self.title()
+        }
+    }
+
     pub fn transcode_as(
         &mut self,
         target_format: AudioFormat,
@@ -165,20 +339,154 @@ impl Track {
                 transcode.asset.unmark_stale();
             }
         } else {
-            let target_filename = format!("{}{}", util::uid(), target_format.extension());
-
-            info_transcoding!("{:?} to {}", self.transcodes.file_meta.path, target_format);
-            ffmpeg::transcode(
-                cover_path,
-                &build.catalog_dir.join(&self.transcodes.file_meta.path),
-                &build.cache_dir.join(&target_filename),
-                transcodes_mut.source_meta.format_family,
-                target_format,
-                tag_mapping
-            ).unwrap();
+            let target_filename = format!("{}{}", util::uid(), self.download_extension(target_format));
+
+            let source_path = build.catalog_dir.join(&self.transcodes.file_meta.path);
+            let target_path = build.cache_dir.join(&target_filename);
+
+            // A FLAC source that is requested as FLAC with its tags unchanged
+            // is already exactly what we would produce, so we copy it as-is
+            // instead of running it through ffmpeg. The resulting transcode
+            // is cached under the same signature (format and tag mapping)
+            // as a regular one, as it is equivalent.
+            let passthrough = is_passthrough(transcodes_mut.source_meta.format_family, target_format, tag_mapping);
+
+            if target_format == AudioFormat::Original {
+                info_transcoding!("{:?} as original (copying the source file verbatim)", self.transcodes.file_meta.path);
+                build.timings.measure("Copying originals", || {
+                    fs::copy(&source_path, &target_path).unwrap();
+                });
+            } else if passthrough {
+                info_transcoding!("{:?} to {} (passthrough, copying the source file)", self.transcodes.file_meta.path, target_format);
+                build.timings.measure("Passthrough copying", || {
+                    fs::copy(&source_path, &target_path).unwrap();
+                });
+            } else {
+                info_transcoding!("{:?} to {}", self.transcodes.file_meta.path, target_format);
+                build.timings.measure(&format!("Transcoding to {target_format}"), || {
+                    ffmpeg::transcode(
+                        cover_path,
+                        &source_path,
+                        &target_path,
+                        transcodes_mut.source_meta.format_family,
+                        target_format,
+                        tag_mapping,
+                        build.transcode_nice
+                    ).unwrap();
+                });
+            }
 
             let asset = Asset::new(build, target_filename, asset_intent);
             transcodes_mut.formats.push(Transcode::new(asset, target_format, generic_hash(tag_mapping)));
         }
     }
+
+    /// Transcodes the given tracks (each with its tag mapping and the cover to
+    /// embed) to the given format, running as many ffmpeg processes at the
+    /// same time as configured for the format (see TranscodeProcesses).
+    /// Tracks for which the transcode is already available, or for which the
+    /// source file is copied instead (see transcode_as), are skipped, calling
+    /// transcode_as afterwards is still required for all tracks.
+    pub fn transcode_in_parallel(
+        build: &Build,
+        asset_intent: AssetIntent,
+        target_format: AudioFormat,
+        tracks: Vec<(&mut Track, &TagMapping, Option<&PathBuf>)>
+    ) {
+        struct Job {
+            cover_path: Option<PathBuf>,
+            source_format_family: AudioFormatFamily,
+            source_path: PathBuf,
+            tag_mapping: TagMapping,
+            target_path: PathBuf
+        }
+
+        let mut jobs = Vec::new();
+        let mut pending = Vec::new();
+
+        for (track, tag_mapping, cover_path) in tracks {
+            let source_format_family = track.transcodes.borrow().source_meta.format_family;
+
+            if target_format == AudioFormat::Original ||
+                is_passthrough(source_format_family, target_format, tag_mapping) ||
+                track.transcodes.borrow().has(target_format, generic_hash(tag_mapping)) {
+                continue;
+            }
+
+            let target_filename = format!("{}{}", util::uid(), track.download_extension(target_format));
+
+            info_transcoding!("{:?} to {}", track.transcodes.file_meta.path, target_format);
+
+            jobs.push(Job {
+                cover_path: cover_path.cloned(),
+                source_format_family,
+                source_path: build.catalog_dir.join(&track.transcodes.file_meta.path),
+                tag_mapping: tag_mapping.clone(),
+                target_path: build.cache_dir.join(&target_filename)
+            });
+
+            pending.push((track, tag_mapping, target_filename));
+        }
+
+        if jobs.is_empty() {
+            return;
+        }
+
+        let jobs = &jobs;
+        let next_job = &AtomicUsize::new(0);
+        let nice = build.transcode_nice;
+        let processes = TranscodeProcesses::for_format(&build.transcode_processes, target_format).min(jobs.len());
+
+        // Each process takes on the next job that is not yet taken until none
+        // are left, the results are collected by job index.
+        let mut results = build.timings.measure(&format!("Transcoding to {target_format}"), || {
+            thread::scope(|scope| {
+                let workers: Vec<_> = (0..processes)
+                    .map(|_| scope.spawn(move || {
+                        let mut results = Vec::new();
+
+                        loop {
+                            let index = next_job.fetch_add(1, Ordering::Relaxed);
+                            let Some(job) = jobs.get(index) else { break };
+
+                            let result = ffmpeg::transcode(
+                                job.cover_path.as_ref(),
+                                &job.source_path,
+                                &job.target_path,
+                                job.source_format_family,
+                                target_format,
+                                &job.tag_mapping,
+                                nice
+                            );
+
+                            results.push((index, result));
+                        }
+
+                        results
+                    }))
+                    .collect();
+
+                workers
+                    .into_iter()
+                    .flat_map(|worker| worker.join().unwrap())
+                    .collect::<Vec<_>>()
+            })
+        });
+
+        results.sort_by_key(|(index, _)| *index);
+
+        for ((track, tag_mapping, target_filename), (_, result)) in pending.into_iter().zip(results) {
+            result.unwrap();
+
+            let asset = Asset::new(build, target_filename, asset_intent);
+            track.transcodes.borrow_mut().formats.push(Transcode::new(asset, target_format, generic_hash(tag_mapping)));
+        }
+    }
+}
+
+/// See the comment on passthrough in Track::transcode_as
+fn is_passthrough(source_format_family: AudioFormatFamily, target_format: AudioFormat, tag_mapping: &TagMapping) -> bool {
+    source_format_family == AudioFormatFamily::Flac &&
+        target_format == AudioFormat::Flac &&
+        matches!(tag_mapping, TagMapping::Copy)
 }
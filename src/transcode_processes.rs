@@ -0,0 +1,82 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! How many ffmpeg processes may transcode audio at the same time, set
+//! through --transcode-processes either for all formats (e.g. "4") or for a
+//! single format (e.g. "flac=8"). Without any setting, as many processes run
+//! as there are CPUs - most audio encoders use a single thread, so this keeps
+//! all CPUs busy without oversubscribing them.
+
+use std::str::FromStr;
+use std::thread;
+
+use crate::AudioFormat;
+
+/// All formats that are transcoded with ffmpeg (i.e. all but the original
+/// format), these are referred to by their asset dirname (e.g. "mp3-v5").
+const TRANSCODED_FORMATS: &[AudioFormat] = &[
+    AudioFormat::Aac,
+    AudioFormat::Aiff,
+    AudioFormat::Alac,
+    AudioFormat::Flac,
+    AudioFormat::Mp3VbrV0,
+    AudioFormat::Mp3VbrV5,
+    AudioFormat::Mp3VbrV7,
+    AudioFormat::OggVorbis,
+    AudioFormat::Opus48Kbps,
+    AudioFormat::Opus96Kbps,
+    AudioFormat::Opus128Kbps,
+    AudioFormat::Wav
+];
+
+#[derive(Clone, Debug)]
+pub struct TranscodeProcesses {
+    /// None means the setting applies to all formats
+    format: Option<AudioFormat>,
+    processes: usize
+}
+
+impl TranscodeProcesses {
+    /// The number of processes for the given format. Of several settings that
+    /// apply to the format the last one wins, without any the number of CPUs
+    /// is returned.
+    pub fn for_format(settings: &[TranscodeProcesses], format: AudioFormat) -> usize {
+        settings
+            .iter()
+            .rev()
+            .find(|setting| setting.format.is_none_or(|setting_format| setting_format == format))
+            .map(|setting| setting.processes)
+            .unwrap_or_else(|| thread::available_parallelism().map(|cpus| cpus.get()).unwrap_or(1))
+    }
+}
+
+impl FromStr for TranscodeProcesses {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<TranscodeProcesses, String> {
+        let (format, processes) = match value.split_once('=') {
+            Some((dirname, processes)) => {
+                let format = TRANSCODED_FORMATS
+                    .iter()
+                    .find(|format| format.asset_dirname() == dirname.trim())
+                    .ok_or_else(|| {
+                        let dirnames = TRANSCODED_FORMATS
+                            .iter()
+                            .map(|format| format.asset_dirname())
+                            .collect::<Vec<&str>>()
+                            .join(", ");
+
+                        format!("'{dirname}' is not a format faircamp transcodes to (available formats: {dirnames})")
+                    })?;
+
+                (Some(*format), processes)
+            }
+            None => (None, value)
+        };
+
+        match processes.trim().parse::<usize>() {
+            Ok(processes) if processes > 0 => Ok(TranscodeProcesses { format, processes }),
+            _ => Err(format!("'{processes}' is not a valid number of processes (it needs to be 1 or more)"))
+        }
+    }
+}
@@ -19,6 +19,21 @@ use crate::{
 };
 use crate::util::url_safe_base64;
 
+/// Hover previews (see the catalog's hover_previews option) are excerpts of
+/// this length, starting a third into the track.
+pub const PREVIEW_DURATION_SECONDS: f32 = 10.0;
+pub const PREVIEW_FORMAT: AudioFormat = AudioFormat::Opus48Kbps;
+
+/// A format comparison clip (see the release's format_comparison option),
+/// i.e. an excerpt of the track transcoded to a specific format.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Clip {
+    pub asset: Asset,
+    pub format: AudioFormat,
+    /// This is a hash computed from the start and duration of the excerpt
+    pub excerpt_signature: u64
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Transcode {
     pub asset: Asset,
@@ -34,8 +49,14 @@ pub struct Transcode {
 /// transcoded versions (formats).
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Transcodes {
+    /// Format comparison clips, only generated for the track that a release's
+    /// format_comparison option refers to
+    pub clips: Vec<Clip>,
     pub formats: Vec<Transcode>,
     pub hash: SourceHash,
+    /// A short excerpt played back when hovering/focusing the release on the
+    /// homepage, only generated if hover previews are enabled
+    pub preview: Option<Asset>,
     pub source_meta: AudioMeta,
     pub views: Vec<View>
 }
@@ -51,6 +72,20 @@ pub struct TranscodesRcView {
     transcodes: TranscodesRc
 }
 
+impl Clip {
+    pub fn new(
+        asset: Asset,
+        excerpt_signature: u64,
+        format: AudioFormat
+    ) -> Clip {
+        Clip {
+            asset,
+            excerpt_signature,
+            format
+        }
+    }
+}
+
 impl Transcode {
     pub fn new(
         asset: Asset,
@@ -70,7 +105,7 @@ impl Transcodes {
     /// (or underlying structs that are contained within). This automatically
     /// informs the cache not to try to deserialize manifests that hold old,
     /// incompatible data.
-    pub const CACHE_SERIALIZATION_KEY: &'static str = "transcodes3";
+    pub const CACHE_SERIALIZATION_KEY: &'static str = "transcodes7";
 
     pub fn deserialize_cached(path: &Path) -> Option<Transcodes> {
         match fs::read(path) {
@@ -108,10 +143,18 @@ impl Transcodes {
     }
 
     pub fn mark_all_stale(&mut self, timestamp: &DateTime<Utc>) {
+        for clip in self.clips.iter_mut() {
+            clip.asset.mark_stale(timestamp);
+        }
+
         for transcode in self.formats.iter_mut() {
             transcode.asset.mark_stale(timestamp);
         }
 
+        if let Some(asset) = self.preview.as_mut() {
+            asset.mark_stale(timestamp);
+        }
+
         for view in self.views.iter_mut() {
             view.mark_stale(timestamp);
         }
@@ -123,8 +166,10 @@ impl Transcodes {
         source_meta: AudioMeta
     ) -> Transcodes {
         Transcodes {
+            clips: Vec::new(),
             formats: Vec::new(),
             hash,
+            preview: None,
             source_meta,
             views: vec![View::new(file_meta)]
         }
@@ -0,0 +1,201 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! A transcript of a track (mostly of interest for spoken word, such as
+//! podcasts), given either as plain text or as WebVTT. It is rendered as an
+//! expandable section on the track page, published next to the track page
+//! (so that the podcast feed can reference it) and offered as an extra in
+//! the downloads.
+
+use std::fs;
+use std::mem;
+use std::path::{Path, PathBuf};
+
+use crate::util::parse_timecode;
+
+/// Configured through the track manifest's `transcript` option.
+#[derive(Clone, Debug)]
+pub struct Transcript {
+    /// The content of the source file as it is published
+    pub content: String,
+    pub cues: Vec<TranscriptCue>,
+    pub format: TranscriptFormat,
+    /// Relative to the catalog directory
+    pub path: PathBuf
+}
+
+/// A paragraph of the transcript. For WebVTT these are the cues of the file
+/// (which all have a start time), for plain text these are the paragraphs
+/// of the text (which have no start time).
+#[derive(Clone, Debug)]
+pub struct TranscriptCue {
+    pub seconds: Option<f32>,
+    pub text: String
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum TranscriptFormat {
+    PlainText,
+    WebVtt
+}
+
+impl Transcript {
+    /// The filename under which the transcript is published in the track
+    /// directory.
+    pub fn filename(&self) -> &'static str {
+        match self.format {
+            TranscriptFormat::PlainText => "transcript.txt",
+            TranscriptFormat::WebVtt => "transcript.vtt"
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self.format {
+            TranscriptFormat::PlainText => "text/plain",
+            TranscriptFormat::WebVtt => "text/vtt"
+        }
+    }
+
+    /// Reads and parses the transcript at the given absolute path, the
+    /// format is determined by the file extension (".vtt" for WebVTT,
+    /// anything else is read as plain text).
+    pub fn read(catalog_dir: &Path, absolute_path: &Path) -> Result<Transcript, String> {
+        let content = fs::read_to_string(absolute_path)
+            .map_err(|err| format!("The transcript could not be read ({err})"))?;
+
+        let is_vtt = absolute_path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("vtt"));
+
+        let (cues, format) = if is_vtt {
+            (parse_webvtt(&content)?, TranscriptFormat::WebVtt)
+        } else {
+            (parse_plain_text(&content), TranscriptFormat::PlainText)
+        };
+
+        if cues.is_empty() {
+            return Err(String::from("The transcript contains no text"));
+        }
+
+        Ok(Transcript {
+            content,
+            cues,
+            format,
+            path: absolute_path.strip_prefix(catalog_dir).unwrap().to_path_buf()
+        })
+    }
+
+    /// Writes the transcript into the track directory.
+    pub fn write(&self, track_dir: &Path) {
+        fs::write(track_dir.join(self.filename()), &self.content).unwrap();
+    }
+}
+
+/// Paragraphs are separated by (at least) one empty line.
+fn parse_plain_text(content: &str) -> Vec<TranscriptCue> {
+    paragraphs(content)
+        .into_iter()
+        .map(|lines| TranscriptCue {
+            seconds: None,
+            text: lines.join("\n")
+        })
+        .collect()
+}
+
+/// Only the start time and text of each cue are read, cue identifiers,
+/// settings, NOTE/STYLE/REGION blocks and markup (e.g. voice spans) are
+/// skipped. For voice spans (`<v Alice>`) the name of the speaker is
+/// prefixed to the text though.
+fn parse_webvtt(content: &str) -> Result<Vec<TranscriptCue>, String> {
+    let content = content.trim_start_matches('\u{feff}');
+
+    if !content.starts_with("WEBVTT") {
+        return Err(String::from("A WebVTT transcript needs to start with the line 'WEBVTT'"));
+    }
+
+    let mut cues = Vec::new();
+
+    // The first paragraph is the header
+    for lines in paragraphs(content).into_iter().skip(1) {
+        let Some(timing_index) = lines.iter().position(|line| line.contains("-->")) else {
+            continue;
+        };
+
+        // Timestamps are given as "mm:ss.ttt" or "hh:mm:ss.ttt"
+        let start = lines[timing_index].split("-->").next().unwrap();
+        let Some(seconds) = parse_timecode(start) else {
+            return Err(format!("The cue timing '{}' could not be read", lines[timing_index]));
+        };
+
+        let text = lines[(timing_index + 1)..]
+            .iter()
+            .map(|line| strip_markup(line))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        if !text.is_empty() {
+            cues.push(TranscriptCue { seconds: Some(seconds), text });
+        }
+    }
+
+    Ok(cues)
+}
+
+/// Groups the non-empty lines of content into paragraphs, with empty lines
+/// marking the boundaries between them.
+fn paragraphs(content: &str) -> Vec<Vec<&str>> {
+    let mut paragraphs = Vec::new();
+    let mut current = Vec::new();
+
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(mem::take(&mut current));
+            }
+        } else {
+            current.push(line);
+        }
+    }
+
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+
+    paragraphs
+}
+
+/// Removes tags from a line of cue text, except for the speaker of a voice
+/// span, which is kept as "Speaker: ".
+fn strip_markup(line: &str) -> String {
+    let mut result = String::new();
+    let mut remainder = line;
+
+    while let Some(tag_begin) = remainder.find('<') {
+        result.push_str(&remainder[..tag_begin]);
+
+        let Some(tag_end) = remainder[tag_begin..].find('>') else {
+            remainder = &remainder[tag_begin..];
+            break;
+        };
+
+        let tag = &remainder[(tag_begin + 1)..(tag_begin + tag_end)];
+        // Voice spans can carry classes (<v.loud Alice>), the name of the
+        // speaker always follows the first space though.
+        if tag.starts_with("v ") || tag.starts_with("v.") {
+            if let Some((_, speaker)) = tag.split_once(' ') {
+                result.push_str(speaker.trim());
+                result.push_str(": ");
+            }
+        }
+
+        remainder = &remainder[(tag_begin + tag_end + 1)..];
+    }
+
+    result.push_str(remainder);
+
+    result
+        .replace("&gt;", ">")
+        .replace("&lt;", "<")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use std::ffi::OsString;
+use std::fmt::Display;
 use std::fs;
 use std::hash::{DefaultHasher, Hash, Hasher};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use base64::Engine;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
@@ -15,6 +16,20 @@ const BYTES_MB: u64 = 1024 * BYTES_KB;
 const BYTES_GB: u64 = 1024 * BYTES_MB; 
 const SECONDS_HOUR: u32 = 60 * 60;
 
+/// Collects all files below dir (recursively), as paths relative to base_dir.
+pub fn collect_files(base_dir: &Path, dir: &Path, files: &mut Vec<PathBuf>) {
+    if let Ok(dir_entries) = dir.read_dir() {
+        for dir_entry in dir_entries.flatten() {
+            let path = dir_entry.path();
+            if path.is_dir() {
+                collect_files(base_dir, &path, files);
+            } else {
+                files.push(path.strip_prefix(base_dir).unwrap().to_path_buf());
+            }
+        }
+    }
+}
+
 /// Takes an existing filename (that has elsewhere been identified to collide
 /// with another file with the same filename) and returns the filename,
 /// systematically altered as to resolve the existing collision and ideally
@@ -149,6 +164,61 @@ pub fn html_escape_outside_attribute(string: &str) -> String {
           .replace('>', "&gt;")
 }
 
+/// Escapes a string for use inside a (double-quoted) json string literal
+pub fn json_escape(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+
+    for character in string.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            character if character.is_control() => escaped.push_str(&format!("\\u{:04x}", character as u32)),
+            character => escaped.push(character)
+        }
+    }
+
+    escaped
+}
+
+/// A number as json, or null
+pub fn json_optional_number(number: Option<impl Display>) -> String {
+    match number {
+        Some(number) => number.to_string(),
+        None => String::from("null")
+    }
+}
+
+/// A (double-quoted, escaped) json string, or null
+pub fn json_optional_string(string: Option<&str>) -> String {
+    match string {
+        Some(string) => format!("\"{}\"", json_escape(string)),
+        None => String::from("null")
+    }
+}
+
+/// An array of (double-quoted, escaped) json strings
+pub fn json_string_array(strings: &[String]) -> String {
+    let joined = strings
+        .iter()
+        .map(|string| format!("\"{}\"", json_escape(string)))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!("[{joined}]")
+}
+
+/// Parses a timecode given as seconds ("90"), minutes and seconds
+/// ("1:30") or hours, minutes and seconds ("1:01:30").
+pub fn parse_timecode(value: &str) -> Option<f32> {
+    value
+        .split(':')
+        .try_fold((0.0, 0), |(seconds, parts), part| {
+            let part_seconds = part.trim().parse::<f32>().ok()?;
+            (part_seconds >= 0.0 && parts < 3).then_some((seconds * 60.0 + part_seconds, parts + 1))
+        })
+        .map(|(seconds, _parts)| seconds)
+}
+
 /// Efficient, reusable implementation of the annoying OsString to String conversion
 pub fn string_from_os(os_string: OsString) -> String {
     match os_string.into_string() {
@@ -0,0 +1,287 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! A visual loop is a short, silent, looping video (or an image sequence
+//! that is turned into one) which is shown in place of the cover on a track
+//! page. The page itself only references a still frame of the loop (the
+//! poster), the video is loaded in by the player script only if the visitor
+//! does not prefer reduced motion and has not requested reduced data usage.
+
+use std::cell::{Ref, RefCell, RefMut};
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    Asset,
+    AssetIntent,
+    Build,
+    SourceHash,
+    ffmpeg,
+    util
+};
+use crate::util::{uid, url_safe_base64};
+
+/// The loop and its poster are written to this directory inside the track
+/// directory
+pub const VISUAL_LOOP_DIRNAME: &str = "visual-loop";
+
+pub const VISUAL_LOOP_POSTER_FILENAME: &str = "poster.jpg";
+pub const VISUAL_LOOP_VIDEO_FILENAME: &str = "loop.mp4";
+
+/// Longer sources are cut off at this duration
+const MAX_DURATION_SECONDS: f32 = 15.0;
+
+/// The video is scaled down (never up) so that its longer edge does not
+/// exceed this size
+const MAX_EDGE_SIZE: u32 = 720;
+
+/// Configured through the track manifest's `visual_loop` option.
+#[derive(Clone, Debug)]
+pub struct VisualLoop {
+    pub description: Option<String>,
+    pub source: VisualLoopSource
+}
+
+/// The encoded loop and its poster, cached under a signature that is
+/// computed from the content of the source file(s) and the encoding
+/// parameters (see VisualLoop::signature).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VisualLoopAssets {
+    pub poster: Asset,
+    pub signature: u64,
+    pub video: Asset
+}
+
+#[derive(Clone, Debug)]
+pub struct VisualLoopAssetsRc {
+    visual_loop_assets: Rc<RefCell<VisualLoopAssets>>
+}
+
+#[derive(Clone, Debug)]
+pub enum VisualLoopSource {
+    /// A directory of images that are played back as frames in the
+    /// (alphabetical) order of their filenames. The path is relative to
+    /// the catalog directory.
+    ImageSequence { framerate: u32, path: PathBuf },
+    /// A video file, the path is relative to the catalog directory.
+    Video(PathBuf)
+}
+
+impl VisualLoop {
+    /// Image sequences are played back at this rate (frames per second)
+    /// unless specified otherwise
+    pub const DEFAULT_FRAMERATE: u32 = 12;
+
+    /// Encodes the loop and extracts its poster into the cache directory.
+    pub fn encode(&self, build: &Build, signature: u64) -> Result<VisualLoopAssets, String> {
+        let video_filename = format!("{}.mp4", uid());
+        let poster_filename = format!("{}.jpg", uid());
+
+        let video_path = build.cache_dir.join(&video_filename);
+        let poster_path = build.cache_dir.join(&poster_filename);
+
+        let result = build.timings.measure("Encoding visual loops", || {
+            match &self.source {
+                VisualLoopSource::ImageSequence { framerate, path } => {
+                    let frames = image_sequence_frames(&build.catalog_dir.join(path))?;
+
+                    info_transcoding!("{:?} ({} images) to visual loop", path, frames.len());
+
+                    // The frames are passed to ffmpeg through its concat
+                    // demuxer, this works the same on all platforms (as
+                    // opposed to glob patterns) and does not require the
+                    // images to follow a numbering scheme.
+                    let frame_duration = 1.0 / *framerate as f32;
+                    let frame_list = frames
+                        .iter()
+                        .map(|frame| {
+                            let escaped = frame.to_string_lossy().replace('\'', "'\\''");
+                            format!("file '{escaped}'\nduration {frame_duration:.6}\n")
+                        })
+                        .collect::<String>();
+
+                    let frame_list_path = build.cache_dir.join(format!("{}.txt", uid()));
+                    fs::write(&frame_list_path, frame_list).map_err(|err| err.to_string())?;
+
+                    let result = ffmpeg::encode_visual_loop(
+                        &frame_list_path,
+                        true,
+                        &video_path,
+                        MAX_DURATION_SECONDS,
+                        MAX_EDGE_SIZE,
+                        build.transcode_nice
+                    );
+
+                    let _ = fs::remove_file(&frame_list_path);
+
+                    result
+                }
+                VisualLoopSource::Video(path) => {
+                    info_transcoding!("{:?} to visual loop", path);
+
+                    ffmpeg::encode_visual_loop(
+                        &build.catalog_dir.join(path),
+                        false,
+                        &video_path,
+                        MAX_DURATION_SECONDS,
+                        MAX_EDGE_SIZE,
+                        build.transcode_nice
+                    )
+                }
+            }?;
+
+            ffmpeg::extract_poster_frame(&video_path, &poster_path, build.transcode_nice)
+        });
+
+        if let Err(err) = result {
+            // Whatever was written before the error is of no use
+            let _ = fs::remove_file(&video_path);
+            let _ = fs::remove_file(&poster_path);
+            return Err(err);
+        }
+
+        Ok(VisualLoopAssets {
+            poster: Asset::new(build, poster_filename, AssetIntent::Deliverable),
+            signature,
+            video: Asset::new(build, video_filename, AssetIntent::Deliverable)
+        })
+    }
+
+    pub fn new(description: Option<String>, source: VisualLoopSource) -> VisualLoop {
+        VisualLoop {
+            description,
+            source
+        }
+    }
+
+    /// Hashes the content of the source file(s) along with the parameters
+    /// the loop is encoded with, this identifies the encoded loop in the
+    /// cache.
+    pub fn signature(&self, build: &Build) -> Result<u64, String> {
+        let mut hasher = DefaultHasher::new();
+
+        let paths = match &self.source {
+            VisualLoopSource::ImageSequence { framerate, path } => {
+                "image_sequence".hash(&mut hasher);
+                framerate.hash(&mut hasher);
+                image_sequence_frames(&build.catalog_dir.join(path))?
+            }
+            VisualLoopSource::Video(path) => {
+                "video".hash(&mut hasher);
+                let video_path = build.catalog_dir.join(path);
+                if !video_path.is_file() {
+                    return Err(format!("The visual loop video {} does not exist", video_path.display()));
+                }
+                vec![video_path]
+            }
+        };
+
+        for path in paths {
+            SourceHash::new(&path).value.hash(&mut hasher);
+        }
+
+        // f32 does not implement Hash, we hash the bit pattern instead
+        MAX_DURATION_SECONDS.to_bits().hash(&mut hasher);
+        MAX_EDGE_SIZE.hash(&mut hasher);
+
+        Ok(hasher.finish())
+    }
+}
+
+/// All image files in the directory, sorted by filename
+fn image_sequence_frames(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut frames = fs::read_dir(dir)
+        .map_err(|err| format!("The image sequence directory {} could not be read ({err})", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| {
+                    matches!(
+                        extension.to_lowercase().as_str(),
+                        "gif" | "jpeg" | "jpg" | "png" | "webp"
+                    )
+                })
+        })
+        .collect::<Vec<PathBuf>>();
+
+    if frames.is_empty() {
+        return Err(format!("The image sequence directory {} contains no images", dir.display()));
+    }
+
+    frames.sort();
+
+    Ok(frames)
+}
+
+impl VisualLoopAssets {
+    /// Increase version on each change to the data layout of
+    /// [VisualLoopAssets]. This automatically informs the cache not to try
+    /// to deserialize manifests that hold old, incompatible data.
+    pub const CACHE_SERIALIZATION_KEY: &'static str = "visual_loop1";
+
+    pub fn deserialize_cached(path: &Path) -> Option<VisualLoopAssets> {
+        match fs::read(path) {
+            Ok(bytes) => bincode::deserialize::<VisualLoopAssets>(&bytes).ok(),
+            Err(_) => None
+        }
+    }
+
+    pub fn manifest_path(&self, cache_dir: &Path) -> PathBuf {
+        let manifest_filename = format!("{}.{}.bincode", url_safe_base64(self.signature), VisualLoopAssets::CACHE_SERIALIZATION_KEY);
+        cache_dir.join(manifest_filename)
+    }
+
+    pub fn mark_all_stale(&mut self, timestamp: &DateTime<Utc>) {
+        self.poster.mark_stale(timestamp);
+        self.video.mark_stale(timestamp);
+    }
+
+    pub fn persist_to_cache(&self, cache_dir: &Path) {
+        let serialized = bincode::serialize(self).unwrap();
+        fs::write(self.manifest_path(cache_dir), serialized).unwrap();
+    }
+
+    pub fn unmark_stale(&mut self) {
+        self.poster.unmark_stale();
+        self.video.unmark_stale();
+    }
+
+    /// Links (or copies) the loop and its poster from the cache into the
+    /// track directory.
+    pub fn write_to_build(&self, build: &Build, track_dir: &Path) {
+        let visual_loop_dir = track_dir.join(VISUAL_LOOP_DIRNAME);
+        util::ensure_dir_all(&visual_loop_dir);
+
+        util::hard_link_or_copy(
+            build.cache_dir.join(&self.poster.filename),
+            visual_loop_dir.join(VISUAL_LOOP_POSTER_FILENAME)
+        );
+
+        util::hard_link_or_copy(
+            build.cache_dir.join(&self.video.filename),
+            visual_loop_dir.join(VISUAL_LOOP_VIDEO_FILENAME)
+        );
+    }
+}
+
+impl VisualLoopAssetsRc {
+    pub fn borrow(&self) -> Ref<'_, VisualLoopAssets> {
+        self.visual_loop_assets.borrow()
+    }
+
+    pub fn borrow_mut(&self) -> RefMut<'_, VisualLoopAssets> {
+        self.visual_loop_assets.borrow_mut()
+    }
+
+    pub fn new(visual_loop_assets: VisualLoopAssets) -> VisualLoopAssetsRc {
+        VisualLoopAssetsRc {
+            visual_loop_assets: Rc::new(RefCell::new(visual_loop_assets))
+        }
+    }
+}
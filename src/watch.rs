@@ -0,0 +1,143 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! With --watch faircamp keeps running after the build, watching the catalog
+//! directory for changes and rebuilding the site whenever something changed.
+//! As the cache is reused across builds, a rebuild only processes what
+//! actually changed. With --preview, the preview server is started only once
+//! and keeps serving the build directory across rebuilds.
+
+use std::env;
+use std::path::{self, Component, Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use crate::{Args, Builder, server};
+
+/// Changes usually come in bursts (an editor saving a file through a
+/// temporary file, many files being copied into the catalog, etc.), so we
+/// only rebuild once no further change was seen for this long.
+const SETTLE_MILLISECONDS: u64 = 300;
+
+/// Whether a change at this path could affect the build. Hidden files and
+/// directories are ignored by faircamp (this also covers the default build
+/// and cache directories, as well as reports faircamp writes into the
+/// catalog directory), and so are custom build and cache directories.
+fn affects_build(catalog_dir: &Path, ignored_dirs: &[PathBuf], path: &Path) -> bool {
+    if ignored_dirs.iter().any(|dir| path.starts_with(dir)) {
+        return false;
+    }
+
+    let Ok(path_relative_to_catalog) = path.strip_prefix(catalog_dir) else {
+        return false;
+    };
+
+    !path_relative_to_catalog
+        .components()
+        .any(|component| match component {
+            Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+            _ => false
+        })
+}
+
+fn build(args: &Args) {
+    // All messages have already been printed during the build, and
+    // regardless of its outcome we keep watching.
+    let _ = Builder::from_args(args.clone()).build();
+}
+
+pub fn run(args: Args) -> ExitCode {
+    if args.deploy {
+        error!("The options --deploy and --watch can not be combined.");
+        return ExitCode::FAILURE;
+    }
+
+    // The paths reported for changes are based on the watched path, so all
+    // paths are made absolute in order to reliably compare them.
+    let catalog_dir = match &args.catalog_dir {
+        Some(catalog_dir) => path::absolute(catalog_dir).unwrap(),
+        None => env::current_dir().expect("Current working directory can not be determined or is unaccessible")
+    };
+
+    let build_dir = match &args.build_dir {
+        Some(build_dir) => path::absolute(build_dir).unwrap(),
+        None => catalog_dir.join(".faircamp_build")
+    };
+
+    let cache_dir = match &args.cache_dir {
+        Some(cache_dir) => path::absolute(cache_dir).unwrap(),
+        None => catalog_dir.join(".faircamp_cache")
+    };
+
+    let ignored_dirs = [&args.build_dir, &args.cache_dir]
+        .into_iter()
+        .flatten()
+        .map(|dir| path::absolute(dir).unwrap())
+        .collect::<Vec<PathBuf>>();
+
+    // Previewing is taken care of here instead of after each build, as the
+    // preview should outlive the builds.
+    let mut build_args = args.clone();
+    build_args.preview = false;
+
+    build(&build_args);
+
+    if args.preview {
+        if args.no_clean_urls && !args.theming_widget {
+            let local_file_url = build_dir.join("index.html");
+            if webbrowser::open(&local_file_url.to_string_lossy()).is_err() {
+                error!("Could not open browser for previewing the site");
+            }
+        } else {
+            let (ip, port) = (args.preview_ip, args.preview_port);
+            thread::spawn(move || server::serve_preview(&build_dir, &cache_dir, ip, port));
+        }
+    }
+
+    let (sender, receiver) = mpsc::channel::<notify::Result<Event>>();
+
+    let mut watcher = match notify::recommended_watcher(sender) {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            error!("Could not set up watching the catalog directory for changes ({err})");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Err(err) = watcher.watch(&catalog_dir, RecursiveMode::Recursive) {
+        error!("Could not watch the catalog directory '{}' for changes ({err})", catalog_dir.display());
+        return ExitCode::FAILURE;
+    }
+
+    info!("Watching the catalog directory '{}' for changes (press Ctrl+C to stop)", catalog_dir.display());
+
+    // Events caused by the build itself are ignored in this way too, as the
+    // build only writes to the build and cache directory (and hidden files).
+    let relevant = |event: &Event| {
+        !matches!(event.kind, EventKind::Access(_)) &&
+        event.paths.iter().any(|path| affects_build(&catalog_dir, &ignored_dirs, path))
+    };
+
+    while let Ok(result) = receiver.recv() {
+        match result {
+            Ok(event) if relevant(&event) => (),
+            Ok(_) => continue,
+            Err(err) => {
+                error!("Watching the catalog directory for changes failed ({err})");
+                continue;
+            }
+        }
+
+        while receiver.recv_timeout(Duration::from_millis(SETTLE_MILLISECONDS)).is_ok() {}
+
+        info!("Changes in the catalog directory were detected, rebuilding");
+
+        build(&build_args);
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,97 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Web Monetization (https://webmonetization.org) lets visitors whose
+//! browser (or browser extension) supports it stream small payments to the
+//! artist/label while they are on a page, over the Interledger protocol.
+
+use indoc::formatdoc;
+use url::Url;
+
+use crate::util::html_escape_inside_attribute;
+
+/// Configured through the `payment_pointer` option, inherited from
+/// catalog/artist to release/track.
+#[derive(Clone, Debug)]
+pub struct PaymentPointer {
+    /// The original "$wallet.example.com/alice" notation, if the payment
+    /// pointer was given like that (which is what the legacy <meta> tag
+    /// requires).
+    pub pointer: Option<String>,
+    /// Whether a link to the downloads is revealed while payments are
+    /// streamed (only applies to paycurtains).
+    pub unlock_downloads: bool,
+    /// The resolved wallet address, always starting with https://
+    pub wallet_address: String
+}
+
+impl PaymentPointer {
+    /// Tags (and, if unlock_downloads is set, a script) for the <head> of
+    /// pages that present streaming audio. The script reveals all elements
+    /// with the class monetization_unlock once the browser reports that
+    /// monetization is active - there is no server-side verification of
+    /// payments, so this is a matter of trust (just like the paycurtain).
+    pub fn head_tags(&self) -> String {
+        let wallet_address_escaped = html_escape_inside_attribute(&self.wallet_address);
+        let mut tags = format!(r#"<link href="{wallet_address_escaped}" rel="monetization">"#);
+
+        if let Some(pointer) = &self.pointer {
+            let pointer_escaped = html_escape_inside_attribute(pointer);
+            tags.push_str(&format!(r#"<meta content="{pointer_escaped}" name="monetization">"#));
+        }
+
+        if self.unlock_downloads {
+            tags.push_str(&formatdoc!(r#"
+                <script>
+                    (() => {{
+                        const reveal = () => {{
+                            document.querySelectorAll('.monetization_unlock').forEach(element => element.hidden = false);
+                        }};
+                        document.querySelector('link[rel="monetization"]').addEventListener('monetization', reveal, {{ once: true }});
+                        if (document.monetization) {{
+                            document.monetization.addEventListener('monetizationstart', reveal, {{ once: true }});
+                        }}
+                    }})();
+                </script>
+            "#));
+        }
+
+        tags
+    }
+
+    /// Accepts either a payment pointer ("$wallet.example.com/alice") or a
+    /// wallet address ("https://wallet.example.com/alice").
+    pub fn parse(input: &str, unlock_downloads: bool) -> Result<PaymentPointer, String> {
+        if let Some(without_dollar) = input.strip_prefix('$') {
+            if without_dollar.is_empty() {
+                return Err(String::from("The payment pointer is empty"));
+            }
+
+            // Payment pointers without a path resolve to /.well-known/pay
+            let wallet_address = match without_dollar.contains('/') {
+                true => format!("https://{without_dollar}"),
+                false => format!("https://{without_dollar}/.well-known/pay")
+            };
+
+            match Url::parse(&wallet_address) {
+                Ok(_) => Ok(PaymentPointer {
+                    pointer: Some(input.to_string()),
+                    unlock_downloads,
+                    wallet_address
+                }),
+                Err(err) => Err(format!("The payment pointer seems to be malformed ({err})"))
+            }
+        } else if input.starts_with("https://") {
+            match Url::parse(input) {
+                Ok(_) => Ok(PaymentPointer {
+                    pointer: None,
+                    unlock_downloads,
+                    wallet_address: input.to_string()
+                }),
+                Err(err) => Err(format!("The wallet address seems to be malformed ({err})"))
+            }
+        } else {
+            Err(String::from("A payment pointer needs to start with '$' (e.g. '$wallet.example.com/alice'), a wallet address with 'https://' (e.g. 'https://wallet.example.com/alice')"))
+        }
+    }
+}
@@ -0,0 +1,174 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Through the catalog's `notify_webhook` option, a json summary is POSTed
+//! to a url after the build and/or the deploy (successful or not), so that
+//! chat integrations and uptime tooling can observe publishing activity.
+//! The request is performed through curl (which needs to be installed).
+//!
+//! To tell which releases are new, the permalinks of the public releases
+//! are recorded in the cache directory with each successful notification,
+//! for comparison with the next one (separately for build and deploy, so
+//! that a deploy notification also reports releases that were already
+//! built before).
+
+use std::collections::HashMap;
+use std::fs;
+use std::process::Command;
+
+use chrono::Utc;
+
+use crate::{Build, Catalog, GENERATOR_INFO};
+use crate::util::{json_escape, json_optional_string};
+
+/// Stored in the cache directory, but not subject to cache maintenance.
+pub const WEBHOOK_CACHE_FILENAME: &str = "webhook.cache";
+
+#[cfg(not(target_os = "windows"))]
+const CURL_BINARY: &str = "curl";
+
+#[cfg(target_os = "windows")]
+const CURL_BINARY: &str = "curl.exe";
+
+const TIMEOUT_SECONDS: u32 = 15;
+
+const WEBHOOK_SCHEMA: &str = "faircamp-webhook/1";
+
+/// Configured through the catalog manifest's `notify_webhook` option.
+#[derive(Clone, Debug)]
+pub struct NotifyWebhook {
+    /// After which of the events a notification is sent
+    pub events: Vec<WebhookEvent>,
+    pub url: String
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WebhookEvent {
+    Build,
+    Deploy
+}
+
+impl WebhookEvent {
+    pub fn from_key(key: &str) -> Option<WebhookEvent> {
+        match key {
+            "build" => Some(WebhookEvent::Build),
+            "deploy" => Some(WebhookEvent::Deploy),
+            _ => None
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            WebhookEvent::Build => "build",
+            WebhookEvent::Deploy => "deploy"
+        }
+    }
+}
+
+/// Sends the summary for the given event, if a webhook is configured and
+/// it is interested in the event. A failure is passed as the reason why
+/// the build or deploy failed. The catalog is not available when the
+/// build already failed while reading it. Problems with the request are
+/// only reported, they do not fail the build.
+pub fn notify(
+    build: &Build,
+    catalog: Option<&Catalog>,
+    event: WebhookEvent,
+    failure: Option<&str>
+) {
+    let Some(notify_webhook) = &build.notify_webhook else { return };
+
+    if !notify_webhook.events.contains(&event) {
+        return;
+    }
+
+    let cache_path = build.cache_dir.join(WEBHOOK_CACHE_FILENAME);
+
+    // Maps event keys to the permalinks of the public releases at the
+    // time of the last successful notification for that event
+    let mut recorded: HashMap<String, Vec<String>> = match fs::read(&cache_path) {
+        Ok(bytes) => bincode::deserialize(&bytes).unwrap_or_default(),
+        Err(_) => HashMap::new()
+    };
+
+    let mut new_releases = Vec::new();
+    let mut public_slugs = Vec::new();
+
+    if let Some(catalog) = catalog {
+        for release in catalog.public_releases() {
+            let release_ref = release.borrow();
+            let slug = &release_ref.permalink.slug;
+
+            // Without a record of a previous notification we can't tell
+            // what is new, so nothing is reported as new the first time.
+            if recorded.get(event.key()).is_some_and(|slugs| !slugs.contains(slug)) {
+                let url = build.base_url
+                    .as_ref()
+                    .map(|base_url| base_url.join_index(build, slug));
+
+                new_releases.push(format!(
+                    "    {{ \"title\": \"{}\", \"url\": {} }}",
+                    json_escape(&release_ref.title),
+                    json_optional_string(url.as_deref())
+                ));
+            }
+
+            public_slugs.push(slug.clone());
+        }
+    }
+
+    let duration = (Utc::now() - build.build_begin).num_milliseconds() as f64 / 1000.0;
+    let title = catalog.map(|catalog| catalog.title());
+    let r_new_releases = new_releases.join(",\n");
+
+    let json = format!(
+        "{{\n  \"catalog\": {},\n  \"duration\": {duration:.3},\n  \"errors\": {},\n  \"event\": \"{}\",\n  \"failure\": {},\n  \"generator\": \"{}\",\n  \"new_releases\": [\n{r_new_releases}\n  ],\n  \"schema\": \"{WEBHOOK_SCHEMA}\",\n  \"success\": {},\n  \"warnings\": {}\n}}\n",
+        json_optional_string(title.as_deref()),
+        build.errors,
+        event.key(),
+        json_optional_string(failure),
+        json_escape(GENERATOR_INFO),
+        failure.is_none(),
+        build.warnings
+    );
+
+    match post(&notify_webhook.url, &json) {
+        Ok(()) => {
+            info!("The {} notification was sent to the webhook", event.key());
+
+            if failure.is_none() && catalog.is_some() {
+                recorded.insert(event.key().to_string(), public_slugs);
+
+                let serialized = bincode::serialize(&recorded).unwrap();
+                if let Err(err) = fs::write(&cache_path, serialized) {
+                    error!("The record of notified releases could not be written to the cache ({})", err);
+                }
+            }
+        }
+        Err(message) => error!("The {} notification could not be sent to the webhook ({})", event.key(), message)
+    }
+}
+
+fn post(url: &str, json: &str) -> Result<(), String> {
+    let mut command = Command::new(CURL_BINARY);
+
+    command.arg("--data-binary").arg(json);
+    command.arg("--header").arg("Content-Type: application/json");
+    command.arg("--max-time").arg(TIMEOUT_SECONDS.to_string());
+    command.arg("--output").arg(if cfg!(target_os = "windows") { "NUL" } else { "/dev/null" });
+    command.arg("--silent");
+    command.arg("--write-out").arg("%{http_code}");
+    command.arg(url);
+
+    match command.output() {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            match stdout.trim().parse::<u16>() {
+                Ok(0) | Err(_) => Err(String::from("no response")),
+                Ok(status_code) if status_code < 400 => Ok(()),
+                Ok(status_code) => Err(format!("HTTP {status_code}"))
+            }
+        }
+        Err(err) => Err(format!("The {CURL_BINARY} child process could not be executed ({err})"))
+    }
+}
@@ -0,0 +1,127 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Files served from /.well-known/ (RFC 8615), through which other software
+//! (fediverse servers, security researchers, funding platforms) discovers
+//! information about the site.
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::{Build, Catalog, funding, util};
+use crate::util::json_escape;
+
+pub const WELL_KNOWN_DIRNAME: &str = ".well-known";
+
+const FUNDING_JSON_FILENAME: &str = "funding.json";
+const SECURITY_TXT_FILENAME: &str = "security.txt";
+const WEBFINGER_FILENAME: &str = "webfinger";
+
+/// Maps an account (as in @music@example.com) to an ActivityPub actor, so
+/// that the site's domain can be used to look up the artist/label on the
+/// fediverse. As a static site can't evaluate the ?resource= query of
+/// webfinger requests, the same response is returned for any account.
+#[derive(Debug)]
+pub struct Webfinger {
+    /// Always stored with the "acct:" prefix, e.g. "acct:music@example.com"
+    pub account: String,
+    /// The url of the ActivityPub actor, e.g. "https://social.example.com/users/music"
+    pub actor: String,
+    /// The url of the (html) profile page, e.g. "https://social.example.com/@music"
+    pub profile: Option<String>
+}
+
+#[derive(Debug)]
+pub struct WellKnown {
+    /// Absolute path to a user-provided funding manifest (see fundingjson.org),
+    /// takes precedence over the one generated from the catalog's funding option
+    pub funding_json: Option<PathBuf>,
+    /// The verbatim content for security.txt (RFC 9116)
+    pub security_txt: Option<String>,
+    pub webfinger: Option<Webfinger>
+}
+
+impl Webfinger {
+    /// The "acct:" prefix is optional when the account is specified in the
+    /// manifest, and a leading "@" (as in fediverse handles) is tolerated.
+    pub fn new(account: &str, actor: String, profile: Option<String>) -> Webfinger {
+        let account = account.strip_prefix("acct:").unwrap_or(account);
+        let account = account.strip_prefix('@').unwrap_or(account);
+
+        Webfinger {
+            account: format!("acct:{account}"),
+            actor,
+            profile
+        }
+    }
+
+    /// Renders the JSON Resource Descriptor (RFC 7033) that is served as
+    /// response to webfinger requests.
+    pub fn jrd(&self) -> String {
+        let mut aliases = vec![format!(r#""{}""#, json_escape(&self.actor))];
+        let mut links = vec![format!(
+            r#"    {{ "href": "{}", "rel": "self", "type": "application/activity+json" }}"#,
+            json_escape(&self.actor)
+        )];
+
+        if let Some(profile) = &self.profile {
+            aliases.insert(0, format!(r#""{}""#, json_escape(profile)));
+            links.push(format!(
+                r#"    {{ "href": "{}", "rel": "http://webfinger.net/rel/profile-page", "type": "text/html" }}"#,
+                json_escape(profile)
+            ));
+        }
+
+        format!(
+            "{{\n  \"aliases\": [{}],\n  \"links\": [\n{}\n  ],\n  \"subject\": \"{}\"\n}}\n",
+            aliases.join(", "),
+            links.join(",\n"),
+            json_escape(&self.account)
+        )
+    }
+}
+
+impl WellKnown {
+    pub fn is_empty(&self) -> bool {
+        self.funding_json.is_none() &&
+        self.security_txt.is_none() &&
+        self.webfinger.is_none()
+    }
+
+    pub fn new() -> WellKnown {
+        WellKnown {
+            funding_json: None,
+            security_txt: None,
+            webfinger: None
+        }
+    }
+
+    /// Writes all configured files to /.well-known/ in the build directory
+    /// and reserves the directory name, so that user-provided site assets
+    /// can not collide with it.
+    pub fn write(&self, build: &mut Build, catalog: &Catalog) {
+        if self.is_empty() && catalog.funding.is_empty() {
+            return;
+        }
+
+        let well_known_dir = build.build_dir.join(WELL_KNOWN_DIRNAME);
+        util::ensure_dir_all(&well_known_dir);
+
+        if let Some(path) = &self.funding_json {
+            util::hard_link_or_copy(path, well_known_dir.join(FUNDING_JSON_FILENAME));
+        } else if !catalog.funding.is_empty() {
+            let funding_json = funding::funding_json(build, catalog);
+            fs::write(well_known_dir.join(FUNDING_JSON_FILENAME), funding_json).unwrap();
+        }
+
+        if let Some(security_txt) = &self.security_txt {
+            fs::write(well_known_dir.join(SECURITY_TXT_FILENAME), security_txt).unwrap();
+        }
+
+        if let Some(webfinger) = &self.webfinger {
+            fs::write(well_known_dir.join(WEBFINGER_FILENAME), webfinger.jrd()).unwrap();
+        }
+
+        build.reserve_filename(WELL_KNOWN_DIRNAME);
+    }
+}
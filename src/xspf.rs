@@ -0,0 +1,69 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+/// XSPF format reference:
+/// - https://www.xspf.org/spec
+
+use indoc::formatdoc;
+
+use crate::{Build, Playlist, SiteUrl};
+use crate::m3u::{release_cover_url, track_url};
+use crate::util::html_escape_outside_attribute;
+
+pub const XSPF_PLAYLIST_FILENAME: &str = "playlist.xspf";
+
+/// Generate complete content of an XSPF playlist for a (curated) playlist
+pub fn generate_for_playlist(
+    base_url: &SiteUrl,
+    build: &Build,
+    playlist: &Playlist
+) -> String {
+    let playlist_title_escaped = html_escape_outside_attribute(&playlist.title);
+    let playlist_url = base_url.join_index(build, &playlist.permalink.slug);
+
+    let r_tracks = playlist.tracks
+        .iter()
+        .map(|playlist_track| {
+            let release_ref = playlist_track.release.borrow();
+            let track = playlist_track.track(&release_ref);
+            let track_number = playlist_track.track_number;
+
+            let artists = track.artists
+                .iter()
+                .map(|artist| artist.borrow().name.clone())
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            let artists_escaped = html_escape_outside_attribute(&artists);
+            let duration_milliseconds = (track.transcodes.borrow().source_meta.duration_seconds * 1000.0) as usize;
+            let image_url = release_cover_url(build, &release_ref);
+            let location = track_url(build, &release_ref, track, track_number);
+            let release_title_escaped = html_escape_outside_attribute(&release_ref.title);
+            let track_title_escaped = html_escape_outside_attribute(&track.title());
+
+            formatdoc!(r#"
+                <track>
+                    <location>{location}</location>
+                    <title>{track_title_escaped}</title>
+                    <creator>{artists_escaped}</creator>
+                    <album>{release_title_escaped}</album>
+                    <trackNum>{track_number}</trackNum>
+                    <duration>{duration_milliseconds}</duration>
+                    <image>{image_url}</image>
+                </track>
+            "#)
+        })
+        .collect::<Vec<String>>()
+        .join("");
+
+    formatdoc!(r#"
+        <?xml version="1.0" encoding="UTF-8"?>
+        <playlist version="1" xmlns="http://xspf.org/ns/0/">
+            <title>{playlist_title_escaped}</title>
+            <location>{playlist_url}</location>
+            <trackList>
+                {r_tracks}
+            </trackList>
+        </playlist>
+    "#)
+}
@@ -0,0 +1,59 @@
+// SPDX-FileCopyrightText: 2025 Simon Repp
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use chrono::Datelike;
+
+use crate::Release;
+
+/// Configured through the catalog's `year_archives` option
+#[derive(Clone, Debug)]
+pub struct YearArchives {
+    /// Whether a page per decade is generated in addition to the year pages
+    pub decades: bool,
+    /// Whether an atom feed is generated for each year/decade page
+    pub feeds: bool
+}
+
+/// A page listing the public releases of one year (written to e.g.
+/// `/2024/`) or of one decade (written to e.g. `/2020s/`), based on the
+/// dates of the releases.
+#[derive(Clone, Debug)]
+pub struct YearArchivePage {
+    pub first_year: i32,
+    pub last_year: i32,
+    pub slug: String
+}
+
+impl YearArchivePage {
+    pub fn decade(first_year: i32) -> YearArchivePage {
+        YearArchivePage {
+            first_year,
+            last_year: first_year + 9,
+            slug: format!("{first_year}s")
+        }
+    }
+
+    pub fn is_decade(&self) -> bool {
+        self.first_year != self.last_year
+    }
+
+    /// E.g. "2024" or "2020–2029"
+    pub fn label(&self) -> String {
+        match self.is_decade() {
+            true => format!("{}–{}", self.first_year, self.last_year),
+            false => self.first_year.to_string()
+        }
+    }
+
+    pub fn matches(&self, release: &Release) -> bool {
+        release.date.is_some_and(|date| date.year() >= self.first_year && date.year() <= self.last_year)
+    }
+
+    pub fn year(year: i32) -> YearArchivePage {
+        YearArchivePage {
+            first_year: year,
+            last_year: year,
+            slug: year.to_string()
+        }
+    }
+}
@@ -6,6 +6,9 @@
 use super::{Reviewed, Translations};
 
 pub const DE: Translations = Translations {
+    add_to_calendar: Reviewed("Zum Kalender hinzufügen"),
+    all_releases: Reviewed("Alle Veröffentlichungen"),
+    archive: Reviewed("Archiv"),
     audio_format_alac: Reviewed("Verlustfrei komprimiert, wenn du nur Apple Produkte verwendest wähle dies hier statt FLAC"),
     audio_format_average: Reviewed("Durchschnittliche Komprimierung, sinnvoll wenn dein Player keine besseren Formate unterstützt"),
     audio_format_flac: Reviewed("Verlustfrei komprimiert, beste Wahl für Archivierung"),
@@ -13,24 +16,39 @@ pub const DE: Translations = Translations {
     audio_format_opus_48: Reviewed("Exzellente Komprimierung, genügsame Qualität, gute Wahl bei limitiertem Speicherplatz"),
     audio_format_opus_96: Reviewed("Exzellente Komprimierung, Standard Qualität, gute Wahl zum offline hören"),
     audio_format_opus_128: Reviewed("Exzellente Komprimierung, höchste Qualität, beste Wahl zum offline hören"),
+    audio_format_original: Reviewed("Die unveränderten Originaldateien, wie von den Artists bereitgestellt – Format und Größe können variieren"),
     audio_format_uncompressed: Reviewed("Unkomprimierte, große Dateien – Nur für Audio Produktion sinnvoll"),
     audio_player_widget_for_xxx: Reviewed(r#"Audio Player Widget für "{title}""#),
     auto_generated_cover: Reviewed("Automatisch generiertes Cover"),
     available_formats: Reviewed("Verfügbare Formate:"),
     browse: Reviewed("Stöbern"),
     buy: Reviewed("Kaufen"),
+    chapters: Reviewed("Kapitel"),
     close: Reviewed("Schließen"),
+    compare_formats: Reviewed("Formate vergleichen"),
     copied: Reviewed("Kopiert"),
     copy: Reviewed("Kopieren"),
     copy_link: Reviewed("Link kopieren"),
+    copy_link_at_current_time: Reviewed("Link zur aktuellen Stelle kopieren"),
     confirm: Reviewed("Bestätigen"),
+    content_warning: Reviewed("Inhaltswarnung"),
+    content_warning_age_xxx: Reviewed("Diese Veröffentlichung ist nur für Personen ab {age} Jahren bestimmt."),
+    content_warning_confirm_age_xxx: Reviewed("Ich bin {age} Jahre oder älter, weiter"),
     r#continue: Reviewed("Weiter"),
     cover_image: Reviewed("Cover Bild"),
+    cover_of_xxx: Reviewed("Cover von {title}"),
+    cover_of_xxx_by_xxx: Reviewed("Cover von {title} von {artists}"),
     default_unlock_info: Reviewed("Du musst einen Code eingeben um diese Downloads freizuschalten. Frag bei den Seitenbetreiber*innen nach wie du einen bekommst."),
+    disc_xxx: Reviewed("Disc {number}"),
+    discover: Reviewed("Entdecken"),
+    discover_text: Reviewed("Du wirst zu einem zufälligen Track weitergeleitet …"),
     download: Reviewed("Downloaden"),
     download_code_seems_incorrect: Reviewed("Der Downloadcode scheint nicht korrekt zu sein, überprüfe ihn bitte auf Tippfehler."),
+    download_selected: Reviewed("Auswahl herunterladen"),
     downloads: Reviewed("Downloads"),
     downloads_permalink: Reviewed("downloads"),
+    draft_preview: Reviewed("Entwurfsvorschau – dieses Release ist noch nicht veröffentlicht"),
+    dyslexia_friendly_font: Reviewed("Legasthenie-freundliche Schrift"),
     embed: Reviewed("Einbetten"),
     embed_entire_release: Reviewed("Den gesamten Release einbetten"),
     enter_code_here: Reviewed("Code hier eingeben"),
@@ -38,7 +56,13 @@ pub const DE: Translations = Translations {
     extras: Reviewed("Extras"),
     failed: Reviewed("Fehler"),
     feed: Reviewed("Feed"),
+    filter_releases: Reviewed("Veröffentlichungen filtern"),
+    format_comparison_hint: Reviewed("Hör dir denselben Ausschnitt in jedem Format an, um den Unterschied selbst zu hören. Beim Wechsel des Formats bleibt die Wiedergabeposition erhalten."),
+    from_xxx: Reviewed("aus {title}"),
     generic_rss: Reviewed("Generisches RSS"),
+    hover_previews: Reviewed("Hörproben beim Überfahren abspielen"),
+    image_description_suggestions: Reviewed("Vorgeschlagene Beschreibungen"),
+    image_description_suggestions_hint: Reviewed("Solange die folgenden Bilder nicht beschrieben sind, hier Beschreibungen, die aus Titeln, Namen und Daten abgeleitet wurden. Was die Bilder tatsächlich zeigen, geht daraus nicht hervor."),
     image_descriptions: Reviewed("Bildbeschreibungen"),
     image_descriptions_guide: Reviewed("\
 Millionen Menschen bewegen sich mit Screen Readern \
@@ -57,35 +81,65 @@ Hier ein paar Tipps zum Schreiben guter Bildbeschreibungen:<br>\
 - Beschreib was da ist und wichtig fürs Verständnis, aber analysiere und interpretiere darüber hinaus nicht.<br>\
 - Du kannst Farbbeschreibungen verwenden wo es Sinn macht - viele Menschen verlieren ihre Sehkraft erst spät im Leben und verstehen und schätzen Farben."),
     image_descriptions_permalink: Reviewed("bildbeschreibungen"),
+    image_of_xxx: Reviewed("Bild von {name}"),
+    in_stock: Reviewed("Erhältlich"),
+    individual_tracks: Reviewed("Einzelne Tracks"),
     javascript_is_disabled_listen_at_xxx: Reviewed("JavaScript ist deaktiviert – Anhören auf {link}"),
     javascript_is_disabled_text: Reviewed("JavaScript ist deaktiviert – Manche Features sind nicht verfügbar"),
+    languages: Reviewed("Sprachen"),
+    list: Reviewed("Liste"),
     listen: Reviewed("Anhören"),
     loading: Reviewed("Lädt"),
+    lyrics: Reviewed("Liedtext"),
     m3u_playlist: Reviewed("M3U Playlist"),
     made_or_arranged_payment: Reviewed("Ich habe die Bezahlung durchgeführt oder arrangiert"),
+    mastodon_instance_prompt: Reviewed("Gib die Domain deiner Mastodon-Instanz ein (z.B. mastodon.social)"),
     missing_image_description_note: Reviewed("Fehlende Bildbeschreibung<br>Klick für mehr Info"),
     more: Reviewed("Mehr"),
+    move_down: Reviewed("Nach unten verschieben"),
+    move_up: Reviewed("Nach oben verschieben"),
     mute: Reviewed("Stummschalten"),
     name_your_price: Reviewed("Nenne einen Preis"),
+    next_page: Reviewed("Nächste Seite"),
     next_track: Reviewed("Nächster Track"),
     nothing_found_for_xxx: Reviewed("Nichts für '{query}' gefunden"),
+    order: Reviewed("Bestellen"),
+    page_xxx_of_xxx: Reviewed("Seite {number} von {total}"),
     pause: Reviewed("Pausieren"),
+    physical_editions: Reviewed("Physische Ausgaben"),
     play: Reviewed("Abspielen"),
     playback_position: Reviewed("Wiedergabeposition"),
     player_closed: Reviewed("Player geschlossen"),
+    player_error_retrying: Reviewed("Laden fehlgeschlagen, neuer Versuch …"),
+    player_error_unavailable: Reviewed("Dieser Track kann gerade nicht abgespielt werden, bitte versuche es später noch einmal"),
     player_open_playing_xxx: Reviewed("Player offen, spielt {title}"),
     player_open_with_xxx: Reviewed("Player offen mit {title}"),
+    playlist: Reviewed("Playlist"),
+    previous_page: Reviewed("Vorherige Seite"),
     previous_track: Reviewed("Vorheriger Track"),
     price: Reviewed("Preis:"),
     purchase_downloads: Reviewed("Downloads bezahlen"),
     purchase_permalink: Reviewed("bezahlen"),
+    queue: Reviewed("Warteschlange"),
     recommended_format: Reviewed("Empfohlenes Format"),
+    release_scheduled_for_xxx: Reviewed("Diese Veröffentlichung erscheint am {date}"),
+    released_xxx: Reviewed("erschienen {year}"),
+    remove_from_queue: Reviewed("Aus der Warteschlange entfernen"),
+    reset_queue: Reviewed("Warteschlange zurücksetzen"),
     search: Reviewed("Suchen"),
+    select_all: Reviewed("Alle auswählen"),
+    share_on_xxx: Reviewed("Auf {platform} teilen"),
+    share_via_email: Reviewed("Per E-Mail teilen"),
     showing_featured_items: Reviewed("Gefeaturete Einträge werden angezeigt"),
     showing_xxx_results_for_xxx: Reviewed("{count} Ergebnisse für '{query}' werden angezeigt"),
     skip_to_main_content: Reviewed("Zum Hauptinhalt springen"),
+    sold_out: Reviewed("Ausverkauft"),
     subscribe: Reviewed("Abonnieren"),
     subscribe_permalink: Reviewed("abonnieren"),
+    support_the_artist: Reviewed("Künstler*in unterstützen"),
+    timeline: Reviewed("Zeitleiste"),
+    transcript: Reviewed("Transkript"),
+    undated: Reviewed("Undatiert"),
     unlisted: Reviewed("Ungelistet"),
     unlock: Reviewed("Freischalten"),
     unlock_downloads: Reviewed("Downloads freischalten"),
@@ -106,6 +160,8 @@ mit /{downloads_permalink}/[dein-downloadcode]{index_suffix} und drücke dann En
     up_to_xxx: Reviewed("Bis zu {xxx}"),
     visual_impairment: Reviewed("Visuelle Beeinträchtigung"),
     volume: Reviewed("Lautstärke"),
+    volume_normalization: Reviewed("Lautstärkeangleichung"),
+    xspf_playlist: Reviewed("XSPF Playlist"),
     xxx_and_others: Reviewed(r#"{xxx} und <a href="{others_link}">Weitere</a>"#),
     xxx_hours: Reviewed("{xxx} Stunden"),
     xxx_minutes: Reviewed("{xxx} Minuten"),
@@ -5,6 +5,9 @@
 use super::{Reviewed, Translations};
 
 pub const EN: Translations = Translations {
+    add_to_calendar: Reviewed("Add to calendar"),
+    all_releases: Reviewed("All releases"),
+    archive: Reviewed("Archive"),
     audio_format_alac: Reviewed("Lossless and compressed, if you exclusively use Apple products choose this over FLAC"),
     audio_format_average: Reviewed("Average compression, appropriate if your player does not support better formats"),
     audio_format_flac: Reviewed("Lossless and compressed, best choice for archival"),
@@ -12,24 +15,39 @@ pub const EN: Translations = Translations {
     audio_format_opus_48: Reviewed("Excellent compression, frugal quality, good choice if space is limited"),
     audio_format_opus_96: Reviewed("Excellent compression, standard quality, good choice for offline listening"),
     audio_format_opus_128: Reviewed("Excellent compression, highest quality, best choice for offline listening"),
+    audio_format_original: Reviewed("The untouched original files as provided by the artist, format and size may vary"),
     audio_format_uncompressed: Reviewed("Uncompressed large files, appropriate only for audio production"),
     audio_player_widget_for_xxx: Reviewed(r#"Audio player widget for "{title}""#),
     auto_generated_cover: Reviewed("Automatically generated cover"),
     available_formats: Reviewed("Available formats:"),
     browse: Reviewed("Browse"),
     buy: Reviewed("Buy"),
+    chapters: Reviewed("Chapters"),
     close: Reviewed("Close"),
+    compare_formats: Reviewed("Compare formats"),
     copied: Reviewed("Copied"),
     copy: Reviewed("Copy"),
     copy_link: Reviewed("Copy link"),
+    copy_link_at_current_time: Reviewed("Copy link at current time"),
     confirm: Reviewed("Confirm"),
+    content_warning: Reviewed("Content warning"),
+    content_warning_age_xxx: Reviewed("This release is only intended for people aged {age} or older."),
+    content_warning_confirm_age_xxx: Reviewed("I am {age} or older, continue"),
     r#continue: Reviewed("Continue"),
     cover_image: Reviewed("Cover Image"),
+    cover_of_xxx: Reviewed("Cover of {title}"),
+    cover_of_xxx_by_xxx: Reviewed("Cover of {title} by {artists}"),
     default_unlock_info: Reviewed("You need to enter a code to unlock these downloads. Ask the site operators for how to obtain one."),
+    disc_xxx: Reviewed("Disc {number}"),
+    discover: Reviewed("Discover"),
+    discover_text: Reviewed("Taking you to a random track …"),
     download: Reviewed("Download"),
     download_code_seems_incorrect: Reviewed("The download code seems to be incorrect, please check for typos."),
+    download_selected: Reviewed("Download selected"),
     downloads: Reviewed("Downloads"),
     downloads_permalink: Reviewed("downloads"),
+    draft_preview: Reviewed("Draft preview – this release is not published yet"),
+    dyslexia_friendly_font: Reviewed("Dyslexia-friendly font"),
     embed: Reviewed("Embed"),
     embed_entire_release: Reviewed("Embed the entire release"),
     enter_code_here: Reviewed("Enter code here"),
@@ -37,7 +55,13 @@ pub const EN: Translations = Translations {
     extras: Reviewed("Extras"),
     failed: Reviewed("Failed"),
     feed: Reviewed("Feed"),
+    filter_releases: Reviewed("Filter releases"),
+    format_comparison_hint: Reviewed("Listen to the same excerpt in each format to hear the difference for yourself. Switching the format keeps the playback position."),
+    from_xxx: Reviewed("from {title}"),
     generic_rss: Reviewed("Generic RSS"),
+    hover_previews: Reviewed("Play previews on hover"),
+    image_description_suggestions: Reviewed("Suggested descriptions"),
+    image_description_suggestions_hint: Reviewed("Until the images below are described, here are descriptions derived from titles, names and dates. They do not tell what the images actually show."),
     image_descriptions: Reviewed("Image Descriptions"),
     image_descriptions_guide: Reviewed("\
 Millions of people browse the web using screen-readers \
@@ -56,35 +80,65 @@ Here are some tips for writing good image descriptions:<br>\
 - Don't interpret. Describe what is there and relevant for its understanding, don't analyze beyond that.<br>\
 - You can use colors where it makes sense - many people only lost their sight later on and understand and appreciate colors."),
     image_descriptions_permalink: Reviewed("image-descriptions"),
+    image_of_xxx: Reviewed("Image of {name}"),
+    in_stock: Reviewed("Available"),
+    individual_tracks: Reviewed("Individual tracks"),
     javascript_is_disabled_listen_at_xxx: Reviewed("JavaScript is disabled – Listen at {link}"),
     javascript_is_disabled_text: Reviewed("JavaScript is disabled – Some features are not available"),
+    languages: Reviewed("Languages"),
+    list: Reviewed("List"),
     listen: Reviewed("Listen"),
     loading: Reviewed("Loading"),
+    lyrics: Reviewed("Lyrics"),
     m3u_playlist: Reviewed("M3U Playlist"),
     made_or_arranged_payment: Reviewed("I have made or arranged the payment"),
+    mastodon_instance_prompt: Reviewed("Enter the domain of your Mastodon instance (e.g. mastodon.social)"),
     missing_image_description_note: Reviewed("Missing image description<br>Click to learn more"),
     more: Reviewed("More"),
+    move_down: Reviewed("Move down"),
+    move_up: Reviewed("Move up"),
     mute: Reviewed("Mute"),
     name_your_price: Reviewed("Name your price"),
+    next_page: Reviewed("Next page"),
     next_track: Reviewed("Next Track"),
     nothing_found_for_xxx: Reviewed("Nothing found for '{query}'"),
+    order: Reviewed("Order"),
+    page_xxx_of_xxx: Reviewed("Page {number} of {total}"),
     pause: Reviewed("Pause"),
+    physical_editions: Reviewed("Physical editions"),
     play: Reviewed("Play"),
     playback_position: Reviewed("Playback position"),
     player_closed: Reviewed("Player closed"),
+    player_error_retrying: Reviewed("Loading failed, retrying …"),
+    player_error_unavailable: Reviewed("This track can not be played right now, please try again later"),
     player_open_playing_xxx: Reviewed("Player open, playing {title}"),
     player_open_with_xxx: Reviewed("Player open with {title}"),
+    playlist: Reviewed("Playlist"),
+    previous_page: Reviewed("Previous page"),
     previous_track: Reviewed("Previous Track"),
     price: Reviewed("Price:"),
     purchase_downloads: Reviewed("Purchase downloads"),
     purchase_permalink: Reviewed("purchase"),
+    queue: Reviewed("Queue"),
     recommended_format: Reviewed("Recommended Format"),
+    release_scheduled_for_xxx: Reviewed("This release comes out on {date}"),
+    released_xxx: Reviewed("released {year}"),
+    remove_from_queue: Reviewed("Remove from queue"),
+    reset_queue: Reviewed("Reset queue"),
     search: Reviewed("Search"),
+    select_all: Reviewed("Select all"),
+    share_on_xxx: Reviewed("Share on {platform}"),
+    share_via_email: Reviewed("Share via email"),
     showing_featured_items: Reviewed("Showing featured items"),
     showing_xxx_results_for_xxx: Reviewed("Showing {count} results for '{query}'"),
     skip_to_main_content: Reviewed("Skip to main content"),
+    sold_out: Reviewed("Sold out"),
     subscribe: Reviewed("Subscribe"),
     subscribe_permalink: Reviewed("subscribe"),
+    support_the_artist: Reviewed("Support the artist"),
+    timeline: Reviewed("Timeline"),
+    transcript: Reviewed("Transcript"),
+    undated: Reviewed("Undated"),
     unlisted: Reviewed("Unlisted"),
     unlock: Reviewed("Unlock"),
     unlock_downloads: Reviewed("Unlock downloads"),
@@ -103,6 +157,8 @@ with /{downloads_permalink}/[your-download-code]{index_suffix} and then press En
     up_to_xxx: Reviewed("Up to {xxx}"),
     visual_impairment: Reviewed("Visual Impairment"),
     volume: Reviewed("Volume"),
+    volume_normalization: Reviewed("Volume normalization"),
+    xspf_playlist: Reviewed("XSPF Playlist"),
     xxx_and_others: Reviewed(r#"{xxx} and <a href="{others_link}">others</a>"#),
     xxx_hours: Reviewed("{xxx} hours"),
     xxx_minutes: Reviewed("{xxx} minutes"),
@@ -55,39 +55,42 @@ pub use Translation::{Reviewed, Unreviewed, Untranslated};
 
 pub fn all_languages() -> Vec<LabelledTranslations> {
     vec![
-        LabelledTranslations { code: "ca", name: "Catalan", translations: CA },
-        LabelledTranslations { code: "da", name: "Danish", translations: DA },
-        LabelledTranslations { code: "de", name: "German", translations: DE },
-        LabelledTranslations { code: "en", name: "English", translations: EN },
-        LabelledTranslations { code: "es", name: "Spanish", translations: ES },
-        LabelledTranslations { code: "fi", name: "Finnish", translations: FI },
-        LabelledTranslations { code: "fr", name: "French", translations: FR },
-        LabelledTranslations { code: "he", name: "Hebrew", translations: HE },
-        LabelledTranslations { code: "it", name: "Italian", translations: IT },
-        LabelledTranslations { code: "ja", name: "Japanese", translations: JA },
-        LabelledTranslations { code: "lt", name: "Lithuanian", translations: LT },
-        LabelledTranslations { code: "nb", name: "Norwegian Bokmål", translations: NB },
-        LabelledTranslations { code: "nl", name: "Dutch", translations: NL },
-        LabelledTranslations { code: "pl", name: "Polish", translations: PL },
-        LabelledTranslations { code: "pt-pt", name: "Portuguese (European)", translations: PT_PT },
-        LabelledTranslations { code: "ru", name: "Russian", translations: RU },
-        LabelledTranslations { code: "sr-cyrl", name: "Serbian (Cyrillic)", translations: SR_CYRL },
-        LabelledTranslations { code: "sr-latn", name: "Serbian (Latin)", translations: SR_LATN },
-        LabelledTranslations { code: "sv", name: "Swedish", translations: SV },
-        LabelledTranslations { code: "tr", name: "Turkish", translations: TR },
-        LabelledTranslations { code: "uk", name: "Ukrainian", translations: UK },
-        LabelledTranslations { code: "zh_hans_cn", name: "Chinese (Simplified, Mandarin, Mainland China)", translations: ZH_HANS_CN },
-        LabelledTranslations { code: "zh_hant_tw", name: "Chinese (Traditional, Mandarin, Taiwan)", translations: ZH_HANT_TW }
+        LabelledTranslations { code: "ca", name: "Catalan", native_name: "Català", translations: CA },
+        LabelledTranslations { code: "da", name: "Danish", native_name: "Dansk", translations: DA },
+        LabelledTranslations { code: "de", name: "German", native_name: "Deutsch", translations: DE },
+        LabelledTranslations { code: "en", name: "English", native_name: "English", translations: EN },
+        LabelledTranslations { code: "es", name: "Spanish", native_name: "Español", translations: ES },
+        LabelledTranslations { code: "fi", name: "Finnish", native_name: "Suomi", translations: FI },
+        LabelledTranslations { code: "fr", name: "French", native_name: "Français", translations: FR },
+        LabelledTranslations { code: "he", name: "Hebrew", native_name: "עברית", translations: HE },
+        LabelledTranslations { code: "it", name: "Italian", native_name: "Italiano", translations: IT },
+        LabelledTranslations { code: "ja", name: "Japanese", native_name: "日本語", translations: JA },
+        LabelledTranslations { code: "lt", name: "Lithuanian", native_name: "Lietuvių", translations: LT },
+        LabelledTranslations { code: "nb", name: "Norwegian Bokmål", native_name: "Norsk bokmål", translations: NB },
+        LabelledTranslations { code: "nl", name: "Dutch", native_name: "Nederlands", translations: NL },
+        LabelledTranslations { code: "pl", name: "Polish", native_name: "Polski", translations: PL },
+        LabelledTranslations { code: "pt-pt", name: "Portuguese (European)", native_name: "Português (Europeu)", translations: PT_PT },
+        LabelledTranslations { code: "ru", name: "Russian", native_name: "Русский", translations: RU },
+        LabelledTranslations { code: "sr-cyrl", name: "Serbian (Cyrillic)", native_name: "Српски (ћирилица)", translations: SR_CYRL },
+        LabelledTranslations { code: "sr-latn", name: "Serbian (Latin)", native_name: "Srpski (latinica)", translations: SR_LATN },
+        LabelledTranslations { code: "sv", name: "Swedish", native_name: "Svenska", translations: SV },
+        LabelledTranslations { code: "tr", name: "Turkish", native_name: "Türkçe", translations: TR },
+        LabelledTranslations { code: "uk", name: "Ukrainian", native_name: "Українська", translations: UK },
+        LabelledTranslations { code: "zh_hans_cn", name: "Chinese (Simplified, Mandarin, Mainland China)", native_name: "简体中文（中国大陆）", translations: ZH_HANS_CN },
+        LabelledTranslations { code: "zh_hant_tw", name: "Chinese (Traditional, Mandarin, Taiwan)", native_name: "繁體中文（台灣）", translations: ZH_HANT_TW }
     ]
 }
 
 pub fn new_language() -> LabelledTranslations {
-    LabelledTranslations { code: "..", name: "New Language", translations: Translations::UNTRANSLATED }
+    LabelledTranslations { code: "..", name: "New Language", native_name: "New Language", translations: Translations::UNTRANSLATED }
 }
 
 pub struct LabelledTranslations {
     pub code: &'static str,
+    /// English name of the language
     pub name: &'static str,
+    /// Name of the language in the language itself
+    pub native_name: &'static str,
     pub translations: Translations
 }
 
@@ -163,6 +166,9 @@ impl std::fmt::Display for Translation {
 /// a method of the same name - these are translations that need to be called
 /// as a function because they interpolate some parameter into the translation.
 pub struct Translations {
+    pub add_to_calendar: Translation,
+    pub all_releases: Translation,
+    pub archive: Translation,
     pub audio_format_alac: Translation,
     pub audio_format_average: Translation,
     pub audio_format_flac: Translation,
@@ -170,25 +176,40 @@ pub struct Translations {
     pub audio_format_opus_48: Translation,
     pub audio_format_opus_96: Translation,
     pub audio_format_opus_128: Translation,
+    pub audio_format_original: Translation,
     pub audio_format_uncompressed: Translation,
     pub audio_player_widget_for_xxx: Translation,
     pub auto_generated_cover: Translation,
     pub available_formats: Translation,
     pub browse: Translation,
     pub buy: Translation,
+    pub chapters: Translation,
     pub close: Translation,
+    pub compare_formats: Translation,
     pub copied: Translation,
     pub copy: Translation,
     pub copy_link: Translation,
+    pub copy_link_at_current_time: Translation,
     pub confirm: Translation,
+    pub content_warning: Translation,
+    pub content_warning_age_xxx: Translation,
+    pub content_warning_confirm_age_xxx: Translation,
     pub r#continue: Translation,
     pub cover_image: Translation,
+    pub cover_of_xxx: Translation,
+    pub cover_of_xxx_by_xxx: Translation,
     pub default_unlock_info: Translation,
+    pub disc_xxx: Translation,
+    pub discover: Translation,
+    pub discover_text: Translation,
     pub download: Translation,
     pub download_code_seems_incorrect: Translation,
+    pub download_selected: Translation,
     pub downloads: Translation,
     /// Must be unique and only contain url-safe characters
     pub downloads_permalink: Translation,
+    pub draft_preview: Translation,
+    pub dyslexia_friendly_font: Translation,
     pub embed: Translation,
     pub embed_entire_release: Translation,
     pub enter_code_here: Translation,
@@ -198,42 +219,78 @@ pub struct Translations {
     pub extras: Translation,
     pub failed: Translation,
     pub feed: Translation,
+    pub filter_releases: Translation,
+    pub format_comparison_hint: Translation,
+    pub from_xxx: Translation,
     /// Must be unique and only contain url-safe characters
     pub generic_rss: Translation,
+    pub hover_previews: Translation,
+    pub image_description_suggestions: Translation,
+    pub image_description_suggestions_hint: Translation,
     pub image_descriptions: Translation,
     pub image_descriptions_guide: Translation,
     /// Must be unique and only contain url-safe characters
     pub image_descriptions_permalink: Translation,
+    pub image_of_xxx: Translation,
+    pub in_stock: Translation,
+    pub individual_tracks: Translation,
     pub javascript_is_disabled_listen_at_xxx: Translation,
     pub javascript_is_disabled_text: Translation,
+    pub languages: Translation,
+    pub list: Translation,
     pub listen: Translation,
     pub loading: Translation,
+    pub lyrics: Translation,
     pub m3u_playlist: Translation,
     pub made_or_arranged_payment: Translation,
+    pub mastodon_instance_prompt: Translation,
     pub missing_image_description_note: Translation,
     pub more: Translation,
+    pub move_down: Translation,
+    pub move_up: Translation,
     pub mute: Translation,
     pub name_your_price: Translation,
+    pub next_page: Translation,
     pub next_track: Translation,
     pub nothing_found_for_xxx: Translation,
+    pub order: Translation,
+    pub page_xxx_of_xxx: Translation,
     pub pause: Translation,
+    pub physical_editions: Translation,
     pub play: Translation,
     pub playback_position: Translation,
     pub player_closed: Translation,
+    pub player_error_retrying: Translation,
+    pub player_error_unavailable: Translation,
     pub player_open_playing_xxx: Translation,
     pub player_open_with_xxx: Translation,
+    pub playlist: Translation,
+    pub previous_page: Translation,
     pub previous_track: Translation,
     pub price: Translation,
     pub purchase_downloads: Translation,
     /// Must be unique and only contain url-safe characters
     pub purchase_permalink: Translation,
+    pub queue: Translation,
     pub recommended_format: Translation,
+    pub release_scheduled_for_xxx: Translation,
+    pub released_xxx: Translation,
+    pub remove_from_queue: Translation,
+    pub reset_queue: Translation,
     pub search: Translation,
+    pub select_all: Translation,
+    pub share_on_xxx: Translation,
+    pub share_via_email: Translation,
     pub showing_featured_items: Translation,
     pub showing_xxx_results_for_xxx: Translation,
     pub skip_to_main_content: Translation,
+    pub sold_out: Translation,
     pub subscribe: Translation,
     pub subscribe_permalink: Translation,
+    pub support_the_artist: Translation,
+    pub timeline: Translation,
+    pub transcript: Translation,
+    pub undated: Translation,
     pub unlisted: Translation,
     pub unlock: Translation,
     pub unlock_downloads: Translation,
@@ -244,6 +301,8 @@ pub struct Translations {
     pub up_to_xxx: Translation,
     pub visual_impairment: Translation,
     pub volume: Translation,
+    pub volume_normalization: Translation,
+    pub xspf_playlist: Translation,
     pub xxx_and_others: Translation,
     pub xxx_hours: Translation,
     pub xxx_minutes: Translation,
@@ -253,6 +312,9 @@ pub struct Translations {
 
 impl Translations {
     pub const KEYS: Translations = Translations {
+        add_to_calendar: Reviewed("add_to_calendar"),
+        all_releases: Reviewed("all_releases"),
+        archive: Reviewed("archive"),
         audio_format_alac: Reviewed("audio_format_alac"),
         audio_format_average: Reviewed("audio_format_average"),
         audio_format_flac: Reviewed("audio_format_flac"),
@@ -260,24 +322,39 @@ impl Translations {
         audio_format_opus_48: Reviewed("audio_format_opus_48"),
         audio_format_opus_96: Reviewed("audio_format_opus_96"),
         audio_format_opus_128: Reviewed("audio_format_opus_128"),
+        audio_format_original: Reviewed("audio_format_original"),
         audio_format_uncompressed: Reviewed("audio_format_uncompressed"),
         audio_player_widget_for_xxx: Reviewed("audio_player_widget_for_xxx"),
         auto_generated_cover: Reviewed("auto_generated_cover"),
         available_formats: Reviewed("available_formats"),
         browse: Reviewed("browse"),
         buy: Reviewed("buy"),
+        chapters: Reviewed("chapters"),
         close: Reviewed("close"),
+        compare_formats: Reviewed("compare_formats"),
         copied: Reviewed("copied"),
         copy: Reviewed("copy"),
         copy_link: Reviewed("copy_link"),
+        copy_link_at_current_time: Reviewed("copy_link_at_current_time"),
         confirm: Reviewed("confirm"),
+        content_warning: Reviewed("content_warning"),
+        content_warning_age_xxx: Reviewed("content_warning_age_xxx"),
+        content_warning_confirm_age_xxx: Reviewed("content_warning_confirm_age_xxx"),
         r#continue: Reviewed("continue"),
         cover_image: Reviewed("cover_image"),
+        cover_of_xxx: Reviewed("cover_of_xxx"),
+        cover_of_xxx_by_xxx: Reviewed("cover_of_xxx_by_xxx"),
         default_unlock_info: Reviewed("default_unlock_info"),
+        disc_xxx: Reviewed("disc_xxx"),
+        discover: Reviewed("discover"),
+        discover_text: Reviewed("discover_text"),
         download: Reviewed("download"),
         download_code_seems_incorrect: Reviewed("download_code_seems_incorrect"),
+        download_selected: Reviewed("download_selected"),
         downloads: Reviewed("downloads"),
         downloads_permalink: Reviewed("downloads_permalink"),
+        draft_preview: Reviewed("draft_preview"),
+        dyslexia_friendly_font: Reviewed("dyslexia_friendly_font"),
         embed: Reviewed("embed"),
         embed_entire_release: Reviewed("embed_entire_release"),
         enter_code_here: Reviewed("enter_code_here"),
@@ -285,39 +362,75 @@ impl Translations {
         extras: Reviewed("extras"),
         failed: Reviewed("failed"),
         feed: Reviewed("feed"),
+        filter_releases: Reviewed("filter_releases"),
+        format_comparison_hint: Reviewed("format_comparison_hint"),
+        from_xxx: Reviewed("from_xxx"),
         generic_rss: Reviewed("generic_rss"),
+        hover_previews: Reviewed("hover_previews"),
+        image_description_suggestions: Reviewed("image_description_suggestions"),
+        image_description_suggestions_hint: Reviewed("image_description_suggestions_hint"),
         image_descriptions: Reviewed("image_descriptions"),
         image_descriptions_guide: Reviewed("image_descriptions_guide"),
         image_descriptions_permalink: Reviewed("image_descriptions_permalink"),
+        image_of_xxx: Reviewed("image_of_xxx"),
+        in_stock: Reviewed("in_stock"),
+        individual_tracks: Reviewed("individual_tracks"),
         javascript_is_disabled_listen_at_xxx: Reviewed("javascript_is_disabled_listen_at_xxx"),
         javascript_is_disabled_text: Reviewed("javascript_is_disabled_text"),
+        languages: Reviewed("languages"),
+        list: Reviewed("list"),
         listen: Reviewed("listen"),
         loading: Reviewed("loading"),
+        lyrics: Reviewed("lyrics"),
         m3u_playlist: Reviewed("m3u_playlist"),
         made_or_arranged_payment: Reviewed("made_or_arranged_payment"),
+        mastodon_instance_prompt: Reviewed("mastodon_instance_prompt"),
         missing_image_description_note: Reviewed("missing_image_description_note"),
         more: Reviewed("more"),
+        move_down: Reviewed("move_down"),
+        move_up: Reviewed("move_up"),
         mute: Reviewed("mute"),
         name_your_price: Reviewed("name_your_price"),
+        next_page: Reviewed("next_page"),
         next_track: Reviewed("next_track"),
         nothing_found_for_xxx: Reviewed("next_track"),
+        order: Reviewed("order"),
+        page_xxx_of_xxx: Reviewed("page_xxx_of_xxx"),
         pause: Reviewed("pause"),
+        physical_editions: Reviewed("physical_editions"),
         play: Reviewed("play"),
         player_closed: Reviewed("player_closed"),
+        player_error_retrying: Reviewed("player_error_retrying"),
+        player_error_unavailable: Reviewed("player_error_unavailable"),
         playback_position: Reviewed("playback_position"),
         player_open_playing_xxx: Reviewed("player_open_playing_xxx"),
         player_open_with_xxx: Reviewed("player_open_with_xxx"),
+        playlist: Reviewed("playlist"),
+        previous_page: Reviewed("previous_page"),
         previous_track: Reviewed("previous_track"),
         price: Reviewed("price"),
         purchase_downloads: Reviewed("purchase_downloads"),
         purchase_permalink: Reviewed("purchase_permalink"),
+        queue: Reviewed("queue"),
         recommended_format: Reviewed("recommended_format"),
+        release_scheduled_for_xxx: Reviewed("release_scheduled_for_xxx"),
+        released_xxx: Reviewed("released_xxx"),
+        remove_from_queue: Reviewed("remove_from_queue"),
+        reset_queue: Reviewed("reset_queue"),
         search: Reviewed("search"),
+        select_all: Reviewed("select_all"),
+        share_on_xxx: Reviewed("share_on_xxx"),
+        share_via_email: Reviewed("share_via_email"),
         showing_featured_items: Reviewed("showing_featured_items"),
         showing_xxx_results_for_xxx: Reviewed("showing_xxx_results_for_xxx"),
         skip_to_main_content: Reviewed("skip_to_main_content"),
+        sold_out: Reviewed("sold_out"),
         subscribe: Reviewed("subscribe"),
         subscribe_permalink: Reviewed("subscribe_permalink"),
+        support_the_artist: Reviewed("support_the_artist"),
+        timeline: Reviewed("timeline"),
+        transcript: Reviewed("transcript"),
+        undated: Reviewed("undated"),
         unlisted: Reviewed("unlisted"),
         unlock: Reviewed("unlock"),
         unlock_downloads: Reviewed("unlock_downloads"),
@@ -327,6 +440,8 @@ impl Translations {
         up_to_xxx: Reviewed("up_to_xxx"),
         visual_impairment: Reviewed("visual_impairment"),
         volume: Reviewed("volume"),
+        volume_normalization: Reviewed("volume_normalization"),
+        xspf_playlist: Reviewed("xspf_playlist"),
         xxx_and_others: Reviewed("xxx_and_others"),
         xxx_hours: Reviewed("xxx_hours"),
         xxx_minutes: Reviewed("xxx_minutes"),
@@ -335,11 +450,15 @@ impl Translations {
     };
 
     pub const UNTRANSLATED: Translations = Translations {
+        add_to_calendar: EN.add_to_calendar.as_untranslated(),
+        all_releases: EN.all_releases.as_untranslated(),
+        archive: EN.archive.as_untranslated(),
         audio_format_alac: EN.audio_format_alac.as_untranslated(),
         audio_format_average: EN.audio_format_average.as_untranslated(),
         audio_format_flac: EN.audio_format_flac.as_untranslated(),
         audio_format_mp3: EN.audio_format_mp3.as_untranslated(),
         audio_format_opus_128: EN.audio_format_opus_128.as_untranslated(),
+        audio_format_original: EN.audio_format_original.as_untranslated(),
         audio_format_opus_48: EN.audio_format_opus_48.as_untranslated(),
         audio_format_opus_96: EN.audio_format_opus_96.as_untranslated(),
         audio_format_uncompressed: EN.audio_format_uncompressed.as_untranslated(),
@@ -348,18 +467,32 @@ impl Translations {
         available_formats: EN.available_formats.as_untranslated(),
         browse: EN.browse.as_untranslated(),
         buy: EN.buy.as_untranslated(),
+        chapters: EN.chapters.as_untranslated(),
         close: EN.close.as_untranslated(),
+        compare_formats: EN.compare_formats.as_untranslated(),
         confirm: EN.confirm.as_untranslated(),
+        content_warning: EN.content_warning.as_untranslated(),
+        content_warning_age_xxx: EN.content_warning_age_xxx.as_untranslated(),
+        content_warning_confirm_age_xxx: EN.content_warning_confirm_age_xxx.as_untranslated(),
         r#continue: EN.r#continue.as_untranslated(),
         copied: EN.copied.as_untranslated(),
         copy: EN.copy.as_untranslated(),
         copy_link: EN.copy_link.as_untranslated(),
+        copy_link_at_current_time: EN.copy_link_at_current_time.as_untranslated(),
         cover_image: EN.cover_image.as_untranslated(),
+        cover_of_xxx: EN.cover_of_xxx.as_untranslated(),
+        cover_of_xxx_by_xxx: EN.cover_of_xxx_by_xxx.as_untranslated(),
         default_unlock_info: EN.default_unlock_info.as_untranslated(),
+        disc_xxx: EN.disc_xxx.as_untranslated(),
+        discover: EN.discover.as_untranslated(),
+        discover_text: EN.discover_text.as_untranslated(),
         download: EN.download.as_untranslated(),
         download_code_seems_incorrect: EN.download_code_seems_incorrect.as_untranslated(),
+        download_selected: EN.download_selected.as_untranslated(),
         downloads: EN.downloads.as_untranslated(),
         downloads_permalink: EN.downloads_permalink.as_untranslated(),
+        draft_preview: EN.draft_preview.as_untranslated(),
+        dyslexia_friendly_font: EN.dyslexia_friendly_font.as_untranslated(),
         embed: EN.embed.as_untranslated(),
         embed_entire_release: EN.embed_entire_release.as_untranslated(),
         enter_code_here: EN.enter_code_here.as_untranslated(),
@@ -367,39 +500,75 @@ impl Translations {
         extras: EN.extras.as_untranslated(),
         failed: EN.failed.as_untranslated(),
         feed: EN.feed.as_untranslated(),
+        filter_releases: EN.filter_releases.as_untranslated(),
+        format_comparison_hint: EN.format_comparison_hint.as_untranslated(),
+        from_xxx: EN.from_xxx.as_untranslated(),
         generic_rss: EN.generic_rss.as_untranslated(),
+        hover_previews: EN.hover_previews.as_untranslated(),
+        image_description_suggestions: EN.image_description_suggestions.as_untranslated(),
+        image_description_suggestions_hint: EN.image_description_suggestions_hint.as_untranslated(),
         image_descriptions: EN.image_descriptions.as_untranslated(),
         image_descriptions_guide: EN.image_descriptions_guide.as_untranslated(),
         image_descriptions_permalink: EN.image_descriptions_permalink.as_untranslated(),
+        image_of_xxx: EN.image_of_xxx.as_untranslated(),
+        in_stock: EN.in_stock.as_untranslated(),
+        individual_tracks: EN.individual_tracks.as_untranslated(),
         javascript_is_disabled_listen_at_xxx: EN.javascript_is_disabled_listen_at_xxx.as_untranslated(),
         javascript_is_disabled_text: EN.javascript_is_disabled_text.as_untranslated(),
+        languages: EN.languages.as_untranslated(),
+        list: EN.list.as_untranslated(),
         listen: EN.listen.as_untranslated(),
         loading: EN.loading.as_untranslated(),
+        lyrics: EN.lyrics.as_untranslated(),
         m3u_playlist: EN.m3u_playlist.as_untranslated(),
         made_or_arranged_payment: EN.made_or_arranged_payment.as_untranslated(),
+        mastodon_instance_prompt: EN.mastodon_instance_prompt.as_untranslated(),
         missing_image_description_note: EN.missing_image_description_note.as_untranslated(),
         more: EN.more.as_untranslated(),
+        move_down: EN.move_down.as_untranslated(),
+        move_up: EN.move_up.as_untranslated(),
         mute: EN.mute.as_untranslated(),
         name_your_price: EN.name_your_price.as_untranslated(),
+        next_page: EN.next_page.as_untranslated(),
         next_track: EN.next_track.as_untranslated(),
         nothing_found_for_xxx: EN.nothing_found_for_xxx.as_untranslated(),
+        order: EN.order.as_untranslated(),
+        page_xxx_of_xxx: EN.page_xxx_of_xxx.as_untranslated(),
         pause: EN.pause.as_untranslated(),
+        physical_editions: EN.physical_editions.as_untranslated(),
         play: EN.play.as_untranslated(),
         playback_position: EN.playback_position.as_untranslated(),
         player_closed: EN.player_closed.as_untranslated(),
+        player_error_retrying: EN.player_error_retrying.as_untranslated(),
+        player_error_unavailable: EN.player_error_unavailable.as_untranslated(),
         player_open_playing_xxx: EN.player_open_playing_xxx.as_untranslated(),
         player_open_with_xxx: EN.player_open_with_xxx.as_untranslated(),
+        playlist: EN.playlist.as_untranslated(),
+        previous_page: EN.previous_page.as_untranslated(),
         previous_track: EN.previous_track.as_untranslated(),
         price: EN.price.as_untranslated(),
         purchase_downloads: EN.purchase_downloads.as_untranslated(),
         purchase_permalink: EN.purchase_permalink.as_untranslated(),
+        queue: EN.queue.as_untranslated(),
         recommended_format: EN.recommended_format.as_untranslated(),
+        release_scheduled_for_xxx: EN.release_scheduled_for_xxx.as_untranslated(),
+        released_xxx: EN.released_xxx.as_untranslated(),
+        remove_from_queue: EN.remove_from_queue.as_untranslated(),
+        reset_queue: EN.reset_queue.as_untranslated(),
         search: EN.search.as_untranslated(),
+        select_all: EN.select_all.as_untranslated(),
+        share_on_xxx: EN.share_on_xxx.as_untranslated(),
+        share_via_email: EN.share_via_email.as_untranslated(),
         showing_featured_items: EN.showing_featured_items.as_untranslated(),
         showing_xxx_results_for_xxx: EN.showing_xxx_results_for_xxx.as_untranslated(),
         skip_to_main_content: EN.skip_to_main_content.as_untranslated(),
+        sold_out: EN.sold_out.as_untranslated(),
         subscribe: EN.subscribe.as_untranslated(),
         subscribe_permalink: EN.subscribe_permalink.as_untranslated(),
+        support_the_artist: EN.support_the_artist.as_untranslated(),
+        timeline: EN.timeline.as_untranslated(),
+        transcript: EN.transcript.as_untranslated(),
+        undated: EN.undated.as_untranslated(),
         unlisted: EN.unlisted.as_untranslated(),
         unlock: EN.unlock.as_untranslated(),
         unlock_downloads: EN.unlock_downloads.as_untranslated(),
@@ -409,6 +578,8 @@ impl Translations {
         up_to_xxx: EN.up_to_xxx.as_untranslated(),
         visual_impairment: EN.visual_impairment.as_untranslated(),
         volume: EN.volume.as_untranslated(),
+        volume_normalization: EN.volume_normalization.as_untranslated(),
+        xspf_playlist: EN.xspf_playlist.as_untranslated(),
         xxx_and_others: EN.xxx_and_others.as_untranslated(),
         xxx_hours: EN.xxx_hours.as_untranslated(),
         xxx_minutes: EN.xxx_minutes.as_untranslated(),
@@ -419,6 +590,9 @@ impl Translations {
     /// (key, value, is_multiline)
     pub fn all_strings(&self) -> Vec<(&'static str, &Translation, bool)> {
         vec![
+            ("add_to_calendar", &self.add_to_calendar, false),
+            ("all_releases", &self.all_releases, false),
+            ("archive", &self.archive, false),
             ("audio_format_alac", &self.audio_format_alac, false),
             ("audio_format_average", &self.audio_format_average, false),
             ("audio_format_flac", &self.audio_format_flac, false),
@@ -426,24 +600,39 @@ impl Translations {
             ("audio_format_opus_48", &self.audio_format_opus_48, false),
             ("audio_format_opus_96", &self.audio_format_opus_96, false),
             ("audio_format_opus_128", &self.audio_format_opus_128, false),
+            ("audio_format_original", &self.audio_format_original, false),
             ("audio_format_uncompressed", &self.audio_format_uncompressed, false),
             ("audio_player_widget_for_xxx", &self.audio_player_widget_for_xxx, false),
             ("auto_generated_cover", &self.auto_generated_cover, false),
             ("available_formats", &self.available_formats, false),
             ("browse", &self.browse, false),
             ("buy", &self.buy, false),
+            ("chapters", &self.chapters, false),
             ("close", &self.close, false),
+            ("compare_formats", &self.compare_formats, false),
             ("copied", &self.copied, false),
             ("copy", &self.copy, false),
             ("copy_link", &self.copy_link, false),
+            ("copy_link_at_current_time", &self.copy_link_at_current_time, false),
             ("confirm", &self.confirm, false),
+            ("content_warning", &self.content_warning, false),
+            ("content_warning_age_xxx", &self.content_warning_age_xxx, false),
+            ("content_warning_confirm_age_xxx", &self.content_warning_confirm_age_xxx, false),
             ("continue", &self.r#continue, false),
             ("cover_image", &self.cover_image, false),
+            ("cover_of_xxx", &self.cover_of_xxx, false),
+            ("cover_of_xxx_by_xxx", &self.cover_of_xxx_by_xxx, false),
             ("default_unlock_info", &self.default_unlock_info, false),
+            ("disc_xxx", &self.disc_xxx, false),
+            ("discover", &self.discover, false),
+            ("discover_text", &self.discover_text, false),
             ("download", &self.download, false),
             ("download_code_seems_incorrect", &self.download_code_seems_incorrect, false),
+            ("download_selected", &self.download_selected, false),
             ("downloads", &self.downloads, false),
             ("downloads_permalink", &self.downloads_permalink, false),
+            ("draft_preview", &self.draft_preview, false),
+            ("dyslexia_friendly_font", &self.dyslexia_friendly_font, false),
             ("embed", &self.embed, false),
             ("embed_entire_release", &self.embed_entire_release, false),
             ("enter_code_here", &self.enter_code_here, false),
@@ -451,39 +640,75 @@ impl Translations {
             ("extras", &self.extras, false),
             ("failed", &self.failed, false),
             ("feed", &self.feed, false),
+            ("filter_releases", &self.filter_releases, false),
+            ("format_comparison_hint", &self.format_comparison_hint, false),
+            ("from_xxx", &self.from_xxx, false),
             ("generic_rss", &self.generic_rss, false),
+            ("hover_previews", &self.hover_previews, false),
+            ("image_description_suggestions", &self.image_description_suggestions, false),
+            ("image_description_suggestions_hint", &self.image_description_suggestions_hint, false),
             ("image_descriptions", &self.image_descriptions, false),
             ("image_descriptions_guide", &self.image_descriptions_guide, true),
             ("image_descriptions_permalink", &self.image_descriptions_permalink, false),
+            ("image_of_xxx", &self.image_of_xxx, false),
+            ("in_stock", &self.in_stock, false),
+            ("individual_tracks", &self.individual_tracks, false),
             ("javascript_is_disabled_listen_at_xxx", &self.javascript_is_disabled_listen_at_xxx, false),
             ("javascript_is_disabled_text", &self.javascript_is_disabled_text, false),
+            ("languages", &self.languages, false),
+            ("list", &self.list, false),
             ("listen", &self.listen, false),
             ("loading", &self.loading, false),
+            ("lyrics", &self.lyrics, false),
             ("m3u_playlist", &self.m3u_playlist, false),
             ("made_or_arranged_payment", &self.made_or_arranged_payment, false),
+            ("mastodon_instance_prompt", &self.mastodon_instance_prompt, false),
             ("missing_image_description_note", &self.missing_image_description_note, false),
             ("more", &self.more, false),
+            ("move_down", &self.move_down, false),
+            ("move_up", &self.move_up, false),
             ("mute", &self.mute, false),
             ("name_your_price", &self.name_your_price, false),
+            ("next_page", &self.next_page, false),
             ("next_track", &self.next_track, false),
             ("nothing_found_for_xxx", &self.nothing_found_for_xxx, false),
+            ("order", &self.order, false),
+            ("page_xxx_of_xxx", &self.page_xxx_of_xxx, false),
             ("pause", &self.pause, false),
+            ("physical_editions", &self.physical_editions, false),
             ("play", &self.play, false),
             ("playback_position", &self.playback_position, false),
             ("player_closed", &self.player_closed, false),
+            ("player_error_retrying", &self.player_error_retrying, false),
+            ("player_error_unavailable", &self.player_error_unavailable, false),
             ("player_open_playing_xxx", &self.player_open_playing_xxx, false),
             ("player_open_with_xxx", &self.player_open_with_xxx, false),
+            ("playlist", &self.playlist, false),
+            ("previous_page", &self.previous_page, false),
             ("previous_track", &self.previous_track, false),
             ("price", &self.price, false),
             ("purchase_downloads", &self.purchase_downloads, false),
             ("purchase_permalink", &self.purchase_permalink, false),
+            ("queue", &self.queue, false),
             ("recommended_format", &self.recommended_format, false),
+            ("release_scheduled_for_xxx", &self.release_scheduled_for_xxx, false),
+            ("released_xxx", &self.released_xxx, false),
+            ("remove_from_queue", &self.remove_from_queue, false),
+            ("reset_queue", &self.reset_queue, false),
             ("search", &self.search, false),
+            ("select_all", &self.select_all, false),
+            ("share_on_xxx", &self.share_on_xxx, false),
+            ("share_via_email", &self.share_via_email, false),
             ("showing_featured_items", &self.showing_featured_items, false),
             ("showing_xxx_results_for_xxx", &self.showing_xxx_results_for_xxx, false),
             ("skip_to_main_content", &self.skip_to_main_content, false),
+            ("sold_out", &self.sold_out, false),
             ("subscribe", &self.subscribe, false),
             ("subscribe_permalink", &self.subscribe_permalink, false),
+            ("support_the_artist", &self.support_the_artist, false),
+            ("timeline", &self.timeline, false),
+            ("transcript", &self.transcript, false),
+            ("undated", &self.undated, false),
             ("unlisted", &self.unlisted, false),
             ("unlock", &self.unlock, false),
             ("unlock_downloads", &self.unlock_downloads, false),
@@ -493,6 +718,8 @@ impl Translations {
             ("up_to_xxx", &self.up_to_xxx, false),
             ("visual_impairment", &self.visual_impairment, false),
             ("volume", &self.volume, false),
+            ("volume_normalization", &self.volume_normalization, false),
+            ("xspf_playlist", &self.xspf_playlist, false),
             ("xxx_and_others", &self.xxx_and_others, false),
             ("xxx_hours", &self.xxx_hours, false),
             ("xxx_minutes", &self.xxx_minutes, false),
@@ -505,6 +732,14 @@ impl Translations {
         self.audio_player_widget_for_xxx.replace("{title}", title)
     }
 
+    pub fn content_warning_age_xxx(&self, age: &str) -> String {
+        self.content_warning_age_xxx.replace("{age}", age)
+    }
+
+    pub fn content_warning_confirm_age_xxx(&self, age: &str) -> String {
+        self.content_warning_confirm_age_xxx.replace("{age}", age)
+    }
+
     pub fn count_untranslated(&self) -> usize {
         self.all_strings()
             .iter()
@@ -519,6 +754,28 @@ impl Translations {
             .count()
     }
 
+    pub fn cover_of_xxx(&self, title: &str) -> String {
+        self.cover_of_xxx.replace("{title}", title)
+    }
+
+    pub fn cover_of_xxx_by_xxx(&self, title: &str, artists: &str) -> String {
+        self.cover_of_xxx_by_xxx
+            .replace("{artists}", artists)
+            .replace("{title}", title)
+    }
+
+    pub fn disc_xxx(&self, number: &str) -> String {
+        self.disc_xxx.replace("{number}", number)
+    }
+
+    pub fn from_xxx(&self, title: &str) -> String {
+        self.from_xxx.replace("{title}", title)
+    }
+
+    pub fn image_of_xxx(&self, name: &str) -> String {
+        self.image_of_xxx.replace("{name}", name)
+    }
+
     pub fn javascript_is_disabled_listen_at_xxx(&self, link: &str) -> String {
         self.javascript_is_disabled_listen_at_xxx.replace("{link}", link)
     }
@@ -561,6 +818,24 @@ impl Translations {
         (translated as f32 / total as f32) * 100.0
     }
 
+    pub fn page_xxx_of_xxx(&self, number: &str, total: &str) -> String {
+        self.page_xxx_of_xxx
+            .replace("{number}", number)
+            .replace("{total}", total)
+    }
+
+    pub fn release_scheduled_for_xxx(&self, date: &str) -> String {
+        self.release_scheduled_for_xxx.replace("{date}", date)
+    }
+
+    pub fn released_xxx(&self, year: &str) -> String {
+        self.released_xxx.replace("{year}", year)
+    }
+
+    pub fn share_on_xxx(&self, platform: &str) -> String {
+        self.share_on_xxx.replace("{platform}", platform)
+    }
+
     pub fn unlock_manual_instructions(&self, page_hash: &str, index_suffix: &str) -> String {
         self.unlock_manual_instructions
             .replace("{downloads_permalink}", &self.downloads_permalink)
@@ -579,6 +854,10 @@ impl Translations {
             .replace("{others_link}", others_link)
     }
 
+    pub fn xxx_hours(&self, xxx: &str) -> String {
+        self.xxx_hours.replace("{xxx}", xxx)
+    }
+
     pub fn xxx_minutes(&self, xxx: &str) -> String {
         self.xxx_minutes.replace("{xxx}", xxx)
     }
@@ -617,10 +896,23 @@ fn check_translations() {
 
     for translations in LOCALES {
         assert!(&translations.audio_player_widget_for_xxx.contains("{title}"));
+        assert!(&translations.content_warning_age_xxx.contains("{age}"));
+        assert!(&translations.content_warning_confirm_age_xxx.contains("{age}"));
+        assert!(&translations.cover_of_xxx.contains("{title}"));
+        assert!(&translations.cover_of_xxx_by_xxx.contains("{artists}"));
+        assert!(&translations.cover_of_xxx_by_xxx.contains("{title}"));
+        assert!(&translations.disc_xxx.contains("{number}"));
+        assert!(&translations.from_xxx.contains("{title}"));
+        assert!(&translations.image_of_xxx.contains("{name}"));
         assert!(&translations.javascript_is_disabled_listen_at_xxx.contains("{link}"));
         assert!(&translations.nothing_found_for_xxx.contains("{query}"));
+        assert!(&translations.page_xxx_of_xxx.contains("{number}"));
+        assert!(&translations.page_xxx_of_xxx.contains("{total}"));
         assert!(&translations.player_open_playing_xxx.contains("{title}"));
         assert!(&translations.player_open_with_xxx.contains("{title}"));
+        assert!(&translations.release_scheduled_for_xxx.contains("{date}"));
+        assert!(&translations.released_xxx.contains("{year}"));
+        assert!(&translations.share_on_xxx.contains("{platform}"));
         assert!(&translations.showing_xxx_results_for_xxx.contains("{count}"));
         assert!(&translations.showing_xxx_results_for_xxx.contains("{query}"));
         assert!(&translations.unlock_manual_instructions.contains("{downloads_permalink}"));